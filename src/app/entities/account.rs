@@ -1,19 +1,75 @@
-use crate::app::entities::common::EntityId;
+use crate::app::entities::common::entity_id;
+use crate::app::entities::institution::InstitutionId;
+use crate::app::entities::tag::TagId;
+use crate::app::typing::account_identifier::AccountIdentifier;
 use crate::app::typing::amount::{Amount, MIN_AMOUNT};
 use crate::app::typing::currency::{Currency, DEFAULT_CURRENCY};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 use utoipa::ToSchema;
 
+entity_id!(
+    /// Unique identifier of an `Account`.
+    AccountId,
+    "account"
+);
+
+/// The description of the transaction recording the money an account held when it was opened.
+pub(crate) const OPENING_BALANCE_DESCRIPTION: &str = "Opening balance";
+
 #[derive(Debug)]
 pub enum ConversionError {
     InvalidCurrency,
+    InvalidStatus,
 }
 
 #[derive(Debug, PartialEq, Clone, Eq, Serialize, Deserialize, ToSchema)]
 pub enum AccountType {
     Checking,
     Savings,
+
+    /// A credit card or a credit line, its balance is the available credit.
     Credit,
+
+    /// A mobile money wallet, held at a mobile money operator. (Eg: "MTN MoMo")
+    MobileMoney,
+
+    /// Money held in hand, at no institution.
+    Cash,
+
+    /// A brokerage or investment account.
+    Investment,
+
+    /// A loan, its balance is the available credit like a credit account.
+    Loan,
+
+    /// A prepaid or digital wallet. (Eg: a transport card)
+    Wallet,
+}
+
+impl AccountType {
+    /// Whether the account is money owed rather than money held: its balance is the available credit,
+    /// the expenses are borrowed and the incomes are repayments.
+    pub fn is_debt(&self) -> bool {
+        matches!(self, AccountType::Credit | AccountType::Loan)
+    }
+
+    /// Whether the account earns, or is charged, interest.
+    /// Cash and prepaid wallets never do.
+    pub fn accrues_interest(&self) -> bool {
+        !matches!(self, AccountType::Cash | AccountType::Wallet)
+    }
+
+    /// Whether the transactions of the account may be charged fees.
+    pub fn charges_fees(&self) -> bool {
+        !matches!(self, AccountType::Cash)
+    }
+
+    /// Whether the account is held at an institution, cash is held in hand.
+    pub fn is_held_at_institution(&self) -> bool {
+        !matches!(self, AccountType::Cash)
+    }
 }
 
 impl TryFrom<&str> for AccountType {
@@ -32,21 +88,26 @@ impl TryFrom<String> for AccountType {
     }
 }
 
-impl Into<String> for &AccountType {
-    fn into(self) -> String {
-        let value = match self {
+impl From<&AccountType> for String {
+    fn from(value: &AccountType) -> Self {
+        let value = match value {
             AccountType::Savings => "savings",
             AccountType::Credit => "credit",
             AccountType::Checking => "checking",
+            AccountType::MobileMoney => "mobile_money",
+            AccountType::Cash => "cash",
+            AccountType::Investment => "investment",
+            AccountType::Loan => "loan",
+            AccountType::Wallet => "wallet",
         };
 
         value.to_string()
     }
 }
 
-impl AccountType {
-    pub fn to_string(&self) -> String {
-        self.into()
+impl Display for AccountType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(self))
     }
 }
 
@@ -55,13 +116,74 @@ fn convert_string_to_account_type(value: &str) -> Result<AccountType, Conversion
         "checking" => Ok(AccountType::Checking),
         "savings" => Ok(AccountType::Savings),
         "credit" => Ok(AccountType::Credit),
+        "mobile_money" => Ok(AccountType::MobileMoney),
+        "cash" => Ok(AccountType::Cash),
+        "investment" => Ok(AccountType::Investment),
+        "loan" => Ok(AccountType::Loan),
+        "wallet" => Ok(AccountType::Wallet),
         _ => Err(ConversionError::InvalidCurrency),
     }
 }
 
+/// Whether an account can be used.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Serialize, Deserialize, ToSchema)]
+pub enum AccountStatus {
+    Active,
+
+    /// Money can be received, but not taken out of the account. (Eg: a lost card)
+    Frozen,
+
+    /// The account is no longer used, it takes no transactions. Its balance is zero.
+    Closed,
+}
+
+impl From<&AccountStatus> for String {
+    fn from(value: &AccountStatus) -> Self {
+        let value = match value {
+            AccountStatus::Active => "active",
+            AccountStatus::Frozen => "frozen",
+            AccountStatus::Closed => "closed",
+        };
+
+        value.to_string()
+    }
+}
+
+impl Display for AccountStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(self))
+    }
+}
+
+impl TryFrom<&str> for AccountStatus {
+    type Error = ConversionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "active" => Ok(AccountStatus::Active),
+            "frozen" => Ok(AccountStatus::Frozen),
+            "closed" => Ok(AccountStatus::Closed),
+            _ => Err(ConversionError::InvalidStatus),
+        }
+    }
+}
+
+/// A change of the status of an account, kept in its history.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StatusChange {
+    /// The status the account was given.
+    pub status: AccountStatus,
+
+    /// Why the status was changed. (Eg: "Card lost")
+    pub reason: String,
+
+    /// When the status was changed.
+    pub changed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
 pub struct Account {
-    id: Option<EntityId>,
+    id: Option<AccountId>,
 
     /**
      * The account name is unique, assigned by the account owner. (usually a label)
@@ -74,10 +196,10 @@ pub struct Account {
     description: String,
 
     /**
-     * The account platform, where it belongs.
-     * Eg: "Equity Bank", "Bank of Kigali", "MTN Mobile Money", "In-Hand"
+     * The institution the account is held at, none for the cash in hand.
+     * Eg: "Equity Bank", "Bank of Kigali", "MTN Mobile Money"
      */
-    platform: String,
+    institution_id: Option<InstitutionId>,
 
     /**
      * The account balance.
@@ -93,14 +215,35 @@ pub struct Account {
      * The account currency.
      */
     currency: Currency,
+
+    /**
+     * The tags of the account.
+     */
+    tags: Vec<TagId>,
+
+    /**
+     * The numbers identifying the account outside of the application, unique across the accounts.
+     * Eg: its IBAN, or the phone number of a mobile money wallet.
+     */
+    identifiers: Vec<AccountIdentifier>,
+
+    /**
+     * Whether the account can be used, a new account is active.
+     */
+    status: AccountStatus,
+
+    /**
+     * The changes of the status of the account, the oldest first.
+     */
+    status_changes: Vec<StatusChange>,
 }
 
 impl Account {
     pub fn new(
-        id: Option<EntityId>,
+        id: Option<AccountId>,
         name: String,
         description: String,
-        platform: String,
+        institution_id: Option<InstitutionId>,
         account_type: AccountType,
         currency: Option<Currency>,
     ) -> Self {
@@ -109,14 +252,18 @@ impl Account {
             id,
             name,
             description,
-            platform,
+            institution_id,
             account_type,
             currency: currency.unwrap_or(DEFAULT_CURRENCY),
             balance: MIN_AMOUNT,
+            tags: vec![],
+            identifiers: vec![],
+            status: AccountStatus::Active,
+            status_changes: vec![],
         }
     }
 
-    pub fn id(&self) -> Option<&EntityId> {
+    pub fn id(&self) -> Option<&AccountId> {
         self.id.as_ref()
     }
 
@@ -132,8 +279,8 @@ impl Account {
         &self.balance
     }
 
-    pub fn platform(&self) -> &str {
-        &self.platform
+    pub fn institution_id(&self) -> Option<&InstitutionId> {
+        self.institution_id.as_ref()
     }
 
     pub fn account_type(&self) -> &AccountType {
@@ -144,32 +291,50 @@ impl Account {
         &self.currency
     }
 
-    pub fn set_id(&mut self, id: Option<EntityId>) {
-        self.id = id;
+    pub fn tags(&self) -> &Vec<TagId> {
+        &self.tags
     }
 
-    pub fn set_name(&mut self, name: String) {
-        self.name = name
+    pub fn identifiers(&self) -> &Vec<AccountIdentifier> {
+        &self.identifiers
     }
 
-    pub fn set_description(&mut self, description: String) {
-        self.description = description;
+    pub fn status(&self) -> AccountStatus {
+        self.status
+    }
+
+    pub fn status_changes(&self) -> &Vec<StatusChange> {
+        &self.status_changes
+    }
+
+    pub fn set_id(&mut self, id: Option<AccountId>) {
+        self.id = id;
     }
 
     pub fn set_balance(&mut self, balance: Amount) {
         self.balance = balance;
     }
 
-    pub fn set_platform(&mut self, platform: String) {
-        self.platform = platform
+    pub fn set_tags(&mut self, tags: Vec<TagId>) {
+        self.tags = tags;
     }
 
-    pub fn set_account_type(&mut self, account_type: AccountType) {
-        self.account_type = account_type;
+    pub fn set_identifiers(&mut self, identifiers: Vec<AccountIdentifier>) {
+        self.identifiers = identifiers;
     }
 
-    pub fn set_currency(&mut self, currency: Currency) {
-        self.currency = currency
+    pub fn set_status(&mut self, status: AccountStatus) {
+        self.status = status;
+    }
+
+    pub fn set_status_changes(&mut self, status_changes: Vec<StatusChange>) {
+        self.status_changes = status_changes;
+    }
+
+    /// Gives the account a new status, and records the change in its history.
+    pub fn change_status(&mut self, change: StatusChange) {
+        self.status = change.status;
+        self.status_changes.push(change);
     }
 
     pub fn deposit(&mut self, amount: &Amount) {
@@ -180,3 +345,26 @@ impl Account {
         self.balance = self.balance() - amount
     }
 }
+
+#[cfg(test)]
+impl Account {
+    pub fn set_name(&mut self, name: String) {
+        self.name = name
+    }
+
+    pub fn set_description(&mut self, description: String) {
+        self.description = description;
+    }
+
+    pub fn set_institution_id(&mut self, institution_id: Option<InstitutionId>) {
+        self.institution_id = institution_id
+    }
+
+    pub fn set_account_type(&mut self, account_type: AccountType) {
+        self.account_type = account_type;
+    }
+
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.currency = currency
+    }
+}
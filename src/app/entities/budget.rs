@@ -0,0 +1,193 @@
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::common::entity_id;
+use crate::app::entities::tag::TagId;
+use crate::app::typing::amount::Amount;
+use crate::app::typing::currency::Currency;
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `Budget`.
+    BudgetId,
+    "budget"
+);
+
+/// The expenses a budget applies to.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    /// The expenses of a category and of all its sub categories.
+    Category(CategoryId),
+
+    /// The expenses carrying a tag.
+    Tag(TagId),
+}
+
+/// The length of the periods a budget is renewed for, aligned on the calendar (in UTC).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl BudgetPeriod {
+    fn months(&self) -> u32 {
+        match self {
+            BudgetPeriod::Monthly => 1,
+            BudgetPeriod::Quarterly => 3,
+            BudgetPeriod::Yearly => 12,
+        }
+    }
+
+    /// The first day of the period containing the date.
+    pub fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        let month = match self {
+            BudgetPeriod::Monthly => date.month(),
+            BudgetPeriod::Quarterly => (date.month() - 1) / 3 * 3 + 1,
+            BudgetPeriod::Yearly => 1,
+        };
+
+        NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap()
+    }
+
+    /// The first day of the period following the one starting on `start`.
+    pub fn next(&self, start: NaiveDate) -> NaiveDate {
+        start + Months::new(self.months())
+    }
+}
+
+/// A spending limit for a category or a tag, renewed every period.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Budget {
+    id: Option<BudgetId>,
+
+    /// A name describing the budget. (Eg: "Groceries")
+    name: String,
+
+    scope: BudgetScope,
+
+    period: BudgetPeriod,
+
+    /// The amount which can be spent each period.
+    amount: Amount,
+
+    /// Only the expenses in this currency count against the budget.
+    currency: Currency,
+
+    /// Whether the amount left unspent at the end of a period is added to the next one.
+    rollover: bool,
+
+    /// A day of the first period of the budget, the periods before it are not budgeted.
+    start: NaiveDate,
+}
+
+/// Midnight (UTC) at the beginning of the day.
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap().and_utc()
+}
+
+impl Budget {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Option<BudgetId>,
+        name: String,
+        scope: BudgetScope,
+        period: BudgetPeriod,
+        amount: Amount,
+        currency: Currency,
+        rollover: bool,
+        start: NaiveDate,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            scope,
+            period,
+            amount,
+            currency,
+            rollover,
+            start,
+        }
+    }
+
+    pub fn id(&self) -> Option<&BudgetId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn scope(&self) -> &BudgetScope {
+        &self.scope
+    }
+
+    pub fn period(&self) -> BudgetPeriod {
+        self.period
+    }
+
+    pub fn amount(&self) -> &Amount {
+        &self.amount
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    pub fn rollover(&self) -> bool {
+        self.rollover
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// The bounds of the periods of the budget, from its first period up to the one containing `date`
+    /// (included), in order. Each period starts at its first bound and ends strictly before the second.
+    /// Empty when the date is before the first period.
+    pub fn periods_until(&self, date: DateTime<Utc>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut periods = vec![];
+        let mut start = self.period.start_of(self.start);
+        while start_of_day(start) <= date {
+            let end = self.period.next(start);
+            periods.push((start_of_day(start), start_of_day(end)));
+            start = end;
+        }
+
+        periods
+    }
+
+    pub fn set_id(&mut self, id: Option<BudgetId>) {
+        self.id = id;
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_scope(&mut self, scope: BudgetScope) {
+        self.scope = scope;
+    }
+}
+
+#[cfg(test)]
+impl Budget {
+    pub fn set_period(&mut self, period: BudgetPeriod) {
+        self.period = period;
+    }
+
+    pub fn set_amount(&mut self, amount: Amount) {
+        self.amount = amount;
+    }
+
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.currency = currency;
+    }
+
+    pub fn set_rollover(&mut self, rollover: bool) {
+        self.rollover = rollover;
+    }
+}
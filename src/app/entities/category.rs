@@ -0,0 +1,105 @@
+use crate::app::entities::common::entity_id;
+use crate::app::entities::transaction::TransactionType;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `Category`.
+    CategoryId,
+    "category"
+);
+
+/// A category of the taxonomy used to classify the transactions.
+/// The categories form a tree: a category without parent is a top level one (Eg: "Food"),
+/// the others refine their parent (Eg: "Food" > "Groceries").
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Category {
+    id: Option<CategoryId>,
+
+    /// The category name, unique among the categories sharing the same parent.
+    name: String,
+
+    /// The parent category, None for a top level category.
+    parent_id: Option<CategoryId>,
+
+    /// The type of the transactions the category applies to (income or expense).
+    /// A sub category always has the kind of its parent.
+    kind: TransactionType,
+
+    /// Colour used to display the category, as a hex code. (Eg: "#4caf50")
+    color: Option<String>,
+
+    /// Name of the icon used to display the category. (Eg: "shopping-cart")
+    icon: Option<String>,
+}
+
+impl Category {
+    pub fn new(
+        id: Option<CategoryId>,
+        name: String,
+        parent_id: Option<CategoryId>,
+        kind: TransactionType,
+        color: Option<String>,
+        icon: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            parent_id,
+            kind,
+            color,
+            icon,
+        }
+    }
+
+    pub fn id(&self) -> Option<&CategoryId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn parent_id(&self) -> Option<&CategoryId> {
+        self.parent_id.as_ref()
+    }
+
+    pub fn kind(&self) -> &TransactionType {
+        &self.kind
+    }
+
+    pub fn color(&self) -> &Option<String> {
+        &self.color
+    }
+
+    pub fn icon(&self) -> &Option<String> {
+        &self.icon
+    }
+
+    pub fn set_id(&mut self, id: Option<CategoryId>) {
+        self.id = id;
+    }
+}
+
+#[cfg(test)]
+impl Category {
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_parent_id(&mut self, parent_id: Option<CategoryId>) {
+        self.parent_id = parent_id;
+    }
+
+    pub fn set_kind(&mut self, kind: TransactionType) {
+        self.kind = kind;
+    }
+
+    pub fn set_color(&mut self, color: Option<String>) {
+        self.color = color;
+    }
+
+    pub fn set_icon(&mut self, icon: Option<String>) {
+        self.icon = icon;
+    }
+}
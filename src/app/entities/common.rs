@@ -1,48 +1,92 @@
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use std::fmt::Debug;
+use thiserror::Error;
 use utoipa::ToSchema;
 
-#[derive(Debug, Default, PartialEq, Clone, Eq, Serialize, ToSchema)]
-pub struct EntityId(pub(crate) String);
-
-impl From<String> for EntityId {
-    fn from(value: String) -> Self {
-        Self(value)
-    }
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum EntityIdParseError {
+    #[error("invalid {entity} id: {value:?}")]
+    Invalid { entity: &'static str, value: String },
 }
 
-impl From<&str> for EntityId {
-    fn from(value: &str) -> Self {
-        Self(value.to_string())
-    }
-}
+/// Declares a typed identifier for an entity.
+///
+/// Every identifier wraps a UUID v7, which is generated by the repositories and sorts by
+/// creation time, so ids of different entities can never be mixed up nor collide.
+macro_rules! entity_id {
+    ($(#[$meta:meta])* $name:ident, $entity:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            Hash,
+            PartialOrd,
+            Ord,
+            serde::Serialize,
+            serde::Deserialize,
+            utoipa::ToSchema,
+        )]
+        #[schema(value_type = String, format = Uuid)]
+        pub struct $name(uuid::Uuid);
 
-impl From<i32> for EntityId {
-    fn from(value: i32) -> Self {
-        Self(value.to_string())
-    }
-}
+        impl $name {
+            /// Generates a new, time ordered, identifier.
+            pub fn generate() -> Self {
+                Self(uuid::Uuid::now_v7())
+            }
+        }
 
-impl From<i64> for EntityId {
-    fn from(value: i64) -> Self {
-        Self(value.to_string())
-    }
-}
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
 
-impl From<&i32> for EntityId {
-    fn from(value: &i32) -> Self {
-        Self(value.to_string())
-    }
+        impl std::str::FromStr for $name {
+            type Err = $crate::app::entities::common::EntityIdParseError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                uuid::Uuid::parse_str(value).map(Self).map_err(|_| {
+                    $crate::app::entities::common::EntityIdParseError::Invalid {
+                        entity: $entity,
+                        value: value.to_string(),
+                    }
+                })
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = $crate::app::entities::common::EntityIdParseError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = $crate::app::entities::common::EntityIdParseError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+    };
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum EntityRef<EntityT> {
-    Id(EntityId),
+pub(crate) use entity_id;
+
+/// A reference to an entity, either by its id or by its (loaded) value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum EntityRef<IdT, EntityT> {
+    Id(IdT),
     Value(EntityT),
 }
 
-impl<EntityT> From<EntityId> for EntityRef<EntityT> {
-    fn from(value: EntityId) -> Self {
+impl<IdT, EntityT> From<IdT> for EntityRef<IdT, EntityT> {
+    fn from(value: IdT) -> Self {
         Self::Id(value)
     }
 }
@@ -0,0 +1,120 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::common::entity_id;
+use crate::app::typing::amount::Amount;
+use crate::app::typing::currency::Currency;
+use chrono::{Months, NaiveDate};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `Goal`.
+    GoalId,
+    "goal"
+);
+
+/// An amount to save by a date, on one or more savings accounts.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Goal {
+    id: Option<GoalId>,
+
+    /// A name describing the goal. (Eg: "Emergency fund")
+    name: String,
+
+    /// The amount to save.
+    target_amount: Amount,
+
+    /// The currency of the target amount, and of the linked accounts.
+    currency: Currency,
+
+    /// The day the target amount should be reached.
+    target_date: NaiveDate,
+
+    /// The savings accounts the money is put aside on, their balances add up to the saved amount.
+    account_ids: Vec<AccountId>,
+}
+
+impl Goal {
+    pub fn new(
+        id: Option<GoalId>,
+        name: String,
+        target_amount: Amount,
+        currency: Currency,
+        target_date: NaiveDate,
+        account_ids: Vec<AccountId>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            target_amount,
+            currency,
+            target_date,
+            account_ids,
+        }
+    }
+
+    pub fn id(&self) -> Option<&GoalId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn target_amount(&self) -> &Amount {
+        &self.target_amount
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    pub fn target_date(&self) -> NaiveDate {
+        self.target_date
+    }
+
+    pub fn account_ids(&self) -> &Vec<AccountId> {
+        &self.account_ids
+    }
+
+    /// The number of monthly contributions left to reach the target: one `today`,
+    /// then one on the same day of every following month, up to the target date (included).
+    /// 0 once the target date passed.
+    pub fn contributions_left(&self, today: NaiveDate) -> u32 {
+        let mut count = 0;
+        while today
+            .checked_add_months(Months::new(count))
+            .is_some_and(|date| date <= self.target_date)
+        {
+            count += 1;
+        }
+
+        count
+    }
+
+    pub fn set_id(&mut self, id: Option<GoalId>) {
+        self.id = id;
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_account_ids(&mut self, account_ids: Vec<AccountId>) {
+        self.account_ids = account_ids;
+    }
+}
+
+#[cfg(test)]
+impl Goal {
+    pub fn set_target_amount(&mut self, target_amount: Amount) {
+        self.target_amount = target_amount;
+    }
+
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.currency = currency;
+    }
+
+    pub fn set_target_date(&mut self, target_date: NaiveDate) {
+        self.target_date = target_date;
+    }
+}
@@ -0,0 +1,170 @@
+use crate::app::entities::common::entity_id;
+use crate::app::entities::transaction::TransactionType;
+use crate::app::typing::amount::Amount;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of an `Institution`.
+    InstitutionId,
+    "institution"
+);
+
+/// What kind of business an institution is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InstitutionKind {
+    Bank,
+
+    /// A mobile network operator running a mobile money service. (Eg: "MTN", "Airtel")
+    MobileMoneyOperator,
+
+    Microfinance,
+
+    /// A savings and credit cooperative.
+    Sacco,
+
+    /// A brokerage firm, or an asset manager.
+    Broker,
+
+    Other,
+}
+
+/// The fee an institution charges on the transactions of a type, within a range of amounts.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeeTier {
+    /// The type of the transactions the tier applies to.
+    pub transaction_type: TransactionType,
+
+    /// The smallest amount of the tier, included.
+    pub min_amount: Amount,
+
+    /// The largest amount of the tier, included, unbounded when unset.
+    pub max_amount: Option<Amount>,
+
+    /// The fixed part of the fee.
+    pub flat_fee: Amount,
+
+    /// The part of the amount charged on top of the flat fee, in percent. (Eg: 0.5)
+    #[serde(default)]
+    pub rate: f32,
+}
+
+impl FeeTier {
+    /// Whether the tier applies to a transaction of the type and amount.
+    pub fn applies_to(&self, transaction_type: &TransactionType, amount: &Amount) -> bool {
+        &self.transaction_type == transaction_type
+            && amount >= &self.min_amount
+            && self
+                .max_amount
+                .as_ref()
+                .is_none_or(|max_amount| amount <= max_amount)
+    }
+}
+
+fn round_to_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// A bank, mobile money operator or any other business the accounts are held at.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Institution {
+    id: Option<InstitutionId>,
+
+    /// The name of the institution, unique ignoring the case. (Eg: "Bank of Kigali")
+    name: String,
+
+    kind: InstitutionKind,
+
+    /// The ISO 3166-1 alpha-2 code of the country the institution operates in. (Eg: "RW")
+    country: String,
+
+    /// The address of the logo of the institution.
+    logo_url: Option<String>,
+
+    /// The fees charged on the transactions, the first tier applying to a transaction is used.
+    fee_schedule: Vec<FeeTier>,
+}
+
+impl Institution {
+    pub fn new(
+        id: Option<InstitutionId>,
+        name: String,
+        kind: InstitutionKind,
+        country: String,
+        logo_url: Option<String>,
+        fee_schedule: Vec<FeeTier>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            kind,
+            country,
+            logo_url,
+            fee_schedule,
+        }
+    }
+
+    pub fn id(&self) -> Option<&InstitutionId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn kind(&self) -> InstitutionKind {
+        self.kind
+    }
+
+    pub fn country(&self) -> &String {
+        &self.country
+    }
+
+    pub fn logo_url(&self) -> Option<&String> {
+        self.logo_url.as_ref()
+    }
+
+    pub fn fee_schedule(&self) -> &Vec<FeeTier> {
+        &self.fee_schedule
+    }
+
+    /// The fee charged on a transaction of the type and amount, from the first tier applying to it.
+    /// Nothing is charged when no tier applies.
+    pub fn fee_for(&self, transaction_type: &TransactionType, amount: &Amount) -> f32 {
+        self.fee_schedule
+            .iter()
+            .find(|tier| tier.applies_to(transaction_type, amount))
+            .map_or(0.0, |tier| {
+                let proportional = amount.value() as f64 * tier.rate as f64 / 100.0;
+                round_to_cents(tier.flat_fee.value() as f64 + proportional) as f32
+            })
+    }
+
+    pub fn set_id(&mut self, id: Option<InstitutionId>) {
+        self.id = id;
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_country(&mut self, country: String) {
+        self.country = country;
+    }
+}
+
+#[cfg(test)]
+impl Institution {
+    pub fn set_kind(&mut self, kind: InstitutionKind) {
+        self.kind = kind;
+    }
+
+    pub fn set_logo_url(&mut self, logo_url: Option<String>) {
+        self.logo_url = logo_url;
+    }
+
+    pub fn set_fee_schedule(&mut self, fee_schedule: Vec<FeeTier>) {
+        self.fee_schedule = fee_schedule;
+    }
+}
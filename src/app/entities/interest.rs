@@ -0,0 +1,368 @@
+use crate::app::entities::account::{AccountId, AccountType, OPENING_BALANCE_DESCRIPTION};
+use crate::app::entities::transaction::{Transaction, TransactionType};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The description of the transactions the interest is posted with.
+pub(crate) const INTEREST_DESCRIPTION: &str = "Interest";
+
+/// The highest annual interest rate, in percent. (Of the accounts, the loans and the debts)
+pub(crate) const MAX_ANNUAL_RATE: f32 = 100.0;
+
+/// How the days of a year are counted to turn the annual rate into a daily one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DayCount {
+    /// Every day counts, over a year of 365 days.
+    Actual365,
+
+    /// Every day counts, over a year of 360 days.
+    Actual360,
+
+    /// Every month counts for 30 days, over a year of 360 days.
+    Thirty360,
+}
+
+impl DayCount {
+    /// The part of a year the day counts for.
+    pub fn year_fraction(&self, day: NaiveDate) -> f64 {
+        match self {
+            DayCount::Actual365 => 1.0 / 365.0,
+            DayCount::Actual360 => 1.0 / 360.0,
+            DayCount::Thirty360 => {
+                let is_last_of_february =
+                    day.month() == 2 && day.checked_add_days(Days::new(1)).unwrap().month() == 3;
+                // The 31st does not count, the last day of February counts for the days up to the 30th.
+                let days = match day.day() {
+                    31 => 0,
+                    day_of_month if is_last_of_february => 30 - day_of_month + 1,
+                    _ => 1,
+                };
+                days as f64 / 360.0
+            }
+        }
+    }
+}
+
+/// How often the accrued interest starts earning interest itself, before it is posted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Compounding {
+    /// The interest is computed on the balance only.
+    Simple,
+
+    Daily,
+
+    /// At the end of every month.
+    Monthly,
+}
+
+/// How often the accrued interest is posted to the account, at the end of the periods of the calendar.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PostingSchedule {
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl PostingSchedule {
+    /// The last day of the period containing the date.
+    pub fn end_of(&self, date: NaiveDate) -> NaiveDate {
+        let (month, months) = match self {
+            PostingSchedule::Monthly => (date.month(), 1),
+            PostingSchedule::Quarterly => ((date.month() - 1) / 3 * 3 + 1, 3),
+            PostingSchedule::Yearly => (1, 12),
+        };
+
+        NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap() + Months::new(months) - Days::new(1)
+    }
+}
+
+/// Whether the interest is paid to the account holder, or by them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InterestDirection {
+    /// Posted as an income.
+    Earned,
+
+    /// Posted as an expense.
+    Charged,
+}
+
+impl InterestDirection {
+    /// The debts (credit and loan accounts) are charged interest, the other accounts earn it.
+    pub fn of(account_type: &AccountType) -> Self {
+        if account_type.is_debt() {
+            InterestDirection::Charged
+        } else {
+            InterestDirection::Earned
+        }
+    }
+
+    /// The type of the transactions the interest is posted with.
+    pub fn transaction_type(&self) -> TransactionType {
+        match self {
+            InterestDirection::Earned => TransactionType::Income,
+            InterestDirection::Charged => TransactionType::Expense,
+        }
+    }
+}
+
+/// The balance of an account at the end of every day, from its transactions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BalanceHistory {
+    /// The balance before the first change.
+    initial: f64,
+
+    /// The closing balance of the last transaction of each day with transactions, in date order.
+    closings: Vec<(NaiveDate, f64)>,
+}
+
+impl BalanceHistory {
+    /// Builds the history from the transactions of an account, sorted by date.
+    /// Without any transaction, the balance has always been the current one.
+    pub fn from_transactions(
+        transactions: impl IntoIterator<Item = Transaction>,
+        current_balance: f64,
+    ) -> Self {
+        let mut initial = None;
+        let mut closings: Vec<(NaiveDate, f64)> = vec![];
+
+        for transaction in transactions {
+            let (Some(opening), Some(closing)) =
+                (transaction.opening_balance(), transaction.closing_balance())
+            else {
+                continue;
+            };
+            initial.get_or_insert(opening.value() as f64);
+
+            let day = transaction.date().date_naive();
+            match closings.last_mut() {
+                Some((last_day, balance)) if *last_day == day => *balance = closing.value() as f64,
+                _ => closings.push((day, closing.value() as f64)),
+            }
+        }
+
+        Self {
+            initial: initial.unwrap_or(current_balance),
+            closings,
+        }
+    }
+
+    /// Builds the history of the amount owed on a debt from its confirmed transactions, sorted by date,
+    /// the way its statements count it: the expenses and the fees are charges, the incomes are payments,
+    /// and the opening balance is the credit available when the account was opened, it is not a payment.
+    /// Nothing is owed before the first transaction.
+    pub fn owed_from_transactions(transactions: impl IntoIterator<Item = Transaction>) -> Self {
+        let mut owed = 0f64;
+        let mut closings: Vec<(NaiveDate, f64)> = vec![];
+
+        for transaction in transactions {
+            if transaction.description().as_deref() == Some(OPENING_BALANCE_DESCRIPTION) {
+                continue;
+            }
+            let amount = transaction.amount().value() as f64;
+            owed += transaction.fee().value() as f64;
+            owed += match transaction.transaction_type() {
+                TransactionType::Income => -amount,
+                TransactionType::Expense => amount,
+            };
+
+            let day = transaction.date().date_naive();
+            match closings.last_mut() {
+                Some((last_day, balance)) if *last_day == day => *balance = owed,
+                _ => closings.push((day, owed)),
+            }
+        }
+
+        Self {
+            initial: 0.0,
+            closings,
+        }
+    }
+
+    /// The balance at the end of the day.
+    pub fn at(&self, day: NaiveDate) -> f64 {
+        let index = self.closings.partition_point(|(date, _)| *date <= day);
+        match index {
+            0 => self.initial,
+            _ => self.closings[index - 1].1,
+        }
+    }
+}
+
+/// Interest to post to an account, on the last day of a posting period.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct InterestPosting {
+    pub date: NaiveDate,
+
+    /// Rounded to the cent.
+    pub amount: f32,
+}
+
+/// The interest of an account over some days.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Accrual {
+    /// The interest of the posting periods which ended, in date order.
+    pub postings: Vec<InterestPosting>,
+
+    /// The interest accrued since the last posting period ended, posted at the end of the current one.
+    pub accrued: f32,
+}
+
+fn round_to_cents(amount: f64) -> f32 {
+    ((amount * 100.0).round() / 100.0) as f32
+}
+
+/// How the interest of an account is computed and posted.
+/// An account has at most one configuration, identified by the account id.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct InterestConfig {
+    account_id: AccountId,
+
+    /// The yearly interest rate, in percent. (Eg: 7.5)
+    annual_rate: f32,
+
+    day_count: DayCount,
+
+    compounding: Compounding,
+
+    posting: PostingSchedule,
+
+    /// The first day interest accrues on.
+    start: NaiveDate,
+
+    /// The last day whose interest was posted, if any.
+    posted_until: Option<NaiveDate>,
+}
+
+impl InterestConfig {
+    pub fn new(
+        account_id: AccountId,
+        annual_rate: f32,
+        day_count: DayCount,
+        compounding: Compounding,
+        posting: PostingSchedule,
+        start: NaiveDate,
+        posted_until: Option<NaiveDate>,
+    ) -> Self {
+        Self {
+            account_id,
+            annual_rate,
+            day_count,
+            compounding,
+            posting,
+            start,
+            posted_until,
+        }
+    }
+
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn annual_rate(&self) -> f32 {
+        self.annual_rate
+    }
+
+    pub fn day_count(&self) -> DayCount {
+        self.day_count
+    }
+
+    pub fn compounding(&self) -> Compounding {
+        self.compounding
+    }
+
+    pub fn posting(&self) -> PostingSchedule {
+        self.posting
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    pub fn posted_until(&self) -> Option<NaiveDate> {
+        self.posted_until
+    }
+
+    /// The first day whose interest is not posted yet.
+    pub fn accrual_start(&self) -> NaiveDate {
+        self.posted_until.map_or(self.start, |posted_until| {
+            (posted_until + Days::new(1)).max(self.start)
+        })
+    }
+
+    /// Whether a posting period ended on or before the day, so there is interest to post.
+    pub fn is_due(&self, day: NaiveDate) -> bool {
+        self.posting.end_of(self.accrual_start()) <= day
+    }
+
+    /// Computes the interest from the first day not posted yet, up to `until` (included).
+    ///
+    /// The interest of a day is computed on the balance at its end, plus the interest compounded so far:
+    /// the money on the account when it earns interest, the amount owed when it is charged.
+    /// The interest of the periods ending on the way is expected to be posted:
+    /// it raises the balance of the following days.
+    pub fn accrue(&self, balances: &BalanceHistory, until: NaiveDate) -> Accrual {
+        let rate = self.annual_rate as f64 / 100.0;
+        let mut postings: Vec<InterestPosting> = vec![];
+        // The interest posted on the way, not in the balance history yet.
+        let mut posted = 0f64;
+        let mut accrued = 0f64;
+        let mut compounded = 0f64;
+
+        let mut day = self.accrual_start();
+        while day <= until {
+            let balance = (balances.at(day) + posted).max(0.0);
+            accrued += (balance + compounded) * rate * self.day_count.year_fraction(day);
+
+            let tomorrow = day + Days::new(1);
+            compounded = match self.compounding {
+                Compounding::Simple => 0.0,
+                Compounding::Daily => accrued,
+                Compounding::Monthly if tomorrow.day() == 1 => accrued,
+                Compounding::Monthly => compounded,
+            };
+
+            if self.posting.end_of(day) == day {
+                let amount = round_to_cents(accrued);
+                posted += amount as f64;
+                postings.push(InterestPosting { date: day, amount });
+                accrued = 0.0;
+                compounded = 0.0;
+            }
+
+            day = tomorrow;
+        }
+
+        Accrual {
+            postings,
+            accrued: round_to_cents(accrued),
+        }
+    }
+
+    pub fn set_account_id(&mut self, account_id: AccountId) {
+        self.account_id = account_id;
+    }
+
+    pub fn set_posted_until(&mut self, posted_until: Option<NaiveDate>) {
+        self.posted_until = posted_until;
+    }
+}
+
+#[cfg(test)]
+impl InterestConfig {
+    pub fn set_annual_rate(&mut self, annual_rate: f32) {
+        self.annual_rate = annual_rate;
+    }
+
+    pub fn set_day_count(&mut self, day_count: DayCount) {
+        self.day_count = day_count;
+    }
+
+    pub fn set_compounding(&mut self, compounding: Compounding) {
+        self.compounding = compounding;
+    }
+}
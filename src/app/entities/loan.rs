@@ -0,0 +1,227 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::common::entity_id;
+use crate::app::typing::amount::Amount;
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `Loan`.
+    LoanId,
+    "loan"
+);
+
+/// How often a loan is repaid.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentFrequency {
+    Weekly,
+
+    /// Every other week.
+    Biweekly,
+
+    Monthly,
+
+    Quarterly,
+}
+
+impl PaymentFrequency {
+    /// The number of instalments in a year.
+    pub fn per_year(&self) -> u32 {
+        match self {
+            PaymentFrequency::Weekly => 52,
+            PaymentFrequency::Biweekly => 26,
+            PaymentFrequency::Monthly => 12,
+            PaymentFrequency::Quarterly => 4,
+        }
+    }
+
+    /// The date `count` periods after `start`.
+    /// The monthly dates of a start at the end of a month fall on the last day of the shorter months.
+    pub fn after(&self, start: NaiveDate, count: u32) -> NaiveDate {
+        match self {
+            PaymentFrequency::Weekly => start + Days::new(7 * count as u64),
+            PaymentFrequency::Biweekly => start + Days::new(14 * count as u64),
+            PaymentFrequency::Monthly => start + Months::new(count),
+            PaymentFrequency::Quarterly => start + Months::new(3 * count),
+        }
+    }
+}
+
+/// A scheduled repayment of a loan.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Instalment {
+    /// The position of the instalment in the schedule, from 1.
+    pub number: u32,
+
+    pub due_date: NaiveDate,
+
+    /// The amount to repay, the principal and the interest together.
+    pub payment: f32,
+
+    pub principal: f32,
+
+    pub interest: f32,
+
+    /// The principal left to repay once the instalment is paid.
+    pub balance_after: f32,
+}
+
+fn round_to_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Money borrowed, repaid with level instalments over a fixed term.
+/// The repayments are the incomes of its credit or loan account.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Loan {
+    id: Option<LoanId>,
+
+    /// A name describing the loan. (Eg: "Car loan")
+    name: String,
+
+    /// The credit or loan account the loan is tracked on, and repaid to.
+    account_id: AccountId,
+
+    /// The amount borrowed.
+    principal: Amount,
+
+    /// The yearly interest rate, in percent. (Eg: 16.5)
+    annual_rate: f32,
+
+    /// The number of instalments.
+    term: u32,
+
+    frequency: PaymentFrequency,
+
+    /// The day the money was received, the first instalment is due one period later.
+    start: NaiveDate,
+}
+
+impl Loan {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Option<LoanId>,
+        name: String,
+        account_id: AccountId,
+        principal: Amount,
+        annual_rate: f32,
+        term: u32,
+        frequency: PaymentFrequency,
+        start: NaiveDate,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            account_id,
+            principal,
+            annual_rate,
+            term,
+            frequency,
+            start,
+        }
+    }
+
+    pub fn id(&self) -> Option<&LoanId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn principal(&self) -> &Amount {
+        &self.principal
+    }
+
+    pub fn annual_rate(&self) -> f32 {
+        self.annual_rate
+    }
+
+    pub fn term(&self) -> u32 {
+        self.term
+    }
+
+    pub fn frequency(&self) -> PaymentFrequency {
+        self.frequency
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// The amortization schedule: every instalment repays the same amount, the interest of the period first.
+    /// The amounts are rounded to the cent, the last instalment repays the principal left.
+    pub fn schedule(&self) -> Vec<Instalment> {
+        let principal = self.principal.value() as f64;
+        let rate = self.annual_rate as f64 / 100.0 / self.frequency.per_year() as f64;
+        let term = self.term as f64;
+        let payment = if rate == 0.0 {
+            principal / term
+        } else {
+            principal * rate / (1.0 - (1.0 + rate).powf(-term))
+        };
+        let payment = round_to_cents(payment);
+
+        let mut balance = principal;
+        (1..=self.term)
+            .map(|number| {
+                let interest = round_to_cents(balance * rate);
+                let principal = if number == self.term {
+                    balance
+                } else {
+                    (payment - interest).min(balance)
+                };
+                balance = round_to_cents(balance - principal);
+
+                Instalment {
+                    number,
+                    due_date: self.frequency.after(self.start, number),
+                    payment: (principal + interest) as f32,
+                    principal: principal as f32,
+                    interest: interest as f32,
+                    balance_after: balance as f32,
+                }
+            })
+            .collect()
+    }
+
+    pub fn set_id(&mut self, id: Option<LoanId>) {
+        self.id = id;
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_account_id(&mut self, account_id: AccountId) {
+        self.account_id = account_id;
+    }
+}
+
+#[cfg(test)]
+impl Loan {
+    pub fn set_principal(&mut self, principal: Amount) {
+        self.principal = principal;
+    }
+
+    pub fn set_annual_rate(&mut self, annual_rate: f32) {
+        self.annual_rate = annual_rate;
+    }
+
+    pub fn set_term(&mut self, term: u32) {
+        self.term = term;
+    }
+
+    pub fn set_frequency(&mut self, frequency: PaymentFrequency) {
+        self.frequency = frequency;
+    }
+
+    pub fn set_start(&mut self, start: NaiveDate) {
+        self.start = start;
+    }
+}
@@ -1,5 +1,30 @@
 pub(crate) mod account;
+pub(crate) mod budget;
+pub(crate) mod category;
 pub(crate) mod common;
+pub(crate) mod goal;
+pub(crate) mod institution;
+pub(crate) mod interest;
+pub(crate) mod loan;
+pub(crate) mod payee;
+pub(crate) mod payoff;
+pub(crate) mod recurring_transaction;
+pub(crate) mod rule;
+pub(crate) mod statement;
+pub(crate) mod tag;
 mod test_account;
+mod test_budget;
+mod test_category;
+mod test_common;
+mod test_goal;
+mod test_institution;
+mod test_interest;
+mod test_loan;
+mod test_payee;
+mod test_payoff;
+mod test_recurring_transaction;
+mod test_rule;
+mod test_statement;
+mod test_tag;
 mod test_transaction;
 pub(crate) mod transaction;
@@ -0,0 +1,120 @@
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::common::entity_id;
+use regex::RegexBuilder;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `Payee`.
+    PayeeId,
+    "payee"
+);
+
+/// A merchant, person or organization the transactions are made with.
+///
+/// The descriptions and messages of the transactions spell the same payee in many ways
+/// (Eg: "SIMBA SUPERMARKET LTD", "Simba Supermkt Kigali"), so a payee is recognized by its name,
+/// its aliases and its patterns.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Payee {
+    id: Option<PayeeId>,
+
+    /// The display name of the payee, unique once normalized.
+    name: String,
+
+    /// Other spellings of the payee, matched as whole words, ignoring the case and the punctuation.
+    aliases: Vec<String>,
+
+    /// Regular expressions matched against the text, ignoring the case. (Eg: "simba\\s+super")
+    patterns: Vec<String>,
+
+    /// The category given to the transactions of the payee, when they have none.
+    default_category_id: Option<CategoryId>,
+}
+
+/// Normalizes a text, so its different spellings compare equal:
+/// it is lower cased, and every run of punctuation or spaces becomes a single space.
+pub fn normalize_text(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+impl Payee {
+    pub fn new(
+        id: Option<PayeeId>,
+        name: String,
+        aliases: Vec<String>,
+        patterns: Vec<String>,
+        default_category_id: Option<CategoryId>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            aliases,
+            patterns,
+            default_category_id,
+        }
+    }
+
+    pub fn id(&self) -> Option<&PayeeId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    pub fn patterns(&self) -> &Vec<String> {
+        &self.patterns
+    }
+
+    pub fn default_category_id(&self) -> Option<&CategoryId> {
+        self.default_category_id.as_ref()
+    }
+
+    /// Whether the text mentions the payee: its name or one of its aliases appears in it as whole words,
+    /// or one of its patterns matches it.
+    pub fn matches(&self, text: &str) -> bool {
+        // Padded with spaces, so the names only match whole words.
+        let text_words = format!(" {} ", normalize_text(text));
+        let mentioned = std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .map(|name| normalize_text(name))
+            .any(|name| !name.is_empty() && text_words.contains(&format!(" {name} ")));
+
+        mentioned
+            || self.patterns.iter().any(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .is_ok_and(|regex| regex.is_match(text))
+            })
+    }
+
+    pub fn set_id(&mut self, id: Option<PayeeId>) {
+        self.id = id;
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_aliases(&mut self, aliases: Vec<String>) {
+        self.aliases = aliases;
+    }
+
+    pub fn set_patterns(&mut self, patterns: Vec<String>) {
+        self.patterns = patterns;
+    }
+
+    pub fn set_default_category_id(&mut self, default_category_id: Option<CategoryId>) {
+        self.default_category_id = default_category_id;
+    }
+}
@@ -0,0 +1,263 @@
+use crate::app::entities::account::AccountId;
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The most months a payoff plan is simulated for. (Eg: 50 years)
+pub(crate) const MAX_MONTHS: u32 = 600;
+
+/// The order the money left after the minimum payments goes to the debts.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoffStrategy {
+    /// The smallest balance first.
+    Snowball,
+
+    /// The highest rate first.
+    Avalanche,
+
+    /// An order chosen by the user.
+    Custom,
+}
+
+/// A debt to pay off, as it stands when the plan starts.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Debt {
+    /// The credit or loan account the debt is owed on.
+    pub account_id: AccountId,
+
+    pub name: String,
+
+    /// The amount owed.
+    pub balance: f32,
+
+    /// The yearly interest rate, in percent, charged monthly on the amount owed.
+    pub annual_rate: f32,
+
+    /// The least amount paid every month, while the debt is not paid off.
+    pub minimum_payment: f32,
+}
+
+impl Debt {
+    /// Orders the debts the way the strategy pays them off, the ties keep their order.
+    /// The custom order is the order of the debts.
+    pub fn sort(debts: &mut [Debt], strategy: PayoffStrategy) {
+        match strategy {
+            PayoffStrategy::Snowball => debts.sort_by(|a, b| {
+                a.balance
+                    .total_cmp(&b.balance)
+                    .then(b.annual_rate.total_cmp(&a.annual_rate))
+            }),
+            PayoffStrategy::Avalanche => debts.sort_by(|a, b| {
+                b.annual_rate
+                    .total_cmp(&a.annual_rate)
+                    .then(a.balance.total_cmp(&b.balance))
+            }),
+            PayoffStrategy::Custom => {}
+        }
+    }
+}
+
+/// The payment of a debt in a month.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct DebtPayment {
+    pub account_id: AccountId,
+
+    /// The interest charged in the month, before the payment.
+    pub interest: f32,
+
+    pub payment: f32,
+
+    /// The amount owed once the payment is made.
+    pub balance_after: f32,
+}
+
+/// The payments of a month, for the debts which are not paid off yet.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct PayoffMonth {
+    /// The day the payments are made.
+    pub date: NaiveDate,
+
+    pub payments: Vec<DebtPayment>,
+
+    pub total_payment: f32,
+
+    /// The amount owed on all the debts once the payments are made.
+    pub total_balance: f32,
+}
+
+/// When a debt is paid off, and what it cost.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct DebtPayoff {
+    pub account_id: AccountId,
+
+    pub name: String,
+
+    pub payoff_date: NaiveDate,
+
+    /// The number of monthly payments made on the debt.
+    pub months: u32,
+
+    pub total_interest: f32,
+
+    pub total_paid: f32,
+}
+
+/// A simulation of the payoff of debts with a monthly budget.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct PayoffPlan {
+    pub strategy: PayoffStrategy,
+
+    /// The order the money left after the minimum payments goes to the debts.
+    pub order: Vec<AccountId>,
+
+    /// The day the last debt is paid off.
+    pub payoff_date: NaiveDate,
+
+    pub total_interest: f32,
+
+    pub total_paid: f32,
+
+    /// The debts, in payoff order.
+    pub debts: Vec<DebtPayoff>,
+
+    pub months: Vec<PayoffMonth>,
+}
+
+fn round_to_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// A debt being paid off.
+struct Simulated {
+    balance: f64,
+    monthly_rate: f64,
+    minimum_payment: f64,
+    months: u32,
+    total_interest: f64,
+    total_paid: f64,
+    payoff_date: Option<NaiveDate>,
+}
+
+impl PayoffPlan {
+    /// Simulates the payoff of the debts, in the order of the strategy, paying `monthly_budget` a month
+    /// from the month after `start`.
+    ///
+    /// Every month, the interest is charged first, then the minimum payments are made,
+    /// and the rest of the budget goes to the debts in order: once a debt is paid off,
+    /// its payment rolls over to the next one.
+    ///
+    /// # Returns
+    /// * `None` — If the debts are not paid off within `MAX_MONTHS`, or stop going down.
+    pub fn simulate(
+        mut debts: Vec<Debt>,
+        strategy: PayoffStrategy,
+        monthly_budget: f32,
+        start: NaiveDate,
+    ) -> Option<PayoffPlan> {
+        Debt::sort(&mut debts, strategy);
+        let monthly_budget = monthly_budget as f64;
+        let mut simulated: Vec<Simulated> = debts
+            .iter()
+            .map(|debt| Simulated {
+                balance: debt.balance as f64,
+                monthly_rate: debt.annual_rate as f64 / 100.0 / 12.0,
+                minimum_payment: debt.minimum_payment as f64,
+                months: 0,
+                total_interest: 0.0,
+                total_paid: 0.0,
+                payoff_date: None,
+            })
+            .collect();
+
+        let mut months: Vec<PayoffMonth> = vec![];
+        let mut month = 0;
+        let mut total_balance: f64 = simulated.iter().map(|debt| debt.balance).sum();
+        while total_balance > 0.0 {
+            month += 1;
+            if month > MAX_MONTHS {
+                return None;
+            }
+            let date = start + Months::new(month);
+
+            let mut payments = vec![0f64; simulated.len()];
+            let mut interests = vec![0f64; simulated.len()];
+            let mut left = monthly_budget;
+            for (index, debt) in simulated.iter_mut().enumerate() {
+                if debt.balance > 0.0 {
+                    interests[index] = round_to_cents(debt.balance * debt.monthly_rate);
+                    debt.balance += interests[index];
+                    payments[index] = debt.minimum_payment.min(debt.balance);
+                    left -= payments[index];
+                }
+            }
+            for (index, debt) in simulated.iter().enumerate() {
+                let extra = left.min(debt.balance - payments[index]).max(0.0);
+                payments[index] += extra;
+                left -= extra;
+            }
+
+            let mut month_payments = vec![];
+            for (index, debt) in simulated.iter_mut().enumerate() {
+                if debt.balance <= 0.0 {
+                    continue;
+                }
+                debt.balance = round_to_cents(debt.balance - payments[index]);
+                debt.months += 1;
+                debt.total_interest += interests[index];
+                debt.total_paid += payments[index];
+                if debt.balance <= 0.0 {
+                    debt.balance = 0.0;
+                    debt.payoff_date = Some(date);
+                }
+                month_payments.push(DebtPayment {
+                    account_id: debts[index].account_id.clone(),
+                    interest: interests[index] as f32,
+                    payment: round_to_cents(payments[index]) as f32,
+                    balance_after: debt.balance as f32,
+                });
+            }
+
+            let balance: f64 = simulated.iter().map(|debt| debt.balance).sum();
+            // The payments do not cover the interest, the debts would never be paid off.
+            if balance >= total_balance {
+                return None;
+            }
+            total_balance = balance;
+            months.push(PayoffMonth {
+                date,
+                total_payment: round_to_cents(payments.iter().sum()) as f32,
+                total_balance: round_to_cents(total_balance) as f32,
+                payments: month_payments,
+            });
+        }
+
+        let total_interest: f64 = simulated.iter().map(|debt| debt.total_interest).sum();
+        let total_paid: f64 = simulated.iter().map(|debt| debt.total_paid).sum();
+        let mut payoffs: Vec<DebtPayoff> = debts
+            .iter()
+            .zip(&simulated)
+            .filter_map(|(debt, simulated)| {
+                Some(DebtPayoff {
+                    account_id: debt.account_id.clone(),
+                    name: debt.name.clone(),
+                    payoff_date: simulated.payoff_date?,
+                    months: simulated.months,
+                    total_interest: round_to_cents(simulated.total_interest) as f32,
+                    total_paid: round_to_cents(simulated.total_paid) as f32,
+                })
+            })
+            .collect();
+        payoffs.sort_by_key(|payoff| payoff.payoff_date);
+
+        Some(PayoffPlan {
+            strategy,
+            order: debts.iter().map(|debt| debt.account_id.clone()).collect(),
+            payoff_date: months.last().map_or(start, |month| month.date),
+            total_interest: round_to_cents(total_interest) as f32,
+            total_paid: round_to_cents(total_paid) as f32,
+            debts: payoffs,
+            months,
+        })
+    }
+}
@@ -0,0 +1,306 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::common::entity_id;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{
+    AccountRef, Transaction, TransactionStatus, TransactionType,
+};
+use crate::app::typing::amount::Amount;
+use crate::app::typing::currency::Currency;
+use chrono::{DateTime, Datelike, Months, NaiveDate, TimeDelta, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `RecurringTransaction`.
+    RecurringTransactionId,
+    "recurring transaction"
+);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Where an occurrence falling on a weekend is moved.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub enum BusinessDayConvention {
+    /// The occurrence is kept on the weekend.
+    #[default]
+    Unadjusted,
+
+    /// The occurrence is moved to the next Monday.
+    Following,
+
+    /// The occurrence is moved to the previous Friday.
+    Preceding,
+}
+
+fn default_interval() -> u32 {
+    1
+}
+
+/// When a recurring transaction occurs, like an iCalendar recurrence rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Schedule {
+    pub frequency: Frequency,
+
+    /// The number of periods between two occurrences. (Eg: 2 for every other week)
+    #[serde(default = "default_interval")]
+    pub interval: u32,
+
+    /// The date of the first occurrence, which the next ones follow.
+    /// The monthly occurrences of a start at the end of a month fall on the last day of the shorter months.
+    pub start: DateTime<Utc>,
+
+    /// The occurrences after this date are dropped.
+    pub end: Option<DateTime<Utc>>,
+
+    /// The number of occurrences, counted from the start.
+    pub count: Option<u32>,
+
+    #[serde(default)]
+    pub business_day: BusinessDayConvention,
+}
+
+impl Schedule {
+    /// The date of an occurrence, before the business day adjustment.
+    /// Every date is computed from the start, so the days cut at the end of the short months are not lost.
+    fn nominal_date(&self, index: u32) -> Option<DateTime<Utc>> {
+        let periods = index.checked_mul(self.interval)?;
+
+        match self.frequency {
+            Frequency::Daily => self
+                .start
+                .checked_add_signed(TimeDelta::try_days(periods as i64)?),
+            Frequency::Weekly => self
+                .start
+                .checked_add_signed(TimeDelta::try_weeks(periods as i64)?),
+            Frequency::Monthly => self.start.checked_add_months(Months::new(periods)),
+            Frequency::Yearly => self
+                .start
+                .checked_add_months(Months::new(periods.checked_mul(12)?)),
+        }
+    }
+
+    /// Moves a date falling on a weekend according to the business day convention.
+    fn adjust(&self, date: DateTime<Utc>) -> DateTime<Utc> {
+        let days = match (&self.business_day, date.weekday()) {
+            (BusinessDayConvention::Following, Weekday::Sat) => 2,
+            (BusinessDayConvention::Following, Weekday::Sun) => 1,
+            (BusinessDayConvention::Preceding, Weekday::Sat) => -1,
+            (BusinessDayConvention::Preceding, Weekday::Sun) => -2,
+            _ => 0,
+        };
+
+        date + TimeDelta::days(days)
+    }
+
+    /// The dates of all the occurrences, in order, after the business day adjustment.
+    /// The occurrences moved onto the same day are merged, so a daily schedule only falls on business days.
+    pub fn occurrences(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        let mut previous: Option<NaiveDate> = None;
+
+        (0..)
+            .map_while(move |index| {
+                if self.count.is_some_and(|count| index >= count) {
+                    return None;
+                }
+                let date = self.nominal_date(index)?;
+                if self.end.is_some_and(|end| date > end) {
+                    return None;
+                }
+
+                Some(self.adjust(date))
+            })
+            .filter(move |date| {
+                let is_new = previous != Some(date.date_naive());
+                previous = Some(date.date_naive());
+                is_new
+            })
+    }
+}
+
+/// The transaction created at each occurrence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionTemplate {
+    pub account_id: AccountId,
+
+    pub transaction_type: TransactionType,
+
+    #[schema(value_type = f32)]
+    pub amount: Amount,
+
+    #[schema(value_type = f32)]
+    pub fee: Amount,
+
+    pub currency: Currency,
+
+    pub description: Option<String>,
+
+    pub message: Option<String>,
+
+    pub category_id: Option<CategoryId>,
+
+    pub payee_id: Option<PayeeId>,
+
+    #[serde(default)]
+    pub tags: Vec<TagId>,
+}
+
+impl TransactionTemplate {
+    /// The pending transaction of an occurrence, to be confirmed once it really happens.
+    pub fn to_transaction(&self, date: DateTime<Utc>) -> Transaction {
+        let mut transaction = Transaction::new(
+            None,
+            AccountRef::Id(self.account_id.clone()),
+            self.transaction_type.clone(),
+            self.amount.clone(),
+            self.fee.clone(),
+            None,
+            None,
+            self.currency.clone(),
+            TransactionStatus::Pending,
+            date,
+            self.description.clone(),
+            None,
+            self.message.clone(),
+        );
+        transaction.set_category_id(self.category_id.clone());
+        transaction.set_payee_id(self.payee_id.clone());
+        transaction.set_tags(self.tags.clone());
+
+        transaction
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, ToSchema)]
+pub enum RecurringStatus {
+    #[default]
+    Active,
+
+    /// No occurrence is created, the ones falling due meanwhile are passed over.
+    Paused,
+}
+
+/// A transaction repeating on a schedule, like a rent, school fees or a salary.
+/// Its occurrences are created as pending transactions once they fall due.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct RecurringTransaction {
+    id: Option<RecurringTransactionId>,
+
+    /// A name describing the recurring transaction. (Eg: "Rent")
+    name: String,
+
+    template: TransactionTemplate,
+
+    schedule: Schedule,
+
+    status: RecurringStatus,
+
+    /// The days of the future occurrences which will not be created.
+    skipped_dates: Vec<NaiveDate>,
+
+    /// The date of the last occurrence which was created, skipped or passed over.
+    last_occurrence: Option<DateTime<Utc>>,
+}
+
+impl RecurringTransaction {
+    pub fn new(
+        id: Option<RecurringTransactionId>,
+        name: String,
+        template: TransactionTemplate,
+        schedule: Schedule,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            template,
+            schedule,
+            status: RecurringStatus::Active,
+            skipped_dates: vec![],
+            last_occurrence: None,
+        }
+    }
+
+    pub fn id(&self) -> Option<&RecurringTransactionId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn template(&self) -> &TransactionTemplate {
+        &self.template
+    }
+
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    pub fn status(&self) -> &RecurringStatus {
+        &self.status
+    }
+
+    pub fn skipped_dates(&self) -> &Vec<NaiveDate> {
+        &self.skipped_dates
+    }
+
+    pub fn last_occurrence(&self) -> Option<&DateTime<Utc>> {
+        self.last_occurrence.as_ref()
+    }
+
+    /// The dates of the occurrences after the last one, skipped ones included.
+    pub fn next_occurrences(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.schedule
+            .occurrences()
+            .skip_while(|date| self.last_occurrence.is_some_and(|last| *date <= last))
+    }
+
+    /// The dates of the occurrences which will be created, leaving out the skipped ones.
+    pub fn upcoming_occurrences(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.next_occurrences()
+            .filter(|date| !self.is_skipped(date))
+    }
+
+    pub fn is_skipped(&self, date: &DateTime<Utc>) -> bool {
+        self.skipped_dates.contains(&date.date_naive())
+    }
+
+    pub fn set_id(&mut self, id: Option<RecurringTransactionId>) {
+        self.id = id;
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_template(&mut self, template: TransactionTemplate) {
+        self.template = template;
+    }
+
+    pub fn set_schedule(&mut self, schedule: Schedule) {
+        self.schedule = schedule;
+    }
+
+    pub fn set_status(&mut self, status: RecurringStatus) {
+        self.status = status;
+    }
+
+    pub fn set_skipped_dates(&mut self, skipped_dates: Vec<NaiveDate>) {
+        self.skipped_dates = skipped_dates;
+    }
+
+    /// Moves past an occurrence, and forgets the skipped days it leaves behind.
+    pub fn set_last_occurrence(&mut self, last_occurrence: Option<DateTime<Utc>>) {
+        self.last_occurrence = last_occurrence;
+        if let Some(last) = last_occurrence {
+            self.skipped_dates.retain(|date| *date > last.date_naive());
+        }
+    }
+}
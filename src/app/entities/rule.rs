@@ -0,0 +1,198 @@
+use crate::app::entities::account::{Account, AccountId, AccountType};
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::common::entity_id;
+use crate::app::entities::institution::InstitutionId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{Transaction, TransactionStatus, TransactionType};
+use crate::app::typing::amount::Amount;
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `Rule`.
+    RuleId,
+    "rule"
+);
+
+/// What a transaction must look like for a rule to apply to it.
+/// Every condition that is set must be met, the unset ones are ignored.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RuleConditions {
+    /// Regular expression matched against the description, ignoring the case.
+    pub description: Option<String>,
+
+    /// Regular expression matched against the message, ignoring the case.
+    pub message: Option<String>,
+
+    /// Regular expression matched against the reference number, ignoring the case.
+    pub reference_number: Option<String>,
+
+    /// The smallest amount, included.
+    pub min_amount: Option<Amount>,
+
+    /// The largest amount, included.
+    pub max_amount: Option<Amount>,
+
+    pub account_id: Option<AccountId>,
+
+    /// The institution the account is held at.
+    pub institution_id: Option<InstitutionId>,
+
+    /// The type of the account. (Eg: the money in hand, held at no institution)
+    pub account_type: Option<AccountType>,
+
+    pub transaction_type: Option<TransactionType>,
+}
+
+/// What a rule does to the transactions it applies to.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RuleActions {
+    /// The category given to the transactions without one.
+    pub category_id: Option<CategoryId>,
+
+    /// The payee given to the transactions without one.
+    pub payee_id: Option<PayeeId>,
+
+    /// Tags added to the transactions.
+    #[serde(default)]
+    pub tags: Vec<TagId>,
+
+    /// The status given to the transactions.
+    pub status: Option<TransactionStatus>,
+}
+
+/// A rule classifying the transactions automatically.
+///
+/// The rules are evaluated by increasing priority: when several rules set the same field,
+/// the one with the lowest priority number wins.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Rule {
+    id: Option<RuleId>,
+
+    /// A name describing the rule. (Eg: "Groceries at Simba")
+    name: String,
+
+    /// The rules are evaluated from the lowest priority number to the highest.
+    priority: u32,
+
+    conditions: RuleConditions,
+
+    actions: RuleActions,
+}
+
+/// Whether the text is set and matches the pattern, ignoring the case.
+/// An invalid pattern never matches.
+fn matches_pattern(pattern: &str, text: &Option<String>) -> bool {
+    text.as_ref().is_some_and(|text| {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .is_ok_and(|regex| regex.is_match(text))
+    })
+}
+
+impl Rule {
+    pub fn new(
+        id: Option<RuleId>,
+        name: String,
+        priority: u32,
+        conditions: RuleConditions,
+        actions: RuleActions,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            priority,
+            conditions,
+            actions,
+        }
+    }
+
+    pub fn id(&self) -> Option<&RuleId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    pub fn conditions(&self) -> &RuleConditions {
+        &self.conditions
+    }
+
+    pub fn actions(&self) -> &RuleActions {
+        &self.actions
+    }
+
+    /// Whether the transaction meets all the conditions of the rule.
+    ///
+    /// # Arguments
+    /// * `account` -- The account of the transaction, the conditions on it never match without.
+    pub fn matches(&self, transaction: &Transaction, account: Option<&Account>) -> bool {
+        let conditions = &self.conditions;
+
+        conditions
+            .description
+            .as_ref()
+            .is_none_or(|pattern| matches_pattern(pattern, transaction.description()))
+            && conditions
+                .message
+                .as_ref()
+                .is_none_or(|pattern| matches_pattern(pattern, transaction.message()))
+            && conditions
+                .reference_number
+                .as_ref()
+                .is_none_or(|pattern| matches_pattern(pattern, transaction.reference_number()))
+            && conditions
+                .min_amount
+                .as_ref()
+                .is_none_or(|min_amount| transaction.amount() >= min_amount)
+            && conditions
+                .max_amount
+                .as_ref()
+                .is_none_or(|max_amount| transaction.amount() <= max_amount)
+            && conditions
+                .account_id
+                .as_ref()
+                .is_none_or(|account_id| transaction.account_id() == Some(account_id))
+            && conditions.institution_id.as_ref().is_none_or(|expected| {
+                account.and_then(|account| account.institution_id()) == Some(expected)
+            })
+            && conditions.account_type.as_ref().is_none_or(|expected| {
+                account.map(|account| account.account_type()) == Some(expected)
+            })
+            && conditions
+                .transaction_type
+                .as_ref()
+                .is_none_or(|transaction_type| transaction.transaction_type() == transaction_type)
+    }
+
+    pub fn set_id(&mut self, id: Option<RuleId>) {
+        self.id = id;
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn set_conditions(&mut self, conditions: RuleConditions) {
+        self.conditions = conditions;
+    }
+
+    pub fn set_actions(&mut self, actions: RuleActions) {
+        self.actions = actions;
+    }
+}
+
+#[cfg(test)]
+impl Rule {
+    pub fn set_priority(&mut self, priority: u32) {
+        self.priority = priority;
+    }
+}
@@ -0,0 +1,303 @@
+use crate::app::entities::account::{AccountId, OPENING_BALANCE_DESCRIPTION};
+use crate::app::entities::interest::INTEREST_DESCRIPTION;
+use crate::app::entities::transaction::{Transaction, TransactionType};
+use crate::app::typing::amount::Amount;
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Where the payment of a statement stands.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DueStatus {
+    /// The due date is not over, and the minimum payment is not paid yet.
+    Upcoming,
+
+    /// At least the minimum payment was paid by the due date, or nothing is owed.
+    Paid,
+
+    /// The due date is over, and the minimum payment was not paid.
+    Overdue,
+}
+
+/// The summary of a statement cycle of a credit account.
+/// The amounts owed are positive, a negative balance is a credit in favour of the account holder.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Statement {
+    /// The first day of the cycle.
+    pub period_start: NaiveDate,
+
+    /// The last day of the cycle.
+    pub closing_date: NaiveDate,
+
+    /// The last day to pay the statement.
+    pub due_date: NaiveDate,
+
+    /// The balance owed at the end of the previous cycle.
+    pub opening_balance: f32,
+
+    /// The expenses of the cycle, but the interest.
+    pub charges: f32,
+
+    /// The incomes of the cycle.
+    pub payments: f32,
+
+    pub interest: f32,
+
+    /// The fees of all the transactions of the cycle.
+    pub fees: f32,
+
+    /// The balance owed at the end of the cycle.
+    pub closing_balance: f32,
+
+    pub minimum_payment: f32,
+
+    /// The amount paid after the closing date, by the due date.
+    pub paid: f32,
+
+    pub status: DueStatus,
+}
+
+fn round_to_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// A statement cycle being walked, with the amounts of its transactions added up so far.
+struct Cycle {
+    period_start: NaiveDate,
+    closing_date: NaiveDate,
+    opening_balance: f64,
+    charges: f64,
+    payments: f64,
+    interest: f64,
+    fees: f64,
+}
+
+impl Cycle {
+    fn add(&mut self, transaction: &Transaction) {
+        let amount = transaction.amount().value() as f64;
+        self.fees += transaction.fee().value() as f64;
+        if transaction.transaction_type() == &TransactionType::Income {
+            self.payments += amount;
+        } else if transaction.description().as_deref() == Some(INTEREST_DESCRIPTION) {
+            self.interest += amount;
+        } else {
+            self.charges += amount;
+        }
+    }
+}
+
+/// How the statements of a credit account are cut and paid.
+/// An account has at most one configuration, identified by the account id.
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct StatementConfig {
+    account_id: AccountId,
+
+    /// The day of the month the cycles end on, the last day of the shorter months.
+    closing_day: u32,
+
+    /// The number of days after the closing date to pay the statement.
+    grace_period_days: u32,
+
+    /// The part of the closing balance to pay at least, in percent. (Eg: 5)
+    minimum_payment_rate: f32,
+
+    /// The least minimum payment, the whole closing balance when it is lower.
+    #[schema(value_type = f32)]
+    minimum_payment_floor: Amount,
+}
+
+impl StatementConfig {
+    pub fn new(
+        account_id: AccountId,
+        closing_day: u32,
+        grace_period_days: u32,
+        minimum_payment_rate: f32,
+        minimum_payment_floor: Amount,
+    ) -> Self {
+        Self {
+            account_id,
+            closing_day,
+            grace_period_days,
+            minimum_payment_rate,
+            minimum_payment_floor,
+        }
+    }
+
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn closing_day(&self) -> u32 {
+        self.closing_day
+    }
+
+    pub fn grace_period_days(&self) -> u32 {
+        self.grace_period_days
+    }
+
+    pub fn minimum_payment_rate(&self) -> f32 {
+        self.minimum_payment_rate
+    }
+
+    pub fn minimum_payment_floor(&self) -> &Amount {
+        &self.minimum_payment_floor
+    }
+
+    /// The closing date of the cycle containing the day.
+    pub fn closing_date(&self, day: NaiveDate) -> NaiveDate {
+        let first_of_month = day.with_day(1).unwrap();
+        let closing_in = |first: NaiveDate| {
+            let last = first + Months::new(1) - Days::new(1);
+            first.with_day(self.closing_day.min(last.day())).unwrap()
+        };
+
+        let closing = closing_in(first_of_month);
+        if day <= closing {
+            closing
+        } else {
+            closing_in(first_of_month + Months::new(1))
+        }
+    }
+
+    /// The amount to pay at least on a closing balance.
+    pub fn minimum_payment(&self, closing_balance: f64) -> f64 {
+        if closing_balance <= 0.0 {
+            return 0.0;
+        }
+
+        let proportional = closing_balance * self.minimum_payment_rate as f64 / 100.0;
+        round_to_cents(
+            proportional
+                .max(self.minimum_payment_floor.value() as f64)
+                .min(closing_balance),
+        )
+    }
+
+    /// Cuts the statements of the cycles closed before the day `today`, with their payments up to its end,
+    /// from the confirmed transactions of the account sorted by date.
+    ///
+    /// The cycles start with the one of the first transaction, when nothing was owed.
+    /// The expenses are charges (the interest apart), the incomes are payments,
+    /// and the incomes made after a closing date, by the due date, pay its statement.
+    /// The opening balance is the credit available when the account was opened, it is not a payment.
+    /// The transactions are walked once, a cycle is cut as soon as a transaction comes after its closing date.
+    pub fn statements(
+        &self,
+        transactions: impl IntoIterator<Item = Transaction>,
+        today: NaiveDate,
+    ) -> Vec<Statement> {
+        let mut transactions = transactions
+            .into_iter()
+            .filter(|transaction| {
+                transaction.description().as_deref() != Some(OPENING_BALANCE_DESCRIPTION)
+            })
+            .take_while(|transaction| transaction.date().date_naive() <= today)
+            .peekable();
+        let Some(first) = transactions.peek() else {
+            return vec![];
+        };
+
+        // The statements cut so far, with the amount paid towards each of them.
+        let mut statements: Vec<(Statement, f64)> = vec![];
+        let mut cycle = self.cycle(first.date().date_naive(), 0.0);
+        for transaction in transactions {
+            let day = transaction.date().date_naive();
+            while cycle.closing_date < day {
+                let (statement, next) = self.cut(cycle);
+                statements.push((statement, 0.0));
+                cycle = next;
+            }
+
+            // A payment pays the statements closed before it and still due, the last ones cut.
+            if transaction.transaction_type() == &TransactionType::Income {
+                for (statement, paid) in statements.iter_mut().rev() {
+                    if statement.due_date < day {
+                        break;
+                    }
+                    *paid += transaction.amount().value() as f64;
+                }
+            }
+            cycle.add(&transaction);
+        }
+        while cycle.closing_date < today {
+            let (statement, next) = self.cut(cycle);
+            statements.push((statement, 0.0));
+            cycle = next;
+        }
+
+        statements
+            .into_iter()
+            .map(|(mut statement, paid)| {
+                statement.paid = round_to_cents(paid) as f32;
+                statement.status = if paid >= statement.minimum_payment as f64 - 0.005 {
+                    DueStatus::Paid
+                } else if statement.due_date < today {
+                    DueStatus::Overdue
+                } else {
+                    DueStatus::Upcoming
+                };
+                statement
+            })
+            .collect()
+    }
+
+    /// The cycle starting on the day, with nothing added up yet.
+    fn cycle(&self, period_start: NaiveDate, opening_balance: f64) -> Cycle {
+        Cycle {
+            period_start,
+            closing_date: self.closing_date(period_start),
+            opening_balance,
+            charges: 0.0,
+            payments: 0.0,
+            interest: 0.0,
+            fees: 0.0,
+        }
+    }
+
+    /// The statement of a closed cycle, and the cycle following it.
+    /// Nothing is paid yet, the payments made after the closing date are added by `statements`.
+    fn cut(&self, cycle: Cycle) -> (Statement, Cycle) {
+        let closing_balance = round_to_cents(
+            cycle.opening_balance + cycle.charges + cycle.interest + cycle.fees - cycle.payments,
+        );
+        let statement = Statement {
+            period_start: cycle.period_start,
+            closing_date: cycle.closing_date,
+            due_date: cycle.closing_date + Days::new(self.grace_period_days as u64),
+            opening_balance: cycle.opening_balance as f32,
+            charges: round_to_cents(cycle.charges) as f32,
+            payments: round_to_cents(cycle.payments) as f32,
+            interest: round_to_cents(cycle.interest) as f32,
+            fees: round_to_cents(cycle.fees) as f32,
+            closing_balance: closing_balance as f32,
+            minimum_payment: self.minimum_payment(closing_balance) as f32,
+            paid: 0.0,
+            status: DueStatus::Upcoming,
+        };
+
+        (
+            statement,
+            self.cycle(cycle.closing_date + Days::new(1), closing_balance),
+        )
+    }
+
+    pub fn set_account_id(&mut self, account_id: AccountId) {
+        self.account_id = account_id;
+    }
+}
+
+#[cfg(test)]
+impl StatementConfig {
+    pub fn set_closing_day(&mut self, closing_day: u32) {
+        self.closing_day = closing_day;
+    }
+
+    pub fn set_grace_period_days(&mut self, grace_period_days: u32) {
+        self.grace_period_days = grace_period_days;
+    }
+
+    pub fn set_minimum_payment_rate(&mut self, minimum_payment_rate: f32) {
+        self.minimum_payment_rate = minimum_payment_rate;
+    }
+}
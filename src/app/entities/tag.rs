@@ -0,0 +1,66 @@
+use crate::app::entities::common::entity_id;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+entity_id!(
+    /// Unique identifier of a `Tag`.
+    TagId,
+    "tag"
+);
+
+/// A free-form label attached to transactions and accounts. (Eg: "trip-musanze", "reimbursable")
+#[derive(Debug, PartialEq, Clone, Serialize, ToSchema)]
+pub struct Tag {
+    id: Option<TagId>,
+
+    /// The tag name, unique and normalized (see `Tag::normalize_name`).
+    name: String,
+}
+
+impl Tag {
+    pub fn new(id: Option<TagId>, name: String) -> Self {
+        Self { id, name }
+    }
+
+    /// Normalizes a tag name, so the same tag is not created twice with different spellings:
+    /// the name is trimmed, lower cased and its inner spaces are replaced by dashes.
+    pub fn normalize_name(name: &str) -> String {
+        name.split_whitespace()
+            .collect::<Vec<&str>>()
+            .join("-")
+            .to_lowercase()
+    }
+
+    pub fn id(&self) -> Option<&TagId> {
+        self.id.as_ref()
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn set_id(&mut self, id: Option<TagId>) {
+        self.id = id;
+    }
+}
+
+#[cfg(test)]
+impl Tag {
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+}
+
+/// Replaces the tag `source` by the tag `target` in a list of tags, keeping their order.
+/// The list keeps `target` only once, when it already had it.
+pub fn replace_tag(tags: &[TagId], source: &TagId, target: &TagId) -> Vec<TagId> {
+    let mut replaced: Vec<TagId> = vec![];
+    for tag in tags {
+        let tag = if tag == source { target } else { tag };
+        if !replaced.contains(tag) {
+            replaced.push(tag.clone());
+        }
+    }
+
+    replaced
+}
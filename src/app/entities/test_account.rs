@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod test_account_entity {
     use crate::app::entities::account::{Account, AccountType};
+    use crate::app::entities::institution::InstitutionId;
     use crate::app::typing::amount::Amount;
     use crate::app::typing::currency::Currency;
 
@@ -15,8 +16,8 @@ mod test_account_entity {
         // AND the account type
         let given_account_type = AccountType::Savings;
 
-        // AND the platform name
-        let given_platform_name = String::from("Platform name");
+        // AND the institution it is held at
+        let given_institution_id = InstitutionId::generate();
 
         // AND given some currency
         let given_currency = Currency::RWF;
@@ -26,7 +27,7 @@ mod test_account_entity {
             None,
             given_account_name.clone(),
             given_account_description.clone(),
-            given_platform_name.clone(),
+            Some(given_institution_id.clone()),
             given_account_type,
             Some(given_currency),
         );
@@ -37,6 +38,9 @@ mod test_account_entity {
         // AND the description should be the same as given
         assert_eq!(*account.description(), given_account_description);
 
+        // AND the account references the institution
+        assert_eq!(account.institution_id(), Some(&given_institution_id));
+
         // AND the balance should start from zero.
         assert_eq!(*account.balance(), 0.0.try_into().unwrap());
     }
@@ -48,7 +52,7 @@ mod test_account_entity {
             None,
             String::from("Account name"),
             String::from("Account Description"),
-            String::from("Platform name"),
+            None,
             AccountType::Savings,
             Some(Currency::RWF),
         );
@@ -74,12 +78,12 @@ mod test_account_entity {
         // THEN the description should be the same as the input
         assert_eq!(*account.description(), new_description);
 
-        // WHEN platform name is updated
-        let new_platform_name = String::from("New platform name");
-        account.set_platform(new_platform_name.clone());
+        // WHEN the institution is updated
+        let new_institution_id = InstitutionId::generate();
+        account.set_institution_id(Some(new_institution_id.clone()));
 
-        // THEN the platform name should be the same as the input
-        assert_eq!(account.platform(), new_platform_name);
+        // THEN the account should reference the new institution
+        assert_eq!(account.institution_id(), Some(&new_institution_id));
 
         // WHEN the account type is updated
         let new_account_type = AccountType::Credit;
@@ -103,7 +107,7 @@ mod test_account_entity {
             None,
             String::from("Account name"),
             String::from("Account Description"),
-            String::from("Platform name"),
+            None,
             AccountType::Savings,
             Some(Currency::RWF),
         );
@@ -139,4 +143,32 @@ mod test_account_entity {
             new_balance - withdrawn_amount + deposited_amount
         );
     }
+
+    #[test]
+    fn test_account_type_rules() {
+        // GIVEN the credit and loan accounts
+        // THEN they are debts, the other accounts hold money
+        assert!(AccountType::Credit.is_debt());
+        assert!(AccountType::Loan.is_debt());
+        assert!(!AccountType::Savings.is_debt());
+        assert!(!AccountType::MobileMoney.is_debt());
+
+        // AND cash is held in hand, free of fees and interest
+        assert!(!AccountType::Cash.is_held_at_institution());
+        assert!(!AccountType::Cash.charges_fees());
+        assert!(!AccountType::Cash.accrues_interest());
+
+        // AND the prepaid wallets charge fees, but never accrue interest
+        assert!(AccountType::Wallet.charges_fees());
+        assert!(!AccountType::Wallet.accrues_interest());
+        assert!(AccountType::Investment.accrues_interest());
+
+        // AND the types are stored by their snake case names
+        assert_eq!(String::from(&AccountType::MobileMoney), "mobile_money");
+        assert_eq!(
+            AccountType::try_from("mobile_money").unwrap(),
+            AccountType::MobileMoney
+        );
+        assert!(AccountType::try_from("MobileMoney").is_err());
+    }
 }
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod test_budget_entity {
+    use crate::app::entities::budget::{Budget, BudgetPeriod, BudgetScope};
+    use crate::app::entities::category::CategoryId;
+    use crate::app::typing::currency::Currency;
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn midnight(year: i32, month: u32, day_of_month: u32) -> DateTime<Utc> {
+        day(year, month, day_of_month)
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn get_budget(period: BudgetPeriod, start: NaiveDate) -> Budget {
+        Budget::new(
+            None,
+            String::from("Groceries"),
+            BudgetScope::Category(CategoryId::generate()),
+            period,
+            100000f32.try_into().unwrap(),
+            Currency::RWF,
+            false,
+            start,
+        )
+    }
+
+    #[test]
+    fn test_period_start() {
+        // GIVEN a day in the middle of August
+        let date = day(2026, 8, 19);
+
+        // WHEN the start of its periods is computed
+        // THEN they are aligned on the calendar
+        assert_eq!(BudgetPeriod::Monthly.start_of(date), day(2026, 8, 1));
+        assert_eq!(BudgetPeriod::Quarterly.start_of(date), day(2026, 7, 1));
+        assert_eq!(BudgetPeriod::Yearly.start_of(date), day(2026, 1, 1));
+
+        // AND the next periods start after as many months
+        assert_eq!(
+            BudgetPeriod::Quarterly.next(day(2026, 10, 1)),
+            day(2027, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_periods_until() {
+        // GIVEN a quarterly budget starting in the middle of February
+        let budget = get_budget(BudgetPeriod::Quarterly, day(2026, 2, 14));
+
+        // WHEN its periods are listed up to a day of the third quarter
+        let periods = budget.periods_until(midnight(2026, 8, 19));
+
+        // THEN they start with the quarter containing the start, and end with the one containing the day
+        assert_eq!(
+            periods,
+            vec![
+                (midnight(2026, 1, 1), midnight(2026, 4, 1)),
+                (midnight(2026, 4, 1), midnight(2026, 7, 1)),
+                (midnight(2026, 7, 1), midnight(2026, 10, 1)),
+            ]
+        );
+
+        // AND there is none before the first period
+        assert!(budget.periods_until(midnight(2025, 12, 31)).is_empty());
+    }
+}
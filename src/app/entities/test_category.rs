@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod test_category_entity {
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::transaction::TransactionType;
+
+    #[test]
+    fn test_new_category() {
+        // GIVEN a parent category id
+        let given_parent_id = CategoryId::generate();
+
+        // WHEN a sub category is created
+        let category = Category::new(
+            None,
+            String::from("Groceries"),
+            Some(given_parent_id.clone()),
+            TransactionType::Expense,
+            Some(String::from("#4caf50")),
+            None,
+        );
+
+        // THEN it has no id yet
+        assert_eq!(category.id(), None);
+
+        // AND it references its parent
+        assert_eq!(category.parent_id(), Some(&given_parent_id));
+
+        // AND the other fields are the same as the input
+        assert_eq!(category.name(), "Groceries");
+        assert_eq!(category.kind(), &TransactionType::Expense);
+        assert_eq!(category.color(), &Some(String::from("#4caf50")));
+        assert_eq!(category.icon(), &None);
+    }
+
+    #[test]
+    fn test_setters_and_getters() {
+        // GIVEN a top level category
+        let mut category = Category::new(
+            None,
+            String::from("Salary"),
+            None,
+            TransactionType::Income,
+            None,
+            None,
+        );
+
+        // WHEN its fields are updated
+        let id = CategoryId::generate();
+        let parent_id = CategoryId::generate();
+        category.set_id(Some(id.clone()));
+        category.set_name(String::from("Bonus"));
+        category.set_parent_id(Some(parent_id.clone()));
+        category.set_kind(TransactionType::Expense);
+        category.set_color(Some(String::from("#ffffff")));
+        category.set_icon(Some(String::from("gift")));
+
+        // THEN the getters return the new values
+        assert_eq!(category.id(), Some(&id));
+        assert_eq!(category.name(), "Bonus");
+        assert_eq!(category.parent_id(), Some(&parent_id));
+        assert_eq!(category.kind(), &TransactionType::Expense);
+        assert_eq!(category.color(), &Some(String::from("#ffffff")));
+        assert_eq!(category.icon(), &Some(String::from("gift")));
+    }
+}
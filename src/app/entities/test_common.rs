@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod test_entity_id {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::common::EntityIdParseError;
+    use crate::app::entities::transaction::TransactionId;
+
+    #[test]
+    fn test_parse_generated_id() {
+        // GIVEN a generated account id
+        let account_id = AccountId::generate();
+
+        // WHEN its text representation is parsed back
+        let parsed_id: AccountId = account_id.to_string().parse().unwrap();
+
+        // THEN it should be the same id
+        assert_eq!(parsed_id, account_id);
+    }
+
+    #[test]
+    fn test_parse_malformed_id() {
+        // GIVEN a malformed id
+        let given_id = "abc";
+
+        // WHEN it is parsed as a transaction id
+        let result = given_id.parse::<TransactionId>();
+
+        // THEN it should fail, naming the entity
+        assert_eq!(
+            result.err().unwrap(),
+            EntityIdParseError::Invalid {
+                entity: "transaction",
+                value: given_id.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_generated_ids_are_ordered() {
+        // GIVEN some ids generated one after the other
+        let ids: Vec<AccountId> = (0..100).map(|_| AccountId::generate()).collect();
+
+        // THEN they should be sorted in their creation order
+        let mut sorted_ids = ids.clone();
+        sorted_ids.sort();
+        assert_eq!(ids, sorted_ids);
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod test_goal_entity {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::goal::Goal;
+    use crate::app::typing::currency::Currency;
+    use chrono::NaiveDate;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_contributions_left() {
+        // GIVEN a goal to reach by the end of the year
+        let goal = Goal::new(
+            None,
+            String::from("Emergency fund"),
+            500000f32.try_into().unwrap(),
+            Currency::RWF,
+            day(2026, 12, 31),
+            vec![AccountId::generate()],
+        );
+
+        // WHEN the contributions left are counted
+        // THEN there is one per month up to the target date, the current one included
+        assert_eq!(goal.contributions_left(day(2026, 10, 19)), 3);
+        assert_eq!(goal.contributions_left(day(2026, 12, 31)), 1);
+        assert_eq!(goal.contributions_left(day(2026, 1, 31)), 12);
+
+        // AND none once the target date passed
+        assert_eq!(goal.contributions_left(day(2027, 1, 1)), 0);
+    }
+}
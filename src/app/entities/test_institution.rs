@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod test_institution_entity {
+    use crate::app::entities::institution::{FeeTier, Institution, InstitutionKind};
+    use crate::app::entities::transaction::TransactionType;
+
+    fn get_tier(min_amount: f32, max_amount: Option<f32>, flat_fee: f32, rate: f32) -> FeeTier {
+        FeeTier {
+            transaction_type: TransactionType::Expense,
+            min_amount: min_amount.try_into().unwrap(),
+            max_amount: max_amount.map(|max_amount| max_amount.try_into().unwrap()),
+            flat_fee: flat_fee.try_into().unwrap(),
+            rate,
+        }
+    }
+
+    #[test]
+    fn test_fee_for() {
+        // GIVEN a mobile money operator charging 100 up to 10000, then 200 and 0.5% above
+        let institution = Institution::new(
+            None,
+            String::from("MTN Mobile Money"),
+            InstitutionKind::MobileMoneyOperator,
+            String::from("RW"),
+            None,
+            vec![
+                get_tier(0.0, Some(10000.0), 100.0, 0.0),
+                get_tier(10000.0, None, 200.0, 0.5),
+            ],
+        );
+
+        // WHEN the fees of the expenses are computed
+        // THEN the first tier applying to the amount is used, its bounds included
+        assert_eq!(
+            institution.fee_for(&TransactionType::Expense, &5000f32.try_into().unwrap()),
+            100.0
+        );
+        assert_eq!(
+            institution.fee_for(&TransactionType::Expense, &10000f32.try_into().unwrap()),
+            100.0
+        );
+        assert_eq!(
+            institution.fee_for(&TransactionType::Expense, &20001f32.try_into().unwrap()),
+            300.01
+        );
+
+        // AND nothing is charged when no tier applies
+        assert_eq!(
+            institution.fee_for(&TransactionType::Income, &5000f32.try_into().unwrap()),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod test_interest_entity {
+    use crate::app::entities::account::{AccountId, AccountType, OPENING_BALANCE_DESCRIPTION};
+    use crate::app::entities::interest::{
+        BalanceHistory, Compounding, DayCount, InterestConfig, InterestDirection, InterestPosting,
+        PostingSchedule,
+    };
+    use crate::app::entities::transaction::TransactionType;
+    use crate::shared::test_utilities::get_random_transaction;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn get_config(compounding: Compounding, posting: PostingSchedule) -> InterestConfig {
+        InterestConfig::new(
+            AccountId::generate(),
+            12.0,
+            DayCount::Actual365,
+            compounding,
+            posting,
+            day(2026, 10, 1),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_calendar() {
+        // GIVEN the 30/360 day count
+        // WHEN the fraction of a year of a day is computed
+        // THEN every month counts for 30 days
+        let fraction = |date| DayCount::Thirty360.year_fraction(date) * 360.0;
+        assert_eq!(fraction(day(2026, 10, 15)), 1.0);
+        assert_eq!(fraction(day(2026, 10, 31)), 0.0);
+        assert_eq!(fraction(day(2026, 2, 28)), 3.0);
+        assert_eq!(fraction(day(2028, 2, 29)), 2.0);
+
+        // AND the posting periods end with the calendar
+        assert_eq!(
+            PostingSchedule::Monthly.end_of(day(2026, 2, 10)),
+            day(2026, 2, 28)
+        );
+        assert_eq!(
+            PostingSchedule::Quarterly.end_of(day(2026, 11, 10)),
+            day(2026, 12, 31)
+        );
+        assert_eq!(
+            PostingSchedule::Yearly.end_of(day(2026, 2, 10)),
+            day(2026, 12, 31)
+        );
+
+        // AND credit accounts are charged the interest, as expenses
+        assert_eq!(
+            InterestDirection::of(&AccountType::Credit).transaction_type(),
+            TransactionType::Expense
+        );
+        assert_eq!(
+            InterestDirection::of(&AccountType::Savings).transaction_type(),
+            TransactionType::Income
+        );
+    }
+
+    #[test]
+    fn test_balance_history() {
+        // GIVEN a deposit of 1000 on the 5th, then two withdrawals of 100 on the 10th
+        let transaction = |date: NaiveDate, opening: f32, closing: f32| {
+            let mut transaction = get_random_transaction();
+            transaction.set_date(Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap()));
+            transaction.set_opening_balance(Some(opening.try_into().unwrap()));
+            transaction.set_closing_balance(Some(closing.try_into().unwrap()));
+            transaction
+        };
+        let history = BalanceHistory::from_transactions(
+            vec![
+                transaction(day(2026, 10, 5), 0.0, 1000.0),
+                transaction(day(2026, 10, 10), 1000.0, 900.0),
+                transaction(day(2026, 10, 10), 900.0, 800.0),
+            ],
+            800.0,
+        );
+
+        // WHEN the balance is read at the end of some days
+        // THEN it is the closing balance of the last transaction made by then
+        assert_eq!(history.at(day(2026, 10, 4)), 0.0);
+        assert_eq!(history.at(day(2026, 10, 5)), 1000.0);
+        assert_eq!(history.at(day(2026, 10, 10)), 800.0);
+        assert_eq!(history.at(day(2027, 1, 1)), 800.0);
+
+        // AND without transactions, it is the current balance
+        let history = BalanceHistory::from_transactions(vec![], 500.0);
+        assert_eq!(history.at(day(2026, 10, 4)), 500.0);
+    }
+
+    #[test]
+    fn test_owed_history() {
+        // GIVEN a card opened with 2000 of credit on the 1st, a purchase of 300 with a fee of 5 on the 5th,
+        // then a payment of 100 on the 10th
+        let transaction = |date: NaiveDate,
+                           transaction_type: TransactionType,
+                           amount: f32,
+                           fee: f32,
+                           description: &str| {
+            let mut transaction = get_random_transaction();
+            transaction.set_date(Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap()));
+            transaction.set_transaction_type(transaction_type);
+            transaction.set_amount(amount.try_into().unwrap());
+            transaction.set_fee(fee.try_into().unwrap());
+            transaction.set_description(Some(String::from(description)));
+            transaction
+        };
+        let history = BalanceHistory::owed_from_transactions(vec![
+            transaction(
+                day(2026, 10, 1),
+                TransactionType::Income,
+                2000.0,
+                0.0,
+                OPENING_BALANCE_DESCRIPTION,
+            ),
+            transaction(
+                day(2026, 10, 5),
+                TransactionType::Expense,
+                300.0,
+                5.0,
+                "Shop",
+            ),
+            transaction(
+                day(2026, 10, 10),
+                TransactionType::Income,
+                100.0,
+                0.0,
+                "Payment",
+            ),
+        ]);
+
+        // WHEN the amount owed is read at the end of some days
+        // THEN the charges and their fees raise it, the payments lower it, and the opening credit is not a payment
+        assert_eq!(history.at(day(2026, 10, 1)), 0.0);
+        assert_eq!(history.at(day(2026, 10, 5)), 305.0);
+        assert_eq!(history.at(day(2026, 10, 10)), 205.0);
+    }
+
+    #[test]
+    fn test_accrue() {
+        // GIVEN a balance of 100000 earning 12% a year, posted every month
+        let history = BalanceHistory::from_transactions(vec![], 100000.0);
+        let config = get_config(Compounding::Simple, PostingSchedule::Monthly);
+
+        // WHEN the interest is accrued up to the middle of November
+        let accrual = config.accrue(&history, day(2026, 11, 15));
+
+        // THEN the interest of the 31 days of October is posted at their end
+        assert_eq!(
+            accrual.postings,
+            vec![InterestPosting {
+                date: day(2026, 10, 31),
+                amount: 1019.18
+            }]
+        );
+
+        // AND the interest of November is computed on the balance increased by that of October
+        assert_eq!(accrual.accrued, 498.18);
+
+        // WHEN it is compounded daily, and posted every quarter
+        let config = get_config(Compounding::Daily, PostingSchedule::Quarterly);
+        let accrual = config.accrue(&history, day(2026, 10, 31));
+
+        // THEN nothing is posted before the end of the quarter, and the interest earns interest
+        assert_eq!(accrual.postings, vec![]);
+        assert_eq!(accrual.accrued, 1024.22);
+
+        // WHEN the interest up to the end of October is posted
+        let mut config = get_config(Compounding::Simple, PostingSchedule::Monthly);
+        config.set_posted_until(Some(day(2026, 10, 31)));
+
+        // THEN the accrual starts in November
+        assert_eq!(config.accrual_start(), day(2026, 11, 1));
+        assert!(!config.is_due(day(2026, 11, 29)));
+        assert!(config.is_due(day(2026, 11, 30)));
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod test_loan_entity {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::loan::{Instalment, Loan, PaymentFrequency};
+    use chrono::NaiveDate;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn get_loan(annual_rate: f32, frequency: PaymentFrequency) -> Loan {
+        Loan::new(
+            None,
+            String::from("Car loan"),
+            AccountId::generate(),
+            100000f32.try_into().unwrap(),
+            annual_rate,
+            12,
+            frequency,
+            day(2026, 1, 31),
+        )
+    }
+
+    #[test]
+    fn test_schedule() {
+        // GIVEN a loan of 100000 at 12% a year, repaid in 12 months from the end of January
+        let loan = get_loan(12.0, PaymentFrequency::Monthly);
+
+        // WHEN its schedule is generated
+        let schedule = loan.schedule();
+
+        // THEN the instalments are level, the interest of the month first
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(
+            schedule[0],
+            Instalment {
+                number: 1,
+                due_date: day(2026, 2, 28),
+                payment: 8884.88,
+                principal: 7884.88,
+                interest: 1000.0,
+                balance_after: 92115.12,
+            }
+        );
+        assert_eq!(schedule[1].due_date, day(2026, 3, 31));
+        assert_eq!(schedule[1].interest, 921.15);
+
+        // AND the last one repays the principal left
+        assert_eq!(
+            schedule[11],
+            Instalment {
+                number: 12,
+                due_date: day(2027, 1, 31),
+                payment: 8884.85,
+                principal: 8796.88,
+                interest: 87.97,
+                balance_after: 0.0,
+            }
+        );
+
+        // AND without interest, the principal is split evenly
+        let schedule = get_loan(0.0, PaymentFrequency::Biweekly).schedule();
+        assert_eq!(schedule[0].payment, 8333.33);
+        assert_eq!(schedule[0].due_date, day(2026, 2, 14));
+        assert_eq!(schedule[11].payment, 8333.37);
+    }
+}
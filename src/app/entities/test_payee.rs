@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod test_payee_entity {
+    use crate::app::entities::payee::{normalize_text, Payee};
+
+    fn get_payee() -> Payee {
+        Payee::new(
+            None,
+            String::from("Simba Supermarket"),
+            vec![String::from("SIMBA SPMKT")],
+            vec![String::from(r"simba\s*super\w*")],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        // GIVEN texts spelled in different ways
+        // WHEN they are normalized
+        // THEN they are lower cased, without punctuation and with single spaces
+        assert_eq!(
+            normalize_text("  SIMBA-Supermarket,  Kigali! "),
+            "simba supermarket kigali"
+        );
+        assert_eq!(normalize_text("MTN*MoMo"), "mtn momo");
+        assert_eq!(normalize_text(" .. "), "");
+    }
+
+    #[test]
+    fn test_matches_name_and_aliases() {
+        // GIVEN a payee with an alias
+        let payee = Payee::new(
+            None,
+            String::from("Simba Supermarket"),
+            vec![String::from("SIMBA SPMKT")],
+            vec![],
+            None,
+        );
+
+        // WHEN texts mention its name or its alias, whatever the case and the punctuation
+        // THEN they match
+        assert!(payee.matches("Payment to SIMBA SUPERMARKET LTD"));
+        assert!(payee.matches("POS simba-spmkt/kigali"));
+
+        // AND a text with only part of a word does not match
+        assert!(!payee.matches("Simbaz Supermarkets"));
+        assert!(!payee.matches("Payment to Nakumatt"));
+    }
+
+    #[test]
+    fn test_matches_patterns() {
+        // GIVEN a payee with a pattern
+        let payee = get_payee();
+
+        // WHEN a text matches the pattern, whatever the case
+        // THEN it matches
+        assert!(payee.matches("POS SIMBASUPERMKT 0045"));
+
+        // AND an invalid pattern never matches
+        let mut payee = get_payee();
+        payee.set_name(String::from("Other"));
+        payee.set_aliases(vec![]);
+        payee.set_patterns(vec![String::from("(unclosed")]);
+        assert!(!payee.matches("(unclosed"));
+    }
+}
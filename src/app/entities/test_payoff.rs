@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests_payoff {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::payoff::{Debt, DebtPayment, PayoffPlan, PayoffStrategy};
+    use chrono::NaiveDate;
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn get_debt(name: &str, balance: f32, annual_rate: f32, minimum_payment: f32) -> Debt {
+        Debt {
+            account_id: AccountId::generate(),
+            name: String::from(name),
+            balance,
+            annual_rate,
+            minimum_payment,
+        }
+    }
+
+    #[test]
+    fn test_simulate() {
+        // GIVEN a small debt at a low rate, and a large one at a high rate, paid off with 500 a month
+        let small = get_debt("Phone", 1000.0, 6.0, 50.0);
+        let large = get_debt("Card", 3000.0, 24.0, 100.0);
+        let debts = vec![large.clone(), small.clone()];
+
+        // WHEN the snowball strategy is simulated
+        let snowball = PayoffPlan::simulate(
+            debts.clone(),
+            PayoffStrategy::Snowball,
+            500.0,
+            day(2026, 1, 15),
+        )
+        .unwrap();
+
+        // THEN the small debt is paid off first
+        assert_eq!(
+            snowball.order,
+            vec![small.account_id.clone(), large.account_id.clone()]
+        );
+        assert_eq!(snowball.debts[0].account_id, small.account_id);
+
+        // AND the first month charges the interest, pays the minimums, and puts the rest on the small debt
+        let first = &snowball.months[0];
+        assert_eq!(first.date, day(2026, 2, 15));
+        assert_eq!(
+            first.payments,
+            vec![
+                DebtPayment {
+                    account_id: small.account_id.clone(),
+                    interest: 5.0,
+                    payment: 400.0,
+                    balance_after: 605.0,
+                },
+                DebtPayment {
+                    account_id: large.account_id.clone(),
+                    interest: 60.0,
+                    payment: 100.0,
+                    balance_after: 2960.0,
+                },
+            ]
+        );
+        assert_eq!(first.total_payment, 500.0);
+        assert_eq!(first.total_balance, 3565.0);
+
+        // AND everything is paid off, the last month paying what is left
+        let last = snowball.months.last().unwrap();
+        assert_eq!(last.total_balance, 0.0);
+        assert!(last.total_payment <= 500.0);
+        assert_eq!(snowball.payoff_date, last.date);
+        assert_eq!(
+            snowball.total_paid,
+            ((4000.0 + snowball.total_interest) * 100.0).round() / 100.0
+        );
+
+        // WHEN the avalanche strategy is simulated
+        let avalanche = PayoffPlan::simulate(
+            debts.clone(),
+            PayoffStrategy::Avalanche,
+            500.0,
+            day(2026, 1, 15),
+        )
+        .unwrap();
+
+        // THEN the debt at the high rate goes first, and less interest is paid
+        assert_eq!(
+            avalanche.order,
+            vec![large.account_id.clone(), small.account_id.clone()]
+        );
+        assert!(avalanche.total_interest < snowball.total_interest);
+
+        // AND a custom order keeps the order of the debts
+        let custom =
+            PayoffPlan::simulate(debts, PayoffStrategy::Custom, 500.0, day(2026, 1, 15)).unwrap();
+        assert_eq!(
+            custom.order,
+            vec![large.account_id.clone(), small.account_id.clone()]
+        );
+    }
+
+    #[test]
+    fn test_simulate_never_paid_off() {
+        // GIVEN a debt whose interest takes the whole budget
+        let debts = vec![get_debt("Card", 3000.0, 24.0, 0.0)];
+
+        // WHEN its payoff is simulated
+        // THEN it is never paid off
+        assert_eq!(
+            PayoffPlan::simulate(debts, PayoffStrategy::Avalanche, 60.0, day(2026, 1, 15)),
+            None
+        );
+    }
+}
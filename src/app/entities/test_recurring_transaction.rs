@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod test_recurring_transaction_entity {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::recurring_transaction::{
+        BusinessDayConvention, Frequency, RecurringTransaction, Schedule, TransactionTemplate,
+    };
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::typing::currency::Currency;
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    fn date(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("{value}T08:00:00Z"))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn get_schedule(frequency: Frequency, start: &str) -> Schedule {
+        Schedule {
+            frequency,
+            interval: 1,
+            start: date(start),
+            end: None,
+            count: None,
+            business_day: BusinessDayConvention::Unadjusted,
+        }
+    }
+
+    fn get_template() -> TransactionTemplate {
+        TransactionTemplate {
+            account_id: AccountId::generate(),
+            transaction_type: TransactionType::Expense,
+            amount: 300000f32.try_into().unwrap(),
+            fee: 0f32.try_into().unwrap(),
+            currency: Currency::RWF,
+            description: Some(String::from("Rent")),
+            message: None,
+            category_id: None,
+            payee_id: None,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn test_monthly_occurrences() {
+        // GIVEN a monthly schedule starting at the end of January
+        let mut schedule = get_schedule(Frequency::Monthly, "2026-01-31");
+        schedule.count = Some(4);
+
+        // WHEN its occurrences are listed
+        let occurrences: Vec<_> = schedule.occurrences().collect();
+
+        // THEN they fall on the last day of the shorter months, up to the count
+        assert_eq!(
+            occurrences,
+            vec![
+                date("2026-01-31"),
+                date("2026-02-28"),
+                date("2026-03-31"),
+                date("2026-04-30"),
+            ]
+        );
+
+        // WHEN the weekend occurrences are moved to the next business day
+        schedule.business_day = BusinessDayConvention::Following;
+        let occurrences: Vec<_> = schedule.occurrences().collect();
+
+        // THEN the Saturdays move to the Mondays
+        assert_eq!(
+            occurrences,
+            vec![
+                date("2026-02-02"),
+                date("2026-03-02"),
+                date("2026-03-31"),
+                date("2026-04-30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_daily_and_weekly_occurrences() {
+        // GIVEN a daily schedule from a Friday to the next Tuesday, on business days
+        let mut schedule = get_schedule(Frequency::Daily, "2026-10-02");
+        schedule.end = Some(date("2026-10-06"));
+        schedule.business_day = BusinessDayConvention::Following;
+
+        // WHEN its occurrences are listed
+        // THEN the weekend is merged into the Monday
+        let expected = vec![date("2026-10-02"), date("2026-10-05"), date("2026-10-06")];
+        assert_eq!(schedule.occurrences().collect::<Vec<_>>(), expected);
+
+        // AND into the Friday, when moved backwards
+        schedule.business_day = BusinessDayConvention::Preceding;
+        assert_eq!(schedule.occurrences().collect::<Vec<_>>(), expected);
+
+        // GIVEN a schedule every other week, ending on an occurrence
+        let mut schedule = get_schedule(Frequency::Weekly, "2026-10-02");
+        schedule.interval = 2;
+        schedule.end = Some(date("2026-10-30"));
+
+        // WHEN its occurrences are listed
+        // THEN the end is included
+        assert_eq!(
+            schedule.occurrences().collect::<Vec<_>>(),
+            vec![date("2026-10-02"), date("2026-10-16"), date("2026-10-30")]
+        );
+    }
+
+    #[test]
+    fn test_upcoming_occurrences() {
+        // GIVEN a yearly recurring transaction, one occurrence created and the third one skipped
+        let mut recurring_transaction = RecurringTransaction::new(
+            None,
+            String::from("Insurance"),
+            get_template(),
+            get_schedule(Frequency::Yearly, "2026-03-15"),
+        );
+        recurring_transaction.set_skipped_dates(vec![
+            NaiveDate::from_ymd_opt(2026, 3, 15).unwrap(),
+            NaiveDate::from_ymd_opt(2028, 3, 15).unwrap(),
+        ]);
+        recurring_transaction.set_last_occurrence(Some(date("2026-03-15")));
+
+        // WHEN its next occurrences are listed
+        // THEN they start after the last one, and the upcoming ones leave out the skipped one
+        let next: Vec<_> = recurring_transaction.next_occurrences().take(2).collect();
+        assert_eq!(next, vec![date("2027-03-15"), date("2028-03-15")]);
+        let upcoming: Vec<_> = recurring_transaction
+            .upcoming_occurrences()
+            .take(2)
+            .collect();
+        assert_eq!(upcoming, vec![date("2027-03-15"), date("2029-03-15")]);
+
+        // AND the skipped days already passed are forgotten
+        assert_eq!(
+            recurring_transaction.skipped_dates(),
+            &vec![NaiveDate::from_ymd_opt(2028, 3, 15).unwrap()]
+        );
+
+        // WHEN the transaction of an occurrence is built
+        let transaction = recurring_transaction
+            .template()
+            .to_transaction(date("2027-03-15"));
+
+        // THEN it is pending, at the date of the occurrence
+        assert_eq!(transaction.status(), &TransactionStatus::Pending);
+        assert_eq!(transaction.date(), date("2027-03-15"));
+        assert_eq!(transaction.description(), &Some(String::from("Rent")));
+    }
+}
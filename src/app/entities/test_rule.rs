@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod test_rule_entity {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::institution::InstitutionId;
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions};
+    use crate::app::entities::transaction::{AccountRef, Transaction, TransactionType};
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+
+    fn get_rule(conditions: RuleConditions) -> Rule {
+        Rule::new(
+            None,
+            String::from("Groceries"),
+            0,
+            conditions,
+            RuleActions::default(),
+        )
+    }
+
+    fn get_transaction(account_id: &AccountId) -> Transaction {
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id.clone()));
+        transaction.set_transaction_type(TransactionType::Expense);
+        transaction.set_amount(25f32.try_into().unwrap());
+        transaction.set_description(Some(String::from("POS 1234 SIMBA Kimihurura")));
+        transaction.set_message(None);
+        transaction.set_reference_number(Some(String::from("FT2025-001")));
+        transaction
+    }
+
+    #[test]
+    fn test_matches_text() {
+        // GIVEN a transaction
+        let account_id = AccountId::generate();
+        let transaction = get_transaction(&account_id);
+
+        // WHEN rules have patterns on its texts
+        // THEN the patterns are matched ignoring the case
+        let rule = get_rule(RuleConditions {
+            description: Some(String::from(r"pos \d+ simba")),
+            reference_number: Some(String::from("^FT")),
+            ..Default::default()
+        });
+        assert!(rule.matches(&transaction, None));
+
+        // AND a pattern on a missing text never matches
+        let rule = get_rule(RuleConditions {
+            message: Some(String::from(".*")),
+            ..Default::default()
+        });
+        assert!(!rule.matches(&transaction, None));
+
+        // AND an invalid pattern never matches
+        let rule = get_rule(RuleConditions {
+            description: Some(String::from("simba(")),
+            ..Default::default()
+        });
+        assert!(!rule.matches(&transaction, None));
+    }
+
+    #[test]
+    fn test_matches_fields() {
+        // GIVEN an expense of 25 on a mobile money account held at MTN
+        let account_id = AccountId::generate();
+        let mtn_id = InstitutionId::generate();
+        let mut account = get_random_account();
+        account.set_id(Some(account_id.clone()));
+        account.set_institution_id(Some(mtn_id.clone()));
+        account.set_account_type(AccountType::MobileMoney);
+        let transaction = get_transaction(&account_id);
+
+        // WHEN a rule has conditions met by the transaction
+        let conditions = RuleConditions {
+            min_amount: Some(25f32.try_into().unwrap()),
+            max_amount: Some(30f32.try_into().unwrap()),
+            account_id: Some(account_id.clone()),
+            institution_id: Some(mtn_id.clone()),
+            account_type: Some(AccountType::MobileMoney),
+            transaction_type: Some(TransactionType::Expense),
+            ..Default::default()
+        };
+
+        // THEN the rule matches
+        assert!(get_rule(conditions.clone()).matches(&transaction, Some(&account)));
+
+        // AND a rule without conditions matches everything
+        assert!(get_rule(RuleConditions::default()).matches(&transaction, Some(&account)));
+        assert!(get_rule(RuleConditions::default()).matches(&transaction, None));
+
+        // WHEN a single condition is not met
+        // THEN the rule does not match
+        let unmet = [
+            RuleConditions {
+                min_amount: Some(26f32.try_into().unwrap()),
+                ..conditions.clone()
+            },
+            RuleConditions {
+                max_amount: Some(24f32.try_into().unwrap()),
+                ..conditions.clone()
+            },
+            RuleConditions {
+                account_id: Some(AccountId::generate()),
+                ..conditions.clone()
+            },
+            RuleConditions {
+                institution_id: Some(InstitutionId::generate()),
+                ..conditions.clone()
+            },
+            RuleConditions {
+                account_type: Some(AccountType::Cash),
+                ..conditions.clone()
+            },
+            RuleConditions {
+                transaction_type: Some(TransactionType::Income),
+                ..conditions.clone()
+            },
+        ];
+        for conditions in unmet {
+            assert!(!get_rule(conditions).matches(&transaction, Some(&account)));
+        }
+
+        // AND a rule on an institution does not match the accounts held at none
+        let held_at_none = RuleConditions {
+            institution_id: Some(mtn_id),
+            ..Default::default()
+        };
+        account.set_institution_id(None);
+        assert!(!get_rule(held_at_none.clone()).matches(&transaction, Some(&account)));
+
+        // AND a rule on the account does not match without it
+        assert!(!get_rule(held_at_none).matches(&transaction, None));
+        let on_type = RuleConditions {
+            account_type: Some(AccountType::MobileMoney),
+            ..Default::default()
+        };
+        assert!(get_rule(on_type.clone()).matches(&transaction, Some(&account)));
+        assert!(!get_rule(on_type).matches(&transaction, None));
+    }
+}
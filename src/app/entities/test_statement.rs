@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests_statement {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::statement::{DueStatus, StatementConfig};
+    use crate::app::entities::transaction::{
+        AccountRef, Transaction, TransactionStatus, TransactionType,
+    };
+    use crate::app::typing::currency::Currency;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Cycles closing on the 25th, paid 20 days later, with a minimum of 5% and at least 1000.
+    fn get_config(account_id: &AccountId) -> StatementConfig {
+        StatementConfig::new(account_id.clone(), 25, 20, 5.0, 1000f32.try_into().unwrap())
+    }
+
+    fn get_transaction(
+        account_id: &AccountId,
+        transaction_type: TransactionType,
+        amount: f32,
+        fee: f32,
+        date: NaiveDate,
+        description: Option<&str>,
+    ) -> Transaction {
+        Transaction::new(
+            None,
+            AccountRef::Id(account_id.clone()),
+            transaction_type,
+            amount.try_into().unwrap(),
+            fee.try_into().unwrap(),
+            None,
+            None,
+            Currency::RWF,
+            TransactionStatus::Confirmed,
+            Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap()),
+            description.map(String::from),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_closing_date_and_minimum_payment() {
+        // GIVEN cycles closing on the 31st
+        let mut config = get_config(&AccountId::generate());
+        config.set_closing_day(31);
+
+        // WHEN the closing dates are computed
+        // THEN the cycles of the shorter months close on their last day
+        assert_eq!(config.closing_date(day(2026, 2, 10)), day(2026, 2, 28));
+        assert_eq!(config.closing_date(day(2026, 3, 1)), day(2026, 3, 31));
+
+        // AND the days after the closing day belong to the cycle of the next month
+        config.set_closing_day(25);
+        assert_eq!(config.closing_date(day(2026, 1, 25)), day(2026, 1, 25));
+        assert_eq!(config.closing_date(day(2026, 1, 26)), day(2026, 2, 25));
+
+        // AND the minimum payment is the proportional part, at least the floor, at most the balance
+        assert_eq!(config.minimum_payment(40000.0), 2000.0);
+        assert_eq!(config.minimum_payment(15400.0), 1000.0);
+        assert_eq!(config.minimum_payment(500.0), 500.0);
+        assert_eq!(config.minimum_payment(-200.0), 0.0);
+    }
+
+    #[test]
+    fn test_statements() {
+        // GIVEN a credit card charged and paid over two cycles
+        let account_id = AccountId::generate();
+        let config = get_config(&account_id);
+        let transactions = vec![
+            get_transaction(
+                &account_id,
+                TransactionType::Expense,
+                20000.0,
+                100.0,
+                day(2026, 1, 5),
+                None,
+            ),
+            get_transaction(
+                &account_id,
+                TransactionType::Income,
+                5000.0,
+                0.0,
+                day(2026, 1, 10),
+                None,
+            ),
+            get_transaction(
+                &account_id,
+                TransactionType::Expense,
+                300.0,
+                0.0,
+                day(2026, 1, 25),
+                Some("Interest"),
+            ),
+            get_transaction(
+                &account_id,
+                TransactionType::Expense,
+                1000.0,
+                0.0,
+                day(2026, 1, 26),
+                None,
+            ),
+            get_transaction(
+                &account_id,
+                TransactionType::Income,
+                2000.0,
+                0.0,
+                day(2026, 2, 10),
+                None,
+            ),
+        ];
+
+        // WHEN the statements are cut on the 1st of March
+        let statements = config.statements(transactions.clone(), day(2026, 3, 1));
+
+        // THEN the first cycle sums up its charges, payments, interest and fees, and was paid in time
+        assert_eq!(statements.len(), 2);
+        let first = &statements[0];
+        assert_eq!(first.period_start, day(2026, 1, 5));
+        assert_eq!(first.closing_date, day(2026, 1, 25));
+        assert_eq!(first.due_date, day(2026, 2, 14));
+        assert_eq!(first.charges, 20000.0);
+        assert_eq!(first.payments, 5000.0);
+        assert_eq!(first.interest, 300.0);
+        assert_eq!(first.fees, 100.0);
+        assert_eq!(first.closing_balance, 15400.0);
+        assert_eq!(first.minimum_payment, 1000.0);
+        assert_eq!(first.paid, 2000.0);
+        assert_eq!(first.status, DueStatus::Paid);
+
+        // AND the second cycle carries its balance over, and is not due yet
+        let second = &statements[1];
+        assert_eq!(second.period_start, day(2026, 1, 26));
+        assert_eq!(second.opening_balance, 15400.0);
+        assert_eq!(second.charges, 1000.0);
+        assert_eq!(second.payments, 2000.0);
+        assert_eq!(second.closing_balance, 14400.0);
+        assert_eq!(second.due_date, day(2026, 3, 17));
+        assert_eq!(second.paid, 0.0);
+        assert_eq!(second.status, DueStatus::Upcoming);
+
+        // WHEN the card was opened with some credit available
+        let mut opened = vec![get_transaction(
+            &account_id,
+            TransactionType::Income,
+            50000.0,
+            0.0,
+            day(2026, 1, 2),
+            Some("Opening balance"),
+        )];
+        opened.extend(transactions.clone());
+
+        // THEN its opening balance is not a payment, the statements are the same
+        assert_eq!(config.statements(opened, day(2026, 3, 1)), statements);
+
+        // WHEN the statements are cut after its due date
+        let statements = config.statements(transactions, day(2026, 3, 20));
+
+        // THEN the second cycle is overdue, and the current cycle is not cut yet
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[1].status, DueStatus::Overdue);
+    }
+
+    #[test]
+    fn test_statements_over_quiet_cycles() {
+        // GIVEN a credit card paid 40 days after the closing dates, charged once and paid once
+        let account_id = AccountId::generate();
+        let mut config = get_config(&account_id);
+        config.set_grace_period_days(40);
+        let transactions = vec![
+            get_transaction(
+                &account_id,
+                TransactionType::Expense,
+                20000.0,
+                0.0,
+                day(2026, 1, 5),
+                None,
+            ),
+            get_transaction(
+                &account_id,
+                TransactionType::Income,
+                1500.0,
+                0.0,
+                day(2026, 3, 1),
+                None,
+            ),
+        ];
+
+        // WHEN the statements are cut on the 1st of April
+        let statements = config.statements(transactions, day(2026, 4, 1));
+
+        // THEN the cycles without transactions are cut too, carrying the balance over
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[1].period_start, day(2026, 1, 26));
+        assert_eq!(statements[1].opening_balance, 20000.0);
+        assert_eq!(statements[1].closing_balance, 20000.0);
+
+        // AND the payment pays both statements it was made after, by their due dates
+        assert_eq!(statements[0].paid, 1500.0);
+        assert_eq!(statements[0].status, DueStatus::Paid);
+        assert_eq!(statements[1].paid, 1500.0);
+        assert_eq!(statements[1].status, DueStatus::Paid);
+
+        // AND it is a payment of the cycle it was made in
+        assert_eq!(statements[2].payments, 1500.0);
+        assert_eq!(statements[2].closing_balance, 18500.0);
+        assert_eq!(statements[2].paid, 0.0);
+        assert_eq!(statements[2].status, DueStatus::Upcoming);
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod test_tag_entity {
+    use crate::app::entities::tag::{replace_tag, Tag, TagId};
+
+    #[test]
+    fn test_normalize_name() {
+        // GIVEN tag names spelled in different ways
+        // WHEN they are normalized
+        // THEN they are the same
+        assert_eq!(Tag::normalize_name("  Trip Musanze "), "trip-musanze");
+        assert_eq!(Tag::normalize_name("trip-musanze"), "trip-musanze");
+        assert_eq!(Tag::normalize_name("REIMBURSABLE"), "reimbursable");
+        assert_eq!(Tag::normalize_name("   "), "");
+    }
+
+    #[test]
+    fn test_setters_and_getters() {
+        // GIVEN a tag
+        let mut tag = Tag::new(None, String::from("reimbursable"));
+        assert_eq!(tag.id(), None);
+        assert_eq!(tag.name(), "reimbursable");
+
+        // WHEN its fields are updated
+        let id = TagId::generate();
+        tag.set_id(Some(id.clone()));
+        tag.set_name(String::from("work"));
+
+        // THEN the getters return the new values
+        assert_eq!(tag.id(), Some(&id));
+        assert_eq!(tag.name(), "work");
+    }
+
+    #[test]
+    fn test_replace_tag() {
+        // GIVEN a list of tags
+        let (first, second, third) = (TagId::generate(), TagId::generate(), TagId::generate());
+        let tags = vec![first.clone(), second.clone()];
+
+        // WHEN a tag is replaced by a new one
+        // THEN it keeps its position
+        assert_eq!(
+            replace_tag(&tags, &first, &third),
+            [third.clone(), second.clone()]
+        );
+
+        // WHEN a tag is replaced by one of the list
+        // THEN the list keeps it once
+        assert_eq!(replace_tag(&tags, &first, &second), vec![second.clone()]);
+
+        // WHEN the replaced tag is not in the list
+        // THEN the list is unchanged
+        assert_eq!(replace_tag(&tags, &third, &first), tags);
+    }
+}
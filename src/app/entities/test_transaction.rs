@@ -1,16 +1,19 @@
 #[cfg(test)]
-mod test_transaction {
+mod test_transaction_entity {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::category::CategoryId;
     use crate::app::entities::transaction::{
-        AccountRef, Transaction, TransactionStatus, TransactionType,
+        AccountRef, Transaction, TransactionSplit, TransactionStatus, TransactionType,
     };
     use crate::app::typing::amount::Amount;
     use crate::app::typing::currency::Currency;
+    use crate::shared::test_utilities::get_random_transaction;
     use chrono::{DateTime, Utc};
 
     #[test]
     fn test_entity() {
         // GIVEN some transaction details,
-        let mut given_account_ref = AccountRef::Id(String::from("1234567890").into());
+        let mut given_account_ref = AccountRef::Id(AccountId::generate());
         let mut given_transaction_type = TransactionType::Expense;
         let mut given_amount: Amount = 100.0f32.try_into().unwrap();
         let mut given_fee: Amount = 10.0f32.try_into().unwrap();
@@ -36,7 +39,7 @@ mod test_transaction {
             given_closing_balance.clone(),
             given_currency.clone(),
             given_status.clone(),
-            given_date.clone(),
+            given_date,
             Some(given_description.clone()),
             Some(given_reference_number.clone()),
             Some(given_transaction_message.clone()),
@@ -62,7 +65,7 @@ mod test_transaction {
         assert_eq!(*transaction.status(), given_status);
 
         // WHEN the transaction details are updated in memory
-        given_account_ref = AccountRef::Id(String::from("updated-1234567890").into());
+        given_account_ref = AccountRef::Id(AccountId::generate());
         given_transaction_type = TransactionType::Income;
         given_amount = 101.0f32.try_into().unwrap();
         given_fee = 11.0f32.try_into().unwrap();
@@ -106,7 +109,7 @@ mod test_transaction {
         transaction.set_closing_balance(given_closing_balance.clone());
         transaction.set_currency(given_currency.clone());
         transaction.set_status(given_status.clone());
-        transaction.set_date(given_date.clone());
+        transaction.set_date(given_date);
         transaction.set_description(Some(given_description.clone()));
         transaction.set_reference_number(Some(given_reference_number.clone()));
         transaction.set_message(Some(given_transaction_message.clone()));
@@ -128,4 +131,40 @@ mod test_transaction {
         );
         assert_eq!(*transaction.message(), Some(given_transaction_message));
     }
+
+    #[test]
+    fn test_category_amounts() {
+        // GIVEN an uncategorized transaction
+        let mut transaction = get_random_transaction();
+
+        // THEN its whole amount has no category
+        let amount = transaction.amount().clone();
+        assert_eq!(transaction.category_amounts(), vec![(None, &amount)]);
+
+        // WHEN it gets a category
+        let category_id = CategoryId::generate();
+        transaction.set_category_id(Some(category_id.clone()));
+
+        // THEN its whole amount is assigned to the category
+        assert_eq!(
+            transaction.category_amounts(),
+            vec![(Some(&category_id), &amount)]
+        );
+
+        // WHEN it is split instead
+        let split_category_id = CategoryId::generate();
+        let split_amount: Amount = 5f32.try_into().unwrap();
+        transaction.set_category_id(None);
+        transaction.set_splits(vec![TransactionSplit {
+            category_id: split_category_id.clone(),
+            amount: split_amount.clone(),
+            memo: None,
+        }]);
+
+        // THEN the amounts of the splits are assigned to their categories
+        assert_eq!(
+            transaction.category_amounts(),
+            vec![(Some(&split_category_id), &split_amount)]
+        );
+    }
 }
@@ -1,16 +1,28 @@
-use crate::app::entities::account::Account;
-use crate::app::entities::common::{EntityId, EntityRef};
+use crate::app::entities::account::{Account, AccountId};
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::common::{entity_id, EntityRef};
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::TagId;
 use crate::app::typing::amount::Amount;
 use crate::app::typing::currency::Currency;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, PartialEq)]
+entity_id!(
+    /// Unique identifier of a `Transaction`.
+    TransactionId,
+    "transaction"
+);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum TransactionType {
     Expense,
     Income,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum TransactionStatus {
     Pending,
     Confirmed,
@@ -18,14 +30,99 @@ pub enum TransactionStatus {
     RolledBack,
 }
 
-pub type AccountRef = EntityRef<Account>;
+#[derive(Debug)]
+pub enum ConversionError {
+    InvalidTransactionType,
+    InvalidTransactionStatus,
+    UnknownRevisionKind,
+}
+
+impl From<&TransactionType> for String {
+    fn from(value: &TransactionType) -> Self {
+        let value = match value {
+            TransactionType::Expense => "expense",
+            TransactionType::Income => "income",
+        };
+
+        value.to_string()
+    }
+}
+
+impl Display for TransactionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(self))
+    }
+}
+
+impl TryFrom<&str> for TransactionType {
+    type Error = ConversionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "expense" => Ok(TransactionType::Expense),
+            "income" => Ok(TransactionType::Income),
+            _ => Err(ConversionError::InvalidTransactionType),
+        }
+    }
+}
+
+impl From<&TransactionStatus> for String {
+    fn from(value: &TransactionStatus) -> Self {
+        let value = match value {
+            TransactionStatus::Pending => "pending",
+            TransactionStatus::Confirmed => "confirmed",
+            TransactionStatus::Failed => "failed",
+            TransactionStatus::RolledBack => "rolled_back",
+        };
+
+        value.to_string()
+    }
+}
+
+impl Display for TransactionStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from(self))
+    }
+}
+
+impl TryFrom<&str> for TransactionStatus {
+    type Error = ConversionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "pending" => Ok(TransactionStatus::Pending),
+            "confirmed" => Ok(TransactionStatus::Confirmed),
+            "failed" => Ok(TransactionStatus::Failed),
+            "rolled_back" => Ok(TransactionStatus::RolledBack),
+            _ => Err(ConversionError::InvalidTransactionStatus),
+        }
+    }
+}
+
+pub type AccountRef = EntityRef<AccountId, Account>;
+
+/// A part of a transaction, assigned to its own category.
+/// Eg: a supermarket receipt split between "Groceries" and "Household".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionSplit {
+    /// The category of this part of the transaction.
+    pub category_id: CategoryId,
+
+    /// The amount of this part, the amounts of the splits add up to the transaction amount.
+    #[schema(value_type = f32)]
+    pub amount: Amount,
 
-#[derive(Debug, Clone, PartialEq)]
+    /// Free text note about this part.
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
 pub struct Transaction {
     // Unique identifier for the transaction.
-    id: Option<EntityId>,
+    id: Option<TransactionId>,
 
     /// Reference to the account
+    #[schema(value_type = Object)]
     account: AccountRef,
 
     /// Type of the transaction (using transaction_ because type is a reserved word)
@@ -59,11 +156,27 @@ pub struct Transaction {
 
     /// Transaction status
     status: TransactionStatus,
+
+    /// The category of the transaction, None while it is not categorized or when it is split.
+    category_id: Option<CategoryId>,
+
+    /// The parts of the transaction, each with its own category. Empty when it is not split.
+    splits: Vec<TransactionSplit>,
+
+    /// The tags of the transaction.
+    tags: Vec<TagId>,
+
+    /// The payee the transaction is made with, recognized from its description or message when not provided.
+    payee_id: Option<PayeeId>,
+
+    /// The transaction this one compensates, when it is a reversal.
+    reverses: Option<TransactionId>,
 }
 
 impl Transaction {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        id: Option<EntityId>,
+        id: Option<TransactionId>,
         account: AccountRef,
         transaction_type: TransactionType,
         amount: Amount,
@@ -91,10 +204,15 @@ impl Transaction {
             reference_number,
             message,
             status,
+            category_id: None,
+            splits: vec![],
+            tags: vec![],
+            payee_id: None,
+            reverses: None,
         }
     }
 
-    pub fn id(&self) -> &Option<EntityId> {
+    pub fn id(&self) -> &Option<TransactionId> {
         &self.id
     }
 
@@ -102,6 +220,14 @@ impl Transaction {
         &self.account
     }
 
+    /// The id of the referenced account, when it is known.
+    pub fn account_id(&self) -> Option<&AccountId> {
+        match &self.account {
+            EntityRef::Id(id) => Some(id),
+            EntityRef::Value(account) => account.id(),
+        }
+    }
+
     pub fn transaction_type(&self) -> &TransactionType {
         &self.transaction_type
     }
@@ -146,7 +272,41 @@ impl Transaction {
         &self.status
     }
 
-    pub fn set_id(&mut self, id: Option<EntityId>) {
+    pub fn category_id(&self) -> Option<&CategoryId> {
+        self.category_id.as_ref()
+    }
+
+    pub fn splits(&self) -> &Vec<TransactionSplit> {
+        &self.splits
+    }
+
+    pub fn tags(&self) -> &Vec<TagId> {
+        &self.tags
+    }
+
+    pub fn payee_id(&self) -> Option<&PayeeId> {
+        self.payee_id.as_ref()
+    }
+
+    pub fn reverses(&self) -> Option<&TransactionId> {
+        self.reverses.as_ref()
+    }
+
+    /// The amount of the transaction assigned to each category.
+    /// A split transaction contributes each of its splits, the others their whole amount
+    /// (to no category when they are not categorized).
+    pub fn category_amounts(&self) -> Vec<(Option<&CategoryId>, &Amount)> {
+        if self.splits.is_empty() {
+            return vec![(self.category_id.as_ref(), &self.amount)];
+        }
+
+        self.splits
+            .iter()
+            .map(|split| (Some(&split.category_id), &split.amount))
+            .collect()
+    }
+
+    pub fn set_id(&mut self, id: Option<TransactionId>) {
         self.id = id;
     }
 
@@ -174,10 +334,6 @@ impl Transaction {
         self.closing_balance = closing_balance;
     }
 
-    pub fn set_currency(&mut self, currency: Currency) {
-        self.currency = currency;
-    }
-
     pub fn set_description(&mut self, description: Option<String>) {
         self.description = description;
     }
@@ -186,6 +342,37 @@ impl Transaction {
         self.date = date;
     }
 
+    pub fn set_status(&mut self, status: TransactionStatus) {
+        self.status = status;
+    }
+
+    pub fn set_category_id(&mut self, category_id: Option<CategoryId>) {
+        self.category_id = category_id;
+    }
+
+    pub fn set_splits(&mut self, splits: Vec<TransactionSplit>) {
+        self.splits = splits;
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<TagId>) {
+        self.tags = tags;
+    }
+
+    pub fn set_payee_id(&mut self, payee_id: Option<PayeeId>) {
+        self.payee_id = payee_id;
+    }
+
+    pub fn set_reverses(&mut self, reverses: Option<TransactionId>) {
+        self.reverses = reverses;
+    }
+}
+
+#[cfg(test)]
+impl Transaction {
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.currency = currency;
+    }
+
     pub fn set_reference_number(&mut self, reference_number: Option<String>) {
         self.reference_number = reference_number;
     }
@@ -193,8 +380,98 @@ impl Transaction {
     pub fn set_message(&mut self, message: Option<String>) {
         self.message = message;
     }
+}
 
-    pub fn set_status(&mut self, status: TransactionStatus) {
-        self.status = status;
+/// What was done to a transaction when one of its versions was replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum RevisionKind {
+    Updated,
+    Deleted,
+}
+
+impl From<&RevisionKind> for String {
+    fn from(value: &RevisionKind) -> Self {
+        let value = match value {
+            RevisionKind::Updated => "updated",
+            RevisionKind::Deleted => "deleted",
+        };
+
+        value.to_string()
+    }
+}
+
+impl TryFrom<&str> for RevisionKind {
+    type Error = ConversionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "updated" => Ok(RevisionKind::Updated),
+            "deleted" => Ok(RevisionKind::Deleted),
+            _ => Err(ConversionError::UnknownRevisionKind),
+        }
     }
 }
+
+/// The fields of a transaction at one point of its life, kept when it is edited or deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionVersion {
+    pub account_id: AccountId,
+    pub transaction_type: TransactionType,
+    #[schema(value_type = f32)]
+    pub amount: Amount,
+    #[schema(value_type = f32)]
+    pub fee: Amount,
+    #[schema(value_type = Option<f32>)]
+    pub opening_balance: Option<Amount>,
+    #[schema(value_type = Option<f32>)]
+    pub closing_balance: Option<Amount>,
+    pub currency: Currency,
+    pub status: TransactionStatus,
+    pub date: DateTime<Utc>,
+    pub description: Option<String>,
+    pub reference_number: Option<String>,
+    pub message: Option<String>,
+    pub category_id: Option<CategoryId>,
+    pub splits: Vec<TransactionSplit>,
+    pub tags: Vec<TagId>,
+    pub payee_id: Option<PayeeId>,
+}
+
+impl From<&Transaction> for TransactionVersion {
+    fn from(transaction: &Transaction) -> Self {
+        Self {
+            account_id: transaction.account_id().unwrap().clone(),
+            transaction_type: transaction.transaction_type.clone(),
+            amount: transaction.amount.clone(),
+            fee: transaction.fee.clone(),
+            opening_balance: transaction.opening_balance.clone(),
+            closing_balance: transaction.closing_balance.clone(),
+            currency: transaction.currency.clone(),
+            status: transaction.status.clone(),
+            date: transaction.date,
+            description: transaction.description.clone(),
+            reference_number: transaction.reference_number.clone(),
+            message: transaction.message.clone(),
+            category_id: transaction.category_id.clone(),
+            splits: transaction.splits.clone(),
+            tags: transaction.tags.clone(),
+            payee_id: transaction.payee_id.clone(),
+        }
+    }
+}
+
+/// A version of a transaction replaced by an edit or removed by a deletion, kept for the record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionRevision {
+    /// The transaction that was edited or deleted.
+    pub transaction_id: TransactionId,
+
+    /// Whether the transaction was edited or deleted.
+    pub kind: RevisionKind,
+
+    /// When the transaction was edited or deleted.
+    pub revised_at: DateTime<Utc>,
+
+    /// The transaction as it was before.
+    pub previous: TransactionVersion,
+}
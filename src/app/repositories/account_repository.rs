@@ -1,36 +1,100 @@
-use crate::app::entities::account::Account;
-use crate::app::entities::common::EntityId;
+use crate::app::entities::account::{Account, AccountId};
+use crate::app::entities::tag::TagId;
+use crate::app::repositories::common::Page;
+use crate::app::typing::account_identifier::AccountIdentifier;
 use thiserror::Error;
 
 #[derive(Debug, PartialEq)]
 pub enum FindByIdAndUpdateError {
     NotFound,
+
+    /// Another account already has the identifier.
+    DuplicateIdentifier(AccountIdentifier),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
 }
 
 #[derive(Error, Debug)]
 pub enum CreateError {
     #[error("Failed to create account")]
     FailedToCreateAccount,
+
+    #[error("Another account already has the identifier {0}")]
+    DuplicateIdentifier(AccountIdentifier),
 }
 
 pub trait AccountRepository {
-    /// Find all accounts.
-    fn find_all(&self) -> Vec<Account>;
+    /// Find the accounts in creation order, one page at a time.
+    /// The page starts after the account `after` (from the first account when None),
+    /// its `next_cursor` is the id of its last account.
+    fn find_page(&self, after: Option<AccountId>, limit: usize) -> Page<Account>;
 
     /// Find an account by ID.
     /// Return None if not found
-    fn find_by_id(&self, id: EntityId) -> Option<Account>;
+    fn find_by_id(&self, id: AccountId) -> Option<Account>;
+
+    /// Find the accounts having the identifier, in creation order.
+    /// A unique identifier is had by one account at most, the last 4 digits of a card by any number of them.
+    fn find_by_identifier(&self, identifier: &AccountIdentifier) -> Vec<Account>;
 
     /**
     Add a new account
+    It will throw a CreateError::DuplicateIdentifier if another account already has one of its unique identifiers.
     */
-    fn create(&mut self, account: Account) -> Result<EntityId, CreateError>;
+    fn create(&mut self, account: Account) -> Result<AccountId, CreateError>;
 
     /// Find an account by ID and update it.
-    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding document in the db.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding document in the db,
+    /// and a FindByIdAndUpdateError::DuplicateIdentifier if another account already has one of its unique identifiers.
+    fn find_by_id_and_update(
+        &mut self,
+        id: AccountId,
+        account: Account,
+    ) -> Result<AccountId, FindByIdAndUpdateError>;
+
+    /// Move the accounts tagged with `source` to the tag `target`.
+    /// An account with both tags keeps `target` only once.
+    fn merge_tags(&mut self, source: &TagId, target: &TagId);
+
+    /// Delete an account, with its tags and identifiers. The account must have no transactions.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding account.
+    fn delete(&mut self, id: AccountId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: AccountRepository + ?Sized> AccountRepository for Box<R> {
+    fn find_page(&self, after: Option<AccountId>, limit: usize) -> Page<Account> {
+        (**self).find_page(after, limit)
+    }
+
+    fn find_by_id(&self, id: AccountId) -> Option<Account> {
+        (**self).find_by_id(id)
+    }
+
+    fn find_by_identifier(&self, identifier: &AccountIdentifier) -> Vec<Account> {
+        (**self).find_by_identifier(identifier)
+    }
+
+    fn create(&mut self, account: Account) -> Result<AccountId, CreateError> {
+        (**self).create(account)
+    }
+
     fn find_by_id_and_update(
         &mut self,
-        id: EntityId,
+        id: AccountId,
         account: Account,
-    ) -> Result<EntityId, FindByIdAndUpdateError>;
+    ) -> Result<AccountId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, account)
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        (**self).merge_tags(source, target)
+    }
+
+    fn delete(&mut self, id: AccountId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
 }
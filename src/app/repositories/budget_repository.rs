@@ -0,0 +1,71 @@
+use crate::app::entities::budget::{Budget, BudgetId};
+use crate::app::entities::tag::TagId;
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait BudgetRepository {
+    /// Find all budgets, in creation order.
+    /// There are few budgets, they are always loaded as a whole.
+    fn find_all(&self) -> Vec<Budget>;
+
+    /// Find a budget by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: BudgetId) -> Option<Budget>;
+
+    /// Add a new budget, and return its generated ID.
+    fn create(&mut self, budget: Budget) -> BudgetId;
+
+    /// Find a budget by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding budget.
+    fn find_by_id_and_update(
+        &mut self,
+        id: BudgetId,
+        budget: Budget,
+    ) -> Result<BudgetId, FindByIdAndUpdateError>;
+
+    /// Delete a budget by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding budget.
+    fn delete(&mut self, id: BudgetId) -> Result<(), DeleteError>;
+
+    /// Make the budgets of the tag `source` apply to the tag `target` instead.
+    fn merge_tags(&mut self, source: &TagId, target: &TagId);
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: BudgetRepository + ?Sized> BudgetRepository for Box<R> {
+    fn find_all(&self) -> Vec<Budget> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: BudgetId) -> Option<Budget> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, budget: Budget) -> BudgetId {
+        (**self).create(budget)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: BudgetId,
+        budget: Budget,
+    ) -> Result<BudgetId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, budget)
+    }
+
+    fn delete(&mut self, id: BudgetId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        (**self).merge_tags(source, target)
+    }
+}
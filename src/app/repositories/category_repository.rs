@@ -0,0 +1,63 @@
+use crate::app::entities::category::{Category, CategoryId};
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait CategoryRepository {
+    /// Find all categories, in creation order.
+    /// The taxonomy is small, it is always loaded as a whole.
+    fn find_all(&self) -> Vec<Category>;
+
+    /// Find a category by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: CategoryId) -> Option<Category>;
+
+    /// Add a new category, and return its generated ID.
+    fn create(&mut self, category: Category) -> CategoryId;
+
+    /// Find a category by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding category.
+    fn find_by_id_and_update(
+        &mut self,
+        id: CategoryId,
+        category: Category,
+    ) -> Result<CategoryId, FindByIdAndUpdateError>;
+
+    /// Delete a category by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding category.
+    fn delete(&mut self, id: CategoryId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: CategoryRepository + ?Sized> CategoryRepository for Box<R> {
+    fn find_all(&self) -> Vec<Category> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: CategoryId) -> Option<Category> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, category: Category) -> CategoryId {
+        (**self).create(category)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: CategoryId,
+        category: Category,
+    ) -> Result<CategoryId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, category)
+    }
+
+    fn delete(&mut self, id: CategoryId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+}
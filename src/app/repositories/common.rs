@@ -0,0 +1,116 @@
+use crate::app::entities::transaction::Transaction;
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use utoipa::ToSchema;
+
+/// A page of results, as returned by the paginated queries.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Page<T> {
+    /// The items of the page.
+    pub items: Vec<T>,
+
+    /// Opaque cursor pointing to the next page, None when this is the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page out of the items fetched for it.
+    /// The repositories fetch one item more than the limit, its presence tells there is a next page.
+    pub fn from_overfetched(
+        mut items: Vec<T>,
+        limit: usize,
+        cursor: impl Fn(&T) -> String,
+    ) -> Self {
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(cursor)
+        } else {
+            None
+        };
+
+        Self { items, next_cursor }
+    }
+
+    /// Converts the items of the page, keeping the cursor.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// The number of items fetched at once when streaming a listing.
+pub(crate) const STREAM_BATCH_SIZE: usize = 100;
+
+/// Iterates over every item of a paginated listing, fetching the pages lazily.
+///
+/// Only one page is held in memory at a time, whatever the size of the listing.
+pub struct PageIterator<T, F> {
+    fetch_page: F,
+    items: std::vec::IntoIter<T>,
+    next_cursor: Option<String>,
+    exhausted: bool,
+}
+
+impl<T, F: FnMut(Option<String>) -> Page<T>> PageIterator<T, F> {
+    /// Builds an iterator out of a function returning the page positioned after a cursor
+    /// (the first page when there is no cursor).
+    pub fn new(fetch_page: F) -> Self {
+        Self {
+            fetch_page,
+            items: Vec::new().into_iter(),
+            next_cursor: None,
+            exhausted: false,
+        }
+    }
+}
+
+impl<T, F: FnMut(Option<String>) -> Page<T>> Iterator for PageIterator<T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(item);
+            }
+
+            if self.exhausted {
+                return None;
+            }
+
+            let page = (self.fetch_page)(self.next_cursor.take());
+            self.exhausted = page.next_cursor.is_none();
+            self.next_cursor = page.next_cursor;
+            self.items = page.items.into_iter();
+        }
+    }
+}
+
+/// Iterates over every transaction matching the query, in the order of the query.
+/// The transactions are fetched in batches while the iterator is consumed, so the whole history
+/// is never loaded at once. The repository is only locked during a fetch, the limit of the query is ignored.
+pub(crate) fn stream_transactions<TR: TransactionRepository>(
+    transaction_repository: Arc<Mutex<TR>>,
+    query: TransactionQuery,
+) -> impl Iterator<Item = Transaction> {
+    let mut query = TransactionQuery {
+        limit: STREAM_BATCH_SIZE,
+        ..query
+    };
+
+    PageIterator::new(move |cursor: Option<String>| {
+        // The first batch starts at the cursor of the query, if any.
+        if let Some(cursor) = cursor {
+            query.cursor = Some(
+                cursor
+                    .parse()
+                    .expect("The cursor comes from the previous page"),
+            );
+        }
+
+        transaction_repository.lock().unwrap().find(&query)
+    })
+}
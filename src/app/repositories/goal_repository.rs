@@ -0,0 +1,63 @@
+use crate::app::entities::goal::{Goal, GoalId};
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait GoalRepository {
+    /// Find all goals, in creation order.
+    /// There are few goals, they are always loaded as a whole.
+    fn find_all(&self) -> Vec<Goal>;
+
+    /// Find a goal by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: GoalId) -> Option<Goal>;
+
+    /// Add a new goal, and return its generated ID.
+    fn create(&mut self, goal: Goal) -> GoalId;
+
+    /// Find a goal by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding goal.
+    fn find_by_id_and_update(
+        &mut self,
+        id: GoalId,
+        goal: Goal,
+    ) -> Result<GoalId, FindByIdAndUpdateError>;
+
+    /// Delete a goal by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding goal.
+    fn delete(&mut self, id: GoalId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: GoalRepository + ?Sized> GoalRepository for Box<R> {
+    fn find_all(&self) -> Vec<Goal> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: GoalId) -> Option<Goal> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, goal: Goal) -> GoalId {
+        (**self).create(goal)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: GoalId,
+        goal: Goal,
+    ) -> Result<GoalId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, goal)
+    }
+
+    fn delete(&mut self, id: GoalId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::app::entities::institution::{Institution, InstitutionId};
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait InstitutionRepository {
+    /// Find all institutions, in creation order.
+    /// There are few institutions, they are always loaded as a whole.
+    fn find_all(&self) -> Vec<Institution>;
+
+    /// Find an institution by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: InstitutionId) -> Option<Institution>;
+
+    /// Add a new institution, and return its generated ID.
+    fn create(&mut self, institution: Institution) -> InstitutionId;
+
+    /// Find an institution by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding institution.
+    fn find_by_id_and_update(
+        &mut self,
+        id: InstitutionId,
+        institution: Institution,
+    ) -> Result<InstitutionId, FindByIdAndUpdateError>;
+
+    /// Delete an institution by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding institution.
+    fn delete(&mut self, id: InstitutionId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: InstitutionRepository + ?Sized> InstitutionRepository for Box<R> {
+    fn find_all(&self) -> Vec<Institution> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: InstitutionId) -> Option<Institution> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, institution: Institution) -> InstitutionId {
+        (**self).create(institution)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: InstitutionId,
+        institution: Institution,
+    ) -> Result<InstitutionId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, institution)
+    }
+
+    fn delete(&mut self, id: InstitutionId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::interest::InterestConfig;
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait InterestConfigRepository {
+    /// Find the interest configurations of all the accounts.
+    fn find_all(&self) -> Vec<InterestConfig>;
+
+    /// Find the interest configuration of an account.
+    /// Return None if the account has none.
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<InterestConfig>;
+
+    /// Save the interest configuration of its account, replacing the previous one if any.
+    fn save(&mut self, config: InterestConfig);
+
+    /// Delete the interest configuration of an account.
+    /// It will throw a DeleteError::NotFound if the account has none.
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: InterestConfigRepository + ?Sized> InterestConfigRepository for Box<R> {
+    fn find_all(&self) -> Vec<InterestConfig> {
+        (**self).find_all()
+    }
+
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<InterestConfig> {
+        (**self).find_by_account_id(account_id)
+    }
+
+    fn save(&mut self, config: InterestConfig) {
+        (**self).save(config)
+    }
+
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError> {
+        (**self).delete(account_id)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::app::entities::loan::{Loan, LoanId};
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait LoanRepository {
+    /// Find all loans, in creation order.
+    /// There are few loans, they are always loaded as a whole.
+    fn find_all(&self) -> Vec<Loan>;
+
+    /// Find a loan by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: LoanId) -> Option<Loan>;
+
+    /// Add a new loan, and return its generated ID.
+    fn create(&mut self, loan: Loan) -> LoanId;
+
+    /// Find a loan by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding loan.
+    fn find_by_id_and_update(
+        &mut self,
+        id: LoanId,
+        loan: Loan,
+    ) -> Result<LoanId, FindByIdAndUpdateError>;
+
+    /// Delete a loan by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding loan.
+    fn delete(&mut self, id: LoanId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: LoanRepository + ?Sized> LoanRepository for Box<R> {
+    fn find_all(&self) -> Vec<Loan> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: LoanId) -> Option<Loan> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, loan: Loan) -> LoanId {
+        (**self).create(loan)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: LoanId,
+        loan: Loan,
+    ) -> Result<LoanId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, loan)
+    }
+
+    fn delete(&mut self, id: LoanId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+}
@@ -1,2 +1,16 @@
 pub(crate) mod account_repository;
+pub(crate) mod budget_repository;
+pub(crate) mod category_repository;
+pub(crate) mod common;
+pub(crate) mod goal_repository;
+pub(crate) mod institution_repository;
+pub(crate) mod interest_config_repository;
+pub(crate) mod loan_repository;
+pub(crate) mod payee_repository;
+pub(crate) mod recurring_transaction_repository;
+pub(crate) mod rule_repository;
+pub(crate) mod statement_config_repository;
+pub(crate) mod tag_repository;
+mod test_common;
+pub(crate) mod transaction_query;
 pub(crate) mod transaction_repository;
@@ -0,0 +1,63 @@
+use crate::app::entities::payee::{Payee, PayeeId};
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait PayeeRepository {
+    /// Find all payees, in creation order.
+    /// The directory is small, it is always loaded as a whole.
+    fn find_all(&self) -> Vec<Payee>;
+
+    /// Find a payee by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: PayeeId) -> Option<Payee>;
+
+    /// Add a new payee, and return its generated ID.
+    fn create(&mut self, payee: Payee) -> PayeeId;
+
+    /// Find a payee by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding payee.
+    fn find_by_id_and_update(
+        &mut self,
+        id: PayeeId,
+        payee: Payee,
+    ) -> Result<PayeeId, FindByIdAndUpdateError>;
+
+    /// Delete a payee by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding payee.
+    fn delete(&mut self, id: PayeeId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: PayeeRepository + ?Sized> PayeeRepository for Box<R> {
+    fn find_all(&self) -> Vec<Payee> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: PayeeId) -> Option<Payee> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, payee: Payee) -> PayeeId {
+        (**self).create(payee)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: PayeeId,
+        payee: Payee,
+    ) -> Result<PayeeId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, payee)
+    }
+
+    fn delete(&mut self, id: PayeeId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::recurring_transaction::{RecurringTransaction, RecurringTransactionId};
+use crate::app::entities::tag::TagId;
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait RecurringTransactionRepository {
+    /// Find all recurring transactions, in creation order.
+    /// There are few recurring transactions, they are always loaded as a whole.
+    fn find_all(&self) -> Vec<RecurringTransaction>;
+
+    /// Find a recurring transaction by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: RecurringTransactionId) -> Option<RecurringTransaction>;
+
+    /// Add a new recurring transaction, and return its generated ID.
+    fn create(&mut self, recurring_transaction: RecurringTransaction) -> RecurringTransactionId;
+
+    /// Find a recurring transaction by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding recurring transaction.
+    fn find_by_id_and_update(
+        &mut self,
+        id: RecurringTransactionId,
+        recurring_transaction: RecurringTransaction,
+    ) -> Result<RecurringTransactionId, FindByIdAndUpdateError>;
+
+    /// Delete a recurring transaction by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding recurring transaction.
+    fn delete(&mut self, id: RecurringTransactionId) -> Result<(), DeleteError>;
+
+    /// Make the recurring transactions tagged `source` tagged `target` instead.
+    fn merge_tags(&mut self, source: &TagId, target: &TagId);
+
+    /// Make the recurring transactions made with the payee `source` made with the payee `target` instead.
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId);
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: RecurringTransactionRepository + ?Sized> RecurringTransactionRepository for Box<R> {
+    fn find_all(&self) -> Vec<RecurringTransaction> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: RecurringTransactionId) -> Option<RecurringTransaction> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, recurring_transaction: RecurringTransaction) -> RecurringTransactionId {
+        (**self).create(recurring_transaction)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: RecurringTransactionId,
+        recurring_transaction: RecurringTransaction,
+    ) -> Result<RecurringTransactionId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, recurring_transaction)
+    }
+
+    fn delete(&mut self, id: RecurringTransactionId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        (**self).merge_tags(source, target)
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        (**self).merge_payees(source, target)
+    }
+}
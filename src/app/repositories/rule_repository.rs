@@ -0,0 +1,79 @@
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::rule::{Rule, RuleId};
+use crate::app::entities::tag::TagId;
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait RuleRepository {
+    /// Find all rules, by increasing priority, then in creation order.
+    /// There are few rules, they are always loaded as a whole.
+    fn find_all(&self) -> Vec<Rule>;
+
+    /// Find a rule by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: RuleId) -> Option<Rule>;
+
+    /// Add a new rule, and return its generated ID.
+    fn create(&mut self, rule: Rule) -> RuleId;
+
+    /// Find a rule by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding rule.
+    fn find_by_id_and_update(
+        &mut self,
+        id: RuleId,
+        rule: Rule,
+    ) -> Result<RuleId, FindByIdAndUpdateError>;
+
+    /// Delete a rule by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding rule.
+    fn delete(&mut self, id: RuleId) -> Result<(), DeleteError>;
+
+    /// Make the rules adding the tag `source` add the tag `target` instead.
+    fn merge_tags(&mut self, source: &TagId, target: &TagId);
+
+    /// Make the rules setting the payee `source` set the payee `target` instead.
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId);
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: RuleRepository + ?Sized> RuleRepository for Box<R> {
+    fn find_all(&self) -> Vec<Rule> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: RuleId) -> Option<Rule> {
+        (**self).find_by_id(id)
+    }
+
+    fn create(&mut self, rule: Rule) -> RuleId {
+        (**self).create(rule)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: RuleId,
+        rule: Rule,
+    ) -> Result<RuleId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, rule)
+    }
+
+    fn delete(&mut self, id: RuleId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        (**self).merge_tags(source, target)
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        (**self).merge_payees(source, target)
+    }
+}
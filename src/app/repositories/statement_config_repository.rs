@@ -0,0 +1,35 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::statement::StatementConfig;
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait StatementConfigRepository {
+    /// Find the statement configuration of an account.
+    /// Return None if the account has none.
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<StatementConfig>;
+
+    /// Save the statement configuration of its account, replacing the previous one if any.
+    fn save(&mut self, config: StatementConfig);
+
+    /// Delete the statement configuration of an account.
+    /// It will throw a DeleteError::NotFound if the account has none.
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: StatementConfigRepository + ?Sized> StatementConfigRepository for Box<R> {
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<StatementConfig> {
+        (**self).find_by_account_id(account_id)
+    }
+
+    fn save(&mut self, config: StatementConfig) {
+        (**self).save(config)
+    }
+
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError> {
+        (**self).delete(account_id)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::app::entities::tag::{Tag, TagId};
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
+
+pub trait TagRepository {
+    /// Find all tags, sorted by name.
+    fn find_all(&self) -> Vec<Tag>;
+
+    /// Find a tag by ID.
+    /// Return None if not found
+    fn find_by_id(&self, id: TagId) -> Option<Tag>;
+
+    /// Find a tag by its (normalized) name.
+    /// Return None if not found
+    fn find_by_name(&self, name: &str) -> Option<Tag>;
+
+    /// Add a new tag, and return its generated ID.
+    fn create(&mut self, tag: Tag) -> TagId;
+
+    /// Find a tag by ID and update it.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding tag.
+    fn find_by_id_and_update(
+        &mut self,
+        id: TagId,
+        tag: Tag,
+    ) -> Result<TagId, FindByIdAndUpdateError>;
+
+    /// Delete a tag by ID.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding tag.
+    fn delete(&mut self, id: TagId) -> Result<(), DeleteError>;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: TagRepository + ?Sized> TagRepository for Box<R> {
+    fn find_all(&self) -> Vec<Tag> {
+        (**self).find_all()
+    }
+
+    fn find_by_id(&self, id: TagId) -> Option<Tag> {
+        (**self).find_by_id(id)
+    }
+
+    fn find_by_name(&self, name: &str) -> Option<Tag> {
+        (**self).find_by_name(name)
+    }
+
+    fn create(&mut self, tag: Tag) -> TagId {
+        (**self).create(tag)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: TagId,
+        tag: Tag,
+    ) -> Result<TagId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, tag)
+    }
+
+    fn delete(&mut self, id: TagId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests_page_iterator {
+    use crate::app::repositories::common::{Page, PageIterator};
+
+    /// Pages over a list of numbers, the cursor being the last number of the page.
+    fn get_page(numbers: &[u32], cursor: Option<String>, limit: usize) -> Page<u32> {
+        let after: Option<u32> = cursor.map(|cursor| cursor.parse().unwrap());
+        let items: Vec<u32> = numbers
+            .iter()
+            .copied()
+            .filter(|number| after.is_none_or(|after| *number > after))
+            .take(limit + 1)
+            .collect();
+
+        Page::from_overfetched(items, limit, |number| number.to_string())
+    }
+
+    #[test]
+    fn test_iterates_over_all_pages() {
+        // GIVEN a listing of seven items, served three at a time
+        let numbers: Vec<u32> = (1..=7).collect();
+        let mut fetched_pages = 0;
+
+        // WHEN the listing is iterated
+        let items: Vec<u32> = PageIterator::new(|cursor| {
+            fetched_pages += 1;
+            get_page(&numbers, cursor, 3)
+        })
+        .collect();
+
+        // THEN every item is returned once, in order
+        assert_eq!(items, numbers);
+
+        // AND the pages were fetched one after the other
+        assert_eq!(fetched_pages, 3);
+    }
+
+    #[test]
+    fn test_empty_listing() {
+        // GIVEN an empty listing
+        let mut fetched_pages = 0;
+
+        // WHEN the listing is iterated
+        let items: Vec<u32> = PageIterator::new(|cursor| {
+            fetched_pages += 1;
+            get_page(&[], cursor, 3)
+        })
+        .collect();
+
+        // THEN nothing is returned, after a single fetch
+        assert!(items.is_empty());
+        assert_eq!(fetched_pages, 1);
+    }
+
+    #[test]
+    fn test_pages_are_fetched_lazily() {
+        // GIVEN a listing of seven items, served three at a time
+        let numbers: Vec<u32> = (1..=7).collect();
+        let mut fetched_pages = 0;
+
+        // WHEN only the first four items are consumed
+        let items: Vec<u32> = PageIterator::new(|cursor| {
+            fetched_pages += 1;
+            get_page(&numbers, cursor, 3)
+        })
+        .take(4)
+        .collect();
+
+        // THEN only the pages holding them are fetched
+        assert_eq!(items, [1, 2, 3, 4]);
+        assert_eq!(fetched_pages, 2);
+    }
+}
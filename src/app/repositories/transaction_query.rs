@@ -0,0 +1,306 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{
+    Transaction, TransactionId, TransactionStatus, TransactionType,
+};
+use crate::app::typing::amount::Amount;
+use crate::app::typing::currency::Currency;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+use utoipa::ToSchema;
+
+/// The number of transactions returned in a page, when no limit is requested.
+pub(crate) const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// The maximum number of transactions that can be returned in a page.
+pub(crate) const MAX_PAGE_SIZE: usize = 500;
+
+/// The field the transactions are sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionSortField {
+    /// Transaction date.
+    #[default]
+    Date,
+
+    /// Transaction amount.
+    Amount,
+
+    /// Order in which the transactions were recorded.
+    CreatedAt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TransactionSort {
+    pub field: TransactionSortField,
+    pub direction: SortDirection,
+}
+
+impl TransactionSort {
+    /// Compares two positions according to the sort field and direction.
+    /// The transaction id breaks the ties, so the order is total and stable across pages.
+    pub fn compare(&self, left: &TransactionCursor, right: &TransactionCursor) -> Ordering {
+        let ordering = match self.field {
+            TransactionSortField::Date => left.date.cmp(&right.date),
+            TransactionSortField::Amount => left.amount.total_cmp(&right.amount),
+            TransactionSortField::CreatedAt => Ordering::Equal,
+        }
+        .then_with(|| left.id.cmp(&right.id));
+
+        match self.direction {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CursorParseError {
+    #[error("invalid cursor: {0:?}")]
+    Invalid(String),
+}
+
+/// Position of a transaction in a listing, used to resume the listing after it.
+///
+/// It carries every sortable value of the transaction, so it stays valid whatever the sort is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionCursor {
+    pub date: DateTime<Utc>,
+    pub amount: f32,
+    pub id: TransactionId,
+}
+
+impl From<&Transaction> for TransactionCursor {
+    fn from(transaction: &Transaction) -> Self {
+        Self {
+            date: transaction.date(),
+            amount: transaction.amount().value(),
+            id: transaction
+                .id()
+                .clone()
+                .expect("Only saved transactions have a position"),
+        }
+    }
+}
+
+impl Display for TransactionCursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}_{}_{}",
+            self.date.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            self.amount,
+            self.id
+        )
+    }
+}
+
+impl FromStr for TransactionCursor {
+    type Err = CursorParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || CursorParseError::Invalid(value.to_string());
+
+        let mut parts = value.split('_');
+        let (Some(date), Some(amount), Some(id), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        Ok(Self {
+            date: DateTime::parse_from_rfc3339(date)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            amount: amount.parse().map_err(|_| invalid())?,
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Criteria used to search the transactions.
+/// Every criterion is optional, the transactions must match all the provided ones.
+#[derive(Debug, Clone)]
+pub struct TransactionQuery {
+    /// The account the transactions belong to.
+    pub account_id: Option<AccountId>,
+
+    /// Transactions on or after this date.
+    pub from: Option<DateTime<Utc>>,
+
+    /// Transactions strictly before this date.
+    pub to: Option<DateTime<Utc>>,
+
+    pub transaction_type: Option<TransactionType>,
+
+    pub status: Option<TransactionStatus>,
+
+    pub currency: Option<Currency>,
+
+    /// Transactions in one of these categories, directly or through one of their splits.
+    /// Any category (or none) when empty.
+    pub category_ids: Vec<CategoryId>,
+
+    /// Transactions carrying all of these tags. Any tags (or none) when empty.
+    pub tag_ids: Vec<TagId>,
+
+    /// Transactions made with this payee.
+    pub payee_id: Option<PayeeId>,
+
+    /// Transactions with an amount greater than or equal to this one.
+    pub min_amount: Option<Amount>,
+
+    /// Transactions with an amount less than or equal to this one.
+    pub max_amount: Option<Amount>,
+
+    /// Case-insensitive text searched in the description, message and reference number.
+    pub text: Option<String>,
+
+    pub sort: TransactionSort,
+
+    /// Resume the listing after this position.
+    pub cursor: Option<TransactionCursor>,
+
+    /// The maximum number of transactions in the page.
+    pub limit: usize,
+}
+
+impl Default for TransactionQuery {
+    fn default() -> Self {
+        Self {
+            account_id: None,
+            from: None,
+            to: None,
+            transaction_type: None,
+            status: None,
+            currency: None,
+            category_ids: vec![],
+            tag_ids: vec![],
+            payee_id: None,
+            min_amount: None,
+            max_amount: None,
+            text: None,
+            sort: TransactionSort::default(),
+            cursor: None,
+            limit: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+fn contains_text(value: &Option<String>, text: &str) -> bool {
+    value
+        .as_ref()
+        .is_some_and(|value| value.to_lowercase().contains(text))
+}
+
+impl TransactionQuery {
+    /// Whether the transaction matches the filters of the query (the cursor is not considered).
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        if self
+            .account_id
+            .as_ref()
+            .is_some_and(|account_id| transaction.account_id() != Some(account_id))
+        {
+            return false;
+        }
+
+        if self.from.is_some_and(|from| transaction.date() < from) {
+            return false;
+        }
+
+        if self.to.is_some_and(|to| transaction.date() >= to) {
+            return false;
+        }
+
+        if self
+            .transaction_type
+            .as_ref()
+            .is_some_and(|transaction_type| transaction.transaction_type() != transaction_type)
+        {
+            return false;
+        }
+
+        if self
+            .status
+            .as_ref()
+            .is_some_and(|status| transaction.status() != status)
+        {
+            return false;
+        }
+
+        if self
+            .currency
+            .as_ref()
+            .is_some_and(|currency| transaction.currency() != currency)
+        {
+            return false;
+        }
+
+        if !self.category_ids.is_empty()
+            && !transaction
+                .category_amounts()
+                .iter()
+                .any(|(category_id, _)| {
+                    category_id.is_some_and(|category_id| self.category_ids.contains(category_id))
+                })
+        {
+            return false;
+        }
+
+        if !self
+            .tag_ids
+            .iter()
+            .all(|tag_id| transaction.tags().contains(tag_id))
+        {
+            return false;
+        }
+
+        if self
+            .payee_id
+            .as_ref()
+            .is_some_and(|payee_id| transaction.payee_id() != Some(payee_id))
+        {
+            return false;
+        }
+
+        if self
+            .min_amount
+            .as_ref()
+            .is_some_and(|min_amount| transaction.amount() < min_amount)
+        {
+            return false;
+        }
+
+        if self
+            .max_amount
+            .as_ref()
+            .is_some_and(|max_amount| transaction.amount() > max_amount)
+        {
+            return false;
+        }
+
+        if let Some(text) = &self.text {
+            let text = text.to_lowercase();
+            return contains_text(transaction.description(), &text)
+                || contains_text(transaction.message(), &text)
+                || contains_text(transaction.reference_number(), &text);
+        }
+
+        true
+    }
+}
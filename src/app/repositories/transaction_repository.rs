@@ -1,19 +1,132 @@
-use crate::app::entities::common::EntityId;
-use crate::app::entities::transaction::Transaction;
+use crate::app::entities::account::AccountId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{Transaction, TransactionId, TransactionRevision};
+use crate::app::repositories::common::Page;
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::typing::amount::Amount;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, PartialEq)]
+pub enum FindByIdAndUpdateError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SetTagsError {
+    NotFound,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteError {
+    NotFound,
+}
 
 pub trait TransactionRepository {
-    /**
-    Get all transaction
-    */
-    fn find_all(&self) -> Vec<Transaction>;
+    /// Find the transactions matching the query, one page at a time.
+    /// The next page is requested with the `next_cursor` of the returned page.
+    fn find(&self, query: &TransactionQuery) -> Page<Transaction>;
 
     /**
     Add a new transaction
     */
-    fn create(&mut self, transaction: Transaction) -> EntityId;
+    fn create(&mut self, transaction: Transaction) -> TransactionId;
 
     /**
     Get a transaction by id
     */
-    fn find_by_id(&self, id: EntityId) -> Option<&Transaction>;
+    fn find_by_id(&self, id: TransactionId) -> Option<Transaction>;
+
+    /// Find a transaction by ID and replace it, along with its splits and tags.
+    /// It will throw a FindByIdAndUpdateError::NotFound if the provided id doesn't have any corresponding transaction.
+    fn find_by_id_and_update(
+        &mut self,
+        id: TransactionId,
+        transaction: Transaction,
+    ) -> Result<TransactionId, FindByIdAndUpdateError>;
+
+    /// Remove a transaction, along with its splits and tags. Its revisions are kept.
+    /// It will throw a DeleteError::NotFound if the provided id doesn't have any corresponding transaction.
+    fn delete(&mut self, id: TransactionId) -> Result<(), DeleteError>;
+
+    /// Keep a version of a transaction replaced by an edit or removed by a deletion.
+    fn add_revision(&mut self, revision: TransactionRevision);
+
+    /// Find the revisions of a transaction, the oldest first.
+    fn find_revisions(&self, id: &TransactionId) -> Vec<TransactionRevision>;
+
+    /// The balance the transactions of the account give it at the date: the closing balance of its last transaction
+    /// on or before the date, else (no such transaction, or no closing balance) the opening balance of its first
+    /// transaction after it. None when neither balance is known.
+    fn balance_at(&self, account_id: &AccountId, at: DateTime<Utc>) -> Option<Amount>;
+
+    /// Replace the tags of a transaction.
+    /// It will throw a SetTagsError::NotFound if the provided id doesn't have any corresponding transaction.
+    fn set_tags(&mut self, id: TransactionId, tags: Vec<TagId>) -> Result<(), SetTagsError>;
+
+    /// Move the transactions tagged with `source` to the tag `target`.
+    /// A transaction with both tags keeps `target` only once.
+    fn merge_tags(&mut self, source: &TagId, target: &TagId);
+
+    /// Move the transactions of the payee `source` to the payee `target`.
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId);
+
+    /// A number which changes each time the transactions change, so what was computed from them
+    /// can be kept until it does.
+    fn version(&self) -> u64;
+}
+
+/// Allows the services to work with a repository chosen at runtime (in memory or sqlite).
+impl<R: TransactionRepository + ?Sized> TransactionRepository for Box<R> {
+    fn find(&self, query: &TransactionQuery) -> Page<Transaction> {
+        (**self).find(query)
+    }
+
+    fn create(&mut self, transaction: Transaction) -> TransactionId {
+        (**self).create(transaction)
+    }
+
+    fn find_by_id(&self, id: TransactionId) -> Option<Transaction> {
+        (**self).find_by_id(id)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: TransactionId,
+        transaction: Transaction,
+    ) -> Result<TransactionId, FindByIdAndUpdateError> {
+        (**self).find_by_id_and_update(id, transaction)
+    }
+
+    fn delete(&mut self, id: TransactionId) -> Result<(), DeleteError> {
+        (**self).delete(id)
+    }
+
+    fn add_revision(&mut self, revision: TransactionRevision) {
+        (**self).add_revision(revision)
+    }
+
+    fn find_revisions(&self, id: &TransactionId) -> Vec<TransactionRevision> {
+        (**self).find_revisions(id)
+    }
+
+    fn balance_at(&self, account_id: &AccountId, at: DateTime<Utc>) -> Option<Amount> {
+        (**self).balance_at(account_id, at)
+    }
+
+    fn set_tags(&mut self, id: TransactionId, tags: Vec<TagId>) -> Result<(), SetTagsError> {
+        (**self).set_tags(id, tags)
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        (**self).merge_tags(source, target)
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        (**self).merge_payees(source, target)
+    }
+
+    fn version(&self) -> u64 {
+        (**self).version()
+    }
 }
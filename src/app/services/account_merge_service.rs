@@ -0,0 +1,350 @@
+use crate::app::entities::account::{Account, AccountId, AccountStatus};
+use crate::app::entities::goal::GoalId;
+use crate::app::entities::loan::LoanId;
+use crate::app::entities::recurring_transaction::RecurringTransactionId;
+use crate::app::entities::rule::RuleId;
+use crate::app::entities::transaction::TransactionId;
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::goal_repository::GoalRepository;
+use crate::app::repositories::interest_config_repository::InterestConfigRepository;
+use crate::app::repositories::loan_repository::LoanRepository;
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::repositories::statement_config_repository::StatementConfigRepository;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::services::account_service::AccountService;
+use crate::app::services::transaction_service::TransactionService;
+use crate::app::typing::account_identifier::AccountIdentifier;
+use crate::app::typing::amount::Amount;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MergeError {
+    #[error("The account to merge was not found")]
+    SourceNotFound(AccountId),
+
+    #[error("The account to merge into was not found")]
+    TargetNotFound(AccountId),
+
+    #[error("An account can not be merged into itself")]
+    SameAccount,
+
+    #[error("An account can not be merged into a closed account")]
+    TargetClosed,
+
+    #[error("Only accounts of the same currency can be merged")]
+    CurrencyMismatch,
+
+    #[error("Only accounts of the same type can be merged")]
+    AccountTypeMismatch,
+
+    #[error("The merged balance would be over the maximum amount")]
+    BalanceOverflow,
+}
+
+/// What became of the configuration (interest or statement) of the merged account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub enum ConfigMerge {
+    /// The merged account had no configuration.
+    NotConfigured,
+
+    /// The configuration now applies to the account merged into.
+    Moved,
+
+    /// The account merged into keeps its own configuration, the other one was removed.
+    Dropped,
+}
+
+/// What an account merge changed.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct AccountMerge {
+    /// The account merged, closed with a zero balance.
+    pub source: Account,
+
+    /// The account merged into, holding both balances.
+    pub target: Account,
+
+    /// The transactions moved to the target, their balances recomputed along with the target's own.
+    pub transaction_ids: Vec<TransactionId>,
+
+    /// The recurring transactions now creating their occurrences on the target.
+    pub recurring_transaction_ids: Vec<RecurringTransactionId>,
+
+    /// The goals now saving on the target.
+    pub goal_ids: Vec<GoalId>,
+
+    /// The loans now repaid on the target.
+    pub loan_ids: Vec<LoanId>,
+
+    /// The rules now matching the transactions of the target.
+    pub rule_ids: Vec<RuleId>,
+
+    /// The identifiers moved to the target.
+    pub identifiers: Vec<AccountIdentifier>,
+
+    pub interest_config: ConfigMerge,
+
+    pub statement_config: ConfigMerge,
+}
+
+/// The transaction service, shared with the API which creates the transactions.
+type SharedTransactionService<TR, AR, CR, PR, RR> =
+    Arc<Mutex<TransactionService<TR, AR, CR, PR, RR>>>;
+
+/// Merges the duplicate accounts, like the ones created twice by imports.
+pub struct AccountMergeService<TR, AR, CR, PR, RR, RTR, GR, LR, IR, SR> {
+    account_service: Arc<Mutex<AccountService<AR, TR>>>,
+    transaction_service: SharedTransactionService<TR, AR, CR, PR, RR>,
+    rule_repository: Arc<Mutex<RR>>,
+    recurring_transaction_repository: Arc<Mutex<RTR>>,
+    goal_repository: Arc<Mutex<GR>>,
+    loan_repository: Arc<Mutex<LR>>,
+    interest_config_repository: Arc<Mutex<IR>>,
+    statement_config_repository: Arc<Mutex<SR>>,
+}
+
+impl<
+        TR: TransactionRepository,
+        AR: AccountRepository,
+        CR: CategoryRepository,
+        PR: PayeeRepository,
+        RR: RuleRepository,
+        RTR: RecurringTransactionRepository,
+        GR: GoalRepository,
+        LR: LoanRepository,
+        IR: InterestConfigRepository,
+        SR: StatementConfigRepository,
+    > AccountMergeService<TR, AR, CR, PR, RR, RTR, GR, LR, IR, SR>
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        account_service: Arc<Mutex<AccountService<AR, TR>>>,
+        transaction_service: SharedTransactionService<TR, AR, CR, PR, RR>,
+        rule_repository: Arc<Mutex<RR>>,
+        recurring_transaction_repository: Arc<Mutex<RTR>>,
+        goal_repository: Arc<Mutex<GR>>,
+        loan_repository: Arc<Mutex<LR>>,
+        interest_config_repository: Arc<Mutex<IR>>,
+        statement_config_repository: Arc<Mutex<SR>>,
+    ) -> Self {
+        Self {
+            account_service,
+            transaction_service,
+            rule_repository,
+            recurring_transaction_repository,
+            goal_repository,
+            loan_repository,
+            interest_config_repository,
+            statement_config_repository,
+        }
+    }
+
+    /// Merges the `source` account into the `target` account.
+    /// Everything referencing the source references the target instead: its transactions, whose balances are
+    /// recomputed, its recurring transactions, goals, loans, rules, identifiers and tags. The target keeps its own
+    /// interest and statement configurations, it takes the source's if it has none. The source is then closed,
+    /// its history kept under the target.
+    /// #### Errors
+    /// * `MergeError::SourceNotFound` / `MergeError::TargetNotFound` — If one of the accounts does not exist.
+    /// * `MergeError::SameAccount` — If an account is merged into itself.
+    /// * `MergeError::TargetClosed` — If the target is closed.
+    /// * `MergeError::CurrencyMismatch` / `MergeError::AccountTypeMismatch` — If the accounts are not alike.
+    /// * `MergeError::BalanceOverflow` — If the balances add up over the maximum amount.
+    pub fn merge(
+        &self,
+        source: &AccountId,
+        target: &AccountId,
+    ) -> Result<AccountMerge, MergeError> {
+        if source == target {
+            return Err(MergeError::SameAccount);
+        }
+
+        let target_account = {
+            let account_service = self.account_service.lock().unwrap();
+            let source_account = account_service
+                .find_by_id_or_fail(source)
+                .map_err(|_| MergeError::SourceNotFound(source.clone()))?;
+            let target_account = account_service
+                .find_by_id_or_fail(target)
+                .map_err(|_| MergeError::TargetNotFound(target.clone()))?;
+
+            if target_account.status() == AccountStatus::Closed {
+                return Err(MergeError::TargetClosed);
+            }
+            if source_account.currency() != target_account.currency() {
+                return Err(MergeError::CurrencyMismatch);
+            }
+            if source_account.account_type() != target_account.account_type() {
+                return Err(MergeError::AccountTypeMismatch);
+            }
+            if Amount::new(source_account.balance().value() + target_account.balance().value())
+                .is_err()
+            {
+                return Err(MergeError::BalanceOverflow);
+            }
+
+            target_account
+        };
+
+        let transaction_ids = self
+            .transaction_service
+            .lock()
+            .unwrap()
+            .move_transactions(source, target);
+        let recurring_transaction_ids = self.move_recurring_transactions(source, target);
+        let goal_ids = self.move_goals(source, target);
+        let loan_ids = self.move_loans(source, target);
+        let rule_ids = self.move_rules(source, target);
+        let interest_config = self.move_interest_config(source, target);
+        let statement_config = self.move_statement_config(source, target);
+
+        let account_service = self.account_service.lock().unwrap();
+        let identifiers = account_service.move_identifiers_and_tags(source, target);
+        let reason = format!("Merged into {}", target_account.name());
+        // A closed account keeps its status, and its history of changes.
+        let source_account = account_service
+            .change_status(source, AccountStatus::Closed, &reason)
+            .or_else(|_| account_service.find_by_id_or_fail(source))
+            .unwrap();
+
+        Ok(AccountMerge {
+            source: source_account,
+            target: account_service.find_by_id_or_fail(target).unwrap(),
+            transaction_ids,
+            recurring_transaction_ids,
+            goal_ids,
+            loan_ids,
+            rule_ids,
+            identifiers,
+            interest_config,
+            statement_config,
+        })
+    }
+
+    fn move_recurring_transactions(
+        &self,
+        source: &AccountId,
+        target: &AccountId,
+    ) -> Vec<RecurringTransactionId> {
+        let mut repository = self.recurring_transaction_repository.lock().unwrap();
+        let mut moved: Vec<RecurringTransactionId> = vec![];
+        for mut recurring_transaction in repository.find_all() {
+            if &recurring_transaction.template().account_id != source {
+                continue;
+            }
+
+            let mut template = recurring_transaction.template().clone();
+            template.account_id = target.clone();
+            recurring_transaction.set_template(template);
+            let id = recurring_transaction.id().unwrap().clone();
+            repository
+                .find_by_id_and_update(id.clone(), recurring_transaction)
+                .unwrap();
+            moved.push(id);
+        }
+
+        moved
+    }
+
+    /// The goals saving on both accounts list the target once.
+    fn move_goals(&self, source: &AccountId, target: &AccountId) -> Vec<GoalId> {
+        let mut repository = self.goal_repository.lock().unwrap();
+        let mut moved: Vec<GoalId> = vec![];
+        for mut goal in repository.find_all() {
+            if !goal.account_ids().contains(source) {
+                continue;
+            }
+
+            let mut account_ids: Vec<AccountId> = vec![];
+            for account_id in goal.account_ids() {
+                let account_id = if account_id == source {
+                    target
+                } else {
+                    account_id
+                };
+                if !account_ids.contains(account_id) {
+                    account_ids.push(account_id.clone());
+                }
+            }
+            goal.set_account_ids(account_ids);
+            let id = goal.id().unwrap().clone();
+            repository.find_by_id_and_update(id.clone(), goal).unwrap();
+            moved.push(id);
+        }
+
+        moved
+    }
+
+    fn move_loans(&self, source: &AccountId, target: &AccountId) -> Vec<LoanId> {
+        let mut repository = self.loan_repository.lock().unwrap();
+        let mut moved: Vec<LoanId> = vec![];
+        for mut loan in repository.find_all() {
+            if loan.account_id() != source {
+                continue;
+            }
+
+            loan.set_account_id(target.clone());
+            let id = loan.id().unwrap().clone();
+            repository.find_by_id_and_update(id.clone(), loan).unwrap();
+            moved.push(id);
+        }
+
+        moved
+    }
+
+    fn move_rules(&self, source: &AccountId, target: &AccountId) -> Vec<RuleId> {
+        let mut repository = self.rule_repository.lock().unwrap();
+        let mut moved: Vec<RuleId> = vec![];
+        for mut rule in repository.find_all() {
+            if rule.conditions().account_id.as_ref() != Some(source) {
+                continue;
+            }
+
+            let mut conditions = rule.conditions().clone();
+            conditions.account_id = Some(target.clone());
+            rule.set_conditions(conditions);
+            let id = rule.id().unwrap().clone();
+            repository.find_by_id_and_update(id.clone(), rule).unwrap();
+            moved.push(id);
+        }
+
+        moved
+    }
+
+    fn move_interest_config(&self, source: &AccountId, target: &AccountId) -> ConfigMerge {
+        let mut repository = self.interest_config_repository.lock().unwrap();
+        let Some(mut config) = repository.find_by_account_id(source.clone()) else {
+            return ConfigMerge::NotConfigured;
+        };
+
+        repository.delete(source.clone()).unwrap();
+        if repository.find_by_account_id(target.clone()).is_some() {
+            return ConfigMerge::Dropped;
+        }
+        config.set_account_id(target.clone());
+        repository.save(config);
+
+        ConfigMerge::Moved
+    }
+
+    fn move_statement_config(&self, source: &AccountId, target: &AccountId) -> ConfigMerge {
+        let mut repository = self.statement_config_repository.lock().unwrap();
+        let Some(mut config) = repository.find_by_account_id(source.clone()) else {
+            return ConfigMerge::NotConfigured;
+        };
+
+        repository.delete(source.clone()).unwrap();
+        if repository.find_by_account_id(target.clone()).is_some() {
+            return ConfigMerge::Dropped;
+        }
+        config.set_account_id(target.clone());
+        repository.save(config);
+
+        ConfigMerge::Moved
+    }
+}
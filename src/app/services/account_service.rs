@@ -1,21 +1,39 @@
-use crate::app::entities::account::Account;
-use crate::app::entities::common::EntityId;
-use crate::app::repositories::account_repository::{AccountRepository, FindByIdAndUpdateError};
-use crate::app::typing::amount::Amount;
+use crate::app::entities::account::{Account, AccountId, AccountStatus, StatusChange};
+use crate::app::entities::tag::TagId;
+use crate::app::repositories::account_repository::{
+    AccountRepository, CreateError as RepositoryCreateError, DeleteError, FindByIdAndUpdateError,
+};
+use crate::app::repositories::common::{PageIterator, STREAM_BATCH_SIZE};
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::typing::account_identifier::AccountIdentifier;
+use crate::app::typing::amount::{Amount, MIN_AMOUNT};
+use crate::app::typing::currency::Currency;
+use chrono::{DateTime, Utc};
 use log::info;
+use serde::Serialize;
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
+use utoipa::ToSchema;
 
 #[derive(Error, Debug)]
 pub enum FindByIdOrFailError {
     #[error("Account not found by the provided id")]
-    NotFound(EntityId),
+    NotFound(AccountId),
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum CreateError {
     #[error("Account ID must not be provided")]
     EntityIdProvided,
+
+    #[error("The account type is not held at an institution")]
+    InstitutionNotAllowed,
+
+    #[error("Another account already has the identifier {0}")]
+    DuplicateIdentifier(AccountIdentifier),
+
+    #[error("Failed to create account")]
+    FailedToCreateAccount,
 }
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -25,49 +43,136 @@ pub enum UpdateError {
 
     #[error("Insufficient funds")]
     InsufficientFunds,
+
+    #[error("Another account already has the identifier {0}")]
+    DuplicateIdentifier(AccountIdentifier),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum StatusError {
+    #[error("Account not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("A reason must be given to change the status of an account")]
+    EmptyReason,
+
+    #[error("The account is already {0}")]
+    Unchanged(AccountStatus),
+
+    #[error("Only an account with a zero balance can be closed")]
+    NonZeroBalance,
+
+    #[error("Another account already has the identifier {0}")]
+    DuplicateIdentifier(AccountIdentifier),
+}
+
+/// The balance of an account at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct AccountBalance {
+    pub account_id: AccountId,
+    pub currency: Currency,
+    #[schema(value_type = f32)]
+    pub balance: Amount,
+    pub at: DateTime<Utc>,
 }
 
-pub struct AccountService<R> {
+pub struct AccountService<R, TR> {
     account_repository: Arc<Mutex<R>>,
+    transaction_repository: Arc<Mutex<TR>>,
 }
 
-impl<R: AccountRepository> AccountService<R> {
-    pub fn new(account_repository: Arc<Mutex<R>>) -> Self {
-        Self { account_repository }
+impl<R: AccountRepository, TR: TransactionRepository> AccountService<R, TR> {
+    pub fn new(account_repository: Arc<Mutex<R>>, transaction_repository: Arc<Mutex<TR>>) -> Self {
+        Self {
+            account_repository,
+            transaction_repository,
+        }
     }
 
     /// Retrieves all `Account` objects from the repository.
     /// # Notes:
-    /// Improve by making this API function return a stream or introduce a new method.
+    /// All the accounts are loaded in memory, prefer `stream` for large listings.
     /// # Returns
-    /// * `&Vec<Account>` — A reference to the vector containing all `Account` objects.
-    pub fn find_all<'a>(&self) -> Vec<Account> {
-        self.account_repository.lock().unwrap().find_all()
+    /// * `Vec<Account>` — All the `Account` objects, in creation order.
+    pub fn find_all(&self) -> Vec<Account> {
+        self.stream().collect()
     }
 
-    pub fn create(&self, account: Account) -> Result<EntityId, CreateError> {
+    /// Iterates over all the accounts, in creation order.
+    /// The accounts are fetched from the repository in batches, while the iterator is consumed,
+    /// so the listing runs in bounded memory. The repository is only locked during a fetch.
+    pub fn stream(&self) -> impl Iterator<Item = Account> {
+        let account_repository = self.account_repository.clone();
+
+        PageIterator::new(move |cursor| {
+            let after: Option<AccountId> = cursor.map(|cursor| {
+                cursor
+                    .parse()
+                    .expect("The cursor is an account id from the previous page")
+            });
+            account_repository
+                .lock()
+                .unwrap()
+                .find_page(after, STREAM_BATCH_SIZE)
+        })
+    }
+
+    /// Retrieves the accounts carrying all the provided tags, in creation order.
+    /// The accounts are filtered while they are streamed, so they are never all held in memory.
+    pub fn find_by_tags(&self, tag_ids: &[TagId]) -> Vec<Account> {
+        self.stream()
+            .filter(|account| tag_ids.iter().all(|tag_id| account.tags().contains(tag_id)))
+            .collect()
+    }
+
+    pub fn create(&self, mut account: Account) -> Result<AccountId, CreateError> {
         // The request to create an account must not have an ID.
         // If it does, throw an error. It should be provided by the repository because.
         // It is a unique identifier for the account. And the repository that handles generating it.
-        match account.id() {
-            Some(_) => return Err(CreateError::EntityIdProvided),
-            None => (),
+        if account.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        // The cash is held in hand, it can not reference an institution.
+        if account.institution_id().is_some() && !account.account_type().is_held_at_institution() {
+            return Err(CreateError::InstitutionNotAllowed);
         }
 
+        account.set_identifiers(unique_identifiers(account.identifiers()));
+
         // Return the generated ID.
-        Ok(self
+        match self.account_repository.lock().unwrap().create(account) {
+            Ok(id) => Ok(id),
+            Err(RepositoryCreateError::DuplicateIdentifier(identifier)) => {
+                Err(CreateError::DuplicateIdentifier(identifier))
+            }
+            Err(RepositoryCreateError::FailedToCreateAccount) => {
+                Err(CreateError::FailedToCreateAccount)
+            }
+        }
+    }
+
+    /// Deletes an account which has no transactions, like one whose opening balance was not recorded.
+    ///
+    /// # Errors
+    /// * `FindByIdOrFailError::NotFound` -- If the account does not exist.
+    pub fn delete(&self, account_id: &AccountId) -> Result<(), FindByIdOrFailError> {
+        match self
             .account_repository
             .lock()
             .unwrap()
-            .create(account)
-            .unwrap())
+            .delete(account_id.clone())
+        {
+            Ok(()) => Ok(()),
+            Err(DeleteError::NotFound) => Err(FindByIdOrFailError::NotFound(account_id.clone())),
+        }
     }
 
-    fn find_account_to_update(&self, account_id: &EntityId) -> Result<Account, UpdateError> {
+    fn find_account_to_update(&self, account_id: &AccountId) -> Result<Account, UpdateError> {
         // call self.find_by_id_or_fail() to check that the provided account_id exists.
-        match self.find_by_id_or_fail(&account_id) {
+        match self.find_by_id_or_fail(account_id) {
             // If it exists in the repository, return the cloned value
-            Ok(account) => Ok(account.clone()),
+            Ok(account) => Ok(account),
             // If it does not exist, return an error, with AccountServiceUpdateError::EntityIdNotFound
             Err(error) => {
                 match error {
@@ -81,32 +186,35 @@ impl<R: AccountRepository> AccountService<R> {
 
     fn update_account(
         &self,
-        account_id: &EntityId,
+        account_id: &AccountId,
         account: Account,
-    ) -> Result<EntityId, UpdateError> {
+    ) -> Result<AccountId, UpdateError> {
         let mut repository = self.account_repository.lock().unwrap();
 
         match repository.find_by_id_and_update(account_id.clone(), account.clone()) {
             Ok(entity_id) => Ok(entity_id),
             Err(error) => match error {
                 FindByIdAndUpdateError::NotFound => Err(UpdateError::EntityIdNotFound),
+                FindByIdAndUpdateError::DuplicateIdentifier(identifier) => {
+                    Err(UpdateError::DuplicateIdentifier(identifier))
+                }
             },
         }
     }
 
     pub fn withdraw(
         &self,
-        account_id: &EntityId,
+        account_id: &AccountId,
         withdrawn_amount: &Amount,
     ) -> Result<Account, UpdateError> {
         // Find the account to withdraw the amount it.
         // We are testing that the provided account_id to update has a corresponding account in the repository (data layer).
         // We are using the `?` Operator to unwrap the result. Which will return the same error if the account does not exist.
-        let mut account = self.find_account_to_update(&account_id)?;
+        let mut account = self.find_account_to_update(account_id)?;
 
         // Check if the account has enough funds to withdraw the requested amount.
         // If not, throw an InsufficientFunds error.
-        if account.balance() < &withdrawn_amount {
+        if account.balance() < withdrawn_amount {
             return Err(UpdateError::InsufficientFunds);
         }
 
@@ -116,39 +224,143 @@ impl<R: AccountRepository> AccountService<R> {
 
         // Update the account in the repository (data layer)
         // This should return the updated entity ID.
-        let entity_id = self.update_account(&account_id, account)?;
+        let entity_id = self.update_account(account_id, account)?;
 
         // Return the updated account
         // Assuming it will return an OK result,
         // since we have already asserted that the account exists.
-        Ok(self.find_by_id_or_fail(&entity_id).unwrap().clone())
+        Ok(self.find_by_id_or_fail(&entity_id).unwrap())
     }
 
     pub fn deposit(
         &self,
-        account_id: &EntityId,
+        account_id: &AccountId,
         deposited_amount: &Amount,
     ) -> Result<Account, UpdateError> {
         // Find the account to deposit the amount it.
         // We are testing that the provided account_id to update has a corresponding account in the repository (data layer).
         // We are using the `?` Operator to unwrap the result. Which will return the same error if the account does not exist.
-        let mut account = self.find_account_to_update(&account_id)?;
+        let mut account = self.find_account_to_update(account_id)?;
 
         // Update the account in in-place
         // With the provided setter function.
-        account.deposit(&deposited_amount);
+        account.deposit(deposited_amount);
 
         // Update the account in the repository (data layer)
         // This should return the updated entity ID.
-        let entity_id = self.update_account(&account_id, account)?;
+        let entity_id = self.update_account(account_id, account)?;
 
         // Return the updated account
         // Assuming it will return an OK result,
         // since we have already asserted that the account exists.
-        Ok(self.find_by_id_or_fail(&entity_id).unwrap().clone())
+        Ok(self.find_by_id_or_fail(&entity_id).unwrap())
+    }
+
+    /// Replaces the identifiers of an account, an identifier given twice is kept once.
+    /// #### Errors
+    /// * `UpdateError::EntityIdNotFound` — If there is no account with the provided ID.
+    /// * `UpdateError::DuplicateIdentifier` — If another account already has one of the identifiers.
+    pub fn set_identifiers(
+        &self,
+        account_id: &AccountId,
+        identifiers: &[AccountIdentifier],
+    ) -> Result<Account, UpdateError> {
+        let mut account = self.find_account_to_update(account_id)?;
+        account.set_identifiers(unique_identifiers(identifiers));
+
+        let entity_id = self.update_account(account_id, account)?;
+        Ok(self.find_by_id_or_fail(&entity_id).unwrap())
+    }
+
+    /// Freezes, closes or reopens an account, the change is recorded in its history with the reason.
+    /// #### Errors
+    /// * `StatusError::EntityIdNotFound` — If there is no account with the provided ID.
+    /// * `StatusError::EmptyReason` — If the reason is blank.
+    /// * `StatusError::Unchanged` — If the account already has the status.
+    /// * `StatusError::NonZeroBalance` — If the account is closed while it still holds money.
+    pub fn change_status(
+        &self,
+        account_id: &AccountId,
+        status: AccountStatus,
+        reason: &str,
+    ) -> Result<Account, StatusError> {
+        let mut account = self
+            .find_by_id_or_fail(account_id)
+            .map_err(|_| StatusError::EntityIdNotFound)?;
+        validate_status_change(&account, status, reason)?;
+        if status == AccountStatus::Closed && account.balance() != &MIN_AMOUNT {
+            return Err(StatusError::NonZeroBalance);
+        }
+
+        account.change_status(StatusChange {
+            status,
+            reason: reason.trim().to_string(),
+            changed_at: Utc::now(),
+        });
+
+        self.update_account(account_id, account)
+            .map_err(|error| match error {
+                UpdateError::EntityIdNotFound => StatusError::EntityIdNotFound,
+                UpdateError::DuplicateIdentifier(identifier) => {
+                    StatusError::DuplicateIdentifier(identifier)
+                }
+                // The balance is left unchanged by a status change.
+                UpdateError::InsufficientFunds => unreachable!(),
+            })?;
+        Ok(self.find_by_id_or_fail(account_id).unwrap())
+    }
+
+    /// Moves the identifiers and the tags of the `source` account to the `target` account, which keeps its own.
+    /// # Returns
+    /// * `Vec<AccountIdentifier>` — The identifiers moved.
+    /// # Panics
+    /// If one of the accounts does not exist, the caller checks both.
+    pub fn move_identifiers_and_tags(
+        &self,
+        source: &AccountId,
+        target: &AccountId,
+    ) -> Vec<AccountIdentifier> {
+        let mut repository = self.account_repository.lock().unwrap();
+        let mut source_account = repository.find_by_id(source.clone()).unwrap();
+        let mut target_account = repository.find_by_id(target.clone()).unwrap();
+        let moved = source_account.identifiers().clone();
+
+        // The source gives its identifiers up first, a unique identifier is used by a single account.
+        source_account.set_identifiers(vec![]);
+        repository
+            .find_by_id_and_update(source.clone(), source_account.clone())
+            .unwrap();
+
+        let identifiers: Vec<AccountIdentifier> = target_account
+            .identifiers()
+            .iter()
+            .chain(&moved)
+            .cloned()
+            .collect();
+        target_account.set_identifiers(unique_identifiers(&identifiers));
+        let mut tags = target_account.tags().clone();
+        for tag in source_account.tags() {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        target_account.set_tags(tags);
+        repository
+            .find_by_id_and_update(target.clone(), target_account)
+            .unwrap();
+
+        moved
+    }
+
+    /// Retrieves the accounts having the identifier, the imported transactions are matched to them.
+    pub fn find_by_identifier(&self, identifier: &AccountIdentifier) -> Vec<Account> {
+        self.account_repository
+            .lock()
+            .unwrap()
+            .find_by_identifier(identifier)
     }
 
-    pub fn find_by_id(&self, id: EntityId) -> Option<Account> {
+    pub fn find_by_id(&self, id: AccountId) -> Option<Account> {
         info!("Finding account by id: {:?}", id);
 
         self.account_repository.lock().unwrap().find_by_id(id)
@@ -161,12 +373,88 @@ impl<R: AccountRepository> AccountService<R> {
     ///  * `Result<&Account, FindByIdOrFailError>` — A `Result` containing the `Account` object if it exists, or an error if it does not.
     ///  #### Errors
     ///  * `FindByIdOrFailError::NotFound` — If the `Account` object with the provided ID does not exist.
-    pub fn find_by_id_or_fail(&self, id: &EntityId) -> Result<Account, FindByIdOrFailError> {
+    pub fn find_by_id_or_fail(&self, id: &AccountId) -> Result<Account, FindByIdOrFailError> {
         let _repository = self.account_repository.lock().unwrap();
 
         match _repository.find_by_id(id.clone()) {
-            Some(account) => Ok(account.clone()),
+            Some(account) => Ok(account),
             None => Err(FindByIdOrFailError::NotFound(id.clone())),
         }
     }
+
+    /// The balance the account had at a point in time, read from its transaction history: the closing balance
+    /// of its last transaction on or before then, or the opening balance of its first transaction after.
+    /// An account without transactions always had its current balance.
+    fn account_balance_at(
+        transaction_repository: &TR,
+        account: &Account,
+        at: DateTime<Utc>,
+    ) -> AccountBalance {
+        let account_id = account.id().unwrap();
+
+        AccountBalance {
+            account_id: account_id.clone(),
+            currency: account.currency().clone(),
+            balance: transaction_repository
+                .balance_at(account_id, at)
+                .unwrap_or_else(|| account.balance().clone()),
+            at,
+        }
+    }
+
+    /// The balance of an account at a point in time.
+    ///  #### Errors
+    ///  * `FindByIdOrFailError::NotFound` — If the account does not exist.
+    pub fn balance_at(
+        &self,
+        id: &AccountId,
+        at: DateTime<Utc>,
+    ) -> Result<AccountBalance, FindByIdOrFailError> {
+        let account = self.find_by_id_or_fail(id)?;
+
+        Ok(Self::account_balance_at(
+            &self.transaction_repository.lock().unwrap(),
+            &account,
+            at,
+        ))
+    }
+
+    /// The balances of all the accounts at a point in time, in creation order.
+    pub fn balances_at(&self, at: DateTime<Utc>) -> Vec<AccountBalance> {
+        let accounts = self.find_all();
+        let transaction_repository = self.transaction_repository.lock().unwrap();
+
+        accounts
+            .iter()
+            .map(|account| Self::account_balance_at(&transaction_repository, account, at))
+            .collect()
+    }
+}
+
+/// Checks that the account can be given the status for the reason.
+/// The balance of a closed account is left to the caller, it may be swept before the account is closed.
+pub fn validate_status_change(
+    account: &Account,
+    status: AccountStatus,
+    reason: &str,
+) -> Result<(), StatusError> {
+    if reason.trim().is_empty() {
+        return Err(StatusError::EmptyReason);
+    }
+    if account.status() == status {
+        return Err(StatusError::Unchanged(status));
+    }
+
+    Ok(())
+}
+
+/// The identifiers without the repeated ones, in the order they were given.
+fn unique_identifiers(identifiers: &[AccountIdentifier]) -> Vec<AccountIdentifier> {
+    let mut unique: Vec<AccountIdentifier> = vec![];
+    for identifier in identifiers {
+        if !unique.contains(identifier) {
+            unique.push(identifier.clone());
+        }
+    }
+    unique
 }
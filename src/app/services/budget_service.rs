@@ -0,0 +1,346 @@
+use crate::app::entities::budget::{Budget, BudgetId, BudgetScope};
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+use crate::app::repositories::budget_repository::{
+    BudgetRepository, DeleteError as RepositoryDeleteError,
+};
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::common::stream_transactions;
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+use crate::app::repositories::tag_repository::TagRepository;
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::services::category_service::CategoryService;
+use crate::app::typing::currency::Currency;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Budget not found by the provided id")]
+    NotFound(BudgetId),
+}
+
+/// The reasons a budget can not be saved, whether it is created or updated.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The budget name must not be empty")]
+    EmptyName,
+
+    #[error("The budget amount must be greater than zero")]
+    ZeroAmount,
+
+    #[error("The category of the budget was not found")]
+    CategoryNotFound(CategoryId),
+
+    #[error("A budget applies to expenses, its category must be an expense category")]
+    IncomeCategory,
+
+    #[error("The tag of the budget was not found")]
+    TagNotFound(TagId),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Budget ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Budget not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Budget not found by the provided id")]
+    EntityIdNotFound,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum StatusError {
+    #[error("Budget not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("The budget has not started at this date")]
+    NotStarted,
+}
+
+/// The progress of a budget over one of its periods.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct BudgetStatus {
+    pub budget_id: BudgetId,
+
+    pub currency: Currency,
+
+    /// The beginning of the period.
+    pub period_start: DateTime<Utc>,
+
+    /// The end of the period, excluded.
+    pub period_end: DateTime<Utc>,
+
+    /// The amount of the budget for a period.
+    pub budgeted: f32,
+
+    /// The amount left unspent by the previous periods, carried over when the budget rolls over.
+    pub rolled_over: f32,
+
+    /// The amount which can be spent over the period: the budgeted and rolled over amounts.
+    pub available: f32,
+
+    /// The total of the confirmed expenses of the period so far.
+    pub spent: f32,
+
+    /// The amount which can still be spent over the period, negative when it is overspent.
+    pub remaining: f32,
+
+    /// The number of expenses of the period so far.
+    pub transaction_count: usize,
+
+    /// The share of the period elapsed, from 0 to 1.
+    pub elapsed: f32,
+
+    /// The amount spent at the end of the period, if the spending goes on at the same pace.
+    pub projected_spent: f32,
+
+    /// How much the projected amount exceeds the available one, 0 when the budget should be kept.
+    pub projected_overspend: f32,
+}
+
+/// The category service, shared with the API which manages the categories.
+type SharedCategoryService<CR, TR, PR, RTR, BR> = Arc<Mutex<CategoryService<CR, TR, PR, RTR, BR>>>;
+
+pub struct BudgetService<R, TR, CR, TGR, PR, RTR> {
+    budget_repository: Arc<Mutex<R>>,
+    transaction_repository: Arc<Mutex<TR>>,
+    category_service: SharedCategoryService<CR, TR, PR, RTR, R>,
+    tag_repository: Arc<Mutex<TGR>>,
+}
+
+impl<
+        R: BudgetRepository,
+        TR: TransactionRepository,
+        CR: CategoryRepository,
+        TGR: TagRepository,
+        PR: PayeeRepository,
+        RTR: RecurringTransactionRepository,
+    > BudgetService<R, TR, CR, TGR, PR, RTR>
+{
+    pub fn new(
+        budget_repository: Arc<Mutex<R>>,
+        transaction_repository: Arc<Mutex<TR>>,
+        category_service: SharedCategoryService<CR, TR, PR, RTR, R>,
+        tag_repository: Arc<Mutex<TGR>>,
+    ) -> Self {
+        Self {
+            budget_repository,
+            transaction_repository,
+            category_service,
+            tag_repository,
+        }
+    }
+
+    /// Retrieves all the budgets, in creation order.
+    pub fn find_all(&self) -> Vec<Budget> {
+        self.budget_repository.lock().unwrap().find_all()
+    }
+
+    pub fn find_by_id(&self, id: BudgetId) -> Option<Budget> {
+        self.budget_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Retrieves a budget by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no budget with the provided ID.
+    pub fn find_by_id_or_fail(&self, id: &BudgetId) -> Result<Budget, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Trims the name of the budget, then checks its amount and what it applies to.
+    fn validate(&self, budget: &mut Budget) -> Result<(), ValidationError> {
+        let name = budget.name().trim().to_string();
+        if name.is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+        budget.set_name(name);
+
+        if budget.amount().value() <= 0.0 {
+            return Err(ValidationError::ZeroAmount);
+        }
+
+        match budget.scope() {
+            BudgetScope::Category(category_id) => {
+                let category = self
+                    .category_service
+                    .lock()
+                    .unwrap()
+                    .find_by_id(category_id.clone())
+                    .ok_or_else(|| ValidationError::CategoryNotFound(category_id.clone()))?;
+                if category.kind() != &TransactionType::Expense {
+                    return Err(ValidationError::IncomeCategory);
+                }
+            }
+            BudgetScope::Tag(tag_id) => {
+                if self
+                    .tag_repository
+                    .lock()
+                    .unwrap()
+                    .find_by_id(tag_id.clone())
+                    .is_none()
+                {
+                    return Err(ValidationError::TagNotFound(tag_id.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn create(&self, mut budget: Budget) -> Result<BudgetId, CreateError> {
+        if budget.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&mut budget)?;
+
+        Ok(self.budget_repository.lock().unwrap().create(budget))
+    }
+
+    pub fn update(&self, id: &BudgetId, mut budget: Budget) -> Result<Budget, UpdateError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(UpdateError::EntityIdNotFound);
+        }
+        budget.set_id(Some(id.clone()));
+
+        self.validate(&mut budget)?;
+
+        self.budget_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), budget.clone())
+            .map(|_| budget)
+            .map_err(|_| UpdateError::EntityIdNotFound)
+    }
+
+    pub fn delete(&self, id: &BudgetId) -> Result<(), DeleteError> {
+        match self.budget_repository.lock().unwrap().delete(id.clone()) {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// Computes the progress of a budget over the period containing `at`, from the confirmed expenses
+    /// made up to `at` included. With rollover, the amounts left unspent by the previous periods
+    /// are carried over, an overspent period resets what is carried over to 0.
+    /// #### Errors
+    /// * `StatusError::EntityIdNotFound` — If there is no budget with the provided ID.
+    /// * `StatusError::NotStarted` — If `at` is before the first period of the budget.
+    pub fn status(&self, id: &BudgetId, at: DateTime<Utc>) -> Result<BudgetStatus, StatusError> {
+        let budget = self
+            .find_by_id(id.clone())
+            .ok_or(StatusError::EntityIdNotFound)?;
+        let periods = budget.periods_until(at);
+        let (Some(first_period), Some(&(period_start, period_end))) =
+            (periods.first(), periods.last())
+        else {
+            return Err(StatusError::NotStarted);
+        };
+
+        // The expenses of a category include the ones of its sub categories.
+        let category_ids = match budget.scope() {
+            BudgetScope::Category(category_id) => self
+                .category_service
+                .lock()
+                .unwrap()
+                .find_with_descendants(category_id)
+                .unwrap_or_else(|_| vec![category_id.clone()]),
+            BudgetScope::Tag(_) => vec![],
+        };
+        let query = TransactionQuery {
+            from: Some(first_period.0),
+            // The end of the query is excluded, the expenses made right at `at` are spent already.
+            to: Some((at + TimeDelta::nanoseconds(1)).min(period_end)),
+            transaction_type: Some(TransactionType::Expense),
+            status: Some(TransactionStatus::Confirmed),
+            currency: Some(budget.currency().clone()),
+            category_ids: category_ids.clone(),
+            tag_ids: match budget.scope() {
+                BudgetScope::Category(_) => vec![],
+                BudgetScope::Tag(tag_id) => vec![tag_id.clone()],
+            },
+            ..Default::default()
+        };
+
+        let mut spent_per_period = vec![0f32; periods.len()];
+        let mut transaction_count = 0;
+        let transactions = stream_transactions(self.transaction_repository.clone(), query);
+        for transaction in transactions {
+            // Only the splits of the budgeted categories count for a split transaction.
+            let amount: f32 = match budget.scope() {
+                BudgetScope::Category(_) => transaction
+                    .category_amounts()
+                    .into_iter()
+                    .filter(|(category_id, _)| {
+                        category_id.is_some_and(|category_id| category_ids.contains(category_id))
+                    })
+                    .map(|(_, amount)| amount.value())
+                    .sum(),
+                BudgetScope::Tag(_) => transaction.amount().value(),
+            };
+
+            let index = periods.partition_point(|(start, _)| *start <= transaction.date()) - 1;
+            spent_per_period[index] += amount;
+            if index == periods.len() - 1 {
+                transaction_count += 1;
+            }
+        }
+
+        let budgeted = budget.amount().value();
+        let mut rolled_over = 0f32;
+        if budget.rollover() {
+            for spent in &spent_per_period[..periods.len() - 1] {
+                rolled_over = (budgeted + rolled_over - spent).max(0.0);
+            }
+        }
+        let available = budgeted + rolled_over;
+        let spent = spent_per_period[periods.len() - 1];
+
+        let elapsed = ((at - period_start).num_seconds() as f32
+            / (period_end - period_start).num_seconds() as f32)
+            .clamp(0.0, 1.0);
+        let projected_spent = if elapsed > 0.0 {
+            spent / elapsed
+        } else {
+            spent
+        };
+
+        Ok(BudgetStatus {
+            budget_id: id.clone(),
+            currency: budget.currency().clone(),
+            period_start,
+            period_end,
+            budgeted,
+            rolled_over,
+            available,
+            spent,
+            remaining: available - spent,
+            transaction_count,
+            elapsed,
+            projected_spent,
+            projected_overspend: (projected_spent - available).max(0.0),
+        })
+    }
+}
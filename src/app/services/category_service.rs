@@ -0,0 +1,606 @@
+use crate::app::entities::budget::BudgetScope;
+use crate::app::entities::category::{Category, CategoryId};
+use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+use crate::app::repositories::budget_repository::BudgetRepository;
+use crate::app::repositories::category_repository::{
+    CategoryRepository, DeleteError as RepositoryDeleteError, FindByIdAndUpdateError,
+};
+use crate::app::repositories::common::stream_transactions;
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::typing::currency::Currency;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Category not found by the provided id")]
+    NotFound(CategoryId),
+}
+
+/// The reasons a category can not be saved, whether it is created or updated.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The category name must not be empty")]
+    EmptyName,
+
+    #[error("The colour must be a hex code, like #4caf50")]
+    InvalidColor,
+
+    #[error("The parent category was not found")]
+    ParentNotFound(CategoryId),
+
+    #[error("A sub category must have the kind of its parent")]
+    KindMismatch,
+
+    #[error("A category with the same name and parent already exists")]
+    DuplicateName,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Category ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Category not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("A category can not be moved under itself or one of its sub categories")]
+    CyclicParent,
+
+    #[error("The kind of a category can not change while it has sub categories or transactions")]
+    KindChangeNotAllowed,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Category not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("The category has sub categories, they must be deleted or moved first")]
+    HasSubCategories,
+
+    #[error("The category is used by some transactions")]
+    InUse,
+
+    #[error("The category is the default category of some payees")]
+    UsedByPayees,
+
+    #[error("The category is used by some recurring transactions")]
+    UsedByRecurringTransactions,
+
+    #[error("The category has some budgets")]
+    UsedByBudgets,
+}
+
+/// The total of the transactions of a category, in one currency.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CategoryTotal {
+    /// The category, None for the transactions without category.
+    pub category_id: Option<CategoryId>,
+
+    pub currency: Currency,
+
+    /// The number of transactions of the category itself (a split transaction counts in each of its categories).
+    pub transaction_count: usize,
+
+    /// The total amount of the transactions of the category itself.
+    pub amount: f32,
+
+    /// The total amount of the transactions of the category and of all its sub categories.
+    pub total: f32,
+}
+
+/// A category of the default taxonomy, with its sub categories.
+struct DefaultCategory {
+    name: &'static str,
+    kind: TransactionType,
+    color: &'static str,
+    icon: &'static str,
+    children: &'static [&'static str],
+}
+
+/// The taxonomy created on the first start, it can then be customized freely.
+const DEFAULT_CATEGORIES: &[DefaultCategory] = &[
+    DefaultCategory {
+        name: "Salary",
+        kind: TransactionType::Income,
+        color: "#4caf50",
+        icon: "briefcase",
+        children: &["Wages", "Bonus"],
+    },
+    DefaultCategory {
+        name: "Business",
+        kind: TransactionType::Income,
+        color: "#009688",
+        icon: "store",
+        children: &["Sales", "Services"],
+    },
+    DefaultCategory {
+        name: "Investments",
+        kind: TransactionType::Income,
+        color: "#3f51b5",
+        icon: "chart-line",
+        children: &["Interest", "Dividends"],
+    },
+    DefaultCategory {
+        name: "Gifts received",
+        kind: TransactionType::Income,
+        color: "#e91e63",
+        icon: "gift",
+        children: &[],
+    },
+    DefaultCategory {
+        name: "Other income",
+        kind: TransactionType::Income,
+        color: "#9e9e9e",
+        icon: "plus",
+        children: &[],
+    },
+    DefaultCategory {
+        name: "Food",
+        kind: TransactionType::Expense,
+        color: "#ff9800",
+        icon: "utensils",
+        children: &["Groceries", "Restaurants"],
+    },
+    DefaultCategory {
+        name: "Housing",
+        kind: TransactionType::Expense,
+        color: "#795548",
+        icon: "home",
+        children: &["Rent", "Utilities", "Maintenance"],
+    },
+    DefaultCategory {
+        name: "Transport",
+        kind: TransactionType::Expense,
+        color: "#2196f3",
+        icon: "bus",
+        children: &["Public transport", "Fuel", "Taxi"],
+    },
+    DefaultCategory {
+        name: "Communication",
+        kind: TransactionType::Expense,
+        color: "#00bcd4",
+        icon: "phone",
+        children: &["Airtime", "Internet"],
+    },
+    DefaultCategory {
+        name: "Health",
+        kind: TransactionType::Expense,
+        color: "#f44336",
+        icon: "heart",
+        children: &["Medical", "Pharmacy"],
+    },
+    DefaultCategory {
+        name: "Education",
+        kind: TransactionType::Expense,
+        color: "#673ab7",
+        icon: "book",
+        children: &["School fees", "Books"],
+    },
+    DefaultCategory {
+        name: "Shopping",
+        kind: TransactionType::Expense,
+        color: "#ffc107",
+        icon: "shopping-bag",
+        children: &["Clothing", "Household"],
+    },
+    DefaultCategory {
+        name: "Entertainment",
+        kind: TransactionType::Expense,
+        color: "#9c27b0",
+        icon: "film",
+        children: &[],
+    },
+    DefaultCategory {
+        name: "Fees & charges",
+        kind: TransactionType::Expense,
+        color: "#607d8b",
+        icon: "receipt",
+        children: &[],
+    },
+    DefaultCategory {
+        name: "Gifts & donations",
+        kind: TransactionType::Expense,
+        color: "#e91e63",
+        icon: "hand-holding-heart",
+        children: &[],
+    },
+    DefaultCategory {
+        name: "Other expenses",
+        kind: TransactionType::Expense,
+        color: "#9e9e9e",
+        icon: "minus",
+        children: &[],
+    },
+];
+
+/// Whether the colour is a `#rrggbb` hex code.
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub struct CategoryService<R, TR, PR, RTR, BR> {
+    category_repository: Arc<Mutex<R>>,
+    transaction_repository: Arc<Mutex<TR>>,
+    payee_repository: Arc<Mutex<PR>>,
+    recurring_transaction_repository: Arc<Mutex<RTR>>,
+    budget_repository: Arc<Mutex<BR>>,
+}
+
+impl<
+        R: CategoryRepository,
+        TR: TransactionRepository,
+        PR: PayeeRepository,
+        RTR: RecurringTransactionRepository,
+        BR: BudgetRepository,
+    > CategoryService<R, TR, PR, RTR, BR>
+{
+    pub fn new(
+        category_repository: Arc<Mutex<R>>,
+        transaction_repository: Arc<Mutex<TR>>,
+        payee_repository: Arc<Mutex<PR>>,
+        recurring_transaction_repository: Arc<Mutex<RTR>>,
+        budget_repository: Arc<Mutex<BR>>,
+    ) -> Self {
+        Self {
+            category_repository,
+            transaction_repository,
+            payee_repository,
+            recurring_transaction_repository,
+            budget_repository,
+        }
+    }
+
+    /// Creates the default taxonomy, when there is no category yet.
+    ///
+    /// # Returns
+    /// * `usize` -- The number of categories created.
+    pub fn seed_default_categories(&self) -> usize {
+        let mut repository = self.category_repository.lock().unwrap();
+        if !repository.find_all().is_empty() {
+            return 0;
+        }
+
+        let mut created = 0;
+        for default in DEFAULT_CATEGORIES {
+            let parent_id = repository.create(Category::new(
+                None,
+                default.name.to_string(),
+                None,
+                default.kind.clone(),
+                Some(default.color.to_string()),
+                Some(default.icon.to_string()),
+            ));
+
+            for child in default.children {
+                repository.create(Category::new(
+                    None,
+                    child.to_string(),
+                    Some(parent_id.clone()),
+                    default.kind.clone(),
+                    None,
+                    None,
+                ));
+            }
+            created += 1 + default.children.len();
+        }
+
+        created
+    }
+
+    /// Retrieves all the categories, in creation order.
+    pub fn find_all(&self) -> Vec<Category> {
+        self.category_repository.lock().unwrap().find_all()
+    }
+
+    pub fn find_by_id(&self, id: CategoryId) -> Option<Category> {
+        self.category_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Retrieves a category by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no category with the provided ID.
+    pub fn find_by_id_or_fail(&self, id: &CategoryId) -> Result<Category, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Retrieves the ID of a category along with the IDs of all its sub categories, at any depth.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no category with the provided ID.
+    pub fn find_with_descendants(
+        &self,
+        id: &CategoryId,
+    ) -> Result<Vec<CategoryId>, FindByIdOrFailError> {
+        let categories = self.find_all();
+        if !categories.iter().any(|category| category.id() == Some(id)) {
+            return Err(FindByIdOrFailError::NotFound(id.clone()));
+        }
+
+        let mut ids = vec![id.clone()];
+        let mut index = 0;
+        // Breadth first walk, the ids found are visited in turn to collect their own children.
+        while index < ids.len() {
+            let parent_id = ids[index].clone();
+            ids.extend(
+                categories
+                    .iter()
+                    .filter(|category| category.parent_id() == Some(&parent_id))
+                    .map(|category| category.id().unwrap().clone()),
+            );
+            index += 1;
+        }
+
+        Ok(ids)
+    }
+
+    /// Checks the fields of the category against the rest of the taxonomy.
+    fn validate(
+        &self,
+        category: &Category,
+        categories: &[Category],
+    ) -> Result<(), ValidationError> {
+        if category.name().trim().is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+
+        if category
+            .color()
+            .as_ref()
+            .is_some_and(|color| !is_hex_color(color))
+        {
+            return Err(ValidationError::InvalidColor);
+        }
+
+        if let Some(parent_id) = category.parent_id() {
+            let parent = categories
+                .iter()
+                .find(|parent| parent.id() == Some(parent_id))
+                .ok_or_else(|| ValidationError::ParentNotFound(parent_id.clone()))?;
+
+            if parent.kind() != category.kind() {
+                return Err(ValidationError::KindMismatch);
+            }
+        }
+
+        let duplicate = categories.iter().any(|other| {
+            other.id() != category.id()
+                && other.parent_id() == category.parent_id()
+                && other.name().to_lowercase() == category.name().trim().to_lowercase()
+        });
+        if duplicate {
+            return Err(ValidationError::DuplicateName);
+        }
+
+        Ok(())
+    }
+
+    pub fn create(&self, category: Category) -> Result<CategoryId, CreateError> {
+        if category.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&category, &self.find_all())?;
+
+        Ok(self.category_repository.lock().unwrap().create(category))
+    }
+
+    /// Whether some transactions reference the category.
+    fn is_used(&self, id: &CategoryId) -> bool {
+        let query = TransactionQuery {
+            category_ids: vec![id.clone()],
+            limit: 1,
+            ..Default::default()
+        };
+
+        !self
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find(&query)
+            .items
+            .is_empty()
+    }
+
+    pub fn update(&self, id: &CategoryId, mut category: Category) -> Result<Category, UpdateError> {
+        let current = self
+            .find_by_id(id.clone())
+            .ok_or(UpdateError::EntityIdNotFound)?;
+        category.set_id(Some(id.clone()));
+
+        let categories = self.find_all();
+
+        // The new parent must not be the category itself, nor one of its descendants.
+        if let Some(parent_id) = category.parent_id() {
+            let descendants = self
+                .find_with_descendants(id)
+                .map_err(|_| UpdateError::EntityIdNotFound)?;
+            if descendants.contains(parent_id) {
+                return Err(UpdateError::CyclicParent);
+            }
+        }
+
+        if current.kind() != category.kind() {
+            let has_children = categories.iter().any(|child| child.parent_id() == Some(id));
+            if has_children || self.is_used(id) {
+                return Err(UpdateError::KindChangeNotAllowed);
+            }
+        }
+
+        self.validate(&category, &categories)?;
+
+        match self
+            .category_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), category.clone())
+        {
+            Ok(_) => Ok(category),
+            Err(FindByIdAndUpdateError::NotFound) => Err(UpdateError::EntityIdNotFound),
+        }
+    }
+
+    /// Deletes a category.
+    /// A category can only be deleted when no sub category, transaction, payee, recurring transaction or budget references it,
+    /// so the taxonomy and the history stay consistent.
+    pub fn delete(&self, id: &CategoryId) -> Result<(), DeleteError> {
+        let categories = self.find_all();
+        if !categories.iter().any(|category| category.id() == Some(id)) {
+            return Err(DeleteError::EntityIdNotFound);
+        }
+
+        if categories
+            .iter()
+            .any(|category| category.parent_id() == Some(id))
+        {
+            return Err(DeleteError::HasSubCategories);
+        }
+
+        if self.is_used(id) {
+            return Err(DeleteError::InUse);
+        }
+
+        if self
+            .payee_repository
+            .lock()
+            .unwrap()
+            .find_all()
+            .iter()
+            .any(|payee| payee.default_category_id() == Some(id))
+        {
+            return Err(DeleteError::UsedByPayees);
+        }
+
+        if self
+            .recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .find_all()
+            .iter()
+            .any(|recurring_transaction| {
+                recurring_transaction.template().category_id.as_ref() == Some(id)
+            })
+        {
+            return Err(DeleteError::UsedByRecurringTransactions);
+        }
+
+        if self
+            .budget_repository
+            .lock()
+            .unwrap()
+            .find_all()
+            .iter()
+            .any(|budget| budget.scope() == &BudgetScope::Category(id.clone()))
+        {
+            return Err(DeleteError::UsedByBudgets);
+        }
+
+        match self.category_repository.lock().unwrap().delete(id.clone()) {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// Totals the confirmed transactions of each category, per currency.
+    ///
+    /// # Arguments
+    /// * `from` -- Only the transactions on or after this date.
+    /// * `to` -- Only the transactions strictly before this date.
+    pub fn report(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Vec<CategoryTotal> {
+        let query = TransactionQuery {
+            from,
+            to,
+            status: Some(TransactionStatus::Confirmed),
+            ..Default::default()
+        };
+
+        // (category, currency) -> (count, amount), ordered so the report is stable.
+        let mut own_totals: BTreeMap<(Option<CategoryId>, String), (usize, f32)> = BTreeMap::new();
+        let transactions = stream_transactions(self.transaction_repository.clone(), query);
+        for transaction in transactions {
+            // A split transaction counts once in the category of each of its splits.
+            let mut amounts: BTreeMap<Option<CategoryId>, f32> = BTreeMap::new();
+            for (category_id, amount) in transaction.category_amounts() {
+                *amounts.entry(category_id.cloned()).or_default() += amount.value();
+            }
+
+            for (category_id, amount) in amounts {
+                let key = (category_id, transaction.currency().to_string());
+                let entry = own_totals.entry(key).or_default();
+                entry.0 += 1;
+                entry.1 += amount;
+            }
+        }
+
+        // Roll the totals up to every ancestor of the categories.
+        let parents: HashMap<CategoryId, Option<CategoryId>> = self
+            .find_all()
+            .into_iter()
+            .map(|category| {
+                (
+                    category.id().unwrap().clone(),
+                    category.parent_id().cloned(),
+                )
+            })
+            .collect();
+        let mut totals: BTreeMap<(Option<CategoryId>, String), f32> = BTreeMap::new();
+        for ((category_id, currency), (_, amount)) in &own_totals {
+            let mut visited: HashSet<CategoryId> = HashSet::new();
+            let mut current = category_id.clone();
+            loop {
+                *totals
+                    .entry((current.clone(), currency.clone()))
+                    .or_default() += amount;
+
+                match current
+                    .as_ref()
+                    .and_then(|id| parents.get(id).cloned().flatten())
+                {
+                    Some(parent_id) if visited.insert(parent_id.clone()) => {
+                        current = Some(parent_id)
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        totals
+            .into_iter()
+            .map(|((category_id, currency), total)| {
+                let (transaction_count, amount) = own_totals
+                    .get(&(category_id.clone(), currency.clone()))
+                    .cloned()
+                    .unwrap_or_default();
+
+                CategoryTotal {
+                    category_id,
+                    currency: currency.try_into().unwrap(),
+                    transaction_count,
+                    amount,
+                    total,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,268 @@
+use crate::app::entities::account::{AccountId, AccountType};
+use crate::app::entities::goal::{Goal, GoalId};
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::goal_repository::{
+    DeleteError as RepositoryDeleteError, GoalRepository,
+};
+use crate::app::typing::currency::Currency;
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Goal not found by the provided id")]
+    NotFound(GoalId),
+}
+
+/// The reasons a goal can not be saved, whether it is created or updated.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The goal name must not be empty")]
+    EmptyName,
+
+    #[error("The target amount must be greater than zero")]
+    ZeroTarget,
+
+    #[error("A goal must be linked to at least one savings account")]
+    NoAccount,
+
+    #[error("The linked account was not found")]
+    AccountNotFound(AccountId),
+
+    #[error("The linked account is not a savings account")]
+    NotASavingsAccount(AccountId),
+
+    #[error("The linked account is not in the currency of the goal")]
+    CurrencyMismatch(AccountId),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Goal ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Goal not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Goal not found by the provided id")]
+    EntityIdNotFound,
+}
+
+/// Where a goal stands.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalState {
+    /// The target amount is not saved yet, and the target date is not passed.
+    InProgress,
+
+    /// The target amount is saved.
+    Achieved,
+
+    /// The target date passed before the target amount was saved.
+    Overdue,
+}
+
+/// The progress of a goal, from the balances of its accounts.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct GoalStatus {
+    pub goal_id: GoalId,
+
+    pub name: String,
+
+    pub currency: Currency,
+
+    pub target_amount: f32,
+
+    pub target_date: NaiveDate,
+
+    /// The total balance of the linked accounts.
+    pub saved: f32,
+
+    /// The amount left to save, 0 once the goal is achieved.
+    pub remaining: f32,
+
+    /// The share of the target amount saved, from 0 to 1.
+    pub progress: f32,
+
+    /// The number of monthly contributions left before the target date, this month's included.
+    pub contributions_left: u32,
+
+    /// The amount to save every month to reach the target on time.
+    /// Once the target date passed, the whole remaining amount.
+    pub monthly_contribution: f32,
+
+    pub state: GoalState,
+}
+
+pub struct GoalService<R, AR> {
+    goal_repository: Arc<Mutex<R>>,
+    account_repository: Arc<Mutex<AR>>,
+}
+
+impl<R: GoalRepository, AR: AccountRepository> GoalService<R, AR> {
+    pub fn new(goal_repository: Arc<Mutex<R>>, account_repository: Arc<Mutex<AR>>) -> Self {
+        Self {
+            goal_repository,
+            account_repository,
+        }
+    }
+
+    /// Retrieves all the goals, in creation order.
+    pub fn find_all(&self) -> Vec<Goal> {
+        self.goal_repository.lock().unwrap().find_all()
+    }
+
+    pub fn find_by_id(&self, id: GoalId) -> Option<Goal> {
+        self.goal_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Retrieves a goal by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no goal with the provided ID.
+    pub fn find_by_id_or_fail(&self, id: &GoalId) -> Result<Goal, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Trims the name of the goal and drops its duplicated accounts,
+    /// then checks its target and that its accounts are savings accounts in its currency.
+    fn validate(&self, goal: &mut Goal) -> Result<(), ValidationError> {
+        let name = goal.name().trim().to_string();
+        if name.is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+        goal.set_name(name);
+
+        if goal.target_amount().value() <= 0.0 {
+            return Err(ValidationError::ZeroTarget);
+        }
+
+        let mut account_ids: Vec<AccountId> = vec![];
+        for account_id in goal.account_ids() {
+            if !account_ids.contains(account_id) {
+                account_ids.push(account_id.clone());
+            }
+        }
+        if account_ids.is_empty() {
+            return Err(ValidationError::NoAccount);
+        }
+
+        let account_repository = self.account_repository.lock().unwrap();
+        for account_id in &account_ids {
+            let account = account_repository
+                .find_by_id(account_id.clone())
+                .ok_or_else(|| ValidationError::AccountNotFound(account_id.clone()))?;
+            if account.account_type() != &AccountType::Savings {
+                return Err(ValidationError::NotASavingsAccount(account_id.clone()));
+            }
+            if account.currency() != goal.currency() {
+                return Err(ValidationError::CurrencyMismatch(account_id.clone()));
+            }
+        }
+        goal.set_account_ids(account_ids);
+
+        Ok(())
+    }
+
+    pub fn create(&self, mut goal: Goal) -> Result<GoalId, CreateError> {
+        if goal.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&mut goal)?;
+
+        Ok(self.goal_repository.lock().unwrap().create(goal))
+    }
+
+    pub fn update(&self, id: &GoalId, mut goal: Goal) -> Result<Goal, UpdateError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(UpdateError::EntityIdNotFound);
+        }
+        goal.set_id(Some(id.clone()));
+
+        self.validate(&mut goal)?;
+
+        self.goal_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), goal.clone())
+            .map(|_| goal)
+            .map_err(|_| UpdateError::EntityIdNotFound)
+    }
+
+    /// Deletes a goal, its accounts are kept.
+    pub fn delete(&self, id: &GoalId) -> Result<(), DeleteError> {
+        match self.goal_repository.lock().unwrap().delete(id.clone()) {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// Computes the progress of a goal on the day `today`, from the current balances of its accounts.
+    fn compute_status(&self, goal: &Goal, today: NaiveDate) -> GoalStatus {
+        let account_repository = self.account_repository.lock().unwrap();
+        let saved: f32 = goal
+            .account_ids()
+            .iter()
+            .filter_map(|account_id| account_repository.find_by_id(account_id.clone()))
+            .map(|account| account.balance().value())
+            .sum();
+
+        let target_amount = goal.target_amount().value();
+        let remaining = (target_amount - saved).max(0.0);
+        let contributions_left = goal.contributions_left(today);
+        let state = if remaining == 0.0 {
+            GoalState::Achieved
+        } else if contributions_left == 0 {
+            GoalState::Overdue
+        } else {
+            GoalState::InProgress
+        };
+
+        GoalStatus {
+            goal_id: goal.id().unwrap().clone(),
+            name: goal.name().clone(),
+            currency: goal.currency().clone(),
+            target_amount,
+            target_date: goal.target_date(),
+            saved,
+            remaining,
+            progress: (saved / target_amount).min(1.0),
+            contributions_left,
+            monthly_contribution: remaining / contributions_left.max(1) as f32,
+            state,
+        }
+    }
+
+    /// Computes the progress of a goal on the day `today`.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no goal with the provided ID.
+    pub fn status(&self, id: &GoalId, today: NaiveDate) -> Result<GoalStatus, FindByIdOrFailError> {
+        let goal = self.find_by_id_or_fail(id)?;
+
+        Ok(self.compute_status(&goal, today))
+    }
+
+    /// Computes the progress of all the goals on the day `today`, in creation order.
+    pub fn statuses(&self, today: NaiveDate) -> Vec<GoalStatus> {
+        self.find_all()
+            .iter()
+            .map(|goal| self.compute_status(goal, today))
+            .collect()
+    }
+}
@@ -0,0 +1,244 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::institution::{Institution, InstitutionId};
+use crate::app::entities::transaction::TransactionType;
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::common::{PageIterator, STREAM_BATCH_SIZE};
+use crate::app::repositories::institution_repository::{
+    DeleteError as RepositoryDeleteError, InstitutionRepository,
+};
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::typing::amount::Amount;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// The highest rate of a fee tier, in percent.
+pub(crate) const MAX_FEE_RATE: f32 = 100.0;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Institution not found by the provided id")]
+    NotFound(InstitutionId),
+}
+
+/// The reasons an institution can not be saved, whether it is created or updated.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The institution name must not be empty")]
+    EmptyName,
+
+    #[error("Another institution already has this name")]
+    DuplicateName,
+
+    #[error("The country must be an ISO 3166-1 alpha-2 code (Eg: RW)")]
+    InvalidCountry,
+
+    #[error("The logo URL must be an http or https address")]
+    InvalidLogoUrl,
+
+    #[error("A fee tier must not end below its start, and its rate must be between 0 and {max}%", max = MAX_FEE_RATE)]
+    InvalidFeeTier,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Institution ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Institution not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Institution not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("The institution is used by some accounts or rules")]
+    InUse,
+}
+
+pub struct InstitutionService<R, AR, RR> {
+    institution_repository: Arc<Mutex<R>>,
+    account_repository: Arc<Mutex<AR>>,
+    rule_repository: Arc<Mutex<RR>>,
+}
+
+impl<R: InstitutionRepository, AR: AccountRepository, RR: RuleRepository>
+    InstitutionService<R, AR, RR>
+{
+    pub fn new(
+        institution_repository: Arc<Mutex<R>>,
+        account_repository: Arc<Mutex<AR>>,
+        rule_repository: Arc<Mutex<RR>>,
+    ) -> Self {
+        Self {
+            institution_repository,
+            account_repository,
+            rule_repository,
+        }
+    }
+
+    /// Retrieves all the institutions, in creation order.
+    pub fn find_all(&self) -> Vec<Institution> {
+        self.institution_repository.lock().unwrap().find_all()
+    }
+
+    pub fn find_by_id(&self, id: InstitutionId) -> Option<Institution> {
+        self.institution_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Retrieves an institution by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no institution with the provided ID.
+    pub fn find_by_id_or_fail(
+        &self,
+        id: &InstitutionId,
+    ) -> Result<Institution, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Trims the name of the institution and upper cases its country, then checks them,
+    /// its logo and its fee schedule.
+    fn validate(&self, institution: &mut Institution) -> Result<(), ValidationError> {
+        let name = institution.name().trim().to_string();
+        if name.is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+        let duplicate = self.find_all().iter().any(|other| {
+            other.id() != institution.id() && other.name().eq_ignore_ascii_case(&name)
+        });
+        if duplicate {
+            return Err(ValidationError::DuplicateName);
+        }
+        institution.set_name(name);
+
+        let country = institution.country().trim().to_ascii_uppercase();
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(ValidationError::InvalidCountry);
+        }
+        institution.set_country(country);
+
+        if let Some(logo_url) = institution.logo_url() {
+            if !logo_url.starts_with("http://") && !logo_url.starts_with("https://") {
+                return Err(ValidationError::InvalidLogoUrl);
+            }
+        }
+
+        let invalid_tier = institution.fee_schedule().iter().any(|tier| {
+            tier.max_amount
+                .as_ref()
+                .is_some_and(|max_amount| max_amount < &tier.min_amount)
+                || !(0.0..=MAX_FEE_RATE).contains(&tier.rate)
+        });
+        if invalid_tier {
+            return Err(ValidationError::InvalidFeeTier);
+        }
+
+        Ok(())
+    }
+
+    pub fn create(&self, mut institution: Institution) -> Result<InstitutionId, CreateError> {
+        if institution.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&mut institution)?;
+
+        Ok(self
+            .institution_repository
+            .lock()
+            .unwrap()
+            .create(institution))
+    }
+
+    pub fn update(
+        &self,
+        id: &InstitutionId,
+        mut institution: Institution,
+    ) -> Result<Institution, UpdateError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(UpdateError::EntityIdNotFound);
+        }
+        institution.set_id(Some(id.clone()));
+
+        self.validate(&mut institution)?;
+
+        self.institution_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), institution.clone())
+            .map(|_| institution)
+            .map_err(|_| UpdateError::EntityIdNotFound)
+    }
+
+    /// Whether an account is held at the institution, the accounts are streamed in batches.
+    fn has_accounts(&self, id: &InstitutionId) -> bool {
+        let account_repository = self.account_repository.clone();
+        let mut accounts = PageIterator::new(move |cursor: Option<String>| {
+            let after: Option<AccountId> = cursor.map(|cursor| {
+                cursor
+                    .parse()
+                    .expect("The cursor is an account id from the previous page")
+            });
+            account_repository
+                .lock()
+                .unwrap()
+                .find_page(after, STREAM_BATCH_SIZE)
+        });
+
+        accounts.any(|account| account.institution_id() == Some(id))
+    }
+
+    /// Deletes an institution.
+    /// An institution can only be deleted when no account is held at it, and no rule matches on it.
+    pub fn delete(&self, id: &InstitutionId) -> Result<(), DeleteError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(DeleteError::EntityIdNotFound);
+        }
+
+        let in_rules = self
+            .rule_repository
+            .lock()
+            .unwrap()
+            .find_all()
+            .iter()
+            .any(|rule| rule.conditions().institution_id.as_ref() == Some(id));
+        if in_rules || self.has_accounts(id) {
+            return Err(DeleteError::InUse);
+        }
+
+        match self
+            .institution_repository
+            .lock()
+            .unwrap()
+            .delete(id.clone())
+        {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// The fee the institution charges on a transaction of the type and amount, from its fee schedule.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no institution with the provided ID.
+    pub fn quote_fee(
+        &self,
+        id: &InstitutionId,
+        transaction_type: &TransactionType,
+        amount: &Amount,
+    ) -> Result<f32, FindByIdOrFailError> {
+        Ok(self
+            .find_by_id_or_fail(id)?
+            .fee_for(transaction_type, amount))
+    }
+}
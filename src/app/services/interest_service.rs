@@ -0,0 +1,314 @@
+use crate::app::entities::account::{Account, AccountId};
+use crate::app::entities::interest::{
+    BalanceHistory, InterestConfig, InterestDirection, InterestPosting, INTEREST_DESCRIPTION,
+    MAX_ANNUAL_RATE,
+};
+use crate::app::entities::transaction::{
+    AccountRef, Transaction, TransactionId, TransactionStatus,
+};
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::interest_config_repository::{
+    DeleteError as RepositoryDeleteError, InterestConfigRepository,
+};
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::repositories::transaction_query::{
+    SortDirection, TransactionQuery, TransactionSort, TransactionSortField,
+};
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::services::transaction_service::TransactionService;
+use crate::app::typing::amount::{Amount, MIN_AMOUNT};
+use crate::app::typing::currency::Currency;
+use chrono::{Days, NaiveDate, TimeZone, Utc};
+use log::warn;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("The account has no interest configuration")]
+    NotFound(AccountId),
+}
+
+/// The reasons an interest configuration can not be saved.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The account was not found")]
+    AccountNotFound(AccountId),
+
+    #[error("The account does not accrue interest")]
+    NoInterest(AccountId),
+
+    #[error("The annual rate must be greater than zero, and at most {max}%", max = MAX_ANNUAL_RATE)]
+    RateOutOfBounds,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("The account has no interest configuration")]
+    EntityIdNotFound,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PreviewError {
+    #[error("The account has no interest configuration")]
+    NotConfigured(AccountId),
+
+    #[error("The account was not found")]
+    AccountNotFound(AccountId),
+}
+
+/// The interest an account would be posted, from the first day not posted yet.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct InterestPreview {
+    pub account_id: AccountId,
+
+    pub currency: Currency,
+
+    pub direction: InterestDirection,
+
+    /// The first day whose interest is not posted yet.
+    pub from: NaiveDate,
+
+    /// The last day of the preview (included).
+    pub until: NaiveDate,
+
+    /// The interest of the posting periods ending by `until`.
+    pub postings: Vec<InterestPosting>,
+
+    /// The interest accrued after the last of these periods, posted at the end of the current one.
+    pub accrued: f32,
+
+    /// The interest of the postings and the accrued interest together.
+    pub total: f32,
+}
+
+/// The transaction service, shared with the API which creates the other transactions.
+type SharedTransactionService<TR, AR, CR, PR, RR> =
+    Arc<Mutex<TransactionService<TR, AR, CR, PR, RR>>>;
+
+pub struct InterestService<R, TR, AR, CR, PR, RR> {
+    interest_config_repository: Arc<Mutex<R>>,
+    account_repository: Arc<Mutex<AR>>,
+    transaction_service: SharedTransactionService<TR, AR, CR, PR, RR>,
+}
+
+impl<
+        R: InterestConfigRepository,
+        TR: TransactionRepository,
+        AR: AccountRepository,
+        CR: CategoryRepository,
+        PR: PayeeRepository,
+        RR: RuleRepository,
+    > InterestService<R, TR, AR, CR, PR, RR>
+{
+    pub fn new(
+        interest_config_repository: Arc<Mutex<R>>,
+        account_repository: Arc<Mutex<AR>>,
+        transaction_service: SharedTransactionService<TR, AR, CR, PR, RR>,
+    ) -> Self {
+        Self {
+            interest_config_repository,
+            account_repository,
+            transaction_service,
+        }
+    }
+
+    /// Retrieves the interest configuration of an account.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If the account has no interest configuration.
+    pub fn find_by_account_id_or_fail(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<InterestConfig, FindByIdOrFailError> {
+        self.interest_config_repository
+            .lock()
+            .unwrap()
+            .find_by_account_id(account_id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(account_id.clone()))
+    }
+
+    fn find_account(&self, account_id: &AccountId) -> Option<Account> {
+        self.account_repository
+            .lock()
+            .unwrap()
+            .find_by_id(account_id.clone())
+    }
+
+    /// Sets how the interest of an account is computed and posted, replacing its previous configuration.
+    /// The interest already posted is kept, the accrual resumes after it with the new configuration.
+    pub fn configure(&self, mut config: InterestConfig) -> Result<InterestConfig, ValidationError> {
+        let account_id = config.account_id().clone();
+        let account = self
+            .find_account(&account_id)
+            .ok_or_else(|| ValidationError::AccountNotFound(account_id.clone()))?;
+        if !account.account_type().accrues_interest() {
+            return Err(ValidationError::NoInterest(account_id));
+        }
+        if config.annual_rate() <= 0.0 || config.annual_rate() > MAX_ANNUAL_RATE {
+            return Err(ValidationError::RateOutOfBounds);
+        }
+
+        let mut repository = self.interest_config_repository.lock().unwrap();
+        config.set_posted_until(
+            repository
+                .find_by_account_id(account_id)
+                .and_then(|previous| previous.posted_until()),
+        );
+        repository.save(config.clone());
+
+        Ok(config)
+    }
+
+    /// Stops accruing interest on an account, the interest already posted is kept.
+    pub fn remove(&self, account_id: &AccountId) -> Result<(), DeleteError> {
+        match self
+            .interest_config_repository
+            .lock()
+            .unwrap()
+            .delete(account_id.clone())
+        {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// What the interest of the account is computed on at the end of every day:
+    /// its balance when it earns interest, the amount owed on it when it is charged.
+    fn balance_history(&self, account: &Account, direction: InterestDirection) -> BalanceHistory {
+        let query = TransactionQuery {
+            account_id: account.id().cloned(),
+            // A debt is owed for its confirmed charges, like on its statements.
+            status: match direction {
+                InterestDirection::Earned => None,
+                InterestDirection::Charged => Some(TransactionStatus::Confirmed),
+            },
+            sort: TransactionSort {
+                field: TransactionSortField::Date,
+                direction: SortDirection::Asc,
+            },
+            ..TransactionQuery::default()
+        };
+
+        let transaction_service = self.transaction_service.lock().unwrap();
+        let transactions = transaction_service
+            .stream(query)
+            .expect("The query has no range to invert");
+        match direction {
+            InterestDirection::Earned => {
+                BalanceHistory::from_transactions(transactions, account.balance().value() as f64)
+            }
+            InterestDirection::Charged => BalanceHistory::owed_from_transactions(transactions),
+        }
+    }
+
+    /// Computes the interest of an account from the first day not posted yet up to `until` (included),
+    /// as it would be posted, without posting it.
+    pub fn preview(
+        &self,
+        account_id: &AccountId,
+        until: NaiveDate,
+    ) -> Result<InterestPreview, PreviewError> {
+        let config = self
+            .find_by_account_id_or_fail(account_id)
+            .map_err(|_| PreviewError::NotConfigured(account_id.clone()))?;
+        let account = self
+            .find_account(account_id)
+            .ok_or_else(|| PreviewError::AccountNotFound(account_id.clone()))?;
+
+        let direction = InterestDirection::of(account.account_type());
+        let history = self.balance_history(&account, direction);
+        let mut accrual = config.accrue(&history, until);
+        // Nothing is posted for the periods without interest.
+        accrual.postings.retain(|posting| posting.amount > 0.0);
+        let total = accrual
+            .postings
+            .iter()
+            .map(|posting| posting.amount)
+            .sum::<f32>()
+            + accrual.accrued;
+
+        Ok(InterestPreview {
+            account_id: account_id.clone(),
+            currency: account.currency().clone(),
+            direction,
+            from: config.accrual_start(),
+            until,
+            postings: accrual.postings,
+            accrued: accrual.accrued,
+            total,
+        })
+    }
+
+    /// Posts the interest of the posting periods which ended before `today`,
+    /// as confirmed incomes on the accounts earning it and expenses on the accounts charged it.
+    ///
+    /// A posting which can not be created (eg: the account lacks the funds to be charged)
+    /// is retried on the next run, and the later postings of its account wait for it.
+    ///
+    /// # Returns
+    /// * `Vec<TransactionId>` — The created transactions.
+    pub fn post_due(&self, today: NaiveDate) -> Vec<TransactionId> {
+        let until = today - Days::new(1);
+        let mut created: Vec<TransactionId> = vec![];
+
+        let configs = self.interest_config_repository.lock().unwrap().find_all();
+        for mut config in configs {
+            if !config.is_due(until) {
+                continue;
+            }
+            let account_id = config.account_id().clone();
+            let Some(account) = self.find_account(&account_id) else {
+                warn!("The interest of the account {account_id} was not posted: the account was not found");
+                continue;
+            };
+
+            let direction = InterestDirection::of(account.account_type());
+            let history = self.balance_history(&account, direction);
+            for posting in config.accrue(&history, until).postings {
+                if posting.amount > 0.0 {
+                    let Ok(amount) = Amount::try_from(posting.amount) else {
+                        warn!(
+                            "The interest of {} of the account {account_id} was not posted: {} is out of bounds",
+                            posting.date, posting.amount
+                        );
+                        break;
+                    };
+                    let transaction = Transaction::new(
+                        None,
+                        AccountRef::Id(account_id.clone()),
+                        direction.transaction_type(),
+                        amount,
+                        MIN_AMOUNT,
+                        None,
+                        None,
+                        account.currency().clone(),
+                        TransactionStatus::Confirmed,
+                        Utc.from_utc_datetime(&posting.date.and_hms_opt(23, 59, 59).unwrap()),
+                        Some(String::from(INTEREST_DESCRIPTION)),
+                        None,
+                        None,
+                    );
+                    match self.transaction_service.lock().unwrap().create(transaction) {
+                        Ok(transaction_id) => created.push(transaction_id),
+                        Err(error) => {
+                            warn!(
+                                "The interest of {} of the account {account_id} was not posted: {error}",
+                                posting.date
+                            );
+                            break;
+                        }
+                    }
+                }
+                config.set_posted_until(Some(posting.date));
+            }
+
+            self.interest_config_repository.lock().unwrap().save(config);
+        }
+
+        created
+    }
+}
@@ -0,0 +1,445 @@
+use crate::app::entities::account::{AccountId, OPENING_BALANCE_DESCRIPTION};
+use crate::app::entities::interest::MAX_ANNUAL_RATE;
+use crate::app::entities::loan::{Instalment, Loan, LoanId};
+use crate::app::entities::transaction::{TransactionId, TransactionStatus, TransactionType};
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::common::stream_transactions;
+use crate::app::repositories::loan_repository::{
+    DeleteError as RepositoryDeleteError, LoanRepository,
+};
+use crate::app::repositories::transaction_query::{
+    SortDirection, TransactionQuery, TransactionSort, TransactionSortField,
+};
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::typing::currency::Currency;
+use chrono::{Days, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+/// The most instalments a loan can be repaid with. (Eg: 50 years of monthly instalments)
+pub(crate) const MAX_TERM: u32 = 600;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Loan not found by the provided id")]
+    NotFound(LoanId),
+}
+
+/// The reasons a loan can not be saved, whether it is created or updated.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The loan name must not be empty")]
+    EmptyName,
+
+    #[error("The principal must be greater than zero")]
+    ZeroPrincipal,
+
+    #[error("The annual rate must be between 0 and {max}%", max = MAX_ANNUAL_RATE)]
+    RateOutOfBounds,
+
+    #[error("The term must be between 1 and {max} instalments", max = MAX_TERM)]
+    TermOutOfBounds,
+
+    #[error("The account of the loan was not found")]
+    AccountNotFound(AccountId),
+
+    #[error("The account of the loan is neither a credit nor a loan account")]
+    NotADebtAccount(AccountId),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Loan ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Loan not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Loan not found by the provided id")]
+    EntityIdNotFound,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum StatusError {
+    #[error("Loan not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("The account of the loan was not found")]
+    AccountNotFound(AccountId),
+}
+
+/// Where a scheduled instalment stands.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InstalmentState {
+    /// Repaid in full.
+    Paid,
+
+    /// Not due yet, and repaid in part.
+    PartiallyPaid,
+
+    /// Not due yet, and not repaid at all.
+    Upcoming,
+
+    /// Past its due date, and not repaid in full.
+    Overdue,
+}
+
+/// A scheduled instalment, with the repayments matched to it.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct InstalmentStatus {
+    #[serde(flatten)]
+    pub instalment: Instalment,
+
+    /// The amount repaid for the instalment.
+    pub paid: f32,
+
+    /// The part of the amount repaid which went to the interest, which is repaid first.
+    pub paid_interest: f32,
+
+    pub paid_principal: f32,
+
+    pub state: InstalmentState,
+
+    /// The day the instalment was repaid in full.
+    pub paid_on: Option<NaiveDate>,
+
+    /// The repayments matched to the instalment, in date order.
+    pub transaction_ids: Vec<TransactionId>,
+}
+
+/// The repayment of a loan at a date.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct LoanStatus {
+    pub loan_id: LoanId,
+
+    pub name: String,
+
+    pub currency: Currency,
+
+    /// The status is computed at the end of this day.
+    pub at: NaiveDate,
+
+    pub principal: f32,
+
+    /// The principal repaid so far.
+    pub paid_principal: f32,
+
+    /// The interest repaid so far.
+    pub paid_interest: f32,
+
+    /// The principal left to repay.
+    pub outstanding_principal: f32,
+
+    /// The amount left unpaid on the overdue instalments.
+    pub arrears: f32,
+
+    pub overdue_instalments: u32,
+
+    /// The due date of the first instalment not repaid in full, none once the loan is repaid.
+    pub next_due_date: Option<NaiveDate>,
+
+    /// The amount repaid beyond the whole schedule.
+    pub overpaid: f32,
+
+    pub instalments: Vec<InstalmentStatus>,
+}
+
+fn round_to_cents(amount: f64) -> f32 {
+    ((amount * 100.0).round() / 100.0) as f32
+}
+
+/// An instalment being matched to the repayments.
+struct Allocation {
+    instalment: Instalment,
+    paid: f64,
+    paid_interest: f64,
+    paid_on: Option<NaiveDate>,
+    transaction_ids: Vec<TransactionId>,
+}
+
+impl Allocation {
+    fn left(&self) -> f64 {
+        self.instalment.payment as f64 - self.paid
+    }
+
+    /// Whether nothing is left to repay, up to the rounding of the cents.
+    fn is_paid(&self) -> bool {
+        self.left() < 0.005
+    }
+}
+
+pub struct LoanService<R, AR, TR> {
+    loan_repository: Arc<Mutex<R>>,
+    account_repository: Arc<Mutex<AR>>,
+    transaction_repository: Arc<Mutex<TR>>,
+}
+
+impl<R: LoanRepository, AR: AccountRepository, TR: TransactionRepository> LoanService<R, AR, TR> {
+    pub fn new(
+        loan_repository: Arc<Mutex<R>>,
+        account_repository: Arc<Mutex<AR>>,
+        transaction_repository: Arc<Mutex<TR>>,
+    ) -> Self {
+        Self {
+            loan_repository,
+            account_repository,
+            transaction_repository,
+        }
+    }
+
+    /// Retrieves all the loans, in creation order.
+    pub fn find_all(&self) -> Vec<Loan> {
+        self.loan_repository.lock().unwrap().find_all()
+    }
+
+    pub fn find_by_id(&self, id: LoanId) -> Option<Loan> {
+        self.loan_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Retrieves a loan by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no loan with the provided ID.
+    pub fn find_by_id_or_fail(&self, id: &LoanId) -> Result<Loan, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Trims the name of the loan, then checks its terms and that it is tracked on a credit or loan account.
+    fn validate(&self, loan: &mut Loan) -> Result<(), ValidationError> {
+        let name = loan.name().trim().to_string();
+        if name.is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+        loan.set_name(name);
+
+        if loan.principal().value() <= 0.0 {
+            return Err(ValidationError::ZeroPrincipal);
+        }
+        if !(0.0..=MAX_ANNUAL_RATE).contains(&loan.annual_rate()) {
+            return Err(ValidationError::RateOutOfBounds);
+        }
+        if loan.term() == 0 || loan.term() > MAX_TERM {
+            return Err(ValidationError::TermOutOfBounds);
+        }
+
+        let account_id = loan.account_id().clone();
+        let account = self
+            .account_repository
+            .lock()
+            .unwrap()
+            .find_by_id(account_id.clone())
+            .ok_or_else(|| ValidationError::AccountNotFound(account_id.clone()))?;
+        if !account.account_type().is_debt() {
+            return Err(ValidationError::NotADebtAccount(account_id));
+        }
+
+        Ok(())
+    }
+
+    pub fn create(&self, mut loan: Loan) -> Result<LoanId, CreateError> {
+        if loan.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&mut loan)?;
+
+        Ok(self.loan_repository.lock().unwrap().create(loan))
+    }
+
+    pub fn update(&self, id: &LoanId, mut loan: Loan) -> Result<Loan, UpdateError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(UpdateError::EntityIdNotFound);
+        }
+        loan.set_id(Some(id.clone()));
+
+        self.validate(&mut loan)?;
+
+        self.loan_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), loan.clone())
+            .map(|_| loan)
+            .map_err(|_| UpdateError::EntityIdNotFound)
+    }
+
+    /// Deletes a loan, its account and its repayments are kept.
+    pub fn delete(&self, id: &LoanId) -> Result<(), DeleteError> {
+        match self.loan_repository.lock().unwrap().delete(id.clone()) {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// The amortization schedule of a loan.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no loan with the provided ID.
+    pub fn schedule(&self, id: &LoanId) -> Result<Vec<Instalment>, FindByIdOrFailError> {
+        Ok(self.find_by_id_or_fail(id)?.schedule())
+    }
+
+    /// Computes the repayment of a loan at the end of the day `at`.
+    ///
+    /// The repayments are the confirmed incomes of its account in the currency of the account,
+    /// made after the day the money was received, but the opening balance of the account.
+    /// They are matched to the instalments in date order: each one goes to the first instalment
+    /// not repaid in full, then to the next ones, the interest of an instalment first, then its principal.
+    pub fn status(&self, id: &LoanId, at: NaiveDate) -> Result<LoanStatus, StatusError> {
+        let loan = self
+            .find_by_id(id.clone())
+            .ok_or(StatusError::EntityIdNotFound)?;
+        self.compute_status(&loan, at)
+    }
+
+    /// Computes the repayment of all the loans at the end of the day `at`, in creation order.
+    /// The loans whose account is not found are left out.
+    pub fn statuses(&self, at: NaiveDate) -> Vec<LoanStatus> {
+        self.find_all()
+            .iter()
+            .filter_map(|loan| self.compute_status(loan, at).ok())
+            .collect()
+    }
+
+    fn compute_status(&self, loan: &Loan, at: NaiveDate) -> Result<LoanStatus, StatusError> {
+        let account = self
+            .account_repository
+            .lock()
+            .unwrap()
+            .find_by_id(loan.account_id().clone())
+            .ok_or_else(|| StatusError::AccountNotFound(loan.account_id().clone()))?;
+
+        let mut allocations: Vec<Allocation> = loan
+            .schedule()
+            .into_iter()
+            .map(|instalment| Allocation {
+                instalment,
+                paid: 0.0,
+                paid_interest: 0.0,
+                paid_on: None,
+                transaction_ids: vec![],
+            })
+            .collect();
+
+        let start_of = |day: NaiveDate| Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).unwrap());
+        let query = TransactionQuery {
+            account_id: Some(loan.account_id().clone()),
+            from: Some(start_of(loan.start() + Days::new(1))),
+            to: Some(start_of(at + Days::new(1))),
+            transaction_type: Some(TransactionType::Income),
+            status: Some(TransactionStatus::Confirmed),
+            currency: Some(account.currency().clone()),
+            sort: TransactionSort {
+                field: TransactionSortField::Date,
+                direction: SortDirection::Asc,
+            },
+            ..TransactionQuery::default()
+        };
+        let repayments =
+            stream_transactions(self.transaction_repository.clone(), query).filter(|transaction| {
+                transaction.description().as_deref() != Some(OPENING_BALANCE_DESCRIPTION)
+            });
+
+        let mut index = 0;
+        let mut overpaid = 0f64;
+        for repayment in repayments {
+            let transaction_id = repayment.id().clone().expect("The transactions are saved");
+            let mut left = repayment.amount().value() as f64;
+
+            while left > 0.0 {
+                let Some(allocation) = allocations.get_mut(index) else {
+                    overpaid += left;
+                    break;
+                };
+
+                let amount = left.min(allocation.left());
+                let interest_left =
+                    (allocation.instalment.interest as f64 - allocation.paid_interest).max(0.0);
+                allocation.paid += amount;
+                allocation.paid_interest += amount.min(interest_left);
+                if !allocation.transaction_ids.contains(&transaction_id) {
+                    allocation.transaction_ids.push(transaction_id.clone());
+                }
+                left -= amount;
+
+                if allocation.is_paid() {
+                    allocation.paid_on = Some(repayment.date().date_naive());
+                    index += 1;
+                }
+            }
+        }
+
+        let instalments: Vec<InstalmentStatus> = allocations
+            .into_iter()
+            .map(|allocation| {
+                let state = if allocation.is_paid() {
+                    InstalmentState::Paid
+                } else if allocation.instalment.due_date < at {
+                    InstalmentState::Overdue
+                } else if allocation.paid > 0.0 {
+                    InstalmentState::PartiallyPaid
+                } else {
+                    InstalmentState::Upcoming
+                };
+
+                InstalmentStatus {
+                    paid: round_to_cents(allocation.paid),
+                    paid_interest: round_to_cents(allocation.paid_interest),
+                    paid_principal: round_to_cents(allocation.paid - allocation.paid_interest),
+                    state,
+                    paid_on: allocation.paid_on,
+                    transaction_ids: allocation.transaction_ids,
+                    instalment: allocation.instalment,
+                }
+            })
+            .collect();
+
+        let principal = loan.principal().value();
+        let paid_principal: f32 = instalments.iter().map(|status| status.paid_principal).sum();
+        let overdue = instalments
+            .iter()
+            .filter(|status| status.state == InstalmentState::Overdue);
+
+        Ok(LoanStatus {
+            loan_id: loan.id().cloned().expect("The loans are saved"),
+            name: loan.name().clone(),
+            currency: account.currency().clone(),
+            at,
+            principal,
+            paid_principal: round_to_cents(paid_principal as f64),
+            paid_interest: round_to_cents(
+                instalments
+                    .iter()
+                    .map(|status| status.paid_interest as f64)
+                    .sum(),
+            ),
+            outstanding_principal: round_to_cents((principal - paid_principal).max(0.0) as f64),
+            arrears: round_to_cents(
+                overdue
+                    .clone()
+                    .map(|status| (status.instalment.payment - status.paid) as f64)
+                    .sum(),
+            ),
+            overdue_instalments: overdue.count() as u32,
+            next_due_date: instalments
+                .iter()
+                .find(|status| status.state != InstalmentState::Paid)
+                .map(|status| status.instalment.due_date),
+            overpaid: round_to_cents(overpaid),
+            instalments,
+        })
+    }
+}
@@ -1,4 +1,32 @@
+pub(crate) mod account_merge_service;
 pub(crate) mod account_service;
+pub(crate) mod budget_service;
+pub(crate) mod category_service;
+pub(crate) mod goal_service;
+pub(crate) mod institution_service;
+pub(crate) mod interest_service;
+pub(crate) mod loan_service;
+pub(crate) mod payee_service;
+pub(crate) mod payoff_service;
+pub(crate) mod recurring_transaction_service;
+pub(crate) mod rule_service;
+pub(crate) mod statement_service;
+pub(crate) mod suggestion_service;
+pub(crate) mod tag_service;
+mod test_account_merge_service;
 mod test_account_service;
+mod test_budget_service;
+mod test_category_service;
+mod test_goal_service;
+mod test_institution_service;
+mod test_interest_service;
+mod test_loan_service;
+mod test_payee_service;
+mod test_payoff_service;
+mod test_recurring_transaction_service;
+mod test_rule_service;
+mod test_statement_service;
+mod test_suggestion_service;
+mod test_tag_service;
 mod test_transaction_service;
 pub(crate) mod transaction_service;
@@ -0,0 +1,332 @@
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::payee::{normalize_text, Payee, PayeeId};
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::payee_repository::{
+    DeleteError as RepositoryDeleteError, FindByIdAndUpdateError, PayeeRepository,
+};
+use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use regex::Regex;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Payee not found by the provided id")]
+    NotFound(PayeeId),
+}
+
+/// The reasons a payee can not be saved, whether it is created or updated.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The payee name must not be empty")]
+    EmptyName,
+
+    #[error("A payee with the same name already exists")]
+    DuplicateName(PayeeId),
+
+    #[error("The pattern is not a valid regular expression: {0}")]
+    InvalidPattern(String),
+
+    #[error("The default category was not found")]
+    CategoryNotFound(CategoryId),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Payee ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Payee not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Payee not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("The payee is used by some transactions, it can be merged into another payee instead")]
+    InUse,
+
+    #[error("The payee is used by some recurring transactions, it can be merged into another payee instead")]
+    UsedByRecurringTransactions,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MergeError {
+    #[error("The payee to merge was not found")]
+    SourceNotFound(PayeeId),
+
+    #[error("The payee to merge into was not found")]
+    TargetNotFound(PayeeId),
+
+    #[error("A payee can not be merged into itself")]
+    SamePayee,
+}
+
+/// Trims the names of a list, dropping the blank ones and the ones spelled like a previous one.
+fn clean_names(names: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = vec![];
+    for name in names {
+        let name = name.trim();
+        let normalized = normalize_text(name);
+        if !normalized.is_empty()
+            && !cleaned
+                .iter()
+                .any(|other| normalize_text(other) == normalized)
+        {
+            cleaned.push(name.to_string());
+        }
+    }
+
+    cleaned
+}
+
+pub struct PayeeService<R, TR, CR, RR, RTR> {
+    payee_repository: Arc<Mutex<R>>,
+    transaction_repository: Arc<Mutex<TR>>,
+    category_repository: Arc<Mutex<CR>>,
+    rule_repository: Arc<Mutex<RR>>,
+    recurring_transaction_repository: Arc<Mutex<RTR>>,
+}
+
+impl<
+        R: PayeeRepository,
+        TR: TransactionRepository,
+        CR: CategoryRepository,
+        RR: RuleRepository,
+        RTR: RecurringTransactionRepository,
+    > PayeeService<R, TR, CR, RR, RTR>
+{
+    pub fn new(
+        payee_repository: Arc<Mutex<R>>,
+        transaction_repository: Arc<Mutex<TR>>,
+        category_repository: Arc<Mutex<CR>>,
+        rule_repository: Arc<Mutex<RR>>,
+        recurring_transaction_repository: Arc<Mutex<RTR>>,
+    ) -> Self {
+        Self {
+            payee_repository,
+            transaction_repository,
+            category_repository,
+            rule_repository,
+            recurring_transaction_repository,
+        }
+    }
+
+    /// Retrieves all the payees, in creation order.
+    pub fn find_all(&self) -> Vec<Payee> {
+        self.payee_repository.lock().unwrap().find_all()
+    }
+
+    pub fn find_by_id(&self, id: PayeeId) -> Option<Payee> {
+        self.payee_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Retrieves a payee by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no payee with the provided ID.
+    pub fn find_by_id_or_fail(&self, id: &PayeeId) -> Result<Payee, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Cleans the names of the payee, then checks its fields against the other payees.
+    fn validate(&self, payee: &mut Payee) -> Result<(), ValidationError> {
+        payee.set_name(payee.name().trim().to_string());
+        payee.set_aliases(clean_names(payee.aliases()));
+        let name = normalize_text(payee.name());
+        if name.is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+
+        if let Some(other) = self
+            .find_all()
+            .into_iter()
+            .find(|other| other.id() != payee.id() && normalize_text(other.name()) == name)
+        {
+            return Err(ValidationError::DuplicateName(other.id().unwrap().clone()));
+        }
+
+        if let Some(pattern) = payee
+            .patterns()
+            .iter()
+            .find(|pattern| Regex::new(pattern).is_err())
+        {
+            return Err(ValidationError::InvalidPattern(pattern.clone()));
+        }
+
+        if let Some(category_id) = payee.default_category_id() {
+            if self
+                .category_repository
+                .lock()
+                .unwrap()
+                .find_by_id(category_id.clone())
+                .is_none()
+            {
+                return Err(ValidationError::CategoryNotFound(category_id.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn create(&self, mut payee: Payee) -> Result<PayeeId, CreateError> {
+        if payee.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&mut payee)?;
+
+        Ok(self.payee_repository.lock().unwrap().create(payee))
+    }
+
+    pub fn update(&self, id: &PayeeId, mut payee: Payee) -> Result<Payee, UpdateError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(UpdateError::EntityIdNotFound);
+        }
+        payee.set_id(Some(id.clone()));
+
+        self.validate(&mut payee)?;
+
+        match self
+            .payee_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), payee.clone())
+        {
+            Ok(_) => Ok(payee),
+            Err(FindByIdAndUpdateError::NotFound) => Err(UpdateError::EntityIdNotFound),
+        }
+    }
+
+    /// Deletes a payee.
+    /// A payee can only be deleted when no transaction or recurring transaction references it,
+    /// a duplicate payee is merged instead.
+    pub fn delete(&self, id: &PayeeId) -> Result<(), DeleteError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(DeleteError::EntityIdNotFound);
+        }
+
+        let query = TransactionQuery {
+            payee_id: Some(id.clone()),
+            limit: 1,
+            ..Default::default()
+        };
+        if !self
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find(&query)
+            .items
+            .is_empty()
+        {
+            return Err(DeleteError::InUse);
+        }
+
+        if self
+            .recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .find_all()
+            .iter()
+            .any(|recurring_transaction| {
+                recurring_transaction.template().payee_id.as_ref() == Some(id)
+            })
+        {
+            return Err(DeleteError::UsedByRecurringTransactions);
+        }
+
+        match self.payee_repository.lock().unwrap().delete(id.clone()) {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// Merges the payee `source` into the payee `target`, when they are duplicates.
+    ///
+    /// The transactions, the rules and the recurring transactions of `source` move to `target`, which also takes the name, aliases
+    /// and patterns of `source`, so the next transactions spelled like it are recognized as `target`.
+    /// `target` keeps its default category, or takes the one of `source` when it has none.
+    /// Then `source` is deleted.
+    ///
+    /// The payees stay locked for the whole merge, the transactions, the rules and the recurring transactions are
+    /// locked after them. No lock of those is held while locking the payees, or both could wait on each other.
+    ///
+    /// # Returns
+    /// * `Payee` — The updated payee `target`.
+    pub fn merge(&self, source: &PayeeId, target: &PayeeId) -> Result<Payee, MergeError> {
+        if source == target {
+            return Err(MergeError::SamePayee);
+        }
+
+        let mut repository = self.payee_repository.lock().unwrap();
+        let source_payee = repository
+            .find_by_id(source.clone())
+            .ok_or_else(|| MergeError::SourceNotFound(source.clone()))?;
+        let mut target_payee = repository
+            .find_by_id(target.clone())
+            .ok_or_else(|| MergeError::TargetNotFound(target.clone()))?;
+
+        let aliases: Vec<String> = target_payee
+            .aliases()
+            .iter()
+            .chain(std::iter::once(source_payee.name()))
+            .chain(source_payee.aliases())
+            .cloned()
+            .collect();
+        // The name of the payee is not repeated as an alias.
+        let target_name = normalize_text(target_payee.name());
+        target_payee.set_aliases(
+            clean_names(&aliases)
+                .into_iter()
+                .filter(|alias| normalize_text(alias) != target_name)
+                .collect(),
+        );
+
+        let mut patterns = target_payee.patterns().clone();
+        for pattern in source_payee.patterns() {
+            if !patterns.contains(pattern) {
+                patterns.push(pattern.clone());
+            }
+        }
+        target_payee.set_patterns(patterns);
+
+        if target_payee.default_category_id().is_none() {
+            target_payee.set_default_category_id(source_payee.default_category_id().cloned());
+        }
+
+        self.transaction_repository
+            .lock()
+            .unwrap()
+            .merge_payees(source, target);
+        self.rule_repository
+            .lock()
+            .unwrap()
+            .merge_payees(source, target);
+        self.recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .merge_payees(source, target);
+        repository
+            .find_by_id_and_update(target.clone(), target_payee.clone())
+            .map_err(|_| MergeError::TargetNotFound(target.clone()))?;
+        repository
+            .delete(source.clone())
+            .map_err(|_| MergeError::SourceNotFound(source.clone()))?;
+
+        Ok(target_payee)
+    }
+}
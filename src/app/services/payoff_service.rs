@@ -0,0 +1,243 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::interest::MAX_ANNUAL_RATE;
+use crate::app::entities::payoff::{Debt, PayoffPlan, PayoffStrategy};
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::interest_config_repository::InterestConfigRepository;
+use crate::app::repositories::loan_repository::LoanRepository;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::services::loan_service::LoanService;
+use chrono::NaiveDate;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PlanError {
+    #[error("There is no debt to pay off")]
+    NoDebt,
+
+    #[error("The monthly budget must be greater than zero")]
+    ZeroBudget,
+
+    #[error("The debt of an account is listed more than once")]
+    DuplicateDebt(AccountId),
+
+    #[error("The account of the debt was not found")]
+    AccountNotFound(AccountId),
+
+    #[error("The account of the debt is neither a credit nor a loan account")]
+    NotADebtAccount(AccountId),
+
+    #[error("The balance of the debt is required, there is no loan on its account")]
+    UnknownBalance(AccountId),
+
+    #[error("The balance and the minimum payment of the debt must not be negative, its rate must be between 0 and {max}%", max = MAX_ANNUAL_RATE)]
+    InvalidTerms(AccountId),
+
+    #[error("The monthly budget does not cover the minimum payments of {minimum}")]
+    BudgetBelowMinimums { minimum: f32 },
+
+    #[error("The custom order must list every debt once")]
+    CustomOrderMismatch,
+
+    #[error("The monthly budget does not cover the interest, the debts would never be paid off")]
+    NeverPaidOff,
+}
+
+/// A debt to plan the payoff of, the terms left out are taken from its account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebtTerms {
+    pub account_id: AccountId,
+
+    /// The amount owed, defaults to the principal left on the loans of the account.
+    pub balance: Option<f32>,
+
+    /// Defaults to the rate of the loans of the account, then to the interest charged on the account.
+    pub annual_rate: Option<f32>,
+
+    /// Defaults to the instalments of the loans of the account, as a monthly amount.
+    pub minimum_payment: Option<f32>,
+}
+
+fn round_to_cents(amount: f64) -> f32 {
+    ((amount * 100.0).round() / 100.0) as f32
+}
+
+/// The loan service, shared with the API which manages the loans.
+type SharedLoanService<LR, AR, TR> = Arc<Mutex<LoanService<LR, AR, TR>>>;
+
+pub struct PayoffService<AR, IR, LR, TR> {
+    account_repository: Arc<Mutex<AR>>,
+    interest_config_repository: Arc<Mutex<IR>>,
+    loan_service: SharedLoanService<LR, AR, TR>,
+}
+
+impl<
+        AR: AccountRepository,
+        IR: InterestConfigRepository,
+        LR: LoanRepository,
+        TR: TransactionRepository,
+    > PayoffService<AR, IR, LR, TR>
+{
+    pub fn new(
+        account_repository: Arc<Mutex<AR>>,
+        interest_config_repository: Arc<Mutex<IR>>,
+        loan_service: SharedLoanService<LR, AR, TR>,
+    ) -> Self {
+        Self {
+            account_repository,
+            interest_config_repository,
+            loan_service,
+        }
+    }
+
+    /// Completes the terms of a debt with its account, its loans not repaid on the day `start`,
+    /// and the interest charged on it.
+    fn resolve(&self, terms: DebtTerms, start: NaiveDate) -> Result<Debt, PlanError> {
+        let account_id = terms.account_id;
+        let account = self
+            .account_repository
+            .lock()
+            .unwrap()
+            .find_by_id(account_id.clone())
+            .ok_or_else(|| PlanError::AccountNotFound(account_id.clone()))?;
+        if !account.account_type().is_debt() {
+            return Err(PlanError::NotADebtAccount(account_id));
+        }
+
+        let loan_service = self.loan_service.lock().unwrap();
+        let loans: Vec<_> = loan_service
+            .find_all()
+            .into_iter()
+            .filter(|loan| loan.account_id() == &account_id)
+            .filter_map(|loan| {
+                let status = loan_service.status(loan.id()?, start).ok()?;
+                (status.outstanding_principal > 0.0).then_some((loan, status))
+            })
+            .collect();
+        drop(loan_service);
+
+        let outstanding: f64 = loans
+            .iter()
+            .map(|(_, status)| status.outstanding_principal as f64)
+            .sum();
+        let balance = match terms.balance {
+            Some(balance) => balance,
+            None if !loans.is_empty() => round_to_cents(outstanding),
+            None => return Err(PlanError::UnknownBalance(account_id)),
+        };
+        let annual_rate = match terms.annual_rate {
+            Some(annual_rate) => annual_rate,
+            // The rate of the loans, weighted by the principal they have left.
+            None if !loans.is_empty() => {
+                (loans
+                    .iter()
+                    .map(|(loan, status)| {
+                        loan.annual_rate() as f64 * status.outstanding_principal as f64
+                    })
+                    .sum::<f64>()
+                    / outstanding) as f32
+            }
+            None => self
+                .interest_config_repository
+                .lock()
+                .unwrap()
+                .find_by_account_id(account_id.clone())
+                .map_or(0.0, |config| config.annual_rate()),
+        };
+        let minimum_payment = terms.minimum_payment.unwrap_or_else(|| {
+            round_to_cents(
+                loans
+                    .iter()
+                    .map(|(loan, status)| {
+                        let payment = status.instalments[0].instalment.payment as f64;
+                        payment * loan.frequency().per_year() as f64 / 12.0
+                    })
+                    .sum(),
+            )
+        });
+
+        if balance < 0.0 || minimum_payment < 0.0 || !(0.0..=MAX_ANNUAL_RATE).contains(&annual_rate)
+        {
+            return Err(PlanError::InvalidTerms(account_id));
+        }
+
+        Ok(Debt {
+            account_id,
+            name: account.name().clone(),
+            balance,
+            annual_rate,
+            minimum_payment,
+        })
+    }
+
+    /// Simulates the payoff of the debts with `monthly_budget` a month from the month after `start`,
+    /// with the snowball and avalanche strategies, then the custom order when one is provided.
+    ///
+    /// The debts already paid off are left out.
+    pub fn plan(
+        &self,
+        monthly_budget: f32,
+        debts: Vec<DebtTerms>,
+        custom_order: Option<Vec<AccountId>>,
+        start: NaiveDate,
+    ) -> Result<Vec<PayoffPlan>, PlanError> {
+        if monthly_budget <= 0.0 {
+            return Err(PlanError::ZeroBudget);
+        }
+
+        let mut resolved: Vec<Debt> = vec![];
+        for terms in debts {
+            if resolved
+                .iter()
+                .any(|debt| debt.account_id == terms.account_id)
+            {
+                return Err(PlanError::DuplicateDebt(terms.account_id));
+            }
+            resolved.push(self.resolve(terms, start)?);
+        }
+        let custom = match custom_order {
+            Some(order) => {
+                if order.len() != resolved.len() {
+                    return Err(PlanError::CustomOrderMismatch);
+                }
+                let mut ordered = vec![];
+                for account_id in order {
+                    let index = resolved
+                        .iter()
+                        .position(|debt| debt.account_id == account_id)
+                        .ok_or(PlanError::CustomOrderMismatch)?;
+                    ordered.push(resolved.remove(index));
+                }
+                resolved = ordered;
+                true
+            }
+            None => false,
+        };
+        resolved.retain(|debt| debt.balance > 0.0);
+        if resolved.is_empty() {
+            return Err(PlanError::NoDebt);
+        }
+
+        let minimum = round_to_cents(
+            resolved
+                .iter()
+                .map(|debt| debt.minimum_payment.min(debt.balance) as f64)
+                .sum(),
+        );
+        if monthly_budget < minimum {
+            return Err(PlanError::BudgetBelowMinimums { minimum });
+        }
+
+        let mut strategies = vec![PayoffStrategy::Snowball, PayoffStrategy::Avalanche];
+        if custom {
+            strategies.push(PayoffStrategy::Custom);
+        }
+        strategies
+            .into_iter()
+            .map(|strategy| {
+                PayoffPlan::simulate(resolved.clone(), strategy, monthly_budget, start)
+                    .ok_or(PlanError::NeverPaidOff)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,394 @@
+use crate::app::entities::recurring_transaction::{
+    RecurringStatus, RecurringTransaction, RecurringTransactionId,
+};
+use crate::app::entities::transaction::TransactionId;
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::recurring_transaction_repository::{
+    DeleteError as RepositoryDeleteError, FindByIdAndUpdateError, RecurringTransactionRepository,
+};
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::services::transaction_service::{
+    CreateError as TransactionCreateError, TransactionService,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use log::warn;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// The most upcoming occurrences listed at once.
+pub(crate) const MAX_UPCOMING_OCCURRENCES: usize = 100;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Recurring transaction not found by the provided id")]
+    NotFound(RecurringTransactionId),
+}
+
+/// The reasons a recurring transaction can not be saved, whether it is created or updated.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The recurring transaction name must not be empty")]
+    EmptyName,
+
+    #[error("The interval of the schedule must be at least 1")]
+    ZeroInterval,
+
+    #[error("The count of the schedule must be at least 1")]
+    ZeroCount,
+
+    #[error("The end of the schedule must not be before its start")]
+    EndBeforeStart,
+
+    #[error(transparent)]
+    Template(#[from] TransactionCreateError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Recurring transaction ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Recurring transaction not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Recurring transaction not found by the provided id")]
+    EntityIdNotFound,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SkipError {
+    #[error("Recurring transaction not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("There is no upcoming occurrence on {0}")]
+    NotAnOccurrence(NaiveDate),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpcomingError {
+    #[error("Recurring transaction not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("The limit must be between 1 and {max}", max = MAX_UPCOMING_OCCURRENCES)]
+    LimitOutOfBounds,
+}
+
+/// The transaction service, shared with the API which creates the other transactions.
+type SharedTransactionService<TR, AR, CR, PR, RR> =
+    Arc<Mutex<TransactionService<TR, AR, CR, PR, RR>>>;
+
+pub struct RecurringTransactionService<R, TR, AR, CR, PR, RR> {
+    recurring_transaction_repository: Arc<Mutex<R>>,
+    transaction_service: SharedTransactionService<TR, AR, CR, PR, RR>,
+}
+
+impl<
+        R: RecurringTransactionRepository,
+        TR: TransactionRepository,
+        AR: AccountRepository,
+        CR: CategoryRepository,
+        PR: PayeeRepository,
+        RR: RuleRepository,
+    > RecurringTransactionService<R, TR, AR, CR, PR, RR>
+{
+    pub fn new(
+        recurring_transaction_repository: Arc<Mutex<R>>,
+        transaction_service: SharedTransactionService<TR, AR, CR, PR, RR>,
+    ) -> Self {
+        Self {
+            recurring_transaction_repository,
+            transaction_service,
+        }
+    }
+
+    /// Retrieves all the recurring transactions, in creation order.
+    pub fn find_all(&self) -> Vec<RecurringTransaction> {
+        self.recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .find_all()
+    }
+
+    pub fn find_by_id(&self, id: RecurringTransactionId) -> Option<RecurringTransaction> {
+        self.recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(id)
+    }
+
+    /// Retrieves a recurring transaction by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no recurring transaction with the provided ID.
+    pub fn find_by_id_or_fail(
+        &self,
+        id: &RecurringTransactionId,
+    ) -> Result<RecurringTransaction, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Trims the name of the recurring transaction, then checks its schedule,
+    /// and the references of its template as the transaction service would.
+    fn validate(
+        &self,
+        recurring_transaction: &mut RecurringTransaction,
+    ) -> Result<(), ValidationError> {
+        let name = recurring_transaction.name().trim().to_string();
+        if name.is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+        recurring_transaction.set_name(name);
+
+        let schedule = recurring_transaction.schedule();
+        if schedule.interval == 0 {
+            return Err(ValidationError::ZeroInterval);
+        }
+        if schedule.count == Some(0) {
+            return Err(ValidationError::ZeroCount);
+        }
+        if schedule.end.is_some_and(|end| end < schedule.start) {
+            return Err(ValidationError::EndBeforeStart);
+        }
+
+        let transaction = recurring_transaction
+            .template()
+            .to_transaction(schedule.start);
+        self.transaction_service
+            .lock()
+            .unwrap()
+            .validate_references(&transaction)?;
+
+        Ok(())
+    }
+
+    /// Creates a recurring transaction.
+    /// Its occurrences are created from the start of its schedule, the past ones on the next run.
+    pub fn create(
+        &self,
+        mut recurring_transaction: RecurringTransaction,
+    ) -> Result<RecurringTransactionId, CreateError> {
+        if recurring_transaction.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&mut recurring_transaction)?;
+
+        Ok(self
+            .recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .create(recurring_transaction))
+    }
+
+    /// Saves the changes of a recurring transaction, which apply to its future occurrences only:
+    /// the transactions already created are kept, and the new schedule continues after the last occurrence.
+    /// The name, the template and the schedule are updated, the status and the skipped days are kept.
+    pub fn update(
+        &self,
+        id: &RecurringTransactionId,
+        changes: RecurringTransaction,
+    ) -> Result<RecurringTransaction, UpdateError> {
+        let mut recurring_transaction = self
+            .find_by_id(id.clone())
+            .ok_or(UpdateError::EntityIdNotFound)?;
+        recurring_transaction.set_name(changes.name().clone());
+        recurring_transaction.set_template(changes.template().clone());
+        recurring_transaction.set_schedule(changes.schedule().clone());
+
+        self.validate(&mut recurring_transaction)?;
+
+        self.save(id, recurring_transaction)
+            .map_err(|_| UpdateError::EntityIdNotFound)
+    }
+
+    fn save(
+        &self,
+        id: &RecurringTransactionId,
+        recurring_transaction: RecurringTransaction,
+    ) -> Result<RecurringTransaction, FindByIdAndUpdateError> {
+        self.recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), recurring_transaction.clone())
+            .map(|_| recurring_transaction)
+    }
+
+    /// Deletes a recurring transaction, the transactions of its past occurrences are kept.
+    pub fn delete(&self, id: &RecurringTransactionId) -> Result<(), DeleteError> {
+        match self
+            .recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .delete(id.clone())
+        {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// Lists the dates of the next occurrences which will be created.
+    pub fn upcoming(
+        &self,
+        id: &RecurringTransactionId,
+        limit: usize,
+    ) -> Result<Vec<DateTime<Utc>>, UpcomingError> {
+        if limit == 0 || limit > MAX_UPCOMING_OCCURRENCES {
+            return Err(UpcomingError::LimitOutOfBounds);
+        }
+
+        let recurring_transaction = self
+            .find_by_id(id.clone())
+            .ok_or(UpcomingError::EntityIdNotFound)?;
+
+        Ok(recurring_transaction
+            .upcoming_occurrences()
+            .take(limit)
+            .collect())
+    }
+
+    /// Skips the upcoming occurrence of a day, the next ones are still created.
+    /// #### Errors
+    /// * `SkipError::NotAnOccurrence` — If no occurrence is left to create on that day.
+    pub fn skip(
+        &self,
+        id: &RecurringTransactionId,
+        date: NaiveDate,
+    ) -> Result<RecurringTransaction, SkipError> {
+        let mut recurring_transaction = self
+            .find_by_id(id.clone())
+            .ok_or(SkipError::EntityIdNotFound)?;
+
+        let is_occurrence = recurring_transaction
+            .next_occurrences()
+            .take_while(|occurrence| occurrence.date_naive() <= date)
+            .any(|occurrence| occurrence.date_naive() == date);
+        if !is_occurrence {
+            return Err(SkipError::NotAnOccurrence(date));
+        }
+
+        let mut skipped_dates = recurring_transaction.skipped_dates().clone();
+        if !skipped_dates.contains(&date) {
+            skipped_dates.push(date);
+            skipped_dates.sort();
+        }
+        recurring_transaction.set_skipped_dates(skipped_dates);
+
+        self.save(id, recurring_transaction)
+            .map_err(|_| SkipError::EntityIdNotFound)
+    }
+
+    /// Stops creating the occurrences of a recurring transaction, until it is resumed.
+    pub fn pause(
+        &self,
+        id: &RecurringTransactionId,
+    ) -> Result<RecurringTransaction, FindByIdOrFailError> {
+        let mut recurring_transaction = self.find_by_id_or_fail(id)?;
+        recurring_transaction.set_status(RecurringStatus::Paused);
+
+        self.save(id, recurring_transaction)
+            .map_err(|_| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Resumes a paused recurring transaction.
+    /// The occurrences which fell due while it was paused are passed over, they are not created.
+    pub fn resume(
+        &self,
+        id: &RecurringTransactionId,
+        now: DateTime<Utc>,
+    ) -> Result<RecurringTransaction, FindByIdOrFailError> {
+        let mut recurring_transaction = self.find_by_id_or_fail(id)?;
+
+        if recurring_transaction.status() == &RecurringStatus::Paused {
+            let passed = recurring_transaction
+                .next_occurrences()
+                .take_while(|date| *date <= now)
+                .last();
+            if passed.is_some() {
+                recurring_transaction.set_last_occurrence(passed);
+            }
+            recurring_transaction.set_status(RecurringStatus::Active);
+        }
+
+        self.save(id, recurring_transaction)
+            .map_err(|_| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Creates the transactions of the occurrences which fell due, as pending transactions.
+    ///
+    /// An occurrence which can not be created (eg: the account was deleted, or lacks the funds)
+    /// is retried on the next run, and the later occurrences of its recurring transaction wait for it.
+    ///
+    /// # Returns
+    /// * `Vec<TransactionId>` — The created transactions.
+    pub fn materialize_due(&self, now: DateTime<Utc>) -> Vec<TransactionId> {
+        // The recurring transactions are not locked while the transactions are created: the creation locks the
+        // payees, and a payee merge locks the payees before the recurring transactions.
+        let recurring_transactions = self
+            .recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .find_all();
+        let mut created: Vec<TransactionId> = vec![];
+
+        for recurring_transaction in recurring_transactions {
+            if recurring_transaction.status() != &RecurringStatus::Active {
+                continue;
+            }
+            let id = recurring_transaction
+                .id()
+                .cloned()
+                .expect("The recurring transactions are saved");
+            let due: Vec<DateTime<Utc>> = recurring_transaction
+                .next_occurrences()
+                .take_while(|date| *date <= now)
+                .collect();
+            if due.is_empty() {
+                continue;
+            }
+
+            let mut last_occurrence = None;
+            for date in due {
+                if !recurring_transaction.is_skipped(&date) {
+                    let transaction = recurring_transaction.template().to_transaction(date);
+                    match self.transaction_service.lock().unwrap().create(transaction) {
+                        Ok(transaction_id) => created.push(transaction_id),
+                        Err(error) => {
+                            warn!("The occurrence of {date} of the recurring transaction {id} was not created: {error}");
+                            break;
+                        }
+                    }
+                }
+                last_occurrence = Some(date);
+            }
+            if last_occurrence.is_none() {
+                continue;
+            }
+
+            // Read again, a payee or a category may have been merged into another one meanwhile
+            let mut repository = self.recurring_transaction_repository.lock().unwrap();
+            if let Some(mut recurring_transaction) = repository.find_by_id(id.clone()) {
+                recurring_transaction.set_last_occurrence(last_occurrence);
+                repository
+                    .find_by_id_and_update(id, recurring_transaction)
+                    .expect("The recurring transaction was just found");
+            }
+        }
+
+        created
+    }
+}
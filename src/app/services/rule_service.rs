@@ -0,0 +1,471 @@
+use crate::app::entities::account::{Account, AccountId};
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::rule::{Rule, RuleId};
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{Transaction, TransactionId, TransactionStatus};
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::common::stream_transactions;
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::rule_repository::{
+    DeleteError as RepositoryDeleteError, FindByIdAndUpdateError, RuleRepository,
+};
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Rule not found by the provided id")]
+    NotFound(RuleId),
+}
+
+/// The reasons a rule can not be saved, whether it is created, updated or tried out.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The rule name must not be empty")]
+    EmptyName,
+
+    #[error("A rule must have at least one condition")]
+    NoCondition,
+
+    #[error("A rule must have at least one action")]
+    NoAction,
+
+    #[error("The pattern is not a valid regular expression: {0}")]
+    InvalidPattern(String),
+
+    #[error("The minimum amount must not be greater than the maximum amount")]
+    InvertedAmountRange,
+
+    #[error("The account was not found")]
+    AccountNotFound(AccountId),
+
+    #[error("The category was not found")]
+    CategoryNotFound(CategoryId),
+
+    #[error("The category kind does not match the transaction type of the rule")]
+    CategoryKindMismatch,
+
+    #[error("The payee was not found")]
+    PayeeNotFound(PayeeId),
+
+    #[error("A rule can not roll a transaction back, it is only rolled back by reversing it")]
+    RolledBackStatus,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CreateError {
+    #[error("Rule ID must not be provided")]
+    EntityIdProvided,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpdateError {
+    #[error("Rule not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] ValidationError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("Rule not found by the provided id")]
+    EntityIdNotFound,
+}
+
+/// How the rules change an existing transaction.
+/// The fields are only set when the rules change them.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct RuleChange {
+    pub transaction_id: TransactionId,
+
+    /// The rules which changed the transaction, by priority.
+    pub rule_ids: Vec<RuleId>,
+
+    /// The category given to the transaction.
+    pub category_id: Option<CategoryId>,
+
+    /// The payee given to the transaction.
+    pub payee_id: Option<PayeeId>,
+
+    /// The tags added to the transaction.
+    pub added_tags: Vec<TagId>,
+
+    /// The status given to the transaction.
+    pub status: Option<TransactionStatus>,
+}
+
+impl RuleChange {
+    /// Compares a transaction before and after the rules, returns None when they left it unchanged.
+    fn new(before: &Transaction, after: &Transaction, rule_ids: Vec<RuleId>) -> Option<Self> {
+        let change = Self {
+            transaction_id: before.id().clone()?,
+            rule_ids,
+            category_id: (after.category_id() != before.category_id())
+                .then(|| after.category_id().cloned())
+                .flatten(),
+            payee_id: (after.payee_id() != before.payee_id())
+                .then(|| after.payee_id().cloned())
+                .flatten(),
+            added_tags: after
+                .tags()
+                .iter()
+                .filter(|tag_id| !before.tags().contains(tag_id))
+                .cloned()
+                .collect(),
+            status: (after.status() != before.status()).then(|| after.status().clone()),
+        };
+
+        let changed = change.category_id.is_some()
+            || change.payee_id.is_some()
+            || !change.added_tags.is_empty()
+            || change.status.is_some();
+        changed.then_some(change)
+    }
+}
+
+pub struct RuleService<R, TR, AR, CR, PR> {
+    rule_repository: Arc<Mutex<R>>,
+    transaction_repository: Arc<Mutex<TR>>,
+    account_repository: Arc<Mutex<AR>>,
+    category_repository: Arc<Mutex<CR>>,
+    payee_repository: Arc<Mutex<PR>>,
+}
+
+impl<
+        R: RuleRepository,
+        TR: TransactionRepository,
+        AR: AccountRepository,
+        CR: CategoryRepository,
+        PR: PayeeRepository,
+    > RuleService<R, TR, AR, CR, PR>
+{
+    pub fn new(
+        rule_repository: Arc<Mutex<R>>,
+        transaction_repository: Arc<Mutex<TR>>,
+        account_repository: Arc<Mutex<AR>>,
+        category_repository: Arc<Mutex<CR>>,
+        payee_repository: Arc<Mutex<PR>>,
+    ) -> Self {
+        Self {
+            rule_repository,
+            transaction_repository,
+            account_repository,
+            category_repository,
+            payee_repository,
+        }
+    }
+
+    /// Retrieves all the rules, by increasing priority.
+    pub fn find_all(&self) -> Vec<Rule> {
+        self.rule_repository.lock().unwrap().find_all()
+    }
+
+    pub fn find_by_id(&self, id: RuleId) -> Option<Rule> {
+        self.rule_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Retrieves a rule by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no rule with the provided ID.
+    pub fn find_by_id_or_fail(&self, id: &RuleId) -> Result<Rule, FindByIdOrFailError> {
+        self.find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Trims the name of the rule, then checks its conditions and the entities its actions reference.
+    fn validate(&self, rule: &mut Rule) -> Result<(), ValidationError> {
+        rule.set_name(rule.name().trim().to_string());
+        if rule.name().is_empty() {
+            return Err(ValidationError::EmptyName);
+        }
+
+        let conditions = rule.conditions();
+        let has_condition = conditions.description.is_some()
+            || conditions.message.is_some()
+            || conditions.reference_number.is_some()
+            || conditions.min_amount.is_some()
+            || conditions.max_amount.is_some()
+            || conditions.account_id.is_some()
+            || conditions.institution_id.is_some()
+            || conditions.account_type.is_some()
+            || conditions.transaction_type.is_some();
+        if !has_condition {
+            return Err(ValidationError::NoCondition);
+        }
+
+        let actions = rule.actions();
+        if actions.category_id.is_none()
+            && actions.payee_id.is_none()
+            && actions.tags.is_empty()
+            && actions.status.is_none()
+        {
+            return Err(ValidationError::NoAction);
+        }
+        if actions.status == Some(TransactionStatus::RolledBack) {
+            return Err(ValidationError::RolledBackStatus);
+        }
+
+        if let Some(pattern) = [
+            &conditions.description,
+            &conditions.message,
+            &conditions.reference_number,
+        ]
+        .into_iter()
+        .flatten()
+        .find(|pattern| Regex::new(pattern).is_err())
+        {
+            return Err(ValidationError::InvalidPattern(pattern.clone()));
+        }
+
+        if let (Some(min_amount), Some(max_amount)) =
+            (&conditions.min_amount, &conditions.max_amount)
+        {
+            if min_amount > max_amount {
+                return Err(ValidationError::InvertedAmountRange);
+            }
+        }
+
+        if let Some(account_id) = &conditions.account_id {
+            if self
+                .account_repository
+                .lock()
+                .unwrap()
+                .find_by_id(account_id.clone())
+                .is_none()
+            {
+                return Err(ValidationError::AccountNotFound(account_id.clone()));
+            }
+        }
+
+        if let Some(category_id) = &actions.category_id {
+            let category = self
+                .category_repository
+                .lock()
+                .unwrap()
+                .find_by_id(category_id.clone())
+                .ok_or_else(|| ValidationError::CategoryNotFound(category_id.clone()))?;
+            if conditions
+                .transaction_type
+                .as_ref()
+                .is_some_and(|transaction_type| transaction_type != category.kind())
+            {
+                return Err(ValidationError::CategoryKindMismatch);
+            }
+        }
+
+        if let Some(payee_id) = &actions.payee_id {
+            if self
+                .payee_repository
+                .lock()
+                .unwrap()
+                .find_by_id(payee_id.clone())
+                .is_none()
+            {
+                return Err(ValidationError::PayeeNotFound(payee_id.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn create(&self, mut rule: Rule) -> Result<RuleId, CreateError> {
+        if rule.id().is_some() {
+            return Err(CreateError::EntityIdProvided);
+        }
+
+        self.validate(&mut rule)?;
+
+        Ok(self.rule_repository.lock().unwrap().create(rule))
+    }
+
+    pub fn update(&self, id: &RuleId, mut rule: Rule) -> Result<Rule, UpdateError> {
+        if self.find_by_id(id.clone()).is_none() {
+            return Err(UpdateError::EntityIdNotFound);
+        }
+        rule.set_id(Some(id.clone()));
+
+        self.validate(&mut rule)?;
+
+        match self
+            .rule_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), rule.clone())
+        {
+            Ok(_) => Ok(rule),
+            Err(FindByIdAndUpdateError::NotFound) => Err(UpdateError::EntityIdNotFound),
+        }
+    }
+
+    pub fn delete(&self, id: &RuleId) -> Result<(), DeleteError> {
+        match self.rule_repository.lock().unwrap().delete(id.clone()) {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// Applies the rules to a transaction, in the order they are given.
+    ///
+    /// The category and the payee are only given to a transaction without one, so the manual
+    /// classification is kept, and a field set by a rule is not overwritten by the next ones.
+    /// The actions referencing a category or a payee deleted since the rule was saved,
+    /// or a category of another kind than the transaction, are skipped.
+    ///
+    /// # Arguments
+    /// * `account` -- The account of the transaction.
+    ///
+    /// # Returns
+    /// * `Vec<RuleId>` — The rules which changed the transaction.
+    fn apply_rules(
+        &self,
+        rules: &[Rule],
+        transaction: &mut Transaction,
+        account: Option<&Account>,
+    ) -> Vec<RuleId> {
+        let mut applied: Vec<RuleId> = vec![];
+        let mut status_set = false;
+
+        for rule in rules {
+            if !rule.matches(transaction, account) {
+                continue;
+            }
+            let before = transaction.clone();
+            let actions = rule.actions();
+
+            if let Some(category_id) = &actions.category_id {
+                let category = self
+                    .category_repository
+                    .lock()
+                    .unwrap()
+                    .find_by_id(category_id.clone());
+                if transaction.category_id().is_none()
+                    && transaction.splits().is_empty()
+                    && category
+                        .is_some_and(|category| category.kind() == transaction.transaction_type())
+                {
+                    transaction.set_category_id(Some(category_id.clone()));
+                }
+            }
+
+            if let Some(payee_id) = &actions.payee_id {
+                if transaction.payee_id().is_none()
+                    && self
+                        .payee_repository
+                        .lock()
+                        .unwrap()
+                        .find_by_id(payee_id.clone())
+                        .is_some()
+                {
+                    transaction.set_payee_id(Some(payee_id.clone()));
+                }
+            }
+
+            let mut tags = transaction.tags().clone();
+            for tag_id in &actions.tags {
+                if !tags.contains(tag_id) {
+                    tags.push(tag_id.clone());
+                }
+            }
+            transaction.set_tags(tags);
+
+            if let Some(status) = &actions.status {
+                if !status_set {
+                    transaction.set_status(status.clone());
+                    status_set = true;
+                }
+            }
+
+            if *transaction != before {
+                applied.push(rule.id().cloned().expect("The rules are saved"));
+            }
+        }
+
+        applied
+    }
+
+    /// Applies the saved rules to a new transaction, by increasing priority.
+    ///
+    /// # Arguments
+    /// * `account` -- The account of the transaction.
+    pub fn apply(&self, transaction: &mut Transaction, account: Option<&Account>) -> Vec<RuleId> {
+        let rules = self.find_all();
+        self.apply_rules(&rules, transaction, account)
+    }
+
+    /// Runs the rules over the history, and lists the transactions they change.
+    fn evaluate_history(&self, rules: &[Rule]) -> Vec<(RuleChange, Transaction)> {
+        let mut accounts: HashMap<AccountId, Option<Account>> = HashMap::new();
+        let mut changes: Vec<(RuleChange, Transaction)> = vec![];
+        let transactions = stream_transactions(
+            self.transaction_repository.clone(),
+            TransactionQuery::default(),
+        );
+        for transaction in transactions {
+            let Some(account_id) = transaction.account_id().cloned() else {
+                continue;
+            };
+            let account = accounts.entry(account_id.clone()).or_insert_with(|| {
+                self.account_repository
+                    .lock()
+                    .unwrap()
+                    .find_by_id(account_id)
+            });
+
+            let mut updated = transaction.clone();
+            let rule_ids = self.apply_rules(rules, &mut updated, account.as_ref());
+            if let Some(change) = RuleChange::new(&transaction, &updated, rule_ids) {
+                changes.push((change, updated));
+            }
+        }
+
+        changes
+    }
+
+    /// Lists the existing transactions a rule would change, without changing them.
+    /// The rule does not need to be saved, so it can be tried out before.
+    pub fn dry_run(&self, mut rule: Rule) -> Result<Vec<RuleChange>, ValidationError> {
+        self.validate(&mut rule)?;
+        // The changes reference the rule, an unsaved one is given a temporary id.
+        if rule.id().is_none() {
+            rule.set_id(Some(RuleId::generate()));
+        }
+
+        Ok(self
+            .evaluate_history(&[rule])
+            .into_iter()
+            .map(|(change, _)| change)
+            .collect())
+    }
+
+    /// Applies the saved rules to the existing transactions, by increasing priority.
+    ///
+    /// # Returns
+    /// * `Vec<RuleChange>` — The changed transactions.
+    pub fn apply_to_history(&self) -> Vec<RuleChange> {
+        let rules = self.find_all();
+        let changes = self.evaluate_history(&rules);
+
+        let mut transaction_repository = self.transaction_repository.lock().unwrap();
+        changes
+            .into_iter()
+            .map(|(change, transaction)| {
+                transaction_repository
+                    .find_by_id_and_update(change.transaction_id.clone(), transaction)
+                    .expect("The transaction was just read");
+                change
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,188 @@
+use crate::app::entities::account::{Account, AccountId, AccountType};
+use crate::app::entities::statement::{Statement, StatementConfig};
+use crate::app::entities::transaction::TransactionStatus;
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::common::stream_transactions;
+use crate::app::repositories::statement_config_repository::{
+    DeleteError as RepositoryDeleteError, StatementConfigRepository,
+};
+use crate::app::repositories::transaction_query::{
+    SortDirection, TransactionQuery, TransactionSort, TransactionSortField,
+};
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use chrono::{Days, NaiveDate, TimeZone, Utc};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// The longest grace period, in days.
+pub(crate) const MAX_GRACE_PERIOD_DAYS: u32 = 60;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("The account has no statement configuration")]
+    NotFound(AccountId),
+}
+
+/// The reasons a statement configuration can not be saved.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("The account was not found")]
+    AccountNotFound(AccountId),
+
+    #[error("The account is not a credit account")]
+    NotACreditAccount(AccountId),
+
+    #[error("The closing day must be between 1 and 31")]
+    ClosingDayOutOfBounds,
+
+    #[error("The grace period must be at most {max} days", max = MAX_GRACE_PERIOD_DAYS)]
+    GracePeriodTooLong,
+
+    #[error("The minimum payment rate must be between 0 and 100%")]
+    MinimumPaymentRateOutOfBounds,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DeleteError {
+    #[error("The account has no statement configuration")]
+    EntityIdNotFound,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum StatementError {
+    #[error("The account has no statement configuration")]
+    NotConfigured(AccountId),
+
+    #[error("The account was not found")]
+    AccountNotFound(AccountId),
+
+    #[error("The account has no closed statement cycle yet")]
+    NoStatement(AccountId),
+}
+
+pub struct StatementService<R, AR, TR> {
+    statement_config_repository: Arc<Mutex<R>>,
+    account_repository: Arc<Mutex<AR>>,
+    transaction_repository: Arc<Mutex<TR>>,
+}
+
+impl<R: StatementConfigRepository, AR: AccountRepository, TR: TransactionRepository>
+    StatementService<R, AR, TR>
+{
+    pub fn new(
+        statement_config_repository: Arc<Mutex<R>>,
+        account_repository: Arc<Mutex<AR>>,
+        transaction_repository: Arc<Mutex<TR>>,
+    ) -> Self {
+        Self {
+            statement_config_repository,
+            account_repository,
+            transaction_repository,
+        }
+    }
+
+    /// Retrieves the statement configuration of an account.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If the account has no statement configuration.
+    pub fn find_by_account_id_or_fail(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<StatementConfig, FindByIdOrFailError> {
+        self.statement_config_repository
+            .lock()
+            .unwrap()
+            .find_by_account_id(account_id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(account_id.clone()))
+    }
+
+    fn find_account(&self, account_id: &AccountId) -> Option<Account> {
+        self.account_repository
+            .lock()
+            .unwrap()
+            .find_by_id(account_id.clone())
+    }
+
+    /// Sets how the statements of a credit account are cut and paid, replacing its previous configuration.
+    /// The statements are cut again from the transactions with the new configuration.
+    pub fn configure(&self, config: StatementConfig) -> Result<StatementConfig, ValidationError> {
+        let account_id = config.account_id().clone();
+        let account = self
+            .find_account(&account_id)
+            .ok_or_else(|| ValidationError::AccountNotFound(account_id.clone()))?;
+        if account.account_type() != &AccountType::Credit {
+            return Err(ValidationError::NotACreditAccount(account_id));
+        }
+        if !(1..=31).contains(&config.closing_day()) {
+            return Err(ValidationError::ClosingDayOutOfBounds);
+        }
+        if config.grace_period_days() > MAX_GRACE_PERIOD_DAYS {
+            return Err(ValidationError::GracePeriodTooLong);
+        }
+        if !(0.0..=100.0).contains(&config.minimum_payment_rate()) {
+            return Err(ValidationError::MinimumPaymentRateOutOfBounds);
+        }
+
+        self.statement_config_repository
+            .lock()
+            .unwrap()
+            .save(config.clone());
+
+        Ok(config)
+    }
+
+    /// Stops cutting statements for an account, its transactions are kept.
+    pub fn remove(&self, account_id: &AccountId) -> Result<(), DeleteError> {
+        match self
+            .statement_config_repository
+            .lock()
+            .unwrap()
+            .delete(account_id.clone())
+        {
+            Ok(()) => Ok(()),
+            Err(RepositoryDeleteError::NotFound) => Err(DeleteError::EntityIdNotFound),
+        }
+    }
+
+    /// Cuts the statements of the cycles of a credit account closed before the day `today`, in date order,
+    /// from its confirmed transactions in its currency.
+    pub fn statements(
+        &self,
+        account_id: &AccountId,
+        today: NaiveDate,
+    ) -> Result<Vec<Statement>, StatementError> {
+        let config = self
+            .find_by_account_id_or_fail(account_id)
+            .map_err(|_| StatementError::NotConfigured(account_id.clone()))?;
+        let account = self
+            .find_account(account_id)
+            .ok_or_else(|| StatementError::AccountNotFound(account_id.clone()))?;
+
+        let end_of_today =
+            Utc.from_utc_datetime(&(today + Days::new(1)).and_hms_opt(0, 0, 0).unwrap());
+        let query = TransactionQuery {
+            account_id: Some(account_id.clone()),
+            to: Some(end_of_today),
+            status: Some(TransactionStatus::Confirmed),
+            currency: Some(account.currency().clone()),
+            sort: TransactionSort {
+                field: TransactionSortField::Date,
+                direction: SortDirection::Asc,
+            },
+            ..TransactionQuery::default()
+        };
+        let transactions = stream_transactions(self.transaction_repository.clone(), query);
+
+        Ok(config.statements(transactions, today))
+    }
+
+    /// The statement of the last cycle of a credit account closed before the day `today`.
+    pub fn latest(
+        &self,
+        account_id: &AccountId,
+        today: NaiveDate,
+    ) -> Result<Statement, StatementError> {
+        self.statements(account_id, today)?
+            .pop()
+            .ok_or_else(|| StatementError::NoStatement(account_id.clone()))
+    }
+}
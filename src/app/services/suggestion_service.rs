@@ -0,0 +1,293 @@
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::transaction::{Transaction, TransactionId};
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::common::stream_transactions;
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+/// The number of suggestions given for a transaction.
+pub(crate) const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SuggestError {
+    #[error("Transaction not found by the provided id")]
+    TransactionNotFound(TransactionId),
+}
+
+/// A category the transaction likely belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CategorySuggestion {
+    pub category_id: CategoryId,
+
+    /// How likely the transaction belongs to the category, between 0 and 1.
+    /// The confidences of all the candidate categories add up to 1.
+    pub confidence: f64,
+}
+
+/// The words of a text, lowercased, leaving out the single characters and the numbers,
+/// which are mostly references and dates.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|character: char| !character.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1 && !word.chars().all(|c| c.is_ascii_digit()))
+        .map(|word| word.to_lowercase())
+}
+
+/// What the classifier knows of a transaction: the words of its description and message,
+/// its payee and the order of magnitude of its amount.
+fn features(transaction: &Transaction) -> HashSet<String> {
+    let mut features: HashSet<String> = [transaction.description(), transaction.message()]
+        .into_iter()
+        .flatten()
+        .flat_map(|text| tokenize(text))
+        .map(|word| format!("word:{word}"))
+        .collect();
+
+    if let Some(payee_id) = transaction.payee_id() {
+        features.insert(format!("payee:{payee_id}"));
+    }
+
+    let amount = transaction.amount().value();
+    let magnitude = if amount < 1.0 {
+        0
+    } else {
+        amount.log10().floor() as i32 + 1
+    };
+    features.insert(format!("amount:{magnitude}"));
+
+    features
+}
+
+/// Lowers the count of a feature, which is left out once it reaches 0.
+fn decrement(counts: &mut HashMap<String, usize>, feature: &str) {
+    if let Some(count) = counts.get_mut(feature) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(feature);
+        }
+    }
+}
+
+/// The transactions of a category seen by the classifier.
+#[derive(Default)]
+struct CategoryStatistics {
+    transactions: usize,
+    features: usize,
+    feature_counts: HashMap<String, usize>,
+}
+
+/// A multinomial naive Bayes classifier, trained on the categorized transactions.
+#[derive(Default)]
+struct CategoryClassifier {
+    transactions: usize,
+    categories: HashMap<CategoryId, CategoryStatistics>,
+
+    /// The features seen in training, with the number of transactions having each of them.
+    vocabulary: HashMap<String, usize>,
+}
+
+impl CategoryClassifier {
+    fn train(&mut self, category_id: &CategoryId, transaction: &Transaction) {
+        let statistics = self.categories.entry(category_id.clone()).or_default();
+        self.transactions += 1;
+        statistics.transactions += 1;
+
+        for feature in features(transaction) {
+            statistics.features += 1;
+            *statistics
+                .feature_counts
+                .entry(feature.clone())
+                .or_default() += 1;
+            *self.vocabulary.entry(feature).or_default() += 1;
+        }
+    }
+
+    /// Forgets a transaction it was trained on, as if it never had been.
+    fn untrain(&mut self, category_id: &CategoryId, transaction: &Transaction) {
+        let Some(statistics) = self.categories.get_mut(category_id) else {
+            return;
+        };
+        self.transactions -= 1;
+        statistics.transactions -= 1;
+
+        for feature in features(transaction) {
+            statistics.features -= 1;
+            decrement(&mut statistics.feature_counts, &feature);
+            decrement(&mut self.vocabulary, &feature);
+        }
+        if statistics.transactions == 0 {
+            self.categories.remove(category_id);
+        }
+    }
+
+    /// Ranks the candidate categories of a transaction, from the most likely to the least.
+    ///
+    /// The features never seen in training are ignored, and the counts are Laplace smoothed,
+    /// so a feature unseen in a category lowers its likelihood without ruling it out.
+    fn rank(
+        &self,
+        transaction: &Transaction,
+        is_candidate: impl Fn(&CategoryId) -> bool,
+    ) -> Vec<CategorySuggestion> {
+        let features: Vec<String> = features(transaction)
+            .into_iter()
+            .filter(|feature| self.vocabulary.contains_key(feature))
+            .collect();
+        let vocabulary_size = self.vocabulary.len() as f64;
+
+        let log_likelihoods: Vec<(&CategoryId, f64)> = self
+            .categories
+            .iter()
+            .filter(|(category_id, _)| is_candidate(category_id))
+            .map(|(category_id, statistics)| {
+                let prior = (statistics.transactions as f64 / self.transactions as f64).ln();
+                let likelihood: f64 = features
+                    .iter()
+                    .map(|feature| {
+                        let count = statistics.feature_counts.get(feature).copied().unwrap_or(0);
+                        ((count + 1) as f64 / (statistics.features as f64 + vocabulary_size)).ln()
+                    })
+                    .sum();
+                (category_id, prior + likelihood)
+            })
+            .collect();
+
+        // Normalized in the log space, the likelihoods of long texts would underflow otherwise.
+        let Some(max) = log_likelihoods
+            .iter()
+            .map(|(_, log_likelihood)| *log_likelihood)
+            .reduce(f64::max)
+        else {
+            return vec![];
+        };
+        let total: f64 = log_likelihoods
+            .iter()
+            .map(|(_, log_likelihood)| (log_likelihood - max).exp())
+            .sum();
+
+        let mut suggestions: Vec<CategorySuggestion> = log_likelihoods
+            .into_iter()
+            .map(|(category_id, log_likelihood)| CategorySuggestion {
+                category_id: category_id.clone(),
+                confidence: (log_likelihood - max).exp() / total,
+            })
+            .collect();
+        suggestions.sort_by(|a, b| {
+            b.confidence
+                .partial_cmp(&a.confidence)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.category_id.cmp(&b.category_id))
+        });
+
+        suggestions
+    }
+}
+
+/// Suggests categories for the transactions, learning from the ones already categorized.
+pub struct SuggestionService<R, CR> {
+    transaction_repository: Arc<Mutex<R>>,
+    category_repository: Arc<Mutex<CR>>,
+
+    /// The classifier trained on the history, with the version of the transactions it was trained on.
+    classifier: Option<(u64, CategoryClassifier)>,
+}
+
+impl<R: TransactionRepository, CR: CategoryRepository> SuggestionService<R, CR> {
+    pub fn new(transaction_repository: Arc<Mutex<R>>, category_repository: Arc<Mutex<CR>>) -> Self {
+        Self {
+            transaction_repository,
+            category_repository,
+            classifier: None,
+        }
+    }
+
+    /// Trains a classifier on the transactions given a single category.
+    fn train(&self) -> CategoryClassifier {
+        let mut classifier = CategoryClassifier::default();
+        let transactions = stream_transactions(
+            self.transaction_repository.clone(),
+            TransactionQuery::default(),
+        );
+        for transaction in transactions {
+            if let Some(category_id) = transaction.category_id() {
+                classifier.train(category_id, &transaction);
+            }
+        }
+
+        classifier
+    }
+
+    /// Trains the classifier again when the transactions changed since it last was,
+    /// it is kept between the suggestions otherwise.
+    fn refresh_classifier(&mut self) {
+        let version = self.transaction_repository.lock().unwrap().version();
+        if self
+            .classifier
+            .as_ref()
+            .is_none_or(|(trained_version, _)| *trained_version != version)
+        {
+            self.classifier = Some((version, self.train()));
+        }
+    }
+
+    /// Ranks the categories a transaction likely belongs to, with their confidence.
+    ///
+    /// The classifier is trained on the history, without the transaction itself when it is saved,
+    /// and only the existing categories of the kind of the transaction are suggested.
+    /// There is no suggestion until some transactions of that kind are categorized.
+    pub fn suggest(&mut self, transaction: &Transaction) -> Vec<CategorySuggestion> {
+        self.refresh_classifier();
+        let (_, classifier) = self
+            .classifier
+            .as_mut()
+            .expect("The classifier was just trained");
+
+        // The saved version of the transaction is the one the classifier learned from
+        let saved = transaction
+            .id()
+            .clone()
+            .and_then(|id| self.transaction_repository.lock().unwrap().find_by_id(id));
+        let learned = saved
+            .as_ref()
+            .and_then(|saved| saved.category_id().map(|category_id| (category_id, saved)));
+        if let Some((category_id, saved)) = learned {
+            classifier.untrain(category_id, saved);
+        }
+
+        let category_repository = self.category_repository.lock().unwrap();
+        let mut suggestions = classifier.rank(transaction, |category_id| {
+            category_repository
+                .find_by_id(category_id.clone())
+                .is_some_and(|category| category.kind() == transaction.transaction_type())
+        });
+        suggestions.truncate(MAX_SUGGESTIONS);
+
+        if let Some((category_id, saved)) = learned {
+            classifier.train(category_id, saved);
+        }
+
+        suggestions
+    }
+
+    /// Ranks the categories a saved transaction likely belongs to.
+    /// #### Errors
+    /// * `SuggestError::TransactionNotFound` — If there is no transaction with the provided ID.
+    pub fn suggest_by_id(
+        &mut self,
+        id: TransactionId,
+    ) -> Result<Vec<CategorySuggestion>, SuggestError> {
+        let transaction = self
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(id.clone())
+            .ok_or(SuggestError::TransactionNotFound(id))?;
+
+        Ok(self.suggest(&transaction))
+    }
+}
@@ -0,0 +1,339 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::tag::{Tag, TagId};
+use crate::app::entities::transaction::{TransactionId, TransactionStatus, TransactionType};
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::budget_repository::BudgetRepository;
+use crate::app::repositories::common::stream_transactions;
+use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::repositories::tag_repository::{FindByIdAndUpdateError, TagRepository};
+use crate::app::repositories::transaction_query::TransactionQuery;
+use crate::app::repositories::transaction_repository::{
+    SetTagsError as RepositorySetTagsError, TransactionRepository,
+};
+use crate::app::typing::currency::Currency;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByIdOrFailError {
+    #[error("Tag not found by the provided id")]
+    NotFound(TagId),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FindByNameError {
+    #[error("Tag not found: {0}")]
+    NotFound(String),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum TagNameError {
+    #[error("The tag name must not be empty")]
+    EmptyName,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RenameError {
+    #[error("Tag not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error("Another tag already has this name, the tags can be merged instead")]
+    DuplicateName(TagId),
+
+    #[error(transparent)]
+    Invalid(#[from] TagNameError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MergeError {
+    #[error("The tag to merge was not found")]
+    SourceNotFound(TagId),
+
+    #[error("The tag to merge into was not found")]
+    TargetNotFound(TagId),
+
+    #[error("A tag can not be merged into itself")]
+    SameTag,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SetTagsError {
+    #[error("The tagged entity was not found by the provided id")]
+    EntityIdNotFound,
+
+    #[error(transparent)]
+    Invalid(#[from] TagNameError),
+}
+
+/// The totals of the transactions carrying a tag, in one currency.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct TagTotal {
+    pub tag_id: TagId,
+
+    pub currency: Currency,
+
+    /// The number of transactions carrying the tag.
+    pub transaction_count: usize,
+
+    /// The total amount of the income transactions.
+    pub income: f32,
+
+    /// The total amount of the expense transactions.
+    pub expense: f32,
+}
+
+pub struct TagService<R, TR, AR, RR, RTR, BR> {
+    tag_repository: Arc<Mutex<R>>,
+    transaction_repository: Arc<Mutex<TR>>,
+    account_repository: Arc<Mutex<AR>>,
+    rule_repository: Arc<Mutex<RR>>,
+    recurring_transaction_repository: Arc<Mutex<RTR>>,
+    budget_repository: Arc<Mutex<BR>>,
+}
+
+impl<
+        R: TagRepository,
+        TR: TransactionRepository,
+        AR: AccountRepository,
+        RR: RuleRepository,
+        RTR: RecurringTransactionRepository,
+        BR: BudgetRepository,
+    > TagService<R, TR, AR, RR, RTR, BR>
+{
+    pub fn new(
+        tag_repository: Arc<Mutex<R>>,
+        transaction_repository: Arc<Mutex<TR>>,
+        account_repository: Arc<Mutex<AR>>,
+        rule_repository: Arc<Mutex<RR>>,
+        recurring_transaction_repository: Arc<Mutex<RTR>>,
+        budget_repository: Arc<Mutex<BR>>,
+    ) -> Self {
+        Self {
+            tag_repository,
+            transaction_repository,
+            account_repository,
+            rule_repository,
+            recurring_transaction_repository,
+            budget_repository,
+        }
+    }
+
+    /// Retrieves all the tags, sorted by name.
+    pub fn find_all(&self) -> Vec<Tag> {
+        self.tag_repository.lock().unwrap().find_all()
+    }
+
+    /// Retrieves a tag by its ID.
+    /// #### Errors
+    /// * `FindByIdOrFailError::NotFound` — If there is no tag with the provided ID.
+    pub fn find_by_id_or_fail(&self, id: &TagId) -> Result<Tag, FindByIdOrFailError> {
+        self.tag_repository
+            .lock()
+            .unwrap()
+            .find_by_id(id.clone())
+            .ok_or_else(|| FindByIdOrFailError::NotFound(id.clone()))
+    }
+
+    /// Retrieves the IDs of existing tags, by name. Used to filter the listings, so no tag is created.
+    /// #### Errors
+    /// * `FindByNameError::NotFound` — If one of the names is not a tag.
+    pub fn find_by_names(&self, names: &[String]) -> Result<Vec<TagId>, FindByNameError> {
+        let repository = self.tag_repository.lock().unwrap();
+
+        let mut ids: Vec<TagId> = vec![];
+        for name in names {
+            let tag = repository
+                .find_by_name(&Tag::normalize_name(name))
+                .ok_or_else(|| FindByNameError::NotFound(name.clone()))?;
+            let id = tag.id().unwrap().clone();
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Retrieves the IDs of the tags, by name, creating the tags that do not exist yet.
+    /// The tags are free-form: attaching a tag by name is enough to create it.
+    /// #### Errors
+    /// * `TagNameError::EmptyName` — If one of the names is blank.
+    pub fn find_or_create(&self, names: &[String]) -> Result<Vec<TagId>, TagNameError> {
+        let names: Vec<String> = names.iter().map(|name| Tag::normalize_name(name)).collect();
+        if names.iter().any(|name| name.is_empty()) {
+            return Err(TagNameError::EmptyName);
+        }
+
+        let mut repository = self.tag_repository.lock().unwrap();
+        let mut ids: Vec<TagId> = vec![];
+        for name in names {
+            let id = match repository.find_by_name(&name) {
+                Some(tag) => tag.id().unwrap().clone(),
+                None => repository.create(Tag::new(None, name)),
+            };
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Renames a tag. The name is normalized, and must not be the name of another tag.
+    pub fn rename(&self, id: &TagId, name: &str) -> Result<Tag, RenameError> {
+        let name = Tag::normalize_name(name);
+        if name.is_empty() {
+            return Err(TagNameError::EmptyName.into());
+        }
+
+        let mut repository = self.tag_repository.lock().unwrap();
+        if let Some(other) = repository.find_by_name(&name) {
+            if other.id() != Some(id) {
+                return Err(RenameError::DuplicateName(other.id().unwrap().clone()));
+            }
+        }
+
+        let tag = Tag::new(Some(id.clone()), name);
+        match repository.find_by_id_and_update(id.clone(), tag.clone()) {
+            Ok(_) => Ok(tag),
+            Err(FindByIdAndUpdateError::NotFound) => Err(RenameError::EntityIdNotFound),
+        }
+    }
+
+    /// Merges the tag `source` into the tag `target`: the transactions and accounts tagged with
+    /// `source` are tagged with `target` instead, the budgets of `source` apply to `target`,
+    /// then `source` is deleted.
+    ///
+    /// # Returns
+    /// * `Tag` — The tag `target`.
+    pub fn merge(&self, source: &TagId, target: &TagId) -> Result<Tag, MergeError> {
+        if source == target {
+            return Err(MergeError::SameTag);
+        }
+
+        let mut repository = self.tag_repository.lock().unwrap();
+        if repository.find_by_id(source.clone()).is_none() {
+            return Err(MergeError::SourceNotFound(source.clone()));
+        }
+        let target_tag = repository
+            .find_by_id(target.clone())
+            .ok_or_else(|| MergeError::TargetNotFound(target.clone()))?;
+
+        self.transaction_repository
+            .lock()
+            .unwrap()
+            .merge_tags(source, target);
+        self.account_repository
+            .lock()
+            .unwrap()
+            .merge_tags(source, target);
+        self.rule_repository
+            .lock()
+            .unwrap()
+            .merge_tags(source, target);
+        self.recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .merge_tags(source, target);
+        self.budget_repository
+            .lock()
+            .unwrap()
+            .merge_tags(source, target);
+        repository
+            .delete(source.clone())
+            .map_err(|_| MergeError::SourceNotFound(source.clone()))?;
+
+        Ok(target_tag)
+    }
+
+    /// Replaces the tags of a transaction, the tags are created when they do not exist yet.
+    ///
+    /// # Returns
+    /// * `Vec<TagId>` — The tags of the transaction.
+    pub fn set_transaction_tags(
+        &self,
+        id: &TransactionId,
+        names: &[String],
+    ) -> Result<Vec<TagId>, SetTagsError> {
+        let tags = self.find_or_create(names)?;
+
+        match self
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .set_tags(id.clone(), tags.clone())
+        {
+            Ok(()) => Ok(tags),
+            Err(RepositorySetTagsError::NotFound) => Err(SetTagsError::EntityIdNotFound),
+        }
+    }
+
+    /// Replaces the tags of an account, the tags are created when they do not exist yet.
+    ///
+    /// # Returns
+    /// * `Vec<TagId>` — The tags of the account.
+    pub fn set_account_tags(
+        &self,
+        id: &AccountId,
+        names: &[String],
+    ) -> Result<Vec<TagId>, SetTagsError> {
+        let tags = self.find_or_create(names)?;
+
+        let mut repository = self.account_repository.lock().unwrap();
+        let mut account = repository
+            .find_by_id(id.clone())
+            .ok_or(SetTagsError::EntityIdNotFound)?;
+        account.set_tags(tags.clone());
+        repository
+            .find_by_id_and_update(id.clone(), account)
+            .map_err(|_| SetTagsError::EntityIdNotFound)?;
+
+        Ok(tags)
+    }
+
+    /// Totals the confirmed transactions of each tag, per currency.
+    /// A transaction with several tags counts in each of them.
+    ///
+    /// # Arguments
+    /// * `from` -- Only the transactions on or after this date.
+    /// * `to` -- Only the transactions strictly before this date.
+    pub fn report(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Vec<TagTotal> {
+        let query = TransactionQuery {
+            from,
+            to,
+            status: Some(TransactionStatus::Confirmed),
+            ..Default::default()
+        };
+
+        // (tag, currency) -> total, ordered so the report is stable.
+        let mut totals: BTreeMap<(TagId, String), TagTotal> = BTreeMap::new();
+        let transactions = stream_transactions(self.transaction_repository.clone(), query);
+        for transaction in transactions {
+            for tag_id in transaction.tags() {
+                let currency = transaction.currency();
+                let total = totals
+                    .entry((tag_id.clone(), currency.to_string()))
+                    .or_insert_with(|| TagTotal {
+                        tag_id: tag_id.clone(),
+                        currency: currency.clone(),
+                        transaction_count: 0,
+                        income: 0.0,
+                        expense: 0.0,
+                    });
+
+                total.transaction_count += 1;
+                match transaction.transaction_type() {
+                    TransactionType::Income => total.income += transaction.amount().value(),
+                    TransactionType::Expense => total.expense += transaction.amount().value(),
+                }
+            }
+        }
+
+        totals.into_values().collect()
+    }
+}
@@ -0,0 +1,414 @@
+#[cfg(test)]
+mod tests_account_merge_service {
+    use crate::app::entities::account::{AccountId, AccountStatus, AccountType};
+    use crate::app::entities::goal::Goal;
+    use crate::app::entities::interest::{Compounding, DayCount, InterestConfig, PostingSchedule};
+    use crate::app::entities::loan::{Loan, PaymentFrequency};
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions};
+    use crate::app::entities::statement::StatementConfig;
+    use crate::app::entities::transaction::{AccountRef, TransactionType};
+    use crate::app::repositories::goal_repository::GoalRepository;
+    use crate::app::repositories::interest_config_repository::InterestConfigRepository;
+    use crate::app::repositories::loan_repository::LoanRepository;
+    use crate::app::repositories::rule_repository::RuleRepository;
+    use crate::app::repositories::statement_config_repository::StatementConfigRepository;
+    use crate::app::repositories::transaction_query::TransactionQuery;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::account_merge_service::{
+        AccountMergeService, ConfigMerge, MergeError,
+    };
+    use crate::app::services::account_service::AccountService;
+    use crate::app::services::rule_service::RuleService;
+    use crate::app::services::transaction_service::TransactionService;
+    use crate::app::typing::account_identifier::{AccountIdentifier, IdentifierKind};
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::goal_repository::InMemoryGoalRepository;
+    use crate::infrastructure::repositories::in_memory::interest_config_repository::InMemoryInterestConfigRepository;
+    use crate::infrastructure::repositories::in_memory::loan_repository::InMemoryLoanRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::recurring_transaction_repository::InMemoryRecurringTransactionRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+    use crate::infrastructure::repositories::in_memory::statement_config_repository::InMemoryStatementConfigRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+
+    type InMemoryTransactionService = TransactionService<
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+        InMemoryRuleRepository,
+    >;
+
+    type InMemoryAccountMergeService = AccountMergeService<
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+        InMemoryRuleRepository,
+        InMemoryRecurringTransactionRepository,
+        InMemoryGoalRepository,
+        InMemoryLoanRepository,
+        InMemoryInterestConfigRepository,
+        InMemoryStatementConfigRepository,
+    >;
+
+    struct AccountMergeServiceFixture {
+        merge_service: InMemoryAccountMergeService,
+        transaction_service: Arc<Mutex<InMemoryTransactionService>>,
+        transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        account_service:
+            Arc<Mutex<AccountService<InMemoryAccountRepository, InMemoryTransactionRepository>>>,
+        rule_repository: Arc<Mutex<InMemoryRuleRepository>>,
+        goal_repository: Arc<Mutex<InMemoryGoalRepository>>,
+        loan_repository: Arc<Mutex<InMemoryLoanRepository>>,
+        interest_config_repository: Arc<Mutex<InMemoryInterestConfigRepository>>,
+        statement_config_repository: Arc<Mutex<InMemoryStatementConfigRepository>>,
+    }
+
+    fn get_account_merge_service_fixture() -> AccountMergeServiceFixture {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let account_service = Arc::new(Mutex::new(AccountService::new(
+            account_repository.clone(),
+            transaction_repository.clone(),
+        )));
+        let category_repository = Arc::new(Mutex::new(InMemoryCategoryRepository::new()));
+        let payee_repository = Arc::new(Mutex::new(InMemoryPayeeRepository::new()));
+        let rule_repository = Arc::new(Mutex::new(InMemoryRuleRepository::new()));
+        let rule_service = Arc::new(Mutex::new(RuleService::new(
+            rule_repository.clone(),
+            transaction_repository.clone(),
+            account_repository,
+            category_repository.clone(),
+            payee_repository.clone(),
+        )));
+        let transaction_service = Arc::new(Mutex::new(TransactionService::new(
+            account_service.clone(),
+            transaction_repository.clone(),
+            category_repository,
+            payee_repository,
+            rule_service,
+        )));
+        let goal_repository = Arc::new(Mutex::new(InMemoryGoalRepository::new()));
+        let loan_repository = Arc::new(Mutex::new(InMemoryLoanRepository::new()));
+        let interest_config_repository =
+            Arc::new(Mutex::new(InMemoryInterestConfigRepository::new()));
+        let statement_config_repository =
+            Arc::new(Mutex::new(InMemoryStatementConfigRepository::new()));
+
+        AccountMergeServiceFixture {
+            merge_service: AccountMergeService::new(
+                account_service.clone(),
+                transaction_service.clone(),
+                rule_repository.clone(),
+                Arc::new(Mutex::new(InMemoryRecurringTransactionRepository::new())),
+                goal_repository.clone(),
+                loan_repository.clone(),
+                interest_config_repository.clone(),
+                statement_config_repository.clone(),
+            ),
+            transaction_service,
+            transaction_repository,
+            account_service,
+            rule_repository,
+            goal_repository,
+            loan_repository,
+            interest_config_repository,
+            statement_config_repository,
+        }
+    }
+
+    fn create_credit_account(fixture: &AccountMergeServiceFixture) -> AccountId {
+        let mut account = get_random_account();
+        account.set_account_type(AccountType::Credit);
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap()
+    }
+
+    /// Records a transaction of the amount on the account, dated at noon on the day.
+    fn record(
+        fixture: &AccountMergeServiceFixture,
+        account_id: &AccountId,
+        transaction_type: TransactionType,
+        amount: f32,
+        day: NaiveDate,
+    ) {
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id.clone()));
+        transaction.set_transaction_type(transaction_type);
+        transaction.set_amount(amount.try_into().unwrap());
+        transaction.set_fee(0f32.try_into().unwrap());
+        transaction.set_date(Utc.from_utc_datetime(&day.and_hms_opt(12, 0, 0).unwrap()));
+        transaction.set_opening_balance(None);
+        transaction.set_closing_balance(None);
+        fixture
+            .transaction_service
+            .lock()
+            .unwrap()
+            .create(transaction)
+            .unwrap();
+    }
+
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_merge_transactions() {
+        // GIVEN an account which received 100 in January and spent 30 in March
+        let fixture = get_account_merge_service_fixture();
+        let source = create_credit_account(&fixture);
+        record(
+            &fixture,
+            &source,
+            TransactionType::Income,
+            100.0,
+            day(1, 10),
+        );
+        record(
+            &fixture,
+            &source,
+            TransactionType::Expense,
+            30.0,
+            day(3, 10),
+        );
+
+        // AND its duplicate, which received 50 in February
+        let target = create_credit_account(&fixture);
+        record(&fixture, &target, TransactionType::Income, 50.0, day(2, 10));
+
+        // WHEN the account is merged into its duplicate
+        let merge = fixture.merge_service.merge(&source, &target).unwrap();
+
+        // THEN the duplicate holds both balances, and the history of both accounts
+        assert_eq!(merge.target.balance().value(), 120.0);
+        assert_eq!(merge.transaction_ids.len(), 2);
+        let transactions = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find(&TransactionQuery {
+                account_id: Some(target.clone()),
+                ..TransactionQuery::default()
+            })
+            .items;
+
+        // AND the balances chain in the order of the dates, the latest first
+        let balances: Vec<(f32, f32)> = transactions
+            .iter()
+            .map(|transaction| {
+                (
+                    transaction.opening_balance().as_ref().unwrap().value(),
+                    transaction.closing_balance().as_ref().unwrap().value(),
+                )
+            })
+            .collect();
+        assert_eq!(balances, vec![(150.0, 120.0), (100.0, 150.0), (0.0, 100.0)]);
+
+        // AND the merged account is closed, empty, with the reason recorded
+        assert_eq!(merge.source.status(), AccountStatus::Closed);
+        assert_eq!(merge.source.balance().value(), 0.0);
+        assert_eq!(
+            merge.source.status_changes()[0].reason,
+            format!("Merged into {}", merge.target.name())
+        );
+    }
+
+    #[test]
+    fn test_merge_references() {
+        // GIVEN an account referenced by a goal, a loan, a rule, and its interest and statement configurations
+        let fixture = get_account_merge_service_fixture();
+        let source = create_credit_account(&fixture);
+        let target = create_credit_account(&fixture);
+        let msisdn = AccountIdentifier::new(IdentifierKind::Msisdn, "0788123456").unwrap();
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .set_identifiers(&source, std::slice::from_ref(&msisdn))
+            .unwrap();
+        let goal_id = fixture.goal_repository.lock().unwrap().create(Goal::new(
+            None,
+            String::from("Holidays"),
+            1000f32.try_into().unwrap(),
+            Currency::RWF,
+            day(12, 1),
+            vec![source.clone(), target.clone()],
+        ));
+        let loan_id = fixture.loan_repository.lock().unwrap().create(Loan::new(
+            None,
+            String::from("Car"),
+            source.clone(),
+            1000f32.try_into().unwrap(),
+            10.0,
+            12,
+            PaymentFrequency::Monthly,
+            day(1, 1),
+        ));
+        let rule_id = fixture.rule_repository.lock().unwrap().create(Rule::new(
+            None,
+            String::from("Card"),
+            1,
+            RuleConditions {
+                account_id: Some(source.clone()),
+                ..RuleConditions::default()
+            },
+            RuleActions::default(),
+        ));
+        fixture
+            .interest_config_repository
+            .lock()
+            .unwrap()
+            .save(InterestConfig::new(
+                source.clone(),
+                18.0,
+                DayCount::Actual365,
+                Compounding::Daily,
+                PostingSchedule::Monthly,
+                day(1, 1),
+                None,
+            ));
+        for account_id in [&source, &target] {
+            fixture
+                .statement_config_repository
+                .lock()
+                .unwrap()
+                .save(StatementConfig::new(
+                    account_id.clone(),
+                    25,
+                    21,
+                    5.0,
+                    10f32.try_into().unwrap(),
+                ));
+        }
+
+        // WHEN the account is merged into another one
+        let merge = fixture.merge_service.merge(&source, &target).unwrap();
+
+        // THEN the goal saves on the other account, listed once
+        assert_eq!(merge.goal_ids, vec![goal_id.clone()]);
+        let goal = fixture
+            .goal_repository
+            .lock()
+            .unwrap()
+            .find_by_id(goal_id)
+            .unwrap();
+        assert_eq!(goal.account_ids(), &vec![target.clone()]);
+
+        // AND the loan and the rule reference the other account
+        assert_eq!(merge.loan_ids, vec![loan_id.clone()]);
+        let loan = fixture
+            .loan_repository
+            .lock()
+            .unwrap()
+            .find_by_id(loan_id)
+            .unwrap();
+        assert_eq!(loan.account_id(), &target);
+        assert_eq!(merge.rule_ids, vec![rule_id.clone()]);
+        let rule = fixture
+            .rule_repository
+            .lock()
+            .unwrap()
+            .find_by_id(rule_id)
+            .unwrap();
+        assert_eq!(rule.conditions().account_id, Some(target.clone()));
+
+        // AND the interest configuration is moved, the other account keeping its own statement configuration
+        assert_eq!(merge.interest_config, ConfigMerge::Moved);
+        assert_eq!(merge.statement_config, ConfigMerge::Dropped);
+        let interest_config_repository = fixture.interest_config_repository.lock().unwrap();
+        assert!(interest_config_repository
+            .find_by_account_id(source.clone())
+            .is_none());
+        assert_eq!(
+            interest_config_repository
+                .find_by_account_id(target.clone())
+                .unwrap()
+                .annual_rate(),
+            18.0
+        );
+        assert!(fixture
+            .statement_config_repository
+            .lock()
+            .unwrap()
+            .find_by_account_id(source)
+            .is_none());
+
+        // AND the identifiers now identify the other account
+        assert_eq!(merge.identifiers, vec![msisdn.clone()]);
+        assert_eq!(merge.target.identifiers(), &vec![msisdn.clone()]);
+        assert!(merge.source.identifiers().is_empty());
+    }
+
+    #[test]
+    fn test_merge_invalid() {
+        // GIVEN a credit account
+        let fixture = get_account_merge_service_fixture();
+        let account_id = create_credit_account(&fixture);
+        let unknown_id = AccountId::generate();
+
+        // WHEN it is merged into itself, or with an unknown account
+        // THEN it is rejected
+        let merge =
+            |source: &AccountId, target: &AccountId| fixture.merge_service.merge(source, target);
+        assert_eq!(
+            merge(&account_id, &account_id),
+            Err(MergeError::SameAccount)
+        );
+        assert_eq!(
+            merge(&unknown_id, &account_id),
+            Err(MergeError::SourceNotFound(unknown_id.clone()))
+        );
+        assert_eq!(
+            merge(&account_id, &unknown_id),
+            Err(MergeError::TargetNotFound(unknown_id.clone()))
+        );
+
+        // AND the accounts merged must be alike
+        let savings_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+        assert_eq!(
+            merge(&account_id, &savings_id),
+            Err(MergeError::AccountTypeMismatch)
+        );
+        let mut dollars = get_random_account();
+        dollars.set_account_type(AccountType::Credit);
+        dollars.set_currency(Currency::USD);
+        let dollars_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(dollars)
+            .unwrap();
+        assert_eq!(
+            merge(&account_id, &dollars_id),
+            Err(MergeError::CurrencyMismatch)
+        );
+
+        // AND an account can not be merged into a closed one
+        let closed_id = create_credit_account(&fixture);
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .change_status(&closed_id, AccountStatus::Closed, "Not used")
+            .unwrap();
+        assert_eq!(
+            merge(&account_id, &closed_id),
+            Err(MergeError::TargetClosed)
+        );
+    }
+}
@@ -3,6 +3,7 @@ mod test_account_service_find_all {
     use crate::app::repositories::account_repository::AccountRepository;
     use crate::app::services::account_service::AccountService;
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
     use crate::shared::test_utilities::{assert_accounts_equal, get_random_account};
     use std::sync::{Arc, Mutex};
 
@@ -12,7 +13,10 @@ mod test_account_service_find_all {
         let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
 
         // WHEN getting all accounts
-        let account_service = AccountService::new(account_repository);
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let accounts = account_service.find_all();
 
         // THEN the account list is empty
@@ -40,22 +44,26 @@ mod test_account_service_find_all {
         // WHEN getting all accounts
 
         // WHEN getting all accounts
-        let account_service = AccountService::new(account_repository);
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let accounts = account_service.find_all();
 
         // THEN the accounts should be the same as the given accounts.
         assert_eq!(accounts.len(), 2);
-        assert_accounts_equal(&given_accounts.0, accounts.get(0).unwrap(), false);
+        assert_accounts_equal(&given_accounts.0, accounts.first().unwrap(), false);
         assert_accounts_equal(&given_accounts.1, accounts.get(1).unwrap(), false);
     }
 }
 
 #[cfg(test)]
 mod test_account_service_find_by_id {
-    use crate::app::entities::common::EntityId;
+    use crate::app::entities::account::AccountId;
     use crate::app::repositories::account_repository::AccountRepository;
     use crate::app::services::account_service::AccountService;
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
     use crate::shared::test_utilities::{assert_accounts_equal, get_random_account};
     use std::sync::{Arc, Mutex};
 
@@ -73,7 +81,10 @@ mod test_account_service_find_by_id {
             .unwrap();
 
         // WHEN finding by id
-        let account_service = AccountService::new(account_repository);
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let account = account_service.find_by_id(account_id);
 
         // THEN the account should be the same as the given account.
@@ -86,8 +97,11 @@ mod test_account_service_find_by_id {
         let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
 
         // WHEN finding by an id that does not exist
-        let given_account_id: EntityId = "1".into();
-        let account_service = AccountService::new(account_repository);
+        let given_account_id = AccountId::generate();
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let account = account_service.find_by_id(given_account_id);
 
         // THEN the account should be none.
@@ -97,10 +111,16 @@ mod test_account_service_find_by_id {
 
 #[cfg(test)]
 mod test_account_service_save {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::institution::InstitutionId;
     use crate::app::repositories::account_repository::AccountRepository;
     use crate::app::services::account_service::{AccountService, CreateError};
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
-    use crate::shared::test_utilities::{assert_accounts_equal, get_random_account};
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::infrastructure::repositories::sqlite::account_repository::SQliteAccountRepository;
+    use crate::shared::test_utilities::{
+        assert_accounts_equal, get_random_account, get_sqlite_connection,
+    };
     use std::sync::{Arc, Mutex};
 
     #[test]
@@ -110,7 +130,10 @@ mod test_account_service_save {
 
         // WHEN saving a new account
         let given_account = get_random_account();
-        let account_service = AccountService::new(account_repository.clone());
+        let account_service = AccountService::new(
+            account_repository.clone(),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let account_id = account_service.create(given_account.clone());
 
         // THEN the request should be in the repository.
@@ -129,8 +152,11 @@ mod test_account_service_save {
 
         // WHEN saving a new account with an id provided
         let mut given_account = get_random_account();
-        given_account.set_id(Some("1".into()));
-        let account_service = AccountService::new(account_repository);
+        given_account.set_id(Some(AccountId::generate()));
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let create_response = account_service.create(given_account.clone());
 
         // THEN the request should be in the repository.
@@ -142,14 +168,60 @@ mod test_account_service_save {
             CreateError::EntityIdProvided
         )
     }
+
+    #[test]
+    fn test_save_cash_at_institution() {
+        // GIVEN an in-memory account repository
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
+
+        // WHEN saving a cash account held at an institution
+        let mut given_account = get_random_account();
+        given_account.set_account_type(AccountType::Cash);
+        given_account.set_institution_id(Some(InstitutionId::generate()));
+        let create_response = account_service.create(given_account.clone());
+
+        // THEN it is rejected, the cash is held in hand
+        assert_eq!(create_response, Err(CreateError::InstitutionNotAllowed));
+
+        // AND it is saved once held at none
+        given_account.set_institution_id(None);
+        assert!(account_service.create(given_account).is_ok());
+    }
+
+    #[test]
+    fn test_save_failed() {
+        // GIVEN a sqlite account repository refusing to store accounts
+        let connection = get_sqlite_connection();
+        connection
+            .execute(
+                "CREATE TRIGGER accounts_read_only BEFORE INSERT ON accounts \
+                 BEGIN SELECT RAISE(ABORT, 'read only'); END",
+            )
+            .unwrap();
+        let account_service = AccountService::new(
+            Arc::new(Mutex::new(SQliteAccountRepository::new(connection))),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
+
+        // WHEN saving a new account
+        let create_response = account_service.create(get_random_account());
+
+        // THEN the failure is returned
+        assert_eq!(create_response, Err(CreateError::FailedToCreateAccount));
+    }
 }
 
 #[cfg(test)]
 mod test_account_service_find_by_id_or_fail {
-    use crate::app::entities::common::EntityId;
+    use crate::app::entities::account::AccountId;
     use crate::app::repositories::account_repository::AccountRepository;
     use crate::app::services::account_service::{AccountService, FindByIdOrFailError};
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
     use crate::shared::test_utilities::{assert_accounts_equal, get_random_account};
     use std::sync::{Arc, Mutex};
 
@@ -167,7 +239,10 @@ mod test_account_service_find_by_id_or_fail {
             .unwrap();
 
         // WHEN finding by an id or fail
-        let account_service = AccountService::new(account_repository);
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let account = account_service.find_by_id_or_fail(&account_id).unwrap();
 
         // THEN the account should be the same as the given account.
@@ -180,8 +255,11 @@ mod test_account_service_find_by_id_or_fail {
         let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
 
         // WHEN finding by an id that does not exist
-        let given_account_id: EntityId = "1".into();
-        let account_service = AccountService::new(account_repository);
+        let given_account_id = AccountId::generate();
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let account = account_service.find_by_id_or_fail(&given_account_id);
 
         // THEN the account should return a NotFound Error.
@@ -195,11 +273,12 @@ mod test_account_service_find_by_id_or_fail {
 
 #[cfg(test)]
 mod test_account_service_withdraw {
-    use crate::app::entities::common::EntityId;
+    use crate::app::entities::account::AccountId;
     use crate::app::repositories::account_repository::AccountRepository;
     use crate::app::services::account_service::{AccountService, UpdateError};
     use crate::app::typing::amount::Amount;
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
     use crate::shared::test_utilities::get_random_account;
     use std::sync::{Arc, Mutex};
 
@@ -226,7 +305,10 @@ mod test_account_service_withdraw {
         let amount_to_withdraw: Amount = 50f32.try_into().unwrap();
 
         // WHEN the amount is withdrawn using the service
-        let account_service = AccountService::new(account_repository.clone());
+        let account_service = AccountService::new(
+            account_repository.clone(),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let withdraw_response = account_service.withdraw(&account_id, &amount_to_withdraw);
 
         // THEN the withdrawal request should be successful.
@@ -253,9 +335,12 @@ mod test_account_service_withdraw {
         let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
 
         // WHEN the amount is withdrawn using the service
-        let account_service = AccountService::new(account_repository);
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let given_amount_to_withdraw: Amount = 50f32.try_into().unwrap();
-        let given_account_id: EntityId = "1".into();
+        let given_account_id = AccountId::generate();
         let withdraw_response =
             account_service.withdraw(&given_account_id, &given_amount_to_withdraw);
 
@@ -292,7 +377,10 @@ mod test_account_service_withdraw {
         let amount_to_withdraw: Amount = 50f32.try_into().unwrap();
 
         // WHEN the amount is withdrawn using the service
-        let account_service = AccountService::new(account_repository);
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let withdraw_response = account_service.withdraw(&account_id, &amount_to_withdraw);
 
         // THEN the withdrawal request should fail
@@ -308,11 +396,12 @@ mod test_account_service_withdraw {
 
 #[cfg(test)]
 mod test_account_service_deposit {
-    use crate::app::entities::common::EntityId;
+    use crate::app::entities::account::AccountId;
     use crate::app::repositories::account_repository::AccountRepository;
     use crate::app::services::account_service::{AccountService, UpdateError};
     use crate::app::typing::amount::Amount;
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
     use crate::shared::test_utilities::get_random_account;
     use std::sync::{Arc, Mutex};
 
@@ -339,7 +428,10 @@ mod test_account_service_deposit {
         let amount_to_deposit: Amount = 50f32.try_into().unwrap();
 
         // WHEN the amount is deposited using the service
-        let account_service = AccountService::new(account_repository.clone());
+        let account_service = AccountService::new(
+            account_repository.clone(),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let deposit_response = account_service.deposit(&account_id, &amount_to_deposit);
 
         // THEN the deposit request should be successful.
@@ -366,13 +458,16 @@ mod test_account_service_deposit {
         let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
 
         // AND a random entity id
-        let given_account_id: EntityId = "1".into();
+        let given_account_id = AccountId::generate();
 
         // AND the amount to deposit.
         let amount_to_deposit: Amount = 50f32.try_into().unwrap();
 
         // WHEN the amount is deposited using the service
-        let account_service = AccountService::new(account_repository);
+        let account_service = AccountService::new(
+            account_repository,
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
         let deposit_response = account_service.deposit(&given_account_id, &amount_to_deposit);
 
         // THEN the deposit request should fail.
@@ -385,3 +480,260 @@ mod test_account_service_deposit {
         )
     }
 }
+
+#[cfg(test)]
+mod test_account_service_identifiers {
+    use crate::app::entities::account::AccountId;
+    use crate::app::services::account_service::{AccountService, CreateError, UpdateError};
+    use crate::app::typing::account_identifier::{AccountIdentifier, IdentifierKind};
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_identifiers() {
+        // GIVEN an account created with its phone number, given twice
+        let account_service = AccountService::new(
+            Arc::new(Mutex::new(InMemoryAccountRepository::new())),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
+        let msisdn = AccountIdentifier::new(IdentifierKind::Msisdn, "0788123456").unwrap();
+        let mut account = get_random_account();
+        account.set_identifiers(vec![msisdn.clone(), msisdn.clone()]);
+        let account_id = account_service.create(account).unwrap();
+
+        // WHEN the account is looked up by the number, written another way
+        let lookup = AccountIdentifier::new(IdentifierKind::Msisdn, "+250 788 123 456").unwrap();
+        let found = account_service.find_by_identifier(&lookup);
+
+        // THEN it is found, with the number kept once
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id(), Some(&account_id));
+        assert_eq!(found[0].identifiers(), &vec![msisdn.clone()]);
+
+        // WHEN another account is created with the same number
+        let mut other = get_random_account();
+        other.set_identifiers(vec![msisdn.clone()]);
+
+        // THEN it is rejected
+        assert_eq!(
+            account_service.create(other),
+            Err(CreateError::DuplicateIdentifier(msisdn.clone()))
+        );
+
+        // WHEN the identifiers of the account are replaced
+        let card = AccountIdentifier::new(IdentifierKind::CardLastFour, "4242").unwrap();
+        let account = account_service
+            .set_identifiers(&account_id, &[card.clone(), msisdn.clone()])
+            .unwrap();
+
+        // THEN the account has the new ones
+        assert_eq!(account.identifiers(), &vec![card, msisdn.clone()]);
+
+        // AND another account can not take them
+        let other_id = account_service.create(get_random_account()).unwrap();
+        assert_eq!(
+            account_service.set_identifiers(&other_id, std::slice::from_ref(&msisdn)),
+            Err(UpdateError::DuplicateIdentifier(msisdn.clone()))
+        );
+
+        // AND an unknown account has no identifiers to replace
+        assert_eq!(
+            account_service.set_identifiers(&AccountId::generate(), &[msisdn]),
+            Err(UpdateError::EntityIdNotFound)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_account_service_status {
+    use crate::app::entities::account::{AccountId, AccountStatus};
+    use crate::app::services::account_service::{AccountService, StatusError};
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_change_status() {
+        // GIVEN a new account, which is active
+        let account_service = AccountService::new(
+            Arc::new(Mutex::new(InMemoryAccountRepository::new())),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
+        let account_id = account_service.create(get_random_account()).unwrap();
+        let account = account_service.find_by_id_or_fail(&account_id).unwrap();
+        assert_eq!(account.status(), AccountStatus::Active);
+        assert!(account.status_changes().is_empty());
+
+        // WHEN the account is frozen
+        let account = account_service
+            .change_status(&account_id, AccountStatus::Frozen, " Card lost ")
+            .unwrap();
+
+        // THEN the change is recorded with its reason
+        assert_eq!(account.status(), AccountStatus::Frozen);
+        assert_eq!(account.status_changes().len(), 1);
+        assert_eq!(account.status_changes()[0].status, AccountStatus::Frozen);
+        assert_eq!(account.status_changes()[0].reason, "Card lost");
+
+        // WHEN it is closed, then reopened
+        account_service
+            .change_status(&account_id, AccountStatus::Closed, "Moved to another bank")
+            .unwrap();
+        let account = account_service
+            .change_status(&account_id, AccountStatus::Active, "Back at the bank")
+            .unwrap();
+
+        // THEN the history keeps every change, the oldest first
+        let statuses: Vec<AccountStatus> = account
+            .status_changes()
+            .iter()
+            .map(|change| change.status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                AccountStatus::Frozen,
+                AccountStatus::Closed,
+                AccountStatus::Active
+            ]
+        );
+        assert!(account.status_changes()[0].changed_at <= account.status_changes()[2].changed_at);
+    }
+
+    #[test]
+    fn test_change_status_invalid() {
+        // GIVEN an account holding money
+        let account_service = AccountService::new(
+            Arc::new(Mutex::new(InMemoryAccountRepository::new())),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
+        let account_id = account_service.create(get_random_account()).unwrap();
+        account_service
+            .deposit(&account_id, &100f32.try_into().unwrap())
+            .unwrap();
+
+        // WHEN it is closed
+        // THEN it is rejected, the money must be taken out first
+        assert_eq!(
+            account_service.change_status(&account_id, AccountStatus::Closed, "Not used"),
+            Err(StatusError::NonZeroBalance)
+        );
+
+        // AND a change needs a reason, and a new status
+        assert_eq!(
+            account_service.change_status(&account_id, AccountStatus::Frozen, "  "),
+            Err(StatusError::EmptyReason)
+        );
+        assert_eq!(
+            account_service.change_status(&account_id, AccountStatus::Active, "Still used"),
+            Err(StatusError::Unchanged(AccountStatus::Active))
+        );
+
+        // AND an unknown account has no status to change
+        assert_eq!(
+            account_service.change_status(&AccountId::generate(), AccountStatus::Frozen, "Lost"),
+            Err(StatusError::EntityIdNotFound)
+        );
+
+        // AND nothing was recorded
+        let account = account_service.find_by_id_or_fail(&account_id).unwrap();
+        assert!(account.status_changes().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_account_service_balance_at {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::transaction::{AccountRef, TransactionType};
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::account_service::{AccountService, FindByIdOrFailError};
+    use crate::app::typing::point_in_time::PointInTime;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+
+    fn at(text: &str) -> DateTime<Utc> {
+        text.parse::<PointInTime>().unwrap().value()
+    }
+
+    #[test]
+    fn test_balance_at() {
+        // GIVEN an account which received 100 on January 10th and spent 20 on February 10th, at noon UTC
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let account_service = AccountService::new(
+            Arc::new(Mutex::new(InMemoryAccountRepository::new())),
+            transaction_repository.clone(),
+        );
+        let account_id = account_service.create(get_random_account()).unwrap();
+        account_service
+            .deposit(&account_id, &80f32.try_into().unwrap())
+            .unwrap();
+        for (transaction_type, amount, opening_balance, closing_balance, month) in [
+            (TransactionType::Income, 100.0, 0.0, 100.0, 1),
+            (TransactionType::Expense, 20.0, 100.0, 80.0, 2),
+        ] {
+            let mut transaction = get_random_transaction();
+            transaction.set_account(AccountRef::Id(account_id.clone()));
+            transaction.set_transaction_type(transaction_type);
+            transaction.set_amount(f32::try_into(amount).unwrap());
+            transaction.set_opening_balance(Some(f32::try_into(opening_balance).unwrap()));
+            transaction.set_closing_balance(Some(f32::try_into(closing_balance).unwrap()));
+            transaction.set_date(Utc.with_ymd_and_hms(2026, month, 10, 12, 0, 0).unwrap());
+            transaction_repository.lock().unwrap().create(transaction);
+        }
+
+        // WHEN the balance is asked at points in time of its history
+        let balance_at = |text: &str| {
+            account_service
+                .balance_at(&account_id, at(text))
+                .unwrap()
+                .balance
+                .value()
+        };
+
+        // THEN it is the balance left by the last transaction until then
+        assert_eq!(balance_at("2026-01-31 23:59 Africa/Kigali"), 100.0);
+        assert_eq!(balance_at("2026-02-10T12:00:00Z"), 80.0);
+        assert_eq!(balance_at("2026-12-31 23:59 Africa/Kigali"), 80.0);
+
+        // AND the balance before the first transaction is the one it opened at
+        assert_eq!(balance_at("2025-12-31 23:59 Africa/Kigali"), 0.0);
+
+        // AND the expense of February 10th, at noon UTC, was not made yet at 13:59 in Kigali
+        assert_eq!(balance_at("2026-02-10 13:59 Africa/Kigali"), 100.0);
+    }
+
+    #[test]
+    fn test_balances_at() {
+        // GIVEN an account without transactions, holding 50
+        let account_service = AccountService::new(
+            Arc::new(Mutex::new(InMemoryAccountRepository::new())),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        );
+        let account_id = account_service.create(get_random_account()).unwrap();
+        account_service
+            .deposit(&account_id, &50f32.try_into().unwrap())
+            .unwrap();
+
+        // WHEN the balances of all the accounts are asked at the end of 2025
+        let balances = account_service.balances_at(at("2025-12-31 23:59 Africa/Kigali"));
+
+        // THEN the account always had its current balance
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].account_id, account_id);
+        assert_eq!(balances[0].balance.value(), 50.0);
+        assert_eq!(balances[0].at, at("2025-12-31T21:59:00Z"));
+
+        // AND an unknown account has no balance
+        let unknown_id = AccountId::generate();
+        assert!(matches!(
+            account_service.balance_at(&unknown_id, Utc::now()),
+            Err(FindByIdOrFailError::NotFound(id)) if id == unknown_id
+        ));
+    }
+}
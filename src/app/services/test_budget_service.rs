@@ -0,0 +1,450 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::budget::{Budget, BudgetPeriod, BudgetScope};
+    use crate::app::entities::transaction::Transaction;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::budget_service::BudgetService;
+    use crate::app::services::category_service::CategoryService;
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::budget_repository::InMemoryBudgetRepository;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::recurring_transaction_repository::InMemoryRecurringTransactionRepository;
+    use crate::infrastructure::repositories::in_memory::tag_repository::InMemoryTagRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_transaction;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryBudgetService = BudgetService<
+        InMemoryBudgetRepository,
+        InMemoryTransactionRepository,
+        InMemoryCategoryRepository,
+        InMemoryTagRepository,
+        InMemoryPayeeRepository,
+        InMemoryRecurringTransactionRepository,
+    >;
+
+    pub(crate) type InMemoryCategoryService = CategoryService<
+        InMemoryCategoryRepository,
+        InMemoryTransactionRepository,
+        InMemoryPayeeRepository,
+        InMemoryRecurringTransactionRepository,
+        InMemoryBudgetRepository,
+    >;
+
+    pub(crate) struct BudgetServiceFixture {
+        pub(crate) budget_service: InMemoryBudgetService,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) category_service: Arc<Mutex<InMemoryCategoryService>>,
+        pub(crate) tag_repository: Arc<Mutex<InMemoryTagRepository>>,
+    }
+
+    /// Builds a budget service, along with what its budgets apply to.
+    pub(crate) fn get_budget_service_fixture() -> BudgetServiceFixture {
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let budget_repository = Arc::new(Mutex::new(InMemoryBudgetRepository::new()));
+        let category_service = Arc::new(Mutex::new(CategoryService::new(
+            Arc::new(Mutex::new(InMemoryCategoryRepository::new())),
+            transaction_repository.clone(),
+            Arc::new(Mutex::new(InMemoryPayeeRepository::new())),
+            Arc::new(Mutex::new(InMemoryRecurringTransactionRepository::new())),
+            budget_repository.clone(),
+        )));
+        let tag_repository = Arc::new(Mutex::new(InMemoryTagRepository::new()));
+
+        BudgetServiceFixture {
+            budget_service: BudgetService::new(
+                budget_repository,
+                transaction_repository.clone(),
+                category_service.clone(),
+                tag_repository.clone(),
+            ),
+            transaction_repository,
+            category_service,
+            tag_repository,
+        }
+    }
+
+    pub(crate) fn get_budget(scope: BudgetScope, rollover: bool) -> Budget {
+        Budget::new(
+            None,
+            String::from("Groceries"),
+            scope,
+            BudgetPeriod::Monthly,
+            100000f32.try_into().unwrap(),
+            Currency::RWF,
+            rollover,
+            NaiveDate::from_ymd_opt(2026, 1, 10).unwrap(),
+        )
+    }
+
+    pub(crate) fn date(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    /// Records a confirmed expense in RWF, customized by `update`.
+    pub(crate) fn add_expense(
+        transaction_repository: &Arc<Mutex<InMemoryTransactionRepository>>,
+        date: &str,
+        amount: f32,
+        update: impl FnOnce(&mut Transaction),
+    ) {
+        let mut transaction = get_random_transaction();
+        transaction.set_date(self::date(date));
+        transaction.set_amount(amount.try_into().unwrap());
+        update(&mut transaction);
+        transaction_repository.lock().unwrap().create(transaction);
+    }
+}
+
+#[cfg(test)]
+mod test_budget_service_crud {
+    use super::common::{get_budget, get_budget_service_fixture};
+    use crate::app::entities::budget::BudgetScope;
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::tag::{Tag, TagId};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::repositories::tag_repository::TagRepository;
+    use crate::app::services::budget_service::{
+        CreateError, DeleteError, FindByIdOrFailError, UpdateError, ValidationError,
+    };
+
+    #[test]
+    fn test_create_update_and_delete() {
+        // GIVEN an expense category and a tag
+        let fixture = get_budget_service_fixture();
+        let budget_service = fixture.budget_service;
+        let category_id = fixture
+            .category_service
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Food"),
+                None,
+                TransactionType::Expense,
+                None,
+                None,
+            ))
+            .unwrap();
+        let tag_id = fixture
+            .tag_repository
+            .lock()
+            .unwrap()
+            .create(Tag::new(None, String::from("holidays")));
+
+        // WHEN a budget of the category is created
+        let mut budget = get_budget(BudgetScope::Category(category_id), false);
+        budget.set_name(String::from("  Food  "));
+        let id = budget_service.create(budget).unwrap();
+
+        // THEN it is saved with a trimmed name
+        let found = budget_service.find_by_id_or_fail(&id).unwrap();
+        assert_eq!(found.name(), "Food");
+
+        // WHEN it is changed into a budget of the tag
+        let changed = budget_service
+            .update(&id, get_budget(BudgetScope::Tag(tag_id.clone()), true))
+            .unwrap();
+
+        // THEN the changes are saved
+        assert_eq!(changed.id(), Some(&id));
+        assert_eq!(budget_service.find_all(), vec![changed.clone()]);
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(budget_service.delete(&id), Ok(()));
+        assert_eq!(
+            budget_service.find_by_id_or_fail(&id),
+            Err(FindByIdOrFailError::NotFound(id.clone()))
+        );
+        assert_eq!(
+            budget_service.delete(&id),
+            Err(DeleteError::EntityIdNotFound)
+        );
+        assert_eq!(
+            budget_service.update(&id, get_budget(BudgetScope::Tag(tag_id), true)),
+            Err(UpdateError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN an income category
+        let fixture = get_budget_service_fixture();
+        let budget_service = fixture.budget_service;
+        let income_id = fixture
+            .category_service
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Salary"),
+                None,
+                TransactionType::Income,
+                None,
+                None,
+            ))
+            .unwrap();
+
+        // WHEN budgets without name, without amount, or of an income category are created
+        // THEN they are rejected
+        let mut budget = get_budget(BudgetScope::Category(income_id.clone()), false);
+        budget.set_name(String::from(" "));
+        assert_eq!(
+            budget_service.create(budget),
+            Err(CreateError::Invalid(ValidationError::EmptyName))
+        );
+
+        let mut budget = get_budget(BudgetScope::Category(income_id.clone()), false);
+        budget.set_amount(0f32.try_into().unwrap());
+        assert_eq!(
+            budget_service.create(budget),
+            Err(CreateError::Invalid(ValidationError::ZeroAmount))
+        );
+
+        assert_eq!(
+            budget_service.create(get_budget(BudgetScope::Category(income_id), false)),
+            Err(CreateError::Invalid(ValidationError::IncomeCategory))
+        );
+
+        // AND so are the budgets of an unknown category or tag
+        let category_id = CategoryId::generate();
+        assert_eq!(
+            budget_service.create(get_budget(
+                BudgetScope::Category(category_id.clone()),
+                false
+            )),
+            Err(CreateError::Invalid(ValidationError::CategoryNotFound(
+                category_id
+            )))
+        );
+        let tag_id = TagId::generate();
+        assert_eq!(
+            budget_service.create(get_budget(BudgetScope::Tag(tag_id.clone()), false)),
+            Err(CreateError::Invalid(ValidationError::TagNotFound(tag_id)))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_budget_service_status {
+    use super::common::{add_expense, date, get_budget, get_budget_service_fixture};
+    use crate::app::entities::budget::{BudgetId, BudgetScope};
+    use crate::app::entities::category::Category;
+    use crate::app::entities::tag::Tag;
+    use crate::app::entities::transaction::{TransactionSplit, TransactionStatus, TransactionType};
+    use crate::app::repositories::tag_repository::TagRepository;
+    use crate::app::services::budget_service::StatusError;
+    use crate::app::typing::currency::Currency;
+
+    #[test]
+    fn test_status_of_category() {
+        // GIVEN a monthly budget of a category with a sub category
+        let fixture = get_budget_service_fixture();
+        let budget_service = fixture.budget_service;
+        let (food_id, groceries_id, household_id) = {
+            let category_service = fixture.category_service.lock().unwrap();
+            let category = |name: &str, parent_id| {
+                Category::new(
+                    None,
+                    String::from(name),
+                    parent_id,
+                    TransactionType::Expense,
+                    None,
+                    None,
+                )
+            };
+            let food_id = category_service.create(category("Food", None)).unwrap();
+            let groceries_id = category_service
+                .create(category("Groceries", Some(food_id.clone())))
+                .unwrap();
+            let household_id = category_service
+                .create(category("Household", None))
+                .unwrap();
+            (food_id, groceries_id, household_id)
+        };
+        let id = budget_service
+            .create(get_budget(BudgetScope::Category(food_id.clone()), false))
+            .unwrap();
+
+        // AND expenses of the category and of its sub category in March
+        let transactions = &fixture.transaction_repository;
+        add_expense(transactions, "2026-03-02T10:00:00Z", 20000.0, |t| {
+            t.set_category_id(Some(food_id.clone()))
+        });
+        add_expense(transactions, "2026-03-05T10:00:00Z", 15000.0, |t| {
+            t.set_category_id(Some(groceries_id.clone()))
+        });
+
+        // AND a split expense, partly in the sub category
+        add_expense(transactions, "2026-03-06T10:00:00Z", 30000.0, |t| {
+            t.set_splits(vec![
+                TransactionSplit {
+                    category_id: groceries_id.clone(),
+                    amount: 10000f32.try_into().unwrap(),
+                    memo: None,
+                },
+                TransactionSplit {
+                    category_id: household_id.clone(),
+                    amount: 20000f32.try_into().unwrap(),
+                    memo: None,
+                },
+            ])
+        });
+
+        // AND expenses which do not count: pending, in another currency, in February, after the date
+        add_expense(transactions, "2026-03-03T10:00:00Z", 5000.0, |t| {
+            t.set_category_id(Some(food_id.clone()));
+            t.set_status(TransactionStatus::Pending);
+        });
+        add_expense(transactions, "2026-03-03T10:00:00Z", 5000.0, |t| {
+            t.set_category_id(Some(food_id.clone()));
+            t.set_currency(Currency::USD);
+        });
+        add_expense(transactions, "2026-02-27T10:00:00Z", 5000.0, |t| {
+            t.set_category_id(Some(food_id.clone()))
+        });
+        add_expense(transactions, "2026-03-20T10:00:00Z", 5000.0, |t| {
+            t.set_category_id(Some(food_id.clone()))
+        });
+
+        // WHEN its status is computed on the 11th of March
+        let at = date("2026-03-11T00:00:00Z");
+        let status = budget_service.status(&id, at).unwrap();
+
+        // THEN the expenses of the month so far are spent
+        assert_eq!(status.period_start, date("2026-03-01T00:00:00Z"));
+        assert_eq!(status.period_end, date("2026-04-01T00:00:00Z"));
+        assert_eq!(status.budgeted, 100000.0);
+        assert_eq!(status.rolled_over, 0.0);
+        assert_eq!(status.available, 100000.0);
+        assert_eq!(status.spent, 45000.0);
+        assert_eq!(status.remaining, 55000.0);
+        assert_eq!(status.transaction_count, 3);
+
+        // AND a third of the month elapsed, the spending is projected over the whole month
+        assert_eq!(status.elapsed, 10.0 / 31.0);
+        assert_eq!(status.projected_spent, 45000.0 / (10.0 / 31.0));
+        assert_eq!(
+            status.projected_overspend,
+            45000.0 / (10.0 / 31.0) - 100000.0
+        );
+    }
+
+    #[test]
+    fn test_status_at_an_expense() {
+        // GIVEN a monthly budget of a category
+        let fixture = get_budget_service_fixture();
+        let budget_service = fixture.budget_service;
+        let food_id = fixture
+            .category_service
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Food"),
+                None,
+                TransactionType::Expense,
+                None,
+                None,
+            ))
+            .unwrap();
+        let id = budget_service
+            .create(get_budget(BudgetScope::Category(food_id.clone()), false))
+            .unwrap();
+
+        // AND an expense made right at the date of the status, and one a second after
+        let transactions = &fixture.transaction_repository;
+        add_expense(transactions, "2026-03-11T10:00:00Z", 20000.0, |t| {
+            t.set_category_id(Some(food_id.clone()))
+        });
+        add_expense(transactions, "2026-03-11T10:00:01Z", 5000.0, |t| {
+            t.set_category_id(Some(food_id.clone()))
+        });
+
+        // WHEN its status is computed at the date of the first expense
+        let status = budget_service
+            .status(&id, date("2026-03-11T10:00:00Z"))
+            .unwrap();
+
+        // THEN the expense is spent, the later one is not
+        assert_eq!(status.spent, 20000.0);
+        assert_eq!(status.transaction_count, 1);
+
+        // WHEN it is computed at the end of the month
+        let status = budget_service
+            .status(&id, date("2026-03-31T23:59:59Z"))
+            .unwrap();
+
+        // THEN both are spent
+        assert_eq!(status.spent, 25000.0);
+    }
+
+    #[test]
+    fn test_status_with_rollover() {
+        // GIVEN a monthly budget of a tag, rolling over, from January
+        let fixture = get_budget_service_fixture();
+        let budget_service = fixture.budget_service;
+        let tag_id = fixture
+            .tag_repository
+            .lock()
+            .unwrap()
+            .create(Tag::new(None, String::from("groceries")));
+        let id = budget_service
+            .create(get_budget(BudgetScope::Tag(tag_id.clone()), true))
+            .unwrap();
+
+        // AND tagged expenses: 30000 in January, 250000 in February, 40000 in March, and one untagged
+        let transactions = &fixture.transaction_repository;
+        for (day, amount) in [
+            ("2026-01-15T10:00:00Z", 30000.0),
+            ("2026-02-10T10:00:00Z", 125000.0),
+            ("2026-02-20T10:00:00Z", 125000.0),
+            ("2026-03-01T00:00:00Z", 40000.0),
+        ] {
+            add_expense(transactions, day, amount, |t| {
+                t.set_tags(vec![tag_id.clone()])
+            });
+        }
+        add_expense(transactions, "2026-03-02T10:00:00Z", 5000.0, |_| {});
+
+        // WHEN its status is computed in February
+        let status = budget_service
+            .status(&id, date("2026-02-28T12:00:00Z"))
+            .unwrap();
+
+        // THEN what was left in January is carried over
+        assert_eq!(status.rolled_over, 70000.0);
+        assert_eq!(status.available, 170000.0);
+        assert_eq!(status.spent, 250000.0);
+        assert_eq!(status.remaining, -80000.0);
+
+        // WHEN its status is computed at the end of March
+        let status = budget_service
+            .status(
+                &id,
+                date("2026-04-01T00:00:00Z") - chrono::TimeDelta::seconds(1),
+            )
+            .unwrap();
+
+        // THEN nothing is carried over from the overspent February
+        assert_eq!(status.rolled_over, 0.0);
+        assert_eq!(status.spent, 40000.0);
+        assert_eq!(status.transaction_count, 1);
+        assert_eq!(status.projected_overspend, 0.0);
+
+        // AND there is no status before the first period, nor for an unknown budget
+        assert_eq!(
+            budget_service.status(&id, date("2025-12-31T23:59:59Z")),
+            Err(StatusError::NotStarted)
+        );
+        assert_eq!(
+            budget_service.status(&BudgetId::generate(), date("2026-03-01T00:00:00Z")),
+            Err(StatusError::EntityIdNotFound)
+        );
+    }
+}
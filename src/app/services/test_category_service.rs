@@ -0,0 +1,533 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::category_service::CategoryService;
+    use crate::infrastructure::repositories::in_memory::budget_repository::InMemoryBudgetRepository;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::recurring_transaction_repository::InMemoryRecurringTransactionRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryCategoryService = CategoryService<
+        InMemoryCategoryRepository,
+        InMemoryTransactionRepository,
+        InMemoryPayeeRepository,
+        InMemoryRecurringTransactionRepository,
+        InMemoryBudgetRepository,
+    >;
+
+    pub(crate) struct CategoryServiceFixture {
+        pub(crate) category_service: InMemoryCategoryService,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) payee_repository: Arc<Mutex<InMemoryPayeeRepository>>,
+        pub(crate) recurring_transaction_repository:
+            Arc<Mutex<InMemoryRecurringTransactionRepository>>,
+        pub(crate) budget_repository: Arc<Mutex<InMemoryBudgetRepository>>,
+    }
+
+    /// Builds a category service, along with the repositories of the entities referencing the categories.
+    pub(crate) fn get_category_service_fixture() -> CategoryServiceFixture {
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let payee_repository = Arc::new(Mutex::new(InMemoryPayeeRepository::new()));
+        let recurring_transaction_repository =
+            Arc::new(Mutex::new(InMemoryRecurringTransactionRepository::new()));
+        let budget_repository = Arc::new(Mutex::new(InMemoryBudgetRepository::new()));
+
+        CategoryServiceFixture {
+            category_service: CategoryService::new(
+                Arc::new(Mutex::new(InMemoryCategoryRepository::new())),
+                transaction_repository.clone(),
+                payee_repository.clone(),
+                recurring_transaction_repository.clone(),
+                budget_repository.clone(),
+            ),
+            transaction_repository,
+            payee_repository,
+            recurring_transaction_repository,
+            budget_repository,
+        }
+    }
+
+    /// Builds a category service, along with the repository of the transactions it checks.
+    pub(crate) fn get_category_service() -> (
+        InMemoryCategoryService,
+        Arc<Mutex<InMemoryTransactionRepository>>,
+    ) {
+        let fixture = get_category_service_fixture();
+
+        (fixture.category_service, fixture.transaction_repository)
+    }
+
+    pub(crate) fn get_category(
+        name: &str,
+        parent_id: Option<CategoryId>,
+        kind: TransactionType,
+    ) -> Category {
+        Category::new(None, String::from(name), parent_id, kind, None, None)
+    }
+}
+
+#[cfg(test)]
+mod test_category_service_seed {
+    use crate::app::entities::transaction::TransactionType;
+
+    #[test]
+    fn test_seed_default_categories() {
+        // GIVEN a category service without categories
+        let (category_service, _) = super::common::get_category_service();
+
+        // WHEN the default categories are seeded
+        let created = category_service.seed_default_categories();
+
+        // THEN the taxonomy is created, with income and expense categories
+        let categories = category_service.find_all();
+        assert_eq!(categories.len(), created);
+        assert!(categories
+            .iter()
+            .any(|category| category.kind() == &TransactionType::Income));
+
+        // AND the sub categories have the kind of their parent
+        for category in categories.iter().filter(|c| c.parent_id().is_some()) {
+            let parent = category_service
+                .find_by_id(category.parent_id().unwrap().clone())
+                .unwrap();
+            assert_eq!(parent.kind(), category.kind());
+        }
+
+        // WHEN the default categories are seeded again
+        // THEN nothing is created
+        assert_eq!(category_service.seed_default_categories(), 0);
+        assert_eq!(category_service.find_all().len(), created);
+    }
+}
+
+#[cfg(test)]
+mod test_category_service_create {
+    use super::common::get_category;
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::category_service::{CreateError, ValidationError};
+
+    #[test]
+    fn test_create_success() {
+        // GIVEN a category service
+        let (category_service, _) = super::common::get_category_service();
+
+        // WHEN a category and a sub category are created
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+        let groceries_id = category_service
+            .create(get_category(
+                "Groceries",
+                Some(food_id.clone()),
+                TransactionType::Expense,
+            ))
+            .unwrap();
+
+        // THEN the sub category references its parent
+        let groceries = category_service.find_by_id_or_fail(&groceries_id).unwrap();
+        assert_eq!(groceries.parent_id(), Some(&food_id));
+
+        // AND the descendants of the parent include the sub category
+        assert_eq!(
+            category_service.find_with_descendants(&food_id).unwrap(),
+            [food_id, groceries_id]
+        );
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN a category service, with an expense category
+        let (category_service, _) = super::common::get_category_service();
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+
+        // WHEN the category has no name
+        // THEN it is rejected
+        assert_eq!(
+            category_service.create(get_category(" ", None, TransactionType::Expense)),
+            Err(CreateError::Invalid(ValidationError::EmptyName))
+        );
+
+        // WHEN the colour is not a hex code
+        let mut category = get_category("Rent", None, TransactionType::Expense);
+        category.set_color(Some(String::from("blue")));
+
+        // THEN it is rejected
+        assert_eq!(
+            category_service.create(category),
+            Err(CreateError::Invalid(ValidationError::InvalidColor))
+        );
+
+        // WHEN the parent does not exist
+        let unknown_id = CategoryId::generate();
+
+        // THEN it is rejected
+        assert_eq!(
+            category_service.create(get_category(
+                "Groceries",
+                Some(unknown_id.clone()),
+                TransactionType::Expense
+            )),
+            Err(CreateError::Invalid(ValidationError::ParentNotFound(
+                unknown_id
+            )))
+        );
+
+        // WHEN the kind differs from the parent one
+        // THEN it is rejected
+        assert_eq!(
+            category_service.create(get_category(
+                "Refunds",
+                Some(food_id.clone()),
+                TransactionType::Income
+            )),
+            Err(CreateError::Invalid(ValidationError::KindMismatch))
+        );
+
+        // WHEN a category with the same name already exists at the same level, in any case
+        // THEN it is rejected
+        assert_eq!(
+            category_service.create(get_category("FOOD", None, TransactionType::Expense)),
+            Err(CreateError::Invalid(ValidationError::DuplicateName))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_category_service_update {
+    use super::common::get_category;
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::category_service::UpdateError;
+
+    #[test]
+    fn test_update_success() {
+        // GIVEN a category service, with two top level categories
+        let (category_service, _) = super::common::get_category_service();
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+        let snacks_id = category_service
+            .create(get_category("Snacks", None, TransactionType::Expense))
+            .unwrap();
+
+        // WHEN a category is moved under the other one
+        let mut snacks = category_service.find_by_id_or_fail(&snacks_id).unwrap();
+        snacks.set_parent_id(Some(food_id.clone()));
+        snacks.set_icon(Some(String::from("cookie")));
+        category_service.update(&snacks_id, snacks).unwrap();
+
+        // THEN the change is saved
+        let snacks = category_service.find_by_id_or_fail(&snacks_id).unwrap();
+        assert_eq!(snacks.parent_id(), Some(&food_id));
+        assert_eq!(snacks.icon(), &Some(String::from("cookie")));
+    }
+
+    #[test]
+    fn test_update_invalid() {
+        // GIVEN a category service, with a category and a sub category
+        let (category_service, _) = super::common::get_category_service();
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+        let groceries_id = category_service
+            .create(get_category(
+                "Groceries",
+                Some(food_id.clone()),
+                TransactionType::Expense,
+            ))
+            .unwrap();
+
+        // WHEN the category is moved under its sub category
+        let mut food = category_service.find_by_id_or_fail(&food_id).unwrap();
+        food.set_parent_id(Some(groceries_id.clone()));
+
+        // THEN it is rejected
+        assert_eq!(
+            category_service.update(&food_id, food),
+            Err(UpdateError::CyclicParent)
+        );
+
+        // WHEN the kind of the category with a sub category changes
+        let mut food = category_service.find_by_id_or_fail(&food_id).unwrap();
+        food.set_kind(TransactionType::Income);
+
+        // THEN it is rejected
+        assert_eq!(
+            category_service.update(&food_id, food),
+            Err(UpdateError::KindChangeNotAllowed)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_category_service_delete {
+    use super::common::get_category;
+    use crate::app::entities::budget::{Budget, BudgetPeriod, BudgetScope};
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::payee::Payee;
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::repositories::budget_repository::BudgetRepository;
+    use crate::app::repositories::payee_repository::PayeeRepository;
+    use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::category_service::DeleteError;
+    use crate::app::typing::currency::Currency;
+    use crate::shared::test_utilities::{get_random_recurring_transaction, get_random_transaction};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_delete_protection() {
+        // GIVEN a category service, with a category and a sub category
+        let (category_service, transaction_repository) = super::common::get_category_service();
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+        let groceries_id = category_service
+            .create(get_category(
+                "Groceries",
+                Some(food_id.clone()),
+                TransactionType::Expense,
+            ))
+            .unwrap();
+
+        // AND a transaction of the sub category
+        let mut transaction = get_random_transaction();
+        transaction.set_category_id(Some(groceries_id.clone()));
+        transaction_repository.lock().unwrap().create(transaction);
+
+        // WHEN the parent category is deleted
+        // THEN it is rejected, because of the sub category
+        assert_eq!(
+            category_service.delete(&food_id),
+            Err(DeleteError::HasSubCategories)
+        );
+
+        // WHEN the sub category is deleted
+        // THEN it is rejected, because of the transaction
+        assert_eq!(
+            category_service.delete(&groceries_id),
+            Err(DeleteError::InUse)
+        );
+
+        // WHEN an unknown category is deleted
+        // THEN it is not found
+        assert_eq!(
+            category_service.delete(&CategoryId::generate()),
+            Err(DeleteError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_delete_referenced_category() {
+        // GIVEN a category service, with categories
+        let fixture = super::common::get_category_service_fixture();
+        let category_service = &fixture.category_service;
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+        let housing_id = category_service
+            .create(get_category("Housing", None, TransactionType::Expense))
+            .unwrap();
+        let leisure_id = category_service
+            .create(get_category("Leisure", None, TransactionType::Expense))
+            .unwrap();
+
+        // AND a payee taking it by default
+        fixture.payee_repository.lock().unwrap().create(Payee::new(
+            None,
+            String::from("Simba Supermarket"),
+            vec![],
+            vec![],
+            Some(food_id.clone()),
+        ));
+
+        // WHEN the category is deleted
+        // THEN it is rejected, the payee would refer to a category which no longer exists
+        assert_eq!(
+            category_service.delete(&food_id),
+            Err(DeleteError::UsedByPayees)
+        );
+        assert!(category_service.find_by_id(food_id).is_some());
+
+        // GIVEN a recurring transaction of another category
+        let mut recurring_transaction = get_random_recurring_transaction();
+        let mut template = recurring_transaction.template().clone();
+        template.category_id = Some(housing_id.clone());
+        recurring_transaction.set_template(template);
+        fixture
+            .recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .create(recurring_transaction);
+
+        // WHEN that category is deleted
+        // THEN it is rejected, its next occurrences would refer to a category which no longer exists
+        assert_eq!(
+            category_service.delete(&housing_id),
+            Err(DeleteError::UsedByRecurringTransactions)
+        );
+        assert!(category_service.find_by_id(housing_id).is_some());
+
+        // GIVEN a budget of a third category
+        fixture
+            .budget_repository
+            .lock()
+            .unwrap()
+            .create(Budget::new(
+                None,
+                String::from("Going out"),
+                BudgetScope::Category(leisure_id.clone()),
+                BudgetPeriod::Monthly,
+                50000f32.try_into().unwrap(),
+                Currency::RWF,
+                false,
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            ));
+
+        // WHEN that category is deleted
+        // THEN it is rejected, the budget would have nothing left to track
+        assert_eq!(
+            category_service.delete(&leisure_id),
+            Err(DeleteError::UsedByBudgets)
+        );
+        assert!(category_service.find_by_id(leisure_id).is_some());
+    }
+
+    #[test]
+    fn test_delete_success() {
+        // GIVEN a category service, with an unused category
+        let (category_service, _) = super::common::get_category_service();
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+
+        // WHEN the category is deleted
+        category_service.delete(&food_id).unwrap();
+
+        // THEN it is not found anymore
+        assert!(category_service.find_by_id(food_id).is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_category_service_report {
+    use super::common::get_category;
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::transaction::{TransactionSplit, TransactionStatus, TransactionType};
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::category_service::CategoryTotal;
+    use crate::app::typing::currency::Currency;
+    use crate::shared::test_utilities::get_random_transaction;
+
+    #[test]
+    fn test_report_rolls_up_sub_categories() {
+        // GIVEN a category and a sub category
+        let (category_service, transaction_repository) = super::common::get_category_service();
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+        let groceries_id = category_service
+            .create(get_category(
+                "Groceries",
+                Some(food_id.clone()),
+                TransactionType::Expense,
+            ))
+            .unwrap();
+
+        // AND transactions of both, one without category and a failed one
+        let transactions: [(Option<CategoryId>, f32, TransactionStatus); 4] = [
+            (Some(food_id.clone()), 10.0, TransactionStatus::Confirmed),
+            (
+                Some(groceries_id.clone()),
+                25.0,
+                TransactionStatus::Confirmed,
+            ),
+            (None, 7.0, TransactionStatus::Confirmed),
+            (Some(groceries_id.clone()), 100.0, TransactionStatus::Failed),
+        ];
+        for (category_id, amount, status) in transactions {
+            let mut transaction = get_random_transaction();
+            transaction.set_category_id(category_id);
+            transaction.set_amount(amount.try_into().unwrap());
+            transaction.set_status(status);
+            transaction_repository.lock().unwrap().create(transaction);
+        }
+
+        // WHEN the report is computed
+        let report = category_service.report(None, None);
+
+        // THEN each category totals its confirmed transactions, including its sub categories ones
+        let total_of = |category_id: Option<&CategoryId>| -> &CategoryTotal {
+            report
+                .iter()
+                .find(|total| total.category_id.as_ref() == category_id)
+                .unwrap()
+        };
+        assert_eq!(report.len(), 3);
+
+        let food = total_of(Some(&food_id));
+        assert_eq!(food.currency, Currency::RWF);
+        assert_eq!(food.transaction_count, 1);
+        assert_eq!(food.amount, 10.0);
+        assert_eq!(food.total, 35.0);
+
+        let groceries = total_of(Some(&groceries_id));
+        assert_eq!(groceries.transaction_count, 1);
+        assert_eq!(groceries.amount, 25.0);
+        assert_eq!(groceries.total, 25.0);
+
+        let uncategorized = total_of(None);
+        assert_eq!(uncategorized.amount, 7.0);
+        assert_eq!(uncategorized.total, 7.0);
+    }
+
+    #[test]
+    fn test_report_counts_splits() {
+        // GIVEN two categories
+        let (category_service, transaction_repository) = super::common::get_category_service();
+        let food_id = category_service
+            .create(get_category("Food", None, TransactionType::Expense))
+            .unwrap();
+        let household_id = category_service
+            .create(get_category("Household", None, TransactionType::Expense))
+            .unwrap();
+
+        // AND a transaction split between them, with two splits in the same category
+        let mut transaction = get_random_transaction();
+        transaction.set_amount(35f32.try_into().unwrap());
+        transaction.set_splits(
+            [(&food_id, 20.0), (&household_id, 10.0), (&food_id, 5.0)]
+                .map(
+                    |(category_id, amount): (&CategoryId, f32)| TransactionSplit {
+                        category_id: category_id.clone(),
+                        amount: amount.try_into().unwrap(),
+                        memo: None,
+                    },
+                )
+                .to_vec(),
+        );
+        transaction_repository.lock().unwrap().create(transaction);
+
+        // WHEN the report is computed
+        let report = category_service.report(None, None);
+
+        // THEN each category gets the amount of its splits, the transaction counting once in each
+        assert_eq!(report.len(), 2);
+        for total in report {
+            let (expected_amount, category_id) = if total.category_id == Some(food_id.clone()) {
+                (25.0, &food_id)
+            } else {
+                (10.0, &household_id)
+            };
+            assert_eq!(total.category_id.as_ref(), Some(category_id));
+            assert_eq!(total.transaction_count, 1);
+            assert_eq!(total.amount, expected_amount);
+            assert_eq!(total.total, expected_amount);
+        }
+    }
+}
@@ -0,0 +1,257 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::goal::Goal;
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::services::goal_service::GoalService;
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::goal_repository::InMemoryGoalRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::NaiveDate;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryGoalService =
+        GoalService<InMemoryGoalRepository, InMemoryAccountRepository>;
+
+    /// Builds a goal service, along with the repository of the accounts it follows.
+    pub(crate) fn get_goal_service() -> (InMemoryGoalService, Arc<Mutex<InMemoryAccountRepository>>)
+    {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+
+        (
+            GoalService::new(
+                Arc::new(Mutex::new(InMemoryGoalRepository::new())),
+                account_repository.clone(),
+            ),
+            account_repository,
+        )
+    }
+
+    /// Adds an account of the type and currency, with the balance.
+    pub(crate) fn add_account(
+        account_repository: &Arc<Mutex<InMemoryAccountRepository>>,
+        account_type: AccountType,
+        currency: Currency,
+        balance: f32,
+    ) -> AccountId {
+        let mut account = get_random_account();
+        account.set_account_type(account_type);
+        account.set_currency(currency);
+        account.set_balance(balance.try_into().unwrap());
+        account_repository.lock().unwrap().create(account).unwrap()
+    }
+
+    pub(crate) fn get_goal(account_ids: Vec<AccountId>) -> Goal {
+        Goal::new(
+            None,
+            String::from("Emergency fund"),
+            500000f32.try_into().unwrap(),
+            Currency::RWF,
+            NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+            account_ids,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_goal_service_crud {
+    use super::common::{add_account, get_goal, get_goal_service};
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::services::goal_service::{
+        CreateError, DeleteError, FindByIdOrFailError, UpdateError, ValidationError,
+    };
+    use crate::app::typing::currency::Currency;
+
+    #[test]
+    fn test_create_update_and_delete() {
+        // GIVEN two savings accounts
+        let (goal_service, account_repository) = get_goal_service();
+        let savings_id = add_account(
+            &account_repository,
+            AccountType::Savings,
+            Currency::RWF,
+            0.0,
+        );
+        let other_id = add_account(
+            &account_repository,
+            AccountType::Savings,
+            Currency::RWF,
+            0.0,
+        );
+
+        // WHEN a goal is created, with an account listed twice
+        let mut goal = get_goal(vec![savings_id.clone(), savings_id.clone()]);
+        goal.set_name(String::from(" Emergency fund "));
+        let id = goal_service.create(goal).unwrap();
+
+        // THEN it is saved with a trimmed name, and the account once
+        let found = goal_service.find_by_id_or_fail(&id).unwrap();
+        assert_eq!(found.name(), "Emergency fund");
+        assert_eq!(found.account_ids(), &vec![savings_id.clone()]);
+
+        // WHEN it is linked to both accounts
+        let changed = goal_service
+            .update(&id, get_goal(vec![savings_id, other_id.clone()]))
+            .unwrap();
+
+        // THEN the change is saved
+        assert_eq!(goal_service.find_all(), vec![changed]);
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(goal_service.delete(&id), Ok(()));
+        assert_eq!(
+            goal_service.find_by_id_or_fail(&id),
+            Err(FindByIdOrFailError::NotFound(id.clone()))
+        );
+        assert_eq!(goal_service.delete(&id), Err(DeleteError::EntityIdNotFound));
+        assert_eq!(
+            goal_service.update(&id, get_goal(vec![other_id])),
+            Err(UpdateError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN a checking account and a savings account in dollars
+        let (goal_service, account_repository) = get_goal_service();
+        let checking_id = add_account(
+            &account_repository,
+            AccountType::Checking,
+            Currency::RWF,
+            0.0,
+        );
+        let dollars_id = add_account(
+            &account_repository,
+            AccountType::Savings,
+            Currency::USD,
+            0.0,
+        );
+
+        // WHEN invalid goals are created
+        // THEN they are rejected
+        let mut goal = get_goal(vec![dollars_id.clone()]);
+        goal.set_name(String::new());
+        assert_eq!(
+            goal_service.create(goal),
+            Err(CreateError::Invalid(ValidationError::EmptyName))
+        );
+
+        let mut goal = get_goal(vec![dollars_id.clone()]);
+        goal.set_target_amount(0f32.try_into().unwrap());
+        assert_eq!(
+            goal_service.create(goal),
+            Err(CreateError::Invalid(ValidationError::ZeroTarget))
+        );
+
+        assert_eq!(
+            goal_service.create(get_goal(vec![])),
+            Err(CreateError::Invalid(ValidationError::NoAccount))
+        );
+
+        let unknown_id = AccountId::generate();
+        assert_eq!(
+            goal_service.create(get_goal(vec![unknown_id.clone()])),
+            Err(CreateError::Invalid(ValidationError::AccountNotFound(
+                unknown_id
+            )))
+        );
+        assert_eq!(
+            goal_service.create(get_goal(vec![checking_id.clone()])),
+            Err(CreateError::Invalid(ValidationError::NotASavingsAccount(
+                checking_id
+            )))
+        );
+        assert_eq!(
+            goal_service.create(get_goal(vec![dollars_id.clone()])),
+            Err(CreateError::Invalid(ValidationError::CurrencyMismatch(
+                dollars_id
+            )))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_goal_service_status {
+    use super::common::{add_account, get_goal, get_goal_service};
+    use crate::app::entities::account::AccountType;
+    use crate::app::entities::goal::GoalId;
+    use crate::app::services::goal_service::{FindByIdOrFailError, GoalState};
+    use crate::app::typing::currency::Currency;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_status() {
+        // GIVEN a goal of 500000 by the end of the year, on two accounts holding 140000 together
+        let (goal_service, account_repository) = get_goal_service();
+        let first_id = add_account(
+            &account_repository,
+            AccountType::Savings,
+            Currency::RWF,
+            100000.0,
+        );
+        let second_id = add_account(
+            &account_repository,
+            AccountType::Savings,
+            Currency::RWF,
+            40000.0,
+        );
+        let id = goal_service
+            .create(get_goal(vec![first_id, second_id]))
+            .unwrap();
+
+        // WHEN its status is computed in the middle of October
+        let status = goal_service
+            .status(&id, NaiveDate::from_ymd_opt(2026, 10, 19).unwrap())
+            .unwrap();
+
+        // THEN the rest is spread over the three contributions left
+        assert_eq!(status.saved, 140000.0);
+        assert_eq!(status.remaining, 360000.0);
+        assert_eq!(status.progress, 0.28);
+        assert_eq!(status.contributions_left, 3);
+        assert_eq!(status.monthly_contribution, 120000.0);
+        assert_eq!(status.state, GoalState::InProgress);
+
+        // WHEN its status is computed after the target date
+        let status = goal_service
+            .status(&id, NaiveDate::from_ymd_opt(2027, 1, 1).unwrap())
+            .unwrap();
+
+        // THEN it is overdue, the whole rest is required
+        assert_eq!(status.contributions_left, 0);
+        assert_eq!(status.monthly_contribution, 360000.0);
+        assert_eq!(status.state, GoalState::Overdue);
+
+        // AND an unknown goal has no status
+        let unknown_id = GoalId::generate();
+        assert_eq!(
+            goal_service.status(&unknown_id, NaiveDate::from_ymd_opt(2027, 1, 1).unwrap()),
+            Err(FindByIdOrFailError::NotFound(unknown_id))
+        );
+    }
+
+    #[test]
+    fn test_status_achieved() {
+        // GIVEN a goal whose account holds more than the target
+        let (goal_service, account_repository) = get_goal_service();
+        let account_id = add_account(
+            &account_repository,
+            AccountType::Savings,
+            Currency::RWF,
+            600000.0,
+        );
+        goal_service.create(get_goal(vec![account_id])).unwrap();
+
+        // WHEN the statuses of the goals are computed
+        let statuses = goal_service.statuses(NaiveDate::from_ymd_opt(2026, 10, 19).unwrap());
+
+        // THEN it is achieved, nothing is left to save
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].state, GoalState::Achieved);
+        assert_eq!(statuses[0].progress, 1.0);
+        assert_eq!(statuses[0].remaining, 0.0);
+        assert_eq!(statuses[0].monthly_contribution, 0.0);
+    }
+}
@@ -0,0 +1,205 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::institution::{FeeTier, Institution, InstitutionKind};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::institution_service::InstitutionService;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::institution_repository::InMemoryInstitutionRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryInstitutionService = InstitutionService<
+        InMemoryInstitutionRepository,
+        InMemoryAccountRepository,
+        InMemoryRuleRepository,
+    >;
+
+    pub(crate) struct InstitutionServiceFixture {
+        pub(crate) institution_service: InMemoryInstitutionService,
+        pub(crate) account_repository: Arc<Mutex<InMemoryAccountRepository>>,
+        pub(crate) rule_repository: Arc<Mutex<InMemoryRuleRepository>>,
+    }
+
+    /// Builds an institution service, along with the repositories of the accounts and the rules referencing them.
+    pub(crate) fn get_institution_service_fixture() -> InstitutionServiceFixture {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let rule_repository = Arc::new(Mutex::new(InMemoryRuleRepository::new()));
+
+        InstitutionServiceFixture {
+            institution_service: InstitutionService::new(
+                Arc::new(Mutex::new(InMemoryInstitutionRepository::new())),
+                account_repository.clone(),
+                rule_repository.clone(),
+            ),
+            account_repository,
+            rule_repository,
+        }
+    }
+
+    /// A mobile money operator charging 100 on the expenses up to 10000, then 200 and 0.5% above.
+    pub(crate) fn get_institution(name: &str) -> Institution {
+        let tier = |min_amount: f32, max_amount: Option<f32>, flat_fee: f32, rate: f32| FeeTier {
+            transaction_type: TransactionType::Expense,
+            min_amount: min_amount.try_into().unwrap(),
+            max_amount: max_amount.map(|max_amount| max_amount.try_into().unwrap()),
+            flat_fee: flat_fee.try_into().unwrap(),
+            rate,
+        };
+
+        Institution::new(
+            None,
+            String::from(name),
+            InstitutionKind::MobileMoneyOperator,
+            String::from("rw"),
+            Some(String::from("https://example.com/logo.png")),
+            vec![
+                tier(0.0, Some(10000.0), 100.0, 0.0),
+                tier(10000.0, None, 200.0, 0.5),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_institution_service_crud {
+    use super::common::{get_institution, get_institution_service_fixture};
+    use crate::app::entities::institution::InstitutionId;
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions};
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::repositories::rule_repository::RuleRepository;
+    use crate::app::services::institution_service::{
+        CreateError, DeleteError, FindByIdOrFailError, UpdateError, ValidationError,
+    };
+    use crate::shared::test_utilities::get_random_account;
+
+    #[test]
+    fn test_create_update_and_delete() {
+        // GIVEN an institution service
+        let fixture = get_institution_service_fixture();
+        let service = &fixture.institution_service;
+
+        // WHEN an institution is created
+        let mut institution = get_institution(" MTN Mobile Money ");
+        let id = service.create(institution.clone()).unwrap();
+
+        // THEN it is saved with a trimmed name, and an upper case country
+        let found = service.find_by_id_or_fail(&id).unwrap();
+        assert_eq!(found.name(), "MTN Mobile Money");
+        assert_eq!(found.country(), "RW");
+
+        // AND its fees are quoted from its fee schedule
+        assert_eq!(
+            service.quote_fee(
+                &id,
+                &TransactionType::Expense,
+                &20000f32.try_into().unwrap()
+            ),
+            Ok(300.0)
+        );
+
+        // WHEN its logo is removed
+        institution.set_logo_url(None);
+        let changed = service.update(&id, institution).unwrap();
+
+        // THEN the change is saved
+        assert_eq!(service.find_all(), vec![changed]);
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(service.delete(&id), Ok(()));
+        assert_eq!(
+            service.find_by_id_or_fail(&id),
+            Err(FindByIdOrFailError::NotFound(id.clone()))
+        );
+        assert_eq!(service.delete(&id), Err(DeleteError::EntityIdNotFound));
+        assert_eq!(
+            service.update(&id, get_institution("Airtel")),
+            Err(UpdateError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_delete_in_use() {
+        // GIVEN an institution an account is held at, and another one a rule matches on
+        let fixture = get_institution_service_fixture();
+        let service = &fixture.institution_service;
+        let held_id = service.create(get_institution("MTN")).unwrap();
+        let matched_id = service.create(get_institution("Airtel")).unwrap();
+        let mut account = get_random_account();
+        account.set_institution_id(Some(held_id.clone()));
+        fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap();
+        fixture.rule_repository.lock().unwrap().create(Rule::new(
+            None,
+            String::from("Airtel"),
+            0,
+            RuleConditions {
+                institution_id: Some(matched_id.clone()),
+                ..Default::default()
+            },
+            RuleActions {
+                status: Some(TransactionStatus::Pending),
+                ..Default::default()
+            },
+        ));
+
+        // WHEN they are deleted
+        // THEN they are kept
+        assert_eq!(service.delete(&held_id), Err(DeleteError::InUse));
+        assert_eq!(service.delete(&matched_id), Err(DeleteError::InUse));
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN an institution service, with an institution
+        let fixture = get_institution_service_fixture();
+        let service = &fixture.institution_service;
+        service.create(get_institution("MTN")).unwrap();
+
+        // WHEN invalid institutions are created
+        // THEN they are rejected
+        let mut institution = get_institution("Bank of Kigali");
+        institution.set_id(Some(InstitutionId::generate()));
+        assert_eq!(
+            service.create(institution),
+            Err(CreateError::EntityIdProvided)
+        );
+        assert_eq!(
+            service.create(get_institution("  ")),
+            Err(CreateError::Invalid(ValidationError::EmptyName))
+        );
+        assert_eq!(
+            service.create(get_institution("mtn")),
+            Err(CreateError::Invalid(ValidationError::DuplicateName))
+        );
+
+        let mut institution = get_institution("Bank of Kigali");
+        institution.set_country(String::from("RWA"));
+        assert_eq!(
+            service.create(institution),
+            Err(CreateError::Invalid(ValidationError::InvalidCountry))
+        );
+
+        let mut institution = get_institution("Bank of Kigali");
+        institution.set_logo_url(Some(String::from("logo.png")));
+        assert_eq!(
+            service.create(institution),
+            Err(CreateError::Invalid(ValidationError::InvalidLogoUrl))
+        );
+
+        let mut institution = get_institution("Bank of Kigali");
+        let mut fee_schedule = institution.fee_schedule().clone();
+        fee_schedule[0].max_amount = Some(0f32.try_into().unwrap());
+        fee_schedule[0].min_amount = 10f32.try_into().unwrap();
+        institution.set_fee_schedule(fee_schedule);
+        assert_eq!(
+            service.create(institution),
+            Err(CreateError::Invalid(ValidationError::InvalidFeeTier))
+        );
+    }
+}
@@ -0,0 +1,359 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::interest::{Compounding, DayCount, InterestConfig, PostingSchedule};
+    use crate::app::entities::transaction::{
+        AccountRef, Transaction, TransactionStatus, TransactionType,
+    };
+    use crate::app::services::account_service::AccountService;
+    use crate::app::services::interest_service::InterestService;
+    use crate::app::services::rule_service::RuleService;
+    use crate::app::services::transaction_service::TransactionService;
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::interest_config_repository::InMemoryInterestConfigRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryInterestService = InterestService<
+        InMemoryInterestConfigRepository,
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+        InMemoryRuleRepository,
+    >;
+
+    pub(crate) type InMemoryTransactionService = TransactionService<
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+        InMemoryRuleRepository,
+    >;
+
+    pub(crate) struct InterestServiceFixture {
+        pub(crate) interest_service: InMemoryInterestService,
+        pub(crate) transaction_service: Arc<Mutex<InMemoryTransactionService>>,
+        pub(crate) account_service:
+            Arc<Mutex<AccountService<InMemoryAccountRepository, InMemoryTransactionRepository>>>,
+    }
+
+    /// Builds an interest service, on top of a transaction service posting the interest.
+    pub(crate) fn get_interest_service_fixture() -> InterestServiceFixture {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let account_service = Arc::new(Mutex::new(AccountService::new(
+            account_repository.clone(),
+            transaction_repository.clone(),
+        )));
+        let category_repository = Arc::new(Mutex::new(InMemoryCategoryRepository::new()));
+        let payee_repository = Arc::new(Mutex::new(InMemoryPayeeRepository::new()));
+        let rule_service = Arc::new(Mutex::new(RuleService::new(
+            Arc::new(Mutex::new(InMemoryRuleRepository::new())),
+            transaction_repository.clone(),
+            account_repository.clone(),
+            category_repository.clone(),
+            payee_repository.clone(),
+        )));
+        let transaction_service = Arc::new(Mutex::new(TransactionService::new(
+            account_service.clone(),
+            transaction_repository,
+            category_repository,
+            payee_repository,
+            rule_service,
+        )));
+
+        InterestServiceFixture {
+            interest_service: InterestService::new(
+                Arc::new(Mutex::new(InMemoryInterestConfigRepository::new())),
+                account_repository,
+                transaction_service.clone(),
+            ),
+            transaction_service,
+            account_service,
+        }
+    }
+
+    pub(crate) fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    pub(crate) fn create_account(
+        fixture: &InterestServiceFixture,
+        account_type: AccountType,
+    ) -> AccountId {
+        let mut account = get_random_account();
+        account.set_account_type(account_type);
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap()
+    }
+
+    /// Creates an account of the type, with a deposit of the amount on the 1st of October 2026.
+    pub(crate) fn create_funded_account(
+        fixture: &InterestServiceFixture,
+        account_type: AccountType,
+        amount: f32,
+    ) -> AccountId {
+        let account_id = create_account(fixture, account_type);
+        add_transaction(
+            fixture,
+            &account_id,
+            TransactionType::Income,
+            amount,
+            Utc.with_ymd_and_hms(2026, 10, 1, 8, 0, 0).unwrap(),
+            None,
+        );
+
+        account_id
+    }
+
+    /// Adds a confirmed transaction to the account.
+    pub(crate) fn add_transaction(
+        fixture: &InterestServiceFixture,
+        account_id: &AccountId,
+        transaction_type: TransactionType,
+        amount: f32,
+        date: DateTime<Utc>,
+        description: Option<&str>,
+    ) {
+        let transaction = Transaction::new(
+            None,
+            AccountRef::Id(account_id.clone()),
+            transaction_type,
+            amount.try_into().unwrap(),
+            0f32.try_into().unwrap(),
+            None,
+            None,
+            Currency::RWF,
+            TransactionStatus::Confirmed,
+            date,
+            description.map(String::from),
+            None,
+            None,
+        );
+        fixture
+            .transaction_service
+            .lock()
+            .unwrap()
+            .create(transaction)
+            .unwrap();
+    }
+
+    /// 12% a year on the actual days, posted every month from the 1st of October 2026.
+    pub(crate) fn get_config(account_id: &AccountId) -> InterestConfig {
+        InterestConfig::new(
+            account_id.clone(),
+            12.0,
+            DayCount::Actual365,
+            Compounding::Simple,
+            PostingSchedule::Monthly,
+            day(2026, 10, 1),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_interest_service_configure {
+    use super::common::{create_funded_account, day, get_config, get_interest_service_fixture};
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::interest::Compounding;
+    use crate::app::services::interest_service::{
+        DeleteError, FindByIdOrFailError, ValidationError,
+    };
+
+    #[test]
+    fn test_configure_and_remove() {
+        // GIVEN a savings account
+        let fixture = get_interest_service_fixture();
+        let service = &fixture.interest_service;
+        let account_id = create_funded_account(&fixture, AccountType::Savings, 1000.0);
+
+        // WHEN its interest is configured
+        let config = service.configure(get_config(&account_id)).unwrap();
+
+        // THEN the configuration is saved
+        assert_eq!(service.find_by_account_id_or_fail(&account_id), Ok(config));
+
+        // WHEN it is configured again, once some interest was posted
+        service.post_due(day(2026, 11, 1));
+        let mut changes = get_config(&account_id);
+        changes.set_compounding(Compounding::Daily);
+        let config = service.configure(changes).unwrap();
+
+        // THEN the interest already posted is kept
+        assert_eq!(config.compounding(), Compounding::Daily);
+        assert_eq!(config.posted_until(), Some(day(2026, 10, 31)));
+
+        // WHEN it is removed
+        // THEN the account has no interest configuration anymore
+        assert_eq!(service.remove(&account_id), Ok(()));
+        assert_eq!(
+            service.find_by_account_id_or_fail(&account_id),
+            Err(FindByIdOrFailError::NotFound(account_id.clone()))
+        );
+        assert_eq!(
+            service.remove(&account_id),
+            Err(DeleteError::EntityIdNotFound)
+        );
+
+        // AND invalid configurations are rejected
+        let mut config = get_config(&account_id);
+        config.set_annual_rate(0.0);
+        assert_eq!(
+            service.configure(config),
+            Err(ValidationError::RateOutOfBounds)
+        );
+        let unknown_id = AccountId::generate();
+        assert_eq!(
+            service.configure(get_config(&unknown_id)),
+            Err(ValidationError::AccountNotFound(unknown_id))
+        );
+        let cash_id = create_funded_account(&fixture, AccountType::Cash, 1000.0);
+        assert_eq!(
+            service.configure(get_config(&cash_id)),
+            Err(ValidationError::NoInterest(cash_id))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_interest_service_accrual {
+    use super::common::{
+        add_transaction, create_account, create_funded_account, day, get_config,
+        get_interest_service_fixture,
+    };
+    use crate::app::entities::account::{AccountId, AccountType, OPENING_BALANCE_DESCRIPTION};
+    use crate::app::entities::interest::{InterestDirection, InterestPosting};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::interest_service::PreviewError;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_preview_and_post_due() {
+        // GIVEN a savings account holding 100000 since October, earning 12% a year
+        let fixture = get_interest_service_fixture();
+        let service = &fixture.interest_service;
+        let account_id = create_funded_account(&fixture, AccountType::Savings, 100000.0);
+        service.configure(get_config(&account_id)).unwrap();
+
+        // WHEN the interest is previewed up to the middle of November
+        let preview = service.preview(&account_id, day(2026, 11, 15)).unwrap();
+
+        // THEN the interest of October would be posted, and half of November is accrued
+        assert_eq!(preview.direction, InterestDirection::Earned);
+        assert_eq!(preview.from, day(2026, 10, 1));
+        assert_eq!(
+            preview.postings,
+            vec![InterestPosting {
+                date: day(2026, 10, 31),
+                amount: 1019.18
+            }]
+        );
+        assert_eq!(preview.accrued, 498.18);
+        assert_eq!(preview.total, 1517.36);
+
+        // WHEN the due interest is posted on the 1st of November
+        let created = service.post_due(day(2026, 11, 1));
+
+        // THEN the interest of October is posted as an income, on its last day
+        assert_eq!(created.len(), 1);
+        let transaction_service = fixture.transaction_service.lock().unwrap();
+        let transaction = transaction_service.find_by_id(created[0].clone()).unwrap();
+        assert_eq!(transaction.transaction_type(), &TransactionType::Income);
+        assert_eq!(transaction.amount().value(), 1019.18);
+        assert_eq!(transaction.date().date_naive(), day(2026, 10, 31));
+        drop(transaction_service);
+
+        // AND it is posted once
+        assert_eq!(service.post_due(day(2026, 11, 20)), vec![]);
+
+        // AND the preview starts after it, on the increased balance
+        let preview = service.preview(&account_id, day(2026, 11, 15)).unwrap();
+        assert_eq!(preview.from, day(2026, 11, 1));
+        assert_eq!(preview.postings, vec![]);
+        assert_eq!(preview.accrued, 498.18);
+
+        // AND an account without interest has no preview
+        let unknown_id = AccountId::generate();
+        assert_eq!(
+            service.preview(&unknown_id, day(2026, 11, 15)),
+            Err(PreviewError::NotConfigured(unknown_id))
+        );
+    }
+
+    #[test]
+    fn test_post_due_charged() {
+        // GIVEN a credit account opened with 2000 of credit, charged 12% a year
+        let fixture = get_interest_service_fixture();
+        let service = &fixture.interest_service;
+        let account_id = create_account(&fixture, AccountType::Credit);
+        let october = Utc.with_ymd_and_hms(2026, 10, 1, 8, 0, 0).unwrap();
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Income,
+            2000.0,
+            october,
+            Some(OPENING_BALANCE_DESCRIPTION),
+        );
+        service.configure(get_config(&account_id)).unwrap();
+        // AND a purchase of 1000 on it
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Expense,
+            1000.0,
+            october,
+            None,
+        );
+
+        // WHEN the due interest is posted in December
+        let created = service.post_due(day(2026, 12, 5));
+
+        // THEN the interest of October and November is charged on the amount owed, as expenses
+        let transaction_service = fixture.transaction_service.lock().unwrap();
+        let charged: Vec<f32> = created
+            .iter()
+            .map(|id| {
+                let transaction = transaction_service.find_by_id(id.clone()).unwrap();
+                assert_eq!(transaction.transaction_type(), &TransactionType::Expense);
+                transaction.amount().value()
+            })
+            .collect();
+        drop(transaction_service);
+        // 10.19 for October, then 9.96 for November on the 1010.19 owed with it
+        assert_eq!(charged, vec![10.19, 9.96]);
+        let account = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .find_by_id_or_fail(&account_id)
+            .unwrap();
+        assert_eq!(account.balance().value(), 979.85);
+
+        // WHEN everything owed is paid back on the 1st of December
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Income,
+            1020.15,
+            Utc.with_ymd_and_hms(2026, 12, 1, 8, 0, 0).unwrap(),
+            None,
+        );
+
+        // THEN no interest accrues anymore
+        let preview = service.preview(&account_id, day(2026, 12, 15)).unwrap();
+        assert_eq!(preview.accrued, 0.0);
+    }
+}
@@ -0,0 +1,326 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::loan::{Loan, PaymentFrequency};
+    use crate::app::entities::transaction::{
+        AccountRef, Transaction, TransactionStatus, TransactionType,
+    };
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::loan_service::LoanService;
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::loan_repository::InMemoryLoanRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryLoanService = LoanService<
+        InMemoryLoanRepository,
+        InMemoryAccountRepository,
+        InMemoryTransactionRepository,
+    >;
+
+    pub(crate) struct LoanServiceFixture {
+        pub(crate) loan_service: InMemoryLoanService,
+        pub(crate) account_repository: Arc<Mutex<InMemoryAccountRepository>>,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+    }
+
+    /// Builds a loan service, along with the repositories of the accounts and the repayments it follows.
+    pub(crate) fn get_loan_service_fixture() -> LoanServiceFixture {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+
+        LoanServiceFixture {
+            loan_service: LoanService::new(
+                Arc::new(Mutex::new(InMemoryLoanRepository::new())),
+                account_repository.clone(),
+                transaction_repository.clone(),
+            ),
+            account_repository,
+            transaction_repository,
+        }
+    }
+
+    pub(crate) fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Adds an account of the type, in Rwandan francs.
+    pub(crate) fn add_account(
+        fixture: &LoanServiceFixture,
+        account_type: AccountType,
+    ) -> AccountId {
+        let mut account = get_random_account();
+        account.set_account_type(account_type);
+        account.set_currency(Currency::RWF);
+        fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap()
+    }
+
+    /// Adds a confirmed transaction of the type and amount on the account, at noon of the day.
+    pub(crate) fn add_transaction(
+        fixture: &LoanServiceFixture,
+        account_id: &AccountId,
+        transaction_type: TransactionType,
+        amount: f32,
+        date: NaiveDate,
+    ) {
+        let transaction = Transaction::new(
+            None,
+            AccountRef::Id(account_id.clone()),
+            transaction_type,
+            amount.try_into().unwrap(),
+            0f32.try_into().unwrap(),
+            None,
+            None,
+            Currency::RWF,
+            TransactionStatus::Confirmed,
+            Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap()),
+            None,
+            None,
+            None,
+        );
+        fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction);
+    }
+
+    /// 100000 at 12% a year, repaid with 12 monthly instalments of 8884.88 from the 1st of January 2026.
+    pub(crate) fn get_loan(account_id: &AccountId) -> Loan {
+        Loan::new(
+            None,
+            String::from("Car loan"),
+            account_id.clone(),
+            100000f32.try_into().unwrap(),
+            12.0,
+            12,
+            PaymentFrequency::Monthly,
+            day(2026, 1, 1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_loan_service_crud {
+    use super::common::{add_account, get_loan, get_loan_service_fixture};
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::services::loan_service::{
+        CreateError, DeleteError, FindByIdOrFailError, UpdateError, ValidationError,
+    };
+
+    #[test]
+    fn test_create_update_and_delete() {
+        // GIVEN a credit account
+        let fixture = get_loan_service_fixture();
+        let service = &fixture.loan_service;
+        let account_id = add_account(&fixture, AccountType::Credit);
+
+        // WHEN a loan is created on it
+        let mut loan = get_loan(&account_id);
+        loan.set_name(String::from(" Car loan "));
+        let id = service.create(loan).unwrap();
+
+        // THEN it is saved with a trimmed name, and its schedule is computed
+        let found = service.find_by_id_or_fail(&id).unwrap();
+        assert_eq!(found.name(), "Car loan");
+        let schedule = service.schedule(&id).unwrap();
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule[0].payment, 8884.88);
+
+        // WHEN its term is changed
+        let mut changes = get_loan(&account_id);
+        changes.set_term(24);
+        let changed = service.update(&id, changes).unwrap();
+
+        // THEN the change is saved
+        assert_eq!(service.find_all(), vec![changed]);
+        assert_eq!(service.schedule(&id).unwrap().len(), 24);
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(service.delete(&id), Ok(()));
+        assert_eq!(
+            service.schedule(&id),
+            Err(FindByIdOrFailError::NotFound(id.clone()))
+        );
+        assert_eq!(service.delete(&id), Err(DeleteError::EntityIdNotFound));
+        assert_eq!(
+            service.update(&id, get_loan(&account_id)),
+            Err(UpdateError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN a credit account and a checking account
+        let fixture = get_loan_service_fixture();
+        let service = &fixture.loan_service;
+        let credit_id = add_account(&fixture, AccountType::Credit);
+        let checking_id = add_account(&fixture, AccountType::Checking);
+
+        // WHEN invalid loans are created
+        // THEN they are rejected
+        let mut loan = get_loan(&credit_id);
+        loan.set_name(String::from("  "));
+        assert_eq!(
+            service.create(loan),
+            Err(CreateError::Invalid(ValidationError::EmptyName))
+        );
+
+        let mut loan = get_loan(&credit_id);
+        loan.set_principal(0f32.try_into().unwrap());
+        assert_eq!(
+            service.create(loan),
+            Err(CreateError::Invalid(ValidationError::ZeroPrincipal))
+        );
+
+        let mut loan = get_loan(&credit_id);
+        loan.set_annual_rate(-1.0);
+        assert_eq!(
+            service.create(loan),
+            Err(CreateError::Invalid(ValidationError::RateOutOfBounds))
+        );
+
+        let mut loan = get_loan(&credit_id);
+        loan.set_term(0);
+        assert_eq!(
+            service.create(loan),
+            Err(CreateError::Invalid(ValidationError::TermOutOfBounds))
+        );
+
+        let unknown_id = AccountId::generate();
+        assert_eq!(
+            service.create(get_loan(&unknown_id)),
+            Err(CreateError::Invalid(ValidationError::AccountNotFound(
+                unknown_id
+            )))
+        );
+        assert_eq!(
+            service.create(get_loan(&checking_id)),
+            Err(CreateError::Invalid(ValidationError::NotADebtAccount(
+                checking_id
+            )))
+        );
+
+        // AND a loan without interest is accepted
+        let mut loan = get_loan(&credit_id);
+        loan.set_annual_rate(0.0);
+        assert!(service.create(loan).is_ok());
+
+        // AND a loan tracked on a loan account is accepted
+        let loan_account_id = add_account(&fixture, AccountType::Loan);
+        assert!(service.create(get_loan(&loan_account_id)).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod test_loan_service_status {
+    use super::common::{add_account, add_transaction, day, get_loan, get_loan_service_fixture};
+    use crate::app::entities::account::AccountType;
+    use crate::app::entities::loan::LoanId;
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::loan_service::{InstalmentState, StatusError};
+
+    #[test]
+    fn test_status() {
+        // GIVEN a loan repaid in full in February, then in part in March
+        let fixture = get_loan_service_fixture();
+        let service = &fixture.loan_service;
+        let account_id = add_account(&fixture, AccountType::Credit);
+        let id = service.create(get_loan(&account_id)).unwrap();
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Income,
+            8884.88,
+            day(2026, 2, 1),
+        );
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Income,
+            4000.0,
+            day(2026, 3, 5),
+        );
+        // AND an income on the day the money was received, and a charge, which are not repayments
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Income,
+            500.0,
+            day(2026, 1, 1),
+        );
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Expense,
+            300.0,
+            day(2026, 2, 10),
+        );
+
+        // WHEN its status is computed in February
+        let status = service.status(&id, day(2026, 2, 20)).unwrap();
+
+        // THEN the first instalment is paid, and the second one is upcoming
+        assert_eq!(status.instalments[0].state, InstalmentState::Paid);
+        assert_eq!(status.instalments[0].paid_on, Some(day(2026, 2, 1)));
+        assert_eq!(status.instalments[0].paid_interest, 1000.0);
+        assert_eq!(status.instalments[1].state, InstalmentState::Upcoming);
+        assert_eq!(status.next_due_date, Some(day(2026, 3, 1)));
+        assert_eq!(status.overdue_instalments, 0);
+        assert_eq!(status.arrears, 0.0);
+
+        // WHEN its status is computed once the second instalment is due
+        let status = service.status(&id, day(2026, 3, 10)).unwrap();
+
+        // THEN the partial repayment went to its interest first, and the rest is overdue
+        let second = &status.instalments[1];
+        assert_eq!(second.state, InstalmentState::Overdue);
+        assert_eq!(second.paid, 4000.0);
+        assert_eq!(second.paid_interest, 921.15);
+        assert_eq!(second.paid_principal, 3078.85);
+        assert_eq!(second.paid_on, None);
+        assert_eq!(status.instalments[2].state, InstalmentState::Upcoming);
+        assert_eq!(status.paid_interest, 1921.15);
+        assert_eq!(status.paid_principal, 10963.73);
+        assert_eq!(status.outstanding_principal, 89036.27);
+        assert_eq!(status.arrears, 4884.88);
+        assert_eq!(status.overdue_instalments, 1);
+        assert_eq!(status.next_due_date, Some(day(2026, 3, 1)));
+        assert_eq!(status.overpaid, 0.0);
+
+        // WHEN the rest of the loan is repaid, and more
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Income,
+            100000.0,
+            day(2026, 3, 20),
+        );
+        let status = service.status(&id, day(2026, 3, 20)).unwrap();
+
+        // THEN every instalment is paid, and the excess is reported
+        assert!(status
+            .instalments
+            .iter()
+            .all(|instalment| instalment.state == InstalmentState::Paid));
+        assert_eq!(status.outstanding_principal, 0.0);
+        assert_eq!(status.next_due_date, None);
+        assert!(status.overpaid > 0.0);
+
+        // AND an unknown loan has no status
+        assert_eq!(
+            service.status(&LoanId::generate(), day(2026, 3, 20)),
+            Err(StatusError::EntityIdNotFound)
+        );
+    }
+}
@@ -0,0 +1,411 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::payee::Payee;
+    use crate::app::services::payee_service::PayeeService;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::recurring_transaction_repository::InMemoryRecurringTransactionRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryPayeeService = PayeeService<
+        InMemoryPayeeRepository,
+        InMemoryTransactionRepository,
+        InMemoryCategoryRepository,
+        InMemoryRuleRepository,
+        InMemoryRecurringTransactionRepository,
+    >;
+
+    pub(crate) struct PayeeServiceFixture {
+        pub(crate) payee_service: InMemoryPayeeService,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) category_repository: Arc<Mutex<InMemoryCategoryRepository>>,
+        pub(crate) rule_repository: Arc<Mutex<InMemoryRuleRepository>>,
+        pub(crate) recurring_transaction_repository:
+            Arc<Mutex<InMemoryRecurringTransactionRepository>>,
+    }
+
+    /// Builds a payee service, along with the repositories of the entities referencing the payees,
+    /// and of the categories.
+    pub(crate) fn get_payee_service_fixture() -> PayeeServiceFixture {
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let category_repository = Arc::new(Mutex::new(InMemoryCategoryRepository::new()));
+        let rule_repository = Arc::new(Mutex::new(InMemoryRuleRepository::new()));
+        let recurring_transaction_repository =
+            Arc::new(Mutex::new(InMemoryRecurringTransactionRepository::new()));
+
+        PayeeServiceFixture {
+            payee_service: PayeeService::new(
+                Arc::new(Mutex::new(InMemoryPayeeRepository::new())),
+                transaction_repository.clone(),
+                category_repository.clone(),
+                rule_repository.clone(),
+                recurring_transaction_repository.clone(),
+            ),
+            transaction_repository,
+            category_repository,
+            rule_repository,
+            recurring_transaction_repository,
+        }
+    }
+
+    pub(crate) fn get_payee(name: &str, aliases: &[&str], patterns: &[&str]) -> Payee {
+        Payee::new(
+            None,
+            name.to_string(),
+            aliases.iter().map(|alias| alias.to_string()).collect(),
+            patterns.iter().map(|pattern| pattern.to_string()).collect(),
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_payee_service_create {
+    use super::common::{get_payee, get_payee_service_fixture};
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::services::payee_service::{CreateError, ValidationError};
+
+    #[test]
+    fn test_create() {
+        // GIVEN a payee service, with an expense category
+        let fixture = get_payee_service_fixture();
+        let category_id = fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Groceries"),
+                None,
+                TransactionType::Expense,
+                None,
+                None,
+            ));
+
+        // WHEN a payee is created, with padded and repeated aliases
+        let mut payee = get_payee(" Simba Supermarket ", &["SIMBA", "simba ", " "], &[]);
+        payee.set_default_category_id(Some(category_id.clone()));
+        let payee_id = fixture.payee_service.create(payee).unwrap();
+
+        // THEN the payee is saved with cleaned names
+        let payee = fixture.payee_service.find_by_id_or_fail(&payee_id).unwrap();
+        assert_eq!(payee.name(), "Simba Supermarket");
+        assert_eq!(payee.aliases(), &vec![String::from("SIMBA")]);
+        assert_eq!(payee.default_category_id(), Some(&category_id));
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN a payee service, with a payee
+        let fixture = get_payee_service_fixture();
+        let payee_id = fixture
+            .payee_service
+            .create(get_payee("MTN Rwanda", &[], &[]))
+            .unwrap();
+
+        // WHEN a payee with an id is created
+        let mut payee = get_payee("Airtel", &[], &[]);
+        payee.set_id(Some(PayeeId::generate()));
+
+        // THEN it is refused
+        assert_eq!(
+            fixture.payee_service.create(payee),
+            Err(CreateError::EntityIdProvided)
+        );
+
+        // AND a blank name is refused
+        assert_eq!(
+            fixture.payee_service.create(get_payee(" - ", &[], &[])),
+            Err(CreateError::Invalid(ValidationError::EmptyName))
+        );
+
+        // AND a name spelled like another payee is refused
+        assert_eq!(
+            fixture
+                .payee_service
+                .create(get_payee("mtn-rwanda", &[], &[])),
+            Err(CreateError::Invalid(ValidationError::DuplicateName(
+                payee_id
+            )))
+        );
+
+        // AND an invalid pattern is refused
+        assert_eq!(
+            fixture
+                .payee_service
+                .create(get_payee("Airtel", &[], &["airtel("])),
+            Err(CreateError::Invalid(ValidationError::InvalidPattern(
+                String::from("airtel(")
+            )))
+        );
+
+        // AND an unknown default category is refused
+        let category_id = CategoryId::generate();
+        let mut payee = get_payee("Airtel", &[], &[]);
+        payee.set_default_category_id(Some(category_id.clone()));
+        assert_eq!(
+            fixture.payee_service.create(payee),
+            Err(CreateError::Invalid(ValidationError::CategoryNotFound(
+                category_id
+            )))
+        );
+        assert_eq!(fixture.payee_service.find_all().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod test_payee_service_update {
+    use super::common::{get_payee, get_payee_service_fixture};
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::services::payee_service::{UpdateError, ValidationError};
+
+    #[test]
+    fn test_update() {
+        // GIVEN a payee service, with two payees
+        let fixture = get_payee_service_fixture();
+        let payee_id = fixture
+            .payee_service
+            .create(get_payee("Simba", &[], &[]))
+            .unwrap();
+        let other_id = fixture
+            .payee_service
+            .create(get_payee("Kigali Heights", &[], &[]))
+            .unwrap();
+
+        // WHEN the payee is updated, keeping its name
+        let payee = fixture
+            .payee_service
+            .update(&payee_id, get_payee("Simba", &["Simba Supermarket"], &[]))
+            .unwrap();
+
+        // THEN it is saved
+        assert_eq!(payee.id(), Some(&payee_id));
+        assert_eq!(
+            fixture.payee_service.find_by_id_or_fail(&payee_id).unwrap(),
+            payee
+        );
+
+        // AND the name of the other payee is refused
+        assert_eq!(
+            fixture
+                .payee_service
+                .update(&payee_id, get_payee("KIGALI heights", &[], &[])),
+            Err(UpdateError::Invalid(ValidationError::DuplicateName(
+                other_id
+            )))
+        );
+
+        // AND an unknown payee is not found
+        assert_eq!(
+            fixture
+                .payee_service
+                .update(&PayeeId::generate(), get_payee("Simba", &[], &[])),
+            Err(UpdateError::EntityIdNotFound)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_payee_service_delete {
+    use super::common::{get_payee, get_payee_service_fixture};
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::payee_service::DeleteError;
+    use crate::shared::test_utilities::{get_random_recurring_transaction, get_random_transaction};
+
+    #[test]
+    fn test_delete() {
+        // GIVEN a payee service, with a payee used by a transaction and an unused payee
+        let fixture = get_payee_service_fixture();
+        let used_id = fixture
+            .payee_service
+            .create(get_payee("Simba", &[], &[]))
+            .unwrap();
+        let unused_id = fixture
+            .payee_service
+            .create(get_payee("Airtel", &[], &[]))
+            .unwrap();
+        let mut transaction = get_random_transaction();
+        transaction.set_payee_id(Some(used_id.clone()));
+        fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction);
+
+        // WHEN the payees are deleted
+        // THEN the used payee is kept
+        assert_eq!(
+            fixture.payee_service.delete(&used_id),
+            Err(DeleteError::InUse)
+        );
+        assert!(fixture.payee_service.find_by_id(used_id).is_some());
+
+        // AND the unused payee is deleted
+        assert_eq!(fixture.payee_service.delete(&unused_id), Ok(()));
+        assert!(fixture.payee_service.find_by_id(unused_id).is_none());
+
+        // AND an unknown payee is not found
+        assert_eq!(
+            fixture.payee_service.delete(&PayeeId::generate()),
+            Err(DeleteError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_delete_payee_of_recurring_transaction() {
+        // GIVEN a payee service, with a payee only used by a recurring transaction
+        let fixture = get_payee_service_fixture();
+        let payee_id = fixture
+            .payee_service
+            .create(get_payee("Landlord", &[], &[]))
+            .unwrap();
+        let mut recurring_transaction = get_random_recurring_transaction();
+        let mut template = recurring_transaction.template().clone();
+        template.payee_id = Some(payee_id.clone());
+        recurring_transaction.set_template(template);
+        fixture
+            .recurring_transaction_repository
+            .lock()
+            .unwrap()
+            .create(recurring_transaction);
+
+        // WHEN the payee is deleted
+        // THEN it is kept, its next occurrences would refer to a payee which no longer exists
+        assert_eq!(
+            fixture.payee_service.delete(&payee_id),
+            Err(DeleteError::UsedByRecurringTransactions)
+        );
+        assert!(fixture.payee_service.find_by_id(payee_id).is_some());
+    }
+}
+
+#[cfg(test)]
+mod test_payee_service_merge {
+    use super::common::{get_payee, get_payee_service_fixture};
+    use crate::app::entities::category::Category;
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::repositories::rule_repository::RuleRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::payee_service::MergeError;
+    use crate::shared::test_utilities::get_random_transaction;
+
+    #[test]
+    fn test_merge() {
+        // GIVEN a payee service, with a duplicate payee used by a transaction
+        let fixture = get_payee_service_fixture();
+        let category_id = fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Groceries"),
+                None,
+                TransactionType::Expense,
+                None,
+                None,
+            ));
+        let mut source = get_payee("Simba Supermarket", &["Simba", "Simba Ltd"], &["^simba"]);
+        source.set_default_category_id(Some(category_id.clone()));
+        let source_id = fixture.payee_service.create(source).unwrap();
+        let target_id = fixture
+            .payee_service
+            .create(get_payee("Simba Ltd", &["SIMBA"], &[]))
+            .unwrap();
+        let mut transaction = get_random_transaction();
+        transaction.set_payee_id(Some(source_id.clone()));
+        let transaction_id = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction);
+
+        // AND a rule setting the duplicate
+        let rule_id = fixture.rule_repository.lock().unwrap().create(Rule::new(
+            None,
+            String::from("Groceries"),
+            0,
+            RuleConditions::default(),
+            RuleActions {
+                payee_id: Some(source_id.clone()),
+                ..Default::default()
+            },
+        ));
+
+        // WHEN the duplicate is merged into the other payee
+        let target = fixture.payee_service.merge(&source_id, &target_id).unwrap();
+
+        // THEN the target takes the names, patterns and category of the duplicate
+        assert_eq!(
+            target.aliases(),
+            &vec![String::from("SIMBA"), String::from("Simba Supermarket")]
+        );
+        assert_eq!(target.patterns(), &vec![String::from("^simba")]);
+        assert_eq!(target.default_category_id(), Some(&category_id));
+        assert_eq!(
+            fixture
+                .payee_service
+                .find_by_id_or_fail(&target_id)
+                .unwrap(),
+            target
+        );
+
+        // AND the transaction moves to the target
+        let transaction = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.payee_id(), Some(&target_id));
+
+        // AND the rule sets the target instead
+        let rule = fixture
+            .rule_repository
+            .lock()
+            .unwrap()
+            .find_by_id(rule_id)
+            .unwrap();
+        assert_eq!(rule.actions().payee_id, Some(target_id.clone()));
+
+        // AND the duplicate is deleted
+        assert!(fixture.payee_service.find_by_id(source_id).is_none());
+    }
+
+    #[test]
+    fn test_merge_invalid() {
+        // GIVEN a payee service, with a payee
+        let fixture = get_payee_service_fixture();
+        let payee_id = fixture
+            .payee_service
+            .create(get_payee("Simba", &[], &[]))
+            .unwrap();
+        let unknown_id = PayeeId::generate();
+
+        // WHEN the payee is merged into itself or an unknown payee
+        // THEN the merge is refused
+        assert_eq!(
+            fixture.payee_service.merge(&payee_id, &payee_id),
+            Err(MergeError::SamePayee)
+        );
+        assert_eq!(
+            fixture.payee_service.merge(&payee_id, &unknown_id),
+            Err(MergeError::TargetNotFound(unknown_id.clone()))
+        );
+        assert_eq!(
+            fixture.payee_service.merge(&unknown_id, &payee_id),
+            Err(MergeError::SourceNotFound(unknown_id))
+        );
+        assert!(fixture.payee_service.find_by_id(payee_id).is_some());
+    }
+}
@@ -0,0 +1,244 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::loan::{Loan, PaymentFrequency};
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::services::loan_service::LoanService;
+    use crate::app::services::payoff_service::{DebtTerms, PayoffService};
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::interest_config_repository::InMemoryInterestConfigRepository;
+    use crate::infrastructure::repositories::in_memory::loan_repository::InMemoryLoanRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::NaiveDate;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryLoanService = LoanService<
+        InMemoryLoanRepository,
+        InMemoryAccountRepository,
+        InMemoryTransactionRepository,
+    >;
+
+    pub(crate) type InMemoryPayoffService = PayoffService<
+        InMemoryAccountRepository,
+        InMemoryInterestConfigRepository,
+        InMemoryLoanRepository,
+        InMemoryTransactionRepository,
+    >;
+
+    pub(crate) struct PayoffServiceFixture {
+        pub(crate) payoff_service: InMemoryPayoffService,
+        pub(crate) account_repository: Arc<Mutex<InMemoryAccountRepository>>,
+        pub(crate) interest_config_repository: Arc<Mutex<InMemoryInterestConfigRepository>>,
+        pub(crate) loan_service: Arc<Mutex<InMemoryLoanService>>,
+    }
+
+    /// Builds a payoff service, along with the repositories and the loan service the debts come from.
+    pub(crate) fn get_payoff_service_fixture() -> PayoffServiceFixture {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let interest_config_repository =
+            Arc::new(Mutex::new(InMemoryInterestConfigRepository::new()));
+        let loan_service = Arc::new(Mutex::new(LoanService::new(
+            Arc::new(Mutex::new(InMemoryLoanRepository::new())),
+            account_repository.clone(),
+            Arc::new(Mutex::new(InMemoryTransactionRepository::new())),
+        )));
+
+        PayoffServiceFixture {
+            payoff_service: PayoffService::new(
+                account_repository.clone(),
+                interest_config_repository.clone(),
+                loan_service.clone(),
+            ),
+            account_repository,
+            interest_config_repository,
+            loan_service,
+        }
+    }
+
+    pub(crate) fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    pub(crate) fn add_account(
+        fixture: &PayoffServiceFixture,
+        account_type: AccountType,
+    ) -> AccountId {
+        let mut account = get_random_account();
+        account.set_account_type(account_type);
+        fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap()
+    }
+
+    /// Adds a loan of 100000 at 12% a year, repaid with 12 monthly instalments of 8884.88 from the 1st of January 2026.
+    pub(crate) fn add_loan(fixture: &PayoffServiceFixture, account_id: &AccountId) {
+        let loan = Loan::new(
+            None,
+            String::from("Car loan"),
+            account_id.clone(),
+            100000f32.try_into().unwrap(),
+            12.0,
+            12,
+            PaymentFrequency::Monthly,
+            day(2026, 1, 1),
+        );
+        fixture.loan_service.lock().unwrap().create(loan).unwrap();
+    }
+
+    pub(crate) fn get_terms(account_id: &AccountId) -> DebtTerms {
+        DebtTerms {
+            account_id: account_id.clone(),
+            balance: None,
+            annual_rate: None,
+            minimum_payment: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_payoff_service_plan {
+    use super::common::{add_account, add_loan, day, get_payoff_service_fixture, get_terms};
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::interest::{Compounding, DayCount, InterestConfig, PostingSchedule};
+    use crate::app::entities::payoff::PayoffStrategy;
+    use crate::app::repositories::interest_config_repository::InterestConfigRepository;
+    use crate::app::services::payoff_service::{DebtTerms, PlanError};
+
+    #[test]
+    fn test_plan() {
+        // GIVEN a loan, and a credit card owing 2000 charged 6% a year
+        let fixture = get_payoff_service_fixture();
+        let loan_account_id = add_account(&fixture, AccountType::Credit);
+        add_loan(&fixture, &loan_account_id);
+        let card_id = add_account(&fixture, AccountType::Credit);
+        fixture
+            .interest_config_repository
+            .lock()
+            .unwrap()
+            .save(InterestConfig::new(
+                card_id.clone(),
+                6.0,
+                DayCount::Actual365,
+                Compounding::Simple,
+                PostingSchedule::Monthly,
+                day(2026, 1, 1),
+                None,
+            ));
+        let mut card = get_terms(&card_id);
+        card.balance = Some(2000.0);
+        let debts = vec![get_terms(&loan_account_id), card];
+
+        // WHEN their payoff is planned with 10000 a month, in a custom order
+        let plans = fixture
+            .payoff_service
+            .plan(
+                10000.0,
+                debts.clone(),
+                Some(vec![card_id.clone(), loan_account_id.clone()]),
+                day(2026, 1, 1),
+            )
+            .unwrap();
+
+        // THEN the snowball, avalanche and custom strategies are simulated
+        let strategies: Vec<PayoffStrategy> = plans.iter().map(|plan| plan.strategy).collect();
+        assert_eq!(
+            strategies,
+            vec![
+                PayoffStrategy::Snowball,
+                PayoffStrategy::Avalanche,
+                PayoffStrategy::Custom
+            ]
+        );
+        assert_eq!(
+            plans[0].order,
+            vec![card_id.clone(), loan_account_id.clone()]
+        );
+        assert_eq!(
+            plans[1].order,
+            vec![loan_account_id.clone(), card_id.clone()]
+        );
+
+        // AND the loan pays its instalment and its rate, the card its interest configuration
+        let first = &plans[1].months[0];
+        assert_eq!(first.date, day(2026, 2, 1));
+        assert_eq!(first.payments[0].interest, 1000.0);
+        assert_eq!(first.payments[1].interest, 10.0);
+        assert_eq!(first.payments[1].payment, 0.0);
+        assert!(plans[1].total_interest < plans[0].total_interest);
+
+        // WHEN the budget does not cover the instalment of the loan
+        // THEN the plan is rejected
+        assert_eq!(
+            fixture
+                .payoff_service
+                .plan(8000.0, debts, None, day(2026, 1, 1)),
+            Err(PlanError::BudgetBelowMinimums { minimum: 8884.88 })
+        );
+    }
+
+    #[test]
+    fn test_plan_invalid() {
+        // GIVEN a credit account without loan, and a checking account
+        let fixture = get_payoff_service_fixture();
+        let service = &fixture.payoff_service;
+        let card_id = add_account(&fixture, AccountType::Credit);
+        let checking_id = add_account(&fixture, AccountType::Checking);
+        let start = day(2026, 1, 1);
+        let card = DebtTerms {
+            balance: Some(2000.0),
+            ..get_terms(&card_id)
+        };
+
+        // WHEN invalid plans are requested
+        // THEN they are rejected
+        assert_eq!(
+            service.plan(0.0, vec![card.clone()], None, start),
+            Err(PlanError::ZeroBudget)
+        );
+        assert_eq!(
+            service.plan(500.0, vec![], None, start),
+            Err(PlanError::NoDebt)
+        );
+        assert_eq!(
+            service.plan(500.0, vec![card.clone(), card.clone()], None, start),
+            Err(PlanError::DuplicateDebt(card_id.clone()))
+        );
+        let unknown_id = AccountId::generate();
+        assert_eq!(
+            service.plan(500.0, vec![get_terms(&unknown_id)], None, start),
+            Err(PlanError::AccountNotFound(unknown_id.clone()))
+        );
+        assert_eq!(
+            service.plan(500.0, vec![get_terms(&checking_id)], None, start),
+            Err(PlanError::NotADebtAccount(checking_id))
+        );
+        assert_eq!(
+            service.plan(500.0, vec![get_terms(&card_id)], None, start),
+            Err(PlanError::UnknownBalance(card_id.clone()))
+        );
+        let negative = DebtTerms {
+            balance: Some(-1.0),
+            ..get_terms(&card_id)
+        };
+        assert_eq!(
+            service.plan(500.0, vec![negative], None, start),
+            Err(PlanError::InvalidTerms(card_id.clone()))
+        );
+        assert_eq!(
+            service.plan(500.0, vec![card.clone()], Some(vec![unknown_id]), start),
+            Err(PlanError::CustomOrderMismatch)
+        );
+        let costly = DebtTerms {
+            annual_rate: Some(60.0),
+            ..card
+        };
+        assert_eq!(
+            service.plan(50.0, vec![costly], None, start),
+            Err(PlanError::NeverPaidOff)
+        );
+    }
+}
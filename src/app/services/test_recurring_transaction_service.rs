@@ -0,0 +1,374 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::recurring_transaction::{
+        BusinessDayConvention, Frequency, RecurringTransaction, Schedule, TransactionTemplate,
+    };
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::account_service::AccountService;
+    use crate::app::services::recurring_transaction_service::RecurringTransactionService;
+    use crate::app::services::rule_service::RuleService;
+    use crate::app::services::transaction_service::TransactionService;
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::recurring_transaction_repository::InMemoryRecurringTransactionRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::{DateTime, Utc};
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryRecurringTransactionService = RecurringTransactionService<
+        InMemoryRecurringTransactionRepository,
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+        InMemoryRuleRepository,
+    >;
+
+    pub(crate) struct RecurringTransactionServiceFixture {
+        pub(crate) recurring_transaction_service: InMemoryRecurringTransactionService,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) account_service:
+            Arc<Mutex<AccountService<InMemoryAccountRepository, InMemoryTransactionRepository>>>,
+    }
+
+    /// Builds a recurring transaction service, on top of a transaction service creating the occurrences.
+    pub(crate) fn get_recurring_transaction_service_fixture() -> RecurringTransactionServiceFixture
+    {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let account_service = Arc::new(Mutex::new(AccountService::new(
+            account_repository.clone(),
+            transaction_repository.clone(),
+        )));
+        let category_repository = Arc::new(Mutex::new(InMemoryCategoryRepository::new()));
+        let payee_repository = Arc::new(Mutex::new(InMemoryPayeeRepository::new()));
+        let rule_service = Arc::new(Mutex::new(RuleService::new(
+            Arc::new(Mutex::new(InMemoryRuleRepository::new())),
+            transaction_repository.clone(),
+            account_repository,
+            category_repository.clone(),
+            payee_repository.clone(),
+        )));
+        let transaction_service = Arc::new(Mutex::new(TransactionService::new(
+            account_service.clone(),
+            transaction_repository.clone(),
+            category_repository,
+            payee_repository,
+            rule_service,
+        )));
+
+        RecurringTransactionServiceFixture {
+            recurring_transaction_service: RecurringTransactionService::new(
+                Arc::new(Mutex::new(InMemoryRecurringTransactionRepository::new())),
+                transaction_service,
+            ),
+            transaction_repository,
+            account_service,
+        }
+    }
+
+    pub(crate) fn date(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("{value}T08:00:00Z"))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    pub(crate) fn create_account(fixture: &RecurringTransactionServiceFixture) -> AccountId {
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap()
+    }
+
+    /// A monthly transaction of the account, on the 25th from January 2026, for a year.
+    pub(crate) fn get_recurring_transaction(
+        account_id: &AccountId,
+        transaction_type: TransactionType,
+    ) -> RecurringTransaction {
+        RecurringTransaction::new(
+            None,
+            String::from("Salary"),
+            TransactionTemplate {
+                account_id: account_id.clone(),
+                transaction_type,
+                amount: 50000f32.try_into().unwrap(),
+                fee: 0f32.try_into().unwrap(),
+                currency: Currency::RWF,
+                description: Some(String::from("Salary")),
+                message: None,
+                category_id: None,
+                payee_id: None,
+                tags: vec![],
+            },
+            Schedule {
+                frequency: Frequency::Monthly,
+                interval: 1,
+                start: date("2026-01-25"),
+                end: None,
+                count: Some(12),
+                business_day: BusinessDayConvention::Unadjusted,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_recurring_transaction_service_create {
+    use super::common::{
+        create_account, date, get_recurring_transaction, get_recurring_transaction_service_fixture,
+    };
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::recurring_transaction::{RecurringStatus, RecurringTransactionId};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::recurring_transaction_service::{
+        CreateError, DeleteError, UpdateError, ValidationError,
+    };
+    use crate::app::services::transaction_service::CreateError as TransactionCreateError;
+
+    #[test]
+    fn test_create_update_and_delete() {
+        // GIVEN a recurring transaction service, and an account
+        let fixture = get_recurring_transaction_service_fixture();
+        let service = &fixture.recurring_transaction_service;
+        let account_id = create_account(&fixture);
+
+        // WHEN a recurring transaction is created, with a padded name
+        let mut recurring_transaction =
+            get_recurring_transaction(&account_id, TransactionType::Income);
+        recurring_transaction.set_name(String::from(" Salary "));
+        let id = service.create(recurring_transaction).unwrap();
+
+        // THEN it is saved active, with the trimmed name
+        let saved = service.find_by_id_or_fail(&id).unwrap();
+        assert_eq!(saved.name(), "Salary");
+        assert_eq!(saved.status(), &RecurringStatus::Active);
+
+        // WHEN it is paused, then its schedule is changed
+        service.pause(&id).unwrap();
+        let mut changes = get_recurring_transaction(&account_id, TransactionType::Income);
+        let mut schedule = changes.schedule().clone();
+        schedule.start = date("2026-02-28");
+        changes.set_schedule(schedule.clone());
+        let updated = service.update(&id, changes).unwrap();
+
+        // THEN the schedule is updated, and it stays paused
+        assert_eq!(updated.schedule(), &schedule);
+        assert_eq!(updated.status(), &RecurringStatus::Paused);
+        assert_eq!(service.find_all(), vec![updated]);
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(service.delete(&id), Ok(()));
+        assert_eq!(service.find_by_id(id.clone()), None);
+        assert_eq!(service.delete(&id), Err(DeleteError::EntityIdNotFound));
+        assert_eq!(
+            service.update(
+                &id,
+                get_recurring_transaction(&account_id, TransactionType::Income)
+            ),
+            Err(UpdateError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN a recurring transaction service, and an account
+        let fixture = get_recurring_transaction_service_fixture();
+        let service = &fixture.recurring_transaction_service;
+        let account_id = create_account(&fixture);
+        let valid = || get_recurring_transaction(&account_id, TransactionType::Income);
+
+        // WHEN invalid recurring transactions are created
+        // THEN they are refused
+        let mut with_id = valid();
+        with_id.set_id(Some(RecurringTransactionId::generate()));
+        assert_eq!(service.create(with_id), Err(CreateError::EntityIdProvided));
+
+        let mut blank_name = valid();
+        blank_name.set_name(String::from("  "));
+        let mut zero_interval = valid();
+        let mut schedule = zero_interval.schedule().clone();
+        schedule.interval = 0;
+        zero_interval.set_schedule(schedule);
+        let mut zero_count = valid();
+        let mut schedule = zero_count.schedule().clone();
+        schedule.count = Some(0);
+        zero_count.set_schedule(schedule);
+        let mut inverted = valid();
+        let mut schedule = inverted.schedule().clone();
+        schedule.end = Some(date("2025-12-31"));
+        inverted.set_schedule(schedule);
+        let unknown_account_id = AccountId::generate();
+        let unknown_account =
+            get_recurring_transaction(&unknown_account_id, TransactionType::Income);
+
+        for (recurring_transaction, expected) in [
+            (blank_name, ValidationError::EmptyName),
+            (zero_interval, ValidationError::ZeroInterval),
+            (zero_count, ValidationError::ZeroCount),
+            (inverted, ValidationError::EndBeforeStart),
+            (
+                unknown_account,
+                ValidationError::Template(TransactionCreateError::InvalidAccountRef {
+                    account_id: Some(unknown_account_id.clone()),
+                }),
+            ),
+        ] {
+            assert_eq!(
+                service.create(recurring_transaction),
+                Err(CreateError::Invalid(expected))
+            );
+        }
+        assert!(service.find_all().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_recurring_transaction_service_materialize {
+    use super::common::{
+        create_account, date, get_recurring_transaction, get_recurring_transaction_service_fixture,
+    };
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::recurring_transaction_service::{SkipError, UpcomingError};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_materialize_due() {
+        // GIVEN a monthly salary starting in January
+        let fixture = get_recurring_transaction_service_fixture();
+        let service = &fixture.recurring_transaction_service;
+        let account_id = create_account(&fixture);
+        let id = service
+            .create(get_recurring_transaction(
+                &account_id,
+                TransactionType::Income,
+            ))
+            .unwrap();
+
+        // WHEN the due occurrences are created at the end of March
+        let created = service.materialize_due(date("2026-03-30"));
+
+        // THEN the first three salaries are created, pending, on their day
+        assert_eq!(created.len(), 3);
+        let transaction_repository = fixture.transaction_repository.lock().unwrap();
+        let dates: Vec<_> = created
+            .iter()
+            .map(|transaction_id| {
+                let transaction = transaction_repository
+                    .find_by_id(transaction_id.clone())
+                    .unwrap();
+                assert_eq!(transaction.status(), &TransactionStatus::Pending);
+                assert_eq!(transaction.account_id(), Some(&account_id));
+                transaction.date()
+            })
+            .collect();
+        drop(transaction_repository);
+        assert_eq!(
+            dates,
+            vec![date("2026-01-25"), date("2026-02-25"), date("2026-03-25")]
+        );
+
+        // AND they are not created twice
+        assert!(service.materialize_due(date("2026-03-31")).is_empty());
+
+        // WHEN the April salary is skipped
+        let april = NaiveDate::from_ymd_opt(2026, 4, 25).unwrap();
+        service.skip(&id, april).unwrap();
+
+        // THEN it is not upcoming anymore
+        assert_eq!(
+            service.upcoming(&id, 2),
+            Ok(vec![date("2026-05-25"), date("2026-06-25")])
+        );
+
+        // AND only the May salary is created at the end of May
+        let created = service.materialize_due(date("2026-05-30"));
+        assert_eq!(created.len(), 1);
+        let may = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(created[0].clone())
+            .unwrap();
+        assert_eq!(may.date(), date("2026-05-25"));
+
+        // AND past or unknown days can not be skipped, nor too many occurrences listed
+        assert_eq!(
+            service.skip(&id, april),
+            Err(SkipError::NotAnOccurrence(april))
+        );
+        let not_an_occurrence = NaiveDate::from_ymd_opt(2026, 6, 24).unwrap();
+        assert_eq!(
+            service.skip(&id, not_an_occurrence),
+            Err(SkipError::NotAnOccurrence(not_an_occurrence))
+        );
+        assert_eq!(
+            service.upcoming(&id, 0),
+            Err(UpcomingError::LimitOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        // GIVEN a monthly salary, paused
+        let fixture = get_recurring_transaction_service_fixture();
+        let service = &fixture.recurring_transaction_service;
+        let account_id = create_account(&fixture);
+        let id = service
+            .create(get_recurring_transaction(
+                &account_id,
+                TransactionType::Income,
+            ))
+            .unwrap();
+        service.pause(&id).unwrap();
+
+        // WHEN the due occurrences are created
+        // THEN none is, while it is paused
+        assert!(service.materialize_due(date("2026-02-28")).is_empty());
+
+        // WHEN it is resumed at the end of February
+        service.resume(&id, date("2026-02-28")).unwrap();
+
+        // THEN the occurrences due meanwhile are passed over, and the next ones are created
+        assert!(service.materialize_due(date("2026-02-28")).is_empty());
+        assert_eq!(service.materialize_due(date("2026-03-25")).len(), 1);
+    }
+
+    #[test]
+    fn test_materialize_retries_failures() {
+        // GIVEN a monthly expense of an account without funds
+        let fixture = get_recurring_transaction_service_fixture();
+        let service = &fixture.recurring_transaction_service;
+        let account_id = create_account(&fixture);
+        let id = service
+            .create(get_recurring_transaction(
+                &account_id,
+                TransactionType::Expense,
+            ))
+            .unwrap();
+
+        // WHEN the due occurrences are created
+        // THEN none is, and the first one is still upcoming
+        assert!(service.materialize_due(date("2026-02-28")).is_empty());
+        assert_eq!(service.upcoming(&id, 1), Ok(vec![date("2026-01-25")]));
+
+        // WHEN the account is funded
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .deposit(&account_id, &1_000_000f32.try_into().unwrap())
+            .unwrap();
+
+        // THEN the missed occurrences are created on the next run
+        assert_eq!(service.materialize_due(date("2026-02-28")).len(), 2);
+    }
+}
@@ -0,0 +1,530 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions};
+    use crate::app::entities::transaction::{AccountRef, Transaction, TransactionType};
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::rule_service::RuleService;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_transaction;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryRuleService = RuleService<
+        InMemoryRuleRepository,
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+    >;
+
+    pub(crate) struct RuleServiceFixture {
+        pub(crate) rule_service: InMemoryRuleService,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) account_repository: Arc<Mutex<InMemoryAccountRepository>>,
+        pub(crate) category_repository: Arc<Mutex<InMemoryCategoryRepository>>,
+        pub(crate) payee_repository: Arc<Mutex<InMemoryPayeeRepository>>,
+    }
+
+    /// Builds a rule service, along with the repositories of the entities the rules read and reference.
+    pub(crate) fn get_rule_service_fixture() -> RuleServiceFixture {
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let category_repository = Arc::new(Mutex::new(InMemoryCategoryRepository::new()));
+        let payee_repository = Arc::new(Mutex::new(InMemoryPayeeRepository::new()));
+
+        RuleServiceFixture {
+            rule_service: RuleService::new(
+                Arc::new(Mutex::new(InMemoryRuleRepository::new())),
+                transaction_repository.clone(),
+                account_repository.clone(),
+                category_repository.clone(),
+                payee_repository.clone(),
+            ),
+            transaction_repository,
+            account_repository,
+            category_repository,
+            payee_repository,
+        }
+    }
+
+    /// A rule matching the descriptions mentioning "simba".
+    pub(crate) fn get_rule(name: &str, priority: u32, actions: RuleActions) -> Rule {
+        Rule::new(
+            None,
+            String::from(name),
+            priority,
+            RuleConditions {
+                description: Some(String::from("simba")),
+                ..Default::default()
+            },
+            actions,
+        )
+    }
+
+    pub(crate) fn create_category(
+        fixture: &RuleServiceFixture,
+        name: &str,
+        kind: TransactionType,
+    ) -> CategoryId {
+        fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from(name),
+                None,
+                kind,
+                None,
+                None,
+            ))
+    }
+
+    /// Saves an expense of the account, with the given description.
+    pub(crate) fn create_expense(
+        fixture: &RuleServiceFixture,
+        account_id: &AccountId,
+        description: &str,
+    ) -> Transaction {
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id.clone()));
+        transaction.set_transaction_type(TransactionType::Expense);
+        transaction.set_description(Some(String::from(description)));
+        let id = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction);
+
+        fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(id)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test_rule_service_create {
+    use super::common::{create_category, get_rule, get_rule_service_fixture};
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::entities::rule::{RuleActions, RuleConditions, RuleId};
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::services::rule_service::{
+        CreateError, DeleteError, UpdateError, ValidationError,
+    };
+
+    fn pending() -> RuleActions {
+        RuleActions {
+            status: Some(TransactionStatus::Pending),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_create_update_and_delete() {
+        // GIVEN a rule service
+        let fixture = get_rule_service_fixture();
+
+        // WHEN a rule is created, with a padded name
+        let rule_id = fixture
+            .rule_service
+            .create(get_rule(" Simba ", 1, pending()))
+            .unwrap();
+
+        // THEN it is saved with a trimmed name
+        let rule = fixture.rule_service.find_by_id_or_fail(&rule_id).unwrap();
+        assert_eq!(rule.name(), "Simba");
+
+        // WHEN it is updated
+        let rule = fixture
+            .rule_service
+            .update(&rule_id, get_rule("Simba", 0, pending()))
+            .unwrap();
+
+        // THEN the update is saved
+        assert_eq!(fixture.rule_service.find_all(), vec![rule]);
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(fixture.rule_service.delete(&rule_id), Ok(()));
+        assert!(fixture.rule_service.find_all().is_empty());
+
+        // AND an unknown rule can not be updated nor deleted
+        assert_eq!(
+            fixture
+                .rule_service
+                .update(&rule_id, get_rule("Simba", 0, pending())),
+            Err(UpdateError::EntityIdNotFound)
+        );
+        assert_eq!(
+            fixture.rule_service.delete(&rule_id),
+            Err(DeleteError::EntityIdNotFound)
+        );
+    }
+
+    #[test]
+    fn test_create_invalid() {
+        // GIVEN a rule service, with an income category
+        let fixture = get_rule_service_fixture();
+        let salary_id = create_category(&fixture, "Salary", TransactionType::Income);
+
+        // WHEN invalid rules are created
+        // THEN they are refused
+        let mut rule = get_rule("Simba", 0, pending());
+        rule.set_id(Some(RuleId::generate()));
+        assert_eq!(
+            fixture.rule_service.create(rule),
+            Err(CreateError::EntityIdProvided)
+        );
+
+        let invalid = |conditions: RuleConditions, actions: RuleActions| {
+            let mut rule = get_rule("Simba", 0, actions);
+            rule.set_conditions(conditions);
+            match fixture.rule_service.create(rule) {
+                Err(CreateError::Invalid(error)) => error,
+                result => panic!("The rule should be invalid: {result:?}"),
+            }
+        };
+        let simba = RuleConditions {
+            description: Some(String::from("simba")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            invalid(RuleConditions::default(), pending()),
+            ValidationError::NoCondition
+        );
+        assert_eq!(
+            invalid(simba.clone(), RuleActions::default()),
+            ValidationError::NoAction
+        );
+        assert_eq!(
+            invalid(
+                RuleConditions {
+                    message: Some(String::from("simba(")),
+                    ..Default::default()
+                },
+                pending()
+            ),
+            ValidationError::InvalidPattern(String::from("simba("))
+        );
+        assert_eq!(
+            invalid(
+                RuleConditions {
+                    min_amount: Some(20f32.try_into().unwrap()),
+                    max_amount: Some(10f32.try_into().unwrap()),
+                    ..Default::default()
+                },
+                pending()
+            ),
+            ValidationError::InvertedAmountRange
+        );
+
+        let account_id = AccountId::generate();
+        assert_eq!(
+            invalid(
+                RuleConditions {
+                    account_id: Some(account_id.clone()),
+                    ..Default::default()
+                },
+                pending()
+            ),
+            ValidationError::AccountNotFound(account_id)
+        );
+
+        let payee_id = PayeeId::generate();
+        assert_eq!(
+            invalid(
+                simba.clone(),
+                RuleActions {
+                    payee_id: Some(payee_id.clone()),
+                    ..Default::default()
+                }
+            ),
+            ValidationError::PayeeNotFound(payee_id)
+        );
+
+        // AND a transaction is only rolled back by reversing it, not by a rule
+        assert_eq!(
+            invalid(
+                simba.clone(),
+                RuleActions {
+                    status: Some(TransactionStatus::RolledBack),
+                    ..Default::default()
+                }
+            ),
+            ValidationError::RolledBackStatus
+        );
+
+        // AND a category must not contradict the transaction type of the rule
+        assert_eq!(
+            invalid(
+                RuleConditions {
+                    transaction_type: Some(TransactionType::Expense),
+                    ..simba
+                },
+                RuleActions {
+                    category_id: Some(salary_id),
+                    ..Default::default()
+                }
+            ),
+            ValidationError::CategoryKindMismatch
+        );
+
+        // AND a blank name is refused
+        assert_eq!(
+            fixture.rule_service.create(get_rule(" ", 0, pending())),
+            Err(CreateError::Invalid(ValidationError::EmptyName))
+        );
+        assert!(fixture.rule_service.find_all().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_rule_service_apply {
+    use super::common::{create_category, create_expense, get_rule, get_rule_service_fixture};
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::institution::InstitutionId;
+    use crate::app::entities::payee::Payee;
+    use crate::app::entities::rule::{RuleActions, RuleConditions};
+    use crate::app::entities::tag::TagId;
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::repositories::payee_repository::PayeeRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::rule_service::RuleChange;
+    use crate::shared::test_utilities::get_random_account;
+
+    #[test]
+    fn test_apply_by_priority() {
+        // GIVEN two rules matching the same transactions, setting a category and a status each
+        let fixture = get_rule_service_fixture();
+        let groceries_id = create_category(&fixture, "Groceries", TransactionType::Expense);
+        let food_id = create_category(&fixture, "Food", TransactionType::Expense);
+        let (first_tag, second_tag) = (TagId::generate(), TagId::generate());
+        let second_id = fixture
+            .rule_service
+            .create(get_rule(
+                "Second",
+                1,
+                RuleActions {
+                    category_id: Some(food_id),
+                    tags: vec![second_tag.clone()],
+                    status: Some(TransactionStatus::Failed),
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+        let first_id = fixture
+            .rule_service
+            .create(get_rule(
+                "First",
+                0,
+                RuleActions {
+                    category_id: Some(groceries_id.clone()),
+                    tags: vec![first_tag.clone()],
+                    status: Some(TransactionStatus::Pending),
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+
+        // WHEN they are applied to a matching transaction
+        let account_id = AccountId::generate();
+        let mut transaction = create_expense(&fixture, &account_id, "POS SIMBA Kigali");
+        transaction.set_category_id(None);
+        transaction.set_tags(vec![]);
+        let applied = fixture.rule_service.apply(&mut transaction, None);
+
+        // THEN the rule of the lowest priority number sets the fields, the tags are added by both
+        assert_eq!(applied, [first_id, second_id]);
+        assert_eq!(transaction.category_id(), Some(&groceries_id));
+        assert_eq!(transaction.status(), &TransactionStatus::Pending);
+        assert_eq!(transaction.tags(), &vec![first_tag, second_tag]);
+
+        // AND a transaction not matching is left unchanged
+        let mut other = create_expense(&fixture, &account_id, "Airtel bundle");
+        let before = other.clone();
+        assert!(fixture.rule_service.apply(&mut other, None).is_empty());
+        assert_eq!(other, before);
+    }
+
+    #[test]
+    fn test_apply_keeps_classification() {
+        // GIVEN a rule setting an expense category and a payee
+        let fixture = get_rule_service_fixture();
+        let groceries_id = create_category(&fixture, "Groceries", TransactionType::Expense);
+        let payee_id = fixture.payee_repository.lock().unwrap().create(Payee::new(
+            None,
+            String::from("Simba Supermarket"),
+            vec![],
+            vec![],
+            None,
+        ));
+        fixture
+            .rule_service
+            .create(get_rule(
+                "Simba",
+                0,
+                RuleActions {
+                    category_id: Some(groceries_id.clone()),
+                    payee_id: Some(payee_id.clone()),
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+
+        // WHEN it is applied to a transaction already classified
+        let account_id = AccountId::generate();
+        let mut transaction = create_expense(&fixture, &account_id, "SIMBA");
+        let food_id = create_category(&fixture, "Food", TransactionType::Expense);
+        let other_payee_id = fixture.payee_repository.lock().unwrap().create(Payee::new(
+            None,
+            String::from("Other"),
+            vec![],
+            vec![],
+            None,
+        ));
+        transaction.set_category_id(Some(food_id.clone()));
+        transaction.set_payee_id(Some(other_payee_id.clone()));
+
+        // THEN the classification is kept
+        assert!(fixture
+            .rule_service
+            .apply(&mut transaction, None)
+            .is_empty());
+        assert_eq!(transaction.category_id(), Some(&food_id));
+        assert_eq!(transaction.payee_id(), Some(&other_payee_id));
+
+        // WHEN it is applied to an income
+        let mut income = create_expense(&fixture, &account_id, "Refund from SIMBA");
+        income.set_transaction_type(TransactionType::Income);
+        income.set_category_id(None);
+        income.set_payee_id(None);
+        fixture.rule_service.apply(&mut income, None);
+
+        // THEN the income gets the payee, but not the expense category
+        assert_eq!(income.payee_id(), Some(&payee_id));
+        assert_eq!(income.category_id(), None);
+
+        // WHEN the category and the payee were deleted since
+        fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .delete(groceries_id)
+            .unwrap();
+        fixture
+            .payee_repository
+            .lock()
+            .unwrap()
+            .delete(payee_id)
+            .unwrap();
+        let mut transaction = create_expense(&fixture, &account_id, "SIMBA");
+        transaction.set_category_id(None);
+        transaction.set_payee_id(None);
+
+        // THEN the rule leaves the transaction unchanged
+        assert!(fixture
+            .rule_service
+            .apply(&mut transaction, None)
+            .is_empty());
+        assert_eq!(transaction.category_id(), None);
+        assert_eq!(transaction.payee_id(), None);
+    }
+
+    #[test]
+    fn test_dry_run_and_apply_to_history() {
+        // GIVEN transactions on an MTN account and an Airtel account
+        let fixture = get_rule_service_fixture();
+        let mut account = get_random_account();
+        let mtn_id = InstitutionId::generate();
+        account.set_institution_id(Some(mtn_id.clone()));
+        let mtn_account_id = fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap();
+        let mut account = get_random_account();
+        account.set_institution_id(Some(InstitutionId::generate()));
+        let airtel_account_id = fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap();
+
+        let mtn_expense = create_expense(&fixture, &mtn_account_id, "Bundle");
+        let mtn_expense_id = mtn_expense.id().clone().unwrap();
+        create_expense(&fixture, &airtel_account_id, "Bundle");
+
+        // AND a rule flagging the transactions of the MTN accounts
+        let mut rule = get_rule(
+            "MTN",
+            0,
+            RuleActions {
+                status: Some(TransactionStatus::Pending),
+                ..Default::default()
+            },
+        );
+        rule.set_conditions(RuleConditions {
+            institution_id: Some(mtn_id),
+            ..Default::default()
+        });
+
+        // WHEN the rule is tried out
+        let changes = fixture.rule_service.dry_run(rule.clone()).unwrap();
+
+        // THEN the change of the MTN transaction is listed
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].transaction_id, mtn_expense_id);
+        assert_eq!(changes[0].status, Some(TransactionStatus::Pending));
+
+        // AND the transaction is left unchanged
+        let transaction = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(mtn_expense_id.clone())
+            .unwrap();
+        assert_eq!(transaction, mtn_expense);
+
+        // WHEN the rule is saved and applied to the history
+        let rule_id = fixture.rule_service.create(rule).unwrap();
+        let changes = fixture.rule_service.apply_to_history();
+
+        // THEN the MTN transaction is changed
+        assert_eq!(
+            changes,
+            [RuleChange {
+                transaction_id: mtn_expense_id.clone(),
+                rule_ids: vec![rule_id],
+                category_id: None,
+                payee_id: None,
+                added_tags: vec![],
+                status: Some(TransactionStatus::Pending),
+            }]
+        );
+        let transaction = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(mtn_expense_id)
+            .unwrap();
+        assert_eq!(transaction.status(), &TransactionStatus::Pending);
+
+        // AND applying the rules again changes nothing
+        assert!(fixture.rule_service.apply_to_history().is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::statement::StatementConfig;
+    use crate::app::entities::transaction::{
+        AccountRef, Transaction, TransactionStatus, TransactionType,
+    };
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::statement_service::StatementService;
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::statement_config_repository::InMemoryStatementConfigRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryStatementService = StatementService<
+        InMemoryStatementConfigRepository,
+        InMemoryAccountRepository,
+        InMemoryTransactionRepository,
+    >;
+
+    pub(crate) struct StatementServiceFixture {
+        pub(crate) statement_service: InMemoryStatementService,
+        pub(crate) account_repository: Arc<Mutex<InMemoryAccountRepository>>,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+    }
+
+    /// Builds a statement service, along with the repositories of the accounts and the transactions it cuts.
+    pub(crate) fn get_statement_service_fixture() -> StatementServiceFixture {
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+
+        StatementServiceFixture {
+            statement_service: StatementService::new(
+                Arc::new(Mutex::new(InMemoryStatementConfigRepository::new())),
+                account_repository.clone(),
+                transaction_repository.clone(),
+            ),
+            account_repository,
+            transaction_repository,
+        }
+    }
+
+    pub(crate) fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Adds an account of the type, in Rwandan francs.
+    pub(crate) fn add_account(
+        fixture: &StatementServiceFixture,
+        account_type: AccountType,
+    ) -> AccountId {
+        let mut account = get_random_account();
+        account.set_account_type(account_type);
+        account.set_currency(Currency::RWF);
+        fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap()
+    }
+
+    /// Adds a transaction of the type, amount and status on the account, at noon of the day.
+    pub(crate) fn add_transaction(
+        fixture: &StatementServiceFixture,
+        account_id: &AccountId,
+        transaction_type: TransactionType,
+        amount: f32,
+        status: TransactionStatus,
+        date: NaiveDate,
+    ) {
+        let transaction = Transaction::new(
+            None,
+            AccountRef::Id(account_id.clone()),
+            transaction_type,
+            amount.try_into().unwrap(),
+            0f32.try_into().unwrap(),
+            None,
+            None,
+            Currency::RWF,
+            status,
+            Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap()),
+            None,
+            None,
+            None,
+        );
+        fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction);
+    }
+
+    /// Cycles closing on the 25th, paid 20 days later, with a minimum of 5% and at least 1000.
+    pub(crate) fn get_config(account_id: &AccountId) -> StatementConfig {
+        StatementConfig::new(account_id.clone(), 25, 20, 5.0, 1000f32.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test_statement_service_configure {
+    use super::common::{add_account, get_config, get_statement_service_fixture};
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::services::statement_service::{
+        DeleteError, FindByIdOrFailError, ValidationError,
+    };
+
+    #[test]
+    fn test_configure_and_remove() {
+        // GIVEN a credit account and a checking account
+        let fixture = get_statement_service_fixture();
+        let service = &fixture.statement_service;
+        let credit_id = add_account(&fixture, AccountType::Credit);
+        let checking_id = add_account(&fixture, AccountType::Checking);
+
+        // WHEN the statements of the credit account are configured
+        let config = service.configure(get_config(&credit_id)).unwrap();
+
+        // THEN the configuration is saved
+        assert_eq!(service.find_by_account_id_or_fail(&credit_id), Ok(config));
+
+        // WHEN it is removed
+        // THEN the account has no statement configuration anymore
+        assert_eq!(service.remove(&credit_id), Ok(()));
+        assert_eq!(
+            service.find_by_account_id_or_fail(&credit_id),
+            Err(FindByIdOrFailError::NotFound(credit_id.clone()))
+        );
+        assert_eq!(
+            service.remove(&credit_id),
+            Err(DeleteError::EntityIdNotFound)
+        );
+
+        // AND invalid configurations are rejected
+        let unknown_id = AccountId::generate();
+        assert_eq!(
+            service.configure(get_config(&unknown_id)),
+            Err(ValidationError::AccountNotFound(unknown_id))
+        );
+        assert_eq!(
+            service.configure(get_config(&checking_id)),
+            Err(ValidationError::NotACreditAccount(checking_id))
+        );
+        let mut config = get_config(&credit_id);
+        config.set_closing_day(0);
+        assert_eq!(
+            service.configure(config),
+            Err(ValidationError::ClosingDayOutOfBounds)
+        );
+        let mut config = get_config(&credit_id);
+        config.set_grace_period_days(90);
+        assert_eq!(
+            service.configure(config),
+            Err(ValidationError::GracePeriodTooLong)
+        );
+        let mut config = get_config(&credit_id);
+        config.set_minimum_payment_rate(120.0);
+        assert_eq!(
+            service.configure(config),
+            Err(ValidationError::MinimumPaymentRateOutOfBounds)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_statement_service_statements {
+    use super::common::{
+        add_account, add_transaction, day, get_config, get_statement_service_fixture,
+    };
+    use crate::app::entities::account::AccountType;
+    use crate::app::entities::statement::DueStatus;
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::services::statement_service::StatementError;
+
+    #[test]
+    fn test_statements_and_latest() {
+        // GIVEN a credit card charged in January, and paid in February
+        let fixture = get_statement_service_fixture();
+        let service = &fixture.statement_service;
+        let account_id = add_account(&fixture, AccountType::Credit);
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Expense,
+            20000.0,
+            TransactionStatus::Confirmed,
+            day(2026, 1, 5),
+        );
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Income,
+            1500.0,
+            TransactionStatus::Confirmed,
+            day(2026, 2, 10),
+        );
+        // AND a failed charge, which is left out
+        add_transaction(
+            &fixture,
+            &account_id,
+            TransactionType::Expense,
+            5000.0,
+            TransactionStatus::Failed,
+            day(2026, 1, 6),
+        );
+
+        // WHEN the statements are requested before the statements are configured
+        // THEN there are none
+        assert_eq!(
+            service.statements(&account_id, day(2026, 2, 20)),
+            Err(StatementError::NotConfigured(account_id.clone()))
+        );
+
+        // WHEN they are configured, and requested before the first cycle closed
+        service.configure(get_config(&account_id)).unwrap();
+
+        // THEN there is no statement yet
+        assert_eq!(
+            service.statements(&account_id, day(2026, 1, 20)),
+            Ok(vec![])
+        );
+        assert_eq!(
+            service.latest(&account_id, day(2026, 1, 20)),
+            Err(StatementError::NoStatement(account_id.clone()))
+        );
+
+        // WHEN the latest statement is requested in February
+        let latest = service.latest(&account_id, day(2026, 2, 20)).unwrap();
+
+        // THEN the January cycle is cut, and its minimum payment was paid
+        assert_eq!(latest.closing_date, day(2026, 1, 25));
+        assert_eq!(latest.closing_balance, 20000.0);
+        assert_eq!(latest.minimum_payment, 1000.0);
+        assert_eq!(latest.paid, 1500.0);
+        assert_eq!(latest.status, DueStatus::Paid);
+    }
+}
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod common {
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::transaction::{Transaction, TransactionId, TransactionType};
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::suggestion_service::SuggestionService;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::get_random_transaction;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) struct SuggestionServiceFixture {
+        pub(crate) suggestion_service:
+            SuggestionService<InMemoryTransactionRepository, InMemoryCategoryRepository>,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) category_repository: Arc<Mutex<InMemoryCategoryRepository>>,
+    }
+
+    pub(crate) fn get_suggestion_service_fixture() -> SuggestionServiceFixture {
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let category_repository = Arc::new(Mutex::new(InMemoryCategoryRepository::new()));
+
+        SuggestionServiceFixture {
+            suggestion_service: SuggestionService::new(
+                transaction_repository.clone(),
+                category_repository.clone(),
+            ),
+            transaction_repository,
+            category_repository,
+        }
+    }
+
+    pub(crate) fn create_category(
+        fixture: &SuggestionServiceFixture,
+        name: &str,
+        kind: TransactionType,
+    ) -> CategoryId {
+        fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from(name),
+                None,
+                kind,
+                None,
+                None,
+            ))
+    }
+
+    /// A transaction with the given description, and no message.
+    pub(crate) fn get_transaction(
+        transaction_type: TransactionType,
+        description: &str,
+        amount: f32,
+    ) -> Transaction {
+        let mut transaction = get_random_transaction();
+        transaction.set_transaction_type(transaction_type);
+        transaction.set_description(Some(String::from(description)));
+        transaction.set_message(None);
+        transaction.set_amount(amount.try_into().unwrap());
+        transaction
+    }
+
+    pub(crate) fn create_transaction(
+        fixture: &SuggestionServiceFixture,
+        mut transaction: Transaction,
+        category_id: Option<&CategoryId>,
+    ) -> TransactionId {
+        transaction.set_category_id(category_id.cloned());
+        fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction)
+    }
+}
+
+#[cfg(test)]
+mod test_suggestion_service_suggest {
+    use super::common::{
+        create_category, create_transaction, get_suggestion_service_fixture, get_transaction,
+    };
+    use crate::app::entities::transaction::{TransactionId, TransactionType};
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::services::suggestion_service::{SuggestError, MAX_SUGGESTIONS};
+
+    #[test]
+    fn test_suggest_ranks_categories() {
+        // GIVEN groceries, transport and salary transactions
+        let mut fixture = get_suggestion_service_fixture();
+        let groceries = create_category(&fixture, "Groceries", TransactionType::Expense);
+        let transport = create_category(&fixture, "Transport", TransactionType::Expense);
+        let salary = create_category(&fixture, "Salary", TransactionType::Income);
+        for description in ["Simba Supermarket", "SIMBA supermarket Kigali", "Simba"] {
+            let transaction = get_transaction(TransactionType::Expense, description, 15000f32);
+            create_transaction(&fixture, transaction, Some(&groceries));
+        }
+        for description in ["Moto ride Kigali", "Moto to Nyamirambo"] {
+            let transaction = get_transaction(TransactionType::Expense, description, 1500f32);
+            create_transaction(&fixture, transaction, Some(&transport));
+        }
+        let transaction = get_transaction(TransactionType::Income, "Simba salary", 300000f32);
+        create_transaction(&fixture, transaction, Some(&salary));
+        // AND an uncategorized one, which is not learned from
+        let transaction = get_transaction(TransactionType::Expense, "Moto", 1000f32);
+        create_transaction(&fixture, transaction, None);
+
+        // WHEN categories are suggested for a new supermarket expense
+        let suggestions = fixture.suggestion_service.suggest(&get_transaction(
+            TransactionType::Expense,
+            "simba supermarket",
+            12000f32,
+        ));
+
+        // THEN the expense categories are ranked, groceries first
+        let category_ids: Vec<_> = suggestions.iter().map(|s| &s.category_id).collect();
+        assert_eq!(category_ids, vec![&groceries, &transport]);
+        assert!(suggestions[0].confidence > 0.9);
+        let total: f64 = suggestions.iter().map(|s| s.confidence).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        // WHEN categories are suggested for a new moto ride
+        let suggestions = fixture.suggestion_service.suggest(&get_transaction(
+            TransactionType::Expense,
+            "Moto home",
+            2000f32,
+        ));
+
+        // THEN transport comes first
+        assert_eq!(suggestions[0].category_id, transport);
+        assert!(suggestions[0].confidence > suggestions[1].confidence);
+
+        // WHEN categories are suggested for an income
+        let suggestions = fixture.suggestion_service.suggest(&get_transaction(
+            TransactionType::Income,
+            "simba supermarket",
+            12000f32,
+        ));
+
+        // THEN only the income categories are suggested
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].category_id, salary);
+    }
+
+    #[test]
+    fn test_suggest_limits_and_skips_deleted_categories() {
+        // GIVEN more expense categories than suggestions, one of them deleted since
+        let mut fixture = get_suggestion_service_fixture();
+        let category_ids: Vec<_> = (0..=MAX_SUGGESTIONS)
+            .map(|index| {
+                let category_id =
+                    create_category(&fixture, &format!("C{index}"), TransactionType::Expense);
+                let transaction = get_transaction(TransactionType::Expense, "Shop", 100f32);
+                create_transaction(&fixture, transaction, Some(&category_id));
+                category_id
+            })
+            .collect();
+        fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .delete(category_ids[0].clone())
+            .unwrap();
+
+        // WHEN categories are suggested
+        let suggestions = fixture.suggestion_service.suggest(&get_transaction(
+            TransactionType::Expense,
+            "Shop",
+            100f32,
+        ));
+
+        // THEN the existing categories are suggested, up to the limit
+        assert_eq!(suggestions.len(), MAX_SUGGESTIONS);
+        assert!(suggestions
+            .iter()
+            .all(|suggestion| suggestion.category_id != category_ids[0]));
+    }
+
+    #[test]
+    fn test_suggest_without_history() {
+        // GIVEN no categorized transaction
+        let mut fixture = get_suggestion_service_fixture();
+        create_category(&fixture, "Groceries", TransactionType::Expense);
+
+        // WHEN categories are suggested
+        let suggestions = fixture.suggestion_service.suggest(&get_transaction(
+            TransactionType::Expense,
+            "Simba",
+            100f32,
+        ));
+
+        // THEN there is none
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn test_suggest_by_id() {
+        // GIVEN a miscategorized moto ride, among the groceries and the transport
+        let mut fixture = get_suggestion_service_fixture();
+        let groceries = create_category(&fixture, "Groceries", TransactionType::Expense);
+        let transport = create_category(&fixture, "Transport", TransactionType::Expense);
+        let transaction = get_transaction(TransactionType::Expense, "Simba", 15000f32);
+        create_transaction(&fixture, transaction, Some(&groceries));
+        let transaction = get_transaction(TransactionType::Expense, "Moto ride", 1500f32);
+        create_transaction(&fixture, transaction, Some(&transport));
+        let transaction = get_transaction(TransactionType::Expense, "Moto", 1500f32);
+        let transaction_id = create_transaction(&fixture, transaction, Some(&groceries));
+
+        // WHEN categories are suggested for it
+        let suggestions = fixture
+            .suggestion_service
+            .suggest_by_id(transaction_id.clone())
+            .unwrap();
+
+        // THEN it is not learned from, and transport is suggested first
+        assert_eq!(suggestions[0].category_id, transport);
+
+        // AND it is still learned from for the other transactions, the same suggestions are given again
+        assert_eq!(
+            fixture
+                .suggestion_service
+                .suggest_by_id(transaction_id)
+                .unwrap(),
+            suggestions
+        );
+
+        // WHEN categories are suggested for an unknown transaction
+        let unknown_id = TransactionId::generate();
+        let result = fixture.suggestion_service.suggest_by_id(unknown_id.clone());
+
+        // THEN it is not found
+        assert_eq!(result, Err(SuggestError::TransactionNotFound(unknown_id)));
+    }
+
+    #[test]
+    fn test_suggest_learns_new_transactions() {
+        // GIVEN moto rides categorized as transport
+        let mut fixture = get_suggestion_service_fixture();
+        let groceries = create_category(&fixture, "Groceries", TransactionType::Expense);
+        let transport = create_category(&fixture, "Transport", TransactionType::Expense);
+        for description in ["Moto ride", "Moto home"] {
+            let transaction = get_transaction(TransactionType::Expense, description, 1500f32);
+            create_transaction(&fixture, transaction, Some(&transport));
+        }
+        let supermarket = get_transaction(TransactionType::Expense, "Simba Supermarket", 15000f32);
+
+        // WHEN categories are suggested for a supermarket expense
+        let suggestions = fixture.suggestion_service.suggest(&supermarket);
+
+        // THEN groceries were never learned
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].category_id, transport);
+
+        // WHEN supermarket expenses are then categorized as groceries
+        for description in ["Simba Supermarket", "Simba"] {
+            let transaction = get_transaction(TransactionType::Expense, description, 15000f32);
+            create_transaction(&fixture, transaction, Some(&groceries));
+        }
+        let suggestions = fixture.suggestion_service.suggest(&supermarket);
+
+        // THEN they are learned, groceries comes first
+        assert_eq!(suggestions[0].category_id, groceries);
+    }
+}
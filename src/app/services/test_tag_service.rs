@@ -0,0 +1,450 @@
+#[cfg(test)]
+mod common {
+    use crate::app::services::tag_service::TagService;
+    use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::budget_repository::InMemoryBudgetRepository;
+    use crate::infrastructure::repositories::in_memory::recurring_transaction_repository::InMemoryRecurringTransactionRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+    use crate::infrastructure::repositories::in_memory::tag_repository::InMemoryTagRepository;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use std::sync::{Arc, Mutex};
+
+    pub(crate) type InMemoryTagService = TagService<
+        InMemoryTagRepository,
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryRuleRepository,
+        InMemoryRecurringTransactionRepository,
+        InMemoryBudgetRepository,
+    >;
+
+    pub(crate) struct TagServiceFixture {
+        pub(crate) tag_service: InMemoryTagService,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) account_repository: Arc<Mutex<InMemoryAccountRepository>>,
+        pub(crate) rule_repository: Arc<Mutex<InMemoryRuleRepository>>,
+        pub(crate) budget_repository: Arc<Mutex<InMemoryBudgetRepository>>,
+    }
+
+    /// Builds a tag service, along with the repositories of the entities it tags.
+    pub(crate) fn get_tag_service_fixture() -> TagServiceFixture {
+        let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
+        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
+        let rule_repository = Arc::new(Mutex::new(InMemoryRuleRepository::new()));
+        let budget_repository = Arc::new(Mutex::new(InMemoryBudgetRepository::new()));
+
+        TagServiceFixture {
+            tag_service: TagService::new(
+                Arc::new(Mutex::new(InMemoryTagRepository::new())),
+                transaction_repository.clone(),
+                account_repository.clone(),
+                rule_repository.clone(),
+                Arc::new(Mutex::new(InMemoryRecurringTransactionRepository::new())),
+                budget_repository.clone(),
+            ),
+            transaction_repository,
+            account_repository,
+            rule_repository,
+            budget_repository,
+        }
+    }
+
+    pub(crate) fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test_tag_service_find {
+    use super::common::{get_tag_service_fixture, names};
+    use crate::app::services::tag_service::{FindByNameError, TagNameError};
+
+    #[test]
+    fn test_find_or_create() {
+        // GIVEN a tag service without tags
+        let fixture = get_tag_service_fixture();
+        let tag_service = fixture.tag_service;
+
+        // WHEN tags are requested by name, with different spellings of the same tag
+        let ids = tag_service
+            .find_or_create(&names(&["Trip Musanze", "reimbursable", "trip-musanze"]))
+            .unwrap();
+
+        // THEN each tag is created once, with a normalized name
+        assert_eq!(ids.len(), 2);
+        let tag_names: Vec<String> = tag_service
+            .find_all()
+            .iter()
+            .map(|tag| tag.name().clone())
+            .collect();
+        assert_eq!(tag_names, ["reimbursable", "trip-musanze"]);
+
+        // AND the existing tags are reused
+        assert_eq!(
+            tag_service
+                .find_or_create(&names(&["REIMBURSABLE"]))
+                .unwrap(),
+            [ids[1].clone()]
+        );
+        assert_eq!(tag_service.find_all().len(), 2);
+
+        // AND a blank name is refused
+        assert_eq!(
+            tag_service.find_or_create(&names(&["work", " "])),
+            Err(TagNameError::EmptyName)
+        );
+    }
+
+    #[test]
+    fn test_find_by_names() {
+        // GIVEN a tag service with a tag
+        let fixture = get_tag_service_fixture();
+        let tag_service = fixture.tag_service;
+        let ids = tag_service.find_or_create(&names(&["work"])).unwrap();
+
+        // WHEN the tag is searched by name
+        // THEN it is found
+        assert_eq!(tag_service.find_by_names(&names(&["Work"])), Ok(ids));
+
+        // AND an unknown tag is not created
+        assert_eq!(
+            tag_service.find_by_names(&names(&["work", "holiday"])),
+            Err(FindByNameError::NotFound(String::from("holiday")))
+        );
+        assert_eq!(tag_service.find_all().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod test_tag_service_rename {
+    use super::common::{get_tag_service_fixture, names};
+    use crate::app::entities::tag::TagId;
+    use crate::app::services::tag_service::{RenameError, TagNameError};
+
+    #[test]
+    fn test_rename() {
+        // GIVEN a tag service with two tags
+        let fixture = get_tag_service_fixture();
+        let tag_service = fixture.tag_service;
+        let ids = tag_service
+            .find_or_create(&names(&["work", "holiday"]))
+            .unwrap();
+
+        // WHEN a tag is renamed
+        let tag = tag_service.rename(&ids[0], "Day Job").unwrap();
+
+        // THEN its name is normalized and saved
+        assert_eq!(tag.name(), "day-job");
+        assert_eq!(tag_service.find_by_id_or_fail(&ids[0]), Ok(tag));
+
+        // AND a tag can not take the name of another tag
+        assert_eq!(
+            tag_service.rename(&ids[0], "holiday"),
+            Err(RenameError::DuplicateName(ids[1].clone()))
+        );
+
+        // AND a blank name, or an unknown tag, are refused
+        assert_eq!(
+            tag_service.rename(&ids[0], "  "),
+            Err(RenameError::Invalid(TagNameError::EmptyName))
+        );
+        assert_eq!(
+            tag_service.rename(&TagId::generate(), "other"),
+            Err(RenameError::EntityIdNotFound)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_tag_service_merge {
+    use super::common::{get_tag_service_fixture, names};
+    use crate::app::entities::budget::{Budget, BudgetPeriod, BudgetScope};
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions};
+    use crate::app::entities::tag::TagId;
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::repositories::budget_repository::BudgetRepository;
+    use crate::app::repositories::rule_repository::RuleRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::tag_service::{FindByIdOrFailError, MergeError};
+    use crate::app::typing::currency::Currency;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_merge() {
+        // GIVEN two tags with the same meaning
+        let fixture = get_tag_service_fixture();
+        let tag_service = fixture.tag_service;
+        let ids = tag_service
+            .find_or_create(&names(&["groceries", "grocery"]))
+            .unwrap();
+        let (target, source) = (ids[0].clone(), ids[1].clone());
+
+        // AND a transaction and an account carrying the tag to merge
+        let mut transaction = get_random_transaction();
+        transaction.set_tags(vec![source.clone()]);
+        let transaction_id = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction);
+
+        let mut account = get_random_account();
+        account.set_tags(vec![source.clone(), target.clone()]);
+        let account_id = fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap();
+
+        // AND a rule adding the tag to merge
+        let rule_id = fixture.rule_repository.lock().unwrap().create(Rule::new(
+            None,
+            String::from("Groceries"),
+            0,
+            RuleConditions::default(),
+            RuleActions {
+                tags: vec![source.clone()],
+                ..Default::default()
+            },
+        ));
+
+        // AND a budget of the tag to merge
+        let budget_id = fixture
+            .budget_repository
+            .lock()
+            .unwrap()
+            .create(Budget::new(
+                None,
+                String::from("Groceries"),
+                BudgetScope::Tag(source.clone()),
+                BudgetPeriod::Monthly,
+                100000f32.try_into().unwrap(),
+                Currency::RWF,
+                false,
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            ));
+
+        // WHEN the tag is merged into the other one
+        let tag = tag_service.merge(&source, &target).unwrap();
+
+        // THEN the tagged entities carry the target tag instead, once
+        assert_eq!(tag.id(), Some(&target));
+        let transaction = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.tags(), &vec![target.clone()]);
+        let account = fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .find_by_id(account_id)
+            .unwrap();
+        assert_eq!(account.tags(), &vec![target.clone()]);
+
+        // AND the rule adds the target tag instead
+        let rule = fixture
+            .rule_repository
+            .lock()
+            .unwrap()
+            .find_by_id(rule_id)
+            .unwrap();
+        assert_eq!(rule.actions().tags, vec![target.clone()]);
+
+        // AND the budget applies to the target tag instead
+        let budget = fixture
+            .budget_repository
+            .lock()
+            .unwrap()
+            .find_by_id(budget_id)
+            .unwrap();
+        assert_eq!(budget.scope(), &BudgetScope::Tag(target.clone()));
+
+        // AND the merged tag is deleted
+        assert_eq!(
+            tag_service.find_by_id_or_fail(&source),
+            Err(FindByIdOrFailError::NotFound(source.clone()))
+        );
+
+        // AND it can not be merged again
+        assert_eq!(
+            tag_service.merge(&source, &target),
+            Err(MergeError::SourceNotFound(source))
+        );
+    }
+
+    #[test]
+    fn test_merge_errors() {
+        // GIVEN a tag service with a tag
+        let fixture = get_tag_service_fixture();
+        let tag_service = fixture.tag_service;
+        let id = tag_service.find_or_create(&names(&["work"])).unwrap()[0].clone();
+
+        // WHEN a tag is merged into itself, or into an unknown tag
+        // THEN it is refused
+        assert_eq!(tag_service.merge(&id, &id), Err(MergeError::SameTag));
+        let unknown_id = TagId::generate();
+        assert_eq!(
+            tag_service.merge(&id, &unknown_id),
+            Err(MergeError::TargetNotFound(unknown_id))
+        );
+        assert!(tag_service.find_by_id_or_fail(&id).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod test_tag_service_set_tags {
+    use super::common::{get_tag_service_fixture, names};
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::transaction::TransactionId;
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::tag_service::SetTagsError;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+
+    #[test]
+    fn test_set_tags() {
+        // GIVEN a transaction and an account without tags
+        let fixture = get_tag_service_fixture();
+        let tag_service = fixture.tag_service;
+        let transaction_id = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .create(get_random_transaction());
+        let account_id = fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+
+        // WHEN they are tagged by name
+        let transaction_tags = tag_service
+            .set_transaction_tags(&transaction_id, &names(&["trip-musanze", "reimbursable"]))
+            .unwrap();
+        let account_tags = tag_service
+            .set_account_tags(&account_id, &names(&["Trip Musanze"]))
+            .unwrap();
+
+        // THEN the tags are created once, and saved on the entities
+        assert_eq!(tag_service.find_all().len(), 2);
+        assert_eq!(account_tags, [transaction_tags[0].clone()]);
+        let transaction = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.tags(), &transaction_tags);
+        let account = fixture
+            .account_repository
+            .lock()
+            .unwrap()
+            .find_by_id(account_id)
+            .unwrap();
+        assert_eq!(account.tags(), &account_tags);
+
+        // AND unknown entities can not be tagged
+        assert_eq!(
+            tag_service.set_transaction_tags(&TransactionId::generate(), &names(&["work"])),
+            Err(SetTagsError::EntityIdNotFound)
+        );
+        assert_eq!(
+            tag_service.set_account_tags(&AccountId::generate(), &names(&["work"])),
+            Err(SetTagsError::EntityIdNotFound)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_tag_service_report {
+    use super::common::{get_tag_service_fixture, names};
+    use crate::app::entities::tag::TagId;
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::tag_service::TagTotal;
+    use crate::app::typing::currency::Currency;
+    use crate::shared::test_utilities::get_random_transaction;
+
+    #[test]
+    fn test_report() {
+        // GIVEN two tags
+        let fixture = get_tag_service_fixture();
+        let tag_service = fixture.tag_service;
+        let ids = tag_service
+            .find_or_create(&names(&["trip", "reimbursable"]))
+            .unwrap();
+        let (trip_id, reimbursable_id) = (ids[0].clone(), ids[1].clone());
+
+        // AND tagged transactions, one of them failed, and an untagged one
+        let transactions: [(Vec<TagId>, TransactionType, f32, TransactionStatus); 5] = [
+            (
+                vec![trip_id.clone(), reimbursable_id.clone()],
+                TransactionType::Expense,
+                80.0,
+                TransactionStatus::Confirmed,
+            ),
+            (
+                vec![trip_id.clone()],
+                TransactionType::Expense,
+                5.0,
+                TransactionStatus::Confirmed,
+            ),
+            (
+                vec![reimbursable_id.clone()],
+                TransactionType::Income,
+                80.0,
+                TransactionStatus::Confirmed,
+            ),
+            (
+                vec![trip_id.clone()],
+                TransactionType::Expense,
+                100.0,
+                TransactionStatus::Failed,
+            ),
+            (
+                vec![],
+                TransactionType::Expense,
+                7.0,
+                TransactionStatus::Confirmed,
+            ),
+        ];
+        for (tags, transaction_type, amount, status) in transactions {
+            let mut transaction = get_random_transaction();
+            transaction.set_tags(tags);
+            transaction.set_transaction_type(transaction_type);
+            transaction.set_amount(amount.try_into().unwrap());
+            transaction.set_status(status);
+            fixture
+                .transaction_repository
+                .lock()
+                .unwrap()
+                .create(transaction);
+        }
+
+        // WHEN the report is computed
+        let report = tag_service.report(None, None);
+
+        // THEN each tag totals its confirmed transactions, a transaction counting in each of its tags
+        let total_of = |tag_id: &TagId| -> &TagTotal {
+            report.iter().find(|total| &total.tag_id == tag_id).unwrap()
+        };
+        assert_eq!(report.len(), 2);
+
+        let trip = total_of(&trip_id);
+        assert_eq!(trip.currency, Currency::RWF);
+        assert_eq!(trip.transaction_count, 2);
+        assert_eq!(trip.income, 0.0);
+        assert_eq!(trip.expense, 85.0);
+
+        let reimbursable = total_of(&reimbursable_id);
+        assert_eq!(reimbursable.transaction_count, 2);
+        assert_eq!(reimbursable.income, 80.0);
+        assert_eq!(reimbursable.expense, 80.0);
+    }
+}
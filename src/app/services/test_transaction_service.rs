@@ -1,47 +1,207 @@
 #[cfg(test)]
 mod common {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::transaction::{
+        AccountRef, Transaction, TransactionId, TransactionStatus, TransactionType,
+    };
+    use crate::app::repositories::transaction_query::TransactionQuery;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
     use crate::app::services::account_service::AccountService;
+    use crate::app::services::rule_service::RuleService;
     use crate::app::services::transaction_service::TransactionService;
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+    use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
     use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+    use chrono::{TimeZone, Utc};
     use std::sync::{Arc, Mutex};
 
-    #[cfg(test)]
-    pub(crate) fn get_transaction_service(
-    ) -> TransactionService<InMemoryTransactionRepository, InMemoryAccountRepository> {
+    pub(crate) type InMemoryTransactionService = TransactionService<
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+        InMemoryRuleRepository,
+    >;
+
+    pub(crate) type InMemoryRuleService = RuleService<
+        InMemoryRuleRepository,
+        InMemoryTransactionRepository,
+        InMemoryAccountRepository,
+        InMemoryCategoryRepository,
+        InMemoryPayeeRepository,
+    >;
+
+    /// A transaction service, along with the repositories and services it works with.
+    pub(crate) struct TransactionServiceFixture {
+        pub(crate) transaction_service: InMemoryTransactionService,
+        pub(crate) transaction_repository: Arc<Mutex<InMemoryTransactionRepository>>,
+        pub(crate) account_service:
+            Arc<Mutex<AccountService<InMemoryAccountRepository, InMemoryTransactionRepository>>>,
+        pub(crate) category_repository: Arc<Mutex<InMemoryCategoryRepository>>,
+        pub(crate) payee_repository: Arc<Mutex<InMemoryPayeeRepository>>,
+        pub(crate) rule_service: Arc<Mutex<InMemoryRuleService>>,
+    }
+
+    pub(crate) fn get_transaction_service_fixture() -> TransactionServiceFixture {
         let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
-        let account_service = Arc::new(Mutex::new(AccountService::new(account_repository)));
         let transaction_repository = Arc::new(Mutex::new(InMemoryTransactionRepository::new()));
-        let transaction_service = TransactionService::new(account_service, transaction_repository);
+        let account_service = Arc::new(Mutex::new(AccountService::new(
+            account_repository.clone(),
+            transaction_repository.clone(),
+        )));
+        let category_repository = Arc::new(Mutex::new(InMemoryCategoryRepository::new()));
+        let payee_repository = Arc::new(Mutex::new(InMemoryPayeeRepository::new()));
+        let rule_service = Arc::new(Mutex::new(RuleService::new(
+            Arc::new(Mutex::new(InMemoryRuleRepository::new())),
+            transaction_repository.clone(),
+            account_repository,
+            category_repository.clone(),
+            payee_repository.clone(),
+        )));
+        let transaction_service = TransactionService::new(
+            account_service.clone(),
+            transaction_repository.clone(),
+            category_repository.clone(),
+            payee_repository.clone(),
+            rule_service.clone(),
+        );
 
-        transaction_service
+        TransactionServiceFixture {
+            transaction_service,
+            transaction_repository,
+            account_service,
+            category_repository,
+            payee_repository,
+            rule_service,
+        }
+    }
+
+    /// Builds a transaction service, along with the repository it reads the transactions from.
+    pub(crate) fn get_transaction_service() -> (
+        InMemoryTransactionService,
+        Arc<Mutex<InMemoryTransactionRepository>>,
+    ) {
+        let fixture = get_transaction_service_fixture();
+
+        (fixture.transaction_service, fixture.transaction_repository)
+    }
+
+    /// Builds a confirmed transaction of the account dated at noon on the day of 2026, without a fee.
+    pub(crate) fn get_transaction(
+        account_id: &AccountId,
+        transaction_type: TransactionType,
+        amount: f32,
+        month: u32,
+        day: u32,
+    ) -> Transaction {
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id.clone()));
+        transaction.set_transaction_type(transaction_type);
+        transaction.set_amount(amount.try_into().unwrap());
+        transaction.set_fee(0f32.try_into().unwrap());
+        transaction.set_status(TransactionStatus::Confirmed);
+        transaction.set_date(Utc.with_ymd_and_hms(2026, month, day, 12, 0, 0).unwrap());
+        transaction.set_opening_balance(None);
+        transaction.set_closing_balance(None);
+        transaction
+    }
+
+    /// Creates an account which received 100 in January, spent 20 in February and 30 in March.
+    /// # Returns
+    /// The account, and the ids of its transactions, the oldest first.
+    pub(crate) fn get_account_with_history(
+        fixture: &mut TransactionServiceFixture,
+    ) -> (AccountId, Vec<TransactionId>) {
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+        let transaction_ids = [
+            get_transaction(&account_id, TransactionType::Income, 100.0, 1, 10),
+            get_transaction(&account_id, TransactionType::Expense, 20.0, 2, 10),
+            get_transaction(&account_id, TransactionType::Expense, 30.0, 3, 10),
+        ]
+        .into_iter()
+        .map(|transaction| fixture.transaction_service.create(transaction).unwrap())
+        .collect();
+
+        (account_id, transaction_ids)
+    }
+
+    /// The opening and closing balances of the transactions of the account, the oldest first,
+    /// followed by the balance of the account.
+    pub(crate) fn get_balances(
+        fixture: &TransactionServiceFixture,
+        account_id: &AccountId,
+    ) -> (Vec<(f32, f32)>, f32) {
+        let mut transactions = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find(&TransactionQuery {
+                account_id: Some(account_id.clone()),
+                ..TransactionQuery::default()
+            })
+            .items;
+        transactions.reverse();
+        let account = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .find_by_id_or_fail(account_id)
+            .unwrap();
+
+        (
+            transactions
+                .iter()
+                .map(|transaction| {
+                    (
+                        transaction.opening_balance().as_ref().unwrap().value(),
+                        transaction.closing_balance().as_ref().unwrap().value(),
+                    )
+                })
+                .collect(),
+            account.balance().value(),
+        )
     }
 }
 
 #[cfg(test)]
-mod test_transaction_service_find_all {
-    use crate::app::entities::common::EntityId;
+mod test_transaction_service_find {
+    use crate::app::entities::transaction::TransactionId;
+    use crate::app::repositories::transaction_query::{TransactionQuery, MAX_PAGE_SIZE};
     use crate::app::repositories::transaction_repository::TransactionRepository;
-    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::app::services::transaction_service::QueryError;
     use crate::shared::test_utilities::get_random_transaction;
+    use chrono::{Duration, Utc};
 
     #[test]
-    fn test_find_all_success() {
+    fn test_find_success() {
         // GIVEN an in memory transaction repository
         // AND a transaction service
-        let transaction_service = super::common::get_transaction_service();
+        let (transaction_service, _) = super::common::get_transaction_service();
 
         // WHEN we find all transactions
-        let transactions = transaction_service.find_all();
+        let transactions = transaction_service
+            .find(&TransactionQuery::default())
+            .unwrap()
+            .items;
 
         // THEN the account list is empty
         assert_eq!(transactions.len(), 0);
     }
 
     #[test]
-    fn test_find_all_with_items() {
+    fn test_find_with_items() {
         // GIVEN an in memory transaction repository
-        let mut transaction_repository = InMemoryTransactionRepository::new();
+        // AND a transaction service
+        let (transaction_service, transaction_repository) =
+            super::common::get_transaction_service();
 
         // AND with some transactions
         let transactions = [
@@ -50,54 +210,1211 @@ mod test_transaction_service_find_all {
             get_random_transaction(),
         ];
 
-        let mut transactions_ids: [EntityId; 3] = Default::default();
-        let mut counter = 0;
-        for transaction in transactions {
-            let id = transaction_repository.create(transaction);
-            transactions_ids[counter] = id;
-            counter += 1;
-        }
-        // AND a transaction service
-        let transaction_service = super::common::get_transaction_service();
+        let transactions_ids: Vec<TransactionId> = transactions
+            .into_iter()
+            .map(|transaction| transaction_repository.lock().unwrap().create(transaction))
+            .collect();
 
         // WHEN we find all transactions
-        let transactions = transaction_service.find_all();
+        let transactions = transaction_service
+            .find(&TransactionQuery::default())
+            .unwrap()
+            .items;
 
-        // THEN the account list is empty
-        assert_eq!(transactions.len(), transactions.len());
+        // THEN all the transactions are returned
+        assert_eq!(transactions.len(), transactions_ids.len());
 
         for entity_id in transactions_ids {
-            assert!(transaction_service
-                .find_by_id_or_fail(&transaction_repository, entity_id)
-                .is_ok())
+            assert!(transaction_service.find_by_id_or_fail(entity_id).is_ok())
+        }
+    }
+
+    #[test]
+    fn test_find_invalid_queries() {
+        // GIVEN a transaction service
+        let (transaction_service, _) = super::common::get_transaction_service();
+
+        // WHEN the limit is out of bounds
+        // THEN the query is rejected
+        for limit in [0, MAX_PAGE_SIZE + 1] {
+            let query = TransactionQuery {
+                limit,
+                ..Default::default()
+            };
+            assert_eq!(
+                transaction_service.find(&query).err().unwrap(),
+                QueryError::LimitOutOfBounds
+            );
+        }
+
+        // WHEN the amount range is inverted
+        let query = TransactionQuery {
+            min_amount: Some(100f32.try_into().unwrap()),
+            max_amount: Some(10f32.try_into().unwrap()),
+            ..Default::default()
+        };
+
+        // THEN the query is rejected
+        assert_eq!(
+            transaction_service.find(&query).err().unwrap(),
+            QueryError::InvertedAmountRange
+        );
+
+        // WHEN the date range is inverted
+        let now = Utc::now();
+        let query = TransactionQuery {
+            from: Some(now),
+            to: Some(now - Duration::days(1)),
+            ..Default::default()
+        };
+
+        // THEN the query is rejected
+        assert_eq!(
+            transaction_service.find(&query).err().unwrap(),
+            QueryError::InvertedDateRange
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_stream {
+    use crate::app::entities::transaction::{TransactionId, TransactionType};
+    use crate::app::repositories::common::STREAM_BATCH_SIZE;
+    use crate::app::repositories::transaction_query::TransactionQuery;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::transaction_service::QueryError;
+    use crate::shared::test_utilities::get_random_transaction;
+
+    #[test]
+    fn test_stream_across_batches() {
+        // GIVEN a transaction service
+        let (transaction_service, transaction_repository) =
+            super::common::get_transaction_service();
+
+        // AND more transactions than two batches, on the same date, one in three being an income
+        let transactions_ids: Vec<(TransactionId, TransactionType)> = (0..2 * STREAM_BATCH_SIZE
+            + 5)
+            .map(|index| {
+                let mut transaction = get_random_transaction();
+                if index % 3 == 0 {
+                    transaction.set_transaction_type(TransactionType::Income);
+                }
+                let transaction_type = transaction.transaction_type().clone();
+                let id = transaction_repository.lock().unwrap().create(transaction);
+                (id, transaction_type)
+            })
+            .collect();
+
+        // WHEN all the transactions are streamed
+        let streamed_ids: Vec<TransactionId> = transaction_service
+            .stream(TransactionQuery::default())
+            .unwrap()
+            .map(|transaction| transaction.id().clone().unwrap())
+            .collect();
+
+        // THEN every transaction is returned once, the most recent first
+        let expected_ids: Vec<TransactionId> = transactions_ids
+            .iter()
+            .rev()
+            .map(|(id, _)| id.clone())
+            .collect();
+        assert_eq!(streamed_ids, expected_ids);
+
+        // WHEN only the incomes are streamed
+        let query = TransactionQuery {
+            transaction_type: Some(TransactionType::Income),
+            ..Default::default()
+        };
+        let streamed_count = transaction_service
+            .stream(query)
+            .unwrap()
+            .inspect(|transaction| {
+                assert_eq!(transaction.transaction_type(), &TransactionType::Income)
+            })
+            .count();
+
+        // THEN the filter applies to all the batches
+        let incomes_count = transactions_ids
+            .iter()
+            .filter(|(_, transaction_type)| transaction_type == &TransactionType::Income)
+            .count();
+        assert_eq!(streamed_count, incomes_count);
+    }
+
+    #[test]
+    fn test_stream_invalid_query() {
+        // GIVEN a transaction service
+        let (transaction_service, _) = super::common::get_transaction_service();
+
+        // WHEN the amount range of the query is inverted
+        let query = TransactionQuery {
+            min_amount: Some(100f32.try_into().unwrap()),
+            max_amount: Some(10f32.try_into().unwrap()),
+            ..Default::default()
+        };
+
+        // THEN the stream is rejected
+        assert_eq!(
+            transaction_service.stream(query).err().unwrap(),
+            QueryError::InvertedAmountRange
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_create {
+    use crate::app::entities::account::AccountType;
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::transaction::{AccountRef, Transaction, TransactionType};
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::services::transaction_service::CreateError;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+
+    /// Builds an income of the account, without the fields derived by the service.
+    fn get_income(fixture: &super::common::TransactionServiceFixture) -> Transaction {
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id));
+        transaction.set_transaction_type(TransactionType::Income);
+        transaction.set_opening_balance(None);
+        transaction.set_closing_balance(None);
+        transaction
+    }
+
+    #[test]
+    fn test_create_with_category() {
+        // GIVEN a transaction service, with an income category
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let category_id = fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Salary"),
+                None,
+                TransactionType::Income,
+                None,
+                None,
+            ));
+
+        // WHEN an income of the category is created
+        let mut transaction = get_income(&fixture);
+        transaction.set_category_id(Some(category_id.clone()));
+        let transaction_id = fixture.transaction_service.create(transaction).unwrap();
+
+        // THEN the transaction references the category
+        let transaction = fixture
+            .transaction_service
+            .find_by_id_or_fail(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.category_id(), Some(&category_id));
+    }
+
+    #[test]
+    fn test_create_with_invalid_category() {
+        // GIVEN a transaction service, with an expense category
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let expense_category_id =
+            fixture
+                .category_repository
+                .lock()
+                .unwrap()
+                .create(Category::new(
+                    None,
+                    String::from("Food"),
+                    None,
+                    TransactionType::Expense,
+                    None,
+                    None,
+                ));
+
+        // WHEN an income references an unknown category
+        let unknown_id = CategoryId::generate();
+        let mut transaction = get_income(&fixture);
+        transaction.set_category_id(Some(unknown_id.clone()));
+
+        // THEN it is rejected
+        assert_eq!(
+            fixture
+                .transaction_service
+                .create(transaction)
+                .err()
+                .unwrap(),
+            CreateError::InvalidCategoryRef {
+                category_id: unknown_id
+            }
+        );
+
+        // WHEN an income references an expense category
+        let mut transaction = get_income(&fixture);
+        transaction.set_category_id(Some(expense_category_id));
+
+        // THEN it is rejected
+        assert_eq!(
+            fixture
+                .transaction_service
+                .create(transaction)
+                .err()
+                .unwrap(),
+            CreateError::CategoryKindMismatch
+        );
+    }
+
+    #[test]
+    fn test_create_with_fee_on_cash() {
+        // GIVEN a transaction service, with a cash account
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let mut account = get_random_account();
+        account.set_account_type(AccountType::Cash);
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap();
+
+        // WHEN an income with a fee is created on it
+        let mut transaction = get_income(&fixture);
+        transaction.set_account(AccountRef::Id(account_id));
+        transaction.set_fee(10f32.try_into().unwrap());
+
+        // THEN it is rejected, the cash is never charged fees
+        assert_eq!(
+            fixture.transaction_service.create(transaction.clone()),
+            Err(CreateError::FeeNotAllowed)
+        );
+
+        // AND it is created without the fee
+        transaction.set_fee(0f32.try_into().unwrap());
+        assert!(fixture.transaction_service.create(transaction).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_open_account {
+    use crate::app::entities::account::{
+        AccountStatus, AccountType, StatusChange, OPENING_BALANCE_DESCRIPTION,
+    };
+    use crate::app::entities::institution::InstitutionId;
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::repositories::transaction_query::TransactionQuery;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::account_service::CreateError as AccountCreateError;
+    use crate::app::services::transaction_service::{CreateError, OpenAccountError};
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[test]
+    fn test_open_account_with_balance() {
+        // GIVEN a transaction service
+        let mut fixture = super::common::get_transaction_service_fixture();
+
+        // WHEN a bank account is onboarded with the money it held at the end of last year
+        let opened_at = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        let account_id = fixture
+            .transaction_service
+            .open_account(
+                get_random_account(),
+                Some(25000f32.try_into().unwrap()),
+                Some(opened_at),
+            )
+            .unwrap();
+
+        // THEN the account holds the money
+        let account = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .find_by_id_or_fail(&account_id)
+            .unwrap();
+        assert_eq!(account.balance().value(), 25000.0);
+
+        // AND its history is a confirmed income of the money, dated at the opening
+        let transactions = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find(&TransactionQuery {
+                account_id: Some(account_id),
+                ..TransactionQuery::default()
+            })
+            .items;
+        assert_eq!(transactions.len(), 1);
+        let opening = &transactions[0];
+        assert_eq!(opening.transaction_type(), &TransactionType::Income);
+        assert_eq!(opening.status(), &TransactionStatus::Confirmed);
+        assert_eq!(opening.amount().value(), 25000.0);
+        assert_eq!(opening.date(), opened_at);
+        assert_eq!(
+            opening.description().as_deref(),
+            Some(OPENING_BALANCE_DESCRIPTION)
+        );
+        assert_eq!(opening.opening_balance().as_ref().unwrap().value(), 0.0);
+        assert_eq!(opening.closing_balance().as_ref().unwrap().value(), 25000.0);
+    }
+
+    #[test]
+    fn test_open_account_without_balance() {
+        // GIVEN a transaction service
+        let mut fixture = super::common::get_transaction_service_fixture();
+
+        // WHEN an account is opened without money, or with none
+        let empty_id = fixture
+            .transaction_service
+            .open_account(get_random_account(), None, None)
+            .unwrap();
+        let zero_id = fixture
+            .transaction_service
+            .open_account(get_random_account(), Some(0f32.try_into().unwrap()), None)
+            .unwrap();
+
+        // THEN nothing is recorded
+        for account_id in [empty_id, zero_id] {
+            let transactions = fixture
+                .transaction_repository
+                .lock()
+                .unwrap()
+                .find(&TransactionQuery {
+                    account_id: Some(account_id),
+                    ..TransactionQuery::default()
+                })
+                .items;
+            assert!(transactions.is_empty());
         }
     }
+
+    #[test]
+    fn test_open_account_invalid() {
+        // GIVEN a transaction service
+        let mut fixture = super::common::get_transaction_service_fixture();
+
+        // WHEN an account is opened tomorrow
+        let result = fixture.transaction_service.open_account(
+            get_random_account(),
+            Some(100f32.try_into().unwrap()),
+            Some(Utc::now() + Duration::days(1)),
+        );
+
+        // THEN it is rejected, and no account is created
+        assert_eq!(result, Err(OpenAccountError::OpeningDateInFuture));
+        assert!(fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .find_all()
+            .is_empty());
+
+        // WHEN an invalid account is opened
+        let mut account = get_random_account();
+        account.set_account_type(AccountType::Cash);
+        account.set_institution_id(Some(InstitutionId::generate()));
+        let result = fixture
+            .transaction_service
+            .open_account(account, None, None);
+
+        // THEN the reason is given
+        assert_eq!(
+            result,
+            Err(OpenAccountError::Invalid(
+                AccountCreateError::InstitutionNotAllowed
+            ))
+        );
+    }
+
+    #[test]
+    fn test_open_account_balance_not_recorded() {
+        // GIVEN a transaction service
+        let mut fixture = super::common::get_transaction_service_fixture();
+
+        // WHEN an account taking no transactions is opened with a balance
+        let mut account = get_random_account();
+        account.change_status(StatusChange {
+            status: AccountStatus::Closed,
+            reason: String::from("Moved abroad"),
+            changed_at: Utc::now(),
+        });
+        let result = fixture.transaction_service.open_account(
+            account,
+            Some(100f32.try_into().unwrap()),
+            None,
+        );
+
+        // THEN it is rejected, and no account is left behind
+        assert_eq!(
+            result,
+            Err(OpenAccountError::OpeningBalanceNotRecorded(
+                CreateError::AccountClosed
+            ))
+        );
+        assert!(fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .find_all()
+            .is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_account_status {
+    use crate::app::entities::account::{AccountId, AccountStatus};
+    use crate::app::entities::transaction::{AccountRef, Transaction, TransactionType};
+    use crate::app::repositories::transaction_query::TransactionQuery;
+    use crate::app::repositories::transaction_repository::TransactionRepository;
+    use crate::app::services::account_service::StatusError;
+    use crate::app::services::transaction_service::{CloseAccountError, CreateError};
+    use crate::app::typing::currency::Currency;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+
+    /// Opens an account holding the balance.
+    fn open_account(
+        fixture: &mut super::common::TransactionServiceFixture,
+        balance: f32,
+    ) -> AccountId {
+        fixture
+            .transaction_service
+            .open_account(
+                get_random_account(),
+                Some(balance.try_into().unwrap()),
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Builds a transaction of the account, without a fee nor the fields derived by the service.
+    fn get_transaction(account_id: &AccountId, transaction_type: TransactionType) -> Transaction {
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id.clone()));
+        transaction.set_transaction_type(transaction_type);
+        transaction.set_amount(10f32.try_into().unwrap());
+        transaction.set_fee(0f32.try_into().unwrap());
+        transaction.set_opening_balance(None);
+        transaction.set_closing_balance(None);
+        transaction
+    }
+
+    #[test]
+    fn test_frozen_account() {
+        // GIVEN a frozen account holding money
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let account_id = open_account(&mut fixture, 100.0);
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .change_status(&account_id, AccountStatus::Frozen, "Card lost")
+            .unwrap();
+
+        // WHEN money is taken out of it
+        let result = fixture
+            .transaction_service
+            .create(get_transaction(&account_id, TransactionType::Expense));
+
+        // THEN it is rejected
+        assert_eq!(result, Err(CreateError::AccountFrozen));
+
+        // AND money can still be received
+        assert!(fixture
+            .transaction_service
+            .create(get_transaction(&account_id, TransactionType::Income))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_close_account_with_sweep() {
+        // GIVEN an account holding money, and another one
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let account_id = open_account(&mut fixture, 100.0);
+        let target_id = open_account(&mut fixture, 50.0);
+
+        // WHEN the account is closed, its balance swept into the other one
+        let account = fixture
+            .transaction_service
+            .close_account(&account_id, "Moved to another bank", Some(&target_id))
+            .unwrap();
+
+        // THEN the account is closed and empty, the change recorded
+        assert_eq!(account.status(), AccountStatus::Closed);
+        assert_eq!(account.balance().value(), 0.0);
+        assert_eq!(account.status_changes()[0].reason, "Moved to another bank");
+
+        // AND the other account received the money
+        let target = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .find_by_id_or_fail(&target_id)
+            .unwrap();
+        assert_eq!(target.balance().value(), 150.0);
+
+        // AND the transfer is in the history of both accounts
+        let transactions = fixture
+            .transaction_repository
+            .lock()
+            .unwrap()
+            .find(&TransactionQuery::default())
+            .items;
+        let transfers: Vec<(Option<&AccountId>, &TransactionType, f32)> = transactions
+            .iter()
+            .filter(|transaction| {
+                transaction
+                    .description()
+                    .as_deref()
+                    .is_some_and(|description| description.starts_with("Transfer"))
+            })
+            .map(|transaction| {
+                (
+                    transaction.account_id(),
+                    transaction.transaction_type(),
+                    transaction.amount().value(),
+                )
+            })
+            .collect();
+        assert_eq!(transfers.len(), 2);
+        assert!(transfers.contains(&(Some(&account_id), &TransactionType::Expense, 100.0)));
+        assert!(transfers.contains(&(Some(&target_id), &TransactionType::Income, 100.0)));
+
+        // AND the closed account takes no transaction anymore
+        assert_eq!(
+            fixture
+                .transaction_service
+                .create(get_transaction(&account_id, TransactionType::Income)),
+            Err(CreateError::AccountClosed)
+        );
+    }
+
+    #[test]
+    fn test_close_account_invalid() {
+        // GIVEN an account holding money
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let account_id = open_account(&mut fixture, 100.0);
+
+        // WHEN it is closed without sweeping its balance
+        // THEN it is rejected
+        assert_eq!(
+            fixture
+                .transaction_service
+                .close_account(&account_id, "Not used", None),
+            Err(CloseAccountError::Status(StatusError::NonZeroBalance))
+        );
+
+        // AND the balance can not be swept into itself, an unknown account, or a closed one
+        let closed_id = open_account(&mut fixture, 0.0);
+        fixture
+            .transaction_service
+            .close_account(&closed_id, "Not used", None)
+            .unwrap();
+        for target_id in [account_id.clone(), AccountId::generate(), closed_id] {
+            assert_eq!(
+                fixture.transaction_service.close_account(
+                    &account_id,
+                    "Not used",
+                    Some(&target_id)
+                ),
+                Err(CloseAccountError::InvalidSweepAccount)
+            );
+        }
+
+        // AND not into an account of another currency
+        let mut dollars = get_random_account();
+        dollars.set_currency(Currency::USD);
+        let dollars_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(dollars)
+            .unwrap();
+        assert_eq!(
+            fixture
+                .transaction_service
+                .close_account(&account_id, "Not used", Some(&dollars_id)),
+            Err(CloseAccountError::SweepCurrencyMismatch)
+        );
+
+        // AND not out of a frozen account
+        let target_id = open_account(&mut fixture, 0.0);
+        fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .change_status(&account_id, AccountStatus::Frozen, "Card lost")
+            .unwrap();
+        assert_eq!(
+            fixture
+                .transaction_service
+                .close_account(&account_id, "Not used", Some(&target_id)),
+            Err(CloseAccountError::SweepFailed(CreateError::AccountFrozen))
+        );
+
+        // AND the account is left as it was
+        let account = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .find_by_id_or_fail(&account_id)
+            .unwrap();
+        assert_eq!(account.status(), AccountStatus::Frozen);
+        assert_eq!(account.balance().value(), 100.0);
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_backdated {
+    use super::common::{get_balances, get_transaction};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::services::transaction_service::CreateError;
+    use crate::shared::test_utilities::get_random_account;
+
+    #[test]
+    fn test_create_backdated() {
+        // GIVEN an account which received 100 in January and spent 30 in March
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+        for transaction in [
+            get_transaction(&account_id, TransactionType::Income, 100.0, 1, 10),
+            get_transaction(&account_id, TransactionType::Expense, 30.0, 3, 10),
+        ] {
+            fixture.transaction_service.create(transaction).unwrap();
+        }
+
+        // WHEN an expense of February, forgotten until now, is entered
+        fixture
+            .transaction_service
+            .create(get_transaction(
+                &account_id,
+                TransactionType::Expense,
+                20.0,
+                2,
+                10,
+            ))
+            .unwrap();
+
+        // THEN it opens at the balance of February, and the expense of March opens lower
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 80.0), (80.0, 50.0)], 50.0)
+        );
+
+        // WHEN an income is backdated before all of them
+        fixture
+            .transaction_service
+            .create(get_transaction(
+                &account_id,
+                TransactionType::Income,
+                5.0,
+                1,
+                1,
+            ))
+            .unwrap();
+
+        // THEN every later balance is raised by it, up to the balance of the account
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (
+                vec![(0.0, 5.0), (5.0, 105.0), (105.0, 85.0), (85.0, 55.0)],
+                55.0
+            )
+        );
+    }
+
+    #[test]
+    fn test_create_backdated_without_funds() {
+        // GIVEN an account which received 100 in January and 500 in March
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+        for transaction in [
+            get_transaction(&account_id, TransactionType::Income, 100.0, 1, 10),
+            get_transaction(&account_id, TransactionType::Income, 500.0, 3, 10),
+        ] {
+            fixture.transaction_service.create(transaction).unwrap();
+        }
+
+        // WHEN an expense of 200 is backdated to February, when the account held 100
+        let result = fixture.transaction_service.create(get_transaction(
+            &account_id,
+            TransactionType::Expense,
+            200.0,
+            2,
+            10,
+        ));
+
+        // THEN it is rejected, although the account holds enough now
+        assert_eq!(result, Err(CreateError::InsufficientFunds));
+
+        // AND the history is left as it was
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 600.0)], 600.0)
+        );
+    }
 }
 
 #[cfg(test)]
-mod test_transaction_service_create {}
+mod test_transaction_service_edit {
+    use super::common::{get_account_with_history, get_balances};
+    use crate::app::entities::transaction::{RevisionKind, TransactionId, TransactionType};
+    use crate::app::services::transaction_service::{CreateError, EditError, TransactionEdit};
+    use crate::app::typing::currency::Currency;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_update_transaction() {
+        // GIVEN an account which received 100 in January, spent 20 in February and 30 in March
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, transaction_ids) = get_account_with_history(&mut fixture);
+
+        // WHEN the expense of February is corrected to 50, spent in April
+        let transaction = fixture
+            .transaction_service
+            .update(
+                transaction_ids[1].clone(),
+                TransactionEdit {
+                    amount: Some(50f32.try_into().unwrap()),
+                    date: Some(Utc.with_ymd_and_hms(2026, 4, 10, 12, 0, 0).unwrap()),
+                    description: Some(String::from("Rent")),
+                    ..TransactionEdit::default()
+                },
+            )
+            .unwrap();
+
+        // THEN it is saved with the new values
+        assert_eq!(transaction.amount().value(), 50.0);
+        assert_eq!(transaction.description(), &Some(String::from("Rent")));
+
+        // AND the balances are recomputed from February, up to the corrected balance of the account
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 70.0), (70.0, 20.0)], 20.0)
+        );
+
+        // AND the previous version is kept
+        let revisions = fixture
+            .transaction_service
+            .find_revisions(&transaction_ids[1]);
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].kind, RevisionKind::Updated);
+        assert_eq!(revisions[0].previous.amount.value(), 20.0);
+        assert_eq!(
+            revisions[0]
+                .previous
+                .closing_balance
+                .as_ref()
+                .unwrap()
+                .value(),
+            80.0
+        );
+
+        // WHEN the expense of March turns out to be an income
+        fixture
+            .transaction_service
+            .update(
+                transaction_ids[2].clone(),
+                TransactionEdit {
+                    transaction_type: Some(TransactionType::Income),
+                    ..TransactionEdit::default()
+                },
+            )
+            .unwrap();
+
+        // THEN its effect is reversed, then applied the other way
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 130.0), (130.0, 80.0)], 80.0)
+        );
+    }
+
+    #[test]
+    fn test_update_transaction_account() {
+        // GIVEN an account with a history, and an empty account
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, transaction_ids) = get_account_with_history(&mut fixture);
+        let other_account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+
+        // WHEN the expense of February is moved to the empty account
+        let result = fixture.transaction_service.update(
+            transaction_ids[1].clone(),
+            TransactionEdit {
+                account_id: Some(other_account_id.clone()),
+                ..TransactionEdit::default()
+            },
+        );
+
+        // THEN it is rejected, the empty account can not pay it
+        assert_eq!(
+            result.unwrap_err(),
+            EditError::Invalid(CreateError::InsufficientFunds)
+        );
+
+        // WHEN it is moved as an income instead
+        fixture
+            .transaction_service
+            .update(
+                transaction_ids[1].clone(),
+                TransactionEdit {
+                    account_id: Some(other_account_id.clone()),
+                    transaction_type: Some(TransactionType::Income),
+                    ..TransactionEdit::default()
+                },
+            )
+            .unwrap();
+
+        // THEN the first account gets the expense back
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 70.0)], 70.0)
+        );
+
+        // AND the other account receives it
+        assert_eq!(
+            get_balances(&fixture, &other_account_id),
+            (vec![(0.0, 20.0)], 20.0)
+        );
+    }
+
+    #[test]
+    fn test_update_transaction_account_currency() {
+        // GIVEN an account in RWF with a history, and an account in USD
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, transaction_ids) = get_account_with_history(&mut fixture);
+        let mut dollars = get_random_account();
+        dollars.set_currency(Currency::USD);
+        let dollars_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(dollars)
+            .unwrap();
+
+        // WHEN the income of January is moved to the account in USD
+        let result = fixture.transaction_service.update(
+            transaction_ids[0].clone(),
+            TransactionEdit {
+                account_id: Some(dollars_id.clone()),
+                ..TransactionEdit::default()
+            },
+        );
+
+        // THEN it is rejected, its amount is in RWF
+        assert_eq!(result.unwrap_err(), EditError::CurrencyMismatch);
+
+        // AND both accounts are left as they were
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 80.0), (80.0, 50.0)], 50.0)
+        );
+        assert_eq!(get_balances(&fixture, &dollars_id), (vec![], 0.0));
+    }
+
+    #[test]
+    fn test_update_transaction_invalid() {
+        // GIVEN an account which received 100 in January, spent 20 in February and 30 in March
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, transaction_ids) = get_account_with_history(&mut fixture);
+
+        // WHEN the income of January is lowered below the expenses which followed it
+        let result = fixture.transaction_service.update(
+            transaction_ids[0].clone(),
+            TransactionEdit {
+                amount: Some(40f32.try_into().unwrap()),
+                ..TransactionEdit::default()
+            },
+        );
+
+        // THEN it is rejected, the account could not pay the expense of March
+        assert_eq!(
+            result.unwrap_err(),
+            EditError::Invalid(CreateError::InsufficientFunds)
+        );
+
+        // WHEN an unknown transaction is updated
+        let unknown_id = TransactionId::generate();
+        let result = fixture
+            .transaction_service
+            .update(unknown_id.clone(), TransactionEdit::default());
+
+        // THEN it is not found
+        assert_eq!(result.unwrap_err(), EditError::NotFound(unknown_id));
+
+        // AND the history is left as it was, without any revision
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 80.0), (80.0, 50.0)], 50.0)
+        );
+        assert!(fixture
+            .transaction_service
+            .find_revisions(&transaction_ids[0])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_delete_transaction() {
+        // GIVEN an account which received 100 in January, spent 20 in February and 30 in March
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, transaction_ids) = get_account_with_history(&mut fixture);
+
+        // WHEN the income of January is deleted
+        let result = fixture
+            .transaction_service
+            .delete(transaction_ids[0].clone());
+
+        // THEN it is rejected, the expenses which followed it would not be covered
+        assert_eq!(
+            result.unwrap_err(),
+            EditError::Invalid(CreateError::InsufficientFunds)
+        );
+
+        // WHEN the expense of February is deleted
+        fixture
+            .transaction_service
+            .delete(transaction_ids[1].clone())
+            .unwrap();
+
+        // THEN it is gone, and the account gets the money back
+        assert!(fixture
+            .transaction_service
+            .find_by_id(transaction_ids[1].clone())
+            .is_none());
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (vec![(0.0, 100.0), (100.0, 70.0)], 70.0)
+        );
+
+        // AND the deleted version is kept
+        let revisions = fixture
+            .transaction_service
+            .find_revisions(&transaction_ids[1]);
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].kind, RevisionKind::Deleted);
+        assert_eq!(revisions[0].previous.amount.value(), 20.0);
+
+        // AND it can not be deleted twice
+        assert_eq!(
+            fixture
+                .transaction_service
+                .delete(transaction_ids[1].clone())
+                .unwrap_err(),
+            EditError::NotFound(transaction_ids[1].clone())
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_reverse {
+    use super::common::{get_account_with_history, get_balances, get_transaction};
+    use crate::app::entities::transaction::{TransactionId, TransactionStatus, TransactionType};
+    use crate::app::services::transaction_service::{
+        CreateError, EditError, ReverseError, TransactionEdit,
+    };
+    use crate::app::typing::amount::MIN_AMOUNT;
+
+    #[test]
+    fn test_reverse_transaction() {
+        // GIVEN an account which received 100 in January, spent 20 in February and 30 in March
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, transaction_ids) = get_account_with_history(&mut fixture);
+        let expense_id = transaction_ids[2].clone();
+
+        // WHEN the expense of March is reversed, the bank charging 1 for it
+        let reversal = fixture
+            .transaction_service
+            .reverse(expense_id.clone(), 1f32.try_into().unwrap())
+            .unwrap();
+
+        // THEN a compensating income of the same amount is created, linked to the expense
+        assert_eq!(reversal.transaction_type(), &TransactionType::Income);
+        assert_eq!(reversal.amount().value(), 30.0);
+        assert_eq!(reversal.fee().value(), 1.0);
+        assert_eq!(reversal.status(), &TransactionStatus::Confirmed);
+        assert_eq!(reversal.reverses(), Some(&expense_id));
+        assert_eq!(reversal.closing_balance().as_ref().unwrap().value(), 79.0);
+
+        // AND the expense is rolled back, the account getting its amount back less the fee of the reversal
+        let expense = fixture
+            .transaction_service
+            .find_by_id(expense_id.clone())
+            .unwrap();
+        assert_eq!(expense.status(), &TransactionStatus::RolledBack);
+        assert_eq!(
+            get_balances(&fixture, &account_id),
+            (
+                vec![(0.0, 100.0), (100.0, 80.0), (80.0, 50.0), (50.0, 79.0)],
+                79.0
+            )
+        );
+
+        // WHEN the expense is reversed again
+        let result = fixture
+            .transaction_service
+            .reverse(expense_id.clone(), MIN_AMOUNT);
+
+        // THEN it is rejected
+        assert_eq!(result.unwrap_err(), ReverseError::AlreadyReversed);
+
+        // AND the reversal can not be reversed either
+        let reversal_id = reversal.id().clone().unwrap();
+        assert_eq!(
+            fixture
+                .transaction_service
+                .reverse(reversal_id.clone(), MIN_AMOUNT)
+                .unwrap_err(),
+            ReverseError::ReversalEntry
+        );
+
+        // AND neither can be edited or deleted, which would break the compensation
+        assert_eq!(
+            fixture
+                .transaction_service
+                .update(
+                    expense_id,
+                    TransactionEdit {
+                        amount: Some(10f32.try_into().unwrap()),
+                        ..TransactionEdit::default()
+                    }
+                )
+                .unwrap_err(),
+            EditError::Reversed
+        );
+        assert_eq!(
+            fixture.transaction_service.delete(reversal_id).unwrap_err(),
+            EditError::Reversed
+        );
+        assert_eq!(get_balances(&fixture, &account_id).1, 79.0);
+    }
+
+    #[test]
+    fn test_reverse_income_with_fee() {
+        // GIVEN an account holding 50, which received 10 in April
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, _) = get_account_with_history(&mut fixture);
+        let income_id = fixture
+            .transaction_service
+            .create(get_transaction(
+                &account_id,
+                TransactionType::Income,
+                10.0,
+                4,
+                10,
+            ))
+            .unwrap();
+
+        // WHEN the income is reversed, the bank charging more than the account holds after the compensation
+        let result = fixture
+            .transaction_service
+            .reverse(income_id.clone(), 51f32.try_into().unwrap());
+
+        // THEN it is rejected
+        assert_eq!(
+            result.unwrap_err(),
+            ReverseError::Invalid(CreateError::InsufficientFunds)
+        );
+
+        // WHEN it is reversed, the bank charging 1 for it
+        let reversal = fixture
+            .transaction_service
+            .reverse(income_id, 1f32.try_into().unwrap())
+            .unwrap();
+
+        // THEN the compensating expense takes the income and the fee from the account
+        assert_eq!(reversal.transaction_type(), &TransactionType::Expense);
+        assert_eq!(reversal.closing_balance().as_ref().unwrap().value(), 49.0);
+        assert_eq!(get_balances(&fixture, &account_id).1, 49.0);
+    }
+
+    #[test]
+    fn test_reverse_transaction_invalid() {
+        // GIVEN an account which received 100 in January, spent 20 in February and 30 in March
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (account_id, transaction_ids) = get_account_with_history(&mut fixture);
+        let income_id = transaction_ids[0].clone();
+
+        // AND a failed transaction
+        let mut failed = get_transaction(&account_id, TransactionType::Expense, 5.0, 3, 25);
+        failed.set_status(TransactionStatus::Failed);
+        let failed_id = fixture.transaction_service.create(failed).unwrap();
+
+        // AND a transaction can not be created rolled back, without its reversal
+        let mut rolled_back = get_transaction(&account_id, TransactionType::Expense, 5.0, 3, 25);
+        rolled_back.set_status(TransactionStatus::RolledBack);
+        assert_eq!(
+            fixture.transaction_service.create(rolled_back),
+            Err(CreateError::RolledBackStatus)
+        );
+
+        // WHEN the failed transaction is reversed
+        let result = fixture.transaction_service.reverse(failed_id, MIN_AMOUNT);
+
+        // THEN it is rejected
+        assert_eq!(result.unwrap_err(), ReverseError::TransactionFailed);
+
+        // WHEN the income is reversed, although 50 of it was spent
+        let result = fixture
+            .transaction_service
+            .reverse(income_id.clone(), MIN_AMOUNT);
+
+        // THEN it is rejected, and the income is left as it was
+        assert_eq!(
+            result.unwrap_err(),
+            ReverseError::Invalid(CreateError::InsufficientFunds)
+        );
+        let income = fixture.transaction_service.find_by_id(income_id).unwrap();
+        assert_eq!(income.status(), &TransactionStatus::Confirmed);
+
+        // WHEN an unknown transaction is reversed
+        let unknown_id = TransactionId::generate();
+        let result = fixture
+            .transaction_service
+            .reverse(unknown_id.clone(), MIN_AMOUNT);
+
+        // THEN it is not found
+        assert_eq!(result.unwrap_err(), ReverseError::NotFound(unknown_id));
+    }
+}
 
 #[cfg(test)]
 mod test_transaction_service_find_by_id {
+    use crate::app::entities::transaction::TransactionId;
     use crate::app::repositories::transaction_repository::TransactionRepository;
-    use crate::app::services::transaction_service::TransactionService;
-    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
     use crate::shared::test_utilities::{assert_transactions_equal, get_random_transaction};
 
     #[test]
     fn find_by_id_success() {
         // GIVEN an in memory transaction repository
-        let mut transaction_repository = InMemoryTransactionRepository::new();
+        // AND a transaction service
+        let (transaction_service, transaction_repository) =
+            super::common::get_transaction_service();
 
         // AND a transaction in the repository
         let transaction = get_random_transaction();
-        let id = transaction_repository.create(transaction.clone());
-
-        // AND a transaction service
-        let transaction_service = super::common::get_transaction_service();
+        let id = transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction.clone());
 
         // WHEN querying by id
-        let actual_transaction = transaction_service.find_by_id(&transaction_repository, id);
+        let actual_transaction = transaction_service.find_by_id(id);
 
         // THEN it should exist
         assert!(actual_transaction.is_some());
@@ -109,14 +1426,12 @@ mod test_transaction_service_find_by_id {
     #[test]
     fn find_by_id_none() {
         // GIVEN an in memory transaction repository
-        let transaction_repository = InMemoryTransactionRepository::new();
-
         // AND a transaction service
-        let transaction_service = super::common::get_transaction_service();
+        let (transaction_service, _) = super::common::get_transaction_service();
 
         // WHEN we find all transactions by a random entity id
-        let random_entity_id = "1".into();
-        let transaction = transaction_service.find_by_id(&transaction_repository, random_entity_id);
+        let random_entity_id = TransactionId::generate();
+        let transaction = transaction_service.find_by_id(random_entity_id);
 
         // THEN the response should be none
         assert!(transaction.is_none());
@@ -125,26 +1440,27 @@ mod test_transaction_service_find_by_id {
 
 #[cfg(test)]
 mod test_transaction_service_find_by_id_or_fail {
+    use crate::app::entities::transaction::TransactionId;
     use crate::app::repositories::transaction_repository::TransactionRepository;
-    use crate::app::services::transaction_service::{GetOneError, TransactionService};
-    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::app::services::transaction_service::GetOneError;
     use crate::shared::test_utilities::{assert_transactions_equal, get_random_transaction};
 
     #[test]
     fn find_by_id_or_fail_success() {
         // GIVEN an in memory transaction repository
-        let mut transaction_repository = InMemoryTransactionRepository::new();
+        // AND a transaction service
+        let (transaction_service, transaction_repository) =
+            super::common::get_transaction_service();
 
         // AND a transaction in the repository
         let transaction = get_random_transaction();
-        let id = transaction_repository.create(transaction.clone());
-
-        // AND a transaction service
-        let transaction_service = super::common::get_transaction_service();
+        let id = transaction_repository
+            .lock()
+            .unwrap()
+            .create(transaction.clone());
 
         // WHEN querying by id
-        let actual_transaction =
-            transaction_service.find_by_id_or_fail(&transaction_repository, id);
+        let actual_transaction = transaction_service.find_by_id_or_fail(id);
 
         // THEN it should exist
         assert!(actual_transaction.is_ok());
@@ -156,15 +1472,12 @@ mod test_transaction_service_find_by_id_or_fail {
     #[test]
     fn find_by_id_or_fail_error() {
         // GIVEN an in memory transaction repository
-        let transaction_repository = InMemoryTransactionRepository::new();
-
         // AND a transaction service
-        let transaction_service = super::common::get_transaction_service();
+        let (transaction_service, _) = super::common::get_transaction_service();
 
         // WHEN we find all transactions by a random entity id
-        let random_entity_id = "1".into();
-        let transaction =
-            transaction_service.find_by_id_or_fail(&transaction_repository, random_entity_id);
+        let random_entity_id = TransactionId::generate();
+        let transaction = transaction_service.find_by_id_or_fail(random_entity_id);
 
         // THEN the response should be none
         assert!(transaction.is_err());
@@ -176,3 +1489,433 @@ mod test_transaction_service_find_by_id_or_fail {
         ));
     }
 }
+
+#[cfg(test)]
+mod test_transaction_service_create_split {
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::transaction::{
+        AccountRef, Transaction, TransactionSplit, TransactionType,
+    };
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::services::transaction_service::CreateError;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+
+    /// Builds an income of 30, split in two incomes categories of 20 and 10.
+    fn get_split_income(
+        fixture: &super::common::TransactionServiceFixture,
+    ) -> (Transaction, [CategoryId; 2]) {
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+
+        let mut category_repository = fixture.category_repository.lock().unwrap();
+        let category_ids = ["Wages", "Bonus"].map(|name| {
+            category_repository.create(Category::new(
+                None,
+                String::from(name),
+                None,
+                TransactionType::Income,
+                None,
+                None,
+            ))
+        });
+
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id));
+        transaction.set_transaction_type(TransactionType::Income);
+        transaction.set_opening_balance(None);
+        transaction.set_closing_balance(None);
+        transaction.set_amount(30f32.try_into().unwrap());
+        transaction.set_splits(vec![
+            TransactionSplit {
+                category_id: category_ids[0].clone(),
+                amount: 20f32.try_into().unwrap(),
+                memo: Some(String::from("March")),
+            },
+            TransactionSplit {
+                category_id: category_ids[1].clone(),
+                amount: 10f32.try_into().unwrap(),
+                memo: None,
+            },
+        ]);
+
+        (transaction, category_ids)
+    }
+
+    #[test]
+    fn test_create_split_transaction() {
+        // GIVEN a transaction service
+        let mut fixture = super::common::get_transaction_service_fixture();
+
+        // WHEN a split transaction is created
+        let (transaction, _) = get_split_income(&fixture);
+        let transaction_id = fixture
+            .transaction_service
+            .create(transaction.clone())
+            .unwrap();
+
+        // THEN its splits are saved
+        let saved = fixture
+            .transaction_service
+            .find_by_id_or_fail(transaction_id)
+            .unwrap();
+        assert_eq!(saved.splits(), transaction.splits());
+    }
+
+    #[test]
+    fn test_create_invalid_split_transaction() {
+        // GIVEN a transaction service
+        let mut fixture = super::common::get_transaction_service_fixture();
+
+        // WHEN the splits do not add up to the transaction amount
+        let (mut transaction, _) = get_split_income(&fixture);
+        transaction.set_amount(31f32.try_into().unwrap());
+
+        // THEN the transaction is rejected
+        assert_eq!(
+            fixture
+                .transaction_service
+                .create(transaction)
+                .err()
+                .unwrap(),
+            CreateError::SplitsAmountMismatch
+        );
+
+        // WHEN the split transaction also has a category
+        let (mut transaction, category_ids) = get_split_income(&fixture);
+        transaction.set_category_id(Some(category_ids[0].clone()));
+
+        // THEN the transaction is rejected
+        assert_eq!(
+            fixture
+                .transaction_service
+                .create(transaction)
+                .err()
+                .unwrap(),
+            CreateError::CategoryWithSplits
+        );
+
+        // WHEN a split has an expense category
+        let expense_category_id =
+            fixture
+                .category_repository
+                .lock()
+                .unwrap()
+                .create(Category::new(
+                    None,
+                    String::from("Food"),
+                    None,
+                    TransactionType::Expense,
+                    None,
+                    None,
+                ));
+        let (mut transaction, _) = get_split_income(&fixture);
+        let mut splits = transaction.splits().clone();
+        splits[1].category_id = expense_category_id;
+        transaction.set_splits(splits);
+
+        // THEN the transaction is rejected
+        assert_eq!(
+            fixture
+                .transaction_service
+                .create(transaction)
+                .err()
+                .unwrap(),
+            CreateError::CategoryKindMismatch
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_create_payee {
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::payee::{Payee, PayeeId};
+    use crate::app::entities::transaction::{AccountRef, Transaction, TransactionType};
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::repositories::payee_repository::PayeeRepository;
+    use crate::app::services::transaction_service::CreateError;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+
+    /// Builds an expense of a funded account, with the description and message of a payment.
+    fn get_payment(
+        fixture: &mut super::common::TransactionServiceFixture,
+        description: &str,
+        message: &str,
+    ) -> Transaction {
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(get_random_account())
+            .unwrap();
+
+        let mut deposit = get_random_transaction();
+        deposit.set_account(AccountRef::Id(account_id.clone()));
+        deposit.set_transaction_type(TransactionType::Income);
+        deposit.set_amount(1_000_000f32.try_into().unwrap());
+        deposit.set_opening_balance(None);
+        deposit.set_closing_balance(None);
+        deposit.set_description(None);
+        deposit.set_message(None);
+        fixture.transaction_service.create(deposit).unwrap();
+
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id));
+        transaction.set_amount(100f32.try_into().unwrap());
+        transaction.set_transaction_type(TransactionType::Expense);
+        transaction.set_opening_balance(None);
+        transaction.set_closing_balance(None);
+        transaction.set_description(Some(description.to_string()));
+        transaction.set_message(Some(message.to_string()));
+        transaction
+    }
+
+    /// Creates a payee with an alias and a pattern, giving its payments a category of the given type.
+    fn create_payee(
+        fixture: &super::common::TransactionServiceFixture,
+        category_type: TransactionType,
+    ) -> (PayeeId, CategoryId) {
+        let category_id = fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Groceries"),
+                None,
+                category_type,
+                None,
+                None,
+            ));
+        let payee_id = fixture.payee_repository.lock().unwrap().create(Payee::new(
+            None,
+            String::from("Simba Supermarket"),
+            vec![String::from("Simba")],
+            vec![String::from(r"^POS \d+ SMB")],
+            Some(category_id.clone()),
+        ));
+
+        (payee_id, category_id)
+    }
+
+    #[test]
+    fn test_create_recognizes_payee() {
+        // GIVEN a transaction service, with a payee
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (payee_id, category_id) = create_payee(&fixture, TransactionType::Expense);
+
+        // WHEN payments spelling the payee are created
+        let by_alias = get_payment(&mut fixture, "Payment to SIMBA, Kigali", "");
+        let by_pattern = get_payment(&mut fixture, "Card payment", "pos 1234 smb kimihurura");
+        let unrelated = get_payment(&mut fixture, "Payment to Simbarashe", "");
+        let ids: Vec<_> = [by_alias, by_pattern, unrelated]
+            .into_iter()
+            .map(|transaction| fixture.transaction_service.create(transaction).unwrap())
+            .collect();
+
+        // THEN the payments matching an alias or a pattern reference the payee, with its default category
+        for transaction_id in &ids[..2] {
+            let transaction = fixture
+                .transaction_service
+                .find_by_id_or_fail(transaction_id.clone())
+                .unwrap();
+            assert_eq!(transaction.payee_id(), Some(&payee_id));
+            assert_eq!(transaction.category_id(), Some(&category_id));
+        }
+
+        // AND a payment only containing the alias inside a word has no payee
+        let transaction = fixture
+            .transaction_service
+            .find_by_id_or_fail(ids[2].clone())
+            .unwrap();
+        assert_eq!(transaction.payee_id(), None);
+        assert_eq!(transaction.category_id(), None);
+    }
+
+    #[test]
+    fn test_create_keeps_category() {
+        // GIVEN a transaction service, with a payee whose default category is for incomes
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (payee_id, _) = create_payee(&fixture, TransactionType::Income);
+
+        // WHEN a payment to the payee is created
+        let transaction = get_payment(&mut fixture, "Payment to Simba", "");
+        let transaction_id = fixture.transaction_service.create(transaction).unwrap();
+
+        // THEN it references the payee, without the category of another type
+        let transaction = fixture
+            .transaction_service
+            .find_by_id_or_fail(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.payee_id(), Some(&payee_id));
+        assert_eq!(transaction.category_id(), None);
+
+        // WHEN a payment to the payee is created with its own category
+        let category_id = fixture
+            .category_repository
+            .lock()
+            .unwrap()
+            .create(Category::new(
+                None,
+                String::from("Gifts"),
+                None,
+                TransactionType::Expense,
+                None,
+                None,
+            ));
+        let mut transaction = get_payment(&mut fixture, "Payment to Simba", "");
+        transaction.set_category_id(Some(category_id.clone()));
+        let transaction_id = fixture.transaction_service.create(transaction).unwrap();
+
+        // THEN the category is kept
+        let transaction = fixture
+            .transaction_service
+            .find_by_id_or_fail(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.category_id(), Some(&category_id));
+    }
+
+    #[test]
+    fn test_create_with_payee() {
+        // GIVEN a transaction service, with a payee
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let (payee_id, _) = create_payee(&fixture, TransactionType::Expense);
+        let other_id = fixture.payee_repository.lock().unwrap().create(Payee::new(
+            None,
+            String::from("Airtel"),
+            vec![],
+            vec![],
+            None,
+        ));
+
+        // WHEN a payment spelling the payee is created with another payee
+        let mut transaction = get_payment(&mut fixture, "Payment to Simba", "");
+        transaction.set_payee_id(Some(other_id.clone()));
+        let transaction_id = fixture.transaction_service.create(transaction).unwrap();
+
+        // THEN the provided payee is kept
+        let transaction = fixture
+            .transaction_service
+            .find_by_id_or_fail(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.payee_id(), Some(&other_id));
+        assert_ne!(transaction.payee_id(), Some(&payee_id));
+
+        // WHEN a payment is created with an unknown payee
+        let unknown_id = PayeeId::generate();
+        let mut transaction = get_payment(&mut fixture, "Payment to Simba", "");
+        transaction.set_payee_id(Some(unknown_id.clone()));
+
+        // THEN it is rejected
+        assert_eq!(
+            fixture
+                .transaction_service
+                .create(transaction)
+                .err()
+                .unwrap(),
+            CreateError::InvalidPayeeRef {
+                payee_id: unknown_id
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_transaction_service_create_rules {
+    use crate::app::entities::category::Category;
+    use crate::app::entities::institution::InstitutionId;
+    use crate::app::entities::payee::Payee;
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions};
+    use crate::app::entities::tag::TagId;
+    use crate::app::entities::transaction::{AccountRef, TransactionStatus, TransactionType};
+    use crate::app::repositories::category_repository::CategoryRepository;
+    use crate::app::repositories::payee_repository::PayeeRepository;
+    use crate::shared::test_utilities::{get_random_account, get_random_transaction};
+
+    #[test]
+    fn test_create_applies_rules() {
+        // GIVEN a transaction service, with a payee giving a category to its transactions
+        let mut fixture = super::common::get_transaction_service_fixture();
+        let create_category = |name: &str| {
+            fixture
+                .category_repository
+                .lock()
+                .unwrap()
+                .create(Category::new(
+                    None,
+                    String::from(name),
+                    None,
+                    TransactionType::Income,
+                    None,
+                    None,
+                ))
+        };
+        let salary_id = create_category("Salary");
+        let transfers_id = create_category("Transfers");
+        let payee_id = fixture.payee_repository.lock().unwrap().create(Payee::new(
+            None,
+            String::from("Acme Ltd"),
+            vec![],
+            vec![],
+            Some(transfers_id),
+        ));
+
+        // AND a rule classifying the salaries received on the MTN accounts
+        let tag_id = TagId::generate();
+        let mtn_id = InstitutionId::generate();
+        fixture
+            .rule_service
+            .lock()
+            .unwrap()
+            .create(Rule::new(
+                None,
+                String::from("Salary"),
+                0,
+                RuleConditions {
+                    description: Some(String::from("salary")),
+                    institution_id: Some(mtn_id.clone()),
+                    transaction_type: Some(TransactionType::Income),
+                    ..Default::default()
+                },
+                RuleActions {
+                    category_id: Some(salary_id.clone()),
+                    tags: vec![tag_id.clone()],
+                    status: Some(TransactionStatus::Pending),
+                    ..Default::default()
+                },
+            ))
+            .unwrap();
+
+        // WHEN the salary paid by the payee is received on an MTN account
+        let mut account = get_random_account();
+        account.set_institution_id(Some(mtn_id));
+        let account_id = fixture
+            .account_service
+            .lock()
+            .unwrap()
+            .create(account)
+            .unwrap();
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id));
+        transaction.set_transaction_type(TransactionType::Income);
+        transaction.set_opening_balance(None);
+        transaction.set_closing_balance(None);
+        transaction.set_description(Some(String::from("Acme Ltd salary, October")));
+        let transaction_id = fixture.transaction_service.create(transaction).unwrap();
+
+        // THEN the rule classifies it, before the default category of the payee
+        let transaction = fixture
+            .transaction_service
+            .find_by_id_or_fail(transaction_id)
+            .unwrap();
+        assert_eq!(transaction.category_id(), Some(&salary_id));
+        assert_eq!(transaction.tags(), &vec![tag_id]);
+        assert_eq!(transaction.status(), &TransactionStatus::Pending);
+
+        // AND the payee is still recognized
+        assert_eq!(transaction.payee_id(), Some(&payee_id));
+    }
+}
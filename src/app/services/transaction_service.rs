@@ -1,19 +1,39 @@
-use crate::app::entities::common::EntityId;
-use crate::app::entities::common::EntityRef::{Id, Value};
-use crate::app::entities::transaction::{Transaction, TransactionType};
+use crate::app::entities::account::{
+    Account, AccountId, AccountStatus, OPENING_BALANCE_DESCRIPTION,
+};
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::common::EntityRef::Value;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::transaction::{
+    AccountRef, RevisionKind, Transaction, TransactionId, TransactionRevision, TransactionStatus,
+    TransactionType, TransactionVersion,
+};
 use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::common::{stream_transactions, Page};
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::repositories::transaction_query::{
+    SortDirection, TransactionQuery, TransactionSort, TransactionSortField, MAX_PAGE_SIZE,
+};
 use crate::app::repositories::transaction_repository::TransactionRepository;
-use crate::app::services::account_service::AccountService;
+use crate::app::services::account_service::{
+    validate_status_change, AccountService, CreateError as AccountCreateError, StatusError,
+    UpdateError,
+};
+use crate::app::services::rule_service::RuleService;
+use crate::app::typing::amount::{Amount, MIN_AMOUNT};
+use chrono::{DateTime, Utc};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum GetOneError {
     #[error("The transaction with the id was not found")]
-    NotFound(EntityId),
+    NotFound(TransactionId),
 }
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum CreateError {
     #[error("The entity id should not be provided")]
     EntityIdProvided,
@@ -25,37 +45,414 @@ pub enum CreateError {
     ClosingBalanceProvided,
 
     #[error("The account reference is invalid")]
-    InvalidAccountRef { account_id: Option<EntityId> },
+    InvalidAccountRef { account_id: Option<AccountId> },
+
+    #[error("The account does not have enough funds for the transaction")]
+    InsufficientFunds,
+
+    #[error("The category reference is invalid")]
+    InvalidCategoryRef { category_id: CategoryId },
+
+    #[error("The category kind does not match the transaction type")]
+    CategoryKindMismatch,
+
+    #[error("A split transaction must not have a category, each split has its own")]
+    CategoryWithSplits,
+
+    #[error("The amounts of the splits must add up to the transaction amount")]
+    SplitsAmountMismatch,
+
+    #[error("The payee reference is invalid")]
+    InvalidPayeeRef { payee_id: PayeeId },
+
+    #[error("The transactions of the account can not be charged a fee")]
+    FeeNotAllowed,
+
+    #[error("The account is closed, it takes no transactions")]
+    AccountClosed,
+
+    #[error("The account is frozen, no money can be taken out of it")]
+    AccountFrozen,
+
+    #[error("A transaction is only rolled back by reversing it")]
+    RolledBackStatus,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum OpenAccountError {
+    #[error(transparent)]
+    Invalid(#[from] AccountCreateError),
+
+    #[error("The opening date must not be in the future")]
+    OpeningDateInFuture,
+
+    #[error("The account was created, but its opening balance could not be recorded: {0}")]
+    OpeningBalanceNotRecorded(CreateError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CloseAccountError {
+    #[error(transparent)]
+    Status(#[from] StatusError),
+
+    #[error("The balance can only be swept into another account which is not closed")]
+    InvalidSweepAccount,
+
+    #[error("The balance can only be swept into an account of the same currency")]
+    SweepCurrencyMismatch,
+
+    #[error("The balance could not be swept: {0}")]
+    SweepFailed(CreateError),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EditError {
+    #[error("The transaction with the id was not found")]
+    NotFound(TransactionId),
+
+    #[error("A reversed transaction, or a reversal, can not be edited or deleted")]
+    Reversed,
+
+    #[error("A transaction can only be moved to an account of the same currency")]
+    CurrencyMismatch,
+
+    #[error(transparent)]
+    Invalid(#[from] CreateError),
 }
 
-pub struct TransactionService<R, AR> {
-    account_service: Arc<Mutex<AccountService<AR>>>,
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ReverseError {
+    #[error("The transaction with the id was not found")]
+    NotFound(TransactionId),
+
+    #[error("The transaction is already reversed")]
+    AlreadyReversed,
+
+    #[error("A failed transaction can not be reversed")]
+    TransactionFailed,
+
+    #[error("A reversal can not be reversed itself")]
+    ReversalEntry,
+
+    #[error("The compensating transaction could not be created: {0}")]
+    Invalid(#[from] CreateError),
+}
+
+/// The changes of a transaction, the fields left to `None` keep their value.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionEdit {
+    pub account_id: Option<AccountId>,
+    pub transaction_type: Option<TransactionType>,
+    pub amount: Option<Amount>,
+    pub fee: Option<Amount>,
+    pub date: Option<DateTime<Utc>>,
+    pub description: Option<String>,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum QueryError {
+    #[error("The limit must be between 1 and {max}", max = MAX_PAGE_SIZE)]
+    LimitOutOfBounds,
+
+    #[error("The minimum amount must not be greater than the maximum amount")]
+    InvertedAmountRange,
+
+    #[error("The start date must be before the end date")]
+    InvertedDateRange,
+}
+
+/// Checks that the ranges of the query are not inverted.
+fn validate_filters(query: &TransactionQuery) -> Result<(), QueryError> {
+    if let (Some(min_amount), Some(max_amount)) = (&query.min_amount, &query.max_amount) {
+        if min_amount > max_amount {
+            return Err(QueryError::InvertedAmountRange);
+        }
+    }
+
+    if let (Some(from), Some(to)) = (&query.from, &query.to) {
+        if from >= to {
+            return Err(QueryError::InvertedDateRange);
+        }
+    }
+
+    Ok(())
+}
+
+/// A confirmed transaction without a fee, moving the amount in or out of the account.
+fn transfer(
+    account: &Account,
+    transaction_type: TransactionType,
+    amount: &Amount,
+    description: String,
+    date: DateTime<Utc>,
+) -> Transaction {
+    Transaction::new(
+        None,
+        AccountRef::Id(account.id().unwrap().clone()),
+        transaction_type,
+        amount.clone(),
+        MIN_AMOUNT,
+        None,
+        None,
+        account.currency().clone(),
+        TransactionStatus::Confirmed,
+        date,
+        Some(description),
+        None,
+        None,
+    )
+}
+
+/// What the transaction adds to, or takes from, the balance of its account.
+/// The fees are recorded along with the amounts, except the fee of a reversal: it is taken on top of
+/// the compensation, see `TransactionService::reverse`.
+fn balance_effect(transaction: &Transaction) -> f32 {
+    let effect = match transaction.transaction_type() {
+        TransactionType::Income => transaction.amount().value(),
+        TransactionType::Expense => -transaction.amount().value(),
+    };
+    match transaction.reverses() {
+        Some(_) => effect - transaction.fee().value(),
+        None => effect,
+    }
+}
+
+/// Whether the transaction was reversed, or is the reversal of another one.
+fn is_reversal_pair(transaction: &Transaction) -> bool {
+    transaction.status() == &TransactionStatus::RolledBack || transaction.reverses().is_some()
+}
+
+/// Adds the change to the balance of the account, or takes it from it when it is negative.
+fn change_balance<AR: AccountRepository, R: TransactionRepository>(
+    account_service: &AccountService<AR, R>,
+    account_id: &AccountId,
+    change: f32,
+) -> Result<(), CreateError> {
+    let result = if change > 0.0 {
+        account_service.deposit(account_id, &Amount::new(change).unwrap())
+    } else if change < 0.0 {
+        account_service.withdraw(account_id, &Amount::new(-change).unwrap())
+    } else {
+        return Ok(());
+    };
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(UpdateError::InsufficientFunds) => Err(CreateError::InsufficientFunds),
+        Err(UpdateError::EntityIdNotFound) => Err(CreateError::InvalidAccountRef {
+            account_id: Some(account_id.clone()),
+        }),
+        // A deposit or a withdrawal leaves the identifiers of the account unchanged.
+        Err(UpdateError::DuplicateIdentifier(_)) => unreachable!(),
+    }
+}
+
+/// The rule service, shared with the API which manages the rules.
+type SharedRuleService<RR, R, AR, CR, PR> = Arc<Mutex<RuleService<RR, R, AR, CR, PR>>>;
+
+pub struct TransactionService<R, AR, CR, PR, RR> {
+    account_service: Arc<Mutex<AccountService<AR, R>>>,
     transaction_repository: Arc<Mutex<R>>,
+    category_repository: Arc<Mutex<CR>>,
+    payee_repository: Arc<Mutex<PR>>,
+    rule_service: SharedRuleService<RR, R, AR, CR, PR>,
 }
 
-impl<R: TransactionRepository, AR: AccountRepository> TransactionService<R, AR> {
+impl<
+        R: TransactionRepository,
+        AR: AccountRepository,
+        CR: CategoryRepository,
+        PR: PayeeRepository,
+        RR: RuleRepository,
+    > TransactionService<R, AR, CR, PR, RR>
+{
     pub fn new(
-        account_service: Arc<Mutex<AccountService<AR>>>,
+        account_service: Arc<Mutex<AccountService<AR, R>>>,
         transaction_repository: Arc<Mutex<R>>,
+        category_repository: Arc<Mutex<CR>>,
+        payee_repository: Arc<Mutex<PR>>,
+        rule_service: SharedRuleService<RR, R, AR, CR, PR>,
     ) -> Self {
         Self {
             account_service,
             transaction_repository,
+            category_repository,
+            payee_repository,
+            rule_service,
         }
     }
 
-    /// Retrieves all transactions stored in the repository.
+    /// Links the transaction to its payee.
     ///
-    /// This method fetches all `Transaction` records managed by the repository
+    /// A provided payee must exist. Otherwise the payee is recognized from the description, then the message,
+    /// of the transaction: the first payee (in creation order) mentioning one of them is taken.
+    /// When the transaction has no category, it gets the default category of the payee, if its kind matches.
+    fn recognize_payee(&self, transaction: &mut Transaction) -> Result<(), CreateError> {
+        let payee_repository = self.payee_repository.lock().unwrap();
+
+        let payee = match transaction.payee_id() {
+            Some(payee_id) => Some(payee_repository.find_by_id(payee_id.clone()).ok_or_else(
+                || CreateError::InvalidPayeeRef {
+                    payee_id: payee_id.clone(),
+                },
+            )?),
+            None => {
+                let payees = payee_repository.find_all();
+                [transaction.description(), transaction.message()]
+                    .into_iter()
+                    .flatten()
+                    .find_map(|text| payees.iter().find(|payee| payee.matches(text)).cloned())
+            }
+        };
+        let Some(payee) = payee else {
+            return Ok(());
+        };
+        transaction.set_payee_id(payee.id().cloned());
+
+        if transaction.category_id().is_none() && transaction.splits().is_empty() {
+            let default_category = payee.default_category_id().and_then(|category_id| {
+                self.category_repository
+                    .lock()
+                    .unwrap()
+                    .find_by_id(category_id.clone())
+            });
+            if let Some(category) = default_category {
+                if category.kind() == transaction.transaction_type() {
+                    transaction.set_category_id(category.id().cloned());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a category exists and applies to the type of the transaction.
+    fn validate_category(
+        &self,
+        category_id: &CategoryId,
+        transaction_type: &TransactionType,
+    ) -> Result<(), CreateError> {
+        let category = self
+            .category_repository
+            .lock()
+            .unwrap()
+            .find_by_id(category_id.clone())
+            .ok_or_else(|| CreateError::InvalidCategoryRef {
+                category_id: category_id.clone(),
+            })?;
+
+        if category.kind() != transaction_type {
+            return Err(CreateError::CategoryKindMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the categorization of the transaction: either a single category, or splits
+    /// with valid categories whose amounts add up to the transaction amount.
+    fn validate_categories(&self, transaction: &Transaction) -> Result<(), CreateError> {
+        if transaction.splits().is_empty() {
+            return match transaction.category_id() {
+                Some(category_id) => {
+                    self.validate_category(category_id, transaction.transaction_type())
+                }
+                None => Ok(()),
+            };
+        }
+
+        if transaction.category_id().is_some() {
+            return Err(CreateError::CategoryWithSplits);
+        }
+
+        for split in transaction.splits() {
+            self.validate_category(&split.category_id, transaction.transaction_type())?;
+        }
+
+        // The amounts are compared to the cent, the float sum is not exact.
+        let to_cents = |value: f64| (value * 100.0).round() as i64;
+        let splits_total: f64 = transaction
+            .splits()
+            .iter()
+            .map(|split| split.amount.value() as f64)
+            .sum();
+        if to_cents(splits_total) != to_cents(transaction.amount().value() as f64) {
+            return Err(CreateError::SplitsAmountMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Checks what a transaction references before it is created: its account, payee and categories.
+    /// The funds are not checked, they depend on when the transaction is created.
+    pub fn validate_references(&self, transaction: &Transaction) -> Result<(), CreateError> {
+        let account_id = transaction
+            .account_id()
+            .ok_or(CreateError::InvalidAccountRef { account_id: None })?;
+        if self
+            .account_service
+            .lock()
+            .unwrap()
+            .find_by_id_or_fail(account_id)
+            .is_err()
+        {
+            return Err(CreateError::InvalidAccountRef {
+                account_id: Some(account_id.clone()),
+            });
+        }
+
+        if let Some(payee_id) = transaction.payee_id() {
+            if self
+                .payee_repository
+                .lock()
+                .unwrap()
+                .find_by_id(payee_id.clone())
+                .is_none()
+            {
+                return Err(CreateError::InvalidPayeeRef {
+                    payee_id: payee_id.clone(),
+                });
+            }
+        }
+
+        self.validate_categories(transaction)
+    }
+
+    /// Searches the transactions matching the query.
     ///
     /// # Returns
-    /// * `&Vec<Transaction>` -- A reference to a vector containing all `Transaction` instances.
-    pub fn find_all<'a>(&self) -> Vec<Transaction> {
-        self.transaction_repository.lock().unwrap().find_all()
+    /// * `Page<Transaction>` -- A page of at most `query.limit` transactions, with the cursor of the next page.
+    /// # Errors
+    /// * `QueryError` -- If the query is not consistent (limit out of bounds, inverted ranges).
+    pub fn find(&self, query: &TransactionQuery) -> Result<Page<Transaction>, QueryError> {
+        if query.limit == 0 || query.limit > MAX_PAGE_SIZE {
+            return Err(QueryError::LimitOutOfBounds);
+        }
+
+        validate_filters(query)?;
+
+        Ok(self.transaction_repository.lock().unwrap().find(query))
+    }
+
+    /// Iterates over all the transactions matching the query, in the order of the query.
+    /// The transactions are fetched from the repository in batches, while the iterator is consumed,
+    /// so the listing runs in bounded memory. The limit of the query is ignored.
+    ///
+    /// # Errors
+    /// * `QueryError` -- If the filters of the query are not consistent (inverted ranges).
+    pub fn stream(
+        &self,
+        query: TransactionQuery,
+    ) -> Result<impl Iterator<Item = Transaction>, QueryError> {
+        validate_filters(&query)?;
+
+        Ok(stream_transactions(
+            self.transaction_repository.clone(),
+            query,
+        ))
     }
 
-    pub fn create(&mut self, transaction: Transaction) -> Result<EntityId, CreateError> {
-        let mut _account_service = self.account_service.lock().unwrap();
+    pub fn create(&mut self, transaction: Transaction) -> Result<TransactionId, CreateError> {
+        let _account_service = self.account_service.lock().unwrap();
 
         // 1. Validate that optional fields are not provided
 
@@ -75,19 +472,21 @@ impl<R: TransactionRepository, AR: AccountRepository> TransactionService<R, AR>
             return Err(CreateError::ClosingBalanceProvided);
         }
 
+        // 1.4 A rolled back transaction comes with the reversal compensating it, see `reverse`
+        if transaction.status() == &TransactionStatus::RolledBack {
+            return Err(CreateError::RolledBackStatus);
+        }
+
         // 2. Get the account id, otherwise throw that it was not provided
-        let account_id: EntityId = match transaction.account() {
-            // 2.1 If the actual account is value, extract the id from it
-            Value(account) => match account.id() {
-                Some(id) => id.clone(),
-                None => return Err(CreateError::InvalidAccountRef { account_id: None }),
-            },
-            Id(id) => id.clone(),
+        //    (If the actual account is a value, the id is extracted from it)
+        let account_id: AccountId = match transaction.account_id() {
+            Some(id) => id.clone(),
+            None => return Err(CreateError::InvalidAccountRef { account_id: None }),
         };
 
         // 3. Verify that the account we have can be verified by the account service, otherwise it is an account that does not exist.
         let account = match _account_service.find_by_id_or_fail(&account_id) {
-            Ok(account) => account.clone(),
+            Ok(account) => account,
             Err(_) => {
                 return Err(CreateError::InvalidAccountRef {
                     account_id: account_id.clone().into(),
@@ -95,63 +494,631 @@ impl<R: TransactionRepository, AR: AccountRepository> TransactionService<R, AR>
             }
         };
 
-        // 4. Create a new transaction with the account as value
+        // 3.1 Some accounts, like the cash in hand, are never charged fees
+        if !account.account_type().charges_fees() && transaction.fee().value() > 0.0 {
+            return Err(CreateError::FeeNotAllowed);
+        }
+
+        // 3.2 A closed account takes no transactions, and no money is taken out of a frozen one
+        match (account.status(), transaction.transaction_type()) {
+            (AccountStatus::Closed, _) => return Err(CreateError::AccountClosed),
+            (AccountStatus::Frozen, TransactionType::Expense) => {
+                return Err(CreateError::AccountFrozen)
+            }
+            _ => {}
+        }
+
+        // 3.3 A backdated transaction comes before the later transactions of the account:
+        //     an expense must be covered by the balance the account had at its date, and kept until now
+        let later_transactions: Vec<Transaction> = self
+            .account_history(&account_id, Some(transaction.date()))
+            .into_iter()
+            .filter(|later| later.date() > transaction.date())
+            .collect();
+        let change = balance_effect(&transaction);
+        if change < 0.0 {
+            let lowest_balance = later_transactions
+                .iter()
+                .filter_map(|later| later.opening_balance().as_ref())
+                .map(Amount::value)
+                .fold(account.balance().value(), f32::min);
+            if -change > lowest_balance {
+                return Err(CreateError::InsufficientFunds);
+            }
+        }
+
+        // 4. Apply the rules, then link the payee, which may both give the transaction its category
         let mut savable_transaction = transaction.clone();
+        self.rule_service
+            .lock()
+            .unwrap()
+            .apply(&mut savable_transaction, Some(&account));
+        self.recognize_payee(&mut savable_transaction)?;
+
+        // 5. The category, or the categories of the splits, must exist and match the transaction type
+        self.validate_categories(&savable_transaction)?;
+
+        // 6. Create a new transaction with the account as value
 
         {
             // BLOCK: Scope for the account update and savable transaction of account related fields.
-            // Set the opening balance to the current balance of the account
-            savable_transaction.set_opening_balance(account.balance().clone().into());
+            // Set the opening balance to the balance of the account at the date of the transaction,
+            // its current balance unless the transaction is backdated
+            let opening_balance = match later_transactions.first() {
+                Some(later) => later.opening_balance().clone(),
+                None => Some(account.balance().clone()),
+            };
+            savable_transaction.set_opening_balance(opening_balance.clone());
 
             // Update the respective account with the new transaction
-            let update_account_result = match transaction.transaction_type() {
-                TransactionType::Expense => {
-                    _account_service.withdraw(&account_id, transaction.amount())
-                }
-                TransactionType::Income => {
-                    _account_service.deposit(&account_id, transaction.amount())
-                }
+            let update_account_result = if change < 0.0 {
+                _account_service.withdraw(&account_id, &Amount::new(-change).unwrap())
+            } else {
+                _account_service.deposit(&account_id, &Amount::new(change).unwrap())
             };
 
             // Handle the result of the update
             let new_account = match update_account_result {
                 Ok(account) => account,
-                Err(_) => {
+                Err(UpdateError::InsufficientFunds) => return Err(CreateError::InsufficientFunds),
+                Err(UpdateError::EntityIdNotFound) => {
                     return Err(CreateError::InvalidAccountRef {
                         account_id: account_id.clone().into(),
                     })
                 }
+                // A deposit or a withdrawal leaves the identifiers of the account unchanged.
+                Err(UpdateError::DuplicateIdentifier(_)) => unreachable!(),
             };
 
-            // Update the closing balance of the transaction to the new balance of the account
-            savable_transaction.set_closing_balance(new_account.balance().clone().into());
+            // Update the closing balance of the transaction: the new balance of the account,
+            // or the balance it had right after the transaction if it is backdated
+            let closing_balance = match (later_transactions.first(), opening_balance) {
+                (Some(_), Some(opening_balance)) => {
+                    Amount::new(opening_balance.value() + balance_effect(&transaction)).ok()
+                }
+                _ => Some(new_account.balance().clone()),
+            };
+            savable_transaction.set_closing_balance(closing_balance);
 
             // Set the account to the new account
             savable_transaction.set_account(Value(new_account));
         }
+        drop(_account_service);
 
-        Ok(self
+        let transaction_id = self
             .transaction_repository
             .lock()
             .unwrap()
-            .create(savable_transaction))
+            .create(savable_transaction);
+
+        // 7. The later transactions open and close with the amount of the backdated one added or taken
+        if let Some(later) = later_transactions.first() {
+            self.recompute_balances(&account_id, Some(later.date()));
+        }
+
+        Ok(transaction_id)
+    }
+
+    /// Creates an account holding money already, like a bank account onboarded with its balance.
+    /// The opening balance is recorded as a confirmed income dated at the opening (now by default),
+    /// so the history of the account adds up to its balance. Nothing is recorded without an opening balance.
+    ///
+    /// # Errors
+    /// * `OpenAccountError::Invalid` -- If the account can not be created.
+    /// * `OpenAccountError::OpeningDateInFuture` -- If the account is opened after now.
+    /// * `OpenAccountError::OpeningBalanceNotRecorded` -- If a rule made the opening balance invalid,
+    ///   the account is not kept.
+    pub fn open_account(
+        &mut self,
+        account: Account,
+        opening_balance: Option<Amount>,
+        opened_at: Option<DateTime<Utc>>,
+    ) -> Result<AccountId, OpenAccountError> {
+        let opened_at = opened_at.unwrap_or_else(Utc::now);
+        if opened_at > Utc::now() {
+            return Err(OpenAccountError::OpeningDateInFuture);
+        }
+
+        let currency = account.currency().clone();
+        let account_id = self.account_service.lock().unwrap().create(account)?;
+
+        let Some(opening_balance) = opening_balance.filter(|amount| amount > &MIN_AMOUNT) else {
+            return Ok(account_id);
+        };
+        let transaction = Transaction::new(
+            None,
+            AccountRef::Id(account_id.clone()),
+            TransactionType::Income,
+            opening_balance,
+            MIN_AMOUNT,
+            None,
+            None,
+            currency,
+            TransactionStatus::Confirmed,
+            opened_at,
+            Some(String::from(OPENING_BALANCE_DESCRIPTION)),
+            None,
+            None,
+        );
+        if let Err(error) = self.create(transaction) {
+            // The account is opened with its balance or not at all, it has no transactions yet.
+            self.account_service
+                .lock()
+                .unwrap()
+                .delete(&account_id)
+                .expect("The account was just created");
+            return Err(OpenAccountError::OpeningBalanceNotRecorded(error));
+        }
+
+        Ok(account_id)
     }
 
-    pub fn find_by_id<'a>(
+    /// Closes an account, its remaining balance is first swept into the `sweep_to` account if given:
+    /// the transfer is recorded as an expense of the closed account and an income of the other one.
+    ///
+    /// # Errors
+    /// * `CloseAccountError::Status` -- If the account does not exist, is already closed, the reason is blank,
+    ///   or the account still holds money which is not swept.
+    /// * `CloseAccountError::InvalidSweepAccount` -- If the account swept into does not exist, is the closed one, or is closed.
+    /// * `CloseAccountError::SweepCurrencyMismatch` -- If the account swept into holds another currency.
+    /// * `CloseAccountError::SweepFailed` -- If the transfer is rejected, like out of a frozen account.
+    pub fn close_account(
+        &mut self,
+        account_id: &AccountId,
+        reason: &str,
+        sweep_to: Option<&AccountId>,
+    ) -> Result<Account, CloseAccountError> {
+        let (account, target) = {
+            let account_service = self.account_service.lock().unwrap();
+            let account = account_service
+                .find_by_id_or_fail(account_id)
+                .map_err(|_| StatusError::EntityIdNotFound)?;
+            validate_status_change(&account, AccountStatus::Closed, reason)?;
+
+            let target = match sweep_to {
+                Some(target_id) if target_id != account_id => account_service
+                    .find_by_id(target_id.clone())
+                    .filter(|target| target.status() != AccountStatus::Closed)
+                    .ok_or(CloseAccountError::InvalidSweepAccount)?,
+                Some(_) => return Err(CloseAccountError::InvalidSweepAccount),
+                None => {
+                    return Ok(account_service.change_status(
+                        account_id,
+                        AccountStatus::Closed,
+                        reason,
+                    )?)
+                }
+            };
+            if target.currency() != account.currency() {
+                return Err(CloseAccountError::SweepCurrencyMismatch);
+            }
+
+            (account, target)
+        };
+
+        if account.balance() > &MIN_AMOUNT {
+            let now = Utc::now();
+            let withdrawal = transfer(
+                &account,
+                TransactionType::Expense,
+                account.balance(),
+                format!("Transfer to {}", target.name()),
+                now,
+            );
+            let deposit = transfer(
+                &target,
+                TransactionType::Income,
+                account.balance(),
+                format!("Transfer from {}", account.name()),
+                now,
+            );
+            self.create(withdrawal)
+                .map_err(CloseAccountError::SweepFailed)?;
+            self.create(deposit)
+                .map_err(CloseAccountError::SweepFailed)?;
+        }
+
+        Ok(self.account_service.lock().unwrap().change_status(
+            account_id,
+            AccountStatus::Closed,
+            reason,
+        )?)
+    }
+
+    /// The transactions of an account, the oldest first, since the date (included) if given.
+    fn account_history(
         &self,
-        repository: &'a dyn TransactionRepository,
-        id: EntityId,
-    ) -> Option<&'a Transaction> {
-        repository.find_by_id(id)
+        account_id: &AccountId,
+        since: Option<DateTime<Utc>>,
+    ) -> Vec<Transaction> {
+        self.stream(TransactionQuery {
+            account_id: Some(account_id.clone()),
+            from: since,
+            sort: TransactionSort {
+                field: TransactionSortField::Date,
+                direction: SortDirection::Asc,
+            },
+            ..TransactionQuery::default()
+        })
+        .expect("The query has no range to validate")
+        .collect()
     }
 
-    /// Finds a transaction by its ID or returns an error if not found
-    pub fn find_by_id_or_fail<'a>(
+    /// Recomputes the opening and closing balances of the transactions of an account, in the order of their dates,
+    /// all of them or the ones `since` the date (included). The balances chain up to the current balance of the account:
+    /// the oldest transaction recomputed opens at the balance the account had before, which is zero for the oldest
+    /// transaction of a history accounting for the whole balance.
+    /// # Returns
+    /// * `Vec<TransactionId>` — The transactions whose balances changed.
+    pub fn recompute_balances(
         &self,
-        repository: &'a dyn TransactionRepository,
-        id: EntityId,
-    ) -> Result<&'a Transaction, GetOneError> {
-        match repository.find_by_id(id.clone()) {
+        account_id: &AccountId,
+        since: Option<DateTime<Utc>>,
+    ) -> Vec<TransactionId> {
+        let Some(account) = self
+            .account_service
+            .lock()
+            .unwrap()
+            .find_by_id(account_id.clone())
+        else {
+            return vec![];
+        };
+        let transactions = self.account_history(account_id, since);
+
+        let net: f32 = transactions.iter().map(balance_effect).sum();
+        let mut balance = (account.balance().value() - net).max(MIN_AMOUNT.value());
+        let mut repository = self.transaction_repository.lock().unwrap();
+        let mut changed: Vec<TransactionId> = vec![];
+        for mut transaction in transactions {
+            let opening_balance = Amount::new(balance).ok();
+            balance = (balance + balance_effect(&transaction)).max(MIN_AMOUNT.value());
+            let closing_balance = Amount::new(balance).ok();
+            if transaction.opening_balance() == &opening_balance
+                && transaction.closing_balance() == &closing_balance
+            {
+                continue;
+            }
+
+            transaction.set_opening_balance(opening_balance);
+            transaction.set_closing_balance(closing_balance);
+            let id = transaction.id().clone().unwrap();
+            repository
+                .find_by_id_and_update(id.clone(), transaction)
+                .expect("The transaction was just found");
+            changed.push(id);
+        }
+
+        changed
+    }
+
+    /// Moves the transactions of the `source` account to the `target` account, along with the balance they add up to.
+    /// The balances of the transactions are then recomputed, both histories being merged by date.
+    /// # Returns
+    /// * `Vec<TransactionId>` — The transactions moved.
+    /// # Panics
+    /// If one of the accounts does not exist, or the merged balance is over the maximum amount: the caller checks both.
+    pub fn move_transactions(
+        &mut self,
+        source: &AccountId,
+        target: &AccountId,
+    ) -> Vec<TransactionId> {
+        let transactions = self.account_history(source, None);
+        let mut moved: Vec<TransactionId> = vec![];
+        {
+            let mut repository = self.transaction_repository.lock().unwrap();
+            for mut transaction in transactions {
+                let id = transaction.id().clone().unwrap();
+                transaction.set_account(AccountRef::Id(target.clone()));
+                repository
+                    .find_by_id_and_update(id.clone(), transaction)
+                    .expect("The transaction was just found");
+                moved.push(id);
+            }
+        }
+
+        {
+            let account_service = self.account_service.lock().unwrap();
+            let balance = account_service
+                .find_by_id_or_fail(source)
+                .expect("The source account exists")
+                .balance()
+                .clone();
+            account_service.withdraw(source, &balance).unwrap();
+            account_service.deposit(target, &balance).unwrap();
+        }
+        self.recompute_balances(target, None);
+
+        moved
+    }
+
+    /// Checks that the balance of the account never goes below zero once the transaction `removed` is taken out
+    /// of its history, and the transaction `added` (if any) is put in at its date.
+    fn covers_history(
+        &self,
+        account: &Account,
+        removed: &TransactionId,
+        added: Option<&Transaction>,
+    ) -> bool {
+        let history = self.account_history(account.id().unwrap(), None);
+        let net: f32 = history.iter().map(balance_effect).sum();
+        let mut balance = account.balance().value() - net;
+
+        let mut history: Vec<&Transaction> = history
+            .iter()
+            .filter(|transaction| transaction.id().as_ref() != Some(removed))
+            .collect();
+        if let Some(added) = added {
+            let position =
+                history.partition_point(|transaction| transaction.date() <= added.date());
+            history.insert(position, added);
+        }
+
+        history.into_iter().all(|transaction| {
+            balance += balance_effect(transaction);
+            // The balances are compared to the cent, the float sum is not exact.
+            (balance * 100.0).round() >= 0.0
+        })
+    }
+
+    /// Keeps the version of the transaction replaced by an edit, or removed by a deletion.
+    fn add_revision(&self, transaction: &Transaction, kind: RevisionKind) {
+        self.transaction_repository
+            .lock()
+            .unwrap()
+            .add_revision(TransactionRevision {
+                transaction_id: transaction.id().clone().unwrap(),
+                kind,
+                revised_at: Utc::now(),
+                previous: TransactionVersion::from(transaction),
+            });
+    }
+
+    /// Edits the amount, fee, date, type, account or description of a transaction.
+    /// The effect of the previous version is taken off the balance of its account and the effect of the new one
+    /// is applied, then the balances of the later transactions of both accounts are recomputed.
+    /// The previous version is kept in the revisions of the transaction.
+    ///
+    /// # Errors
+    /// * `EditError::NotFound` -- If the transaction does not exist.
+    /// * `EditError::CurrencyMismatch` -- If the transaction is moved to an account holding another currency.
+    /// * `EditError::Invalid` -- If an account is closed, a frozen one would lose money, an account would not
+    ///   have enough funds at some point of its history, or the new version is not valid.
+    pub fn update(
+        &mut self,
+        id: TransactionId,
+        edit: TransactionEdit,
+    ) -> Result<Transaction, EditError> {
+        let original = self
+            .find_by_id(id.clone())
+            .ok_or_else(|| EditError::NotFound(id.clone()))?;
+        // The reversal compensates the exact effect of the reversed transaction
+        if is_reversal_pair(&original) {
+            return Err(EditError::Reversed);
+        }
+
+        let mut edited = original.clone();
+        let original_account_id = original.account_id().unwrap().clone();
+        let account_id = edit.account_id.unwrap_or(original_account_id.clone());
+        edited.set_account(AccountRef::Id(account_id.clone()));
+        if let Some(transaction_type) = edit.transaction_type {
+            edited.set_transaction_type(transaction_type);
+        }
+        if let Some(amount) = edit.amount {
+            edited.set_amount(amount);
+        }
+        if let Some(fee) = edit.fee {
+            edited.set_fee(fee);
+        }
+        if let Some(date) = edit.date {
+            edited.set_date(date);
+        }
+        if let Some(description) = edit.description {
+            edited.set_description(Some(description));
+        }
+        if TransactionVersion::from(&edited) == TransactionVersion::from(&original) {
+            return Ok(original);
+        }
+
+        let account_service = self.account_service.lock().unwrap();
+        let find_account = |account_id: &AccountId| {
+            account_service.find_by_id_or_fail(account_id).map_err(|_| {
+                CreateError::InvalidAccountRef {
+                    account_id: Some(account_id.clone()),
+                }
+            })
+        };
+        let original_account = find_account(&original_account_id)?;
+        let account = find_account(&account_id)?;
+        // The amount is in the currency of the account, it is not converted
+        if account.currency() != original_account.currency() {
+            return Err(EditError::CurrencyMismatch);
+        }
+
+        if !account.account_type().charges_fees() && edited.fee().value() > 0.0 {
+            return Err(CreateError::FeeNotAllowed.into());
+        }
+        self.validate_categories(&edited)?;
+
+        // The balance of each account changes by the difference between the effects of both versions
+        let changes: Vec<(&Account, f32, Option<&Transaction>)> =
+            if account_id == original_account_id {
+                vec![(
+                    &account,
+                    balance_effect(&edited) - balance_effect(&original),
+                    Some(&edited),
+                )]
+            } else {
+                vec![
+                    (&original_account, -balance_effect(&original), None),
+                    (&account, balance_effect(&edited), Some(&edited)),
+                ]
+            };
+        for (account, change, added) in &changes {
+            match account.status() {
+                AccountStatus::Closed => return Err(CreateError::AccountClosed.into()),
+                AccountStatus::Frozen if *change < 0.0 => {
+                    return Err(CreateError::AccountFrozen.into())
+                }
+                _ => {}
+            }
+            if !self.covers_history(account, &id, *added) {
+                return Err(CreateError::InsufficientFunds.into());
+            }
+        }
+        for (account, change, _) in &changes {
+            change_balance(&account_service, account.id().unwrap(), *change)?;
+        }
+        drop(account_service);
+
+        self.add_revision(&original, RevisionKind::Updated);
+        self.transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id.clone(), edited.clone())
+            .expect("The transaction was just found");
+
+        // The balances change from the earliest of both dates
+        if account_id == original_account_id {
+            self.recompute_balances(&account_id, Some(original.date().min(edited.date())));
+        } else {
+            self.recompute_balances(&original_account_id, Some(original.date()));
+            self.recompute_balances(&account_id, Some(edited.date()));
+        }
+
+        Ok(self
+            .find_by_id(id)
+            .expect("The transaction was just updated"))
+    }
+
+    /// Deletes a transaction: its effect is taken off the balance of its account, and the balances of the later
+    /// transactions of the account are recomputed. The deleted version is kept in the revisions of the transaction.
+    ///
+    /// # Errors
+    /// * `EditError::NotFound` -- If the transaction does not exist.
+    /// * `EditError::Invalid` -- If the account is closed, frozen while losing money, or would not have enough
+    ///   funds at some point of its history.
+    pub fn delete(&mut self, id: TransactionId) -> Result<(), EditError> {
+        let transaction = self
+            .find_by_id(id.clone())
+            .ok_or_else(|| EditError::NotFound(id.clone()))?;
+        if is_reversal_pair(&transaction) {
+            return Err(EditError::Reversed);
+        }
+        let account_id = transaction.account_id().unwrap().clone();
+
+        let account_service = self.account_service.lock().unwrap();
+        let account = account_service
+            .find_by_id_or_fail(&account_id)
+            .map_err(|_| CreateError::InvalidAccountRef {
+                account_id: Some(account_id.clone()),
+            })?;
+        let change = -balance_effect(&transaction);
+        match account.status() {
+            AccountStatus::Closed => return Err(CreateError::AccountClosed.into()),
+            AccountStatus::Frozen if change < 0.0 => return Err(CreateError::AccountFrozen.into()),
+            _ => {}
+        }
+        if !self.covers_history(&account, &id, None) {
+            return Err(CreateError::InsufficientFunds.into());
+        }
+        change_balance(&account_service, &account_id, change)?;
+        drop(account_service);
+
+        self.add_revision(&transaction, RevisionKind::Deleted);
+        self.transaction_repository
+            .lock()
+            .unwrap()
+            .delete(id)
+            .expect("The transaction was just found");
+        self.recompute_balances(&account_id, Some(transaction.date()));
+
+        Ok(())
+    }
+
+    /// Reverses a transaction: it is marked `RolledBack`, and a compensating transaction of the opposite type,
+    /// dated now, gives back its effect on the balance of the account. The compensating transaction references
+    /// the reversed one, keeps its payee and tags, and is charged the given fee: the fee is taken from the balance
+    /// on top of the compensation (the fee of the reversed transaction was charged once and for all).
+    ///
+    /// # Returns
+    /// * `Transaction` -- The compensating transaction.
+    /// # Errors
+    /// * `ReverseError::NotFound` -- If the transaction does not exist.
+    /// * `ReverseError::AlreadyReversed` -- If the transaction was reversed already.
+    /// * `ReverseError::TransactionFailed` -- If the transaction failed.
+    /// * `ReverseError::ReversalEntry` -- If the transaction is a reversal.
+    /// * `ReverseError::Invalid` -- If the compensating transaction can not be created (closed or frozen account,
+    ///   insufficient funds, fee not allowed).
+    pub fn reverse(&mut self, id: TransactionId, fee: Amount) -> Result<Transaction, ReverseError> {
+        let original = self
+            .find_by_id(id.clone())
+            .ok_or_else(|| ReverseError::NotFound(id.clone()))?;
+        match original.status() {
+            TransactionStatus::RolledBack => return Err(ReverseError::AlreadyReversed),
+            TransactionStatus::Failed => return Err(ReverseError::TransactionFailed),
+            _ => {}
+        }
+        if original.reverses().is_some() {
+            return Err(ReverseError::ReversalEntry);
+        }
+
+        let transaction_type = match original.transaction_type() {
+            TransactionType::Expense => TransactionType::Income,
+            TransactionType::Income => TransactionType::Expense,
+        };
+        let description = match original.description() {
+            Some(description) => format!("Reversal of {description}"),
+            None => String::from("Reversal"),
+        };
+        let mut reversal = Transaction::new(
+            None,
+            AccountRef::Id(original.account_id().unwrap().clone()),
+            transaction_type,
+            original.amount().clone(),
+            fee,
+            None,
+            None,
+            original.currency().clone(),
+            TransactionStatus::Confirmed,
+            Utc::now(),
+            Some(description),
+            original.reference_number().clone(),
+            None,
+        );
+        reversal.set_payee_id(original.payee_id().cloned());
+        reversal.set_tags(original.tags().clone());
+        reversal.set_reverses(Some(id.clone()));
+        let reversal_id = self.create(reversal)?;
+
+        // Read again, the creation may have recomputed its balances
+        let mut original = self.find_by_id(id.clone()).unwrap();
+        original.set_status(TransactionStatus::RolledBack);
+        self.transaction_repository
+            .lock()
+            .unwrap()
+            .find_by_id_and_update(id, original)
+            .expect("The transaction was just found");
+
+        Ok(self
+            .find_by_id(reversal_id)
+            .expect("The reversal was just created"))
+    }
+
+    /// The previous versions of a transaction, the oldest first, kept when it was edited or deleted.
+    pub fn find_revisions(&self, id: &TransactionId) -> Vec<TransactionRevision> {
+        self.transaction_repository
+            .lock()
+            .unwrap()
+            .find_revisions(id)
+    }
+
+    pub fn find_by_id(&self, id: TransactionId) -> Option<Transaction> {
+        self.transaction_repository.lock().unwrap().find_by_id(id)
+    }
+
+    /// Finds a transaction by its ID or returns an error if not found
+    pub fn find_by_id_or_fail(&self, id: TransactionId) -> Result<Transaction, GetOneError> {
+        match self.find_by_id(id.clone()) {
             Some(transaction) => Ok(transaction),
             None => Err(GetOneError::NotFound(id)),
         }
@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+/// The Rwandan country calling code.
+const RWANDA_CALLING_CODE: &str = "250";
+
+/// The prefixes of the Rwandan mobile numbers, after the country code.
+/// 72 and 73 are Airtel's, 78 and 79 are MTN's.
+const RWANDAN_MOBILE_PREFIXES: [&str; 4] = ["72", "73", "78", "79"];
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum IdentifierError {
+    #[error("invalid IBAN: {0}, must be a country code, two check digits and up to 30 letters or digits")]
+    Iban(String),
+
+    #[error("invalid IBAN: {0}, its check digits do not match")]
+    IbanChecksum(String),
+
+    #[error("invalid bank account number: {0}, must be 6 to 20 digits")]
+    BankAccountNumber(String),
+
+    #[error("invalid phone number: {0}, must be in the E.164 format (Eg: +250788123456)")]
+    Msisdn(String),
+
+    #[error(
+        "invalid phone number: {0}, a Rwandan mobile number starts with +250 72, 73, 78 or 79"
+    )]
+    RwandanMsisdn(String),
+
+    #[error("invalid card number: {0}, must be the last 4 digits of the card")]
+    CardLastFour(String),
+
+    #[error("unknown identifier kind: {0}")]
+    UnknownKind(String),
+}
+
+/// What an account identifier identifies the account by.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierKind {
+    /// An international bank account number. (Eg: "GB82 WEST 1234 5698 7654 32")
+    Iban,
+
+    /// The number of the account at its bank.
+    BankAccountNumber,
+
+    /// The phone number of a mobile money wallet, in the E.164 format. (Eg: "+250788123456")
+    Msisdn,
+
+    /// The last 4 digits of a card.
+    CardLastFour,
+}
+
+impl IdentifierKind {
+    /// The name of the kind, as it is serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierKind::Iban => "iban",
+            IdentifierKind::BankAccountNumber => "bank_account_number",
+            IdentifierKind::Msisdn => "msisdn",
+            IdentifierKind::CardLastFour => "card_last_four",
+        }
+    }
+
+    /// Whether an identifier of the kind tells a single account apart.
+    /// The last 4 digits of a card are not: the cards of different banks can end with the same digits.
+    pub fn is_unique(&self) -> bool {
+        *self != IdentifierKind::CardLastFour
+    }
+}
+
+impl TryFrom<&str> for IdentifierKind {
+    type Error = IdentifierError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "iban" => Ok(IdentifierKind::Iban),
+            "bank_account_number" => Ok(IdentifierKind::BankAccountNumber),
+            "msisdn" => Ok(IdentifierKind::Msisdn),
+            "card_last_four" => Ok(IdentifierKind::CardLastFour),
+            _ => Err(IdentifierError::UnknownKind(value.to_string())),
+        }
+    }
+}
+
+/// A number identifying an account outside of the application, the imported transactions are matched by it.
+/// The value is normalized when the identifier is built, so the identifiers of the same account are equal.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "RawAccountIdentifier")]
+pub struct AccountIdentifier {
+    kind: IdentifierKind,
+
+    /// The normalized value. (Eg: "+250788123456" for "0788 123 456")
+    value: String,
+}
+
+/// An identifier as received, before it is validated.
+#[derive(Deserialize)]
+struct RawAccountIdentifier {
+    kind: IdentifierKind,
+    value: String,
+}
+
+impl TryFrom<RawAccountIdentifier> for AccountIdentifier {
+    type Error = IdentifierError;
+
+    fn try_from(raw: RawAccountIdentifier) -> Result<Self, Self::Error> {
+        AccountIdentifier::new(raw.kind, &raw.value)
+    }
+}
+
+impl Display for AccountIdentifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl AccountIdentifier {
+    /// Validates the value of an identifier of the kind, and normalizes it.
+    /// The spaces are ignored, as well as the dashes of the bank account numbers and phone numbers.
+    pub fn new(kind: IdentifierKind, value: &str) -> Result<Self, IdentifierError> {
+        let value = match kind {
+            IdentifierKind::Iban => normalize_iban(value)?,
+            IdentifierKind::BankAccountNumber => normalize_bank_account_number(value)?,
+            IdentifierKind::Msisdn => normalize_msisdn(value)?,
+            IdentifierKind::CardLastFour => normalize_card_last_four(value)?,
+        };
+
+        Ok(Self { kind, value })
+    }
+
+    pub fn kind(&self) -> IdentifierKind {
+        self.kind
+    }
+
+    pub fn value(&self) -> &String {
+        &self.value
+    }
+}
+
+/// Removes the spaces, and the other separators given, from a value.
+fn strip(value: &str, separators: &[char]) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !separators.contains(c))
+        .collect()
+}
+
+/// Upper cases the IBAN and checks it against ISO 13616: the mod 97 of its digits,
+/// once the first four characters are moved to the end and the letters are replaced by numbers, is 1.
+fn normalize_iban(value: &str) -> Result<String, IdentifierError> {
+    let iban = strip(value, &[]).to_ascii_uppercase();
+
+    let well_formed = (15..=34).contains(&iban.len())
+        && iban.chars().take(2).all(|c| c.is_ascii_uppercase())
+        && iban.chars().skip(2).take(2).all(|c| c.is_ascii_digit())
+        && iban.chars().all(|c| c.is_ascii_alphanumeric());
+    if !well_formed {
+        return Err(IdentifierError::Iban(value.to_string()));
+    }
+
+    let remainder = iban[4..]
+        .chars()
+        .chain(iban[..4].chars())
+        .fold(0u32, |remainder, c| {
+            // A letter stands for two digits, A being 10 and Z 35.
+            let number = c.to_digit(36).unwrap();
+            let shift = if number < 10 { 10 } else { 100 };
+            (remainder * shift + number) % 97
+        });
+    if remainder != 1 {
+        return Err(IdentifierError::IbanChecksum(value.to_string()));
+    }
+
+    Ok(iban)
+}
+
+fn normalize_bank_account_number(value: &str) -> Result<String, IdentifierError> {
+    let number = strip(value, &['-']);
+    if !(6..=20).contains(&number.len()) || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(IdentifierError::BankAccountNumber(value.to_string()));
+    }
+
+    Ok(number)
+}
+
+/// Puts a phone number in the E.164 format, a `+`, the country code and the subscriber number.
+/// The numbers without a country code are Rwandan ones, dialed locally (Eg: "0788123456")
+/// or without the `+` (Eg: "250788123456").
+fn normalize_msisdn(value: &str) -> Result<String, IdentifierError> {
+    let number = strip(value, &['-', '(', ')']);
+    let digits = if let Some(digits) = number.strip_prefix('+') {
+        digits.to_string()
+    } else if let Some(digits) = number.strip_prefix("00") {
+        digits.to_string()
+    } else if let Some(digits) = number.strip_prefix('0') {
+        format!("{RWANDA_CALLING_CODE}{digits}")
+    } else if number.starts_with(RWANDA_CALLING_CODE) {
+        number
+    } else {
+        return Err(IdentifierError::Msisdn(value.to_string()));
+    };
+
+    let e164 = (8..=15).contains(&digits.len())
+        && !digits.starts_with('0')
+        && digits.chars().all(|c| c.is_ascii_digit());
+    if !e164 {
+        return Err(IdentifierError::Msisdn(value.to_string()));
+    }
+
+    if let Some(subscriber) = digits.strip_prefix(RWANDA_CALLING_CODE) {
+        let mobile = subscriber.len() == 9
+            && RWANDAN_MOBILE_PREFIXES
+                .iter()
+                .any(|prefix| subscriber.starts_with(prefix));
+        if !mobile {
+            return Err(IdentifierError::RwandanMsisdn(value.to_string()));
+        }
+    }
+
+    Ok(format!("+{digits}"))
+}
+
+fn normalize_card_last_four(value: &str) -> Result<String, IdentifierError> {
+    let digits = strip(value, &[]);
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(IdentifierError::CardLastFour(value.to_string()));
+    }
+
+    Ok(digits)
+}
@@ -17,6 +17,7 @@ pub enum AmountError {
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "f32")]
 pub(crate) struct Amount(f32);
 
 impl Amount {
@@ -31,6 +32,11 @@ impl Amount {
 
         Ok(Amount(amount))
     }
+
+    /// The raw value of the amount.
+    pub(crate) fn value(&self) -> f32 {
+        self.0
+    }
 }
 
 // Conversion implementations
@@ -39,7 +45,7 @@ impl TryFrom<f32> for Amount {
     type Error = AmountError;
 
     fn try_from(value: f32) -> Result<Self, Self::Error> {
-        Amount::new(value.into())
+        Amount::new(value)
     }
 }
 
@@ -81,4 +87,5 @@ impl Sub for &Amount {
 pub(crate) const MIN_AMOUNT: Amount = Amount(_MIN_AMOUNT);
 
 /// Max amount that can be stored in the database or processed
+#[allow(dead_code)]
 pub(crate) const MAX_AMOUNT: Amount = Amount(_MAX_AMOUNT);
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 use utoipa::ToSchema;
 
 /// Currency. (Currency used in balances and financial numbers)
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum Currency {
     /// Rwandan franc.
@@ -11,6 +13,7 @@ pub enum Currency {
     USD,
 }
 
+#[allow(dead_code)]
 #[derive(Debug)]
 pub enum CurrencyParseError {
     InvalidCurrencyString,
@@ -25,11 +28,11 @@ impl TryFrom<&str> for Currency {
     }
 }
 
-impl Currency {
-    pub fn to_string(&self) -> String {
+impl Display for Currency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Currency::RWF => "RWF".to_string(),
-            Currency::USD => "USD".to_string(),
+            Currency::RWF => f.write_str("RWF"),
+            Currency::USD => f.write_str("USD"),
         }
     }
 }
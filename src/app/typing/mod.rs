@@ -1,3 +1,7 @@
+pub(crate) mod account_identifier;
 pub(crate) mod amount;
 pub(crate) mod currency;
+pub(crate) mod point_in_time;
+mod test_account_identifier;
 mod test_amount;
+mod test_point_in_time;
@@ -0,0 +1,84 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The formats of a local date and time, the seconds being optional.
+const LOCAL_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum PointInTimeError {
+    #[error("invalid point in time: {0}, must be an RFC 3339 date (Eg: 2025-12-31T23:59:00+02:00) or a date and time followed by a time zone (Eg: 2025-12-31 23:59 Africa/Kigali)")]
+    Format(String),
+
+    #[error("unknown time zone: {0}")]
+    TimeZone(String),
+
+    #[error("{0} does not exist in its time zone, the clocks skip it")]
+    SkippedTime(String),
+}
+
+/// A point in time as written by a person: an RFC 3339 date (Eg: "2025-12-31T23:59:00+02:00"),
+/// or a local date and time followed by the name of its time zone (Eg: "2025-12-31 23:59 Africa/Kigali").
+/// A local date and time without a time zone is in UTC.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+pub struct PointInTime(DateTime<Utc>);
+
+impl PointInTime {
+    pub fn value(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl FromStr for PointInTime {
+    type Err = PointInTimeError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+            return Ok(PointInTime(date.with_timezone(&Utc)));
+        }
+
+        // The time zone is the last word, when it is not part of the time
+        let (local, time_zone) = match text.rsplit_once(' ') {
+            Some((local, time_zone)) if time_zone.contains('/') || time_zone == "UTC" => {
+                (local.trim(), Some(time_zone))
+            }
+            _ => (text, None),
+        };
+        let local = LOCAL_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(local, format).ok())
+            .ok_or_else(|| PointInTimeError::Format(text.to_string()))?;
+
+        let time_zone: Tz = match time_zone {
+            Some(time_zone) => time_zone
+                .parse()
+                .map_err(|_| PointInTimeError::TimeZone(time_zone.to_string()))?,
+            None => Tz::UTC,
+        };
+        // A time repeated when the clocks go back is taken the first time
+        time_zone
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|date| PointInTime(date.with_timezone(&Utc)))
+            .ok_or_else(|| PointInTimeError::SkippedTime(text.to_string()))
+    }
+}
+
+impl TryFrom<String> for PointInTime {
+    type Error = PointInTimeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for PointInTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_rfc3339())
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod test_account_identifier_type {
+    use crate::app::typing::account_identifier::{
+        AccountIdentifier, IdentifierError, IdentifierKind,
+    };
+
+    #[test]
+    fn test_iban() {
+        // GIVEN an IBAN written in groups, in lower case
+        // WHEN the identifier is built
+        let identifier =
+            AccountIdentifier::new(IdentifierKind::Iban, "gb82 west 1234 5698 7654 32");
+
+        // THEN it is kept without the spaces, in upper case
+        assert_eq!(identifier.unwrap().value(), "GB82WEST12345698765432");
+
+        // WHEN a digit of the IBAN is wrong
+        let identifier = AccountIdentifier::new(IdentifierKind::Iban, "GB82WEST12345698765433");
+
+        // THEN its check digits do not match
+        assert_eq!(
+            identifier,
+            Err(IdentifierError::IbanChecksum(String::from(
+                "GB82WEST12345698765433"
+            )))
+        );
+
+        // AND a value without a country code is not an IBAN
+        assert_eq!(
+            AccountIdentifier::new(IdentifierKind::Iban, "1234WEST12345698765432"),
+            Err(IdentifierError::Iban(String::from(
+                "1234WEST12345698765432"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_msisdn() {
+        // GIVEN the ways a Rwandan mobile number is written
+        // WHEN the identifiers are built
+        // THEN they are all put in the E.164 format
+        for number in [
+            "0788 123 456",
+            "250788123456",
+            "+250 788-123-456",
+            "00250788123456",
+        ] {
+            let identifier = AccountIdentifier::new(IdentifierKind::Msisdn, number).unwrap();
+            assert_eq!(identifier.value(), "+250788123456");
+        }
+        assert_eq!(
+            AccountIdentifier::new(IdentifierKind::Msisdn, "0731234567")
+                .unwrap()
+                .value(),
+            "+250731234567"
+        );
+
+        // AND the numbers of the other countries are kept as they are
+        assert_eq!(
+            AccountIdentifier::new(IdentifierKind::Msisdn, "+254 712 345678")
+                .unwrap()
+                .value(),
+            "+254712345678"
+        );
+
+        // WHEN a Rwandan number is not a mobile one, or is too short
+        // THEN it is rejected
+        assert_eq!(
+            AccountIdentifier::new(IdentifierKind::Msisdn, "0252123456"),
+            Err(IdentifierError::RwandanMsisdn(String::from("0252123456")))
+        );
+        assert_eq!(
+            AccountIdentifier::new(IdentifierKind::Msisdn, "078812345"),
+            Err(IdentifierError::RwandanMsisdn(String::from("078812345")))
+        );
+
+        // AND a number without a country code is rejected
+        assert_eq!(
+            AccountIdentifier::new(IdentifierKind::Msisdn, "788123456"),
+            Err(IdentifierError::Msisdn(String::from("788123456")))
+        );
+    }
+
+    #[test]
+    fn test_bank_account_number_and_card() {
+        // GIVEN a bank account number written with dashes
+        // WHEN the identifier is built
+        let identifier =
+            AccountIdentifier::new(IdentifierKind::BankAccountNumber, "00040-0069876-54");
+
+        // THEN only its digits are kept
+        assert_eq!(identifier.unwrap().value(), "00040006987654");
+
+        // AND the numbers with letters, or too short, are rejected
+        assert!(AccountIdentifier::new(IdentifierKind::BankAccountNumber, "BK-12345").is_err());
+        assert!(AccountIdentifier::new(IdentifierKind::BankAccountNumber, "12345").is_err());
+
+        // AND a card is identified by exactly 4 digits
+        assert!(AccountIdentifier::new(IdentifierKind::CardLastFour, "4242").is_ok());
+        assert_eq!(
+            AccountIdentifier::new(IdentifierKind::CardLastFour, "42424"),
+            Err(IdentifierError::CardLastFour(String::from("42424")))
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        // GIVEN identifiers received as JSON
+        // WHEN they are deserialized
+        let identifier: AccountIdentifier =
+            serde_json::from_str(r#"{"kind": "msisdn", "value": "0788123456"}"#).unwrap();
+        let invalid = serde_json::from_str::<AccountIdentifier>(
+            r#"{"kind": "card_last_four", "value": "42"}"#,
+        );
+
+        // THEN they are validated and normalized like the built ones
+        assert_eq!(
+            identifier,
+            AccountIdentifier::new(IdentifierKind::Msisdn, "+250788123456").unwrap()
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_kind_names() {
+        // GIVEN the names of the kinds, as they are stored
+        // WHEN they are read back
+        // THEN each name gives its kind
+        for kind in [
+            IdentifierKind::Iban,
+            IdentifierKind::BankAccountNumber,
+            IdentifierKind::Msisdn,
+            IdentifierKind::CardLastFour,
+        ] {
+            assert_eq!(IdentifierKind::try_from(kind.as_str()), Ok(kind));
+        }
+
+        // AND an unknown name is rejected, instead of being taken for another kind
+        assert_eq!(
+            IdentifierKind::try_from("card"),
+            Err(IdentifierError::UnknownKind(String::from("card")))
+        );
+
+        // AND only the last 4 digits of a card can be shared by several accounts
+        assert!(IdentifierKind::Iban.is_unique());
+        assert!(!IdentifierKind::CardLastFour.is_unique());
+    }
+}
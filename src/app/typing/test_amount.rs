@@ -1,5 +1,5 @@
 #[cfg(test)]
-mod test_amount {
+mod test_amount_type {
     use crate::app::typing::amount::{Amount, AmountError, _MAX_AMOUNT, _MIN_AMOUNT};
 
     #[test]
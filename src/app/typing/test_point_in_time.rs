@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod test_point_in_time_type {
+    use crate::app::typing::point_in_time::{PointInTime, PointInTimeError};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_parse_point_in_time() {
+        // GIVEN the end of 2025 in Kigali, two hours ahead of UTC
+        let expected = Utc.with_ymd_and_hms(2025, 12, 31, 21, 59, 0).unwrap();
+
+        // WHEN it is written with the name of the time zone, or as an RFC 3339 date
+        // THEN both are the same point in time
+        assert_eq!(
+            "2025-12-31 23:59 Africa/Kigali"
+                .parse::<PointInTime>()
+                .unwrap()
+                .value(),
+            expected
+        );
+        assert_eq!(
+            "2025-12-31T23:59:00+02:00"
+                .parse::<PointInTime>()
+                .unwrap()
+                .value(),
+            expected
+        );
+
+        // AND a local date and time without a time zone is in UTC
+        assert_eq!(
+            "2025-12-31 21:59:00"
+                .parse::<PointInTime>()
+                .unwrap()
+                .value(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_point_in_time() {
+        // GIVEN points in time which can not be read
+        // WHEN they are parsed
+        // THEN the reason is given
+        assert_eq!(
+            "31/12/2025 23:59".parse::<PointInTime>(),
+            Err(PointInTimeError::Format(String::from("31/12/2025 23:59")))
+        );
+        assert_eq!(
+            "2025-12-31 23:59 Africa/Atlantis".parse::<PointInTime>(),
+            Err(PointInTimeError::TimeZone(String::from("Africa/Atlantis")))
+        );
+
+        // AND a time skipped when the clocks go forward does not exist
+        assert_eq!(
+            "2026-03-29 02:30 Europe/Paris".parse::<PointInTime>(),
+            Err(PointInTimeError::SkippedTime(String::from(
+                "2026-03-29 02:30 Europe/Paris"
+            )))
+        );
+    }
+}
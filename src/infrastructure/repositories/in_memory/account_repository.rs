@@ -1,64 +1,127 @@
-use crate::app::entities::account::Account;
-use crate::app::entities::common::EntityId;
+use crate::app::entities::account::{Account, AccountId};
+use crate::app::entities::tag::{replace_tag, TagId};
 use crate::app::repositories::account_repository::{
-    AccountRepository, CreateError, FindByIdAndUpdateError,
+    AccountRepository, CreateError, DeleteError, FindByIdAndUpdateError,
 };
+use crate::app::repositories::common::Page;
+use crate::app::typing::account_identifier::AccountIdentifier;
+use std::collections::HashMap;
 
 pub struct InMemoryAccountRepository {
-    next_id: usize,
-    accounts: Vec<Account>,
+    accounts: HashMap<AccountId, Account>,
 }
 
 impl InMemoryAccountRepository {
     pub fn new() -> Self {
         Self {
-            next_id: 0,
-            accounts: vec![],
+            accounts: HashMap::new(),
         }
     }
 }
 
+impl InMemoryAccountRepository {
+    /// The first unique identifier of the account another account already has.
+    fn find_duplicate_identifier(
+        &self,
+        id: Option<&AccountId>,
+        account: &Account,
+    ) -> Option<AccountIdentifier> {
+        account
+            .identifiers()
+            .iter()
+            .filter(|identifier| identifier.kind().is_unique())
+            .find(|identifier| {
+                self.accounts
+                    .values()
+                    .any(|other| other.id() != id && other.identifiers().contains(identifier))
+            })
+            .cloned()
+    }
+}
+
 impl AccountRepository for InMemoryAccountRepository {
-    fn find_all(&self) -> Vec<Account> {
-        // GET all accounts in the memory
-        self.accounts.clone()
+    fn find_page(&self, after: Option<AccountId>, limit: usize) -> Page<Account> {
+        // GET a page of accounts in the memory
+        // The ids are time ordered, sorting by them returns the accounts in creation order.
+        let mut accounts: Vec<&Account> = self
+            .accounts
+            .values()
+            .filter(|account| {
+                after
+                    .as_ref()
+                    .is_none_or(|after| account.id() > Some(after))
+            })
+            .collect();
+        accounts.sort_by(|left, right| left.id().cmp(&right.id()));
+        accounts.truncate(limit + 1);
+
+        Page::from_overfetched(accounts, limit, |account| account.id().unwrap().to_string())
+            .map(|account| account.clone())
     }
 
-    fn find_by_id(&self, id: EntityId) -> Option<Account> {
+    fn find_by_id(&self, id: AccountId) -> Option<Account> {
         // GET an account by an id in the memory
-        let id: usize = id.0.parse().unwrap();
-        self.accounts.get(id).cloned()
+        self.accounts.get(&id).cloned()
     }
 
-    fn create(&mut self, account: Account) -> Result<EntityId, CreateError> {
+    fn find_by_identifier(&self, identifier: &AccountIdentifier) -> Vec<Account> {
+        let mut accounts: Vec<Account> = self
+            .accounts
+            .values()
+            .filter(|account| account.identifiers().contains(identifier))
+            .cloned()
+            .collect();
+        accounts.sort_by(|left, right| left.id().cmp(&right.id()));
+
+        accounts
+    }
+
+    fn create(&mut self, account: Account) -> Result<AccountId, CreateError> {
         // Add an account to the memory
-        let id = self.next_id;
-        let entity_id = EntityId(id.to_string());
+        if let Some(identifier) = self.find_duplicate_identifier(None, &account) {
+            return Err(CreateError::DuplicateIdentifier(identifier));
+        }
+        let id = AccountId::generate();
 
         let mut new_account = account.clone();
-        new_account.set_id(Some(entity_id));
+        new_account.set_id(Some(id.clone()));
 
-        self.accounts.push(new_account);
-        self.next_id += 1;
-        Ok(EntityId(id.to_string()))
+        self.accounts.insert(id.clone(), new_account);
+        Ok(id)
     }
 
     fn find_by_id_and_update(
         &mut self,
-        id: EntityId,
-        account: Account,
-    ) -> Result<EntityId, FindByIdAndUpdateError> {
+        id: AccountId,
+        mut account: Account,
+    ) -> Result<AccountId, FindByIdAndUpdateError> {
         // Find an account by id and update it in the memory
-        match self.find_by_id(id.clone()) {
-            Some(account) => account,
-            None => return Err(FindByIdAndUpdateError::NotFound),
-        };
-
-        let index: usize = id.0.parse().unwrap();
+        if !self.accounts.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+        if let Some(identifier) = self.find_duplicate_identifier(Some(&id), &account) {
+            return Err(FindByIdAndUpdateError::DuplicateIdentifier(identifier));
+        }
 
-        // Update the self.accounts[index
-        self.accounts[index] = account;
+        account.set_id(Some(id.clone()));
+        self.accounts.insert(id.clone(), account);
 
         Ok(id)
     }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        for account in self.accounts.values_mut() {
+            if account.tags().contains(source) {
+                account.set_tags(replace_tag(account.tags(), source, target));
+            }
+        }
+    }
+
+    fn delete(&mut self, id: AccountId) -> Result<(), DeleteError> {
+        // Remove an account from the memory
+        match self.accounts.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
 }
@@ -0,0 +1,67 @@
+use crate::app::entities::budget::{Budget, BudgetId, BudgetScope};
+use crate::app::entities::tag::TagId;
+use crate::app::repositories::budget_repository::{
+    BudgetRepository, DeleteError, FindByIdAndUpdateError,
+};
+use std::collections::BTreeMap;
+
+pub struct InMemoryBudgetRepository {
+    // The ids are time ordered, the map keeps the budgets in creation order.
+    budgets: BTreeMap<BudgetId, Budget>,
+}
+
+impl InMemoryBudgetRepository {
+    pub fn new() -> Self {
+        Self {
+            budgets: BTreeMap::new(),
+        }
+    }
+}
+
+impl BudgetRepository for InMemoryBudgetRepository {
+    fn find_all(&self) -> Vec<Budget> {
+        self.budgets.values().cloned().collect()
+    }
+
+    fn find_by_id(&self, id: BudgetId) -> Option<Budget> {
+        self.budgets.get(&id).cloned()
+    }
+
+    fn create(&mut self, mut budget: Budget) -> BudgetId {
+        let id = BudgetId::generate();
+        budget.set_id(Some(id.clone()));
+        self.budgets.insert(id.clone(), budget);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: BudgetId,
+        mut budget: Budget,
+    ) -> Result<BudgetId, FindByIdAndUpdateError> {
+        if !self.budgets.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        budget.set_id(Some(id.clone()));
+        self.budgets.insert(id.clone(), budget);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: BudgetId) -> Result<(), DeleteError> {
+        match self.budgets.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        for budget in self.budgets.values_mut() {
+            if budget.scope() == &BudgetScope::Tag(source.clone()) {
+                budget.set_scope(BudgetScope::Tag(target.clone()));
+            }
+        }
+    }
+}
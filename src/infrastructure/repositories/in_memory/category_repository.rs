@@ -0,0 +1,60 @@
+use crate::app::entities::category::{Category, CategoryId};
+use crate::app::repositories::category_repository::{
+    CategoryRepository, DeleteError, FindByIdAndUpdateError,
+};
+use std::collections::HashMap;
+
+pub struct InMemoryCategoryRepository {
+    categories: HashMap<CategoryId, Category>,
+}
+
+impl InMemoryCategoryRepository {
+    pub fn new() -> Self {
+        Self {
+            categories: HashMap::new(),
+        }
+    }
+}
+
+impl CategoryRepository for InMemoryCategoryRepository {
+    fn find_all(&self) -> Vec<Category> {
+        // The ids are time ordered, sorting by them returns the categories in creation order.
+        let mut categories: Vec<Category> = self.categories.values().cloned().collect();
+        categories.sort_by(|left, right| left.id().cmp(&right.id()));
+        categories
+    }
+
+    fn find_by_id(&self, id: CategoryId) -> Option<Category> {
+        self.categories.get(&id).cloned()
+    }
+
+    fn create(&mut self, mut category: Category) -> CategoryId {
+        let id = CategoryId::generate();
+        category.set_id(Some(id.clone()));
+        self.categories.insert(id.clone(), category);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: CategoryId,
+        mut category: Category,
+    ) -> Result<CategoryId, FindByIdAndUpdateError> {
+        if !self.categories.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        category.set_id(Some(id.clone()));
+        self.categories.insert(id.clone(), category);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: CategoryId) -> Result<(), DeleteError> {
+        match self.categories.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
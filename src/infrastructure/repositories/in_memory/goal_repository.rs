@@ -0,0 +1,58 @@
+use crate::app::entities::goal::{Goal, GoalId};
+use crate::app::repositories::goal_repository::{
+    DeleteError, FindByIdAndUpdateError, GoalRepository,
+};
+use std::collections::BTreeMap;
+
+pub struct InMemoryGoalRepository {
+    // The ids are time ordered, the map keeps the goals in creation order.
+    goals: BTreeMap<GoalId, Goal>,
+}
+
+impl InMemoryGoalRepository {
+    pub fn new() -> Self {
+        Self {
+            goals: BTreeMap::new(),
+        }
+    }
+}
+
+impl GoalRepository for InMemoryGoalRepository {
+    fn find_all(&self) -> Vec<Goal> {
+        self.goals.values().cloned().collect()
+    }
+
+    fn find_by_id(&self, id: GoalId) -> Option<Goal> {
+        self.goals.get(&id).cloned()
+    }
+
+    fn create(&mut self, mut goal: Goal) -> GoalId {
+        let id = GoalId::generate();
+        goal.set_id(Some(id.clone()));
+        self.goals.insert(id.clone(), goal);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: GoalId,
+        mut goal: Goal,
+    ) -> Result<GoalId, FindByIdAndUpdateError> {
+        if !self.goals.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        goal.set_id(Some(id.clone()));
+        self.goals.insert(id.clone(), goal);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: GoalId) -> Result<(), DeleteError> {
+        match self.goals.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::app::entities::institution::{Institution, InstitutionId};
+use crate::app::repositories::institution_repository::{
+    DeleteError, FindByIdAndUpdateError, InstitutionRepository,
+};
+use std::collections::BTreeMap;
+
+pub struct InMemoryInstitutionRepository {
+    // The ids are time ordered, the map keeps the institutions in creation order.
+    institutions: BTreeMap<InstitutionId, Institution>,
+}
+
+impl InMemoryInstitutionRepository {
+    pub fn new() -> Self {
+        Self {
+            institutions: BTreeMap::new(),
+        }
+    }
+}
+
+impl InstitutionRepository for InMemoryInstitutionRepository {
+    fn find_all(&self) -> Vec<Institution> {
+        self.institutions.values().cloned().collect()
+    }
+
+    fn find_by_id(&self, id: InstitutionId) -> Option<Institution> {
+        self.institutions.get(&id).cloned()
+    }
+
+    fn create(&mut self, mut institution: Institution) -> InstitutionId {
+        let id = InstitutionId::generate();
+        institution.set_id(Some(id.clone()));
+        self.institutions.insert(id.clone(), institution);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: InstitutionId,
+        mut institution: Institution,
+    ) -> Result<InstitutionId, FindByIdAndUpdateError> {
+        if !self.institutions.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        institution.set_id(Some(id.clone()));
+        self.institutions.insert(id.clone(), institution);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: InstitutionId) -> Result<(), DeleteError> {
+        match self.institutions.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
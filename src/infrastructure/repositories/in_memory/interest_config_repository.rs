@@ -0,0 +1,37 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::interest::InterestConfig;
+use crate::app::repositories::interest_config_repository::{DeleteError, InterestConfigRepository};
+use std::collections::BTreeMap;
+
+pub struct InMemoryInterestConfigRepository {
+    configs: BTreeMap<AccountId, InterestConfig>,
+}
+
+impl InMemoryInterestConfigRepository {
+    pub fn new() -> Self {
+        Self {
+            configs: BTreeMap::new(),
+        }
+    }
+}
+
+impl InterestConfigRepository for InMemoryInterestConfigRepository {
+    fn find_all(&self) -> Vec<InterestConfig> {
+        self.configs.values().cloned().collect()
+    }
+
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<InterestConfig> {
+        self.configs.get(&account_id).cloned()
+    }
+
+    fn save(&mut self, config: InterestConfig) {
+        self.configs.insert(config.account_id().clone(), config);
+    }
+
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError> {
+        match self.configs.remove(&account_id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
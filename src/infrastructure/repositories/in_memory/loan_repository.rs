@@ -0,0 +1,58 @@
+use crate::app::entities::loan::{Loan, LoanId};
+use crate::app::repositories::loan_repository::{
+    DeleteError, FindByIdAndUpdateError, LoanRepository,
+};
+use std::collections::BTreeMap;
+
+pub struct InMemoryLoanRepository {
+    // The ids are time ordered, the map keeps the loans in creation order.
+    loans: BTreeMap<LoanId, Loan>,
+}
+
+impl InMemoryLoanRepository {
+    pub fn new() -> Self {
+        Self {
+            loans: BTreeMap::new(),
+        }
+    }
+}
+
+impl LoanRepository for InMemoryLoanRepository {
+    fn find_all(&self) -> Vec<Loan> {
+        self.loans.values().cloned().collect()
+    }
+
+    fn find_by_id(&self, id: LoanId) -> Option<Loan> {
+        self.loans.get(&id).cloned()
+    }
+
+    fn create(&mut self, mut loan: Loan) -> LoanId {
+        let id = LoanId::generate();
+        loan.set_id(Some(id.clone()));
+        self.loans.insert(id.clone(), loan);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: LoanId,
+        mut loan: Loan,
+    ) -> Result<LoanId, FindByIdAndUpdateError> {
+        if !self.loans.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        loan.set_id(Some(id.clone()));
+        self.loans.insert(id.clone(), loan);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: LoanId) -> Result<(), DeleteError> {
+        match self.loans.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::app::entities::payee::{Payee, PayeeId};
+use crate::app::repositories::payee_repository::{
+    DeleteError, FindByIdAndUpdateError, PayeeRepository,
+};
+use std::collections::HashMap;
+
+pub struct InMemoryPayeeRepository {
+    payees: HashMap<PayeeId, Payee>,
+}
+
+impl InMemoryPayeeRepository {
+    pub fn new() -> Self {
+        Self {
+            payees: HashMap::new(),
+        }
+    }
+}
+
+impl PayeeRepository for InMemoryPayeeRepository {
+    fn find_all(&self) -> Vec<Payee> {
+        // The ids are time ordered, sorting by them returns the payees in creation order.
+        let mut payees: Vec<Payee> = self.payees.values().cloned().collect();
+        payees.sort_by(|left, right| left.id().cmp(&right.id()));
+        payees
+    }
+
+    fn find_by_id(&self, id: PayeeId) -> Option<Payee> {
+        self.payees.get(&id).cloned()
+    }
+
+    fn create(&mut self, mut payee: Payee) -> PayeeId {
+        let id = PayeeId::generate();
+        payee.set_id(Some(id.clone()));
+        self.payees.insert(id.clone(), payee);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: PayeeId,
+        mut payee: Payee,
+    ) -> Result<PayeeId, FindByIdAndUpdateError> {
+        if !self.payees.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        payee.set_id(Some(id.clone()));
+        self.payees.insert(id.clone(), payee);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: PayeeId) -> Result<(), DeleteError> {
+        match self.payees.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::recurring_transaction::{RecurringTransaction, RecurringTransactionId};
+use crate::app::entities::tag::{replace_tag, TagId};
+use crate::app::repositories::recurring_transaction_repository::{
+    DeleteError, FindByIdAndUpdateError, RecurringTransactionRepository,
+};
+use std::collections::BTreeMap;
+
+pub struct InMemoryRecurringTransactionRepository {
+    // The ids are time ordered, the map keeps the recurring transactions in creation order.
+    recurring_transactions: BTreeMap<RecurringTransactionId, RecurringTransaction>,
+}
+
+impl InMemoryRecurringTransactionRepository {
+    pub fn new() -> Self {
+        Self {
+            recurring_transactions: BTreeMap::new(),
+        }
+    }
+}
+
+impl RecurringTransactionRepository for InMemoryRecurringTransactionRepository {
+    fn find_all(&self) -> Vec<RecurringTransaction> {
+        self.recurring_transactions.values().cloned().collect()
+    }
+
+    fn find_by_id(&self, id: RecurringTransactionId) -> Option<RecurringTransaction> {
+        self.recurring_transactions.get(&id).cloned()
+    }
+
+    fn create(
+        &mut self,
+        mut recurring_transaction: RecurringTransaction,
+    ) -> RecurringTransactionId {
+        let id = RecurringTransactionId::generate();
+        recurring_transaction.set_id(Some(id.clone()));
+        self.recurring_transactions
+            .insert(id.clone(), recurring_transaction);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: RecurringTransactionId,
+        mut recurring_transaction: RecurringTransaction,
+    ) -> Result<RecurringTransactionId, FindByIdAndUpdateError> {
+        if !self.recurring_transactions.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        recurring_transaction.set_id(Some(id.clone()));
+        self.recurring_transactions
+            .insert(id.clone(), recurring_transaction);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: RecurringTransactionId) -> Result<(), DeleteError> {
+        match self.recurring_transactions.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        for recurring_transaction in self.recurring_transactions.values_mut() {
+            if recurring_transaction.template().tags.contains(source) {
+                let mut template = recurring_transaction.template().clone();
+                template.tags = replace_tag(&template.tags, source, target);
+                recurring_transaction.set_template(template);
+            }
+        }
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        for recurring_transaction in self.recurring_transactions.values_mut() {
+            if recurring_transaction.template().payee_id.as_ref() == Some(source) {
+                let mut template = recurring_transaction.template().clone();
+                template.payee_id = Some(target.clone());
+                recurring_transaction.set_template(template);
+            }
+        }
+    }
+}
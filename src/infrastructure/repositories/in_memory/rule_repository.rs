@@ -0,0 +1,84 @@
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::rule::{Rule, RuleId};
+use crate::app::entities::tag::{replace_tag, TagId};
+use crate::app::repositories::rule_repository::{
+    DeleteError, FindByIdAndUpdateError, RuleRepository,
+};
+use std::collections::HashMap;
+
+pub struct InMemoryRuleRepository {
+    rules: HashMap<RuleId, Rule>,
+}
+
+impl InMemoryRuleRepository {
+    pub fn new() -> Self {
+        Self {
+            rules: HashMap::new(),
+        }
+    }
+}
+
+impl RuleRepository for InMemoryRuleRepository {
+    fn find_all(&self) -> Vec<Rule> {
+        // The ids are time ordered, they order the rules of the same priority by creation.
+        let mut rules: Vec<Rule> = self.rules.values().cloned().collect();
+        rules.sort_by(|left, right| {
+            (left.priority(), left.id()).cmp(&(right.priority(), right.id()))
+        });
+        rules
+    }
+
+    fn find_by_id(&self, id: RuleId) -> Option<Rule> {
+        self.rules.get(&id).cloned()
+    }
+
+    fn create(&mut self, mut rule: Rule) -> RuleId {
+        let id = RuleId::generate();
+        rule.set_id(Some(id.clone()));
+        self.rules.insert(id.clone(), rule);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: RuleId,
+        mut rule: Rule,
+    ) -> Result<RuleId, FindByIdAndUpdateError> {
+        if !self.rules.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        rule.set_id(Some(id.clone()));
+        self.rules.insert(id.clone(), rule);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: RuleId) -> Result<(), DeleteError> {
+        match self.rules.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        for rule in self.rules.values_mut() {
+            if rule.actions().tags.contains(source) {
+                let mut actions = rule.actions().clone();
+                actions.tags = replace_tag(&actions.tags, source, target);
+                rule.set_actions(actions);
+            }
+        }
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        for rule in self.rules.values_mut() {
+            if rule.actions().payee_id.as_ref() == Some(source) {
+                let mut actions = rule.actions().clone();
+                actions.payee_id = Some(target.clone());
+                rule.set_actions(actions);
+            }
+        }
+    }
+}
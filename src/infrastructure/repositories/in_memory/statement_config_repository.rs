@@ -0,0 +1,35 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::statement::StatementConfig;
+use crate::app::repositories::statement_config_repository::{
+    DeleteError, StatementConfigRepository,
+};
+use std::collections::BTreeMap;
+
+pub struct InMemoryStatementConfigRepository {
+    configs: BTreeMap<AccountId, StatementConfig>,
+}
+
+impl InMemoryStatementConfigRepository {
+    pub fn new() -> Self {
+        Self {
+            configs: BTreeMap::new(),
+        }
+    }
+}
+
+impl StatementConfigRepository for InMemoryStatementConfigRepository {
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<StatementConfig> {
+        self.configs.get(&account_id).cloned()
+    }
+
+    fn save(&mut self, config: StatementConfig) {
+        self.configs.insert(config.account_id().clone(), config);
+    }
+
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError> {
+        match self.configs.remove(&account_id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::app::entities::tag::{Tag, TagId};
+use crate::app::repositories::tag_repository::{
+    DeleteError, FindByIdAndUpdateError, TagRepository,
+};
+use std::collections::HashMap;
+
+pub struct InMemoryTagRepository {
+    tags: HashMap<TagId, Tag>,
+}
+
+impl InMemoryTagRepository {
+    pub fn new() -> Self {
+        Self {
+            tags: HashMap::new(),
+        }
+    }
+}
+
+impl TagRepository for InMemoryTagRepository {
+    fn find_all(&self) -> Vec<Tag> {
+        let mut tags: Vec<Tag> = self.tags.values().cloned().collect();
+        tags.sort_by(|left, right| left.name().cmp(right.name()));
+        tags
+    }
+
+    fn find_by_id(&self, id: TagId) -> Option<Tag> {
+        self.tags.get(&id).cloned()
+    }
+
+    fn find_by_name(&self, name: &str) -> Option<Tag> {
+        self.tags.values().find(|tag| tag.name() == name).cloned()
+    }
+
+    fn create(&mut self, mut tag: Tag) -> TagId {
+        let id = TagId::generate();
+        tag.set_id(Some(id.clone()));
+        self.tags.insert(id.clone(), tag);
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: TagId,
+        mut tag: Tag,
+    ) -> Result<TagId, FindByIdAndUpdateError> {
+        if !self.tags.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        tag.set_id(Some(id.clone()));
+        self.tags.insert(id.clone(), tag);
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: TagId) -> Result<(), DeleteError> {
+        match self.tags.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(DeleteError::NotFound),
+        }
+    }
+}
@@ -1,8 +1,13 @@
 #[cfg(test)]
 mod tests_account_repository {
+    use crate::app::entities::account::AccountId;
     use crate::app::entities::account::{Account, AccountType};
-    use crate::app::entities::common::EntityId;
-    use crate::app::repositories::account_repository::{AccountRepository, FindByIdAndUpdateError};
+    use crate::app::entities::institution::InstitutionId;
+    use crate::app::entities::tag::TagId;
+    use crate::app::repositories::account_repository::{
+        AccountRepository, CreateError, DeleteError, FindByIdAndUpdateError,
+    };
+    use crate::app::typing::account_identifier::{AccountIdentifier, IdentifierKind};
     use crate::app::typing::currency::Currency;
     use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
     use crate::shared::test_utilities::{
@@ -10,13 +15,13 @@ mod tests_account_repository {
     };
 
     #[test]
-    fn test_empty_find_all() {
+    fn test_empty_find_page() {
         // GIVEN the in memory account repository is initialized.
         // AND no items are added in the repository.
         let account_repository = InMemoryAccountRepository::new();
 
-        // WHEN the find_all method is called
-        let accounts = account_repository.find_all();
+        // WHEN the find_page method is called
+        let accounts = account_repository.find_page(None, 10).items;
 
         // THEN the result is empty
         assert_eq!(accounts.len(), 0);
@@ -30,17 +35,49 @@ mod tests_account_repository {
         // WHEN an account is added in the repository
         let account = get_random_account();
 
-        account_repository.create(account.clone());
+        account_repository.create(account.clone()).unwrap();
 
-        // AND the find_all method is called
-        let accounts = account_repository.find_all();
+        // AND the find_page method is called
+        let accounts = account_repository.find_page(None, 10).items;
 
         // THEN the result is not empty
         assert_eq!(accounts.len(), 1);
 
         // AND the first item should be the same as the one input
-        let first_account = accounts.get(0).unwrap();
-        assert_accounts_equal(&first_account, &account, false);
+        let first_account = accounts.first().unwrap();
+        assert_accounts_equal(first_account, &account, false);
+    }
+
+    #[test]
+    fn test_find_page_after_cursor() {
+        // GIVEN the in memory account repository is initialized.
+        let mut account_repository = InMemoryAccountRepository::new();
+
+        // AND five accounts are added in the repository
+        let ids: Vec<AccountId> = (0..5)
+            .map(|_| account_repository.create(get_random_account()).unwrap())
+            .collect();
+
+        // WHEN the accounts are fetched two at a time
+        let first_page = account_repository.find_page(None, 2);
+        let second_page = account_repository.find_page(Some(ids[1].clone()), 2);
+        let last_page = account_repository.find_page(Some(ids[3].clone()), 2);
+
+        // THEN each page starts after the given account, in creation order
+        let page_ids = |accounts: &[Account]| -> Vec<AccountId> {
+            accounts
+                .iter()
+                .map(|account| account.id().unwrap().clone())
+                .collect()
+        };
+        assert_eq!(page_ids(&first_page.items), ids[0..2]);
+        assert_eq!(page_ids(&second_page.items), ids[2..4]);
+        assert_eq!(page_ids(&last_page.items), ids[4..]);
+
+        // AND the cursor points to the last account of the page, until the last page
+        assert_eq!(first_page.next_cursor, Some(ids[1].to_string()));
+        assert_eq!(second_page.next_cursor, Some(ids[3].to_string()));
+        assert_eq!(last_page.next_cursor, None);
     }
 
     #[test]
@@ -53,7 +90,7 @@ mod tests_account_repository {
             None,
             String::from("MTN Momo Account"),
             String::from("Some cool description"),
-            String::from("Platform name"),
+            Some(InstitutionId::generate()),
             AccountType::Savings,
             Some(Currency::RWF),
         );
@@ -107,7 +144,7 @@ mod tests_account_repository {
         let mut account_repository = InMemoryAccountRepository::new();
 
         // WHEN the account_repository.find_by_id_and_update is called with an invalid id
-        let entity_id: EntityId = "100".into();
+        let entity_id = AccountId::generate();
 
         // THEN it should return an error
         let result = account_repository.find_by_id_and_update(entity_id, get_random_account());
@@ -118,4 +155,141 @@ mod tests_account_repository {
         // AND the error should be an invalid id error
         assert_eq!(result.err().unwrap(), FindByIdAndUpdateError::NotFound)
     }
+
+    #[test]
+    fn test_delete() {
+        // GIVEN an in memory account repository with an account identified by its phone number
+        let mut account_repository = InMemoryAccountRepository::new();
+        let msisdn = AccountIdentifier::new(IdentifierKind::Msisdn, "0788123456").unwrap();
+        let mut account = get_random_account();
+        account.set_identifiers(vec![msisdn.clone()]);
+        let id = account_repository.create(account.clone()).unwrap();
+
+        // WHEN the account is deleted
+        account_repository.delete(id.clone()).unwrap();
+
+        // THEN it is not found anymore, nor by its identifier
+        assert!(account_repository.find_by_id(id.clone()).is_none());
+        assert!(account_repository.find_by_identifier(&msisdn).is_empty());
+
+        // AND its identifier can be given to another account
+        assert!(account_repository.create(account).is_ok());
+
+        // AND deleting it again fails
+        assert_eq!(account_repository.delete(id), Err(DeleteError::NotFound));
+    }
+
+    #[test]
+    fn test_tags_are_saved_and_merged() {
+        // GIVEN a in memory account repository with a tagged account.
+        let mut account_repository = InMemoryAccountRepository::new();
+        let savings_id = TagId::generate();
+        let family_id = TagId::generate();
+        let mut account = get_random_account();
+        account.set_tags(vec![savings_id.clone()]);
+        let id = account_repository.create(account.clone()).unwrap();
+
+        // THEN the account is found with its tags
+        let mut account = account_repository.find_by_id(id.clone()).unwrap();
+        assert_eq!(account.tags(), &vec![savings_id.clone()]);
+
+        // WHEN its tags are updated
+        account.set_tags(vec![family_id.clone(), savings_id.clone()]);
+        account_repository
+            .find_by_id_and_update(id.clone(), account)
+            .unwrap();
+
+        // THEN the new tags are saved, in order
+        assert_eq!(
+            account_repository.find_by_id(id.clone()).unwrap().tags(),
+            &vec![family_id.clone(), savings_id.clone()]
+        );
+
+        // WHEN a tag is merged into the other one
+        account_repository.merge_tags(&savings_id, &family_id);
+
+        // THEN the account carries the remaining tag, once
+        let accounts = account_repository.find_page(None, 10).items;
+        assert_eq!(accounts[0].tags(), &vec![family_id]);
+    }
+
+    #[test]
+    fn test_identifiers_are_unique() {
+        // GIVEN a in memory account repository with an account identified by its IBAN and its phone number.
+        let mut account_repository = InMemoryAccountRepository::new();
+        let iban = AccountIdentifier::new(IdentifierKind::Iban, "GB82WEST12345698765432").unwrap();
+        let msisdn = AccountIdentifier::new(IdentifierKind::Msisdn, "0788123456").unwrap();
+        let mut account = get_random_account();
+        account.set_identifiers(vec![iban.clone(), msisdn.clone()]);
+        let id = account_repository.create(account).unwrap();
+
+        // WHEN the account is looked up by one of its identifiers
+        let mut accounts = account_repository.find_by_identifier(&msisdn);
+
+        // THEN it is the only one found, with all its identifiers
+        assert_eq!(accounts.len(), 1);
+        let mut found = accounts.remove(0);
+        assert_eq!(found.id(), Some(&id));
+        assert_eq!(found.identifiers(), &vec![iban.clone(), msisdn.clone()]);
+
+        // WHEN another account is created with the same phone number
+        let mut other = get_random_account();
+        other.set_identifiers(vec![msisdn.clone()]);
+        let result = account_repository.create(other.clone());
+
+        // THEN it is rejected
+        assert!(matches!(
+            result,
+            Err(CreateError::DuplicateIdentifier(identifier)) if identifier == msisdn
+        ));
+
+        // WHEN the other account is created without it, then updated with it
+        other.set_identifiers(vec![]);
+        let other_id = account_repository.create(other.clone()).unwrap();
+        other.set_identifiers(vec![msisdn.clone()]);
+        let result = account_repository.find_by_id_and_update(other_id, other);
+
+        // THEN the update is rejected too
+        assert_eq!(
+            result,
+            Err(FindByIdAndUpdateError::DuplicateIdentifier(msisdn.clone()))
+        );
+
+        // WHEN the first account keeps its IBAN only
+        found.set_identifiers(vec![iban.clone()]);
+        account_repository.find_by_id_and_update(id, found).unwrap();
+
+        // THEN the phone number identifies no account anymore
+        assert!(account_repository.find_by_identifier(&msisdn).is_empty());
+        assert_eq!(account_repository.find_by_identifier(&iban).len(), 1);
+    }
+
+    #[test]
+    fn test_card_last_four_is_shared() {
+        // GIVEN a in memory account repository with an account identified by the last 4 digits of its card
+        let mut account_repository = InMemoryAccountRepository::new();
+        let card = AccountIdentifier::new(IdentifierKind::CardLastFour, "4242").unwrap();
+        let mut account = get_random_account();
+        account.set_identifiers(vec![card.clone()]);
+        let id = account_repository.create(account).unwrap();
+
+        // WHEN another account is created with a card ending with the same digits
+        let mut other = get_random_account();
+        other.set_identifiers(vec![card.clone()]);
+        let other_id = account_repository.create(other.clone()).unwrap();
+
+        // AND it is updated, keeping them
+        other.set_name(get_random_string(10));
+        account_repository
+            .find_by_id_and_update(other_id.clone(), other)
+            .unwrap();
+
+        // THEN both accounts are found by the digits, in creation order
+        let ids: Vec<AccountId> = account_repository
+            .find_by_identifier(&card)
+            .iter()
+            .map(|account| account.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, vec![id, other_id]);
+    }
 }
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests_category_repository {
+    use crate::app::entities::category::{Category, CategoryId};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::repositories::category_repository::{
+        CategoryRepository, DeleteError, FindByIdAndUpdateError,
+    };
+    use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+
+    fn get_category(name: &str, parent_id: Option<CategoryId>) -> Category {
+        Category::new(
+            None,
+            String::from(name),
+            parent_id,
+            TransactionType::Expense,
+            Some(String::from("#ff9800")),
+            Some(String::from("utensils")),
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the in memory category repository is initialized.
+        let mut category_repository = InMemoryCategoryRepository::new();
+
+        // WHEN a category and one of its sub categories are added
+        let food_id = category_repository.create(get_category("Food", None));
+        let groceries_id =
+            category_repository.create(get_category("Groceries", Some(food_id.clone())));
+
+        // THEN they can be found by id, with their fields
+        let groceries = category_repository
+            .find_by_id(groceries_id.clone())
+            .unwrap();
+        assert_eq!(groceries.id(), Some(&groceries_id));
+        assert_eq!(groceries.name(), "Groceries");
+        assert_eq!(groceries.parent_id(), Some(&food_id));
+        assert_eq!(groceries.kind(), &TransactionType::Expense);
+        assert_eq!(groceries.color(), &Some(String::from("#ff9800")));
+        assert_eq!(groceries.icon(), &Some(String::from("utensils")));
+
+        // AND they are all listed, in creation order
+        let ids: Vec<CategoryId> = category_repository
+            .find_all()
+            .iter()
+            .map(|category| category.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [food_id, groceries_id]);
+
+        // AND an unknown id is not found
+        assert_eq!(category_repository.find_by_id(CategoryId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update() {
+        // GIVEN the in memory category repository, with a category
+        let mut category_repository = InMemoryCategoryRepository::new();
+        let id = category_repository.create(get_category("Food", None));
+
+        // WHEN the category is updated
+        let mut category = category_repository.find_by_id(id.clone()).unwrap();
+        category.set_name(String::from("Food & drinks"));
+        category.set_color(None);
+        let result = category_repository.find_by_id_and_update(id.clone(), category);
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        let category = category_repository.find_by_id(id).unwrap();
+        assert_eq!(category.name(), "Food & drinks");
+        assert_eq!(category.color(), &None);
+
+        // AND an unknown category can not be updated
+        assert_eq!(
+            category_repository
+                .find_by_id_and_update(CategoryId::generate(), get_category("Other", None)),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_delete() {
+        // GIVEN the in memory category repository, with a category
+        let mut category_repository = InMemoryCategoryRepository::new();
+        let id = category_repository.create(get_category("Food", None));
+
+        // WHEN the category is deleted
+        // THEN it is not found anymore
+        assert_eq!(category_repository.delete(id.clone()), Ok(()));
+        assert_eq!(category_repository.find_by_id(id.clone()), None);
+
+        // AND it can not be deleted twice
+        assert_eq!(category_repository.delete(id), Err(DeleteError::NotFound));
+    }
+}
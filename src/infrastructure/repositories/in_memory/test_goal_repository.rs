@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests_goal_repository {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::goal::{Goal, GoalId};
+    use crate::app::repositories::goal_repository::{
+        DeleteError, FindByIdAndUpdateError, GoalRepository,
+    };
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::goal_repository::InMemoryGoalRepository;
+    use chrono::NaiveDate;
+
+    fn get_goal(name: &str) -> Goal {
+        Goal::new(
+            None,
+            String::from(name),
+            500000f32.try_into().unwrap(),
+            Currency::RWF,
+            NaiveDate::from_ymd_opt(2027, 6, 30).unwrap(),
+            vec![AccountId::generate(), AccountId::generate()],
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the in memory goal repository is initialized.
+        let mut repository = InMemoryGoalRepository::new();
+
+        // WHEN goals are added
+        let emergency_fund = get_goal("Emergency fund");
+        let emergency_fund_id = repository.create(emergency_fund.clone());
+        let car_id = repository.create(get_goal("Car"));
+
+        // THEN they can be found by id, with their linked accounts
+        let mut expected = emergency_fund;
+        expected.set_id(Some(emergency_fund_id.clone()));
+        assert_eq!(
+            repository.find_by_id(emergency_fund_id.clone()),
+            Some(expected)
+        );
+
+        // AND they are all listed in creation order
+        let ids: Vec<GoalId> = repository
+            .find_all()
+            .iter()
+            .map(|goal| goal.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [emergency_fund_id, car_id]);
+
+        // AND an unknown goal is not found
+        assert_eq!(repository.find_by_id(GoalId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_and_delete() {
+        // GIVEN the in memory goal repository, with a goal
+        let mut repository = InMemoryGoalRepository::new();
+        let id = repository.create(get_goal("Emergency fund"));
+
+        // WHEN its target and its accounts are changed
+        let mut goal = repository.find_by_id(id.clone()).unwrap();
+        goal.set_target_amount(800000f32.try_into().unwrap());
+        goal.set_currency(Currency::USD);
+        goal.set_target_date(NaiveDate::from_ymd_opt(2028, 1, 1).unwrap());
+        goal.set_account_ids(vec![AccountId::generate()]);
+        let result = repository.find_by_id_and_update(id.clone(), goal.clone());
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(repository.find_by_id(id.clone()), Some(goal));
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(id.clone()), Ok(()));
+        assert_eq!(repository.find_by_id(id.clone()), None);
+
+        // AND an unknown goal can not be updated nor deleted
+        assert_eq!(
+            repository.find_by_id_and_update(id.clone(), get_goal("Other")),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+        assert_eq!(repository.delete(id), Err(DeleteError::NotFound));
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests_institution_repository {
+    use crate::app::entities::institution::{FeeTier, Institution, InstitutionId, InstitutionKind};
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::repositories::institution_repository::{
+        DeleteError, FindByIdAndUpdateError, InstitutionRepository,
+    };
+    use crate::infrastructure::repositories::in_memory::institution_repository::InMemoryInstitutionRepository;
+
+    fn get_institution(name: &str) -> Institution {
+        Institution::new(
+            None,
+            String::from(name),
+            InstitutionKind::Bank,
+            String::from("RW"),
+            None,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the in memory institution repository is initialized.
+        let mut repository = InMemoryInstitutionRepository::new();
+
+        // WHEN institutions are added
+        let bank = get_institution("Bank of Kigali");
+        let bank_id = repository.create(bank.clone());
+        let equity_id = repository.create(get_institution("Equity Bank"));
+
+        // THEN they can be found by id
+        let mut expected = bank;
+        expected.set_id(Some(bank_id.clone()));
+        assert_eq!(repository.find_by_id(bank_id.clone()), Some(expected));
+
+        // AND they are all listed in creation order
+        let ids: Vec<InstitutionId> = repository
+            .find_all()
+            .iter()
+            .map(|institution| institution.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [bank_id, equity_id]);
+
+        // AND an unknown institution is not found
+        assert_eq!(repository.find_by_id(InstitutionId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_and_delete() {
+        // GIVEN the in memory institution repository, with an institution
+        let mut repository = InMemoryInstitutionRepository::new();
+        let id = repository.create(get_institution("MTN"));
+
+        // WHEN its kind, logo and fee schedule are changed
+        let mut institution = repository.find_by_id(id.clone()).unwrap();
+        institution.set_kind(InstitutionKind::MobileMoneyOperator);
+        institution.set_logo_url(Some(String::from("https://example.com/mtn.png")));
+        institution.set_fee_schedule(vec![FeeTier {
+            transaction_type: TransactionType::Expense,
+            min_amount: 0f32.try_into().unwrap(),
+            max_amount: None,
+            flat_fee: 100f32.try_into().unwrap(),
+            rate: 0.5,
+        }]);
+        let result = repository.find_by_id_and_update(id.clone(), institution.clone());
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(repository.find_by_id(id.clone()), Some(institution));
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(id.clone()), Ok(()));
+        assert_eq!(repository.find_by_id(id.clone()), None);
+
+        // AND an unknown institution can not be updated nor deleted
+        assert_eq!(
+            repository.find_by_id_and_update(id.clone(), get_institution("Other")),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+        assert_eq!(repository.delete(id), Err(DeleteError::NotFound));
+    }
+}
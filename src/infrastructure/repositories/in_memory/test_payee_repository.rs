@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests_payee_repository {
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::payee::{Payee, PayeeId};
+    use crate::app::repositories::payee_repository::{
+        DeleteError, FindByIdAndUpdateError, PayeeRepository,
+    };
+    use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+
+    fn get_payee(name: &str) -> Payee {
+        Payee::new(
+            None,
+            String::from(name),
+            vec![String::from("SIMBA SPMKT"), String::from("Simba Kigali")],
+            vec![String::from(r"simba\s*super")],
+            Some(CategoryId::generate()),
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the in memory payee repository is initialized.
+        let mut payee_repository = InMemoryPayeeRepository::new();
+
+        // WHEN two payees are added
+        let payee = get_payee("Simba Supermarket");
+        let simba_id = payee_repository.create(payee.clone());
+        let mtn_id =
+            payee_repository.create(Payee::new(None, String::from("MTN"), vec![], vec![], None));
+
+        // THEN they can be found by id, with their fields
+        let simba = payee_repository.find_by_id(simba_id.clone()).unwrap();
+        assert_eq!(simba.id(), Some(&simba_id));
+        assert_eq!(simba.name(), "Simba Supermarket");
+        assert_eq!(simba.aliases(), payee.aliases());
+        assert_eq!(simba.patterns(), payee.patterns());
+        assert_eq!(simba.default_category_id(), payee.default_category_id());
+
+        // AND they are all listed, in creation order
+        let ids: Vec<PayeeId> = payee_repository
+            .find_all()
+            .iter()
+            .map(|payee| payee.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [simba_id, mtn_id]);
+
+        // AND an unknown payee is not found
+        assert_eq!(payee_repository.find_by_id(PayeeId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update() {
+        // GIVEN the in memory payee repository, with a payee
+        let mut payee_repository = InMemoryPayeeRepository::new();
+        let id = payee_repository.create(get_payee("Simba"));
+
+        // WHEN the payee is updated
+        let mut payee = payee_repository.find_by_id(id.clone()).unwrap();
+        payee.set_name(String::from("Simba Supermarket"));
+        payee.set_aliases(vec![]);
+        payee.set_default_category_id(None);
+        let result = payee_repository.find_by_id_and_update(id.clone(), payee);
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        let payee = payee_repository.find_by_id(id).unwrap();
+        assert_eq!(payee.name(), "Simba Supermarket");
+        assert!(payee.aliases().is_empty());
+        assert_eq!(payee.default_category_id(), None);
+
+        // AND an unknown payee can not be updated
+        assert_eq!(
+            payee_repository.find_by_id_and_update(PayeeId::generate(), get_payee("Other")),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_delete() {
+        // GIVEN the in memory payee repository, with a payee
+        let mut payee_repository = InMemoryPayeeRepository::new();
+        let id = payee_repository.create(get_payee("Simba"));
+
+        // WHEN the payee is deleted
+        // THEN it is not found anymore
+        assert_eq!(payee_repository.delete(id.clone()), Ok(()));
+        assert_eq!(payee_repository.find_by_id(id.clone()), None);
+
+        // AND it can not be deleted twice
+        assert_eq!(payee_repository.delete(id), Err(DeleteError::NotFound));
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests_statement_config_repository {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::statement::StatementConfig;
+    use crate::app::repositories::statement_config_repository::{
+        DeleteError, StatementConfigRepository,
+    };
+    use crate::infrastructure::repositories::in_memory::statement_config_repository::InMemoryStatementConfigRepository;
+
+    fn get_config(account_id: AccountId) -> StatementConfig {
+        StatementConfig::new(account_id, 25, 20, 5.0, 1000f32.try_into().unwrap())
+    }
+
+    #[test]
+    fn test_save_find_and_delete() {
+        // GIVEN the in memory statement configuration repository is initialized.
+        let mut repository = InMemoryStatementConfigRepository::new();
+        let account_id = AccountId::generate();
+
+        // WHEN the configurations of two accounts are saved
+        let config = get_config(account_id.clone());
+        repository.save(config.clone());
+        repository.save(get_config(AccountId::generate()));
+
+        // THEN they can be found by account
+        assert_eq!(
+            repository.find_by_account_id(account_id.clone()),
+            Some(config.clone())
+        );
+
+        // WHEN the configuration of the account is saved again
+        let mut changed = config;
+        changed.set_closing_day(31);
+        changed.set_minimum_payment_rate(3.5);
+        repository.save(changed.clone());
+
+        // THEN it replaces the previous one
+        assert_eq!(
+            repository.find_by_account_id(account_id.clone()),
+            Some(changed)
+        );
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(account_id.clone()), Ok(()));
+        assert_eq!(repository.find_by_account_id(account_id.clone()), None);
+        assert_eq!(repository.delete(account_id), Err(DeleteError::NotFound));
+    }
+}
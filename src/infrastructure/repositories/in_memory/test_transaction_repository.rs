@@ -1,17 +1,20 @@
 #[cfg(test)]
-mod test_transaction_repository {
+mod tests_transaction_repository {
+    use crate::app::repositories::transaction_query::TransactionQuery;
     use crate::app::repositories::transaction_repository::TransactionRepository;
     use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
     use crate::shared::test_utilities::get_random_transaction;
 
     #[test]
-    fn test_empty_find_all() {
+    fn test_empty_find() {
         // GIVEN the in memory transaction repository is initialized.
         // AND no items are added in the repository.
         let in_memory_transaction_repository = InMemoryTransactionRepository::new();
 
-        // WHEN the find_all method is called
-        let transactions = in_memory_transaction_repository.find_all();
+        // WHEN the find method is called
+        let transactions = in_memory_transaction_repository
+            .find(&TransactionQuery::default())
+            .items;
 
         // THEN the result is empty
         assert_eq!(transactions.len(), 0);
@@ -27,14 +30,16 @@ mod test_transaction_repository {
 
         let transaction_id = transactions_repository.create(transaction.clone());
 
-        // AND the find_all method is called
-        let transactions = transactions_repository.find_all();
+        // AND the find method is called
+        let transactions = transactions_repository
+            .find(&TransactionQuery::default())
+            .items;
 
         // THEN the result is not empty
         assert_eq!(transactions.len(), 1);
 
         // AND the first item should be the same as the one input
-        let first_transaction = transactions.get(0).unwrap();
+        let first_transaction = transactions.first().unwrap();
         transaction.set_id(Some(transaction_id));
         assert_eq!(*first_transaction, transaction);
     }
@@ -53,6 +58,6 @@ mod test_transaction_repository {
         let created_account = transactions_repository.find_by_id(new_id);
 
         // THEN the result is the same as the one input
-        assert_eq!(created_account, Some(&transaction));
+        assert_eq!(created_account, Some(transaction));
     }
 }
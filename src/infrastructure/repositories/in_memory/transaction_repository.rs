@@ -1,38 +1,160 @@
-use crate::app::entities::common::EntityId;
-use crate::app::entities::transaction::Transaction;
-use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::entities::account::AccountId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::{replace_tag, TagId};
+use crate::app::entities::transaction::{Transaction, TransactionId, TransactionRevision};
+use crate::app::repositories::common::Page;
+use crate::app::repositories::transaction_query::{TransactionCursor, TransactionQuery};
+use crate::app::repositories::transaction_repository::{
+    DeleteError, FindByIdAndUpdateError, SetTagsError, TransactionRepository,
+};
+use crate::app::typing::amount::Amount;
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 pub struct InMemoryTransactionRepository {
-    next_id: usize,
-    transactions: Vec<Transaction>,
+    transactions: HashMap<TransactionId, Transaction>,
+    revisions: Vec<TransactionRevision>,
+
+    /// The number of changes made to the transactions.
+    changes: u64,
 }
 
 impl InMemoryTransactionRepository {
     pub fn new() -> Self {
         Self {
-            next_id: Default::default(),
             transactions: Default::default(),
+            revisions: vec![],
+            changes: 0,
         }
     }
 }
 
 impl TransactionRepository for InMemoryTransactionRepository {
-    fn find_all(&self) -> Vec<Transaction> {
-        self.transactions.clone()
+    fn find(&self, query: &TransactionQuery) -> Page<Transaction> {
+        let mut transactions: Vec<(TransactionCursor, &Transaction)> =
+            self.transactions
+                .values()
+                .filter(|transaction| query.matches(transaction))
+                .map(|transaction| (TransactionCursor::from(transaction), transaction))
+                // Keep the transactions positioned after the cursor
+                .filter(|(position, _)| {
+                    query.cursor.as_ref().is_none_or(|cursor| {
+                        query.sort.compare(position, cursor) == Ordering::Greater
+                    })
+                })
+                .collect();
+
+        transactions.sort_by(|left, right| query.sort.compare(&left.0, &right.0));
+        transactions.truncate(query.limit + 1);
+
+        Page::from_overfetched(transactions, query.limit, |(position, _)| {
+            position.to_string()
+        })
+        .map(|(_, transaction)| transaction.clone())
     }
 
-    fn create(&mut self, mut transaction: Transaction) -> EntityId {
-        let id = EntityId(self.next_id.to_string());
+    fn create(&mut self, mut transaction: Transaction) -> TransactionId {
+        let id = TransactionId::generate();
         transaction.set_id(Some(id.clone()));
-        self.transactions.push(transaction);
-
-        self.next_id += 1;
+        self.transactions.insert(id.clone(), transaction);
+        self.changes += 1;
 
         id
     }
 
-    fn find_by_id(&self, id: EntityId) -> Option<&Transaction> {
-        let id: usize = id.0.parse().unwrap();
-        self.transactions.get(id)
+    fn find_by_id(&self, id: TransactionId) -> Option<Transaction> {
+        self.transactions.get(&id).cloned()
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: TransactionId,
+        mut transaction: Transaction,
+    ) -> Result<TransactionId, FindByIdAndUpdateError> {
+        if !self.transactions.contains_key(&id) {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        transaction.set_id(Some(id.clone()));
+        self.transactions.insert(id.clone(), transaction);
+        self.changes += 1;
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: TransactionId) -> Result<(), DeleteError> {
+        match self.transactions.remove(&id) {
+            Some(_) => {
+                self.changes += 1;
+                Ok(())
+            }
+            None => Err(DeleteError::NotFound),
+        }
+    }
+
+    fn add_revision(&mut self, revision: TransactionRevision) {
+        self.revisions.push(revision);
+    }
+
+    fn find_revisions(&self, id: &TransactionId) -> Vec<TransactionRevision> {
+        self.revisions
+            .iter()
+            .filter(|revision| &revision.transaction_id == id)
+            .cloned()
+            .collect()
+    }
+
+    fn balance_at(&self, account_id: &AccountId, at: DateTime<Utc>) -> Option<Amount> {
+        let (before, after): (Vec<&Transaction>, Vec<&Transaction>) = self
+            .transactions
+            .values()
+            .filter(|transaction| transaction.account_id() == Some(account_id))
+            .partition(|transaction| transaction.date() <= at);
+        let position = |transaction: &&Transaction| (transaction.date(), transaction.id().clone());
+
+        before
+            .into_iter()
+            .max_by_key(position)
+            .and_then(|last| last.closing_balance().clone())
+            .or_else(|| {
+                after
+                    .into_iter()
+                    .min_by_key(position)
+                    .and_then(|first| first.opening_balance().clone())
+            })
+    }
+
+    fn set_tags(&mut self, id: TransactionId, tags: Vec<TagId>) -> Result<(), SetTagsError> {
+        match self.transactions.get_mut(&id) {
+            Some(transaction) => {
+                transaction.set_tags(tags);
+                self.changes += 1;
+                Ok(())
+            }
+            None => Err(SetTagsError::NotFound),
+        }
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        for transaction in self.transactions.values_mut() {
+            if transaction.tags().contains(source) {
+                transaction.set_tags(replace_tag(transaction.tags(), source, target));
+            }
+        }
+        self.changes += 1;
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        for transaction in self.transactions.values_mut() {
+            if transaction.payee_id() == Some(source) {
+                transaction.set_payee_id(Some(target.clone()));
+            }
+        }
+        self.changes += 1;
+    }
+
+    fn version(&self) -> u64 {
+        self.changes
     }
 }
@@ -1,2 +1,3 @@
 pub(crate) mod in_memory;
-mod sqlite;
+pub(crate) mod sqlite;
+mod test_transaction_query;
@@ -1,9 +1,13 @@
-use crate::app::entities::account::Account;
-use crate::app::entities::common::EntityId;
+use crate::app::entities::account::{Account, AccountId};
+use crate::app::entities::tag::TagId;
 use crate::app::repositories::account_repository::{
-    AccountRepository, CreateError, FindByIdAndUpdateError,
+    AccountRepository, CreateError, DeleteError, FindByIdAndUpdateError,
 };
-use sqlite::{Connection, State};
+use crate::app::repositories::common::Page;
+use crate::app::typing::account_identifier::AccountIdentifier;
+use crate::infrastructure::repositories::sqlite::tag_repository::TagLinks;
+use sqlite::{Connection, State, Statement, Value};
+use std::collections::HashMap;
 
 pub struct SQliteAccountRepository {
     connection: Connection,
@@ -13,66 +17,308 @@ impl SQliteAccountRepository {
     pub fn new(connection: Connection) -> Self {
         Self { connection }
     }
+
+    /// Reads the tags and the identifiers of the accounts, which are stored in their own tables.
+    fn load_links(&self, accounts: &mut [Account]) {
+        let ids: Vec<String> = accounts
+            .iter()
+            .map(|account| account.id().unwrap().to_string())
+            .collect();
+        let mut tags = TagLinks::Accounts.load(&self.connection, &ids);
+        let mut identifiers = self.load_identifiers(&ids);
+
+        for (account, id) in accounts.iter_mut().zip(ids) {
+            if let Some(tags) = tags.remove(&id) {
+                account.set_tags(tags);
+            }
+            if let Some(identifiers) = identifiers.remove(&id) {
+                account.set_identifiers(identifiers);
+            }
+        }
+    }
+
+    /// Reads the identifiers of the accounts, in the order they were given.
+    ///
+    /// # Returns
+    /// The identifiers, by account id. The accounts without identifiers are left out.
+    fn load_identifiers(&self, ids: &[String]) -> HashMap<String, Vec<AccountIdentifier>> {
+        let mut identifiers: HashMap<String, Vec<AccountIdentifier>> = HashMap::new();
+        if ids.is_empty() {
+            return identifiers;
+        }
+
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let mut statement = self
+            .connection
+            .prepare(format!(
+                "SELECT account_id, kind, value FROM account_identifiers WHERE account_id IN ({placeholders}) ORDER BY rowid"
+            ))
+            .unwrap();
+        let values: Vec<(usize, Value)> = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (index + 1, id.as_str().into()))
+            .collect();
+        statement.bind(&values[..]).unwrap();
+
+        while let Ok(State::Row) = statement.next() {
+            let id = statement.read::<String, _>("account_id").unwrap();
+            identifiers
+                .entry(id)
+                .or_default()
+                .push(read_identifier(&statement));
+        }
+
+        identifiers
+    }
+
+    /// The first unique identifier of the account another account already has.
+    fn find_duplicate_identifier(&self, id: &str, account: &Account) -> Option<AccountIdentifier> {
+        account
+            .identifiers()
+            .iter()
+            .filter(|identifier| identifier.kind().is_unique())
+            .find_map(|identifier| {
+            let mut statement = self
+                .connection
+                .prepare("SELECT 1 FROM account_identifiers WHERE kind = ? AND value = ? AND account_id != ?")
+                .unwrap();
+            statement
+                .bind((1, identifier.kind().as_str()))
+                .unwrap();
+            statement.bind((2, identifier.value().as_str())).unwrap();
+            statement.bind((3, id)).unwrap();
+
+            match statement.next() {
+                Ok(State::Row) => Some(identifier.clone()),
+                _ => None,
+            }
+        })
+    }
+
+    /// Replaces the identifiers of an account.
+    fn save_identifiers(&self, id: &str, identifiers: &[AccountIdentifier]) {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM account_identifiers WHERE account_id = ?")
+            .unwrap();
+        statement.bind((1, id)).unwrap();
+        statement.next().unwrap();
+
+        for identifier in identifiers {
+            let mut statement = self
+                .connection
+                .prepare("INSERT OR IGNORE INTO account_identifiers (kind, value, account_id) VALUES (?, ?, ?)")
+                .unwrap();
+            statement.bind((1, identifier.kind().as_str())).unwrap();
+            statement.bind((2, identifier.value().as_str())).unwrap();
+            statement.bind((3, id)).unwrap();
+            statement.next().unwrap();
+        }
+    }
+}
+
+/// Builds an identifier from the `kind` and `value` columns of the current row of the statement.
+/// The stored values are already normalized, they are validated again as they are read.
+fn read_identifier(statement: &Statement) -> AccountIdentifier {
+    let kind = statement.read::<String, _>("kind").unwrap();
+    let value = statement.read::<String, _>("value").unwrap();
+
+    AccountIdentifier::new(kind.as_str().try_into().unwrap(), &value).unwrap()
+}
+
+/// Builds an account from the current row of the statement.
+fn read_account(statement: &Statement) -> Account {
+    let account_id = statement.read::<String, _>("id").unwrap();
+    let account_name = statement.read::<String, _>("name").unwrap();
+    let account_description = statement.read::<String, _>("description").unwrap();
+    let account_institution_id = statement
+        .read::<Option<String>, _>("institution_id")
+        .unwrap();
+    let account_balance = statement.read::<f64, _>("balance").unwrap();
+    let account_type = statement.read::<String, _>("account_type").unwrap();
+    let account_currency = statement.read::<String, _>("currency").unwrap();
+    let account_status = statement.read::<String, _>("status").unwrap();
+    let account_status_changes = statement.read::<String, _>("status_changes").unwrap();
+
+    let mut account = Account::new(
+        Some(account_id.try_into().unwrap()),
+        account_name,
+        account_description,
+        account_institution_id.map(|id| id.try_into().unwrap()),
+        account_type.try_into().unwrap(),
+        Some(account_currency.try_into().unwrap()),
+    );
+    account.set_balance((account_balance as f32).try_into().unwrap());
+    account.set_status(account_status.as_str().try_into().unwrap());
+    account.set_status_changes(serde_json::from_str(&account_status_changes).unwrap());
+
+    account
 }
 
 impl AccountRepository for SQliteAccountRepository {
-    fn find_all(&self) -> Vec<Account> {
-        let mut query = self.connection.prepare("SELECT * from accounts").unwrap();
+    fn find_page(&self, after: Option<AccountId>, limit: usize) -> Page<Account> {
+        // The ids are time ordered, sorting by them returns the accounts in creation order.
+        // An empty id sorts before all the others, it stands for the start of the listing.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM accounts WHERE id > ? ORDER BY id LIMIT ?")
+            .unwrap();
+        let after = after.map(|id| id.to_string()).unwrap_or_default();
+        query.bind((1, after.as_str())).unwrap();
+        query.bind((2, (limit + 1) as i64)).unwrap();
+
+        let mut accounts: Vec<Account> = vec![];
+        while let Ok(State::Row) = query.next() {
+            accounts.push(read_account(&query))
+        }
+        self.load_links(&mut accounts);
+
+        Page::from_overfetched(accounts, limit, |account| account.id().unwrap().to_string())
+    }
+
+    fn find_by_id(&self, id: AccountId) -> Option<Account> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM accounts WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        let mut account = match query.next() {
+            Ok(State::Row) => read_account(&query),
+            _ => return None,
+        };
+        self.load_links(std::slice::from_mut(&mut account));
+
+        Some(account)
+    }
+
+    fn find_by_identifier(&self, identifier: &AccountIdentifier) -> Vec<Account> {
+        let mut query = self
+            .connection
+            .prepare("SELECT accounts.* FROM accounts JOIN account_identifiers ON account_identifiers.account_id = accounts.id WHERE kind = ? AND value = ? ORDER BY accounts.id")
+            .unwrap();
+        query.bind((1, identifier.kind().as_str())).unwrap();
+        query.bind((2, identifier.value().as_str())).unwrap();
 
         let mut accounts: Vec<Account> = vec![];
         while let Ok(State::Row) = query.next() {
-            let account_id = query.read::<i64, _>("id").unwrap();
-            let account_name = query.read::<String, _>("name").unwrap();
-            let account_description = query.read::<String, _>("description").unwrap();
-            let account_platform = query.read::<String, _>("platform").unwrap();
-            let account_type = query.read::<String, _>("account_type").unwrap();
-            let account_currency = query.read::<String, _>("currency").unwrap();
-
-            let account = Account::new(
-                Some(account_id.into()),
-                account_name,
-                account_description,
-                account_platform,
-                account_type.try_into().unwrap(),
-                Some(account_currency.try_into().unwrap()),
-            );
-
-            accounts.push(account)
+            accounts.push(read_account(&query))
         }
+        self.load_links(&mut accounts);
 
         accounts
     }
 
-    fn find_by_id(&self, id: EntityId) -> Option<Account> {
-        todo!()
+    fn create(&mut self, account: Account) -> Result<AccountId, CreateError> {
+        let query = "INSERT INTO accounts (id, name, description, institution_id, balance, account_type, currency, status, status_changes) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = AccountId::generate();
+        if let Some(identifier) = self.find_duplicate_identifier(&id.to_string(), &account) {
+            return Err(CreateError::DuplicateIdentifier(identifier));
+        }
+        let currency: &str = &account.currency().to_string();
+        let account_type: &str = &account.account_type().to_string();
+        let institution_id = account.institution_id().map(|id| id.to_string());
+
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.bind((2, account.name().as_str())).unwrap();
+        statement.bind((3, account.description().as_str())).unwrap();
+        statement.bind((4, institution_id.as_deref())).unwrap();
+        statement
+            .bind((5, account.balance().value() as f64))
+            .unwrap();
+        statement.bind((6, account_type)).unwrap();
+        statement.bind((7, currency)).unwrap();
+        statement
+            .bind((8, account.status().to_string().as_str()))
+            .unwrap();
+        statement
+            .bind((
+                9,
+                serde_json::to_string(account.status_changes())
+                    .unwrap()
+                    .as_str(),
+            ))
+            .unwrap();
+
+        match statement.next() {
+            Ok(State::Done) => {
+                TagLinks::Accounts.save(&self.connection, &id.to_string(), account.tags());
+                self.save_identifiers(&id.to_string(), account.identifiers());
+                Ok(id)
+            }
+            _ => Err(CreateError::FailedToCreateAccount),
+        }
     }
 
-    fn create(&mut self, account: Account) -> Result<EntityId, CreateError> {
-        let query = "INSERT INTO accounts (name, description, platform, account_type, currency) VALUES (?, ?, ?, ?, ?) RETURNING id";
+    fn find_by_id_and_update(
+        &mut self,
+        id: AccountId,
+        account: Account,
+    ) -> Result<AccountId, FindByIdAndUpdateError> {
+        if let Some(identifier) = self.find_duplicate_identifier(&id.to_string(), &account) {
+            return Err(FindByIdAndUpdateError::DuplicateIdentifier(identifier));
+        }
+
+        let query = "UPDATE accounts SET name = ?, description = ?, institution_id = ?, balance = ?, account_type = ?, currency = ?, status = ?, status_changes = ? WHERE id = ?";
         let mut statement = self.connection.prepare(query).unwrap();
 
         let currency: &str = &account.currency().to_string();
         let account_type: &str = &account.account_type().to_string();
+        let institution_id = account.institution_id().map(|id| id.to_string());
 
         statement.bind((1, account.name().as_str())).unwrap();
         statement.bind((2, account.description().as_str())).unwrap();
-        statement.bind((3, account.platform())).unwrap();
-        statement.bind((4, account_type)).unwrap();
-        statement.bind((5, currency)).unwrap();
+        statement.bind((3, institution_id.as_deref())).unwrap();
+        statement
+            .bind((4, account.balance().value() as f64))
+            .unwrap();
+        statement.bind((5, account_type)).unwrap();
+        statement.bind((6, currency)).unwrap();
+        statement
+            .bind((7, account.status().to_string().as_str()))
+            .unwrap();
+        statement
+            .bind((
+                8,
+                serde_json::to_string(account.status_changes())
+                    .unwrap()
+                    .as_str(),
+            ))
+            .unwrap();
+        statement.bind((9, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
 
-        while let Ok(State::Row) = statement.next() {
-            let id = statement.read::<i64, _>(0).unwrap();
-            return Ok(id.into());
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
         }
+        TagLinks::Accounts.save(&self.connection, &id.to_string(), account.tags());
+        self.save_identifiers(&id.to_string(), account.identifiers());
 
-        Err(CreateError::FailedToCreateAccount)
+        Ok(id)
     }
 
-    fn find_by_id_and_update(
-        &mut self,
-        id: EntityId,
-        account: Account,
-    ) -> Result<EntityId, FindByIdAndUpdateError> {
-        todo!()
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        TagLinks::Accounts.merge(&self.connection, source, target);
+    }
+
+    fn delete(&mut self, id: AccountId) -> Result<(), DeleteError> {
+        TagLinks::Accounts.save(&self.connection, &id.to_string(), &[]);
+        self.save_identifiers(&id.to_string(), &[]);
+
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM accounts WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,185 @@
+use crate::app::entities::budget::{Budget, BudgetId, BudgetPeriod, BudgetScope};
+use crate::app::entities::tag::TagId;
+use crate::app::repositories::budget_repository::{
+    BudgetRepository, DeleteError, FindByIdAndUpdateError,
+};
+use chrono::NaiveDate;
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteBudgetRepository {
+    connection: Connection,
+}
+
+impl SQliteBudgetRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+fn period_to_str(period: BudgetPeriod) -> &'static str {
+    match period {
+        BudgetPeriod::Monthly => "monthly",
+        BudgetPeriod::Quarterly => "quarterly",
+        BudgetPeriod::Yearly => "yearly",
+    }
+}
+
+fn period_from_str(period: &str) -> BudgetPeriod {
+    match period {
+        "quarterly" => BudgetPeriod::Quarterly,
+        "yearly" => BudgetPeriod::Yearly,
+        _ => BudgetPeriod::Monthly,
+    }
+}
+
+/// Builds a budget from the current row of the statement.
+/// The scope is stored in two columns, exactly one of `category_id` and `tag_id` is set.
+fn read_budget(statement: &Statement) -> Budget {
+    let id = statement.read::<String, _>("id").unwrap();
+    let category_id = statement.read::<Option<String>, _>("category_id").unwrap();
+    let tag_id = statement.read::<Option<String>, _>("tag_id").unwrap();
+    let currency = statement.read::<String, _>("currency").unwrap();
+    let start = statement.read::<String, _>("start").unwrap();
+
+    let scope = match (category_id, tag_id) {
+        (Some(category_id), _) => BudgetScope::Category(category_id.try_into().unwrap()),
+        (None, Some(tag_id)) => BudgetScope::Tag(tag_id.try_into().unwrap()),
+        (None, None) => panic!("A budget applies to a category or a tag"),
+    };
+
+    Budget::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        scope,
+        period_from_str(&statement.read::<String, _>("period").unwrap()),
+        (statement.read::<f64, _>("amount").unwrap() as f32)
+            .try_into()
+            .unwrap(),
+        currency.try_into().unwrap(),
+        statement.read::<i64, _>("rollover").unwrap() != 0,
+        NaiveDate::parse_from_str(&start, "%Y-%m-%d").unwrap(),
+    )
+}
+
+/// Binds the fields of the budget, in the order of the
+/// `name, category_id, tag_id, period, amount, currency, rollover, start` columns, starting at the given index.
+fn bind_budget(statement: &mut Statement, first_index: usize, budget: &Budget) {
+    let (category_id, tag_id) = match budget.scope() {
+        BudgetScope::Category(category_id) => (Some(category_id.to_string()), None),
+        BudgetScope::Tag(tag_id) => (None, Some(tag_id.to_string())),
+    };
+
+    statement
+        .bind((first_index, budget.name().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 1, category_id.as_deref()))
+        .unwrap();
+    statement
+        .bind((first_index + 2, tag_id.as_deref()))
+        .unwrap();
+    statement
+        .bind((first_index + 3, period_to_str(budget.period())))
+        .unwrap();
+    statement
+        .bind((first_index + 4, budget.amount().value() as f64))
+        .unwrap();
+    statement
+        .bind((first_index + 5, budget.currency().to_string().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 6, budget.rollover() as i64))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 7,
+            budget.start().format("%Y-%m-%d").to_string().as_str(),
+        ))
+        .unwrap();
+}
+
+impl BudgetRepository for SQliteBudgetRepository {
+    fn find_all(&self) -> Vec<Budget> {
+        // The ids are time ordered, they order the budgets by creation.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM budgets ORDER BY id")
+            .unwrap();
+
+        let mut budgets: Vec<Budget> = vec![];
+        while let Ok(State::Row) = query.next() {
+            budgets.push(read_budget(&query))
+        }
+
+        budgets
+    }
+
+    fn find_by_id(&self, id: BudgetId) -> Option<Budget> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM budgets WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_budget(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, budget: Budget) -> BudgetId {
+        let query = "INSERT INTO budgets (id, name, category_id, tag_id, period, amount, currency, rollover, start) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = BudgetId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_budget(&mut statement, 2, &budget);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: BudgetId,
+        budget: Budget,
+    ) -> Result<BudgetId, FindByIdAndUpdateError> {
+        let query = "UPDATE budgets SET name = ?, category_id = ?, tag_id = ?, period = ?, amount = ?, currency = ?, rollover = ?, start = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_budget(&mut statement, 1, &budget);
+        statement.bind((9, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: BudgetId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM budgets WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        let mut statement = self
+            .connection
+            .prepare("UPDATE budgets SET tag_id = ? WHERE tag_id = ?")
+            .unwrap();
+        statement.bind((1, target.to_string().as_str())).unwrap();
+        statement.bind((2, source.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+    }
+}
@@ -0,0 +1,129 @@
+use crate::app::entities::category::{Category, CategoryId};
+use crate::app::repositories::category_repository::{
+    CategoryRepository, DeleteError, FindByIdAndUpdateError,
+};
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteCategoryRepository {
+    connection: Connection,
+}
+
+impl SQliteCategoryRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+/// Builds a category from the current row of the statement.
+fn read_category(statement: &Statement) -> Category {
+    let id = statement.read::<String, _>("id").unwrap();
+    let parent_id = statement.read::<Option<String>, _>("parent_id").unwrap();
+    let kind = statement.read::<String, _>("kind").unwrap();
+
+    Category::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        parent_id.map(|parent_id| parent_id.try_into().unwrap()),
+        kind.as_str().try_into().unwrap(),
+        statement.read::<Option<String>, _>("color").unwrap(),
+        statement.read::<Option<String>, _>("icon").unwrap(),
+    )
+}
+
+/// Binds the fields of the category, in the order of the `name, parent_id, kind, color, icon` columns,
+/// starting at the given index.
+fn bind_category(statement: &mut Statement, first_index: usize, category: &Category) {
+    let parent_id = category.parent_id().map(|parent_id| parent_id.to_string());
+
+    statement
+        .bind((first_index, category.name().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 1, parent_id.as_deref()))
+        .unwrap();
+    statement
+        .bind((first_index + 2, category.kind().to_string().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 3, category.color().as_deref()))
+        .unwrap();
+    statement
+        .bind((first_index + 4, category.icon().as_deref()))
+        .unwrap();
+}
+
+impl CategoryRepository for SQliteCategoryRepository {
+    fn find_all(&self) -> Vec<Category> {
+        // The ids are time ordered, sorting by them returns the categories in creation order.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM categories ORDER BY id")
+            .unwrap();
+
+        let mut categories: Vec<Category> = vec![];
+        while let Ok(State::Row) = query.next() {
+            categories.push(read_category(&query))
+        }
+
+        categories
+    }
+
+    fn find_by_id(&self, id: CategoryId) -> Option<Category> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM categories WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_category(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, category: Category) -> CategoryId {
+        let query = "INSERT INTO categories (id, name, parent_id, kind, color, icon) VALUES (?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = CategoryId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_category(&mut statement, 2, &category);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: CategoryId,
+        category: Category,
+    ) -> Result<CategoryId, FindByIdAndUpdateError> {
+        let query = "UPDATE categories SET name = ?, parent_id = ?, kind = ?, color = ?, icon = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_category(&mut statement, 1, &category);
+        statement.bind((6, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: CategoryId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM categories WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -2,8 +2,364 @@ use dotenvy::dotenv;
 use sqlite::Connection;
 use std::env;
 
+/// The initial database schema.
+/// Its statements are idempotent, the databases created before the migrations were versioned already have it.
+const INITIAL_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS accounts (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    platform TEXT NOT NULL,
+    balance REAL NOT NULL,
+    account_type TEXT NOT NULL,
+    currency TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transactions (
+    id TEXT PRIMARY KEY NOT NULL,
+    account_id TEXT NOT NULL,
+    transaction_type TEXT NOT NULL,
+    amount REAL NOT NULL,
+    fee REAL NOT NULL,
+    opening_balance REAL,
+    closing_balance REAL,
+    currency TEXT NOT NULL,
+    status TEXT NOT NULL,
+    date TEXT NOT NULL,
+    description TEXT,
+    reference_number TEXT,
+    message TEXT
+);
+
+CREATE INDEX IF NOT EXISTS transactions_by_date ON transactions (date, id);
+CREATE INDEX IF NOT EXISTS transactions_by_account_and_date ON transactions (account_id, date, id);
+";
+
+const CATEGORIES: &str = "
+CREATE TABLE categories (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    parent_id TEXT REFERENCES categories (id),
+    kind TEXT NOT NULL,
+    color TEXT,
+    icon TEXT
+);
+
+ALTER TABLE transactions ADD COLUMN category_id TEXT REFERENCES categories (id);
+CREATE INDEX transactions_by_category ON transactions (category_id);
+";
+
+const TRANSACTION_SPLITS: &str = "
+CREATE TABLE transaction_splits (
+    transaction_id TEXT NOT NULL REFERENCES transactions (id),
+    position INTEGER NOT NULL,
+    category_id TEXT NOT NULL REFERENCES categories (id),
+    amount REAL NOT NULL,
+    memo TEXT,
+    PRIMARY KEY (transaction_id, position)
+);
+
+CREATE INDEX transaction_splits_by_category ON transaction_splits (category_id);
+";
+
+const TAGS: &str = "
+CREATE TABLE tags (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE transaction_tags (
+    transaction_id TEXT NOT NULL REFERENCES transactions (id),
+    tag_id TEXT NOT NULL REFERENCES tags (id),
+    PRIMARY KEY (transaction_id, tag_id)
+);
+
+CREATE INDEX transaction_tags_by_tag ON transaction_tags (tag_id);
+
+CREATE TABLE account_tags (
+    account_id TEXT NOT NULL REFERENCES accounts (id),
+    tag_id TEXT NOT NULL REFERENCES tags (id),
+    PRIMARY KEY (account_id, tag_id)
+);
+
+CREATE INDEX account_tags_by_tag ON account_tags (tag_id);
+";
+
+const PAYEES: &str = "
+CREATE TABLE payees (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    aliases TEXT NOT NULL,
+    patterns TEXT NOT NULL,
+    default_category_id TEXT REFERENCES categories (id)
+);
+
+ALTER TABLE transactions ADD COLUMN payee_id TEXT REFERENCES payees (id);
+CREATE INDEX transactions_by_payee ON transactions (payee_id);
+";
+
+const RULES: &str = "
+CREATE TABLE rules (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    conditions TEXT NOT NULL,
+    actions TEXT NOT NULL
+);
+";
+
+const RECURRING_TRANSACTIONS: &str = "
+CREATE TABLE recurring_transactions (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    template TEXT NOT NULL,
+    schedule TEXT NOT NULL,
+    status TEXT NOT NULL,
+    skipped_dates TEXT NOT NULL,
+    last_occurrence TEXT
+);
+";
+
+const BUDGETS: &str = "
+CREATE TABLE budgets (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    category_id TEXT,
+    tag_id TEXT,
+    period TEXT NOT NULL,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    rollover INTEGER NOT NULL,
+    start TEXT NOT NULL
+);
+";
+
+const GOALS: &str = "
+CREATE TABLE goals (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    target_amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    target_date TEXT NOT NULL,
+    account_ids TEXT NOT NULL
+);
+";
+
+const INTEREST_CONFIGS: &str = "
+CREATE TABLE interest_configs (
+    account_id TEXT PRIMARY KEY NOT NULL,
+    annual_rate REAL NOT NULL,
+    day_count TEXT NOT NULL,
+    compounding TEXT NOT NULL,
+    posting TEXT NOT NULL,
+    start TEXT NOT NULL,
+    posted_until TEXT
+);
+";
+
+const LOANS: &str = "
+CREATE TABLE loans (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    principal REAL NOT NULL,
+    annual_rate REAL NOT NULL,
+    term INTEGER NOT NULL,
+    frequency TEXT NOT NULL,
+    start TEXT NOT NULL
+);
+";
+
+const STATEMENT_CONFIGS: &str = "
+CREATE TABLE statement_configs (
+    account_id TEXT PRIMARY KEY NOT NULL,
+    closing_day INTEGER NOT NULL,
+    grace_period_days INTEGER NOT NULL,
+    minimum_payment_rate REAL NOT NULL,
+    minimum_payment_floor REAL NOT NULL
+);
+";
+
+/// Turns the platform names of the accounts into institutions, referenced by the accounts.
+/// The rules matching on a platform name match on its institution instead.
+///
+/// The platforms differing by the case are the same institution, its kind is guessed from the name.
+/// The ids are UUID v7: the current time in milliseconds, followed by random bits.
+/// The checking accounts held in hand are cash, the ones of the mobile money operators are mobile money.
+/// Money in hand is held at no institution, the rules matching on its platforms match on the cash accounts.
+const INSTITUTIONS: &str = "
+CREATE TABLE institutions (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    country TEXT NOT NULL,
+    logo_url TEXT,
+    fee_schedule TEXT NOT NULL
+);
+
+INSERT INTO institutions (id, name, kind, country, logo_url, fee_schedule)
+SELECT
+    substr(time, 1, 8) || '-' || substr(time, 9, 4)
+        || '-7' || substr(lower(hex(randomblob(2))), 2)
+        || '-' || substr('89ab', 1 + abs(random()) % 4, 1) || substr(lower(hex(randomblob(2))), 2)
+        || '-' || lower(hex(randomblob(6))),
+    name,
+    CASE
+        WHEN lower(name) LIKE '%mobile money%' OR lower(name) LIKE '%momo%'
+            OR lower(name) LIKE '%mtn%' OR lower(name) LIKE '%airtel%' THEN 'mobile_money_operator'
+        WHEN lower(name) LIKE '%sacco%' THEN 'sacco'
+        WHEN lower(name) LIKE '%microfinance%' THEN 'microfinance'
+        WHEN lower(name) LIKE '%bank%' THEN 'bank'
+        ELSE 'other'
+    END,
+    'RW',
+    NULL,
+    '[]'
+FROM (
+    SELECT
+        min(platform) AS name,
+        printf('%012x', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER)) AS time
+    FROM (
+        SELECT trim(platform) AS platform FROM accounts
+        UNION ALL
+        SELECT trim(json_extract(conditions, '$.platform')) FROM rules
+    )
+    WHERE platform IS NOT NULL AND platform <> '' AND lower(platform) NOT IN ('in-hand', 'in hand', 'cash')
+    GROUP BY lower(platform)
+);
+
+ALTER TABLE accounts ADD COLUMN institution_id TEXT REFERENCES institutions (id);
+UPDATE accounts
+SET institution_id = (SELECT id FROM institutions WHERE lower(name) = lower(trim(accounts.platform)));
+UPDATE accounts
+SET account_type = 'cash', institution_id = NULL
+WHERE account_type = 'checking' AND lower(trim(platform)) IN ('in-hand', 'in hand', 'cash');
+UPDATE accounts
+SET account_type = 'mobile_money'
+WHERE account_type = 'checking'
+    AND institution_id IN (SELECT id FROM institutions WHERE kind = 'mobile_money_operator');
+ALTER TABLE accounts DROP COLUMN platform;
+
+UPDATE rules
+SET conditions = json_remove(json_set(conditions, '$.account_type', 'Cash'), '$.platform')
+WHERE lower(trim(json_extract(conditions, '$.platform'))) IN ('in-hand', 'in hand', 'cash');
+UPDATE rules
+SET conditions = json_remove(
+    json_set(
+        conditions,
+        '$.institution_id',
+        (SELECT id FROM institutions WHERE lower(name) = lower(trim(json_extract(rules.conditions, '$.platform'))))
+    ),
+    '$.platform'
+)
+WHERE json_extract(conditions, '$.platform') IS NOT NULL;
+";
+
+/// The identifiers of an account, a kind and a value are used by a single account.
+const ACCOUNT_IDENTIFIERS: &str = "
+CREATE TABLE account_identifiers (
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL,
+    account_id TEXT NOT NULL REFERENCES accounts (id),
+    PRIMARY KEY (kind, value)
+);
+CREATE INDEX account_identifiers_by_account ON account_identifiers (account_id);
+";
+
+/// The status of the accounts, and the history of its changes as JSON. The existing accounts are active.
+const ACCOUNT_STATUSES: &str = "
+ALTER TABLE accounts ADD COLUMN status TEXT NOT NULL DEFAULT 'active';
+ALTER TABLE accounts ADD COLUMN status_changes TEXT NOT NULL DEFAULT '[]';
+";
+
+// No reference to the transactions: the revisions of a deleted transaction are kept.
+const TRANSACTION_REVISIONS: &str = "
+CREATE TABLE transaction_revisions (
+    transaction_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    revised_at TEXT NOT NULL,
+    previous TEXT NOT NULL
+);
+
+CREATE INDEX transaction_revisions_by_transaction ON transaction_revisions (transaction_id, revised_at);
+";
+
+const TRANSACTION_REVERSALS: &str = "
+ALTER TABLE transactions ADD COLUMN reverses TEXT REFERENCES transactions (id);
+
+CREATE INDEX transactions_by_reversed ON transactions (reverses);
+";
+
+/// The last 4 digits of a card are shared by the cards of different banks, only the other kinds
+/// of identifiers are used by a single account. The table is rebuilt to key it by account.
+const SHARED_CARD_IDENTIFIERS: &str = "
+CREATE TABLE account_identifiers_rebuilt (
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL,
+    account_id TEXT NOT NULL REFERENCES accounts (id),
+    PRIMARY KEY (account_id, kind, value)
+);
+INSERT INTO account_identifiers_rebuilt (kind, value, account_id)
+SELECT kind, value, account_id FROM account_identifiers ORDER BY rowid;
+DROP TABLE account_identifiers;
+ALTER TABLE account_identifiers_rebuilt RENAME TO account_identifiers;
+
+CREATE INDEX account_identifiers_by_value ON account_identifiers (kind, value);
+CREATE UNIQUE INDEX account_identifiers_unique ON account_identifiers (kind, value)
+WHERE kind <> 'card_last_four';
+";
+
+/// The database migrations, in the order they are applied.
+/// A database records how many of them it went through (`user_version`), so each one runs once:
+/// a schema change is a new migration appended here, the applied ones must never change.
+const MIGRATIONS: &[&str] = &[
+    INITIAL_SCHEMA,
+    CATEGORIES,
+    TRANSACTION_SPLITS,
+    TAGS,
+    PAYEES,
+    RULES,
+    RECURRING_TRANSACTIONS,
+    BUDGETS,
+    GOALS,
+    INTEREST_CONFIGS,
+    LOANS,
+    STATEMENT_CONFIGS,
+    INSTITUTIONS,
+    ACCOUNT_IDENTIFIERS,
+    ACCOUNT_STATUSES,
+    TRANSACTION_REVISIONS,
+    TRANSACTION_REVERSALS,
+    SHARED_CARD_IDENTIFIERS,
+];
+
 pub fn establish_sqlite_connection() -> Connection {
     dotenv().ok();
     let database_file = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    sqlite::open(database_file).unwrap()
+    let connection = sqlite::open(database_file).unwrap();
+    migrate(&connection);
+    connection
+}
+
+/// Applies the migrations the database did not go through yet.
+pub fn migrate(connection: &Connection) {
+    migrate_until(connection, MIGRATIONS.len());
+}
+
+/// Applies the migrations the database did not go through yet, up to the `version`th one.
+/// Lets the data migrations be tested on a database left at a previous version.
+pub(crate) fn migrate_until(connection: &Connection, version: usize) {
+    let mut statement = connection.prepare("PRAGMA user_version").unwrap();
+    statement.next().unwrap();
+    let current = statement.read::<i64, _>(0).unwrap() as usize;
+    drop(statement);
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().take(version).skip(current) {
+        connection
+            .execute(format!(
+                "BEGIN; {migration} PRAGMA user_version = {}; COMMIT;",
+                index + 1
+            ))
+            .unwrap();
+    }
 }
@@ -0,0 +1,136 @@
+use crate::app::entities::goal::{Goal, GoalId};
+use crate::app::repositories::goal_repository::{
+    DeleteError, FindByIdAndUpdateError, GoalRepository,
+};
+use chrono::NaiveDate;
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteGoalRepository {
+    connection: Connection,
+}
+
+impl SQliteGoalRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+/// Builds a goal from the current row of the statement.
+/// The linked accounts are stored as a JSON list, they are never queried on their own.
+fn read_goal(statement: &Statement) -> Goal {
+    let id = statement.read::<String, _>("id").unwrap();
+    let currency = statement.read::<String, _>("currency").unwrap();
+    let target_date = statement.read::<String, _>("target_date").unwrap();
+    let account_ids = statement.read::<String, _>("account_ids").unwrap();
+
+    Goal::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        (statement.read::<f64, _>("target_amount").unwrap() as f32)
+            .try_into()
+            .unwrap(),
+        currency.try_into().unwrap(),
+        NaiveDate::parse_from_str(&target_date, "%Y-%m-%d").unwrap(),
+        serde_json::from_str(&account_ids).unwrap(),
+    )
+}
+
+/// Binds the fields of the goal, in the order of the
+/// `name, target_amount, currency, target_date, account_ids` columns, starting at the given index.
+fn bind_goal(statement: &mut Statement, first_index: usize, goal: &Goal) {
+    statement.bind((first_index, goal.name().as_str())).unwrap();
+    statement
+        .bind((first_index + 1, goal.target_amount().value() as f64))
+        .unwrap();
+    statement
+        .bind((first_index + 2, goal.currency().to_string().as_str()))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 3,
+            goal.target_date().format("%Y-%m-%d").to_string().as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 4,
+            serde_json::to_string(goal.account_ids()).unwrap().as_str(),
+        ))
+        .unwrap();
+}
+
+impl GoalRepository for SQliteGoalRepository {
+    fn find_all(&self) -> Vec<Goal> {
+        // The ids are time ordered, they order the goals by creation.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM goals ORDER BY id")
+            .unwrap();
+
+        let mut goals: Vec<Goal> = vec![];
+        while let Ok(State::Row) = query.next() {
+            goals.push(read_goal(&query))
+        }
+
+        goals
+    }
+
+    fn find_by_id(&self, id: GoalId) -> Option<Goal> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM goals WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_goal(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, goal: Goal) -> GoalId {
+        let query = "INSERT INTO goals (id, name, target_amount, currency, target_date, account_ids) VALUES (?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = GoalId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_goal(&mut statement, 2, &goal);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: GoalId,
+        goal: Goal,
+    ) -> Result<GoalId, FindByIdAndUpdateError> {
+        let query = "UPDATE goals SET name = ?, target_amount = ?, currency = ?, target_date = ?, account_ids = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_goal(&mut statement, 1, &goal);
+        statement.bind((6, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: GoalId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM goals WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::app::entities::institution::{Institution, InstitutionId, InstitutionKind};
+use crate::app::repositories::institution_repository::{
+    DeleteError, FindByIdAndUpdateError, InstitutionRepository,
+};
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteInstitutionRepository {
+    connection: Connection,
+}
+
+impl SQliteInstitutionRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+fn kind_to_str(kind: InstitutionKind) -> &'static str {
+    match kind {
+        InstitutionKind::Bank => "bank",
+        InstitutionKind::MobileMoneyOperator => "mobile_money_operator",
+        InstitutionKind::Microfinance => "microfinance",
+        InstitutionKind::Sacco => "sacco",
+        InstitutionKind::Broker => "broker",
+        InstitutionKind::Other => "other",
+    }
+}
+
+fn kind_from_str(kind: &str) -> InstitutionKind {
+    match kind {
+        "bank" => InstitutionKind::Bank,
+        "mobile_money_operator" => InstitutionKind::MobileMoneyOperator,
+        "microfinance" => InstitutionKind::Microfinance,
+        "sacco" => InstitutionKind::Sacco,
+        "broker" => InstitutionKind::Broker,
+        _ => InstitutionKind::Other,
+    }
+}
+
+/// Builds an institution from the current row of the statement.
+/// The fee schedule is stored as a JSON array, it is never queried on its own.
+fn read_institution(statement: &Statement) -> Institution {
+    let id = statement.read::<String, _>("id").unwrap();
+    let fee_schedule = statement.read::<String, _>("fee_schedule").unwrap();
+
+    Institution::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        kind_from_str(&statement.read::<String, _>("kind").unwrap()),
+        statement.read::<String, _>("country").unwrap(),
+        statement.read::<Option<String>, _>("logo_url").unwrap(),
+        serde_json::from_str(&fee_schedule).unwrap(),
+    )
+}
+
+/// Binds the fields of the institution, in the order of the
+/// `name, kind, country, logo_url, fee_schedule` columns, starting at the given index.
+fn bind_institution(statement: &mut Statement, first_index: usize, institution: &Institution) {
+    statement
+        .bind((first_index, institution.name().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 1, kind_to_str(institution.kind())))
+        .unwrap();
+    statement
+        .bind((first_index + 2, institution.country().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 3, institution.logo_url().map(String::as_str)))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 4,
+            serde_json::to_string(institution.fee_schedule())
+                .unwrap()
+                .as_str(),
+        ))
+        .unwrap();
+}
+
+impl InstitutionRepository for SQliteInstitutionRepository {
+    fn find_all(&self) -> Vec<Institution> {
+        // The ids are time ordered, they order the institutions by creation.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM institutions ORDER BY id")
+            .unwrap();
+
+        let mut institutions: Vec<Institution> = vec![];
+        while let Ok(State::Row) = query.next() {
+            institutions.push(read_institution(&query))
+        }
+
+        institutions
+    }
+
+    fn find_by_id(&self, id: InstitutionId) -> Option<Institution> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM institutions WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_institution(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, institution: Institution) -> InstitutionId {
+        let query = "INSERT INTO institutions (id, name, kind, country, logo_url, fee_schedule) VALUES (?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = InstitutionId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_institution(&mut statement, 2, &institution);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: InstitutionId,
+        institution: Institution,
+    ) -> Result<InstitutionId, FindByIdAndUpdateError> {
+        let query = "UPDATE institutions SET name = ?, kind = ?, country = ?, logo_url = ?, fee_schedule = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_institution(&mut statement, 1, &institution);
+        statement.bind((6, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: InstitutionId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM institutions WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::interest::InterestConfig;
+use crate::app::repositories::interest_config_repository::{DeleteError, InterestConfigRepository};
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteInterestConfigRepository {
+    connection: Connection,
+}
+
+impl SQliteInterestConfigRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+/// The name of a unit variant, as it is serialized. (Eg: "actual365")
+fn variant_to_text<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+fn variant_from_text<T: DeserializeOwned>(text: String) -> T {
+    serde_json::from_value(serde_json::Value::String(text)).unwrap()
+}
+
+fn read_config(statement: &Statement) -> InterestConfig {
+    let account_id = statement.read::<String, _>("account_id").unwrap();
+    let start = statement.read::<String, _>("start").unwrap();
+    let posted_until = statement.read::<Option<String>, _>("posted_until").unwrap();
+
+    InterestConfig::new(
+        account_id.try_into().unwrap(),
+        statement.read::<f64, _>("annual_rate").unwrap() as f32,
+        variant_from_text(statement.read::<String, _>("day_count").unwrap()),
+        variant_from_text(statement.read::<String, _>("compounding").unwrap()),
+        variant_from_text(statement.read::<String, _>("posting").unwrap()),
+        NaiveDate::parse_from_str(&start, "%Y-%m-%d").unwrap(),
+        posted_until.map(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap()),
+    )
+}
+
+impl InterestConfigRepository for SQliteInterestConfigRepository {
+    fn find_all(&self) -> Vec<InterestConfig> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM interest_configs ORDER BY account_id")
+            .unwrap();
+
+        let mut configs: Vec<InterestConfig> = vec![];
+        while let Ok(State::Row) = query.next() {
+            configs.push(read_config(&query))
+        }
+
+        configs
+    }
+
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<InterestConfig> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM interest_configs WHERE account_id = ?")
+            .unwrap();
+        query.bind((1, account_id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_config(&query)),
+            _ => None,
+        }
+    }
+
+    fn save(&mut self, config: InterestConfig) {
+        let query = "INSERT OR REPLACE INTO interest_configs (account_id, annual_rate, day_count, compounding, posting, start, posted_until) VALUES (?, ?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        statement
+            .bind((1, config.account_id().to_string().as_str()))
+            .unwrap();
+        statement.bind((2, config.annual_rate() as f64)).unwrap();
+        statement
+            .bind((3, variant_to_text(&config.day_count()).as_str()))
+            .unwrap();
+        statement
+            .bind((4, variant_to_text(&config.compounding()).as_str()))
+            .unwrap();
+        statement
+            .bind((5, variant_to_text(&config.posting()).as_str()))
+            .unwrap();
+        statement
+            .bind((6, config.start().format("%Y-%m-%d").to_string().as_str()))
+            .unwrap();
+        statement
+            .bind((
+                7,
+                config
+                    .posted_until()
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .as_deref(),
+            ))
+            .unwrap();
+        statement.next().unwrap();
+    }
+
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM interest_configs WHERE account_id = ?")
+            .unwrap();
+        statement
+            .bind((1, account_id.to_string().as_str()))
+            .unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use crate::app::entities::loan::{Loan, LoanId, PaymentFrequency};
+use crate::app::repositories::loan_repository::{
+    DeleteError, FindByIdAndUpdateError, LoanRepository,
+};
+use chrono::NaiveDate;
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteLoanRepository {
+    connection: Connection,
+}
+
+impl SQliteLoanRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+fn frequency_to_str(frequency: PaymentFrequency) -> &'static str {
+    match frequency {
+        PaymentFrequency::Weekly => "weekly",
+        PaymentFrequency::Biweekly => "biweekly",
+        PaymentFrequency::Monthly => "monthly",
+        PaymentFrequency::Quarterly => "quarterly",
+    }
+}
+
+fn frequency_from_str(frequency: &str) -> PaymentFrequency {
+    match frequency {
+        "weekly" => PaymentFrequency::Weekly,
+        "biweekly" => PaymentFrequency::Biweekly,
+        "quarterly" => PaymentFrequency::Quarterly,
+        _ => PaymentFrequency::Monthly,
+    }
+}
+
+/// Builds a loan from the current row of the statement.
+fn read_loan(statement: &Statement) -> Loan {
+    let id = statement.read::<String, _>("id").unwrap();
+    let account_id = statement.read::<String, _>("account_id").unwrap();
+    let start = statement.read::<String, _>("start").unwrap();
+
+    Loan::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        account_id.try_into().unwrap(),
+        (statement.read::<f64, _>("principal").unwrap() as f32)
+            .try_into()
+            .unwrap(),
+        statement.read::<f64, _>("annual_rate").unwrap() as f32,
+        statement.read::<i64, _>("term").unwrap() as u32,
+        frequency_from_str(&statement.read::<String, _>("frequency").unwrap()),
+        NaiveDate::parse_from_str(&start, "%Y-%m-%d").unwrap(),
+    )
+}
+
+/// Binds the fields of the loan, in the order of the
+/// `name, account_id, principal, annual_rate, term, frequency, start` columns, starting at the given index.
+fn bind_loan(statement: &mut Statement, first_index: usize, loan: &Loan) {
+    statement.bind((first_index, loan.name().as_str())).unwrap();
+    statement
+        .bind((first_index + 1, loan.account_id().to_string().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 2, loan.principal().value() as f64))
+        .unwrap();
+    statement
+        .bind((first_index + 3, loan.annual_rate() as f64))
+        .unwrap();
+    statement
+        .bind((first_index + 4, loan.term() as i64))
+        .unwrap();
+    statement
+        .bind((first_index + 5, frequency_to_str(loan.frequency())))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 6,
+            loan.start().format("%Y-%m-%d").to_string().as_str(),
+        ))
+        .unwrap();
+}
+
+impl LoanRepository for SQliteLoanRepository {
+    fn find_all(&self) -> Vec<Loan> {
+        // The ids are time ordered, they order the loans by creation.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM loans ORDER BY id")
+            .unwrap();
+
+        let mut loans: Vec<Loan> = vec![];
+        while let Ok(State::Row) = query.next() {
+            loans.push(read_loan(&query))
+        }
+
+        loans
+    }
+
+    fn find_by_id(&self, id: LoanId) -> Option<Loan> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM loans WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_loan(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, loan: Loan) -> LoanId {
+        let query = "INSERT INTO loans (id, name, account_id, principal, annual_rate, term, frequency, start) VALUES (?, ?, ?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = LoanId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_loan(&mut statement, 2, &loan);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: LoanId,
+        loan: Loan,
+    ) -> Result<LoanId, FindByIdAndUpdateError> {
+        let query = "UPDATE loans SET name = ?, account_id = ?, principal = ?, annual_rate = ?, term = ?, frequency = ?, start = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_loan(&mut statement, 1, &loan);
+        statement.bind((8, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: LoanId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM loans WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
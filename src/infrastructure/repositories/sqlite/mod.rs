@@ -1,2 +1,27 @@
-mod account_repository;
-mod common;
+pub(crate) mod account_repository;
+pub(crate) mod budget_repository;
+pub(crate) mod category_repository;
+pub(crate) mod common;
+pub(crate) mod goal_repository;
+pub(crate) mod institution_repository;
+pub(crate) mod interest_config_repository;
+pub(crate) mod loan_repository;
+pub(crate) mod payee_repository;
+pub(crate) mod recurring_transaction_repository;
+pub(crate) mod rule_repository;
+pub(crate) mod statement_config_repository;
+pub(crate) mod tag_repository;
+mod test_account_repository;
+mod test_budget_repository;
+mod test_category_repository;
+mod test_goal_repository;
+mod test_institution_repository;
+mod test_interest_config_repository;
+mod test_loan_repository;
+mod test_payee_repository;
+mod test_recurring_transaction_repository;
+mod test_rule_repository;
+mod test_statement_config_repository;
+mod test_tag_repository;
+mod test_transaction_repository;
+pub(crate) mod transaction_repository;
@@ -0,0 +1,137 @@
+use crate::app::entities::payee::{Payee, PayeeId};
+use crate::app::repositories::payee_repository::{
+    DeleteError, FindByIdAndUpdateError, PayeeRepository,
+};
+use sqlite::{Connection, State, Statement};
+
+pub struct SQlitePayeeRepository {
+    connection: Connection,
+}
+
+impl SQlitePayeeRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+/// Builds a payee from the current row of the statement.
+/// The aliases and the patterns are stored as JSON arrays, they are never queried on their own.
+fn read_payee(statement: &Statement) -> Payee {
+    let id = statement.read::<String, _>("id").unwrap();
+    let aliases = statement.read::<String, _>("aliases").unwrap();
+    let patterns = statement.read::<String, _>("patterns").unwrap();
+    let default_category_id = statement
+        .read::<Option<String>, _>("default_category_id")
+        .unwrap();
+
+    Payee::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        serde_json::from_str(&aliases).unwrap(),
+        serde_json::from_str(&patterns).unwrap(),
+        default_category_id.map(|category_id| category_id.try_into().unwrap()),
+    )
+}
+
+/// Binds the fields of the payee, in the order of the `name, aliases, patterns, default_category_id` columns,
+/// starting at the given index.
+fn bind_payee(statement: &mut Statement, first_index: usize, payee: &Payee) {
+    let default_category_id = payee
+        .default_category_id()
+        .map(|category_id| category_id.to_string());
+
+    statement
+        .bind((first_index, payee.name().as_str()))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 1,
+            serde_json::to_string(payee.aliases()).unwrap().as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 2,
+            serde_json::to_string(payee.patterns()).unwrap().as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((first_index + 3, default_category_id.as_deref()))
+        .unwrap();
+}
+
+impl PayeeRepository for SQlitePayeeRepository {
+    fn find_all(&self) -> Vec<Payee> {
+        // The ids are time ordered, sorting by them returns the payees in creation order.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM payees ORDER BY id")
+            .unwrap();
+
+        let mut payees: Vec<Payee> = vec![];
+        while let Ok(State::Row) = query.next() {
+            payees.push(read_payee(&query))
+        }
+
+        payees
+    }
+
+    fn find_by_id(&self, id: PayeeId) -> Option<Payee> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM payees WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_payee(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, payee: Payee) -> PayeeId {
+        let query = "INSERT INTO payees (id, name, aliases, patterns, default_category_id) VALUES (?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = PayeeId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_payee(&mut statement, 2, &payee);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: PayeeId,
+        payee: Payee,
+    ) -> Result<PayeeId, FindByIdAndUpdateError> {
+        let query = "UPDATE payees SET name = ?, aliases = ?, patterns = ?, default_category_id = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_payee(&mut statement, 1, &payee);
+        statement.bind((5, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: PayeeId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM payees WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
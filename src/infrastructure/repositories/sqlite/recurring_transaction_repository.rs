@@ -0,0 +1,236 @@
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::recurring_transaction::{
+    RecurringStatus, RecurringTransaction, RecurringTransactionId,
+};
+use crate::app::entities::tag::{replace_tag, TagId};
+use crate::app::repositories::recurring_transaction_repository::{
+    DeleteError, FindByIdAndUpdateError, RecurringTransactionRepository,
+};
+use crate::infrastructure::repositories::sqlite::transaction_repository::format_date;
+use chrono::{DateTime, Utc};
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteRecurringTransactionRepository {
+    connection: Connection,
+}
+
+impl SQliteRecurringTransactionRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+
+    /// Saves the recurring transactions whose template was changed, together.
+    fn update_all(&mut self, recurring_transactions: Vec<RecurringTransaction>) {
+        self.connection.execute("BEGIN").unwrap();
+        for recurring_transaction in recurring_transactions {
+            let id = recurring_transaction.id().unwrap().clone();
+            self.find_by_id_and_update(id, recurring_transaction)
+                .unwrap();
+        }
+        self.connection.execute("COMMIT").unwrap();
+    }
+}
+
+fn status_to_str(status: &RecurringStatus) -> &'static str {
+    match status {
+        RecurringStatus::Active => "active",
+        RecurringStatus::Paused => "paused",
+    }
+}
+
+fn status_from_str(status: &str) -> RecurringStatus {
+    match status {
+        "paused" => RecurringStatus::Paused,
+        _ => RecurringStatus::Active,
+    }
+}
+
+/// Builds a recurring transaction from the current row of the statement.
+/// The template, the schedule and the skipped dates are stored as JSON, they are never queried on their own.
+fn read_recurring_transaction(statement: &Statement) -> RecurringTransaction {
+    let id = statement.read::<String, _>("id").unwrap();
+    let template = statement.read::<String, _>("template").unwrap();
+    let schedule = statement.read::<String, _>("schedule").unwrap();
+    let skipped_dates = statement.read::<String, _>("skipped_dates").unwrap();
+    let last_occurrence = statement
+        .read::<Option<String>, _>("last_occurrence")
+        .unwrap();
+
+    let mut recurring_transaction = RecurringTransaction::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        serde_json::from_str(&template).unwrap(),
+        serde_json::from_str(&schedule).unwrap(),
+    );
+    recurring_transaction.set_status(status_from_str(
+        &statement.read::<String, _>("status").unwrap(),
+    ));
+    recurring_transaction.set_last_occurrence(last_occurrence.map(|date| {
+        DateTime::parse_from_rfc3339(&date)
+            .unwrap()
+            .with_timezone(&Utc)
+    }));
+    recurring_transaction.set_skipped_dates(serde_json::from_str(&skipped_dates).unwrap());
+
+    recurring_transaction
+}
+
+/// Binds the fields of the recurring transaction, in the order of the
+/// `name, template, schedule, status, skipped_dates, last_occurrence` columns, starting at the given index.
+fn bind_recurring_transaction(
+    statement: &mut Statement,
+    first_index: usize,
+    recurring_transaction: &RecurringTransaction,
+) {
+    statement
+        .bind((first_index, recurring_transaction.name().as_str()))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 1,
+            serde_json::to_string(recurring_transaction.template())
+                .unwrap()
+                .as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 2,
+            serde_json::to_string(recurring_transaction.schedule())
+                .unwrap()
+                .as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 3,
+            status_to_str(recurring_transaction.status()),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 4,
+            serde_json::to_string(recurring_transaction.skipped_dates())
+                .unwrap()
+                .as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 5,
+            recurring_transaction
+                .last_occurrence()
+                .map(format_date)
+                .as_deref(),
+        ))
+        .unwrap();
+}
+
+impl RecurringTransactionRepository for SQliteRecurringTransactionRepository {
+    fn find_all(&self) -> Vec<RecurringTransaction> {
+        // The ids are time ordered, they order the recurring transactions by creation.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM recurring_transactions ORDER BY id")
+            .unwrap();
+
+        let mut recurring_transactions: Vec<RecurringTransaction> = vec![];
+        while let Ok(State::Row) = query.next() {
+            recurring_transactions.push(read_recurring_transaction(&query))
+        }
+
+        recurring_transactions
+    }
+
+    fn find_by_id(&self, id: RecurringTransactionId) -> Option<RecurringTransaction> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM recurring_transactions WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_recurring_transaction(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, recurring_transaction: RecurringTransaction) -> RecurringTransactionId {
+        let query = "INSERT INTO recurring_transactions (id, name, template, schedule, status, skipped_dates, last_occurrence) VALUES (?, ?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = RecurringTransactionId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_recurring_transaction(&mut statement, 2, &recurring_transaction);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: RecurringTransactionId,
+        recurring_transaction: RecurringTransaction,
+    ) -> Result<RecurringTransactionId, FindByIdAndUpdateError> {
+        let query = "UPDATE recurring_transactions SET name = ?, template = ?, schedule = ?, status = ?, skipped_dates = ?, last_occurrence = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_recurring_transaction(&mut statement, 1, &recurring_transaction);
+        statement.bind((7, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: RecurringTransactionId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM recurring_transactions WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        // The templates are stored as JSON, the recurring transactions are rewritten rather than updated in place.
+        let recurring_transactions = self
+            .find_all()
+            .into_iter()
+            .filter(|recurring_transaction| recurring_transaction.template().tags.contains(source))
+            .map(|mut recurring_transaction| {
+                let mut template = recurring_transaction.template().clone();
+                template.tags = replace_tag(&template.tags, source, target);
+                recurring_transaction.set_template(template);
+                recurring_transaction
+            })
+            .collect();
+        self.update_all(recurring_transactions);
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        let recurring_transactions = self
+            .find_all()
+            .into_iter()
+            .filter(|recurring_transaction| {
+                recurring_transaction.template().payee_id.as_ref() == Some(source)
+            })
+            .map(|mut recurring_transaction| {
+                let mut template = recurring_transaction.template().clone();
+                template.payee_id = Some(target.clone());
+                recurring_transaction.set_template(template);
+                recurring_transaction
+            })
+            .collect();
+        self.update_all(recurring_transactions);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::rule::{Rule, RuleId};
+use crate::app::entities::tag::{replace_tag, TagId};
+use crate::app::repositories::rule_repository::{
+    DeleteError, FindByIdAndUpdateError, RuleRepository,
+};
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteRuleRepository {
+    connection: Connection,
+}
+
+impl SQliteRuleRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+
+    /// Saves the rules whose actions were changed, together.
+    fn update_all(&mut self, rules: Vec<Rule>) {
+        self.connection.execute("BEGIN").unwrap();
+        for rule in rules {
+            let id = rule.id().unwrap().clone();
+            self.find_by_id_and_update(id, rule).unwrap();
+        }
+        self.connection.execute("COMMIT").unwrap();
+    }
+}
+
+/// Builds a rule from the current row of the statement.
+/// The conditions and the actions are stored as JSON objects, they are never queried on their own.
+fn read_rule(statement: &Statement) -> Rule {
+    let id = statement.read::<String, _>("id").unwrap();
+    let conditions = statement.read::<String, _>("conditions").unwrap();
+    let actions = statement.read::<String, _>("actions").unwrap();
+
+    Rule::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+        statement.read::<i64, _>("priority").unwrap() as u32,
+        serde_json::from_str(&conditions).unwrap(),
+        serde_json::from_str(&actions).unwrap(),
+    )
+}
+
+/// Binds the fields of the rule, in the order of the `name, priority, conditions, actions` columns,
+/// starting at the given index.
+fn bind_rule(statement: &mut Statement, first_index: usize, rule: &Rule) {
+    statement.bind((first_index, rule.name().as_str())).unwrap();
+    statement
+        .bind((first_index + 1, rule.priority() as i64))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 2,
+            serde_json::to_string(rule.conditions()).unwrap().as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 3,
+            serde_json::to_string(rule.actions()).unwrap().as_str(),
+        ))
+        .unwrap();
+}
+
+impl RuleRepository for SQliteRuleRepository {
+    fn find_all(&self) -> Vec<Rule> {
+        // The ids are time ordered, they order the rules of the same priority by creation.
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM rules ORDER BY priority, id")
+            .unwrap();
+
+        let mut rules: Vec<Rule> = vec![];
+        while let Ok(State::Row) = query.next() {
+            rules.push(read_rule(&query))
+        }
+
+        rules
+    }
+
+    fn find_by_id(&self, id: RuleId) -> Option<Rule> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM rules WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_rule(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, rule: Rule) -> RuleId {
+        let query =
+            "INSERT INTO rules (id, name, priority, conditions, actions) VALUES (?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = RuleId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_rule(&mut statement, 2, &rule);
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: RuleId,
+        rule: Rule,
+    ) -> Result<RuleId, FindByIdAndUpdateError> {
+        let query =
+            "UPDATE rules SET name = ?, priority = ?, conditions = ?, actions = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        bind_rule(&mut statement, 1, &rule);
+        statement.bind((5, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: RuleId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM rules WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        // The actions are stored as JSON, the rules are rewritten rather than updated in place.
+        let rules = self
+            .find_all()
+            .into_iter()
+            .filter(|rule| rule.actions().tags.contains(source))
+            .map(|mut rule| {
+                let mut actions = rule.actions().clone();
+                actions.tags = replace_tag(&actions.tags, source, target);
+                rule.set_actions(actions);
+                rule
+            })
+            .collect();
+        self.update_all(rules);
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        let rules = self
+            .find_all()
+            .into_iter()
+            .filter(|rule| rule.actions().payee_id.as_ref() == Some(source))
+            .map(|mut rule| {
+                let mut actions = rule.actions().clone();
+                actions.payee_id = Some(target.clone());
+                rule.set_actions(actions);
+                rule
+            })
+            .collect();
+        self.update_all(rules);
+    }
+}
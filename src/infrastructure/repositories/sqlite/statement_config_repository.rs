@@ -0,0 +1,82 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::statement::StatementConfig;
+use crate::app::repositories::statement_config_repository::{
+    DeleteError, StatementConfigRepository,
+};
+use sqlite::{Connection, State, Statement};
+
+pub struct SQliteStatementConfigRepository {
+    connection: Connection,
+}
+
+impl SQliteStatementConfigRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+fn read_config(statement: &Statement) -> StatementConfig {
+    let account_id = statement.read::<String, _>("account_id").unwrap();
+
+    StatementConfig::new(
+        account_id.try_into().unwrap(),
+        statement.read::<i64, _>("closing_day").unwrap() as u32,
+        statement.read::<i64, _>("grace_period_days").unwrap() as u32,
+        statement.read::<f64, _>("minimum_payment_rate").unwrap() as f32,
+        (statement.read::<f64, _>("minimum_payment_floor").unwrap() as f32)
+            .try_into()
+            .unwrap(),
+    )
+}
+
+impl StatementConfigRepository for SQliteStatementConfigRepository {
+    fn find_by_account_id(&self, account_id: AccountId) -> Option<StatementConfig> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM statement_configs WHERE account_id = ?")
+            .unwrap();
+        query.bind((1, account_id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_config(&query)),
+            _ => None,
+        }
+    }
+
+    fn save(&mut self, config: StatementConfig) {
+        let query = "INSERT OR REPLACE INTO statement_configs (account_id, closing_day, grace_period_days, minimum_payment_rate, minimum_payment_floor) VALUES (?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        statement
+            .bind((1, config.account_id().to_string().as_str()))
+            .unwrap();
+        statement.bind((2, config.closing_day() as i64)).unwrap();
+        statement
+            .bind((3, config.grace_period_days() as i64))
+            .unwrap();
+        statement
+            .bind((4, config.minimum_payment_rate() as f64))
+            .unwrap();
+        statement
+            .bind((5, config.minimum_payment_floor().value() as f64))
+            .unwrap();
+        statement.next().unwrap();
+    }
+
+    fn delete(&mut self, account_id: AccountId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM statement_configs WHERE account_id = ?")
+            .unwrap();
+        statement
+            .bind((1, account_id.to_string().as_str()))
+            .unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use crate::app::entities::tag::{Tag, TagId};
+use crate::app::repositories::tag_repository::{
+    DeleteError, FindByIdAndUpdateError, TagRepository,
+};
+use sqlite::{Connection, State, Statement, Value};
+use std::collections::HashMap;
+
+pub struct SQliteTagRepository {
+    connection: Connection,
+}
+
+impl SQliteTagRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+}
+
+/// Builds a tag from the current row of the statement.
+fn read_tag(statement: &Statement) -> Tag {
+    let id = statement.read::<String, _>("id").unwrap();
+
+    Tag::new(
+        Some(id.try_into().unwrap()),
+        statement.read::<String, _>("name").unwrap(),
+    )
+}
+
+/// The tables linking the tags to the entities they are attached to.
+#[derive(Clone, Copy)]
+pub(super) enum TagLinks {
+    Transactions,
+    Accounts,
+}
+
+impl TagLinks {
+    /// The table name, and the column referencing the tagged entity.
+    fn table(&self) -> (&'static str, &'static str) {
+        match self {
+            TagLinks::Transactions => ("transaction_tags", "transaction_id"),
+            TagLinks::Accounts => ("account_tags", "account_id"),
+        }
+    }
+
+    /// Reads the tags of the entities, in the order they were attached.
+    ///
+    /// # Returns
+    /// The tags, by entity id. The entities without tags are left out.
+    pub(super) fn load(
+        &self,
+        connection: &Connection,
+        ids: &[String],
+    ) -> HashMap<String, Vec<TagId>> {
+        let mut tags: HashMap<String, Vec<TagId>> = HashMap::new();
+        if ids.is_empty() {
+            return tags;
+        }
+
+        let (table, column) = self.table();
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let mut statement = connection
+            .prepare(format!(
+                "SELECT {column}, tag_id FROM {table} WHERE {column} IN ({placeholders}) ORDER BY rowid"
+            ))
+            .unwrap();
+        let values: Vec<(usize, Value)> = ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (index + 1, id.as_str().into()))
+            .collect();
+        statement.bind(&values[..]).unwrap();
+
+        while let Ok(State::Row) = statement.next() {
+            let id = statement.read::<String, _>(column).unwrap();
+            let tag_id = statement.read::<String, _>("tag_id").unwrap();
+            tags.entry(id).or_default().push(tag_id.try_into().unwrap());
+        }
+
+        tags
+    }
+
+    /// Replaces the tags of an entity.
+    pub(super) fn save(&self, connection: &Connection, id: &str, tags: &[TagId]) {
+        let (table, column) = self.table();
+
+        let mut statement = connection
+            .prepare(format!("DELETE FROM {table} WHERE {column} = ?"))
+            .unwrap();
+        statement.bind((1, id)).unwrap();
+        statement.next().unwrap();
+
+        for tag_id in tags {
+            let mut statement = connection
+                .prepare(format!(
+                    "INSERT OR IGNORE INTO {table} ({column}, tag_id) VALUES (?, ?)"
+                ))
+                .unwrap();
+            statement.bind((1, id)).unwrap();
+            statement.bind((2, tag_id.to_string().as_str())).unwrap();
+            statement.next().unwrap();
+        }
+    }
+
+    /// Moves the entities tagged with `source` to the tag `target`.
+    /// The links are updated in place, so the tags keep their order.
+    pub(super) fn merge(&self, connection: &Connection, source: &TagId, target: &TagId) {
+        let (table, _) = self.table();
+
+        // The entities already tagged with `target` are skipped, their `source` link is deleted next.
+        let mut statement = connection
+            .prepare(format!(
+                "UPDATE OR IGNORE {table} SET tag_id = ? WHERE tag_id = ?"
+            ))
+            .unwrap();
+        statement.bind((1, target.to_string().as_str())).unwrap();
+        statement.bind((2, source.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        let mut statement = connection
+            .prepare(format!("DELETE FROM {table} WHERE tag_id = ?"))
+            .unwrap();
+        statement.bind((1, source.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+    }
+}
+
+impl TagRepository for SQliteTagRepository {
+    fn find_all(&self) -> Vec<Tag> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM tags ORDER BY name")
+            .unwrap();
+
+        let mut tags: Vec<Tag> = vec![];
+        while let Ok(State::Row) = query.next() {
+            tags.push(read_tag(&query))
+        }
+
+        tags
+    }
+
+    fn find_by_id(&self, id: TagId) -> Option<Tag> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM tags WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_tag(&query)),
+            _ => None,
+        }
+    }
+
+    fn find_by_name(&self, name: &str) -> Option<Tag> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM tags WHERE name = ?")
+            .unwrap();
+        query.bind((1, name)).unwrap();
+
+        match query.next() {
+            Ok(State::Row) => Some(read_tag(&query)),
+            _ => None,
+        }
+    }
+
+    fn create(&mut self, tag: Tag) -> TagId {
+        let mut statement = self
+            .connection
+            .prepare("INSERT INTO tags (id, name) VALUES (?, ?)")
+            .unwrap();
+
+        let id = TagId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.bind((2, tag.name().as_str())).unwrap();
+        statement.next().unwrap();
+
+        id
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: TagId,
+        tag: Tag,
+    ) -> Result<TagId, FindByIdAndUpdateError> {
+        let mut statement = self
+            .connection
+            .prepare("UPDATE tags SET name = ? WHERE id = ?")
+            .unwrap();
+        statement.bind((1, tag.name().as_str())).unwrap();
+        statement.bind((2, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: TagId) -> Result<(), DeleteError> {
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM tags WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            return Err(DeleteError::NotFound);
+        }
+
+        Ok(())
+    }
+}
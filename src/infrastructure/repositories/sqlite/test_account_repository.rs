@@ -0,0 +1,354 @@
+#[cfg(test)]
+mod tests_account_repository {
+    use crate::app::entities::account::{Account, AccountId, AccountStatus, StatusChange};
+    use crate::app::entities::institution::InstitutionId;
+    use crate::app::entities::tag::TagId;
+    use crate::app::repositories::account_repository::{
+        AccountRepository, CreateError, DeleteError, FindByIdAndUpdateError,
+    };
+    use crate::app::typing::account_identifier::{AccountIdentifier, IdentifierKind};
+    use crate::app::typing::amount::Amount;
+    use crate::infrastructure::repositories::sqlite::account_repository::SQliteAccountRepository;
+    use crate::infrastructure::repositories::sqlite::common::{migrate, migrate_until};
+    use crate::shared::test_utilities::{
+        assert_accounts_equal, get_random_account, get_random_string, get_sqlite_connection,
+    };
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_empty_find_page() {
+        // GIVEN the sqlite account repository is initialized.
+        // AND no items are added in the repository.
+        let account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+
+        // WHEN the find_page method is called
+        let accounts = account_repository.find_page(None, 10).items;
+
+        // THEN the result is empty
+        assert_eq!(accounts.len(), 0);
+    }
+
+    #[test]
+    fn test_items_added() {
+        // GIVEN the sqlite account repository is initialized.
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+
+        // WHEN two accounts are added in the repository
+        let accounts = [get_random_account(), get_random_account()];
+        for account in &accounts {
+            account_repository.create(account.clone()).unwrap();
+        }
+
+        // AND the find_page method is called
+        let db_accounts = account_repository.find_page(None, 10).items;
+
+        // THEN they are returned in the creation order
+        assert_eq!(db_accounts.len(), 2);
+        assert_accounts_equal(&db_accounts[0], &accounts[0], false);
+        assert_accounts_equal(&db_accounts[1], &accounts[1], false);
+    }
+
+    #[test]
+    fn test_find_page_after_cursor() {
+        // GIVEN the sqlite account repository is initialized.
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+
+        // AND five accounts are added in the repository
+        let ids: Vec<AccountId> = (0..5)
+            .map(|_| account_repository.create(get_random_account()).unwrap())
+            .collect();
+
+        // WHEN the accounts are fetched two at a time
+        let first_page = account_repository.find_page(None, 2);
+        let second_page = account_repository.find_page(Some(ids[1].clone()), 2);
+        let last_page = account_repository.find_page(Some(ids[3].clone()), 2);
+
+        // THEN each page starts after the given account, in creation order
+        let page_ids = |accounts: &[Account]| -> Vec<AccountId> {
+            accounts
+                .iter()
+                .map(|account| account.id().unwrap().clone())
+                .collect()
+        };
+        assert_eq!(page_ids(&first_page.items), ids[0..2]);
+        assert_eq!(page_ids(&second_page.items), ids[2..4]);
+        assert_eq!(page_ids(&last_page.items), ids[4..]);
+
+        // AND the cursor points to the last account of the page, until the last page
+        assert_eq!(first_page.next_cursor, Some(ids[1].to_string()));
+        assert_eq!(second_page.next_cursor, Some(ids[3].to_string()));
+        assert_eq!(last_page.next_cursor, None);
+    }
+
+    #[test]
+    fn test_find_by_id() {
+        // GIVEN the sqlite account repository is initialized.
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+
+        // AND an account with some balance, held at an institution, is added in the repository
+        let mut account = get_random_account();
+        let balance: Amount = 150f32.try_into().unwrap();
+        account.set_balance(balance);
+        account.set_institution_id(Some(InstitutionId::generate()));
+        let new_id = account_repository.create(account.clone()).unwrap();
+
+        // WHEN the new id is queried
+        let created_account = account_repository.find_by_id(new_id.clone()).unwrap();
+
+        // THEN the result is the same as the one input
+        assert_accounts_equal(&created_account, &account, false);
+
+        // AND it has the generated id
+        assert_eq!(created_account.id(), Some(&new_id));
+    }
+
+    #[test]
+    fn test_find_by_unknown_id() {
+        // GIVEN the sqlite account repository is initialized, without any account.
+        let account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+
+        // WHEN a random id is queried
+        let account = account_repository.find_by_id(AccountId::generate());
+
+        // THEN nothing is found
+        assert!(account.is_none());
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_success() {
+        // GIVEN a sqlite account repository with an account.
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+        let mut account = get_random_account();
+        let new_id = account_repository.create(account.clone()).unwrap();
+
+        // WHEN the account is updated with a new name
+        let new_name = get_random_string(20);
+        account.set_name(new_name.clone());
+        let update_result =
+            account_repository.find_by_id_and_update(new_id.clone(), account.clone());
+
+        // THEN it should be updated successfully.
+        assert!(update_result.is_ok());
+
+        // AND the new name should be saved
+        let db_account = account_repository.find_by_id(new_id).unwrap();
+        assert_accounts_equal(&account, &db_account, false);
+        assert_eq!(&new_name, db_account.name())
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_with_invalid_id() {
+        // GIVEN a sqlite account repository without any account.
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+
+        // WHEN an unknown account is updated
+        let result =
+            account_repository.find_by_id_and_update(AccountId::generate(), get_random_account());
+
+        // THEN the error should be a not found error
+        assert_eq!(result.err().unwrap(), FindByIdAndUpdateError::NotFound)
+    }
+
+    #[test]
+    fn test_delete() {
+        // GIVEN a sqlite account repository with an account identified by its phone number
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+        let msisdn = AccountIdentifier::new(IdentifierKind::Msisdn, "0788123456").unwrap();
+        let mut account = get_random_account();
+        account.set_identifiers(vec![msisdn.clone()]);
+        let id = account_repository.create(account.clone()).unwrap();
+
+        // WHEN the account is deleted
+        account_repository.delete(id.clone()).unwrap();
+
+        // THEN it is not found anymore, nor by its identifier
+        assert!(account_repository.find_by_id(id.clone()).is_none());
+        assert!(account_repository.find_by_identifier(&msisdn).is_empty());
+
+        // AND its identifier can be given to another account
+        assert!(account_repository.create(account).is_ok());
+
+        // AND deleting it again fails
+        assert_eq!(account_repository.delete(id), Err(DeleteError::NotFound));
+    }
+
+    #[test]
+    fn test_tags_are_saved_and_merged() {
+        // GIVEN a sqlite account repository with a tagged account.
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+        let savings_id = TagId::generate();
+        let family_id = TagId::generate();
+        let mut account = get_random_account();
+        account.set_tags(vec![savings_id.clone()]);
+        let id = account_repository.create(account.clone()).unwrap();
+
+        // THEN the account is found with its tags
+        let mut account = account_repository.find_by_id(id.clone()).unwrap();
+        assert_eq!(account.tags(), &vec![savings_id.clone()]);
+
+        // WHEN its tags are updated
+        account.set_tags(vec![family_id.clone(), savings_id.clone()]);
+        account_repository
+            .find_by_id_and_update(id.clone(), account)
+            .unwrap();
+
+        // THEN the new tags are saved, in order
+        assert_eq!(
+            account_repository.find_by_id(id.clone()).unwrap().tags(),
+            &vec![family_id.clone(), savings_id.clone()]
+        );
+
+        // WHEN a tag is merged into the other one
+        account_repository.merge_tags(&savings_id, &family_id);
+
+        // THEN the account carries the remaining tag, once
+        let accounts = account_repository.find_page(None, 10).items;
+        assert_eq!(accounts[0].tags(), &vec![family_id]);
+    }
+
+    #[test]
+    fn test_status_is_saved() {
+        // GIVEN a sqlite account repository with an account
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+        let id = account_repository.create(get_random_account()).unwrap();
+
+        // WHEN the account is frozen
+        let mut account = account_repository.find_by_id(id.clone()).unwrap();
+        assert_eq!(account.status(), AccountStatus::Active);
+        let change = StatusChange {
+            status: AccountStatus::Frozen,
+            reason: String::from("Card lost"),
+            changed_at: Utc.with_ymd_and_hms(2026, 1, 15, 8, 30, 0).unwrap(),
+        };
+        account.change_status(change.clone());
+        account_repository
+            .find_by_id_and_update(id.clone(), account)
+            .unwrap();
+
+        // THEN it is read back with its status and its history
+        let account = account_repository.find_by_id(id).unwrap();
+        assert_eq!(account.status(), AccountStatus::Frozen);
+        assert_eq!(account.status_changes(), &vec![change]);
+    }
+
+    #[test]
+    fn test_identifiers_are_unique() {
+        // GIVEN a sqlite account repository with an account identified by its IBAN and its phone number.
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+        let iban = AccountIdentifier::new(IdentifierKind::Iban, "GB82WEST12345698765432").unwrap();
+        let msisdn = AccountIdentifier::new(IdentifierKind::Msisdn, "0788123456").unwrap();
+        let mut account = get_random_account();
+        account.set_identifiers(vec![iban.clone(), msisdn.clone()]);
+        let id = account_repository.create(account).unwrap();
+
+        // WHEN the account is looked up by one of its identifiers
+        let mut accounts = account_repository.find_by_identifier(&msisdn);
+
+        // THEN it is the only one found, with all its identifiers
+        assert_eq!(accounts.len(), 1);
+        let mut found = accounts.remove(0);
+        assert_eq!(found.id(), Some(&id));
+        assert_eq!(found.identifiers(), &vec![iban.clone(), msisdn.clone()]);
+
+        // WHEN another account is created with the same phone number
+        let mut other = get_random_account();
+        other.set_identifiers(vec![msisdn.clone()]);
+        let result = account_repository.create(other.clone());
+
+        // THEN it is rejected
+        assert!(matches!(
+            result,
+            Err(CreateError::DuplicateIdentifier(identifier)) if identifier == msisdn
+        ));
+
+        // WHEN the other account is created without it, then updated with it
+        other.set_identifiers(vec![]);
+        let other_id = account_repository.create(other.clone()).unwrap();
+        other.set_identifiers(vec![msisdn.clone()]);
+        let result = account_repository.find_by_id_and_update(other_id, other);
+
+        // THEN the update is rejected too
+        assert_eq!(
+            result,
+            Err(FindByIdAndUpdateError::DuplicateIdentifier(msisdn.clone()))
+        );
+
+        // WHEN the first account keeps its IBAN only
+        found.set_identifiers(vec![iban.clone()]);
+        account_repository.find_by_id_and_update(id, found).unwrap();
+
+        // THEN the phone number identifies no account anymore
+        assert!(account_repository.find_by_identifier(&msisdn).is_empty());
+        assert_eq!(account_repository.find_by_identifier(&iban).len(), 1);
+    }
+
+    #[test]
+    fn test_card_last_four_is_shared() {
+        // GIVEN a sqlite account repository with an account identified by the last 4 digits of its card
+        let mut account_repository = SQliteAccountRepository::new(get_sqlite_connection());
+        let card = AccountIdentifier::new(IdentifierKind::CardLastFour, "4242").unwrap();
+        let mut account = get_random_account();
+        account.set_identifiers(vec![card.clone()]);
+        let id = account_repository.create(account).unwrap();
+
+        // WHEN another account is created with a card ending with the same digits
+        let mut other = get_random_account();
+        other.set_identifiers(vec![card.clone()]);
+        let other_id = account_repository.create(other.clone()).unwrap();
+
+        // AND it is updated, keeping them
+        other.set_name(get_random_string(10));
+        account_repository
+            .find_by_id_and_update(other_id.clone(), other)
+            .unwrap();
+
+        // THEN both accounts are found by the digits, in creation order
+        let ids: Vec<AccountId> = account_repository
+            .find_by_identifier(&card)
+            .iter()
+            .map(|account| account.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, vec![id, other_id]);
+    }
+
+    #[test]
+    fn test_shared_card_identifiers_migration() {
+        // GIVEN a database where an account is identified by its IBAN and its card
+        let connection = sqlite::open(":memory:").unwrap();
+        migrate_until(&connection, 17);
+        let id = AccountId::generate();
+        connection
+            .execute(format!(
+                "INSERT INTO accounts (id, name, description, institution_id, balance, account_type, currency) \
+                 VALUES ('{id}', 'Current', '', NULL, 0, 'checking', 'RWF'); \
+                 INSERT INTO account_identifiers (kind, value, account_id) \
+                 VALUES ('iban', 'GB82WEST12345698765432', '{id}'), ('card_last_four', '4242', '{id}')"
+            ))
+            .unwrap();
+
+        // WHEN the database is migrated
+        migrate(&connection);
+        let mut account_repository = SQliteAccountRepository::new(connection);
+
+        // THEN the account keeps its identifiers, in order
+        let iban = AccountIdentifier::new(IdentifierKind::Iban, "GB82WEST12345698765432").unwrap();
+        let card = AccountIdentifier::new(IdentifierKind::CardLastFour, "4242").unwrap();
+        assert_eq!(
+            account_repository
+                .find_by_id(id.clone())
+                .unwrap()
+                .identifiers(),
+            &vec![iban.clone(), card.clone()]
+        );
+
+        // AND another account can share the card, but not the IBAN
+        let mut other = get_random_account();
+        other.set_identifiers(vec![card.clone()]);
+        assert!(account_repository.create(other.clone()).is_ok());
+        other.set_identifiers(vec![iban.clone()]);
+        assert!(matches!(
+            account_repository.create(other),
+            Err(CreateError::DuplicateIdentifier(identifier)) if identifier == iban
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests_budget_repository {
+    use crate::app::entities::budget::{Budget, BudgetId, BudgetPeriod, BudgetScope};
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::tag::TagId;
+    use crate::app::repositories::budget_repository::{
+        BudgetRepository, DeleteError, FindByIdAndUpdateError,
+    };
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::sqlite::budget_repository::SQliteBudgetRepository;
+    use crate::shared::test_utilities::get_sqlite_connection;
+    use chrono::NaiveDate;
+
+    fn get_budget(name: &str, scope: BudgetScope) -> Budget {
+        Budget::new(
+            None,
+            String::from(name),
+            scope,
+            BudgetPeriod::Monthly,
+            150000f32.try_into().unwrap(),
+            Currency::RWF,
+            true,
+            NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the sqlite budget repository is initialized.
+        let mut repository = SQliteBudgetRepository::new(get_sqlite_connection());
+
+        // WHEN budgets of a category and of a tag are added
+        let groceries = get_budget("Groceries", BudgetScope::Category(CategoryId::generate()));
+        let groceries_id = repository.create(groceries.clone());
+        let holidays_id =
+            repository.create(get_budget("Holidays", BudgetScope::Tag(TagId::generate())));
+
+        // THEN they can be found by id, with all their fields
+        let mut expected = groceries;
+        expected.set_id(Some(groceries_id.clone()));
+        assert_eq!(repository.find_by_id(groceries_id.clone()), Some(expected));
+
+        // AND they are all listed in creation order
+        let ids: Vec<BudgetId> = repository
+            .find_all()
+            .iter()
+            .map(|budget| budget.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [groceries_id, holidays_id]);
+
+        // AND an unknown budget is not found
+        assert_eq!(repository.find_by_id(BudgetId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_and_delete() {
+        // GIVEN the sqlite budget repository, with a budget
+        let mut repository = SQliteBudgetRepository::new(get_sqlite_connection());
+        let id = repository.create(get_budget(
+            "Groceries",
+            BudgetScope::Category(CategoryId::generate()),
+        ));
+
+        // WHEN it is changed into a yearly budget of a tag, without rollover
+        let mut budget = repository.find_by_id(id.clone()).unwrap();
+        budget.set_scope(BudgetScope::Tag(TagId::generate()));
+        budget.set_period(BudgetPeriod::Yearly);
+        budget.set_amount(900000f32.try_into().unwrap());
+        budget.set_currency(Currency::USD);
+        budget.set_rollover(false);
+        let result = repository.find_by_id_and_update(id.clone(), budget.clone());
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(repository.find_by_id(id.clone()), Some(budget));
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(id.clone()), Ok(()));
+        assert_eq!(repository.find_by_id(id.clone()), None);
+
+        // AND an unknown budget can not be updated nor deleted
+        assert_eq!(
+            repository.find_by_id_and_update(
+                id.clone(),
+                get_budget("Other", BudgetScope::Tag(TagId::generate()))
+            ),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+        assert_eq!(repository.delete(id), Err(DeleteError::NotFound));
+    }
+
+    #[test]
+    fn test_merge_tags() {
+        // GIVEN the sqlite budget repository, with a budget of a tag and one of a category
+        let mut repository = SQliteBudgetRepository::new(get_sqlite_connection());
+        let source = TagId::generate();
+        let tag_budget_id =
+            repository.create(get_budget("Holidays", BudgetScope::Tag(source.clone())));
+        let category_scope = BudgetScope::Category(CategoryId::generate());
+        let category_budget_id = repository.create(get_budget("Groceries", category_scope.clone()));
+
+        // WHEN the tag is merged into another one
+        let target = TagId::generate();
+        repository.merge_tags(&source, &target);
+
+        // THEN the budget of the tag applies to the remaining tag, the other one is unchanged
+        assert_eq!(
+            repository.find_by_id(tag_budget_id).unwrap().scope(),
+            &BudgetScope::Tag(target)
+        );
+        assert_eq!(
+            repository.find_by_id(category_budget_id).unwrap().scope(),
+            &category_scope
+        );
+    }
+}
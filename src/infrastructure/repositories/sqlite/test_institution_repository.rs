@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod tests_institution_repository {
+    use crate::app::entities::account::{AccountId, AccountType};
+    use crate::app::entities::institution::{FeeTier, Institution, InstitutionId, InstitutionKind};
+    use crate::app::entities::rule::{RuleActions, RuleId};
+    use crate::app::entities::transaction::{AccountRef, TransactionStatus, TransactionType};
+    use crate::app::repositories::account_repository::AccountRepository;
+    use crate::app::repositories::institution_repository::{
+        DeleteError, FindByIdAndUpdateError, InstitutionRepository,
+    };
+    use crate::app::repositories::rule_repository::RuleRepository;
+    use crate::infrastructure::repositories::sqlite::account_repository::SQliteAccountRepository;
+    use crate::infrastructure::repositories::sqlite::common::{migrate, migrate_until};
+    use crate::infrastructure::repositories::sqlite::institution_repository::SQliteInstitutionRepository;
+    use crate::infrastructure::repositories::sqlite::rule_repository::SQliteRuleRepository;
+    use crate::shared::test_utilities::{
+        get_random_string, get_random_transaction, get_sqlite_connection,
+    };
+    use std::env;
+
+    fn get_institution(name: &str) -> Institution {
+        Institution::new(
+            None,
+            String::from(name),
+            InstitutionKind::Bank,
+            String::from("RW"),
+            None,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the sqlite institution repository is initialized.
+        let mut repository = SQliteInstitutionRepository::new(get_sqlite_connection());
+
+        // WHEN institutions are added
+        let bank = get_institution("Bank of Kigali");
+        let bank_id = repository.create(bank.clone());
+        let equity_id = repository.create(get_institution("Equity Bank"));
+
+        // THEN they can be found by id
+        let mut expected = bank;
+        expected.set_id(Some(bank_id.clone()));
+        assert_eq!(repository.find_by_id(bank_id.clone()), Some(expected));
+
+        // AND they are all listed in creation order
+        let ids: Vec<InstitutionId> = repository
+            .find_all()
+            .iter()
+            .map(|institution| institution.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [bank_id, equity_id]);
+
+        // AND an unknown institution is not found
+        assert_eq!(repository.find_by_id(InstitutionId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_and_delete() {
+        // GIVEN the sqlite institution repository, with an institution
+        let mut repository = SQliteInstitutionRepository::new(get_sqlite_connection());
+        let id = repository.create(get_institution("MTN"));
+
+        // WHEN its kind, logo and fee schedule are changed
+        let mut institution = repository.find_by_id(id.clone()).unwrap();
+        institution.set_kind(InstitutionKind::MobileMoneyOperator);
+        institution.set_logo_url(Some(String::from("https://example.com/mtn.png")));
+        institution.set_fee_schedule(vec![FeeTier {
+            transaction_type: TransactionType::Expense,
+            min_amount: 0f32.try_into().unwrap(),
+            max_amount: None,
+            flat_fee: 100f32.try_into().unwrap(),
+            rate: 0.5,
+        }]);
+        let result = repository.find_by_id_and_update(id.clone(), institution.clone());
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(repository.find_by_id(id.clone()), Some(institution));
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(id.clone()), Ok(()));
+        assert_eq!(repository.find_by_id(id.clone()), None);
+
+        // AND an unknown institution can not be updated nor deleted
+        assert_eq!(
+            repository.find_by_id_and_update(id.clone(), get_institution("Other")),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+        assert_eq!(repository.delete(id), Err(DeleteError::NotFound));
+    }
+
+    #[test]
+    fn test_platforms_migration() {
+        // GIVEN a database from before the institutions, with accounts on three platforms
+        let file = env::temp_dir().join(format!("institutions-{}.db", get_random_string(10)));
+        let connection = sqlite::open(&file).unwrap();
+        migrate_until(&connection, 12);
+        let momo_id = AccountId::generate();
+        let cash_id = AccountId::generate();
+        let savings_id = AccountId::generate();
+        let checking_id = AccountId::generate();
+        for (id, platform, account_type) in [
+            (&momo_id, "MTN Mobile Money", "checking"),
+            (&cash_id, "In-Hand", "checking"),
+            (&savings_id, "Bank of Kigali", "savings"),
+            (&checking_id, "bank of kigali ", "checking"),
+        ] {
+            connection
+                .execute(format!(
+                    "INSERT INTO accounts (id, name, description, platform, balance, account_type, currency) \
+                     VALUES ('{id}', 'Account', '', '{platform}', 0, '{account_type}', 'RWF')"
+                ))
+                .unwrap();
+        }
+
+        // AND a rule matching the mobile money platform
+        let actions = RuleActions {
+            status: Some(TransactionStatus::Confirmed),
+            ..RuleActions::default()
+        };
+        connection
+            .execute(format!(
+                "INSERT INTO rules (id, name, priority, conditions, actions) \
+                 VALUES ('{}', 'MoMo', 1, '{{\"platform\":\"mtn mobile money\"}}', '{}')",
+                RuleId::generate(),
+                serde_json::to_string(&actions).unwrap()
+            ))
+            .unwrap();
+
+        // WHEN the database is migrated
+        migrate(&connection);
+        let institutions = SQliteInstitutionRepository::new(connection).find_all();
+        let accounts = SQliteAccountRepository::new(sqlite::open(&file).unwrap());
+        let rules = SQliteRuleRepository::new(sqlite::open(&file).unwrap()).find_all();
+        std::fs::remove_file(&file).unwrap();
+
+        // THEN every platform but the money in hand is an institution, the ones differing by the case together
+        let names: Vec<(&str, InstitutionKind)> = institutions
+            .iter()
+            .map(|institution| (institution.name().as_str(), institution.kind()))
+            .collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&("MTN Mobile Money", InstitutionKind::MobileMoneyOperator)));
+        assert!(names.contains(&("Bank of Kigali", InstitutionKind::Bank)));
+        let id_of = |name: &str| {
+            institutions
+                .iter()
+                .find(|institution| institution.name() == name)
+                .and_then(|institution| institution.id().cloned())
+        };
+
+        // AND the accounts reference their institution
+        let momo = accounts.find_by_id(momo_id).unwrap();
+        assert_eq!(momo.institution_id(), id_of("MTN Mobile Money").as_ref());
+        let savings = accounts.find_by_id(savings_id).unwrap();
+        assert_eq!(savings.institution_id(), id_of("Bank of Kigali").as_ref());
+        assert_eq!(savings.account_type(), &AccountType::Savings);
+        let checking = accounts.find_by_id(checking_id).unwrap();
+        assert_eq!(checking.institution_id(), id_of("Bank of Kigali").as_ref());
+        assert_eq!(checking.account_type(), &AccountType::Checking);
+
+        // AND the checking accounts of the mobile money operators, and in hand, get their own types
+        assert_eq!(momo.account_type(), &AccountType::MobileMoney);
+        let cash = accounts.find_by_id(cash_id).unwrap();
+        assert_eq!(cash.account_type(), &AccountType::Cash);
+        assert_eq!(cash.institution_id(), None);
+
+        // AND the rule matches on the institution of the platform
+        assert_eq!(
+            rules[0].conditions().institution_id,
+            id_of("MTN Mobile Money")
+        );
+        assert_eq!(rules[0].actions(), &actions);
+    }
+
+    #[test]
+    fn test_platforms_migration_in_hand_rules() {
+        // GIVEN a database from before the institutions, with an account in hand and one at a bank
+        let file = env::temp_dir().join(format!("institutions-{}.db", get_random_string(10)));
+        let connection = sqlite::open(&file).unwrap();
+        migrate_until(&connection, 12);
+        let cash_id = AccountId::generate();
+        let bank_id = AccountId::generate();
+        for (id, platform) in [(&cash_id, "In-Hand"), (&bank_id, "Bank of Kigali")] {
+            connection
+                .execute(format!(
+                    "INSERT INTO accounts (id, name, description, platform, balance, account_type, currency) \
+                     VALUES ('{id}', 'Account', '', '{platform}', 0, 'checking', 'RWF')"
+                ))
+                .unwrap();
+        }
+
+        // AND a rule matching the platform in hand, ignoring the case
+        connection
+            .execute(format!(
+                "INSERT INTO rules (id, name, priority, conditions, actions) \
+                 VALUES ('{}', 'Pocket money', 1, '{{\"platform\":\" in-hand\"}}', '{{}}')",
+                RuleId::generate(),
+            ))
+            .unwrap();
+        let mut matched_before: Vec<AccountId> = vec![];
+        connection
+            .iterate(
+                "SELECT accounts.id FROM accounts, rules \
+                 WHERE lower(trim(accounts.platform)) = lower(trim(json_extract(rules.conditions, '$.platform')))",
+                |row| {
+                    matched_before.push(row[0].1.unwrap().parse().unwrap());
+                    true
+                },
+            )
+            .unwrap();
+        assert_eq!(matched_before, std::slice::from_ref(&cash_id));
+
+        // WHEN the database is migrated
+        migrate(&connection);
+        let institutions = SQliteInstitutionRepository::new(connection).find_all();
+        let accounts = SQliteAccountRepository::new(sqlite::open(&file).unwrap());
+        let rules = SQliteRuleRepository::new(sqlite::open(&file).unwrap()).find_all();
+        std::fs::remove_file(&file).unwrap();
+
+        // THEN the rule matches on the cash accounts, and no institution is made for the money in hand
+        assert_eq!(rules[0].conditions().account_type, Some(AccountType::Cash));
+        assert_eq!(rules[0].conditions().institution_id, None);
+        assert_eq!(institutions.len(), 1);
+
+        // AND it still matches the transactions of the account in hand only
+        let matched_after: Vec<AccountId> = [cash_id.clone(), bank_id]
+            .into_iter()
+            .filter(|id| {
+                let account = accounts.find_by_id(id.clone()).unwrap();
+                let mut transaction = get_random_transaction();
+                transaction.set_account(AccountRef::Id(id.clone()));
+                rules[0].matches(&transaction, Some(&account))
+            })
+            .collect();
+        assert_eq!(matched_after, matched_before);
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests_interest_config_repository {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::interest::{Compounding, DayCount, InterestConfig, PostingSchedule};
+    use crate::app::repositories::interest_config_repository::{
+        DeleteError, InterestConfigRepository,
+    };
+    use crate::infrastructure::repositories::sqlite::interest_config_repository::SQliteInterestConfigRepository;
+    use crate::shared::test_utilities::get_sqlite_connection;
+    use chrono::NaiveDate;
+
+    fn get_config(account_id: AccountId) -> InterestConfig {
+        InterestConfig::new(
+            account_id,
+            7.5,
+            DayCount::Actual365,
+            Compounding::Monthly,
+            PostingSchedule::Quarterly,
+            NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_save_find_and_delete() {
+        // GIVEN the sqlite interest configuration repository is initialized.
+        let mut repository = SQliteInterestConfigRepository::new(get_sqlite_connection());
+        let account_id = AccountId::generate();
+
+        // WHEN the configurations of two accounts are saved
+        let config = get_config(account_id.clone());
+        repository.save(config.clone());
+        repository.save(get_config(AccountId::generate()));
+
+        // THEN they can be found by account
+        assert_eq!(
+            repository.find_by_account_id(account_id.clone()),
+            Some(config.clone())
+        );
+        assert_eq!(repository.find_all().len(), 2);
+
+        // WHEN the configuration of the account is saved again, once posted
+        let mut posted = config;
+        posted.set_posted_until(NaiveDate::from_ymd_opt(2026, 12, 31));
+        posted.set_day_count(DayCount::Thirty360);
+        repository.save(posted.clone());
+
+        // THEN it replaces the previous one
+        assert_eq!(
+            repository.find_by_account_id(account_id.clone()),
+            Some(posted)
+        );
+        assert_eq!(repository.find_all().len(), 2);
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(account_id.clone()), Ok(()));
+        assert_eq!(repository.find_by_account_id(account_id.clone()), None);
+        assert_eq!(repository.delete(account_id), Err(DeleteError::NotFound));
+    }
+}
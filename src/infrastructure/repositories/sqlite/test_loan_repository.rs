@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests_loan_repository {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::loan::{Loan, LoanId, PaymentFrequency};
+    use crate::app::repositories::loan_repository::{
+        DeleteError, FindByIdAndUpdateError, LoanRepository,
+    };
+    use crate::infrastructure::repositories::sqlite::loan_repository::SQliteLoanRepository;
+    use crate::shared::test_utilities::get_sqlite_connection;
+    use chrono::NaiveDate;
+
+    fn get_loan(name: &str) -> Loan {
+        Loan::new(
+            None,
+            String::from(name),
+            AccountId::generate(),
+            500000f32.try_into().unwrap(),
+            16.5,
+            24,
+            PaymentFrequency::Monthly,
+            NaiveDate::from_ymd_opt(2026, 6, 30).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the sqlite loan repository is initialized.
+        let mut repository = SQliteLoanRepository::new(get_sqlite_connection());
+
+        // WHEN loans are added
+        let car_loan = get_loan("Car loan");
+        let car_loan_id = repository.create(car_loan.clone());
+        let mortgage_id = repository.create(get_loan("Mortgage"));
+
+        // THEN they can be found by id, with their account
+        let mut expected = car_loan;
+        expected.set_id(Some(car_loan_id.clone()));
+        assert_eq!(repository.find_by_id(car_loan_id.clone()), Some(expected));
+
+        // AND they are all listed in creation order
+        let ids: Vec<LoanId> = repository
+            .find_all()
+            .iter()
+            .map(|loan| loan.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [car_loan_id, mortgage_id]);
+
+        // AND an unknown loan is not found
+        assert_eq!(repository.find_by_id(LoanId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_and_delete() {
+        // GIVEN the sqlite loan repository, with a loan
+        let mut repository = SQliteLoanRepository::new(get_sqlite_connection());
+        let id = repository.create(get_loan("Car loan"));
+
+        // WHEN its terms and its account are changed
+        let mut loan = repository.find_by_id(id.clone()).unwrap();
+        loan.set_account_id(AccountId::generate());
+        loan.set_principal(800000f32.try_into().unwrap());
+        loan.set_annual_rate(12.0);
+        loan.set_term(52);
+        loan.set_frequency(PaymentFrequency::Biweekly);
+        loan.set_start(NaiveDate::from_ymd_opt(2026, 9, 1).unwrap());
+        let result = repository.find_by_id_and_update(id.clone(), loan.clone());
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(repository.find_by_id(id.clone()), Some(loan));
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(id.clone()), Ok(()));
+        assert_eq!(repository.find_by_id(id.clone()), None);
+
+        // AND an unknown loan can not be updated nor deleted
+        assert_eq!(
+            repository.find_by_id_and_update(id.clone(), get_loan("Other")),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+        assert_eq!(repository.delete(id), Err(DeleteError::NotFound));
+    }
+}
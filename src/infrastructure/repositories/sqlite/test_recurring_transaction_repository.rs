@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests_recurring_transaction_repository {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::entities::recurring_transaction::{
+        BusinessDayConvention, Frequency, RecurringStatus, RecurringTransaction,
+        RecurringTransactionId, Schedule, TransactionTemplate,
+    };
+    use crate::app::entities::tag::TagId;
+    use crate::app::entities::transaction::TransactionType;
+    use crate::app::repositories::recurring_transaction_repository::{
+        DeleteError, FindByIdAndUpdateError, RecurringTransactionRepository,
+    };
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::sqlite::recurring_transaction_repository::SQliteRecurringTransactionRepository;
+    use crate::shared::test_utilities::get_sqlite_connection;
+    use chrono::{DateTime, NaiveDate, Utc};
+
+    fn get_recurring_transaction(name: &str) -> RecurringTransaction {
+        RecurringTransaction::new(
+            None,
+            String::from(name),
+            TransactionTemplate {
+                account_id: AccountId::generate(),
+                transaction_type: TransactionType::Expense,
+                amount: 300000f32.try_into().unwrap(),
+                fee: 500f32.try_into().unwrap(),
+                currency: Currency::RWF,
+                description: Some(String::from("Rent")),
+                message: None,
+                category_id: None,
+                payee_id: Some(PayeeId::generate()),
+                tags: vec![TagId::generate()],
+            },
+            Schedule {
+                frequency: Frequency::Monthly,
+                interval: 1,
+                start: DateTime::parse_from_rfc3339("2026-01-31T08:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+                end: None,
+                count: Some(12),
+                business_day: BusinessDayConvention::Following,
+            },
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the sqlite recurring transaction repository is initialized.
+        let mut repository = SQliteRecurringTransactionRepository::new(get_sqlite_connection());
+
+        // WHEN recurring transactions are added
+        let rent = get_recurring_transaction("Rent");
+        let rent_id = repository.create(rent.clone());
+        let fees_id = repository.create(get_recurring_transaction("School fees"));
+
+        // THEN they can be found by id, with their template and schedule
+        let found = repository.find_by_id(rent_id.clone()).unwrap();
+        assert_eq!(found.id(), Some(&rent_id));
+        assert_eq!(found.name(), "Rent");
+        assert_eq!(found.template(), rent.template());
+        assert_eq!(found.schedule(), rent.schedule());
+        assert_eq!(found.status(), &RecurringStatus::Active);
+
+        // AND they are all listed in creation order
+        let ids: Vec<RecurringTransactionId> = repository
+            .find_all()
+            .iter()
+            .map(|recurring_transaction| recurring_transaction.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [rent_id, fees_id]);
+
+        // AND an unknown recurring transaction is not found
+        assert_eq!(
+            repository.find_by_id(RecurringTransactionId::generate()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_and_delete() {
+        // GIVEN the sqlite recurring transaction repository, with a recurring transaction
+        let mut repository = SQliteRecurringTransactionRepository::new(get_sqlite_connection());
+        let id = repository.create(get_recurring_transaction("Rent"));
+
+        // WHEN it is paused, after an occurrence, with a future one skipped
+        let mut recurring_transaction = repository.find_by_id(id.clone()).unwrap();
+        recurring_transaction.set_status(RecurringStatus::Paused);
+        recurring_transaction.set_skipped_dates(vec![NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()]);
+        recurring_transaction.set_last_occurrence(Some(
+            DateTime::parse_from_rfc3339("2026-02-02T08:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        ));
+        let result = repository.find_by_id_and_update(id.clone(), recurring_transaction.clone());
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(
+            repository.find_by_id(id.clone()),
+            Some(recurring_transaction)
+        );
+
+        // WHEN it is deleted
+        // THEN it is not found anymore
+        assert_eq!(repository.delete(id.clone()), Ok(()));
+        assert_eq!(repository.find_by_id(id.clone()), None);
+
+        // AND an unknown recurring transaction can not be updated nor deleted
+        assert_eq!(
+            repository.find_by_id_and_update(id.clone(), get_recurring_transaction("Other")),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+        assert_eq!(repository.delete(id), Err(DeleteError::NotFound));
+    }
+
+    #[test]
+    fn test_merge_tags_and_payees() {
+        // GIVEN the sqlite recurring transaction repository, with a recurring transaction
+        let mut repository = SQliteRecurringTransactionRepository::new(get_sqlite_connection());
+        let recurring_transaction = get_recurring_transaction("Rent");
+        let source_tag = recurring_transaction.template().tags[0].clone();
+        let source_payee = recurring_transaction.template().payee_id.clone().unwrap();
+        let id = repository.create(recurring_transaction);
+
+        // WHEN its tag and its payee are merged into other ones
+        let (target_tag, target_payee) = (TagId::generate(), PayeeId::generate());
+        repository.merge_tags(&source_tag, &target_tag);
+        repository.merge_payees(&source_payee, &target_payee);
+
+        // THEN it references the remaining tag and payee
+        let recurring_transaction = repository.find_by_id(id).unwrap();
+        assert_eq!(recurring_transaction.template().tags, vec![target_tag]);
+        assert_eq!(
+            recurring_transaction.template().payee_id,
+            Some(target_payee)
+        );
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests_rule_repository {
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::entities::rule::{Rule, RuleActions, RuleConditions, RuleId};
+    use crate::app::entities::tag::TagId;
+    use crate::app::entities::transaction::{TransactionStatus, TransactionType};
+    use crate::app::repositories::rule_repository::{
+        DeleteError, FindByIdAndUpdateError, RuleRepository,
+    };
+    use crate::infrastructure::repositories::sqlite::rule_repository::SQliteRuleRepository;
+    use crate::shared::test_utilities::get_sqlite_connection;
+
+    fn get_rule(name: &str, priority: u32, actions: RuleActions) -> Rule {
+        Rule::new(
+            None,
+            String::from(name),
+            priority,
+            RuleConditions {
+                description: Some(String::from(r"simba\s*super")),
+                min_amount: Some(10f32.try_into().unwrap()),
+                transaction_type: Some(TransactionType::Expense),
+                ..Default::default()
+            },
+            actions,
+        )
+    }
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the sqlite rule repository is initialized.
+        let mut rule_repository = SQliteRuleRepository::new(get_sqlite_connection());
+
+        // WHEN rules of different priorities are added
+        let rule = get_rule(
+            "Groceries",
+            2,
+            RuleActions {
+                category_id: Some(CategoryId::generate()),
+                payee_id: Some(PayeeId::generate()),
+                tags: vec![TagId::generate()],
+                status: Some(TransactionStatus::Pending),
+            },
+        );
+        let groceries_id = rule_repository.create(rule.clone());
+        let late_id = rule_repository.create(get_rule("Late", 2, RuleActions::default()));
+        let first_id = rule_repository.create(get_rule("First", 0, RuleActions::default()));
+
+        // THEN they can be found by id, with their conditions and actions
+        let groceries = rule_repository.find_by_id(groceries_id.clone()).unwrap();
+        assert_eq!(groceries.id(), Some(&groceries_id));
+        assert_eq!(groceries.name(), "Groceries");
+        assert_eq!(groceries.priority(), 2);
+        assert_eq!(groceries.conditions(), rule.conditions());
+        assert_eq!(groceries.actions(), rule.actions());
+
+        // AND they are all listed by priority, then in creation order
+        let ids: Vec<RuleId> = rule_repository
+            .find_all()
+            .iter()
+            .map(|rule| rule.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [first_id, groceries_id, late_id]);
+
+        // AND an unknown rule is not found
+        assert_eq!(rule_repository.find_by_id(RuleId::generate()), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update_and_delete() {
+        // GIVEN the sqlite rule repository, with a rule
+        let mut rule_repository = SQliteRuleRepository::new(get_sqlite_connection());
+        let id = rule_repository.create(get_rule("Groceries", 0, RuleActions::default()));
+
+        // WHEN the rule is updated
+        let mut rule = rule_repository.find_by_id(id.clone()).unwrap();
+        rule.set_priority(5);
+        rule.set_conditions(RuleConditions::default());
+        let result = rule_repository.find_by_id_and_update(id.clone(), rule.clone());
+
+        // THEN the update is saved
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(rule_repository.find_by_id(id.clone()), Some(rule));
+
+        // WHEN the rule is deleted
+        // THEN it is not found anymore
+        assert_eq!(rule_repository.delete(id.clone()), Ok(()));
+        assert_eq!(rule_repository.find_by_id(id.clone()), None);
+
+        // AND an unknown rule can not be updated nor deleted
+        assert_eq!(
+            rule_repository
+                .find_by_id_and_update(id.clone(), get_rule("Other", 0, RuleActions::default())),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+        assert_eq!(rule_repository.delete(id), Err(DeleteError::NotFound));
+    }
+
+    #[test]
+    fn test_merge_tags_and_payees() {
+        // GIVEN the sqlite rule repository, with a rule setting a payee and two tags
+        let mut rule_repository = SQliteRuleRepository::new(get_sqlite_connection());
+        let (source_tag, target_tag) = (TagId::generate(), TagId::generate());
+        let (source_payee, target_payee) = (PayeeId::generate(), PayeeId::generate());
+        let id = rule_repository.create(get_rule(
+            "Groceries",
+            0,
+            RuleActions {
+                payee_id: Some(source_payee.clone()),
+                tags: vec![source_tag.clone(), target_tag.clone()],
+                ..Default::default()
+            },
+        ));
+
+        // WHEN the tag and the payee are merged into other ones
+        rule_repository.merge_tags(&source_tag, &target_tag);
+        rule_repository.merge_payees(&source_payee, &target_payee);
+
+        // THEN the rule references the remaining tag, once, and the remaining payee
+        let rule = rule_repository.find_by_id(id).unwrap();
+        assert_eq!(rule.actions().tags, vec![target_tag]);
+        assert_eq!(rule.actions().payee_id, Some(target_payee));
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests_tag_repository {
+    use crate::app::entities::tag::{Tag, TagId};
+    use crate::app::repositories::tag_repository::{
+        DeleteError, FindByIdAndUpdateError, TagRepository,
+    };
+    use crate::infrastructure::repositories::sqlite::tag_repository::SQliteTagRepository;
+    use crate::shared::test_utilities::get_sqlite_connection;
+
+    #[test]
+    fn test_create_and_find() {
+        // GIVEN the sqlite tag repository is initialized.
+        let mut tag_repository = SQliteTagRepository::new(get_sqlite_connection());
+
+        // WHEN two tags are added
+        let work_id = tag_repository.create(Tag::new(None, String::from("work")));
+        let trip_id = tag_repository.create(Tag::new(None, String::from("trip-musanze")));
+
+        // THEN they can be found by id and by name
+        let trip = tag_repository.find_by_id(trip_id.clone()).unwrap();
+        assert_eq!(trip.id(), Some(&trip_id));
+        assert_eq!(trip.name(), "trip-musanze");
+        assert_eq!(
+            tag_repository.find_by_name("work").unwrap().id(),
+            Some(&work_id)
+        );
+
+        // AND they are all listed, sorted by name
+        let ids: Vec<TagId> = tag_repository
+            .find_all()
+            .iter()
+            .map(|tag| tag.id().unwrap().clone())
+            .collect();
+        assert_eq!(ids, [trip_id, work_id]);
+
+        // AND an unknown tag is not found
+        assert_eq!(tag_repository.find_by_id(TagId::generate()), None);
+        assert_eq!(tag_repository.find_by_name("unknown"), None);
+    }
+
+    #[test]
+    fn test_find_by_id_and_update() {
+        // GIVEN the sqlite tag repository, with a tag
+        let mut tag_repository = SQliteTagRepository::new(get_sqlite_connection());
+        let id = tag_repository.create(Tag::new(None, String::from("work")));
+
+        // WHEN the tag is renamed
+        let result =
+            tag_repository.find_by_id_and_update(id.clone(), Tag::new(None, String::from("job")));
+
+        // THEN it is found by its new name only
+        assert_eq!(result, Ok(id.clone()));
+        assert_eq!(tag_repository.find_by_name("job").unwrap().id(), Some(&id));
+        assert_eq!(tag_repository.find_by_name("work"), None);
+
+        // AND an unknown tag can not be updated
+        assert_eq!(
+            tag_repository
+                .find_by_id_and_update(TagId::generate(), Tag::new(None, String::from("other"))),
+            Err(FindByIdAndUpdateError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_delete() {
+        // GIVEN the sqlite tag repository, with a tag
+        let mut tag_repository = SQliteTagRepository::new(get_sqlite_connection());
+        let id = tag_repository.create(Tag::new(None, String::from("work")));
+
+        // WHEN the tag is deleted
+        // THEN it is not found anymore
+        assert_eq!(tag_repository.delete(id.clone()), Ok(()));
+        assert_eq!(tag_repository.find_by_id(id.clone()), None);
+
+        // AND it can not be deleted twice
+        assert_eq!(tag_repository.delete(id), Err(DeleteError::NotFound));
+    }
+}
@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests_transaction_repository {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::transaction::{
+        AccountRef, RevisionKind, TransactionId, TransactionRevision, TransactionSplit,
+        TransactionVersion,
+    };
+    use crate::app::repositories::transaction_query::TransactionQuery;
+    use crate::app::repositories::transaction_repository::{DeleteError, TransactionRepository};
+    use crate::infrastructure::repositories::sqlite::transaction_repository::SQliteTransactionRepository;
+    use crate::shared::test_utilities::{
+        assert_transactions_equal, get_random_transaction, get_sqlite_connection,
+    };
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_empty_find() {
+        // GIVEN the sqlite transaction repository is initialized.
+        // AND no items are added in the repository.
+        let transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+
+        // WHEN the find method is called
+        let transactions = transaction_repository
+            .find(&TransactionQuery::default())
+            .items;
+
+        // THEN the result is empty
+        assert_eq!(transactions.len(), 0);
+    }
+
+    #[test]
+    fn test_items_added() {
+        // GIVEN the sqlite transaction repository is initialized.
+        let mut transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+
+        // WHEN a transaction is added in the repository
+        let mut transaction = get_random_transaction();
+        let transaction_id = transaction_repository.create(transaction.clone());
+
+        // AND the find method is called
+        let transactions = transaction_repository
+            .find(&TransactionQuery::default())
+            .items;
+
+        // THEN it is the only one, with the generated id
+        assert_eq!(transactions.len(), 1);
+        transaction.set_id(Some(transaction_id));
+        assert_eq!(transactions[0], transaction);
+    }
+
+    #[test]
+    fn test_find_by_id() {
+        // GIVEN the sqlite transaction repository with a transaction.
+        let mut transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+        let transaction = get_random_transaction();
+        let new_id = transaction_repository.create(transaction.clone());
+
+        // WHEN the new id is queried
+        let created_transaction = transaction_repository.find_by_id(new_id);
+
+        // THEN the result is the same as the one input
+        assert_transactions_equal(&created_transaction.unwrap(), &transaction, false);
+
+        // AND an unknown id is not found
+        assert!(transaction_repository
+            .find_by_id(TransactionId::generate())
+            .is_none());
+    }
+
+    #[test]
+    fn test_splits_are_saved() {
+        // GIVEN the sqlite transaction repository is initialized.
+        let mut transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+
+        // AND a transaction split across two categories, next to a categorized one
+        let mut split_transaction = get_random_transaction();
+        split_transaction.set_amount(30f32.try_into().unwrap());
+        split_transaction.set_splits(vec![
+            TransactionSplit {
+                category_id: CategoryId::generate(),
+                amount: 20f32.try_into().unwrap(),
+                memo: Some(String::from("Vegetables")),
+            },
+            TransactionSplit {
+                category_id: CategoryId::generate(),
+                amount: 10f32.try_into().unwrap(),
+                memo: None,
+            },
+        ]);
+        let mut categorized_transaction = get_random_transaction();
+        categorized_transaction.set_category_id(Some(CategoryId::generate()));
+
+        // WHEN they are added in the repository
+        let split_id = transaction_repository.create(split_transaction.clone());
+        let categorized_id = transaction_repository.create(categorized_transaction.clone());
+        split_transaction.set_id(Some(split_id.clone()));
+        categorized_transaction.set_id(Some(categorized_id));
+
+        // THEN the splits are read back, in order, by id
+        assert_eq!(
+            transaction_repository.find_by_id(split_id),
+            Some(split_transaction.clone())
+        );
+
+        // AND when listing
+        let transactions = transaction_repository
+            .find(&TransactionQuery::default())
+            .items;
+        assert!(transactions.contains(&split_transaction));
+        assert!(transactions.contains(&categorized_transaction));
+    }
+
+    #[test]
+    fn test_delete_keeps_revisions() {
+        // GIVEN the sqlite transaction repository with a split transaction
+        let mut transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+        let mut transaction = get_random_transaction();
+        transaction.set_amount(30f32.try_into().unwrap());
+        transaction.set_splits(vec![TransactionSplit {
+            category_id: CategoryId::generate(),
+            amount: 30f32.try_into().unwrap(),
+            memo: None,
+        }]);
+        let transaction_id = transaction_repository.create(transaction.clone());
+        transaction.set_id(Some(transaction_id.clone()));
+
+        // AND the versions it had before being deleted
+        let revisions: Vec<TransactionRevision> = [RevisionKind::Updated, RevisionKind::Deleted]
+            .into_iter()
+            .enumerate()
+            .map(|(index, kind)| TransactionRevision {
+                transaction_id: transaction_id.clone(),
+                kind,
+                revised_at: Utc
+                    .with_ymd_and_hms(2026, 3, 10 + index as u32, 8, 30, 0)
+                    .unwrap(),
+                previous: TransactionVersion::from(&transaction),
+            })
+            .collect();
+        for revision in revisions.clone() {
+            transaction_repository.add_revision(revision);
+        }
+
+        // WHEN it is deleted
+        let result = transaction_repository.delete(transaction_id.clone());
+
+        // THEN it is not found anymore, nor listed
+        assert_eq!(result, Ok(()));
+        assert!(transaction_repository
+            .find_by_id(transaction_id.clone())
+            .is_none());
+        assert!(transaction_repository
+            .find(&TransactionQuery::default())
+            .items
+            .is_empty());
+
+        // AND its revisions are kept, the oldest first
+        assert_eq!(
+            transaction_repository.find_revisions(&transaction_id),
+            revisions
+        );
+
+        // AND it can not be deleted twice
+        assert_eq!(
+            transaction_repository.delete(transaction_id),
+            Err(DeleteError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_reversal_is_saved() {
+        // GIVEN the sqlite transaction repository with a transaction
+        let mut transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+        let reversed_id = transaction_repository.create(get_random_transaction());
+
+        // WHEN its reversal is added
+        let mut reversal = get_random_transaction();
+        reversal.set_reverses(Some(reversed_id.clone()));
+        let reversal_id = transaction_repository.create(reversal);
+
+        // THEN the reversal is read back with the transaction it reverses
+        let reversal = transaction_repository.find_by_id(reversal_id).unwrap();
+        assert_eq!(reversal.reverses(), Some(&reversed_id));
+
+        // AND the reversed transaction reverses nothing
+        let reversed = transaction_repository.find_by_id(reversed_id).unwrap();
+        assert_eq!(reversed.reverses(), None);
+    }
+
+    #[test]
+    fn test_balance_at() {
+        // GIVEN the sqlite transaction repository with the history of an account
+        let mut transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+        let account_id = AccountId::generate();
+        for (opening_balance, closing_balance, day) in [(0.0, 100.0, 10), (100.0, 80.0, 20)] {
+            let mut transaction = get_random_transaction();
+            transaction.set_account(AccountRef::Id(account_id.clone()));
+            transaction.set_opening_balance(Some(f32::try_into(opening_balance).unwrap()));
+            transaction.set_closing_balance(Some(f32::try_into(closing_balance).unwrap()));
+            transaction.set_date(Utc.with_ymd_and_hms(2026, 1, day, 12, 0, 0).unwrap());
+            transaction_repository.create(transaction);
+        }
+        // AND a transaction of another account
+        transaction_repository.create(get_random_transaction());
+
+        // WHEN the balance is asked between, on, before and after its transactions
+        let balance_at = |day: u32, hour: u32| {
+            transaction_repository
+                .balance_at(
+                    &account_id,
+                    Utc.with_ymd_and_hms(2026, 1, day, hour, 0, 0).unwrap(),
+                )
+                .map(|balance| balance.value())
+        };
+
+        // THEN it is the closing balance of the last transaction until then
+        assert_eq!(balance_at(15, 0), Some(100.0));
+        assert_eq!(balance_at(20, 12), Some(80.0));
+        assert_eq!(balance_at(31, 0), Some(80.0));
+
+        // AND the opening balance of the first transaction before it
+        assert_eq!(balance_at(1, 0), Some(0.0));
+
+        // AND an account without transactions has no balance in the history
+        assert_eq!(
+            transaction_repository.balance_at(&AccountId::generate(), Utc::now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_version_changes_with_the_transactions() {
+        // GIVEN the sqlite transaction repository with a transaction
+        let mut transaction_repository = SQliteTransactionRepository::new(get_sqlite_connection());
+        let transaction_id = transaction_repository.create(get_random_transaction());
+        let version = transaction_repository.version();
+
+        // WHEN the transactions are only read
+        transaction_repository.find(&TransactionQuery::default());
+        transaction_repository.find_by_id(transaction_id.clone());
+
+        // THEN the version is the same
+        assert_eq!(transaction_repository.version(), version);
+
+        // WHEN a transaction is deleted
+        transaction_repository.delete(transaction_id).unwrap();
+
+        // THEN the version changes
+        assert_ne!(transaction_repository.version(), version);
+    }
+}
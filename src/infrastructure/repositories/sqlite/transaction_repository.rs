@@ -0,0 +1,626 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{
+    AccountRef, Transaction, TransactionId, TransactionRevision, TransactionSplit,
+};
+use crate::app::repositories::common::Page;
+use crate::app::repositories::transaction_query::{
+    SortDirection, TransactionCursor, TransactionQuery, TransactionSortField,
+};
+use crate::app::repositories::transaction_repository::{
+    DeleteError, FindByIdAndUpdateError, SetTagsError, TransactionRepository,
+};
+use crate::app::typing::amount::Amount;
+use crate::infrastructure::repositories::sqlite::tag_repository::TagLinks;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlite::{Connection, State, Statement, Value};
+use std::collections::HashMap;
+
+pub struct SQliteTransactionRepository {
+    connection: Connection,
+}
+
+impl SQliteTransactionRepository {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection }
+    }
+
+    /// Reads the splits of the transactions, which are stored in their own table.
+    fn load_splits(&self, transactions: &mut [Transaction]) {
+        if transactions.is_empty() {
+            return;
+        }
+
+        let placeholders = vec!["?"; transactions.len()].join(", ");
+        let mut statement = self
+            .connection
+            .prepare(format!(
+                "SELECT * FROM transaction_splits WHERE transaction_id IN ({placeholders}) ORDER BY transaction_id, position"
+            ))
+            .unwrap();
+        let ids: Vec<(usize, Value)> = transactions
+            .iter()
+            .enumerate()
+            .map(|(index, transaction)| {
+                (
+                    index + 1,
+                    transaction.id().as_ref().unwrap().to_string().into(),
+                )
+            })
+            .collect();
+        statement.bind(&ids[..]).unwrap();
+
+        let mut splits: HashMap<String, Vec<TransactionSplit>> = HashMap::new();
+        while let Ok(State::Row) = statement.next() {
+            let transaction_id = statement.read::<String, _>("transaction_id").unwrap();
+            let category_id = statement.read::<String, _>("category_id").unwrap();
+
+            splits
+                .entry(transaction_id)
+                .or_default()
+                .push(TransactionSplit {
+                    category_id: category_id.try_into().unwrap(),
+                    amount: read_amount(&statement, "amount").unwrap(),
+                    memo: statement.read::<Option<String>, _>("memo").unwrap(),
+                });
+        }
+
+        for transaction in transactions {
+            let id = transaction.id().as_ref().unwrap().to_string();
+            if let Some(splits) = splits.remove(&id) {
+                transaction.set_splits(splits);
+            }
+        }
+    }
+
+    /// Reads the tags of the transactions, which are stored in their own table.
+    fn load_tags(&self, transactions: &mut [Transaction]) {
+        let ids: Vec<String> = transactions
+            .iter()
+            .map(|transaction| transaction.id().as_ref().unwrap().to_string())
+            .collect();
+        let mut tags = TagLinks::Transactions.load(&self.connection, &ids);
+
+        for (transaction, id) in transactions.iter_mut().zip(ids) {
+            if let Some(tags) = tags.remove(&id) {
+                transaction.set_tags(tags);
+            }
+        }
+    }
+
+    fn insert_splits(&self, id: &TransactionId, splits: &[TransactionSplit]) {
+        let query = "INSERT INTO transaction_splits (transaction_id, position, category_id, amount, memo) VALUES (?, ?, ?, ?, ?)";
+
+        for (position, split) in splits.iter().enumerate() {
+            let mut statement = self.connection.prepare(query).unwrap();
+            statement.bind((1, id.to_string().as_str())).unwrap();
+            statement.bind((2, position as i64)).unwrap();
+            statement
+                .bind((3, split.category_id.to_string().as_str()))
+                .unwrap();
+            statement.bind((4, split.amount.value() as f64)).unwrap();
+            statement.bind((5, split.memo.as_deref())).unwrap();
+            statement.next().unwrap();
+        }
+    }
+}
+
+/// Binds the fields of the transaction, in the order of the `account_id, transaction_type, amount, fee,
+/// opening_balance, closing_balance, currency, status, date, description, reference_number, message,
+/// category_id, payee_id, reverses` columns, starting at the given index.
+fn bind_transaction(statement: &mut Statement, first_index: usize, transaction: &Transaction) {
+    let account_id = transaction
+        .account_id()
+        .expect("A transaction must reference a saved account")
+        .to_string();
+
+    statement.bind((first_index, account_id.as_str())).unwrap();
+    statement
+        .bind((
+            first_index + 1,
+            transaction.transaction_type().to_string().as_str(),
+        ))
+        .unwrap();
+    statement
+        .bind((first_index + 2, transaction.amount().value() as f64))
+        .unwrap();
+    statement
+        .bind((first_index + 3, transaction.fee().value() as f64))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 4,
+            transaction
+                .opening_balance()
+                .as_ref()
+                .map(|amount| amount.value() as f64),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 5,
+            transaction
+                .closing_balance()
+                .as_ref()
+                .map(|amount| amount.value() as f64),
+        ))
+        .unwrap();
+    statement
+        .bind((first_index + 6, transaction.currency().to_string().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 7, transaction.status().to_string().as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 8, format_date(&transaction.date()).as_str()))
+        .unwrap();
+    statement
+        .bind((first_index + 9, transaction.description().as_deref()))
+        .unwrap();
+    statement
+        .bind((first_index + 10, transaction.reference_number().as_deref()))
+        .unwrap();
+    statement
+        .bind((first_index + 11, transaction.message().as_deref()))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 12,
+            transaction
+                .category_id()
+                .map(|category_id| category_id.to_string())
+                .as_deref(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 13,
+            transaction
+                .payee_id()
+                .map(|payee_id| payee_id.to_string())
+                .as_deref(),
+        ))
+        .unwrap();
+    statement
+        .bind((
+            first_index + 14,
+            transaction
+                .reverses()
+                .map(|reversed_id| reversed_id.to_string())
+                .as_deref(),
+        ))
+        .unwrap();
+}
+
+/// Dates are stored with a fixed precision, so their text representation sorts chronologically.
+pub(super) fn format_date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+fn read_amount(statement: &Statement, column: &str) -> Option<Amount> {
+    statement
+        .read::<Option<f64>, _>(column)
+        .unwrap()
+        .map(|value| (value as f32).try_into().unwrap())
+}
+
+/// Builds a transaction from the current row of the statement.
+fn read_transaction(statement: &Statement) -> Transaction {
+    let id = statement.read::<String, _>("id").unwrap();
+    let account_id = statement.read::<String, _>("account_id").unwrap();
+    let transaction_type = statement.read::<String, _>("transaction_type").unwrap();
+    let currency = statement.read::<String, _>("currency").unwrap();
+    let status = statement.read::<String, _>("status").unwrap();
+    let date = statement.read::<String, _>("date").unwrap();
+    let category_id = statement.read::<Option<String>, _>("category_id").unwrap();
+    let payee_id = statement.read::<Option<String>, _>("payee_id").unwrap();
+
+    let mut transaction = Transaction::new(
+        Some(id.try_into().unwrap()),
+        AccountRef::Id(account_id.try_into().unwrap()),
+        transaction_type.as_str().try_into().unwrap(),
+        read_amount(statement, "amount").unwrap(),
+        read_amount(statement, "fee").unwrap(),
+        read_amount(statement, "opening_balance"),
+        read_amount(statement, "closing_balance"),
+        currency.try_into().unwrap(),
+        status.as_str().try_into().unwrap(),
+        DateTime::parse_from_rfc3339(&date)
+            .unwrap()
+            .with_timezone(&Utc),
+        statement.read::<Option<String>, _>("description").unwrap(),
+        statement
+            .read::<Option<String>, _>("reference_number")
+            .unwrap(),
+        statement.read::<Option<String>, _>("message").unwrap(),
+    );
+    transaction.set_category_id(category_id.map(|category_id| category_id.try_into().unwrap()));
+    transaction.set_payee_id(payee_id.map(|payee_id| payee_id.try_into().unwrap()));
+    transaction.set_reverses(
+        statement
+            .read::<Option<String>, _>("reverses")
+            .unwrap()
+            .map(|reversed_id| reversed_id.try_into().unwrap()),
+    );
+
+    transaction
+}
+
+/// Escapes the `LIKE` wildcards of a text, so it is matched literally (`\` is the escape character).
+fn like_pattern(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Builds the `WHERE` clause of a transaction query, with the values to bind to it.
+fn where_clause(query: &TransactionQuery) -> (String, Vec<Value>) {
+    let mut conditions: Vec<String> = vec![];
+    let mut values: Vec<Value> = vec![];
+
+    if let Some(account_id) = &query.account_id {
+        conditions.push("account_id = ?".to_string());
+        values.push(account_id.to_string().into());
+    }
+
+    if let Some(from) = &query.from {
+        conditions.push("date >= ?".to_string());
+        values.push(format_date(from).into());
+    }
+
+    if let Some(to) = &query.to {
+        conditions.push("date < ?".to_string());
+        values.push(format_date(to).into());
+    }
+
+    if let Some(transaction_type) = &query.transaction_type {
+        conditions.push("transaction_type = ?".to_string());
+        values.push(transaction_type.to_string().into());
+    }
+
+    if let Some(status) = &query.status {
+        conditions.push("status = ?".to_string());
+        values.push(status.to_string().into());
+    }
+
+    if let Some(currency) = &query.currency {
+        conditions.push("currency = ?".to_string());
+        values.push(currency.to_string().into());
+    }
+
+    if !query.category_ids.is_empty() {
+        // The transaction itself, or one of its splits, is in one of the categories.
+        let placeholders = vec!["?"; query.category_ids.len()].join(", ");
+        conditions.push(format!(
+            "(category_id IN ({placeholders}) OR id IN (SELECT transaction_id FROM transaction_splits WHERE category_id IN ({placeholders})))"
+        ));
+        for _ in 0..2 {
+            values.extend(
+                query
+                    .category_ids
+                    .iter()
+                    .map(|category_id| category_id.to_string().into()),
+            );
+        }
+    }
+
+    for tag_id in &query.tag_ids {
+        conditions.push(
+            "id IN (SELECT transaction_id FROM transaction_tags WHERE tag_id = ?)".to_string(),
+        );
+        values.push(tag_id.to_string().into());
+    }
+
+    if let Some(payee_id) = &query.payee_id {
+        conditions.push("payee_id = ?".to_string());
+        values.push(payee_id.to_string().into());
+    }
+
+    if let Some(min_amount) = &query.min_amount {
+        conditions.push("amount >= ?".to_string());
+        values.push((min_amount.value() as f64).into());
+    }
+
+    if let Some(max_amount) = &query.max_amount {
+        conditions.push("amount <= ?".to_string());
+        values.push((max_amount.value() as f64).into());
+    }
+
+    if let Some(text) = &query.text {
+        // LIKE is case-insensitive for ASCII characters.
+        conditions.push(
+            "(description LIKE ? ESCAPE '\\' OR message LIKE ? ESCAPE '\\' OR reference_number LIKE ? ESCAPE '\\')"
+                .to_string(),
+        );
+        let pattern = like_pattern(text);
+        values.extend([
+            pattern.clone().into(),
+            pattern.clone().into(),
+            pattern.into(),
+        ]);
+    }
+
+    if let Some(cursor) = &query.cursor {
+        // Keyset pagination: the rows positioned after the cursor, with the id breaking the ties.
+        let operator = match query.sort.direction {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        };
+        let cursor_id = cursor.id.to_string();
+
+        let sort_value: Option<(&str, Value)> = match query.sort.field {
+            TransactionSortField::Date => Some(("date", format_date(&cursor.date).into())),
+            TransactionSortField::Amount => Some(("amount", (cursor.amount as f64).into())),
+            TransactionSortField::CreatedAt => None,
+        };
+
+        match sort_value {
+            Some((column, value)) => {
+                conditions.push(format!(
+                    "({column} {operator} ? OR ({column} = ? AND id {operator} ?))"
+                ));
+                values.extend([value.clone(), value, cursor_id.into()]);
+            }
+            None => {
+                conditions.push(format!("id {operator} ?"));
+                values.push(cursor_id.into());
+            }
+        }
+    }
+
+    if conditions.is_empty() {
+        return (String::new(), values);
+    }
+
+    (format!("WHERE {}", conditions.join(" AND ")), values)
+}
+
+/// Builds the `ORDER BY` clause of a transaction query.
+fn order_by_clause(query: &TransactionQuery) -> String {
+    let direction = match query.sort.direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+
+    match query.sort.field {
+        TransactionSortField::Date => format!("ORDER BY date {direction}, id {direction}"),
+        TransactionSortField::Amount => format!("ORDER BY amount {direction}, id {direction}"),
+        TransactionSortField::CreatedAt => format!("ORDER BY id {direction}"),
+    }
+}
+
+impl TransactionRepository for SQliteTransactionRepository {
+    fn find(&self, query: &TransactionQuery) -> Page<Transaction> {
+        let (where_clause, mut values) = where_clause(query);
+        let sql = format!(
+            "SELECT * FROM transactions {where_clause} {} LIMIT ?",
+            order_by_clause(query)
+        );
+        values.push(((query.limit + 1) as i64).into());
+
+        let mut statement = self.connection.prepare(sql).unwrap();
+        let values: Vec<(usize, Value)> = values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (index + 1, value))
+            .collect();
+        statement.bind(&values[..]).unwrap();
+
+        let mut transactions: Vec<Transaction> = vec![];
+        while let Ok(State::Row) = statement.next() {
+            transactions.push(read_transaction(&statement))
+        }
+        self.load_splits(&mut transactions);
+        self.load_tags(&mut transactions);
+
+        Page::from_overfetched(transactions, query.limit, |transaction| {
+            TransactionCursor::from(transaction).to_string()
+        })
+    }
+
+    fn create(&mut self, transaction: Transaction) -> TransactionId {
+        // The transaction, its splits and its tags are saved together, or not at all.
+        self.connection.execute("BEGIN").unwrap();
+
+        let query = "INSERT INTO transactions (id, account_id, transaction_type, amount, fee, opening_balance, closing_balance, currency, status, date, description, reference_number, message, category_id, payee_id, reverses) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+        let mut statement = self.connection.prepare(query).unwrap();
+
+        let id = TransactionId::generate();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        bind_transaction(&mut statement, 2, &transaction);
+        statement.next().unwrap();
+
+        self.insert_splits(&id, transaction.splits());
+        TagLinks::Transactions.save(&self.connection, &id.to_string(), transaction.tags());
+        self.connection.execute("COMMIT").unwrap();
+
+        id
+    }
+
+    fn find_by_id(&self, id: TransactionId) -> Option<Transaction> {
+        let mut query = self
+            .connection
+            .prepare("SELECT * FROM transactions WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+
+        let mut transaction = match query.next() {
+            Ok(State::Row) => read_transaction(&query),
+            _ => return None,
+        };
+        self.load_splits(std::slice::from_mut(&mut transaction));
+        self.load_tags(std::slice::from_mut(&mut transaction));
+
+        Some(transaction)
+    }
+
+    fn find_by_id_and_update(
+        &mut self,
+        id: TransactionId,
+        transaction: Transaction,
+    ) -> Result<TransactionId, FindByIdAndUpdateError> {
+        // The transaction, its splits and its tags are replaced together, or not at all.
+        self.connection.execute("BEGIN").unwrap();
+
+        let query = "UPDATE transactions SET account_id = ?, transaction_type = ?, amount = ?, fee = ?, opening_balance = ?, closing_balance = ?, currency = ?, status = ?, date = ?, description = ?, reference_number = ?, message = ?, category_id = ?, payee_id = ?, reverses = ? WHERE id = ?";
+        let mut statement = self.connection.prepare(query).unwrap();
+        bind_transaction(&mut statement, 1, &transaction);
+        statement.bind((16, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            self.connection.execute("ROLLBACK").unwrap();
+            return Err(FindByIdAndUpdateError::NotFound);
+        }
+
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM transaction_splits WHERE transaction_id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+        self.insert_splits(&id, transaction.splits());
+        TagLinks::Transactions.save(&self.connection, &id.to_string(), transaction.tags());
+        self.connection.execute("COMMIT").unwrap();
+
+        Ok(id)
+    }
+
+    fn delete(&mut self, id: TransactionId) -> Result<(), DeleteError> {
+        // The transaction, its splits and its tags are removed together, or not at all.
+        self.connection.execute("BEGIN").unwrap();
+
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM transaction_splits WHERE transaction_id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+        TagLinks::Transactions.save(&self.connection, &id.to_string(), &[]);
+
+        let mut statement = self
+            .connection
+            .prepare("DELETE FROM transactions WHERE id = ?")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+
+        if self.connection.change_count() == 0 {
+            self.connection.execute("ROLLBACK").unwrap();
+            return Err(DeleteError::NotFound);
+        }
+        self.connection.execute("COMMIT").unwrap();
+
+        Ok(())
+    }
+
+    fn add_revision(&mut self, revision: TransactionRevision) {
+        let mut statement = self
+            .connection
+            .prepare("INSERT INTO transaction_revisions (transaction_id, kind, revised_at, previous) VALUES (?, ?, ?, ?)")
+            .unwrap();
+        statement
+            .bind((1, revision.transaction_id.to_string().as_str()))
+            .unwrap();
+        statement
+            .bind((2, String::from(&revision.kind).as_str()))
+            .unwrap();
+        statement
+            .bind((3, format_date(&revision.revised_at).as_str()))
+            .unwrap();
+        statement
+            .bind((
+                4,
+                serde_json::to_string(&revision.previous).unwrap().as_str(),
+            ))
+            .unwrap();
+        statement.next().unwrap();
+    }
+
+    fn find_revisions(&self, id: &TransactionId) -> Vec<TransactionRevision> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT * FROM transaction_revisions WHERE transaction_id = ? ORDER BY revised_at, rowid")
+            .unwrap();
+        statement.bind((1, id.to_string().as_str())).unwrap();
+
+        let mut revisions = vec![];
+        while let Ok(State::Row) = statement.next() {
+            let kind = statement.read::<String, _>("kind").unwrap();
+            let revised_at = statement.read::<String, _>("revised_at").unwrap();
+            let previous = statement.read::<String, _>("previous").unwrap();
+
+            revisions.push(TransactionRevision {
+                transaction_id: id.clone(),
+                kind: kind.as_str().try_into().unwrap(),
+                revised_at: DateTime::parse_from_rfc3339(&revised_at)
+                    .unwrap()
+                    .with_timezone(&Utc),
+                previous: serde_json::from_str(&previous).unwrap(),
+            });
+        }
+
+        revisions
+    }
+
+    fn balance_at(&self, account_id: &AccountId, at: DateTime<Utc>) -> Option<Amount> {
+        // Both lookups seek the (account_id, date, id) index, the history of the account is not scanned.
+        let query = "SELECT COALESCE(
+            (SELECT closing_balance FROM transactions WHERE account_id = :account_id AND date <= :at ORDER BY date DESC, id DESC LIMIT 1),
+            (SELECT opening_balance FROM transactions WHERE account_id = :account_id AND date > :at ORDER BY date, id LIMIT 1)
+        ) AS balance";
+        let mut statement = self.connection.prepare(query).unwrap();
+        statement
+            .bind((":account_id", account_id.to_string().as_str()))
+            .unwrap();
+        statement.bind((":at", format_date(&at).as_str())).unwrap();
+        statement.next().unwrap();
+
+        read_amount(&statement, "balance")
+    }
+
+    fn set_tags(&mut self, id: TransactionId, tags: Vec<TagId>) -> Result<(), SetTagsError> {
+        let mut query = self
+            .connection
+            .prepare("SELECT id FROM transactions WHERE id = ?")
+            .unwrap();
+        query.bind((1, id.to_string().as_str())).unwrap();
+        if !matches!(query.next(), Ok(State::Row)) {
+            return Err(SetTagsError::NotFound);
+        }
+        drop(query);
+
+        self.connection.execute("BEGIN").unwrap();
+        TagLinks::Transactions.save(&self.connection, &id.to_string(), &tags);
+        self.connection.execute("COMMIT").unwrap();
+
+        Ok(())
+    }
+
+    fn merge_tags(&mut self, source: &TagId, target: &TagId) {
+        TagLinks::Transactions.merge(&self.connection, source, target);
+    }
+
+    fn merge_payees(&mut self, source: &PayeeId, target: &PayeeId) {
+        let mut statement = self
+            .connection
+            .prepare("UPDATE transactions SET payee_id = ? WHERE payee_id = ?")
+            .unwrap();
+        statement.bind((1, target.to_string().as_str())).unwrap();
+        statement.bind((2, source.to_string().as_str())).unwrap();
+        statement.next().unwrap();
+    }
+
+    fn version(&self) -> u64 {
+        // The rows changed through this connection, and the commits of the other connections to the database,
+        // made by the other repositories or by the other processes, like a command run while the server is up.
+        let mut statement = self.connection.prepare("PRAGMA data_version").unwrap();
+        statement.next().unwrap();
+        let data_version = statement.read::<i64, _>(0).unwrap() as u64;
+
+        self.connection.total_change_count() as u64 + data_version
+    }
+}
@@ -0,0 +1,542 @@
+#[cfg(test)]
+mod tests_transaction_query {
+    use crate::app::entities::account::AccountId;
+    use crate::app::entities::category::CategoryId;
+    use crate::app::entities::payee::PayeeId;
+    use crate::app::entities::tag::TagId;
+    use crate::app::entities::transaction::{
+        AccountRef, Transaction, TransactionId, TransactionSplit, TransactionStatus,
+        TransactionType,
+    };
+    use crate::app::repositories::transaction_query::{
+        SortDirection, TransactionQuery, TransactionSort, TransactionSortField,
+    };
+    use crate::app::repositories::transaction_repository::{
+        FindByIdAndUpdateError, SetTagsError, TransactionRepository,
+    };
+    use crate::app::typing::currency::Currency;
+    use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+    use crate::infrastructure::repositories::sqlite::transaction_repository::SQliteTransactionRepository;
+    use crate::shared::test_utilities::{get_random_transaction, get_sqlite_connection};
+    use chrono::{DateTime, Duration, Utc};
+
+    /// Both backends must answer the queries in the same way.
+    fn get_repositories() -> Vec<Box<dyn TransactionRepository>> {
+        vec![
+            Box::new(InMemoryTransactionRepository::new()),
+            Box::new(SQliteTransactionRepository::new(get_sqlite_connection())),
+        ]
+    }
+
+    fn get_date(day: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+            + Duration::days(day)
+    }
+
+    fn get_transaction(account_id: &AccountId, amount: f32, day: i64) -> Transaction {
+        let mut transaction = get_random_transaction();
+        transaction.set_account(AccountRef::Id(account_id.clone()));
+        transaction.set_amount(amount.try_into().unwrap());
+        transaction.set_date(get_date(day));
+        transaction
+    }
+
+    fn find_ids(
+        repository: &dyn TransactionRepository,
+        query: &TransactionQuery,
+    ) -> Vec<TransactionId> {
+        repository
+            .find(query)
+            .items
+            .into_iter()
+            .map(|transaction| transaction.id().clone().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        for mut repository in get_repositories() {
+            // GIVEN transactions of two accounts, with different attributes
+            let account_id = AccountId::generate();
+            let other_account_id = AccountId::generate();
+
+            let mut groceries = get_transaction(&account_id, 50.0, 1);
+            groceries.set_description(Some(String::from("Groceries at SIMBA")));
+            let groceries_id = repository.create(groceries);
+
+            let mut salary = get_transaction(&account_id, 900.0, 2);
+            salary.set_transaction_type(TransactionType::Income);
+            salary.set_message(Some(String::from("You have received your salary")));
+            let salary_id = repository.create(salary);
+
+            let mut pending = get_transaction(&account_id, 10.0, 3);
+            pending.set_status(TransactionStatus::Pending);
+            pending.set_reference_number(Some(String::from("REF-100%")));
+            let pending_id = repository.create(pending);
+
+            let mut dollars = get_transaction(&other_account_id, 20.0, 4);
+            dollars.set_currency(Currency::USD);
+            let dollars_id = repository.create(dollars);
+
+            // WHEN filtering by account
+            let query = TransactionQuery {
+                account_id: Some(other_account_id.clone()),
+                ..Default::default()
+            };
+
+            // THEN only the transactions of the account are returned
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                vec![dollars_id.clone()]
+            );
+
+            // WHEN filtering by type
+            let query = TransactionQuery {
+                transaction_type: Some(TransactionType::Income),
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                vec![salary_id.clone()]
+            );
+
+            // WHEN filtering by status
+            let query = TransactionQuery {
+                status: Some(TransactionStatus::Pending),
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                vec![pending_id.clone()]
+            );
+
+            // WHEN filtering by currency
+            let query = TransactionQuery {
+                currency: Some(Currency::USD),
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                vec![dollars_id.clone()]
+            );
+
+            // WHEN filtering by an amount range (bounds included)
+            let query = TransactionQuery {
+                min_amount: Some(20f32.try_into().unwrap()),
+                max_amount: Some(50f32.try_into().unwrap()),
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                [dollars_id.clone(), groceries_id.clone()]
+            );
+
+            // WHEN filtering by a date range (end excluded)
+            let query = TransactionQuery {
+                from: Some(get_date(2)),
+                to: Some(get_date(4)),
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                [pending_id.clone(), salary_id.clone()]
+            );
+
+            // WHEN searching a text, in any case, in the description, message or reference number
+            for (text, expected_id) in [
+                ("simba", &groceries_id),
+                ("SALARY", &salary_id),
+                ("ref-100%", &pending_id),
+            ] {
+                let query = TransactionQuery {
+                    text: Some(text.to_string()),
+                    ..Default::default()
+                };
+                assert_eq!(
+                    find_ids(repository.as_ref(), &query),
+                    vec![expected_id.clone()]
+                );
+            }
+
+            // WHEN the filters are combined
+            let query = TransactionQuery {
+                account_id: Some(account_id.clone()),
+                transaction_type: Some(TransactionType::Expense),
+                max_amount: Some(20f32.try_into().unwrap()),
+                ..Default::default()
+            };
+
+            // THEN the transactions must match all of them
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                vec![pending_id.clone()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_category_filter() {
+        for mut repository in get_repositories() {
+            // GIVEN a transaction of a category, and a transaction split across two categories
+            let account_id = AccountId::generate();
+            let food_id = CategoryId::generate();
+            let household_id = CategoryId::generate();
+
+            let mut groceries = get_transaction(&account_id, 10.0, 1);
+            groceries.set_category_id(Some(food_id.clone()));
+            let groceries_id = repository.create(groceries);
+
+            let mut supermarket = get_transaction(&account_id, 30.0, 2);
+            supermarket.set_splits(vec![
+                TransactionSplit {
+                    category_id: food_id.clone(),
+                    amount: 20f32.try_into().unwrap(),
+                    memo: None,
+                },
+                TransactionSplit {
+                    category_id: household_id.clone(),
+                    amount: 10f32.try_into().unwrap(),
+                    memo: None,
+                },
+            ]);
+            let supermarket_id = repository.create(supermarket);
+
+            // AND an uncategorized transaction
+            repository.create(get_transaction(&account_id, 5.0, 3));
+
+            // WHEN filtering by the category of both
+            let query = TransactionQuery {
+                category_ids: vec![food_id.clone()],
+                ..Default::default()
+            };
+
+            // THEN the split transaction matches through its split
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                [supermarket_id.clone(), groceries_id.clone()]
+            );
+
+            // WHEN filtering by the category of a split only
+            let query = TransactionQuery {
+                category_ids: vec![household_id.clone()],
+                ..Default::default()
+            };
+            assert_eq!(find_ids(repository.as_ref(), &query), vec![supermarket_id]);
+        }
+    }
+
+    #[test]
+    fn test_tag_filter() {
+        for mut repository in get_repositories() {
+            // GIVEN a transaction with two tags, and a transaction with one of them
+            let account_id = AccountId::generate();
+            let trip_id = TagId::generate();
+            let reimbursable_id = TagId::generate();
+
+            let mut hotel = get_transaction(&account_id, 80.0, 1);
+            hotel.set_tags(vec![trip_id.clone(), reimbursable_id.clone()]);
+            let hotel_id = repository.create(hotel);
+
+            let mut bus = get_transaction(&account_id, 5.0, 2);
+            bus.set_tags(vec![trip_id.clone()]);
+            let bus_id = repository.create(bus);
+
+            // AND a transaction without tags
+            repository.create(get_transaction(&account_id, 15.0, 3));
+
+            // WHEN filtering by one tag
+            let query = TransactionQuery {
+                tag_ids: vec![trip_id.clone()],
+                ..Default::default()
+            };
+
+            // THEN the transactions carrying it match, with their tags
+            let page = repository.find(&query);
+            assert_eq!(
+                page.items
+                    .iter()
+                    .map(|transaction| transaction.id().clone().unwrap())
+                    .collect::<Vec<TransactionId>>(),
+                [bus_id.clone(), hotel_id.clone()]
+            );
+            assert_eq!(
+                page.items[1].tags(),
+                &vec![trip_id.clone(), reimbursable_id.clone()]
+            );
+
+            // WHEN filtering by both tags
+            // THEN only the transaction carrying both matches
+            let query = TransactionQuery {
+                tag_ids: vec![trip_id.clone(), reimbursable_id.clone()],
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                vec![hotel_id.clone()]
+            );
+
+            // WHEN the tags of a transaction are replaced
+            assert_eq!(
+                repository.set_tags(bus_id.clone(), vec![reimbursable_id.clone()]),
+                Ok(())
+            );
+
+            // THEN the transaction is found by its new tag only
+            let query = TransactionQuery {
+                tag_ids: vec![reimbursable_id.clone()],
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                [bus_id.clone(), hotel_id.clone()]
+            );
+
+            // WHEN a tag is merged into the other one
+            repository.merge_tags(&reimbursable_id, &trip_id);
+
+            // THEN the transactions carry the remaining tag, once
+            assert_eq!(
+                repository.find_by_id(hotel_id).unwrap().tags(),
+                &vec![trip_id.clone()]
+            );
+            assert_eq!(
+                repository.find_by_id(bus_id).unwrap().tags(),
+                &vec![trip_id.clone()]
+            );
+
+            // AND an unknown transaction can not be tagged
+            assert_eq!(
+                repository.set_tags(TransactionId::generate(), vec![trip_id]),
+                Err(SetTagsError::NotFound)
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_by_id_and_update() {
+        for mut repository in get_repositories() {
+            // GIVEN a split transaction with a tag
+            let account_id = AccountId::generate();
+            let mut transaction = get_transaction(&account_id, 30.0, 1);
+            transaction.set_splits(vec![
+                TransactionSplit {
+                    category_id: CategoryId::generate(),
+                    amount: 20f32.try_into().unwrap(),
+                    memo: None,
+                },
+                TransactionSplit {
+                    category_id: CategoryId::generate(),
+                    amount: 10f32.try_into().unwrap(),
+                    memo: None,
+                },
+            ]);
+            transaction.set_tags(vec![TagId::generate()]);
+            let id = repository.create(transaction);
+
+            // WHEN it is replaced by a transaction with a category, a payee and other tags
+            let mut transaction = repository.find_by_id(id.clone()).unwrap();
+            transaction.set_splits(vec![]);
+            transaction.set_category_id(Some(CategoryId::generate()));
+            transaction.set_payee_id(Some(PayeeId::generate()));
+            transaction.set_tags(vec![TagId::generate(), TagId::generate()]);
+            transaction.set_status(TransactionStatus::Pending);
+            let result = repository.find_by_id_and_update(id.clone(), transaction.clone());
+
+            // THEN the transaction is saved as a whole
+            assert_eq!(result, Ok(id.clone()));
+            assert_eq!(repository.find_by_id(id), Some(transaction.clone()));
+
+            // AND an unknown transaction can not be updated
+            assert_eq!(
+                repository.find_by_id_and_update(TransactionId::generate(), transaction),
+                Err(FindByIdAndUpdateError::NotFound)
+            );
+        }
+    }
+
+    #[test]
+    fn test_payee_filter() {
+        for mut repository in get_repositories() {
+            // GIVEN transactions of two payees, and a transaction without payee
+            let account_id = AccountId::generate();
+            let simba_id = PayeeId::generate();
+            let supermarket_id = PayeeId::generate();
+
+            let mut groceries = get_transaction(&account_id, 20.0, 1);
+            groceries.set_payee_id(Some(simba_id.clone()));
+            let groceries_id = repository.create(groceries);
+
+            let mut snacks = get_transaction(&account_id, 4.0, 2);
+            snacks.set_payee_id(Some(supermarket_id.clone()));
+            let snacks_id = repository.create(snacks);
+
+            repository.create(get_transaction(&account_id, 15.0, 3));
+
+            // WHEN filtering by a payee
+            let query = TransactionQuery {
+                payee_id: Some(simba_id.clone()),
+                ..Default::default()
+            };
+
+            // THEN only its transactions match
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                vec![groceries_id.clone()]
+            );
+
+            // WHEN a payee is merged into the other one
+            repository.merge_payees(&supermarket_id, &simba_id);
+
+            // THEN its transactions reference the remaining payee
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                [snacks_id.clone(), groceries_id]
+            );
+            assert_eq!(
+                repository.find_by_id(snacks_id).unwrap().payee_id(),
+                Some(&simba_id)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sort() {
+        for mut repository in get_repositories() {
+            // GIVEN some transactions
+            let account_id = AccountId::generate();
+            let first = repository.create(get_transaction(&account_id, 30.0, 1));
+            let second = repository.create(get_transaction(&account_id, 10.0, 3));
+            let third = repository.create(get_transaction(&account_id, 20.0, 2));
+
+            // WHEN no sort is requested
+            // THEN the most recent transactions come first
+            assert_eq!(
+                find_ids(repository.as_ref(), &TransactionQuery::default()),
+                [second.clone(), third.clone(), first.clone()]
+            );
+
+            // WHEN sorting by ascending amount
+            let query = TransactionQuery {
+                sort: TransactionSort {
+                    field: TransactionSortField::Amount,
+                    direction: SortDirection::Asc,
+                },
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                [second.clone(), third.clone(), first.clone()]
+            );
+
+            // WHEN sorting by ascending creation
+            let query = TransactionQuery {
+                sort: TransactionSort {
+                    field: TransactionSortField::CreatedAt,
+                    direction: SortDirection::Asc,
+                },
+                ..Default::default()
+            };
+            assert_eq!(
+                find_ids(repository.as_ref(), &query),
+                [first, second, third]
+            );
+        }
+    }
+
+    #[test]
+    fn test_pagination() {
+        for mut repository in get_repositories() {
+            // GIVEN five transactions, some of them on the same date
+            let account_id = AccountId::generate();
+            let expected_ids: Vec<TransactionId> = [1, 2, 2, 2, 3]
+                .into_iter()
+                .map(|day| repository.create(get_transaction(&account_id, 10.0, day)))
+                .collect();
+
+            // WHEN the transactions are listed two at a time, in date order
+            let mut query = TransactionQuery {
+                sort: TransactionSort {
+                    field: TransactionSortField::Date,
+                    direction: SortDirection::Asc,
+                },
+                limit: 2,
+                ..Default::default()
+            };
+
+            let mut pages: Vec<Vec<TransactionId>> = vec![];
+            loop {
+                let page = repository.find(&query);
+                pages.push(
+                    page.items
+                        .iter()
+                        .map(|transaction| transaction.id().clone().unwrap())
+                        .collect(),
+                );
+
+                match page.next_cursor {
+                    Some(cursor) => query.cursor = Some(cursor.parse().unwrap()),
+                    None => break,
+                }
+            }
+
+            // THEN there are three pages
+            assert_eq!(
+                pages.iter().map(|page| page.len()).collect::<Vec<_>>(),
+                [2, 2, 1]
+            );
+
+            // AND every transaction is listed once, in order (the ties are in creation order)
+            assert_eq!(pages.concat(), expected_ids);
+        }
+    }
+
+    #[test]
+    fn test_balance_at_without_closing_balance() {
+        for mut repository in get_repositories() {
+            // GIVEN the history of an account, its transaction of day 20 recorded without balances
+            let account_id = AccountId::generate();
+            for (amount, day, balances) in [
+                (100.0, 10, Some((0.0, 100.0))),
+                (30.0, 20, None),
+                (20.0, 30, Some((70.0, 50.0))),
+            ] {
+                let mut transaction = get_transaction(&account_id, amount, day);
+                let (opening_balance, closing_balance) = match balances {
+                    Some((opening, closing)) => (
+                        Some(f32::try_into(opening).unwrap()),
+                        Some(f32::try_into(closing).unwrap()),
+                    ),
+                    None => (None, None),
+                };
+                transaction.set_opening_balance(opening_balance);
+                transaction.set_closing_balance(closing_balance);
+                repository.create(transaction);
+            }
+            // AND another account, whose only transaction has no balances
+            let other_account_id = AccountId::generate();
+            let mut transaction = get_transaction(&other_account_id, 10.0, 10);
+            transaction.set_opening_balance(None);
+            transaction.set_closing_balance(None);
+            repository.create(transaction);
+
+            // WHEN the balance is asked after each transaction
+            let balance_at = |account_id: &AccountId, day: i64| {
+                repository
+                    .balance_at(account_id, get_date(day) + Duration::hours(1))
+                    .map(|balance| balance.value())
+            };
+
+            // THEN it is the closing balance of the last transaction
+            assert_eq!(balance_at(&account_id, 10), Some(100.0));
+            assert_eq!(balance_at(&account_id, 30), Some(50.0));
+
+            // AND the opening balance of the next one, when the last one has no closing balance
+            assert_eq!(balance_at(&account_id, 20), Some(70.0));
+
+            // AND no balance is known when neither of them has one
+            assert_eq!(balance_at(&other_account_id, 10), None);
+        }
+    }
+}
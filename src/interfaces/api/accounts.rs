@@ -1,11 +1,24 @@
-use crate::app::entities::account::{Account, AccountType};
-use crate::app::entities::common::EntityId;
+use crate::app::entities::account::{Account, AccountId, AccountStatus, AccountType};
+use crate::app::entities::institution::InstitutionId;
+use crate::app::entities::tag::TagId;
+use crate::app::services::account_merge_service::{AccountMerge, MergeError};
+use crate::app::services::account_service::{
+    AccountBalance, CreateError, StatusError, UpdateError,
+};
+use crate::app::services::transaction_service::{CloseAccountError, OpenAccountError};
+use crate::app::typing::account_identifier::{AccountIdentifier, IdentifierKind};
+use crate::app::typing::amount::Amount;
 use crate::app::typing::currency::Currency;
+use crate::app::typing::point_in_time::PointInTime;
+use crate::interfaces::api::common::{
+    ndjson_response, parse_id, parse_list, query_config, ErrorResponse, NDJSON,
+};
 use crate::interfaces::api::state::AppState;
-use actix_web::{get, post, web, HttpResponse, Responder};
+use crate::interfaces::api::tags::{set_tags_response, SetTagsRequest};
+use actix_web::{get, post, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
-use utoipa;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use utoipa_actix_web::service_config::ServiceConfig;
 
 #[derive(Deserialize, ToSchema)]
@@ -16,14 +29,84 @@ struct CreateAccountRequest {
     /// Account description.
     description: String,
 
-    /// Account platform.
-    platform: String,
+    /// The institution the account is held at, none for the cash in hand.
+    institution_id: Option<InstitutionId>,
 
     /// Account type.
     account_type: AccountType,
 
     /// Account currency.
     currency: Currency,
+
+    /// The money the account holds when it is created, recorded as an "Opening balance" income.
+    opening_balance: Option<Amount>,
+
+    /// When the account was opened, the date of its opening balance. Defaults to now.
+    opened_at: Option<DateTime<Utc>>,
+
+    /// The tag names of the account, the tags that do not exist yet are created.
+    #[serde(default)]
+    tags: Vec<String>,
+
+    /// The numbers identifying the account outside of the application, unique across the accounts.
+    #[serde(default)]
+    identifiers: Vec<AccountIdentifier>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct SetIdentifiersRequest {
+    /// The identifiers of the account, replacing its current ones.
+    identifiers: Vec<AccountIdentifier>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct ChangeStatusRequest {
+    /// The new status of the account.
+    status: AccountStatus,
+
+    /// Why the status is changed, kept in the history of the account. (Eg: "Card lost")
+    reason: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct CloseAccountRequest {
+    /// Why the account is closed, kept in the history of the account.
+    reason: String,
+
+    /// The account the remaining balance is transferred to, required when the balance is not zero.
+    sweep_to: Option<AccountId>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct MergeAccountRequest {
+    /// The account the merged account is replaced by.
+    target_id: AccountId,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct LookupParams {
+    /// What the account is identified by.
+    kind: IdentifierKind,
+
+    /// The identifier, as written by its institution. (Eg: "0788 123 456")
+    value: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AccountListParams {
+    /// Only the accounts carrying all these tags, as comma separated names.
+    tags: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct BalanceParams {
+    /// The point in time, as an RFC 3339 date or a date and time followed by a time zone.
+    /// (Eg: "2025-12-31 23:59 Africa/Kigali")
+    #[param(value_type = String)]
+    at: PointInTime,
 }
 
 const ACCOUNTS: &str = "Accounts";
@@ -31,39 +114,157 @@ const ACCOUNTS: &str = "Accounts";
 /// Get all accounts.
 #[utoipa::path(
     tag = ACCOUNTS,
+    params(AccountListParams),
     responses(
-        (status = 200, description = "List current accounts items", body=[Account])
+        (status = 200, description = "List current accounts items", body=[Account]),
+        (status = 404, description = "Tag not found", body=ErrorResponse)
     )
 )]
 #[get("")]
-async fn get_all_accounts(state: web::Data<AppState>) -> impl Responder {
+async fn get_all_accounts(
+    state: web::Data<AppState>,
+    params: web::Query<AccountListParams>,
+) -> impl Responder {
+    let tags = parse_list(params.tags.as_deref());
+    if tags.is_empty() {
+        let account_service = state.account_service.lock().unwrap();
+        return HttpResponse::Ok().json(account_service.find_all());
+    }
+
+    let tag_ids = match state.tag_service.lock().unwrap().find_by_names(&tags) {
+        Ok(tag_ids) => tag_ids,
+        Err(error) => return HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    };
+
+    let account_service = state.account_service.lock().unwrap();
+    HttpResponse::Ok().json(account_service.find_by_tags(&tag_ids))
+}
+
+/// Export all accounts.
+///
+/// The accounts are streamed as newline delimited JSON, one account per line, in creation order.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    responses(
+        (status = 200, description = "All the accounts, one per line", body=Account, content_type = NDJSON)
+    )
+)]
+#[get("/export")]
+async fn export_accounts(state: web::Data<AppState>) -> impl Responder {
+    let accounts = state.account_service.lock().unwrap().stream();
+    ndjson_response(accounts)
+}
+
+/// Find the accounts having an identifier.
+///
+/// The identifier is normalized before the lookup, so it can be written the way its institution does.
+/// Several accounts can end with the same last 4 digits of a card, the other identifiers match one account at most.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    params(LookupParams),
+    responses(
+        (status = 200, description = "The accounts having the identifier, in creation order", body=[Account]),
+        (status = 400, description = "Invalid identifier", body=ErrorResponse)
+    )
+)]
+#[get("/lookup")]
+async fn lookup_account(
+    state: web::Data<AppState>,
+    params: web::Query<LookupParams>,
+) -> impl Responder {
+    let identifier = match AccountIdentifier::new(params.kind, &params.value) {
+        Ok(identifier) => identifier,
+        Err(error) => return HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    };
+
+    let account_service = state.account_service.lock().unwrap();
+    HttpResponse::Ok().json(account_service.find_by_identifier(&identifier))
+}
+
+/// Get the balances of all the accounts at a point in time.
+///
+/// The balances are read from the history of the accounts, an account without transactions always had its current balance.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    params(BalanceParams),
+    responses(
+        (status = 200, description = "The balances of the accounts, in creation order", body=[AccountBalance]),
+        (status = 400, description = "Invalid point in time", body=ErrorResponse)
+    )
+)]
+#[get("/balances")]
+async fn get_balances(
+    state: web::Data<AppState>,
+    params: web::Query<BalanceParams>,
+) -> impl Responder {
     let account_service = state.account_service.lock().unwrap();
-    let accounts = account_service.find_all();
-    HttpResponse::Ok().json(accounts)
+    HttpResponse::Ok().json(account_service.balances_at(params.at.value()))
 }
 
 /// Get account by id.
 #[utoipa::path(
     tag = ACCOUNTS,
     responses(
-        (status = 200, description = "List current accounts items", body=Account)
+        (status = 200, description = "List current accounts items", body=Account),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "Account not found")
     )
 )]
 #[get("/{id}")]
 async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
     let account_service = state.account_service.lock().unwrap();
-    let entity_id: EntityId = id.clone().into();
-    match account_service.find_by_id(entity_id) {
+    match account_service.find_by_id(account_id) {
         Some(account) => HttpResponse::Ok().json(account),
         None => HttpResponse::NotFound().finish(),
     }
 }
 
+/// Get the balance of an account at a point in time.
+///
+/// The balance is read from the history of the account, an account without transactions always had its current balance.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    params(BalanceParams),
+    responses(
+        (status = 200, description = "The balance of the account", body=AccountBalance),
+        (status = 400, description = "Malformed account id or invalid point in time", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/balance")]
+async fn get_balance(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<BalanceParams>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let account_service = state.account_service.lock().unwrap();
+    match account_service.balance_at(&account_id, params.at.value()) {
+        Ok(balance) => HttpResponse::Ok().json(balance),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
 /// Create account.
+///
+/// An account holding money already gets an opening balance, recorded as its first transaction.
 #[utoipa::path(
     tag = ACCOUNTS,
     responses(
-        (status = 200, description = "List current accounts items")
+        (status = 200, description = "List current accounts items"),
+        (status = 400, description = "Invalid tag name, an institution given to cash, or an opening date in the future", body=ErrorResponse),
+        (status = 404, description = "Institution not found", body=ErrorResponse),
+        (status = 409, description = "Another account already has one of the identifiers", body=ErrorResponse),
+        (status = 500, description = "The account could not be saved", body=ErrorResponse)
     )
 )]
 #[post("")]
@@ -71,26 +272,237 @@ async fn create_account(
     state: web::Data<AppState>,
     user_request: web::Json<CreateAccountRequest>,
 ) -> impl Responder {
-    let account_service = state.account_service.lock().unwrap();
+    if let Some(institution_id) = &user_request.institution_id {
+        let institution_service = state.institution_service.lock().unwrap();
+        if let Err(error) = institution_service.find_by_id_or_fail(institution_id) {
+            return HttpResponse::NotFound().json(ErrorResponse::new(error));
+        }
+    }
 
-    let account: Account = Account::new(
+    let tags = match state
+        .tag_service
+        .lock()
+        .unwrap()
+        .find_or_create(&user_request.tags)
+    {
+        Ok(tags) => tags,
+        Err(error) => return HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    };
+    let mut account: Account = Account::new(
         None,
         user_request.name.clone(),
         user_request.description.clone(),
-        user_request.platform.clone(),
+        user_request.institution_id.clone(),
         user_request.account_type.clone(),
         Some(user_request.currency.clone()),
     );
+    account.set_tags(tags);
+    account.set_identifiers(user_request.identifiers.clone());
+
+    let mut transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.open_account(
+        account,
+        user_request.opening_balance.clone(),
+        user_request.opened_at,
+    ) {
+        Ok(account) => HttpResponse::Ok().json(account),
+        Err(error @ OpenAccountError::Invalid(CreateError::DuplicateIdentifier(_))) => {
+            HttpResponse::Conflict().json(ErrorResponse::new(error))
+        }
+        Err(error @ OpenAccountError::Invalid(CreateError::FailedToCreateAccount)) => {
+            HttpResponse::InternalServerError().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Replace the identifiers of an account.
+///
+/// The identifiers are validated and normalized, an identifier given twice is kept once.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    responses(
+        (status = 200, description = "The account with its new identifiers", body=Account),
+        (status = 400, description = "Malformed account id or invalid identifier", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse),
+        (status = 409, description = "Another account already has one of the identifiers", body=ErrorResponse)
+    )
+)]
+#[put("/{id}/identifiers")]
+async fn set_account_identifiers(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<SetIdentifiersRequest>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
 
-    match account_service.create(account) {
+    let account_service = state.account_service.lock().unwrap();
+    match account_service.set_identifiers(&account_id, &user_request.identifiers) {
+        Ok(account) => HttpResponse::Ok().json(account),
+        Err(error @ UpdateError::DuplicateIdentifier(_)) => {
+            HttpResponse::Conflict().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// The response to a rejected status change.
+fn status_error_response(error: StatusError) -> HttpResponse {
+    match error {
+        StatusError::EntityIdNotFound => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+        StatusError::EmptyReason => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+        StatusError::Unchanged(_)
+        | StatusError::NonZeroBalance
+        | StatusError::DuplicateIdentifier(_) => {
+            HttpResponse::Conflict().json(ErrorResponse::new(error))
+        }
+    }
+}
+
+/// Change the status of an account.
+///
+/// A frozen account takes no withdrawals, a closed one no transactions at all. Closing requires a zero balance,
+/// see the close endpoint to transfer the balance first. Reopening is setting the status back to active.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    responses(
+        (status = 200, description = "The account with its new status", body=Account),
+        (status = 400, description = "Malformed account id or blank reason", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse),
+        (status = 409, description = "The account already has the status, is closed while holding money, or another account has one of its identifiers", body=ErrorResponse)
+    )
+)]
+#[put("/{id}/status")]
+async fn change_account_status(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<ChangeStatusRequest>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let account_service = state.account_service.lock().unwrap();
+    match account_service.change_status(&account_id, user_request.status, &user_request.reason) {
         Ok(account) => HttpResponse::Ok().json(account),
-        Err(_) => return HttpResponse::InternalServerError().finish(),
+        Err(error) => status_error_response(error),
     }
 }
 
-pub(super) fn configure(config: &mut ServiceConfig) -> () {
+/// Close an account.
+///
+/// The remaining balance is transferred to the `sweep_to` account, recorded as an expense of the closed account
+/// and an income of the other one.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    responses(
+        (status = 200, description = "The closed account", body=Account),
+        (status = 400, description = "Malformed account id, blank reason, or invalid account to sweep into", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse),
+        (status = 409, description = "The account is already closed, holds money not swept, or can not be withdrawn from", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/close")]
+async fn close_account(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<CloseAccountRequest>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let mut transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.close_account(
+        &account_id,
+        &user_request.reason,
+        user_request.sweep_to.as_ref(),
+    ) {
+        Ok(account) => HttpResponse::Ok().json(account),
+        Err(CloseAccountError::Status(error)) => status_error_response(error),
+        Err(error @ CloseAccountError::SweepFailed(_)) => {
+            HttpResponse::Conflict().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Merge an account into another one.
+///
+/// The transactions of the merged account, and everything else referencing it, are moved to the target account.
+/// The merged account is then closed.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    responses(
+        (status = 200, description = "What the merge changed", body=AccountMerge),
+        (status = 400, description = "Malformed account id, or accounts which can not be merged", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/merge")]
+async fn merge_account(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<MergeAccountRequest>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let account_merge_service = state.account_merge_service.lock().unwrap();
+    match account_merge_service.merge(&account_id, &user_request.target_id) {
+        Ok(merge) => HttpResponse::Ok().json(merge),
+        Err(error @ (MergeError::SourceNotFound(_) | MergeError::TargetNotFound(_))) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Replace the tags of an account.
+#[utoipa::path(
+    tag = ACCOUNTS,
+    responses(
+        (status = 200, description = "The tag ids of the account", body=[TagId]),
+        (status = 400, description = "Malformed account id or invalid tag name", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}/tags")]
+async fn set_account_tags(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<SetTagsRequest>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let tag_service = state.tag_service.lock().unwrap();
+    set_tags_response(tag_service.set_account_tags(&account_id, &user_request.tags))
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
     config
+        .app_data(query_config())
         .service(get_all_accounts)
+        // Registered before `/{id}`, which would match their paths otherwise.
+        .service(export_accounts)
+        .service(lookup_account)
+        .service(get_balances)
         .service(get_by_id)
-        .service(create_account);
+        .service(get_balance)
+        .service(create_account)
+        .service(set_account_tags)
+        .service(set_account_identifiers)
+        .service(change_account_status)
+        .service(close_account)
+        .service(merge_account);
 }
@@ -0,0 +1,229 @@
+use crate::app::entities::budget::{Budget, BudgetId, BudgetPeriod, BudgetScope};
+use crate::app::services::budget_service::{
+    BudgetStatus, CreateError, StatusError, UpdateError, ValidationError,
+};
+use crate::app::typing::amount::Amount;
+use crate::app::typing::currency::Currency;
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct BudgetRequest {
+    /// A name describing the budget. (Eg: "Groceries")
+    name: String,
+
+    /// The expense category (with its sub categories) or the tag the budget applies to.
+    /// (Eg: `{"category": "<id>"}` or `{"tag": "<id>"}`)
+    scope: BudgetScope,
+
+    period: BudgetPeriod,
+
+    /// The amount which can be spent each period.
+    #[schema(value_type = f32)]
+    amount: Amount,
+
+    /// Only the expenses in this currency count against the budget.
+    currency: Currency,
+
+    /// Whether the amount left unspent at the end of a period is added to the next one, defaults to false.
+    #[serde(default)]
+    rollover: bool,
+
+    /// A day of the first period of the budget, defaults to today.
+    start: Option<NaiveDate>,
+}
+
+impl From<BudgetRequest> for Budget {
+    fn from(request: BudgetRequest) -> Self {
+        Budget::new(
+            None,
+            request.name,
+            request.scope,
+            request.period,
+            request.amount,
+            request.currency,
+            request.rollover,
+            request.start.unwrap_or_else(|| Utc::now().date_naive()),
+        )
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct StatusParams {
+    /// The status at this date, defaults to now.
+    at: Option<DateTime<Utc>>,
+}
+
+/// Answers an invalid budget: a missing category or tag is a 404.
+fn validation_error_response(error: &ValidationError) -> HttpResponse {
+    match error {
+        ValidationError::CategoryNotFound(_) | ValidationError::TagNotFound(_) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        _ => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+const BUDGETS: &str = "Budgets";
+
+/// Get all budgets.
+#[utoipa::path(
+    tag = BUDGETS,
+    responses(
+        (status = 200, description = "All the budgets, in creation order", body=[Budget])
+    )
+)]
+#[get("")]
+async fn get_all_budgets(state: web::Data<AppState>) -> impl Responder {
+    let budgets = state.budget_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(budgets)
+}
+
+/// Get budget by id.
+#[utoipa::path(
+    tag = BUDGETS,
+    responses(
+        (status = 200, description = "The budget", body=Budget),
+        (status = 400, description = "Malformed budget id", body=ErrorResponse),
+        (status = 404, description = "Budget not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let budget_id: BudgetId = match parse_id(&id) {
+        Ok(budget_id) => budget_id,
+        Err(response) => return response,
+    };
+
+    let budget_service = state.budget_service.lock().unwrap();
+    match budget_service.find_by_id_or_fail(&budget_id) {
+        Ok(budget) => HttpResponse::Ok().json(budget),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get budget status.
+///
+/// The amounts budgeted, spent and remaining over the period containing the date,
+/// with the overspending projected at the end of the period if the spending goes on at the same pace.
+/// Only the confirmed expenses in the currency of the budget count.
+#[utoipa::path(
+    tag = BUDGETS,
+    params(StatusParams),
+    responses(
+        (status = 200, description = "The status of the budget", body=BudgetStatus),
+        (status = 400, description = "Malformed budget id, or the budget has not started at the date", body=ErrorResponse),
+        (status = 404, description = "Budget not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/status")]
+async fn get_status(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<StatusParams>,
+) -> impl Responder {
+    let budget_id: BudgetId = match parse_id(&id) {
+        Ok(budget_id) => budget_id,
+        Err(response) => return response,
+    };
+
+    let budget_service = state.budget_service.lock().unwrap();
+    match budget_service.status(&budget_id, params.at.unwrap_or_else(Utc::now)) {
+        Ok(status) => HttpResponse::Ok().json(status),
+        Err(error @ StatusError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create budget.
+#[utoipa::path(
+    tag = BUDGETS,
+    responses(
+        (status = 200, description = "The id of the created budget"),
+        (status = 400, description = "Invalid budget", body=ErrorResponse),
+        (status = 404, description = "Category or tag not found", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_budget(
+    state: web::Data<AppState>,
+    user_request: web::Json<BudgetRequest>,
+) -> impl Responder {
+    let budget_service = state.budget_service.lock().unwrap();
+    match budget_service.create(user_request.into_inner().into()) {
+        Ok(budget_id) => HttpResponse::Ok().json(budget_id),
+        Err(CreateError::Invalid(error)) => validation_error_response(&error),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update budget.
+#[utoipa::path(
+    tag = BUDGETS,
+    responses(
+        (status = 200, description = "The updated budget", body=Budget),
+        (status = 400, description = "Invalid budget", body=ErrorResponse),
+        (status = 404, description = "Budget, category or tag not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_budget(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<BudgetRequest>,
+) -> impl Responder {
+    let budget_id: BudgetId = match parse_id(&id) {
+        Ok(budget_id) => budget_id,
+        Err(response) => return response,
+    };
+
+    let budget_service = state.budget_service.lock().unwrap();
+    match budget_service.update(&budget_id, user_request.into_inner().into()) {
+        Ok(budget) => HttpResponse::Ok().json(budget),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(UpdateError::Invalid(error)) => validation_error_response(&error),
+    }
+}
+
+/// Delete budget.
+#[utoipa::path(
+    tag = BUDGETS,
+    responses(
+        (status = 204, description = "The budget was deleted"),
+        (status = 400, description = "Malformed budget id", body=ErrorResponse),
+        (status = 404, description = "Budget not found", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_budget(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let budget_id: BudgetId = match parse_id(&id) {
+        Ok(budget_id) => budget_id,
+        Err(response) => return response,
+    };
+
+    let budget_service = state.budget_service.lock().unwrap();
+    match budget_service.delete(&budget_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_budgets)
+        .service(get_by_id)
+        .service(get_status)
+        .service(create_budget)
+        .service(update_budget)
+        .service(delete_budget);
+}
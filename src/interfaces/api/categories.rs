@@ -0,0 +1,202 @@
+use crate::app::entities::category::{Category, CategoryId};
+use crate::app::entities::transaction::TransactionType;
+use crate::app::services::category_service::{CategoryTotal, DeleteError, UpdateError};
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct CategoryRequest {
+    /// Category name.
+    name: String,
+
+    /// The parent category, none for a top level category.
+    parent_id: Option<CategoryId>,
+
+    /// The type of the transactions of the category.
+    kind: TransactionType,
+
+    /// Display colour, as a hex code. (Eg: "#4caf50")
+    color: Option<String>,
+
+    /// Display icon name.
+    icon: Option<String>,
+}
+
+impl From<CategoryRequest> for Category {
+    fn from(request: CategoryRequest) -> Self {
+        Category::new(
+            None,
+            request.name,
+            request.parent_id,
+            request.kind,
+            request.color,
+            request.icon,
+        )
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ReportParams {
+    /// Only the transactions on or after this date.
+    from: Option<DateTime<Utc>>,
+
+    /// Only the transactions strictly before this date.
+    to: Option<DateTime<Utc>>,
+}
+
+const CATEGORIES: &str = "Categories";
+
+/// Get all categories.
+///
+/// The taxonomy is returned as a flat list, the tree is given by the `parent_id` of the categories.
+#[utoipa::path(
+    tag = CATEGORIES,
+    responses(
+        (status = 200, description = "All the categories", body=[Category])
+    )
+)]
+#[get("")]
+async fn get_all_categories(state: web::Data<AppState>) -> impl Responder {
+    let categories = state.category_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(categories)
+}
+
+/// Totals per category.
+///
+/// The confirmed transactions are totaled per category and currency, the total of a category
+/// includes its sub categories.
+#[utoipa::path(
+    tag = CATEGORIES,
+    params(ReportParams),
+    responses(
+        (status = 200, description = "The totals of the categories", body=[CategoryTotal])
+    )
+)]
+#[get("/report")]
+async fn get_report(
+    state: web::Data<AppState>,
+    params: web::Query<ReportParams>,
+) -> impl Responder {
+    let category_service = state.category_service.lock().unwrap();
+    HttpResponse::Ok().json(category_service.report(params.from, params.to))
+}
+
+/// Get category by id.
+#[utoipa::path(
+    tag = CATEGORIES,
+    responses(
+        (status = 200, description = "The category", body=Category),
+        (status = 400, description = "Malformed category id", body=ErrorResponse),
+        (status = 404, description = "Category not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let category_id: CategoryId = match parse_id(&id) {
+        Ok(category_id) => category_id,
+        Err(response) => return response,
+    };
+
+    let category_service = state.category_service.lock().unwrap();
+    match category_service.find_by_id_or_fail(&category_id) {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create category.
+#[utoipa::path(
+    tag = CATEGORIES,
+    responses(
+        (status = 200, description = "The id of the created category"),
+        (status = 400, description = "Invalid category", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_category(
+    state: web::Data<AppState>,
+    user_request: web::Json<CategoryRequest>,
+) -> impl Responder {
+    let category_service = state.category_service.lock().unwrap();
+
+    match category_service.create(user_request.into_inner().into()) {
+        Ok(category_id) => HttpResponse::Ok().json(category_id),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update category.
+#[utoipa::path(
+    tag = CATEGORIES,
+    responses(
+        (status = 200, description = "The updated category", body=Category),
+        (status = 400, description = "Invalid category", body=ErrorResponse),
+        (status = 404, description = "Category not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_category(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<CategoryRequest>,
+) -> impl Responder {
+    let category_id: CategoryId = match parse_id(&id) {
+        Ok(category_id) => category_id,
+        Err(response) => return response,
+    };
+
+    let category_service = state.category_service.lock().unwrap();
+    match category_service.update(&category_id, user_request.into_inner().into()) {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Delete category.
+///
+/// A category still used by sub categories, transactions, payees, recurring transactions or budgets can not be deleted.
+#[utoipa::path(
+    tag = CATEGORIES,
+    responses(
+        (status = 204, description = "The category was deleted"),
+        (status = 400, description = "Malformed category id", body=ErrorResponse),
+        (status = 404, description = "Category not found", body=ErrorResponse),
+        (status = 409, description = "The category is still in use", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_category(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let category_id: CategoryId = match parse_id(&id) {
+        Ok(category_id) => category_id,
+        Err(response) => return response,
+    };
+
+    let category_service = state.category_service.lock().unwrap();
+    match category_service.delete(&category_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error @ DeleteError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::Conflict().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_categories)
+        // Registered before `/{id}`, which would match the path otherwise.
+        .service(get_report)
+        .service(get_by_id)
+        .service(create_category)
+        .service(update_category)
+        .service(delete_category);
+}
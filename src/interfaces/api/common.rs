@@ -0,0 +1,74 @@
+use crate::app::entities::common::EntityIdParseError;
+use actix_web::error::InternalError;
+use actix_web::web::{Bytes, QueryConfig};
+use actix_web::HttpResponse;
+use futures_util::stream;
+use serde::Serialize;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Body of the error responses.
+#[derive(Serialize, ToSchema)]
+pub(super) struct ErrorResponse {
+    /// Human readable description of the error.
+    message: String,
+}
+
+impl ErrorResponse {
+    pub(super) fn new(message: impl ToString) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Parses an entity id received in the request path.
+/// A malformed id is answered with a `400 Bad Request` response.
+pub(super) fn parse_id<T>(value: &str) -> Result<T, HttpResponse>
+where
+    T: FromStr<Err = EntityIdParseError>,
+{
+    value.parse().map_err(|error: EntityIdParseError| {
+        HttpResponse::BadRequest().json(ErrorResponse::new(error))
+    })
+}
+
+/// Answers a query string which can not be read, like a malformed point in time,
+/// with a `400 Bad Request` response carrying an `ErrorResponse` instead of plain text.
+pub(super) fn query_config() -> QueryConfig {
+    QueryConfig::default().error_handler(|error, _| {
+        let response = HttpResponse::BadRequest().json(ErrorResponse::new(&error));
+        InternalError::from_response(error, response).into()
+    })
+}
+
+/// Splits a comma separated list received in a query parameter, ignoring the blank items.
+pub(super) fn parse_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Content type of the newline delimited JSON responses.
+pub(super) const NDJSON: &str = "application/x-ndjson";
+
+/// Streams the items as newline delimited JSON, one item per line.
+/// The items are serialized while the response is sent, so they are never all held in memory.
+pub(super) fn ndjson_response<T, I>(items: I) -> HttpResponse
+where
+    T: Serialize,
+    I: Iterator<Item = T> + 'static,
+{
+    let lines = items.map(|item| {
+        let mut line = serde_json::to_vec(&item)?;
+        line.push(b'\n');
+        Ok::<_, serde_json::Error>(Bytes::from(line))
+    });
+
+    HttpResponse::Ok()
+        .content_type(NDJSON)
+        .streaming(stream::iter(lines))
+}
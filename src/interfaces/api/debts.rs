@@ -0,0 +1,89 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::payoff::PayoffPlan;
+use crate::app::services::payoff_service::{DebtTerms, PlanError};
+use crate::interfaces::api::common::ErrorResponse;
+use crate::interfaces::api::state::AppState;
+use actix_web::{post, web, HttpResponse, Responder};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct DebtRequest {
+    /// The credit or loan account the debt is owed on.
+    account_id: AccountId,
+
+    /// The amount owed, defaults to the principal left on the loans of the account.
+    balance: Option<f32>,
+
+    /// The yearly interest rate, in percent.
+    /// Defaults to the rate of the loans of the account, then to the interest charged on the account.
+    annual_rate: Option<f32>,
+
+    /// The least amount paid every month, defaults to the instalments of the loans of the account.
+    minimum_payment: Option<f32>,
+}
+
+impl From<DebtRequest> for DebtTerms {
+    fn from(request: DebtRequest) -> Self {
+        DebtTerms {
+            account_id: request.account_id,
+            balance: request.balance,
+            annual_rate: request.annual_rate,
+            minimum_payment: request.minimum_payment,
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+struct PayoffPlanRequest {
+    /// The amount paid every month, on all the debts together.
+    monthly_budget: f32,
+
+    debts: Vec<DebtRequest>,
+
+    /// The accounts of the debts in the order to pay them off, to simulate along with the strategies.
+    custom_order: Option<Vec<AccountId>>,
+
+    /// The payments are made every month from the month after this day, defaults to today.
+    start: Option<NaiveDate>,
+}
+
+const DEBTS: &str = "Debts";
+
+/// Plan debt payoff.
+///
+/// Simulates the payoff of the debts with a monthly budget, with the snowball (smallest balance first)
+/// and avalanche (highest rate first) strategies, then the custom order when one is provided.
+/// Every month the minimum payments are made, and the rest of the budget goes to the debts in order.
+#[utoipa::path(
+    tag = DEBTS,
+    responses(
+        (status = 200, description = "The plan of every strategy", body=[PayoffPlan]),
+        (status = 400, description = "Invalid debts, or a budget which does not pay them off", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[post("/payoff-plan")]
+async fn plan_payoff(
+    state: web::Data<AppState>,
+    user_request: web::Json<PayoffPlanRequest>,
+) -> impl Responder {
+    let request = user_request.into_inner();
+    let debts = request.debts.into_iter().map(DebtTerms::from).collect();
+    let start = request.start.unwrap_or_else(|| Utc::now().date_naive());
+
+    let payoff_service = state.payoff_service.lock().unwrap();
+    match payoff_service.plan(request.monthly_budget, debts, request.custom_order, start) {
+        Ok(plans) => HttpResponse::Ok().json(plans),
+        Err(error @ PlanError::AccountNotFound(_)) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config.service(plan_payoff);
+}
@@ -0,0 +1,221 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::goal::{Goal, GoalId};
+use crate::app::services::goal_service::{CreateError, GoalStatus, UpdateError, ValidationError};
+use crate::app::typing::amount::Amount;
+use crate::app::typing::currency::Currency;
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct GoalRequest {
+    /// A name describing the goal. (Eg: "Emergency fund")
+    name: String,
+
+    /// The amount to save.
+    #[schema(value_type = f32)]
+    target_amount: Amount,
+
+    currency: Currency,
+
+    /// The day the target amount should be reached.
+    target_date: NaiveDate,
+
+    /// The savings accounts the money is put aside on, in the currency of the goal.
+    account_ids: Vec<AccountId>,
+}
+
+impl From<GoalRequest> for Goal {
+    fn from(request: GoalRequest) -> Self {
+        Goal::new(
+            None,
+            request.name,
+            request.target_amount,
+            request.currency,
+            request.target_date,
+            request.account_ids,
+        )
+    }
+}
+
+/// Answers an invalid goal: a missing account is a 404.
+fn validation_error_response(error: &ValidationError) -> HttpResponse {
+    match error {
+        ValidationError::AccountNotFound(_) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        _ => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+const GOALS: &str = "Goals";
+
+/// Get all goals.
+#[utoipa::path(
+    tag = GOALS,
+    responses(
+        (status = 200, description = "All the goals, in creation order", body=[Goal])
+    )
+)]
+#[get("")]
+async fn get_all_goals(state: web::Data<AppState>) -> impl Responder {
+    let goals = state.goal_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(goals)
+}
+
+/// Get the status of all goals.
+#[utoipa::path(
+    tag = GOALS,
+    responses(
+        (status = 200, description = "The status of every goal, in creation order", body=[GoalStatus])
+    )
+)]
+#[get("/status")]
+async fn get_all_statuses(state: web::Data<AppState>) -> impl Responder {
+    let statuses = state
+        .goal_service
+        .lock()
+        .unwrap()
+        .statuses(Utc::now().date_naive());
+    HttpResponse::Ok().json(statuses)
+}
+
+/// Get goal by id.
+#[utoipa::path(
+    tag = GOALS,
+    responses(
+        (status = 200, description = "The goal", body=Goal),
+        (status = 400, description = "Malformed goal id", body=ErrorResponse),
+        (status = 404, description = "Goal not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let goal_id: GoalId = match parse_id(&id) {
+        Ok(goal_id) => goal_id,
+        Err(response) => return response,
+    };
+
+    let goal_service = state.goal_service.lock().unwrap();
+    match goal_service.find_by_id_or_fail(&goal_id) {
+        Ok(goal) => HttpResponse::Ok().json(goal),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get goal status.
+///
+/// The amount saved on the linked accounts, and the monthly contribution required to reach the target on time.
+#[utoipa::path(
+    tag = GOALS,
+    responses(
+        (status = 200, description = "The status of the goal", body=GoalStatus),
+        (status = 400, description = "Malformed goal id", body=ErrorResponse),
+        (status = 404, description = "Goal not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/status")]
+async fn get_status(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let goal_id: GoalId = match parse_id(&id) {
+        Ok(goal_id) => goal_id,
+        Err(response) => return response,
+    };
+
+    let goal_service = state.goal_service.lock().unwrap();
+    match goal_service.status(&goal_id, Utc::now().date_naive()) {
+        Ok(status) => HttpResponse::Ok().json(status),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create goal.
+#[utoipa::path(
+    tag = GOALS,
+    responses(
+        (status = 200, description = "The id of the created goal"),
+        (status = 400, description = "Invalid goal", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_goal(
+    state: web::Data<AppState>,
+    user_request: web::Json<GoalRequest>,
+) -> impl Responder {
+    let goal_service = state.goal_service.lock().unwrap();
+    match goal_service.create(user_request.into_inner().into()) {
+        Ok(goal_id) => HttpResponse::Ok().json(goal_id),
+        Err(CreateError::Invalid(error)) => validation_error_response(&error),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update goal.
+#[utoipa::path(
+    tag = GOALS,
+    responses(
+        (status = 200, description = "The updated goal", body=Goal),
+        (status = 400, description = "Invalid goal", body=ErrorResponse),
+        (status = 404, description = "Goal or account not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_goal(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<GoalRequest>,
+) -> impl Responder {
+    let goal_id: GoalId = match parse_id(&id) {
+        Ok(goal_id) => goal_id,
+        Err(response) => return response,
+    };
+
+    let goal_service = state.goal_service.lock().unwrap();
+    match goal_service.update(&goal_id, user_request.into_inner().into()) {
+        Ok(goal) => HttpResponse::Ok().json(goal),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(UpdateError::Invalid(error)) => validation_error_response(&error),
+    }
+}
+
+/// Delete goal.
+///
+/// The linked accounts are kept.
+#[utoipa::path(
+    tag = GOALS,
+    responses(
+        (status = 204, description = "The goal was deleted"),
+        (status = 400, description = "Malformed goal id", body=ErrorResponse),
+        (status = 404, description = "Goal not found", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_goal(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let goal_id: GoalId = match parse_id(&id) {
+        Ok(goal_id) => goal_id,
+        Err(response) => return response,
+    };
+
+    let goal_service = state.goal_service.lock().unwrap();
+    match goal_service.delete(&goal_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_goals)
+        .service(get_all_statuses)
+        .service(get_by_id)
+        .service(get_status)
+        .service(create_goal)
+        .service(update_goal)
+        .service(delete_goal);
+}
@@ -0,0 +1,215 @@
+use crate::app::entities::institution::{FeeTier, Institution, InstitutionId, InstitutionKind};
+use crate::app::entities::transaction::TransactionType;
+use crate::app::services::institution_service::{DeleteError, UpdateError};
+use crate::app::typing::amount::Amount;
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct InstitutionRequest {
+    /// The name of the institution, unique ignoring the case. (Eg: "Bank of Kigali")
+    name: String,
+
+    kind: InstitutionKind,
+
+    /// The ISO 3166-1 alpha-2 code of the country the institution operates in. (Eg: "RW")
+    country: String,
+
+    /// The address of the logo of the institution.
+    logo_url: Option<String>,
+
+    /// The fees charged on the transactions, the first tier applying to a transaction is used.
+    #[serde(default)]
+    fee_schedule: Vec<FeeTier>,
+}
+
+impl From<InstitutionRequest> for Institution {
+    fn from(request: InstitutionRequest) -> Self {
+        Institution::new(
+            None,
+            request.name,
+            request.kind,
+            request.country,
+            request.logo_url,
+            request.fee_schedule,
+        )
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct FeeParams {
+    /// The type of the transaction.
+    transaction_type: TransactionType,
+
+    /// The amount of the transaction.
+    #[param(value_type = f32)]
+    amount: Amount,
+}
+
+#[derive(Serialize, ToSchema)]
+struct FeeQuote {
+    /// The fee charged on the transaction, none when no tier of the fee schedule applies.
+    fee: f32,
+}
+
+const INSTITUTIONS: &str = "Institutions";
+
+/// Get all institutions.
+#[utoipa::path(
+    tag = INSTITUTIONS,
+    responses(
+        (status = 200, description = "All the institutions, in creation order", body=[Institution])
+    )
+)]
+#[get("")]
+async fn get_all_institutions(state: web::Data<AppState>) -> impl Responder {
+    let institutions = state.institution_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(institutions)
+}
+
+/// Get institution by id.
+#[utoipa::path(
+    tag = INSTITUTIONS,
+    responses(
+        (status = 200, description = "The institution", body=Institution),
+        (status = 400, description = "Malformed institution id", body=ErrorResponse),
+        (status = 404, description = "Institution not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let institution_id: InstitutionId = match parse_id(&id) {
+        Ok(institution_id) => institution_id,
+        Err(response) => return response,
+    };
+
+    let institution_service = state.institution_service.lock().unwrap();
+    match institution_service.find_by_id_or_fail(&institution_id) {
+        Ok(institution) => HttpResponse::Ok().json(institution),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Quote the fee of a transaction.
+///
+/// The fee is computed from the first tier of the fee schedule applying to the transaction type and amount.
+#[utoipa::path(
+    tag = INSTITUTIONS,
+    params(FeeParams),
+    responses(
+        (status = 200, description = "The fee charged by the institution", body=FeeQuote),
+        (status = 400, description = "Malformed institution id", body=ErrorResponse),
+        (status = 404, description = "Institution not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/fee")]
+async fn get_fee(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<FeeParams>,
+) -> impl Responder {
+    let institution_id: InstitutionId = match parse_id(&id) {
+        Ok(institution_id) => institution_id,
+        Err(response) => return response,
+    };
+
+    let institution_service = state.institution_service.lock().unwrap();
+    match institution_service.quote_fee(&institution_id, &params.transaction_type, &params.amount) {
+        Ok(fee) => HttpResponse::Ok().json(FeeQuote { fee }),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create institution.
+#[utoipa::path(
+    tag = INSTITUTIONS,
+    responses(
+        (status = 200, description = "The id of the created institution"),
+        (status = 400, description = "Invalid institution", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_institution(
+    state: web::Data<AppState>,
+    user_request: web::Json<InstitutionRequest>,
+) -> impl Responder {
+    let institution_service = state.institution_service.lock().unwrap();
+    match institution_service.create(user_request.into_inner().into()) {
+        Ok(institution_id) => HttpResponse::Ok().json(institution_id),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update institution.
+#[utoipa::path(
+    tag = INSTITUTIONS,
+    responses(
+        (status = 200, description = "The updated institution", body=Institution),
+        (status = 400, description = "Invalid institution", body=ErrorResponse),
+        (status = 404, description = "Institution not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_institution(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<InstitutionRequest>,
+) -> impl Responder {
+    let institution_id: InstitutionId = match parse_id(&id) {
+        Ok(institution_id) => institution_id,
+        Err(response) => return response,
+    };
+
+    let institution_service = state.institution_service.lock().unwrap();
+    match institution_service.update(&institution_id, user_request.into_inner().into()) {
+        Ok(institution) => HttpResponse::Ok().json(institution),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Delete institution.
+///
+/// An institution some accounts are held at, or some rules match on, can not be deleted.
+#[utoipa::path(
+    tag = INSTITUTIONS,
+    responses(
+        (status = 204, description = "The institution was deleted"),
+        (status = 400, description = "Malformed institution id", body=ErrorResponse),
+        (status = 404, description = "Institution not found", body=ErrorResponse),
+        (status = 409, description = "The institution is still in use", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_institution(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let institution_id: InstitutionId = match parse_id(&id) {
+        Ok(institution_id) => institution_id,
+        Err(response) => return response,
+    };
+
+    let institution_service = state.institution_service.lock().unwrap();
+    match institution_service.delete(&institution_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error @ DeleteError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::Conflict().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_institutions)
+        .service(get_by_id)
+        .service(get_fee)
+        .service(create_institution)
+        .service(update_institution)
+        .service(delete_institution);
+}
@@ -0,0 +1,175 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::interest::{Compounding, DayCount, InterestConfig, PostingSchedule};
+use crate::app::services::interest_service::{InterestPreview, ValidationError};
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, put, web, HttpResponse, Responder};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct InterestConfigRequest {
+    /// The yearly interest rate, in percent. (Eg: 7.5)
+    annual_rate: f32,
+
+    day_count: DayCount,
+
+    compounding: Compounding,
+
+    /// How often the accrued interest is posted to the account.
+    posting: PostingSchedule,
+
+    /// The first day interest accrues on, defaults to today.
+    start: Option<NaiveDate>,
+}
+
+impl InterestConfigRequest {
+    fn into_config(self, account_id: AccountId) -> InterestConfig {
+        InterestConfig::new(
+            account_id,
+            self.annual_rate,
+            self.day_count,
+            self.compounding,
+            self.posting,
+            self.start.unwrap_or_else(|| Utc::now().date_naive()),
+            None,
+        )
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct PreviewParams {
+    /// The last day of the preview (included), defaults to today.
+    until: Option<NaiveDate>,
+}
+
+const INTEREST: &str = "Interest";
+
+/// Get account interest configuration.
+#[utoipa::path(
+    tag = INTEREST,
+    responses(
+        (status = 200, description = "The interest configuration of the account", body=InterestConfig),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "The account has no interest configuration", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/interest")]
+async fn get_interest_config(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let interest_service = state.interest_service.lock().unwrap();
+    match interest_service.find_by_account_id_or_fail(&account_id) {
+        Ok(config) => HttpResponse::Ok().json(config),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Configure account interest.
+///
+/// Replaces the previous configuration of the account, if any.
+/// The interest already posted is kept, the accrual resumes after it.
+/// Credit accounts are charged the interest as expenses, the other accounts earn it as incomes.
+#[utoipa::path(
+    tag = INTEREST,
+    responses(
+        (status = 200, description = "The saved interest configuration", body=InterestConfig),
+        (status = 400, description = "Malformed account id, or invalid configuration", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}/interest")]
+async fn configure_interest(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<InterestConfigRequest>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let interest_service = state.interest_service.lock().unwrap();
+    match interest_service.configure(user_request.into_inner().into_config(account_id)) {
+        Ok(config) => HttpResponse::Ok().json(config),
+        Err(error @ ValidationError::AccountNotFound(_)) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Remove account interest configuration.
+///
+/// The interest already posted is kept.
+#[utoipa::path(
+    tag = INTEREST,
+    responses(
+        (status = 204, description = "The account does not accrue interest anymore"),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "The account has no interest configuration", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}/interest")]
+async fn remove_interest_config(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let interest_service = state.interest_service.lock().unwrap();
+    match interest_service.remove(&account_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Preview account interest.
+///
+/// The interest accrued from the first day not posted yet, up to a day, computed on the daily balances,
+/// or on the amount owed for the credit and loan accounts.
+/// The interest of the posting periods ending on the way is listed as it would be posted, nothing is posted.
+#[utoipa::path(
+    tag = INTEREST,
+    params(PreviewParams),
+    responses(
+        (status = 200, description = "The interest of the account", body=InterestPreview),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "The account was not found, or has no interest configuration", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/interest/preview")]
+async fn preview_interest(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<PreviewParams>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let until = params.until.unwrap_or_else(|| Utc::now().date_naive());
+    let interest_service = state.interest_service.lock().unwrap();
+    match interest_service.preview(&account_id, until) {
+        Ok(preview) => HttpResponse::Ok().json(preview),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_interest_config)
+        .service(configure_interest)
+        .service(remove_interest_config)
+        .service(preview_interest);
+}
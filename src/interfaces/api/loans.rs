@@ -0,0 +1,277 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::loan::{Instalment, Loan, LoanId, PaymentFrequency};
+use crate::app::services::loan_service::{
+    CreateError, LoanStatus, StatusError, UpdateError, ValidationError,
+};
+use crate::app::typing::amount::Amount;
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct LoanRequest {
+    /// A name describing the loan. (Eg: "Car loan")
+    name: String,
+
+    /// The credit or loan account the loan is tracked on, and repaid to.
+    account_id: AccountId,
+
+    /// The amount borrowed.
+    #[schema(value_type = f32)]
+    principal: Amount,
+
+    /// The yearly interest rate, in percent. (Eg: 16.5)
+    annual_rate: f32,
+
+    /// The number of instalments.
+    term: u32,
+
+    frequency: PaymentFrequency,
+
+    /// The day the money was received, the first instalment is due one period later.
+    start: NaiveDate,
+}
+
+impl From<LoanRequest> for Loan {
+    fn from(request: LoanRequest) -> Self {
+        Loan::new(
+            None,
+            request.name,
+            request.account_id,
+            request.principal,
+            request.annual_rate,
+            request.term,
+            request.frequency,
+            request.start,
+        )
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct StatusParams {
+    /// The status is computed at the end of this day, defaults to today.
+    at: Option<NaiveDate>,
+}
+
+/// Answers an invalid loan: a missing account is a 404.
+fn validation_error_response(error: &ValidationError) -> HttpResponse {
+    match error {
+        ValidationError::AccountNotFound(_) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        _ => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+const LOANS: &str = "Loans";
+
+/// Get all loans.
+#[utoipa::path(
+    tag = LOANS,
+    responses(
+        (status = 200, description = "All the loans, in creation order", body=[Loan])
+    )
+)]
+#[get("")]
+async fn get_all_loans(state: web::Data<AppState>) -> impl Responder {
+    let loans = state.loan_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(loans)
+}
+
+/// Get the status of all loans.
+#[utoipa::path(
+    tag = LOANS,
+    params(StatusParams),
+    responses(
+        (status = 200, description = "The status of every loan, in creation order", body=[LoanStatus])
+    )
+)]
+#[get("/status")]
+async fn get_all_statuses(
+    state: web::Data<AppState>,
+    params: web::Query<StatusParams>,
+) -> impl Responder {
+    let at = params.at.unwrap_or_else(|| Utc::now().date_naive());
+    let statuses = state.loan_service.lock().unwrap().statuses(at);
+    HttpResponse::Ok().json(statuses)
+}
+
+/// Get loan by id.
+#[utoipa::path(
+    tag = LOANS,
+    responses(
+        (status = 200, description = "The loan", body=Loan),
+        (status = 400, description = "Malformed loan id", body=ErrorResponse),
+        (status = 404, description = "Loan not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let loan_id: LoanId = match parse_id(&id) {
+        Ok(loan_id) => loan_id,
+        Err(response) => return response,
+    };
+
+    let loan_service = state.loan_service.lock().unwrap();
+    match loan_service.find_by_id_or_fail(&loan_id) {
+        Ok(loan) => HttpResponse::Ok().json(loan),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get loan amortization schedule.
+///
+/// Every instalment repays the same amount, the interest of the period first.
+/// The amounts are rounded to the cent, the last instalment repays the principal left.
+#[utoipa::path(
+    tag = LOANS,
+    responses(
+        (status = 200, description = "The instalments of the loan, in due date order", body=[Instalment]),
+        (status = 400, description = "Malformed loan id", body=ErrorResponse),
+        (status = 404, description = "Loan not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/schedule")]
+async fn get_schedule(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let loan_id: LoanId = match parse_id(&id) {
+        Ok(loan_id) => loan_id,
+        Err(response) => return response,
+    };
+
+    let loan_service = state.loan_service.lock().unwrap();
+    match loan_service.schedule(&loan_id) {
+        Ok(schedule) => HttpResponse::Ok().json(schedule),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get loan status.
+///
+/// The repayments are the confirmed incomes of the loan account made after the money was received.
+/// They are matched to the instalments in date order, the interest of an instalment first,
+/// and the instalments past their due date which are not repaid in full are overdue.
+#[utoipa::path(
+    tag = LOANS,
+    params(StatusParams),
+    responses(
+        (status = 200, description = "The status of the loan", body=LoanStatus),
+        (status = 400, description = "Malformed loan id", body=ErrorResponse),
+        (status = 404, description = "Loan or account not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/status")]
+async fn get_status(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<StatusParams>,
+) -> impl Responder {
+    let loan_id: LoanId = match parse_id(&id) {
+        Ok(loan_id) => loan_id,
+        Err(response) => return response,
+    };
+
+    let at = params.at.unwrap_or_else(|| Utc::now().date_naive());
+    let loan_service = state.loan_service.lock().unwrap();
+    match loan_service.status(&loan_id, at) {
+        Ok(status) => HttpResponse::Ok().json(status),
+        Err(error @ (StatusError::EntityIdNotFound | StatusError::AccountNotFound(_))) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+    }
+}
+
+/// Create loan.
+///
+/// The loan is tracked on a credit or loan account, whose incomes are its repayments.
+#[utoipa::path(
+    tag = LOANS,
+    responses(
+        (status = 200, description = "The id of the created loan"),
+        (status = 400, description = "Invalid loan", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_loan(
+    state: web::Data<AppState>,
+    user_request: web::Json<LoanRequest>,
+) -> impl Responder {
+    let loan_service = state.loan_service.lock().unwrap();
+    match loan_service.create(user_request.into_inner().into()) {
+        Ok(loan_id) => HttpResponse::Ok().json(loan_id),
+        Err(CreateError::Invalid(error)) => validation_error_response(&error),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update loan.
+#[utoipa::path(
+    tag = LOANS,
+    responses(
+        (status = 200, description = "The updated loan", body=Loan),
+        (status = 400, description = "Invalid loan", body=ErrorResponse),
+        (status = 404, description = "Loan or account not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_loan(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<LoanRequest>,
+) -> impl Responder {
+    let loan_id: LoanId = match parse_id(&id) {
+        Ok(loan_id) => loan_id,
+        Err(response) => return response,
+    };
+
+    let loan_service = state.loan_service.lock().unwrap();
+    match loan_service.update(&loan_id, user_request.into_inner().into()) {
+        Ok(loan) => HttpResponse::Ok().json(loan),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(UpdateError::Invalid(error)) => validation_error_response(&error),
+    }
+}
+
+/// Delete loan.
+///
+/// Its account and repayments are kept.
+#[utoipa::path(
+    tag = LOANS,
+    responses(
+        (status = 204, description = "The loan was deleted"),
+        (status = 400, description = "Malformed loan id", body=ErrorResponse),
+        (status = 404, description = "Loan not found", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_loan(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let loan_id: LoanId = match parse_id(&id) {
+        Ok(loan_id) => loan_id,
+        Err(response) => return response,
+    };
+
+    let loan_service = state.loan_service.lock().unwrap();
+    match loan_service.delete(&loan_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_loans)
+        .service(get_all_statuses)
+        .service(get_by_id)
+        .service(get_schedule)
+        .service(get_status)
+        .service(create_loan)
+        .service(update_loan)
+        .service(delete_loan);
+}
@@ -1,5 +1,20 @@
 // Compare: rocket vs Actix web vs Axum
 
 mod accounts;
+mod budgets;
+mod categories;
+mod common;
+mod debts;
+mod goals;
+mod institutions;
+mod interest;
+mod loans;
+mod payees;
+mod recurring_transactions;
+mod rules;
+mod scheduler;
 pub(crate) mod server;
-mod state;
+pub(crate) mod state;
+mod statements;
+mod tags;
+mod transactions;
@@ -0,0 +1,205 @@
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::payee::{Payee, PayeeId};
+use crate::app::services::payee_service::{DeleteError, MergeError, UpdateError};
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct PayeeRequest {
+    /// Payee name.
+    name: String,
+
+    /// Other spellings of the payee, matched as whole words in the transactions.
+    #[serde(default)]
+    aliases: Vec<String>,
+
+    /// Regular expressions matched against the transactions, ignoring the case.
+    #[serde(default)]
+    patterns: Vec<String>,
+
+    /// The category given to the transactions of the payee, when they have none.
+    default_category_id: Option<CategoryId>,
+}
+
+impl From<PayeeRequest> for Payee {
+    fn from(request: PayeeRequest) -> Self {
+        Payee::new(
+            None,
+            request.name,
+            request.aliases,
+            request.patterns,
+            request.default_category_id,
+        )
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+struct MergePayeeRequest {
+    /// The payee the merged payee is replaced by.
+    target_id: PayeeId,
+}
+
+const PAYEES: &str = "Payees";
+
+/// Get all payees.
+#[utoipa::path(
+    tag = PAYEES,
+    responses(
+        (status = 200, description = "All the payees, in creation order", body=[Payee])
+    )
+)]
+#[get("")]
+async fn get_all_payees(state: web::Data<AppState>) -> impl Responder {
+    let payees = state.payee_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(payees)
+}
+
+/// Get payee by id.
+#[utoipa::path(
+    tag = PAYEES,
+    responses(
+        (status = 200, description = "The payee", body=Payee),
+        (status = 400, description = "Malformed payee id", body=ErrorResponse),
+        (status = 404, description = "Payee not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let payee_id: PayeeId = match parse_id(&id) {
+        Ok(payee_id) => payee_id,
+        Err(response) => return response,
+    };
+
+    let payee_service = state.payee_service.lock().unwrap();
+    match payee_service.find_by_id_or_fail(&payee_id) {
+        Ok(payee) => HttpResponse::Ok().json(payee),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create payee.
+#[utoipa::path(
+    tag = PAYEES,
+    responses(
+        (status = 200, description = "The id of the created payee"),
+        (status = 400, description = "Invalid payee", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_payee(
+    state: web::Data<AppState>,
+    user_request: web::Json<PayeeRequest>,
+) -> impl Responder {
+    let payee_service = state.payee_service.lock().unwrap();
+
+    match payee_service.create(user_request.into_inner().into()) {
+        Ok(payee_id) => HttpResponse::Ok().json(payee_id),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update payee.
+#[utoipa::path(
+    tag = PAYEES,
+    responses(
+        (status = 200, description = "The updated payee", body=Payee),
+        (status = 400, description = "Invalid payee", body=ErrorResponse),
+        (status = 404, description = "Payee not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_payee(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<PayeeRequest>,
+) -> impl Responder {
+    let payee_id: PayeeId = match parse_id(&id) {
+        Ok(payee_id) => payee_id,
+        Err(response) => return response,
+    };
+
+    let payee_service = state.payee_service.lock().unwrap();
+    match payee_service.update(&payee_id, user_request.into_inner().into()) {
+        Ok(payee) => HttpResponse::Ok().json(payee),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Delete payee.
+///
+/// A payee still used by transactions or recurring transactions can not be deleted, it can be merged into another payee instead.
+#[utoipa::path(
+    tag = PAYEES,
+    responses(
+        (status = 204, description = "The payee was deleted"),
+        (status = 400, description = "Malformed payee id", body=ErrorResponse),
+        (status = 404, description = "Payee not found", body=ErrorResponse),
+        (status = 409, description = "The payee is still in use", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_payee(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let payee_id: PayeeId = match parse_id(&id) {
+        Ok(payee_id) => payee_id,
+        Err(response) => return response,
+    };
+
+    let payee_service = state.payee_service.lock().unwrap();
+    match payee_service.delete(&payee_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error @ DeleteError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::Conflict().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Merge payee.
+///
+/// The transactions of the payee move to the target payee, which also takes its name, aliases and patterns,
+/// then the payee is deleted.
+#[utoipa::path(
+    tag = PAYEES,
+    responses(
+        (status = 200, description = "The updated target payee", body=Payee),
+        (status = 400, description = "Malformed payee id, or a payee merged into itself", body=ErrorResponse),
+        (status = 404, description = "Payee not found", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/merge")]
+async fn merge_payee(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<MergePayeeRequest>,
+) -> impl Responder {
+    let payee_id: PayeeId = match parse_id(&id) {
+        Ok(payee_id) => payee_id,
+        Err(response) => return response,
+    };
+
+    let payee_service = state.payee_service.lock().unwrap();
+    match payee_service.merge(&payee_id, &user_request.target_id) {
+        Ok(payee) => HttpResponse::Ok().json(payee),
+        Err(error @ MergeError::SamePayee) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_payees)
+        .service(get_by_id)
+        .service(create_payee)
+        .service(update_payee)
+        .service(delete_payee)
+        .service(merge_payee);
+}
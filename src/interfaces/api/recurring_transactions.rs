@@ -0,0 +1,397 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::recurring_transaction::{
+    RecurringTransaction, RecurringTransactionId, Schedule, TransactionTemplate,
+};
+use crate::app::entities::transaction::TransactionType;
+use crate::app::services::recurring_transaction_service::{
+    CreateError, SkipError, UpcomingError, UpdateError, ValidationError,
+};
+use crate::app::services::transaction_service::CreateError as TransactionCreateError;
+use crate::app::typing::amount::{Amount, MIN_AMOUNT};
+use crate::app::typing::currency::Currency;
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+/// Number of upcoming occurrences listed when no limit is given.
+const DEFAULT_UPCOMING_OCCURRENCES: usize = 10;
+
+#[derive(Deserialize, ToSchema)]
+struct TransactionTemplateRequest {
+    /// The account the transactions belong to.
+    account_id: AccountId,
+
+    transaction_type: TransactionType,
+
+    #[schema(value_type = f32)]
+    amount: Amount,
+
+    /// Transaction fee, defaults to zero.
+    #[schema(value_type = Option<f32>)]
+    fee: Option<Amount>,
+
+    currency: Currency,
+
+    description: Option<String>,
+
+    message: Option<String>,
+
+    /// The category of the transactions, it must have the kind of the transaction type.
+    category_id: Option<CategoryId>,
+
+    payee_id: Option<PayeeId>,
+
+    /// The tag names of the transactions, the tags that do not exist yet are created.
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct RecurringTransactionRequest {
+    /// A name describing the recurring transaction. (Eg: "Rent")
+    name: String,
+
+    /// The transaction created at each occurrence.
+    template: TransactionTemplateRequest,
+
+    /// When the transaction occurs.
+    schedule: Schedule,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct SkipRequest {
+    /// The day of the occurrence to skip.
+    date: NaiveDate,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct OccurrencesParams {
+    /// Maximum number of occurrences, defaults to 10.
+    limit: Option<usize>,
+}
+
+/// Builds the recurring transaction of a request, creating the tags of its template that do not exist yet.
+fn build_recurring_transaction(
+    state: &AppState,
+    request: RecurringTransactionRequest,
+) -> Result<RecurringTransaction, HttpResponse> {
+    let template = request.template;
+    let tags = state
+        .tag_service
+        .lock()
+        .unwrap()
+        .find_or_create(&template.tags)
+        .map_err(|error| HttpResponse::BadRequest().json(ErrorResponse::new(error)))?;
+
+    Ok(RecurringTransaction::new(
+        None,
+        request.name,
+        TransactionTemplate {
+            account_id: template.account_id,
+            transaction_type: template.transaction_type,
+            amount: template.amount,
+            fee: template.fee.unwrap_or(MIN_AMOUNT),
+            currency: template.currency,
+            description: template.description,
+            message: template.message,
+            category_id: template.category_id,
+            payee_id: template.payee_id,
+            tags,
+        },
+        request.schedule,
+    ))
+}
+
+/// Answers an invalid recurring transaction: a missing reference of the template is a 404, like for a transaction.
+fn validation_error_response(error: &ValidationError) -> HttpResponse {
+    match error {
+        ValidationError::Template(
+            TransactionCreateError::InvalidAccountRef { .. }
+            | TransactionCreateError::InvalidCategoryRef { .. }
+            | TransactionCreateError::InvalidPayeeRef { .. },
+        ) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+        _ => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+const RECURRING_TRANSACTIONS: &str = "Recurring transactions";
+
+/// Get all recurring transactions.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 200, description = "All the recurring transactions, in creation order", body=[RecurringTransaction])
+    )
+)]
+#[get("")]
+async fn get_all_recurring_transactions(state: web::Data<AppState>) -> impl Responder {
+    let recurring_transactions = state
+        .recurring_transaction_service
+        .lock()
+        .unwrap()
+        .find_all();
+    HttpResponse::Ok().json(recurring_transactions)
+}
+
+/// Get recurring transaction by id.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 200, description = "The recurring transaction", body=RecurringTransaction),
+        (status = 400, description = "Malformed recurring transaction id", body=ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let recurring_transaction_id: RecurringTransactionId = match parse_id(&id) {
+        Ok(recurring_transaction_id) => recurring_transaction_id,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.find_by_id_or_fail(&recurring_transaction_id) {
+        Ok(recurring_transaction) => HttpResponse::Ok().json(recurring_transaction),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// List the upcoming occurrences.
+///
+/// The dates of the next transactions the recurring transaction will create, leaving out the skipped ones.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    params(OccurrencesParams),
+    responses(
+        (status = 200, description = "The dates of the upcoming occurrences", body=[chrono::DateTime<Utc>]),
+        (status = 400, description = "Malformed recurring transaction id or limit out of bounds", body=ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/occurrences")]
+async fn get_upcoming_occurrences(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<OccurrencesParams>,
+) -> impl Responder {
+    let recurring_transaction_id: RecurringTransactionId = match parse_id(&id) {
+        Ok(recurring_transaction_id) => recurring_transaction_id,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.upcoming(
+        &recurring_transaction_id,
+        params.limit.unwrap_or(DEFAULT_UPCOMING_OCCURRENCES),
+    ) {
+        Ok(dates) => HttpResponse::Ok().json(dates),
+        Err(error @ UpcomingError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create recurring transaction.
+///
+/// Its occurrences are created as pending transactions once they fall due,
+/// from the start of its schedule.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 200, description = "The id of the created recurring transaction"),
+        (status = 400, description = "Invalid recurring transaction", body=ErrorResponse),
+        (status = 404, description = "Account, category or payee not found", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_recurring_transaction(
+    state: web::Data<AppState>,
+    user_request: web::Json<RecurringTransactionRequest>,
+) -> impl Responder {
+    let recurring_transaction = match build_recurring_transaction(&state, user_request.into_inner())
+    {
+        Ok(recurring_transaction) => recurring_transaction,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.create(recurring_transaction) {
+        Ok(recurring_transaction_id) => HttpResponse::Ok().json(recurring_transaction_id),
+        Err(CreateError::Invalid(error)) => validation_error_response(&error),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update recurring transaction.
+///
+/// The changes apply to the future occurrences only, the transactions already created are kept.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 200, description = "The updated recurring transaction", body=RecurringTransaction),
+        (status = 400, description = "Invalid recurring transaction", body=ErrorResponse),
+        (status = 404, description = "Recurring transaction, account, category or payee not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_recurring_transaction(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<RecurringTransactionRequest>,
+) -> impl Responder {
+    let recurring_transaction_id: RecurringTransactionId = match parse_id(&id) {
+        Ok(recurring_transaction_id) => recurring_transaction_id,
+        Err(response) => return response,
+    };
+    let recurring_transaction = match build_recurring_transaction(&state, user_request.into_inner())
+    {
+        Ok(recurring_transaction) => recurring_transaction,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.update(&recurring_transaction_id, recurring_transaction) {
+        Ok(recurring_transaction) => HttpResponse::Ok().json(recurring_transaction),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(UpdateError::Invalid(error)) => validation_error_response(&error),
+    }
+}
+
+/// Delete recurring transaction.
+///
+/// The transactions of its past occurrences are kept.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 204, description = "The recurring transaction was deleted"),
+        (status = 400, description = "Malformed recurring transaction id", body=ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_recurring_transaction(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> impl Responder {
+    let recurring_transaction_id: RecurringTransactionId = match parse_id(&id) {
+        Ok(recurring_transaction_id) => recurring_transaction_id,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.delete(&recurring_transaction_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Skip an occurrence.
+///
+/// The transaction of the upcoming occurrence of that day will not be created, the next ones still are.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 200, description = "The updated recurring transaction", body=RecurringTransaction),
+        (status = 400, description = "No upcoming occurrence on that day", body=ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/skip")]
+async fn skip_occurrence(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<SkipRequest>,
+) -> impl Responder {
+    let recurring_transaction_id: RecurringTransactionId = match parse_id(&id) {
+        Ok(recurring_transaction_id) => recurring_transaction_id,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.skip(&recurring_transaction_id, user_request.date) {
+        Ok(recurring_transaction) => HttpResponse::Ok().json(recurring_transaction),
+        Err(error @ SkipError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Pause recurring transaction.
+///
+/// No occurrence is created until it is resumed.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 200, description = "The paused recurring transaction", body=RecurringTransaction),
+        (status = 400, description = "Malformed recurring transaction id", body=ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/pause")]
+async fn pause_recurring_transaction(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> impl Responder {
+    let recurring_transaction_id: RecurringTransactionId = match parse_id(&id) {
+        Ok(recurring_transaction_id) => recurring_transaction_id,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.pause(&recurring_transaction_id) {
+        Ok(recurring_transaction) => HttpResponse::Ok().json(recurring_transaction),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Resume recurring transaction.
+///
+/// The occurrences which fell due while it was paused are not created.
+#[utoipa::path(
+    tag = RECURRING_TRANSACTIONS,
+    responses(
+        (status = 200, description = "The resumed recurring transaction", body=RecurringTransaction),
+        (status = 400, description = "Malformed recurring transaction id", body=ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/resume")]
+async fn resume_recurring_transaction(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> impl Responder {
+    let recurring_transaction_id: RecurringTransactionId = match parse_id(&id) {
+        Ok(recurring_transaction_id) => recurring_transaction_id,
+        Err(response) => return response,
+    };
+
+    let recurring_transaction_service = state.recurring_transaction_service.lock().unwrap();
+    match recurring_transaction_service.resume(&recurring_transaction_id, Utc::now()) {
+        Ok(recurring_transaction) => HttpResponse::Ok().json(recurring_transaction),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_recurring_transactions)
+        .service(get_by_id)
+        .service(get_upcoming_occurrences)
+        .service(create_recurring_transaction)
+        .service(update_recurring_transaction)
+        .service(delete_recurring_transaction)
+        .service(skip_occurrence)
+        .service(pause_recurring_transaction)
+        .service(resume_recurring_transaction);
+}
@@ -0,0 +1,260 @@
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::rule::{Rule, RuleActions, RuleConditions, RuleId};
+use crate::app::entities::transaction::TransactionStatus;
+use crate::app::services::rule_service::{RuleChange, UpdateError};
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, Default, ToSchema)]
+struct RuleActionsRequest {
+    /// The category given to the transactions without one.
+    category_id: Option<CategoryId>,
+
+    /// The payee given to the transactions without one.
+    payee_id: Option<PayeeId>,
+
+    /// The names of the tags added to the transactions.
+    #[serde(default)]
+    tags: Vec<String>,
+
+    /// The status given to the transactions.
+    status: Option<TransactionStatus>,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct RuleRequest {
+    /// Rule name.
+    name: String,
+
+    /// The rules are evaluated from the lowest priority number to the highest.
+    #[serde(default)]
+    priority: u32,
+
+    /// What a transaction must look like for the rule to apply to it.
+    #[serde(default)]
+    conditions: RuleConditions,
+
+    /// What the rule does to the transactions.
+    #[serde(default)]
+    actions: RuleActionsRequest,
+}
+
+/// Builds the rule of a request, resolving the names of its tags.
+///
+/// The tags that do not exist yet are created when `create_tags` is set,
+/// otherwise they are answered with a 404.
+fn build_rule(
+    state: &AppState,
+    request: RuleRequest,
+    create_tags: bool,
+) -> Result<Rule, HttpResponse> {
+    let tag_service = state.tag_service.lock().unwrap();
+    let tags = if create_tags {
+        tag_service
+            .find_or_create(&request.actions.tags)
+            .map_err(|error| HttpResponse::BadRequest().json(ErrorResponse::new(error)))?
+    } else {
+        tag_service
+            .find_by_names(&request.actions.tags)
+            .map_err(|error| HttpResponse::NotFound().json(ErrorResponse::new(error)))?
+    };
+
+    Ok(Rule::new(
+        None,
+        request.name,
+        request.priority,
+        request.conditions,
+        RuleActions {
+            category_id: request.actions.category_id,
+            payee_id: request.actions.payee_id,
+            tags,
+            status: request.actions.status,
+        },
+    ))
+}
+
+const RULES: &str = "Rules";
+
+/// Get all rules.
+#[utoipa::path(
+    tag = RULES,
+    responses(
+        (status = 200, description = "All the rules, by increasing priority", body=[Rule])
+    )
+)]
+#[get("")]
+async fn get_all_rules(state: web::Data<AppState>) -> impl Responder {
+    let rules = state.rule_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(rules)
+}
+
+/// Try out a rule.
+///
+/// Lists the existing transactions the rule would change, without saving the rule nor changing them.
+#[utoipa::path(
+    tag = RULES,
+    responses(
+        (status = 200, description = "The transactions the rule would change", body=[RuleChange]),
+        (status = 400, description = "Invalid rule", body=ErrorResponse),
+        (status = 404, description = "Tag not found", body=ErrorResponse)
+    )
+)]
+#[post("/dry-run")]
+async fn dry_run_rule(
+    state: web::Data<AppState>,
+    user_request: web::Json<RuleRequest>,
+) -> impl Responder {
+    let rule = match build_rule(&state, user_request.into_inner(), false) {
+        Ok(rule) => rule,
+        Err(response) => return response,
+    };
+
+    let rule_service = state.rule_service.lock().unwrap();
+    match rule_service.dry_run(rule) {
+        Ok(changes) => HttpResponse::Ok().json(changes),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Apply the rules to the history.
+///
+/// The rules are evaluated against every existing transaction, by increasing priority.
+/// The category and the payee are only given to the transactions without one.
+#[utoipa::path(
+    tag = RULES,
+    responses(
+        (status = 200, description = "The changed transactions", body=[RuleChange])
+    )
+)]
+#[post("/apply")]
+async fn apply_rules(state: web::Data<AppState>) -> impl Responder {
+    let changes = state.rule_service.lock().unwrap().apply_to_history();
+    HttpResponse::Ok().json(changes)
+}
+
+/// Get rule by id.
+#[utoipa::path(
+    tag = RULES,
+    responses(
+        (status = 200, description = "The rule", body=Rule),
+        (status = 400, description = "Malformed rule id", body=ErrorResponse),
+        (status = 404, description = "Rule not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let rule_id: RuleId = match parse_id(&id) {
+        Ok(rule_id) => rule_id,
+        Err(response) => return response,
+    };
+
+    let rule_service = state.rule_service.lock().unwrap();
+    match rule_service.find_by_id_or_fail(&rule_id) {
+        Ok(rule) => HttpResponse::Ok().json(rule),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create rule.
+///
+/// The rule applies to the transactions created from now on,
+/// the existing ones are only changed when the rules are applied to the history.
+#[utoipa::path(
+    tag = RULES,
+    responses(
+        (status = 200, description = "The id of the created rule"),
+        (status = 400, description = "Invalid rule", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_rule(
+    state: web::Data<AppState>,
+    user_request: web::Json<RuleRequest>,
+) -> impl Responder {
+    let rule = match build_rule(&state, user_request.into_inner(), true) {
+        Ok(rule) => rule,
+        Err(response) => return response,
+    };
+
+    let rule_service = state.rule_service.lock().unwrap();
+    match rule_service.create(rule) {
+        Ok(rule_id) => HttpResponse::Ok().json(rule_id),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update rule.
+#[utoipa::path(
+    tag = RULES,
+    responses(
+        (status = 200, description = "The updated rule", body=Rule),
+        (status = 400, description = "Invalid rule", body=ErrorResponse),
+        (status = 404, description = "Rule not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_rule(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<RuleRequest>,
+) -> impl Responder {
+    let rule_id: RuleId = match parse_id(&id) {
+        Ok(rule_id) => rule_id,
+        Err(response) => return response,
+    };
+    let rule = match build_rule(&state, user_request.into_inner(), true) {
+        Ok(rule) => rule,
+        Err(response) => return response,
+    };
+
+    let rule_service = state.rule_service.lock().unwrap();
+    match rule_service.update(&rule_id, rule) {
+        Ok(rule) => HttpResponse::Ok().json(rule),
+        Err(error @ UpdateError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Delete rule.
+///
+/// The transactions the rule already changed are kept as they are.
+#[utoipa::path(
+    tag = RULES,
+    responses(
+        (status = 204, description = "The rule was deleted"),
+        (status = 400, description = "Malformed rule id", body=ErrorResponse),
+        (status = 404, description = "Rule not found", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_rule(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let rule_id: RuleId = match parse_id(&id) {
+        Ok(rule_id) => rule_id,
+        Err(response) => return response,
+    };
+
+    let rule_service = state.rule_service.lock().unwrap();
+    match rule_service.delete(&rule_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_rules)
+        // Registered before `/{id}`, which would match the paths otherwise.
+        .service(dry_run_rule)
+        .service(apply_rules)
+        .service(get_by_id)
+        .service(create_rule)
+        .service(update_rule)
+        .service(delete_rule);
+}
@@ -0,0 +1,44 @@
+use crate::interfaces::api::state::AppState;
+use chrono::Utc;
+use log::{error, info};
+use std::time::Duration;
+
+/// How often the due occurrences of the recurring transactions, and the due interest, are looked for.
+const SCHEDULER_PERIOD: Duration = Duration::from_secs(60);
+
+/// Creates the due occurrences of the recurring transactions, and posts the due interest of the accounts,
+/// in the background while the server runs.
+/// The first run happens right away, to catch up on what fell due while it was stopped.
+pub(super) fn start_scheduler(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULER_PERIOD);
+        loop {
+            interval.tick().await;
+            // The services lock their repositories and query SQLite, off the threads of the async runtime
+            let state = state.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let created = state
+                    .recurring_transaction_service
+                    .lock()
+                    .unwrap()
+                    .materialize_due(Utc::now());
+                if !created.is_empty() {
+                    info!("Created {} recurring transaction(s)", created.len());
+                }
+
+                let posted = state
+                    .interest_service
+                    .lock()
+                    .unwrap()
+                    .post_due(Utc::now().date_naive());
+                if !posted.is_empty() {
+                    info!("Posted {} interest transaction(s)", posted.len());
+                }
+            })
+            .await;
+            if let Err(error) = result {
+                error!("The scheduled run failed: {error}");
+            }
+        }
+    });
+}
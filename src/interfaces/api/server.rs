@@ -1,7 +1,11 @@
-use crate::interfaces::api::accounts;
+use crate::interfaces::api::scheduler::start_scheduler;
 use crate::interfaces::api::state::AppState;
+use crate::interfaces::api::{
+    accounts, budgets, categories, debts, goals, institutions, interest, loans, payees,
+    recurring_transactions, rules, statements, tags, transactions,
+};
 use actix_web::{middleware::Logger, web, App, HttpServer};
-use utoipa::{Modify, OpenApi};
+use utoipa::OpenApi;
 use utoipa_actix_web::AppExt;
 use utoipa_scalar::{Scalar, Servable as ScalarServable};
 
@@ -17,21 +21,55 @@ pub async fn start_server(address: &str) {
     #[derive(OpenApi)]
     #[openapi(
         tags(
-            (name = "accounts", description = "Accounts related endpoints")
+            (name = "accounts", description = "Accounts related endpoints"),
+            (name = "institutions", description = "Institutions related endpoints"),
+            (name = "transactions", description = "Transactions related endpoints"),
+            (name = "categories", description = "Categories related endpoints"),
+            (name = "tags", description = "Tags related endpoints"),
+            (name = "payees", description = "Payees related endpoints"),
+            (name = "rules", description = "Rules related endpoints"),
+            (name = "recurring transactions", description = "Recurring transactions related endpoints"),
+            (name = "budgets", description = "Budgets related endpoints"),
+            (name = "goals", description = "Savings goals related endpoints"),
+            (name = "interest", description = "Interest accrual related endpoints"),
+            (name = "loans", description = "Loans related endpoints"),
+            (name = "debts", description = "Debt payoff planning endpoints"),
+            (name = "statements", description = "Credit card statements related endpoints")
         ),
     )]
     struct ApiDoc;
 
-    HttpServer::new(move || {
-        let app_state = AppState::new();
+    // The state is shared by all the workers, so they all see the same data.
+    let app_state = AppState::new();
+    start_scheduler(app_state.clone());
 
+    HttpServer::new(move || {
         App::new()
             .into_utoipa_app()
             .openapi(ApiDoc::openapi())
-            .service(utoipa_actix_web::scope("/accounts").configure(accounts::configure))
+            .service(
+                utoipa_actix_web::scope("/accounts")
+                    .configure(accounts::configure)
+                    .configure(interest::configure)
+                    .configure(statements::configure),
+            )
+            .service(utoipa_actix_web::scope("/institutions").configure(institutions::configure))
+            .service(utoipa_actix_web::scope("/transactions").configure(transactions::configure))
+            .service(utoipa_actix_web::scope("/categories").configure(categories::configure))
+            .service(utoipa_actix_web::scope("/tags").configure(tags::configure))
+            .service(utoipa_actix_web::scope("/payees").configure(payees::configure))
+            .service(utoipa_actix_web::scope("/rules").configure(rules::configure))
+            .service(
+                utoipa_actix_web::scope("/recurring-transactions")
+                    .configure(recurring_transactions::configure),
+            )
+            .service(utoipa_actix_web::scope("/budgets").configure(budgets::configure))
+            .service(utoipa_actix_web::scope("/goals").configure(goals::configure))
+            .service(utoipa_actix_web::scope("/loans").configure(loans::configure))
+            .service(utoipa_actix_web::scope("/debts").configure(debts::configure))
             .map(|app| {
                 app.wrap(Logger::default())
-                    .app_data(web::Data::new(app_state))
+                    .app_data(web::Data::new(app_state.clone()))
             })
             .route("/", web::get().to(index))
             .openapi_service(|api| Scalar::with_url("/scalar", api))
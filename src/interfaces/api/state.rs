@@ -1,17 +1,411 @@
+use crate::app::repositories::account_repository::AccountRepository;
+use crate::app::repositories::budget_repository::BudgetRepository;
+use crate::app::repositories::category_repository::CategoryRepository;
+use crate::app::repositories::goal_repository::GoalRepository;
+use crate::app::repositories::institution_repository::InstitutionRepository;
+use crate::app::repositories::interest_config_repository::InterestConfigRepository;
+use crate::app::repositories::loan_repository::LoanRepository;
+use crate::app::repositories::payee_repository::PayeeRepository;
+use crate::app::repositories::recurring_transaction_repository::RecurringTransactionRepository;
+use crate::app::repositories::rule_repository::RuleRepository;
+use crate::app::repositories::statement_config_repository::StatementConfigRepository;
+use crate::app::repositories::tag_repository::TagRepository;
+use crate::app::repositories::transaction_repository::TransactionRepository;
+use crate::app::services::account_merge_service::AccountMergeService;
 use crate::app::services::account_service::AccountService;
+use crate::app::services::budget_service::BudgetService;
+use crate::app::services::category_service::CategoryService;
+use crate::app::services::goal_service::GoalService;
+use crate::app::services::institution_service::InstitutionService;
+use crate::app::services::interest_service::InterestService;
+use crate::app::services::loan_service::LoanService;
+use crate::app::services::payee_service::PayeeService;
+use crate::app::services::payoff_service::PayoffService;
+use crate::app::services::recurring_transaction_service::RecurringTransactionService;
+use crate::app::services::rule_service::RuleService;
+use crate::app::services::statement_service::StatementService;
+use crate::app::services::suggestion_service::SuggestionService;
+use crate::app::services::tag_service::TagService;
+use crate::app::services::transaction_service::TransactionService;
 use crate::infrastructure::repositories::in_memory::account_repository::InMemoryAccountRepository;
+use crate::infrastructure::repositories::in_memory::budget_repository::InMemoryBudgetRepository;
+use crate::infrastructure::repositories::in_memory::category_repository::InMemoryCategoryRepository;
+use crate::infrastructure::repositories::in_memory::goal_repository::InMemoryGoalRepository;
+use crate::infrastructure::repositories::in_memory::institution_repository::InMemoryInstitutionRepository;
+use crate::infrastructure::repositories::in_memory::interest_config_repository::InMemoryInterestConfigRepository;
+use crate::infrastructure::repositories::in_memory::loan_repository::InMemoryLoanRepository;
+use crate::infrastructure::repositories::in_memory::payee_repository::InMemoryPayeeRepository;
+use crate::infrastructure::repositories::in_memory::recurring_transaction_repository::InMemoryRecurringTransactionRepository;
+use crate::infrastructure::repositories::in_memory::rule_repository::InMemoryRuleRepository;
+use crate::infrastructure::repositories::in_memory::statement_config_repository::InMemoryStatementConfigRepository;
+use crate::infrastructure::repositories::in_memory::tag_repository::InMemoryTagRepository;
+use crate::infrastructure::repositories::in_memory::transaction_repository::InMemoryTransactionRepository;
+use crate::infrastructure::repositories::sqlite::account_repository::SQliteAccountRepository;
+use crate::infrastructure::repositories::sqlite::budget_repository::SQliteBudgetRepository;
+use crate::infrastructure::repositories::sqlite::category_repository::SQliteCategoryRepository;
+use crate::infrastructure::repositories::sqlite::common::establish_sqlite_connection;
+use crate::infrastructure::repositories::sqlite::goal_repository::SQliteGoalRepository;
+use crate::infrastructure::repositories::sqlite::institution_repository::SQliteInstitutionRepository;
+use crate::infrastructure::repositories::sqlite::interest_config_repository::SQliteInterestConfigRepository;
+use crate::infrastructure::repositories::sqlite::loan_repository::SQliteLoanRepository;
+use crate::infrastructure::repositories::sqlite::payee_repository::SQlitePayeeRepository;
+use crate::infrastructure::repositories::sqlite::recurring_transaction_repository::SQliteRecurringTransactionRepository;
+use crate::infrastructure::repositories::sqlite::rule_repository::SQliteRuleRepository;
+use crate::infrastructure::repositories::sqlite::statement_config_repository::SQliteStatementConfigRepository;
+use crate::infrastructure::repositories::sqlite::tag_repository::SQliteTagRepository;
+use crate::infrastructure::repositories::sqlite::transaction_repository::SQliteTransactionRepository;
+use std::env;
 use std::sync::{Arc, Mutex};
 
+type AccountRepositoryImpl = Box<dyn AccountRepository + Send>;
+type TransactionRepositoryImpl = Box<dyn TransactionRepository + Send>;
+type CategoryRepositoryImpl = Box<dyn CategoryRepository + Send>;
+type TagRepositoryImpl = Box<dyn TagRepository + Send>;
+type PayeeRepositoryImpl = Box<dyn PayeeRepository + Send>;
+type RuleRepositoryImpl = Box<dyn RuleRepository + Send>;
+type RecurringTransactionRepositoryImpl = Box<dyn RecurringTransactionRepository + Send>;
+type BudgetRepositoryImpl = Box<dyn BudgetRepository + Send>;
+type GoalRepositoryImpl = Box<dyn GoalRepository + Send>;
+type InterestConfigRepositoryImpl = Box<dyn InterestConfigRepository + Send>;
+type LoanRepositoryImpl = Box<dyn LoanRepository + Send>;
+type StatementConfigRepositoryImpl = Box<dyn StatementConfigRepository + Send>;
+type InstitutionRepositoryImpl = Box<dyn InstitutionRepository + Send>;
+
+type TransactionServiceImpl = TransactionService<
+    TransactionRepositoryImpl,
+    AccountRepositoryImpl,
+    CategoryRepositoryImpl,
+    PayeeRepositoryImpl,
+    RuleRepositoryImpl,
+>;
+
+type RuleServiceImpl = RuleService<
+    RuleRepositoryImpl,
+    TransactionRepositoryImpl,
+    AccountRepositoryImpl,
+    CategoryRepositoryImpl,
+    PayeeRepositoryImpl,
+>;
+
+type CategoryServiceImpl = CategoryService<
+    CategoryRepositoryImpl,
+    TransactionRepositoryImpl,
+    PayeeRepositoryImpl,
+    RecurringTransactionRepositoryImpl,
+    BudgetRepositoryImpl,
+>;
+
+type TagServiceImpl = TagService<
+    TagRepositoryImpl,
+    TransactionRepositoryImpl,
+    AccountRepositoryImpl,
+    RuleRepositoryImpl,
+    RecurringTransactionRepositoryImpl,
+    BudgetRepositoryImpl,
+>;
+
+type PayeeServiceImpl = PayeeService<
+    PayeeRepositoryImpl,
+    TransactionRepositoryImpl,
+    CategoryRepositoryImpl,
+    RuleRepositoryImpl,
+    RecurringTransactionRepositoryImpl,
+>;
+
+type RecurringTransactionServiceImpl = RecurringTransactionService<
+    RecurringTransactionRepositoryImpl,
+    TransactionRepositoryImpl,
+    AccountRepositoryImpl,
+    CategoryRepositoryImpl,
+    PayeeRepositoryImpl,
+    RuleRepositoryImpl,
+>;
+
+type BudgetServiceImpl = BudgetService<
+    BudgetRepositoryImpl,
+    TransactionRepositoryImpl,
+    CategoryRepositoryImpl,
+    TagRepositoryImpl,
+    PayeeRepositoryImpl,
+    RecurringTransactionRepositoryImpl,
+>;
+
+type InterestServiceImpl = InterestService<
+    InterestConfigRepositoryImpl,
+    TransactionRepositoryImpl,
+    AccountRepositoryImpl,
+    CategoryRepositoryImpl,
+    PayeeRepositoryImpl,
+    RuleRepositoryImpl,
+>;
+
+type LoanServiceImpl =
+    LoanService<LoanRepositoryImpl, AccountRepositoryImpl, TransactionRepositoryImpl>;
+
+type PayoffServiceImpl = PayoffService<
+    AccountRepositoryImpl,
+    InterestConfigRepositoryImpl,
+    LoanRepositoryImpl,
+    TransactionRepositoryImpl,
+>;
+
+type StatementServiceImpl = StatementService<
+    StatementConfigRepositoryImpl,
+    AccountRepositoryImpl,
+    TransactionRepositoryImpl,
+>;
+
+type AccountMergeServiceImpl = AccountMergeService<
+    TransactionRepositoryImpl,
+    AccountRepositoryImpl,
+    CategoryRepositoryImpl,
+    PayeeRepositoryImpl,
+    RuleRepositoryImpl,
+    RecurringTransactionRepositoryImpl,
+    GoalRepositoryImpl,
+    LoanRepositoryImpl,
+    InterestConfigRepositoryImpl,
+    StatementConfigRepositoryImpl,
+>;
+
+type InstitutionServiceImpl =
+    InstitutionService<InstitutionRepositoryImpl, AccountRepositoryImpl, RuleRepositoryImpl>;
+
+/// The repositories the services are built on, all from the same backend.
+struct Repositories {
+    account: AccountRepositoryImpl,
+    transaction: TransactionRepositoryImpl,
+    category: CategoryRepositoryImpl,
+    tag: TagRepositoryImpl,
+    payee: PayeeRepositoryImpl,
+    rule: RuleRepositoryImpl,
+    recurring_transaction: RecurringTransactionRepositoryImpl,
+    budget: BudgetRepositoryImpl,
+    goal: GoalRepositoryImpl,
+    interest_config: InterestConfigRepositoryImpl,
+    loan: LoanRepositoryImpl,
+    statement_config: StatementConfigRepositoryImpl,
+    institution: InstitutionRepositoryImpl,
+}
+
+impl Repositories {
+    /// Each repository has its own connection to the database file.
+    fn sqlite() -> Self {
+        Self {
+            account: Box::new(SQliteAccountRepository::new(establish_sqlite_connection())),
+            transaction: Box::new(SQliteTransactionRepository::new(
+                establish_sqlite_connection(),
+            )),
+            category: Box::new(SQliteCategoryRepository::new(establish_sqlite_connection())),
+            tag: Box::new(SQliteTagRepository::new(establish_sqlite_connection())),
+            payee: Box::new(SQlitePayeeRepository::new(establish_sqlite_connection())),
+            rule: Box::new(SQliteRuleRepository::new(establish_sqlite_connection())),
+            recurring_transaction: Box::new(SQliteRecurringTransactionRepository::new(
+                establish_sqlite_connection(),
+            )),
+            budget: Box::new(SQliteBudgetRepository::new(establish_sqlite_connection())),
+            goal: Box::new(SQliteGoalRepository::new(establish_sqlite_connection())),
+            interest_config: Box::new(SQliteInterestConfigRepository::new(
+                establish_sqlite_connection(),
+            )),
+            loan: Box::new(SQliteLoanRepository::new(establish_sqlite_connection())),
+            statement_config: Box::new(SQliteStatementConfigRepository::new(
+                establish_sqlite_connection(),
+            )),
+            institution: Box::new(SQliteInstitutionRepository::new(
+                establish_sqlite_connection(),
+            )),
+        }
+    }
+
+    fn in_memory() -> Self {
+        Self {
+            account: Box::new(InMemoryAccountRepository::new()),
+            transaction: Box::new(InMemoryTransactionRepository::new()),
+            category: Box::new(InMemoryCategoryRepository::new()),
+            tag: Box::new(InMemoryTagRepository::new()),
+            payee: Box::new(InMemoryPayeeRepository::new()),
+            rule: Box::new(InMemoryRuleRepository::new()),
+            recurring_transaction: Box::new(InMemoryRecurringTransactionRepository::new()),
+            budget: Box::new(InMemoryBudgetRepository::new()),
+            goal: Box::new(InMemoryGoalRepository::new()),
+            interest_config: Box::new(InMemoryInterestConfigRepository::new()),
+            loan: Box::new(InMemoryLoanRepository::new()),
+            statement_config: Box::new(InMemoryStatementConfigRepository::new()),
+            institution: Box::new(InMemoryInstitutionRepository::new()),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct AppState {
-    pub account_service: Arc<Mutex<AccountService<InMemoryAccountRepository>>>,
+    pub account_service:
+        Arc<Mutex<AccountService<AccountRepositoryImpl, TransactionRepositoryImpl>>>,
+    pub account_merge_service: Arc<Mutex<AccountMergeServiceImpl>>,
+    pub institution_service: Arc<Mutex<InstitutionServiceImpl>>,
+    pub transaction_service: Arc<Mutex<TransactionServiceImpl>>,
+    pub category_service: Arc<Mutex<CategoryServiceImpl>>,
+    pub tag_service: Arc<Mutex<TagServiceImpl>>,
+    pub payee_service: Arc<Mutex<PayeeServiceImpl>>,
+    pub rule_service: Arc<Mutex<RuleServiceImpl>>,
+    pub recurring_transaction_service: Arc<Mutex<RecurringTransactionServiceImpl>>,
+    pub budget_service: Arc<Mutex<BudgetServiceImpl>>,
+    pub goal_service: Arc<Mutex<GoalService<GoalRepositoryImpl, AccountRepositoryImpl>>>,
+    pub interest_service: Arc<Mutex<InterestServiceImpl>>,
+    pub loan_service: Arc<Mutex<LoanServiceImpl>>,
+    pub payoff_service: Arc<Mutex<PayoffServiceImpl>>,
+    pub statement_service: Arc<Mutex<StatementServiceImpl>>,
+    pub suggestion_service:
+        Arc<Mutex<SuggestionService<TransactionRepositoryImpl, CategoryRepositoryImpl>>>,
 }
 
 impl AppState {
+    /// Builds the services on top of the sqlite repositories when `DATABASE_URL` is set,
+    /// otherwise everything is kept in memory.
     pub fn new() -> Self {
-        let account_repository = Arc::new(Mutex::new(InMemoryAccountRepository::new()));
-        let account_service = Arc::new(Mutex::new(AccountService::new(account_repository)));
+        let repositories = match env::var("DATABASE_URL") {
+            Ok(_) => Repositories::sqlite(),
+            Err(_) => Repositories::in_memory(),
+        };
+
+        let account_repository = Arc::new(Mutex::new(repositories.account));
+        let transaction_repository = Arc::new(Mutex::new(repositories.transaction));
+        let account_service = Arc::new(Mutex::new(AccountService::new(
+            account_repository.clone(),
+            transaction_repository.clone(),
+        )));
+
+        let category_repository = Arc::new(Mutex::new(repositories.category));
+        let payee_repository = Arc::new(Mutex::new(repositories.payee));
+        let rule_repository = Arc::new(Mutex::new(repositories.rule));
+        let recurring_transaction_repository =
+            Arc::new(Mutex::new(repositories.recurring_transaction));
+        let tag_repository = Arc::new(Mutex::new(repositories.tag));
+        let budget_repository = Arc::new(Mutex::new(repositories.budget));
+        let rule_service = Arc::new(Mutex::new(RuleService::new(
+            rule_repository.clone(),
+            transaction_repository.clone(),
+            account_repository.clone(),
+            category_repository.clone(),
+            payee_repository.clone(),
+        )));
+        let transaction_service = Arc::new(Mutex::new(TransactionService::new(
+            account_service.clone(),
+            transaction_repository.clone(),
+            category_repository.clone(),
+            payee_repository.clone(),
+            rule_service.clone(),
+        )));
+        let recurring_transaction_service = Arc::new(Mutex::new(RecurringTransactionService::new(
+            recurring_transaction_repository.clone(),
+            transaction_service.clone(),
+        )));
+        let interest_config_repository = Arc::new(Mutex::new(repositories.interest_config));
+        let interest_service = Arc::new(Mutex::new(InterestService::new(
+            interest_config_repository.clone(),
+            account_repository.clone(),
+            transaction_service.clone(),
+        )));
+
+        let category_service = CategoryService::new(
+            category_repository.clone(),
+            transaction_repository.clone(),
+            payee_repository.clone(),
+            recurring_transaction_repository.clone(),
+            budget_repository.clone(),
+        );
+        category_service.seed_default_categories();
+        let category_service = Arc::new(Mutex::new(category_service));
+
+        let tag_service = Arc::new(Mutex::new(TagService::new(
+            tag_repository.clone(),
+            transaction_repository.clone(),
+            account_repository.clone(),
+            rule_repository.clone(),
+            recurring_transaction_repository.clone(),
+            budget_repository.clone(),
+        )));
+
+        let payee_service = Arc::new(Mutex::new(PayeeService::new(
+            payee_repository,
+            transaction_repository.clone(),
+            category_repository.clone(),
+            rule_repository.clone(),
+            recurring_transaction_repository.clone(),
+        )));
+
+        let budget_service = Arc::new(Mutex::new(BudgetService::new(
+            budget_repository,
+            transaction_repository.clone(),
+            category_service.clone(),
+            tag_repository,
+        )));
+
+        let goal_repository = Arc::new(Mutex::new(repositories.goal));
+        let goal_service = Arc::new(Mutex::new(GoalService::new(
+            goal_repository.clone(),
+            account_repository.clone(),
+        )));
+
+        let loan_repository = Arc::new(Mutex::new(repositories.loan));
+        let loan_service = Arc::new(Mutex::new(LoanService::new(
+            loan_repository.clone(),
+            account_repository.clone(),
+            transaction_repository.clone(),
+        )));
+
+        let statement_config_repository = Arc::new(Mutex::new(repositories.statement_config));
+        let statement_service = Arc::new(Mutex::new(StatementService::new(
+            statement_config_repository.clone(),
+            account_repository.clone(),
+            transaction_repository.clone(),
+        )));
+
+        let account_merge_service = Arc::new(Mutex::new(AccountMergeService::new(
+            account_service.clone(),
+            transaction_service.clone(),
+            rule_repository.clone(),
+            recurring_transaction_repository,
+            goal_repository,
+            loan_repository,
+            interest_config_repository.clone(),
+            statement_config_repository,
+        )));
+
+        let payoff_service = Arc::new(Mutex::new(PayoffService::new(
+            account_repository.clone(),
+            interest_config_repository,
+            loan_service.clone(),
+        )));
+
+        let institution_service = Arc::new(Mutex::new(InstitutionService::new(
+            Arc::new(Mutex::new(repositories.institution)),
+            account_repository,
+            rule_repository,
+        )));
+
+        let suggestion_service = Arc::new(Mutex::new(SuggestionService::new(
+            transaction_repository,
+            category_repository,
+        )));
 
-        Self { account_service }
+        Self {
+            account_service,
+            account_merge_service,
+            institution_service,
+            transaction_service,
+            category_service,
+            tag_service,
+            payee_service,
+            rule_service,
+            recurring_transaction_service,
+            budget_service,
+            goal_service,
+            interest_service,
+            loan_service,
+            payoff_service,
+            statement_service,
+            suggestion_service,
+        }
     }
 }
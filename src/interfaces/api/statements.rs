@@ -0,0 +1,207 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::statement::{Statement, StatementConfig};
+use crate::app::services::statement_service::{StatementError, ValidationError};
+use crate::app::typing::amount::Amount;
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{delete, get, put, web, HttpResponse, Responder};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct StatementConfigRequest {
+    /// The day of the month the cycles end on, the last day of the shorter months. (Eg: 25)
+    closing_day: u32,
+
+    /// The number of days after the closing date to pay the statement.
+    grace_period_days: u32,
+
+    /// The part of the closing balance to pay at least, in percent. (Eg: 5)
+    minimum_payment_rate: f32,
+
+    /// The least minimum payment, the whole closing balance when it is lower.
+    #[schema(value_type = f32)]
+    minimum_payment_floor: Amount,
+}
+
+impl StatementConfigRequest {
+    fn into_config(self, account_id: AccountId) -> StatementConfig {
+        StatementConfig::new(
+            account_id,
+            self.closing_day,
+            self.grace_period_days,
+            self.minimum_payment_rate,
+            self.minimum_payment_floor,
+        )
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct StatementParams {
+    /// The statements of the cycles closed before this day, paid by its end, defaults to today.
+    at: Option<NaiveDate>,
+}
+
+const STATEMENTS: &str = "Statements";
+
+/// Get account statement configuration.
+#[utoipa::path(
+    tag = STATEMENTS,
+    responses(
+        (status = 200, description = "The statement configuration of the account", body=StatementConfig),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "The account has no statement configuration", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/statement-config")]
+async fn get_statement_config(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let statement_service = state.statement_service.lock().unwrap();
+    match statement_service.find_by_account_id_or_fail(&account_id) {
+        Ok(config) => HttpResponse::Ok().json(config),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Configure account statements.
+///
+/// Replaces the previous configuration of the credit account, if any.
+/// The statements are cut from the transactions, so they all follow the new configuration.
+#[utoipa::path(
+    tag = STATEMENTS,
+    responses(
+        (status = 200, description = "The saved statement configuration", body=StatementConfig),
+        (status = 400, description = "Malformed account id, or invalid configuration", body=ErrorResponse),
+        (status = 404, description = "Account not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}/statement-config")]
+async fn configure_statements(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<StatementConfigRequest>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let statement_service = state.statement_service.lock().unwrap();
+    match statement_service.configure(user_request.into_inner().into_config(account_id)) {
+        Ok(config) => HttpResponse::Ok().json(config),
+        Err(error @ ValidationError::AccountNotFound(_)) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Remove account statement configuration.
+#[utoipa::path(
+    tag = STATEMENTS,
+    responses(
+        (status = 204, description = "The account has no statements anymore"),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "The account has no statement configuration", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}/statement-config")]
+async fn remove_statement_config(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let statement_service = state.statement_service.lock().unwrap();
+    match statement_service.remove(&account_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get account statements.
+///
+/// The statements of the closed cycles of the credit account, from its first transaction, in date order.
+/// The expenses are charges, the incomes are payments, and the incomes made after a closing date,
+/// by the due date, pay its statement.
+#[utoipa::path(
+    tag = STATEMENTS,
+    params(StatementParams),
+    responses(
+        (status = 200, description = "The statements of the account", body=[Statement]),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "The account was not found, or has no statement configuration", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/statements")]
+async fn get_statements(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<StatementParams>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let at = params.at.unwrap_or_else(|| Utc::now().date_naive());
+    let statement_service = state.statement_service.lock().unwrap();
+    match statement_service.statements(&account_id, at) {
+        Ok(statements) => HttpResponse::Ok().json(statements),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get latest account statement.
+///
+/// The statement of the last closed cycle, with its minimum payment and due status.
+#[utoipa::path(
+    tag = STATEMENTS,
+    params(StatementParams),
+    responses(
+        (status = 200, description = "The latest statement of the account", body=Statement),
+        (status = 400, description = "Malformed account id", body=ErrorResponse),
+        (status = 404, description = "The account was not found, has no statement configuration, or no closed cycle", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/statements/latest")]
+async fn get_latest_statement(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    params: web::Query<StatementParams>,
+) -> impl Responder {
+    let account_id: AccountId = match parse_id(&id) {
+        Ok(account_id) => account_id,
+        Err(response) => return response,
+    };
+
+    let at = params.at.unwrap_or_else(|| Utc::now().date_naive());
+    let statement_service = state.statement_service.lock().unwrap();
+    match statement_service.latest(&account_id, at) {
+        Ok(statement) => HttpResponse::Ok().json(statement),
+        Err(
+            error @ (StatementError::NotConfigured(_)
+            | StatementError::AccountNotFound(_)
+            | StatementError::NoStatement(_)),
+        ) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_statement_config)
+        .service(configure_statements)
+        .service(remove_statement_config)
+        .service(get_statements)
+        .service(get_latest_statement);
+}
@@ -0,0 +1,186 @@
+use crate::app::entities::tag::{Tag, TagId};
+use crate::app::services::tag_service::{MergeError, RenameError, SetTagsError, TagTotal};
+use crate::interfaces::api::common::{parse_id, ErrorResponse};
+use crate::interfaces::api::state::AppState;
+use actix_web::{get, post, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+/// Body of the requests replacing the tags of a transaction or an account.
+#[derive(Deserialize, ToSchema)]
+pub(super) struct SetTagsRequest {
+    /// The tag names, the tags that do not exist yet are created.
+    pub(super) tags: Vec<String>,
+}
+
+/// Answers a request replacing the tags of a transaction or an account.
+pub(super) fn set_tags_response(result: Result<Vec<TagId>, SetTagsError>) -> HttpResponse {
+    match result {
+        Ok(tags) => HttpResponse::Ok().json(tags),
+        Err(error @ SetTagsError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+struct RenameTagRequest {
+    /// The new tag name.
+    name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct MergeTagRequest {
+    /// The tag the merged tag is replaced by.
+    target_id: TagId,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ReportParams {
+    /// Only the transactions on or after this date.
+    from: Option<DateTime<Utc>>,
+
+    /// Only the transactions strictly before this date.
+    to: Option<DateTime<Utc>>,
+}
+
+const TAGS: &str = "Tags";
+
+/// Get all tags.
+#[utoipa::path(
+    tag = TAGS,
+    responses(
+        (status = 200, description = "All the tags, sorted by name", body=[Tag])
+    )
+)]
+#[get("")]
+async fn get_all_tags(state: web::Data<AppState>) -> impl Responder {
+    let tags = state.tag_service.lock().unwrap().find_all();
+    HttpResponse::Ok().json(tags)
+}
+
+/// Totals per tag.
+///
+/// The confirmed transactions are totaled per tag and currency,
+/// a transaction with several tags counts in each of them.
+#[utoipa::path(
+    tag = TAGS,
+    params(ReportParams),
+    responses(
+        (status = 200, description = "The totals of the tags", body=[TagTotal])
+    )
+)]
+#[get("/report")]
+async fn get_report(
+    state: web::Data<AppState>,
+    params: web::Query<ReportParams>,
+) -> impl Responder {
+    let tag_service = state.tag_service.lock().unwrap();
+    HttpResponse::Ok().json(tag_service.report(params.from, params.to))
+}
+
+/// Get tag by id.
+#[utoipa::path(
+    tag = TAGS,
+    responses(
+        (status = 200, description = "The tag", body=Tag),
+        (status = 400, description = "Malformed tag id", body=ErrorResponse),
+        (status = 404, description = "Tag not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let tag_id: TagId = match parse_id(&id) {
+        Ok(tag_id) => tag_id,
+        Err(response) => return response,
+    };
+
+    let tag_service = state.tag_service.lock().unwrap();
+    match tag_service.find_by_id_or_fail(&tag_id) {
+        Ok(tag) => HttpResponse::Ok().json(tag),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Rename tag.
+///
+/// The new name must not be used by another tag, the two tags can be merged instead.
+#[utoipa::path(
+    tag = TAGS,
+    responses(
+        (status = 200, description = "The renamed tag", body=Tag),
+        (status = 400, description = "Invalid tag name", body=ErrorResponse),
+        (status = 404, description = "Tag not found", body=ErrorResponse),
+        (status = 409, description = "Another tag has this name", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn rename_tag(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<RenameTagRequest>,
+) -> impl Responder {
+    let tag_id: TagId = match parse_id(&id) {
+        Ok(tag_id) => tag_id,
+        Err(response) => return response,
+    };
+
+    let tag_service = state.tag_service.lock().unwrap();
+    match tag_service.rename(&tag_id, &user_request.name) {
+        Ok(tag) => HttpResponse::Ok().json(tag),
+        Err(error @ RenameError::EntityIdNotFound) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error @ RenameError::DuplicateName(_)) => {
+            HttpResponse::Conflict().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Merge tag.
+///
+/// The transactions and accounts carrying the tag are tagged with the target tag instead,
+/// then the tag is deleted.
+#[utoipa::path(
+    tag = TAGS,
+    responses(
+        (status = 200, description = "The target tag", body=Tag),
+        (status = 400, description = "Malformed tag id, or a tag merged into itself", body=ErrorResponse),
+        (status = 404, description = "Tag not found", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/merge")]
+async fn merge_tag(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<MergeTagRequest>,
+) -> impl Responder {
+    let tag_id: TagId = match parse_id(&id) {
+        Ok(tag_id) => tag_id,
+        Err(response) => return response,
+    };
+
+    let tag_service = state.tag_service.lock().unwrap();
+    match tag_service.merge(&tag_id, &user_request.target_id) {
+        Ok(tag) => HttpResponse::Ok().json(tag),
+        Err(error @ MergeError::SameTag) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_tags)
+        // Registered before `/{id}`, which would match the path otherwise.
+        .service(get_report)
+        .service(get_by_id)
+        .service(rename_tag)
+        .service(merge_tag);
+}
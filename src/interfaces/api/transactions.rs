@@ -0,0 +1,588 @@
+use crate::app::entities::account::AccountId;
+use crate::app::entities::category::CategoryId;
+use crate::app::entities::payee::PayeeId;
+use crate::app::entities::tag::TagId;
+use crate::app::entities::transaction::{
+    AccountRef, Transaction, TransactionId, TransactionRevision, TransactionSplit,
+    TransactionStatus, TransactionType,
+};
+use crate::app::repositories::common::Page;
+use crate::app::repositories::transaction_query::{
+    SortDirection, TransactionQuery, TransactionSort, TransactionSortField, DEFAULT_PAGE_SIZE,
+};
+use crate::app::services::suggestion_service::CategorySuggestion;
+use crate::app::services::transaction_service::{
+    CreateError, EditError, ReverseError, TransactionEdit,
+};
+use crate::app::typing::amount::{Amount, MIN_AMOUNT};
+use crate::app::typing::currency::Currency;
+use crate::interfaces::api::common::{
+    ndjson_response, parse_id, parse_list, ErrorResponse, NDJSON,
+};
+use crate::interfaces::api::state::AppState;
+use crate::interfaces::api::tags::{set_tags_response, SetTagsRequest};
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+#[derive(Deserialize, ToSchema)]
+struct CreateTransactionRequest {
+    /// The account the transaction belongs to.
+    account_id: AccountId,
+
+    /// Transaction type.
+    transaction_type: TransactionType,
+
+    /// Transaction amount.
+    #[schema(value_type = f32)]
+    amount: Amount,
+
+    /// Transaction fee, defaults to zero.
+    #[schema(value_type = Option<f32>)]
+    fee: Option<Amount>,
+
+    /// Transaction currency.
+    currency: Currency,
+
+    /// Transaction status.
+    status: TransactionStatus,
+
+    /// Transaction date.
+    date: DateTime<Utc>,
+
+    /// Transaction description.
+    description: Option<String>,
+
+    /// Reference number.
+    reference_number: Option<String>,
+
+    /// Transaction message. (Eg: the SMS received for the transaction)
+    message: Option<String>,
+
+    /// The category of the transaction, it must have the kind of the transaction type.
+    category_id: Option<CategoryId>,
+
+    /// The parts of the transaction, each with its own category, instead of a single category.
+    /// Their amounts must add up to the transaction amount.
+    #[serde(default)]
+    splits: Vec<TransactionSplit>,
+
+    /// The tag names of the transaction, the tags that do not exist yet are created.
+    #[serde(default)]
+    tags: Vec<String>,
+
+    /// The payee of the transaction. When none is provided, it is recognized from the description or the message.
+    payee_id: Option<PayeeId>,
+}
+
+/// The fields to change, the ones not provided keep their value.
+#[derive(Deserialize, ToSchema)]
+struct UpdateTransactionRequest {
+    /// The account the transaction moves to.
+    account_id: Option<AccountId>,
+
+    /// Transaction type.
+    transaction_type: Option<TransactionType>,
+
+    /// Transaction amount.
+    #[schema(value_type = Option<f32>)]
+    amount: Option<Amount>,
+
+    /// Transaction fee.
+    #[schema(value_type = Option<f32>)]
+    fee: Option<Amount>,
+
+    /// Transaction date.
+    date: Option<DateTime<Utc>>,
+
+    /// Transaction description.
+    description: Option<String>,
+}
+
+impl From<UpdateTransactionRequest> for TransactionEdit {
+    fn from(request: UpdateTransactionRequest) -> Self {
+        TransactionEdit {
+            account_id: request.account_id,
+            transaction_type: request.transaction_type,
+            amount: request.amount,
+            fee: request.fee,
+            date: request.date,
+            description: request.description,
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+struct ReverseTransactionRequest {
+    /// The fee charged for the reversal, defaults to zero.
+    #[schema(value_type = Option<f32>)]
+    fee: Option<Amount>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct TransactionQueryParams {
+    /// Only the transactions of this account.
+    account_id: Option<AccountId>,
+
+    /// Only the transactions on or after this date.
+    from: Option<DateTime<Utc>>,
+
+    /// Only the transactions strictly before this date.
+    to: Option<DateTime<Utc>>,
+
+    /// Only the transactions of this type.
+    transaction_type: Option<TransactionType>,
+
+    /// Only the transactions with this status.
+    status: Option<TransactionStatus>,
+
+    /// Only the transactions in this currency.
+    currency: Option<Currency>,
+
+    /// Only the transactions of this category or of its sub categories (including split transactions).
+    category_id: Option<CategoryId>,
+
+    /// Only the transactions carrying all these tags, as comma separated names.
+    tags: Option<String>,
+
+    /// Only the transactions made with this payee.
+    payee_id: Option<PayeeId>,
+
+    /// Only the transactions with an amount greater than or equal to this one.
+    #[param(value_type = Option<f32>)]
+    min_amount: Option<Amount>,
+
+    /// Only the transactions with an amount less than or equal to this one.
+    #[param(value_type = Option<f32>)]
+    max_amount: Option<Amount>,
+
+    /// Text searched in the description, message and reference number (case-insensitive).
+    q: Option<String>,
+
+    /// Sort field, defaults to the transaction date.
+    sort: Option<TransactionSortField>,
+
+    /// Sort direction, defaults to descending.
+    order: Option<SortDirection>,
+
+    /// The `next_cursor` of the previous page.
+    cursor: Option<String>,
+
+    /// Maximum number of transactions in the page.
+    limit: Option<usize>,
+}
+
+impl TryFrom<TransactionQueryParams> for TransactionQuery {
+    type Error = HttpResponse;
+
+    fn try_from(params: TransactionQueryParams) -> Result<Self, Self::Error> {
+        let cursor = match params.cursor {
+            Some(cursor) => Some(
+                cursor
+                    .parse()
+                    .map_err(|error| HttpResponse::BadRequest().json(ErrorResponse::new(error)))?,
+            ),
+            None => None,
+        };
+
+        Ok(TransactionQuery {
+            account_id: params.account_id,
+            from: params.from,
+            to: params.to,
+            transaction_type: params.transaction_type,
+            status: params.status,
+            currency: params.currency,
+            category_ids: params.category_id.into_iter().collect(),
+            // Resolved by `build_query`, the tags are given by name.
+            tag_ids: vec![],
+            payee_id: params.payee_id,
+            min_amount: params.min_amount,
+            max_amount: params.max_amount,
+            text: params.q,
+            sort: TransactionSort {
+                field: params.sort.unwrap_or_default(),
+                direction: params.order.unwrap_or_default(),
+            },
+            cursor,
+            limit: params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        })
+    }
+}
+
+/// Parses the query parameters, the category filter covering the sub categories of the requested one.
+fn build_query(
+    state: &AppState,
+    params: TransactionQueryParams,
+) -> Result<TransactionQuery, HttpResponse> {
+    let tags = parse_list(params.tags.as_deref());
+    let mut query: TransactionQuery = params.try_into()?;
+
+    if !tags.is_empty() {
+        let tag_service = state.tag_service.lock().unwrap();
+        query.tag_ids = tag_service
+            .find_by_names(&tags)
+            .map_err(|error| HttpResponse::NotFound().json(ErrorResponse::new(error)))?;
+    }
+
+    if let Some(category_id) = query.category_ids.first().cloned() {
+        let category_service = state.category_service.lock().unwrap();
+        query.category_ids = category_service
+            .find_with_descendants(&category_id)
+            .map_err(|error| HttpResponse::NotFound().json(ErrorResponse::new(error)))?;
+    }
+
+    Ok(query)
+}
+
+const TRANSACTIONS: &str = "Transactions";
+
+/// Search transactions.
+///
+/// The transactions matching all the provided filters are returned one page at a time,
+/// the next page is requested by passing the `next_cursor` of the page as `cursor`.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    params(TransactionQueryParams),
+    responses(
+        (status = 200, description = "A page of transactions", body=Page<Transaction>),
+        (status = 400, description = "Invalid query", body=ErrorResponse),
+        (status = 404, description = "Category or tag not found", body=ErrorResponse)
+    )
+)]
+#[get("")]
+async fn get_all_transactions(
+    state: web::Data<AppState>,
+    params: web::Query<TransactionQueryParams>,
+) -> impl Responder {
+    let query = match build_query(&state, params.into_inner()) {
+        Ok(query) => query,
+        Err(response) => return response,
+    };
+
+    let transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.find(&query) {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Export transactions.
+///
+/// All the transactions matching the filters are streamed as newline delimited JSON,
+/// one transaction per line, in the requested order. The `limit` is ignored.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    params(TransactionQueryParams),
+    responses(
+        (status = 200, description = "The transactions, one per line", body=Transaction, content_type = NDJSON),
+        (status = 400, description = "Invalid query", body=ErrorResponse),
+        (status = 404, description = "Category or tag not found", body=ErrorResponse)
+    )
+)]
+#[get("/export")]
+async fn export_transactions(
+    state: web::Data<AppState>,
+    params: web::Query<TransactionQueryParams>,
+) -> impl Responder {
+    let query = match build_query(&state, params.into_inner()) {
+        Ok(query) => query,
+        Err(response) => return response,
+    };
+
+    let transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.stream(query) {
+        Ok(transactions) => ndjson_response(transactions),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get transaction by id.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 200, description = "The transaction", body=Transaction),
+        (status = 400, description = "Malformed transaction id", body=ErrorResponse),
+        (status = 404, description = "Transaction not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}")]
+async fn get_by_id(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let transaction_id: TransactionId = match parse_id(&id) {
+        Ok(transaction_id) => transaction_id,
+        Err(response) => return response,
+    };
+
+    let transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.find_by_id_or_fail(transaction_id) {
+        Ok(transaction) => HttpResponse::Ok().json(transaction),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Create transaction.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 200, description = "The id of the created transaction"),
+        (status = 400, description = "Invalid transaction", body=ErrorResponse),
+        (status = 404, description = "Account, category or payee not found", body=ErrorResponse)
+    )
+)]
+#[post("")]
+async fn create_transaction(
+    state: web::Data<AppState>,
+    user_request: web::Json<CreateTransactionRequest>,
+) -> impl Responder {
+    let user_request = user_request.into_inner();
+    let tags = match state
+        .tag_service
+        .lock()
+        .unwrap()
+        .find_or_create(&user_request.tags)
+    {
+        Ok(tags) => tags,
+        Err(error) => return HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    };
+
+    let mut transaction = Transaction::new(
+        None,
+        AccountRef::Id(user_request.account_id),
+        user_request.transaction_type,
+        user_request.amount,
+        user_request.fee.unwrap_or(MIN_AMOUNT),
+        None,
+        None,
+        user_request.currency,
+        user_request.status,
+        user_request.date,
+        user_request.description,
+        user_request.reference_number,
+        user_request.message,
+    );
+    transaction.set_category_id(user_request.category_id);
+    transaction.set_splits(user_request.splits);
+    transaction.set_tags(tags);
+    transaction.set_payee_id(user_request.payee_id);
+
+    let mut transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.create(transaction) {
+        Ok(transaction_id) => HttpResponse::Ok().json(transaction_id),
+        Err(
+            error @ (CreateError::InvalidAccountRef { .. }
+            | CreateError::InvalidCategoryRef { .. }
+            | CreateError::InvalidPayeeRef { .. }),
+        ) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+fn edit_error_response(error: EditError) -> HttpResponse {
+    match error {
+        EditError::NotFound(_)
+        | EditError::Invalid(
+            CreateError::InvalidAccountRef { .. } | CreateError::InvalidCategoryRef { .. },
+        ) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+        EditError::Reversed => HttpResponse::Conflict().json(ErrorResponse::new(error)),
+        _ => HttpResponse::BadRequest().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Update transaction.
+///
+/// The balance of the account is corrected by the difference between both versions of the transaction,
+/// and the balances of the later transactions are recomputed. The previous version is kept in its revisions.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 200, description = "The updated transaction", body=Transaction),
+        (status = 400, description = "Malformed transaction id or invalid change", body=ErrorResponse),
+        (status = 404, description = "Transaction or account not found", body=ErrorResponse),
+        (status = 409, description = "The transaction was reversed, or is a reversal", body=ErrorResponse)
+    )
+)]
+#[put("/{id}")]
+async fn update_transaction(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<UpdateTransactionRequest>,
+) -> impl Responder {
+    let transaction_id: TransactionId = match parse_id(&id) {
+        Ok(transaction_id) => transaction_id,
+        Err(response) => return response,
+    };
+
+    let mut transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.update(transaction_id, user_request.into_inner().into()) {
+        Ok(transaction) => HttpResponse::Ok().json(transaction),
+        Err(error) => edit_error_response(error),
+    }
+}
+
+/// Delete transaction.
+///
+/// The effect of the transaction is taken off the balance of its account, and the balances of the later
+/// transactions are recomputed. The deleted version is kept in its revisions.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 204, description = "The transaction was deleted"),
+        (status = 400, description = "Malformed transaction id or the account can not give the money back", body=ErrorResponse),
+        (status = 404, description = "Transaction not found", body=ErrorResponse),
+        (status = 409, description = "The transaction was reversed, or is a reversal", body=ErrorResponse)
+    )
+)]
+#[delete("/{id}")]
+async fn delete_transaction(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let transaction_id: TransactionId = match parse_id(&id) {
+        Ok(transaction_id) => transaction_id,
+        Err(response) => return response,
+    };
+
+    let mut transaction_service = state.transaction_service.lock().unwrap();
+    match transaction_service.delete(transaction_id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => edit_error_response(error),
+    }
+}
+
+/// Reverse transaction.
+///
+/// The transaction is marked `RolledBack`, and a compensating transaction of the opposite type, dated now,
+/// gives its amount back to the account. The compensating transaction references the reversed one.
+/// The body is optional: the fee of the reversal, if any, is taken from the balance on top of the compensation.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 200, description = "The compensating transaction", body=Transaction),
+        (status = 400, description = "Malformed transaction id, or the compensating transaction is invalid", body=ErrorResponse),
+        (status = 404, description = "Transaction not found", body=ErrorResponse),
+        (status = 409, description = "The transaction was reversed already, failed, or is a reversal", body=ErrorResponse)
+    )
+)]
+#[post("/{id}/reverse")]
+async fn reverse_transaction(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: Option<web::Json<ReverseTransactionRequest>>,
+) -> impl Responder {
+    let transaction_id: TransactionId = match parse_id(&id) {
+        Ok(transaction_id) => transaction_id,
+        Err(response) => return response,
+    };
+
+    let mut transaction_service = state.transaction_service.lock().unwrap();
+    let fee = user_request
+        .and_then(|user_request| user_request.into_inner().fee)
+        .unwrap_or(MIN_AMOUNT);
+    match transaction_service.reverse(transaction_id, fee) {
+        Ok(reversal) => HttpResponse::Ok().json(reversal),
+        Err(error @ ReverseError::NotFound(_)) => {
+            HttpResponse::NotFound().json(ErrorResponse::new(error))
+        }
+        Err(error @ ReverseError::Invalid(_)) => {
+            HttpResponse::BadRequest().json(ErrorResponse::new(error))
+        }
+        Err(error) => HttpResponse::Conflict().json(ErrorResponse::new(error)),
+    }
+}
+
+/// Get the revisions of a transaction.
+///
+/// The previous versions of the transaction, the oldest first, kept when it was edited or deleted.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 200, description = "The revisions of the transaction", body=[TransactionRevision]),
+        (status = 400, description = "Malformed transaction id", body=ErrorResponse),
+        (status = 404, description = "Transaction not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/revisions")]
+async fn get_revisions(state: web::Data<AppState>, id: web::Path<String>) -> impl Responder {
+    let transaction_id: TransactionId = match parse_id(&id) {
+        Ok(transaction_id) => transaction_id,
+        Err(response) => return response,
+    };
+
+    let transaction_service = state.transaction_service.lock().unwrap();
+    let revisions = transaction_service.find_revisions(&transaction_id);
+    // A deleted transaction is still known by its revisions.
+    if revisions.is_empty() {
+        if let Err(error) = transaction_service.find_by_id_or_fail(transaction_id) {
+            return HttpResponse::NotFound().json(ErrorResponse::new(error));
+        }
+    }
+
+    HttpResponse::Ok().json(revisions)
+}
+
+/// Replace the tags of a transaction.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 200, description = "The tag ids of the transaction", body=[TagId]),
+        (status = 400, description = "Malformed transaction id or invalid tag name", body=ErrorResponse),
+        (status = 404, description = "Transaction not found", body=ErrorResponse)
+    )
+)]
+#[put("/{id}/tags")]
+async fn set_transaction_tags(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+    user_request: web::Json<SetTagsRequest>,
+) -> impl Responder {
+    let transaction_id: TransactionId = match parse_id(&id) {
+        Ok(transaction_id) => transaction_id,
+        Err(response) => return response,
+    };
+
+    let tag_service = state.tag_service.lock().unwrap();
+    set_tags_response(tag_service.set_transaction_tags(&transaction_id, &user_request.tags))
+}
+
+/// Suggest categories for a transaction.
+///
+/// The categories are learned from the transactions already categorized,
+/// from their description, message, payee and amount.
+#[utoipa::path(
+    tag = TRANSACTIONS,
+    responses(
+        (status = 200, description = "The likely categories, the most likely first", body=[CategorySuggestion]),
+        (status = 400, description = "Malformed transaction id", body=ErrorResponse),
+        (status = 404, description = "Transaction not found", body=ErrorResponse)
+    )
+)]
+#[get("/{id}/suggestions")]
+async fn get_category_suggestions(
+    state: web::Data<AppState>,
+    id: web::Path<String>,
+) -> impl Responder {
+    let transaction_id: TransactionId = match parse_id(&id) {
+        Ok(transaction_id) => transaction_id,
+        Err(response) => return response,
+    };
+
+    let mut suggestion_service = state.suggestion_service.lock().unwrap();
+    match suggestion_service.suggest_by_id(transaction_id) {
+        Ok(suggestions) => HttpResponse::Ok().json(suggestions),
+        Err(error) => HttpResponse::NotFound().json(ErrorResponse::new(error)),
+    }
+}
+
+pub(super) fn configure(config: &mut ServiceConfig) {
+    config
+        .service(get_all_transactions)
+        // Registered before `/{id}`, which would match the path otherwise.
+        .service(export_transactions)
+        .service(get_by_id)
+        .service(create_transaction)
+        .service(update_transaction)
+        .service(delete_transaction)
+        .service(reverse_transaction)
+        .service(get_revisions)
+        .service(set_transaction_tags)
+        .service(get_category_suggestions);
+}
@@ -0,0 +1,89 @@
+use crate::app::entities::account::AccountId;
+use crate::app::services::account_merge_service::{AccountMerge, ConfigMerge};
+use crate::interfaces::api::state::AppState;
+
+fn format_config(config: ConfigMerge) -> &'static str {
+    match config {
+        ConfigMerge::NotConfigured => "none",
+        ConfigMerge::Moved => "moved",
+        ConfigMerge::Dropped => "dropped, the target keeps its own",
+    }
+}
+
+/// What a merge changed, one field per line.
+pub(super) fn format_merge(merge: &AccountMerge) -> String {
+    let target = &merge.target;
+    let identifiers: Vec<String> = merge
+        .identifiers
+        .iter()
+        .map(|identifier| identifier.to_string())
+        .collect();
+
+    [
+        format!(
+            "{} ({}) merged into {} ({})",
+            merge.source.name(),
+            merge.source.id().unwrap(),
+            target.name(),
+            target.id().unwrap()
+        ),
+        format!(
+            "  Balance:                {:.2} {}",
+            target.balance().value(),
+            target.currency()
+        ),
+        format!("  Transactions:           {}", merge.transaction_ids.len()),
+        format!(
+            "  Recurring transactions: {}",
+            merge.recurring_transaction_ids.len()
+        ),
+        format!("  Goals:                  {}", merge.goal_ids.len()),
+        format!("  Loans:                  {}", merge.loan_ids.len()),
+        format!("  Rules:                  {}", merge.rule_ids.len()),
+        format!(
+            "  Identifiers:            {}",
+            if identifiers.is_empty() {
+                String::from("none")
+            } else {
+                identifiers.join(", ")
+            }
+        ),
+        format!(
+            "  Interest:               {}",
+            format_config(merge.interest_config)
+        ),
+        format!(
+            "  Statements:             {}",
+            format_config(merge.statement_config)
+        ),
+    ]
+    .join("\n")
+}
+
+/// Merges the account whose id is given first into the second one, and prints what changed.
+pub(super) fn merge(state: &AppState, source: &str, target: &str) -> i32 {
+    let ids = source
+        .parse::<AccountId>()
+        .and_then(|source| target.parse::<AccountId>().map(|target| (source, target)));
+    let merge = ids
+        .map_err(|error| error.to_string())
+        .and_then(|(source, target)| {
+            state
+                .account_merge_service
+                .lock()
+                .unwrap()
+                .merge(&source, &target)
+                .map_err(|error| error.to_string())
+        });
+
+    match merge {
+        Ok(merge) => {
+            println!("{}", format_merge(&merge));
+            0
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            1
+        }
+    }
+}
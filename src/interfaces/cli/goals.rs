@@ -0,0 +1,91 @@
+use crate::app::entities::goal::GoalId;
+use crate::app::services::goal_service::{GoalState, GoalStatus};
+use crate::interfaces::api::state::AppState;
+use chrono::Utc;
+
+fn format_state(state: &GoalState) -> &'static str {
+    match state {
+        GoalState::InProgress => "in progress",
+        GoalState::Achieved => "achieved",
+        GoalState::Overdue => "overdue",
+    }
+}
+
+/// One line summing up the progress of a goal.
+pub(super) fn format_summary(status: &GoalStatus) -> String {
+    format!(
+        "{}: {:.2} / {:.2} {} ({:.0}%), {}",
+        status.name,
+        status.saved,
+        status.target_amount,
+        status.currency,
+        status.progress * 100.0,
+        format_state(&status.state),
+    )
+}
+
+/// The progress of a goal in detail, one field per line.
+pub(super) fn format_details(status: &GoalStatus) -> String {
+    let currency = &status.currency;
+    let contribution = match status.state {
+        GoalState::InProgress => format!(
+            "{:.2} {currency} a month, {} contribution(s) left",
+            status.monthly_contribution, status.contributions_left
+        ),
+        GoalState::Overdue => format!("{:.2} {currency} now", status.monthly_contribution),
+        GoalState::Achieved => String::from("none"),
+    };
+
+    [
+        format!("{} ({})", status.name, status.goal_id),
+        format!(
+            "  Target:       {:.2} {currency} by {}",
+            status.target_amount, status.target_date
+        ),
+        format!(
+            "  Saved:        {:.2} {currency} ({:.0}%)",
+            status.saved,
+            status.progress * 100.0
+        ),
+        format!("  Remaining:    {:.2} {currency}", status.remaining),
+        format!("  Contribution: {contribution}"),
+        format!("  Status:       {}", format_state(&status.state)),
+    ]
+    .join("\n")
+}
+
+/// Prints the progress of every goal, or the details of the goal whose id is given.
+pub(super) fn run(state: &AppState, id: Option<&String>) -> i32 {
+    let goal_service = state.goal_service.lock().unwrap();
+    let today = Utc::now().date_naive();
+
+    let Some(id) = id else {
+        let statuses = goal_service.statuses(today);
+        if statuses.is_empty() {
+            println!("No savings goal yet.");
+        }
+        for status in statuses {
+            println!("{}", format_summary(&status));
+        }
+        return 0;
+    };
+
+    let status = id
+        .parse::<GoalId>()
+        .map_err(|error| error.to_string())
+        .and_then(|goal_id| {
+            goal_service
+                .status(&goal_id, today)
+                .map_err(|error| error.to_string())
+        });
+    match status {
+        Ok(status) => {
+            println!("{}", format_details(&status));
+            0
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            1
+        }
+    }
+}
@@ -1 +1,30 @@
+mod accounts;
+mod goals;
+mod test_accounts;
+mod test_goals;
 
+use crate::interfaces::api::state::AppState;
+
+const USAGE: &str = "Usage:
+  me-finance                 Start the API server
+  me-finance accounts merge <source id> <target id>
+                             Merge an account into another one
+  me-finance goals           Show the progress of every savings goal
+  me-finance goals <id>      Show the progress of a savings goal";
+
+/// Runs a command given on the command line, on the same data as the server.
+///
+/// # Returns
+/// * `i32` — The exit code of the process: 0 on success, 1 when the command failed, 2 on a usage error.
+pub(crate) fn run(args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        Some("accounts") if args.len() == 4 && args[1] == "merge" => {
+            accounts::merge(&AppState::new(), &args[2], &args[3])
+        }
+        Some("goals") if args.len() <= 2 => goals::run(&AppState::new(), args.get(1)),
+        _ => {
+            eprintln!("{USAGE}");
+            2
+        }
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod test_accounts_command {
+    use crate::app::entities::account::{AccountId, AccountStatus, StatusChange};
+    use crate::app::entities::transaction::TransactionId;
+    use crate::app::services::account_merge_service::{AccountMerge, ConfigMerge};
+    use crate::app::typing::account_identifier::{AccountIdentifier, IdentifierKind};
+    use crate::interfaces::cli::accounts::format_merge;
+    use crate::shared::test_utilities::get_random_account;
+    use chrono::Utc;
+
+    #[test]
+    fn test_format_merge() {
+        // GIVEN the report of a merge which moved two transactions and a phone number
+        let mut source = get_random_account();
+        source.set_id(Some(AccountId::generate()));
+        source.change_status(StatusChange {
+            status: AccountStatus::Closed,
+            reason: String::from("Merged"),
+            changed_at: Utc::now(),
+        });
+        let mut target = get_random_account();
+        target.set_id(Some(AccountId::generate()));
+        target.set_balance(120f32.try_into().unwrap());
+        let merge = AccountMerge {
+            source: source.clone(),
+            target: target.clone(),
+            transaction_ids: vec![TransactionId::generate(), TransactionId::generate()],
+            recurring_transaction_ids: vec![],
+            goal_ids: vec![],
+            loan_ids: vec![],
+            rule_ids: vec![],
+            identifiers: vec![AccountIdentifier::new(IdentifierKind::Msisdn, "0788123456").unwrap()],
+            interest_config: ConfigMerge::NotConfigured,
+            statement_config: ConfigMerge::Moved,
+        };
+
+        // WHEN it is formatted
+        // THEN every change is listed, one per line
+        assert_eq!(
+            format_merge(&merge),
+            format!(
+                "{} ({}) merged into {} ({})
+  Balance:                120.00 RWF
+  Transactions:           2
+  Recurring transactions: 0
+  Goals:                  0
+  Loans:                  0
+  Rules:                  0
+  Identifiers:            +250788123456
+  Interest:               none
+  Statements:             moved",
+                source.name(),
+                source.id().unwrap(),
+                target.name(),
+                target.id().unwrap()
+            )
+        );
+    }
+}
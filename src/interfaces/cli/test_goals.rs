@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod test_goals_command {
+    use crate::app::entities::goal::GoalId;
+    use crate::app::services::goal_service::{GoalState, GoalStatus};
+    use crate::app::typing::currency::Currency;
+    use crate::interfaces::cli::goals::{format_details, format_summary};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_format() {
+        // GIVEN the status of a goal in progress
+        let goal_id = GoalId::generate();
+        let status = GoalStatus {
+            goal_id: goal_id.clone(),
+            name: String::from("Emergency fund"),
+            currency: Currency::RWF,
+            target_amount: 500000.0,
+            target_date: NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+            saved: 140000.0,
+            remaining: 360000.0,
+            progress: 0.28,
+            contributions_left: 3,
+            monthly_contribution: 120000.0,
+            state: GoalState::InProgress,
+        };
+
+        // WHEN it is formatted
+        // THEN the summary fits on a line
+        assert_eq!(
+            format_summary(&status),
+            "Emergency fund: 140000.00 / 500000.00 RWF (28%), in progress"
+        );
+
+        // AND the details give the contribution required
+        assert_eq!(
+            format_details(&status),
+            format!(
+                "Emergency fund ({goal_id})
+  Target:       500000.00 RWF by 2026-12-31
+  Saved:        140000.00 RWF (28%)
+  Remaining:    360000.00 RWF
+  Contribution: 120000.00 RWF a month, 3 contribution(s) left
+  Status:       in progress"
+            )
+        );
+    }
+}
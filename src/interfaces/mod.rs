@@ -1,2 +1,2 @@
 pub(crate) mod api;
-mod cli;
+pub(crate) mod cli;
@@ -1,4 +1,6 @@
 use dotenvy::dotenv;
+use std::env;
+use std::process;
 
 mod app;
 mod infrastructure;
@@ -6,11 +8,18 @@ mod interfaces;
 mod shared;
 
 use interfaces::api::server::start_server;
+use interfaces::cli;
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
 
+    // A command given on the command line is run instead of the server.
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        process::exit(cli::run(&args));
+    }
+
     // The address to listen on for HTTP requests.
     let address = "127.0.0.1:8000";
 
@@ -1,11 +1,16 @@
-use crate::app::entities::account::{Account, AccountType};
+use crate::app::entities::account::{Account, AccountId, AccountType};
+use crate::app::entities::recurring_transaction::{
+    BusinessDayConvention, Frequency, RecurringTransaction, Schedule, TransactionTemplate,
+};
 use crate::app::entities::transaction::{
     AccountRef, Transaction, TransactionStatus, TransactionType,
 };
 use crate::app::typing::amount::Amount;
 use crate::app::typing::currency::Currency;
+use crate::infrastructure::repositories::sqlite::common::migrate;
 use chrono::{DateTime, Utc};
 use rand::{distr::Alphanumeric, Rng};
+use sqlite::Connection;
 
 #[allow(dead_code)]
 pub fn get_random_string(len: usize) -> String {
@@ -23,7 +28,7 @@ fn get_random_amount() -> Amount {
 
 #[allow(dead_code)]
 pub fn get_random_transaction() -> Transaction {
-    let given_account_ref = AccountRef::Id(get_random_string(10).into());
+    let given_account_ref = AccountRef::Id(AccountId::generate());
     let given_transaction_type = TransactionType::Expense;
     let given_amount = get_random_amount();
     let given_fee = get_random_amount();
@@ -42,13 +47,13 @@ pub fn get_random_transaction() -> Transaction {
         None,
         given_account_ref.clone(),
         given_transaction_type.clone(),
-        given_amount.clone().into(),
-        given_fee.clone().into(),
-        Some(given_opening_balance.clone().into()),
-        Some(given_closing_balance.clone().into()),
+        given_amount.clone(),
+        given_fee.clone(),
+        Some(given_opening_balance.clone()),
+        Some(given_closing_balance.clone()),
         given_currency.clone(),
         given_status.clone(),
-        given_date.clone(),
+        given_date,
         Some(given_description.clone()),
         Some(given_reference_number.clone()),
         Some(given_transaction_message.clone()),
@@ -61,12 +66,43 @@ pub fn get_random_account() -> Account {
         None,
         get_random_string(10),
         get_random_string(200),
-        get_random_string(30),
+        None,
         AccountType::Savings,
         Some(Currency::RWF),
     )
 }
 
+/// A monthly rent paid from a random account, without category nor payee.
+#[allow(dead_code)]
+pub fn get_random_recurring_transaction() -> RecurringTransaction {
+    RecurringTransaction::new(
+        None,
+        String::from("Rent"),
+        TransactionTemplate {
+            account_id: AccountId::generate(),
+            transaction_type: TransactionType::Expense,
+            amount: get_random_amount(),
+            fee: get_random_amount(),
+            currency: Currency::RWF,
+            description: Some(get_random_string(100)),
+            message: None,
+            category_id: None,
+            payee_id: None,
+            tags: vec![],
+        },
+        Schedule {
+            frequency: Frequency::Monthly,
+            interval: 1,
+            start: DateTime::parse_from_rfc3339("2026-01-31T08:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            end: None,
+            count: None,
+            business_day: BusinessDayConvention::Unadjusted,
+        },
+    )
+}
+
 #[allow(dead_code)]
 pub fn assert_accounts_equal(left: &Account, right: &Account, include_id: bool) {
     if include_id {
@@ -75,7 +111,7 @@ pub fn assert_accounts_equal(left: &Account, right: &Account, include_id: bool)
 
     assert_eq!(left.name(), right.name());
     assert_eq!(left.description(), right.description());
-    assert_eq!(left.platform(), right.platform());
+    assert_eq!(left.institution_id(), right.institution_id());
     assert_eq!(left.balance(), right.balance());
     assert_eq!(left.account_type(), right.account_type());
     assert_eq!(left.currency(), right.currency());
@@ -99,3 +135,11 @@ pub fn assert_transactions_equal(left: &Transaction, right: &Transaction, includ
     assert_eq!(left.description(), right.description());
     assert_eq!(left.reference_number(), right.reference_number());
 }
+
+/// Opens a fresh in memory sqlite database with the schema applied.
+#[allow(dead_code)]
+pub fn get_sqlite_connection() -> Connection {
+    let connection = sqlite::open(":memory:").unwrap();
+    migrate(&connection);
+    connection
+}